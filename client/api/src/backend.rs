@@ -18,7 +18,7 @@
 
 use scale_codec::{Decode, Encode};
 // Substrate
-use sp_core::{H160, H256};
+use sp_core::{H160, H256, U256};
 use sp_runtime::traits::Block as BlockT;
 // Frontier
 use fp_storage::EthereumStorageSchema;
@@ -30,6 +30,40 @@ pub struct TransactionMetadata<Block: BlockT> {
 	pub ethereum_index: u32,
 }
 
+/// The receipt data of an indexed transaction.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransactionReceiptMetadata {
+	pub status: u8,
+	pub cumulative_gas_used: U256,
+	pub gas_used: U256,
+	pub effective_gas_price: U256,
+	pub contract_address: Option<H160>,
+}
+
+/// An indexed transaction sent from or to an address.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AddressTransaction<Block: BlockT> {
+	pub ethereum_transaction_hash: H256,
+	pub substrate_block_hash: Block::Hash,
+	pub ethereum_block_hash: H256,
+	pub block_number: u32,
+	pub ethereum_index: u32,
+	pub from: H160,
+	pub to: Option<H160>,
+	pub value: U256,
+}
+
+/// An indexed transaction deploying a contract.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContractCreation<Block: BlockT> {
+	pub ethereum_transaction_hash: H256,
+	pub substrate_block_hash: Block::Hash,
+	pub ethereum_block_hash: H256,
+	pub block_number: u32,
+	pub ethereum_index: u32,
+	pub creator: H160,
+}
+
 /// The frontier backend interface.
 #[async_trait::async_trait]
 pub trait Backend<Block: BlockT>: Send + Sync {
@@ -45,6 +79,42 @@ pub trait Backend<Block: BlockT>: Send + Sync {
 		ethereum_transaction_hash: &H256,
 	) -> Result<Vec<TransactionMetadata<Block>>, String>;
 
+	/// Get the indexed receipt data of the transaction included in the given substrate block.
+	///
+	/// Backends not storing receipt data return `None`. The receipt RPC only takes the effective
+	/// gas price from it: the receipts themselves are still built from the runtime storage.
+	async fn transaction_receipt_metadata(
+		&self,
+		_ethereum_transaction_hash: &H256,
+		_substrate_block_hash: &Block::Hash,
+	) -> Result<Option<TransactionReceiptMetadata>, String> {
+		Ok(None)
+	}
+
+	/// Get the canonical transactions sent from or to the given address, most recent first,
+	/// skipping the first `offset` ones and returning at most `limit` of them.
+	///
+	/// Backends not indexing the transaction parties return an error.
+	async fn transactions_by_address(
+		&self,
+		_address: &H160,
+		_offset: u32,
+		_limit: u32,
+	) -> Result<Vec<AddressTransaction<Block>>, String> {
+		Err("transactions are not indexed by address in this backend".to_string())
+	}
+
+	/// Get the canonical transaction which deployed the contract at the given address.
+	///
+	/// Only contracts deployed by a transaction are indexed, not the ones created by
+	/// another contract. Backends not indexing the created contracts return an error.
+	async fn contract_creation(
+		&self,
+		_address: &H160,
+	) -> Result<Option<ContractCreation<Block>>, String> {
+		Err("contract creations are not indexed in this backend".to_string())
+	}
+
 	/// Returns reference to log indexer backend.
 	fn log_indexer(&self) -> &dyn LogIndexerBackend<Block>;
 
@@ -55,6 +125,43 @@ pub trait Backend<Block: BlockT>: Send + Sync {
 
 	/// Get the hash of the latest substrate block fully indexed by the backend.
 	async fn latest_block_hash(&self) -> Result<Block::Hash, String>;
+
+	/// Get the first block indexed by the backend, the history below it is not served.
+	///
+	/// Backends indexing from genesis return `0`.
+	async fn history_horizon(&self) -> Result<u64, String> {
+		Ok(0)
+	}
+}
+
+/// The fee history data of a block, as served by `eth_feeHistory`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FeeHistoryEntry {
+	pub base_fee: u64,
+	pub gas_used_ratio: f64,
+	/// The effective priority fees at the evenly spaced percentiles of the block gas.
+	pub rewards: Vec<u64>,
+}
+
+/// The fee history backend interface, persisting the fee history cache across restarts.
+#[async_trait::async_trait]
+pub trait FeeHistoryBackend: Send + Sync {
+	/// Store the fee history of the block at `block_number`, replacing any previous one.
+	async fn write_fee_history(
+		&self,
+		block_number: u64,
+		entry: &FeeHistoryEntry,
+	) -> Result<(), String>;
+
+	/// Delete the fee history of the given blocks.
+	async fn remove_fee_history(&self, block_numbers: &[u64]) -> Result<(), String>;
+
+	/// Get the stored fee history of the blocks in `[from_block, to_block]`.
+	async fn fee_history(
+		&self,
+		from_block: u64,
+		to_block: u64,
+	) -> Result<Vec<(u64, FeeHistoryEntry)>, String>;
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -67,6 +174,21 @@ pub struct FilteredLog<Block: BlockT> {
 	pub log_index: u32,
 }
 
+/// The error of a log indexer query.
+#[derive(Debug, Eq, PartialEq)]
+pub enum LogIndexerError {
+	/// The query matched more logs than the backend is configured to return.
+	TooManyResults { limit: u32 },
+	/// Any other backend failure.
+	Other(String),
+}
+
+impl From<String> for LogIndexerError {
+	fn from(err: String) -> Self {
+		Self::Other(err)
+	}
+}
+
 /// The log indexer backend interface.
 #[async_trait::async_trait]
 pub trait LogIndexerBackend<Block: BlockT>: Send + Sync {
@@ -80,5 +202,16 @@ pub trait LogIndexerBackend<Block: BlockT>: Send + Sync {
 		to_block: u64,
 		addresses: Vec<H160>,
 		topics: Vec<Vec<Option<H256>>>,
-	) -> Result<Vec<FilteredLog<Block>>, String>;
+	) -> Result<Vec<FilteredLog<Block>>, LogIndexerError>;
+
+	/// Request the blocks in `[from_block, to_block]` to be indexed ahead of the others,
+	/// possibly waiting for them to be indexed.
+	///
+	/// Backends always indexing in order return immediately.
+	async fn prioritize_range(&self, _from_block: u64, _to_block: u64) {}
+
+	/// Get the registered event ABI fragments for the given `topic0` values.
+	async fn event_signatures(&self, _topics: &[H256]) -> Result<Vec<(H256, String)>, String> {
+		Ok(Vec::new())
+	}
 }
@@ -0,0 +1,166 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use sp_core::{hashing::keccak_256, H256};
+
+/// A parameter of an event signature.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EventParam {
+	/// The parameter name, may be empty.
+	pub name: String,
+	/// The canonical ABI type of the parameter.
+	pub kind: String,
+	/// Whether the parameter is stored in the log topics.
+	pub indexed: bool,
+}
+
+/// An event ABI fragment, e.g.
+/// `Transfer(address indexed from, address indexed to, uint256 value)`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EventSignature {
+	/// The event name.
+	pub name: String,
+	/// The event parameters.
+	pub params: Vec<EventParam>,
+}
+
+impl EventSignature {
+	/// Parses an event ABI fragment. A leading `event` keyword is accepted.
+	pub fn parse(fragment: &str) -> Result<Self, String> {
+		let fragment = fragment.trim();
+		let fragment = fragment.strip_prefix("event ").unwrap_or(fragment).trim();
+		let (name, params) = fragment
+			.strip_suffix(')')
+			.and_then(|fragment| fragment.split_once('('))
+			.ok_or_else(|| format!("Invalid event fragment: {fragment}"))?;
+		let name = name.trim();
+		if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+			return Err(format!("Invalid event name: {name}"));
+		}
+
+		let params = split_params(params)
+			.into_iter()
+			.filter(|param| !param.is_empty())
+			.map(|param| {
+				let mut tokens = param.split_whitespace();
+				let kind = tokens
+					.next()
+					.map(canonical_type)
+					.ok_or_else(|| format!("Invalid event parameter: {param}"))?;
+				let mut indexed = false;
+				let mut name = String::new();
+				for token in tokens {
+					match token {
+						"indexed" if !indexed && name.is_empty() => indexed = true,
+						token if name.is_empty() => name = token.to_string(),
+						_ => return Err(format!("Invalid event parameter: {param}")),
+					}
+				}
+				Ok(EventParam {
+					name,
+					kind,
+					indexed,
+				})
+			})
+			.collect::<Result<Vec<_>, String>>()?;
+
+		Ok(Self {
+			name: name.to_string(),
+			params,
+		})
+	}
+
+	/// Returns the canonical signature, e.g. `Transfer(address,address,uint256)`.
+	pub fn canonical(&self) -> String {
+		let kinds = self
+			.params
+			.iter()
+			.map(|param| param.kind.as_str())
+			.collect::<Vec<_>>();
+		format!("{}({})", self.name, kinds.join(","))
+	}
+
+	/// Returns the `topic0` of the logs emitted by this event.
+	pub fn topic(&self) -> H256 {
+		H256::from(keccak_256(self.canonical().as_bytes()))
+	}
+}
+
+/// Splits the parameter list on the commas which are not nested in a tuple.
+fn split_params(params: &str) -> Vec<&str> {
+	let mut out = Vec::new();
+	let (mut depth, mut start) = (0usize, 0usize);
+	for (i, c) in params.char_indices() {
+		match c {
+			'(' => depth += 1,
+			')' => depth = depth.saturating_sub(1),
+			',' if depth == 0 => {
+				out.push(params[start..i].trim());
+				start = i + 1;
+			}
+			_ => {}
+		}
+	}
+	out.push(params[start..].trim());
+	out
+}
+
+/// Expands the `uint` and `int` aliases.
+fn canonical_type(kind: &str) -> String {
+	let (base, suffix) = kind.split_at(kind.find('[').unwrap_or(kind.len()));
+	match base {
+		"uint" => format!("uint256{suffix}"),
+		"int" => format!("int256{suffix}"),
+		_ => kind.to_string(),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parse_event_fragment_works() {
+		let signature = EventSignature::parse(
+			"event Transfer(address indexed from, address indexed to, uint value)",
+		)
+		.expect("valid fragment");
+		assert_eq!(signature.canonical(), "Transfer(address,address,uint256)");
+		assert_eq!(
+			signature.params[0],
+			EventParam {
+				name: "from".to_string(),
+				kind: "address".to_string(),
+				indexed: true,
+			}
+		);
+		assert!(!signature.params[2].indexed);
+		// keccak256("Transfer(address,address,uint256)")
+		assert_eq!(
+			format!("{:?}", signature.topic()),
+			"0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+		);
+	}
+
+	#[test]
+	fn parse_invalid_event_fragment_fails() {
+		assert!(EventSignature::parse("Transfer(address").is_err());
+		assert!(EventSignature::parse("(address)").is_err());
+		assert!(EventSignature::parse("Transfer(address indexed from to)").is_err());
+	}
+}
@@ -0,0 +1,244 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+	fs::File,
+	io::{BufWriter, Write},
+	path::PathBuf,
+	sync::Arc,
+};
+
+use clap::ValueEnum;
+use ethereum_types::{H160, H256};
+// Substrate
+use sp_runtime::traits::Block as BlockT;
+// Frontier
+use fc_db::sql::ExportedLog;
+
+/// Number of blocks whose logs are fetched at once.
+const EXPORT_CHUNK_SIZE: u32 = 1_000;
+
+/// Exports the canonical logs indexed by the SQL backend to a file.
+#[derive(Debug, Clone, clap::Args)]
+pub struct ExportLogsCmd {
+	/// First block to export.
+	#[arg(long)]
+	pub from: u32,
+
+	/// Last block to export.
+	#[arg(long)]
+	pub to: u32,
+
+	/// Format of the exported file.
+	///
+	/// Can be one of `csv | parquet`.
+	#[arg(long, value_enum, ignore_case = true, default_value = "csv")]
+	pub format: ExportFormat,
+
+	/// Path of the exported file.
+	#[arg(long)]
+	pub output: PathBuf,
+
+	/// Only export the logs emitted by these addresses.
+	#[arg(long)]
+	pub address: Vec<H160>,
+
+	/// Only export the logs with this first topic.
+	#[arg(long)]
+	pub topic: Option<H256>,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+pub enum ExportFormat {
+	Csv,
+	#[cfg(feature = "parquet")]
+	Parquet,
+}
+
+impl ExportLogsCmd {
+	pub async fn run<B: BlockT>(&self, backend: Arc<fc_db::sql::Backend<B>>) -> sc_cli::Result<()> {
+		if self.from > self.to {
+			return Err("`--from` must not be greater than `--to`".into());
+		}
+		let file = File::create(&self.output)?;
+		let mut writer = match self.format {
+			ExportFormat::Csv => LogWriter::csv(file)?,
+			#[cfg(feature = "parquet")]
+			ExportFormat::Parquet => LogWriter::parquet(file)?,
+		};
+
+		let mut exported = 0;
+		let mut from = self.from;
+		loop {
+			let to = from.saturating_add(EXPORT_CHUNK_SIZE - 1).min(self.to);
+			let logs = backend
+				.export_logs(from, to, &self.address, self.topic)
+				.await
+				.map_err(|e| format!("{e:?}"))?;
+			writer.write(&logs)?;
+			exported += logs.len();
+			if to == self.to {
+				break;
+			}
+			from = to + 1;
+		}
+		writer.finish()?;
+
+		println!(
+			"Exported {exported} logs of blocks #{}..=#{} to {}",
+			self.from,
+			self.to,
+			self.output.display(),
+		);
+		Ok(())
+	}
+}
+
+const COLUMNS: [&str; 11] = [
+	"block_number",
+	"block_hash",
+	"substrate_block_hash",
+	"transaction_hash",
+	"transaction_index",
+	"log_index",
+	"address",
+	"topic_0",
+	"topic_1",
+	"topic_2",
+	"topic_3",
+];
+
+enum LogWriter {
+	Csv(BufWriter<File>),
+	#[cfg(feature = "parquet")]
+	Parquet(parquet::arrow::ArrowWriter<File>),
+}
+
+impl LogWriter {
+	fn csv(file: File) -> sc_cli::Result<Self> {
+		let mut writer = BufWriter::new(file);
+		writeln!(writer, "{}", COLUMNS.join(","))?;
+		Ok(Self::Csv(writer))
+	}
+
+	#[cfg(feature = "parquet")]
+	fn parquet(file: File) -> sc_cli::Result<Self> {
+		let writer = parquet::arrow::ArrowWriter::try_new(file, parquet_schema(), None)
+			.map_err(|e| e.to_string())?;
+		Ok(Self::Parquet(writer))
+	}
+
+	fn write(&mut self, logs: &[ExportedLog]) -> sc_cli::Result<()> {
+		match self {
+			Self::Csv(writer) => {
+				for log in logs {
+					let topic = |i: usize| log.topics.get(i).map(hex).unwrap_or_default();
+					writeln!(
+						writer,
+						"{},{},{},{},{},{},{},{},{},{},{}",
+						log.block_number,
+						hex(&log.ethereum_block_hash),
+						hex(&log.substrate_block_hash),
+						log.transaction_hash.as_ref().map(hex).unwrap_or_default(),
+						log.transaction_index,
+						log.log_index,
+						hex(&log.address),
+						topic(0),
+						topic(1),
+						topic(2),
+						topic(3),
+					)?;
+				}
+			}
+			#[cfg(feature = "parquet")]
+			Self::Parquet(writer) => {
+				if !logs.is_empty() {
+					writer
+						.write(&record_batch(logs)?)
+						.map_err(|e| e.to_string())?;
+				}
+			}
+		}
+		Ok(())
+	}
+
+	fn finish(self) -> sc_cli::Result<()> {
+		match self {
+			Self::Csv(mut writer) => writer.flush()?,
+			#[cfg(feature = "parquet")]
+			Self::Parquet(writer) => {
+				writer.close().map_err(|e| e.to_string())?;
+			}
+		}
+		Ok(())
+	}
+}
+
+fn hex<T: std::fmt::Debug>(value: &T) -> String {
+	format!("{value:?}")
+}
+
+#[cfg(feature = "parquet")]
+fn parquet_schema() -> arrow_schema::SchemaRef {
+	use arrow_schema::{DataType, Field, Schema};
+
+	Arc::new(Schema::new(
+		COLUMNS
+			.iter()
+			.map(|column| match *column {
+				"block_number" | "transaction_index" | "log_index" => {
+					Field::new(*column, DataType::UInt32, false)
+				}
+				"transaction_hash" | "topic_0" | "topic_1" | "topic_2" | "topic_3" => {
+					Field::new(*column, DataType::Utf8, true)
+				}
+				_ => Field::new(*column, DataType::Utf8, false),
+			})
+			.collect::<Vec<_>>(),
+	))
+}
+
+#[cfg(feature = "parquet")]
+fn record_batch(logs: &[ExportedLog]) -> sc_cli::Result<arrow_array::RecordBatch> {
+	use arrow_array::{ArrayRef, RecordBatch, StringArray, UInt32Array};
+
+	let numbers = |f: fn(&ExportedLog) -> u32| -> ArrayRef {
+		Arc::new(UInt32Array::from_iter_values(logs.iter().map(f)))
+	};
+	let strings = |f: &dyn Fn(&ExportedLog) -> Option<String>| -> ArrayRef {
+		Arc::new(StringArray::from_iter(logs.iter().map(f)))
+	};
+	let topic = |i: usize| strings(&|log| log.topics.get(i).map(hex));
+	RecordBatch::try_new(
+		parquet_schema(),
+		vec![
+			numbers(|log| log.block_number),
+			strings(&|log| Some(hex(&log.ethereum_block_hash))),
+			strings(&|log| Some(hex(&log.substrate_block_hash))),
+			strings(&|log| log.transaction_hash.as_ref().map(hex)),
+			numbers(|log| log.transaction_index),
+			numbers(|log| log.log_index),
+			strings(&|log| Some(hex(&log.address))),
+			topic(0),
+			topic(1),
+			topic(2),
+			topic(3),
+		],
+	)
+	.map_err(|e| e.to_string().into())
+}
@@ -27,7 +27,7 @@ use sp_runtime::traits::Block as BlockT;
 // Frontier
 use fp_rpc::EthereumRuntimeRPCApi;
 
-use super::{utils::FrontierDbMessage, Column, FrontierDbCmd, Operation};
+use super::{utils::FrontierDbMessage, Column, Operation};
 
 #[derive(Debug, Deserialize)]
 #[serde(untagged)]
@@ -41,7 +41,7 @@ pub enum MappingKey {
 }
 
 pub struct MappingDb<'a, B, C> {
-	cmd: &'a FrontierDbCmd,
+	operation: &'a Operation,
 	client: Arc<C>,
 	backend: Arc<fc_db::kv::Backend<B, C>>,
 }
@@ -53,12 +53,12 @@ where
 	C::Api: EthereumRuntimeRPCApi<B>,
 {
 	pub fn new(
-		cmd: &'a FrontierDbCmd,
+		operation: &'a Operation,
 		client: Arc<C>,
 		backend: Arc<fc_db::kv::Backend<B, C>>,
 	) -> Self {
 		Self {
-			cmd,
+			operation,
 			client,
 			backend,
 		}
@@ -70,7 +70,7 @@ where
 		key: &MappingKey,
 		value: &Option<MappingValue<B::Hash>>,
 	) -> sc_cli::Result<()> {
-		match self.cmd.operation {
+		match self.operation {
 			Operation::Create => match (key, value) {
 				// Insert a mapping commitment using the state at the requested block.
 				(
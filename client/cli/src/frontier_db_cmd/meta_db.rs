@@ -28,7 +28,7 @@ use sp_blockchain::HeaderBackend;
 // Substrate
 use sp_runtime::traits::Block as BlockT;
 
-use super::{utils::FrontierDbMessage, FrontierDbCmd, Operation};
+use super::{utils::FrontierDbMessage, Operation};
 
 #[derive(Debug, Deserialize)]
 #[serde(untagged)]
@@ -59,17 +59,17 @@ impl FromStr for MetaKey {
 }
 
 pub struct MetaDb<'a, B, C> {
-	cmd: &'a FrontierDbCmd,
+	operation: &'a Operation,
 	backend: Arc<fc_db::kv::Backend<B, C>>,
 }
 
 impl<'a, B: BlockT, C: HeaderBackend<B>> MetaDb<'a, B, C> {
-	pub fn new(cmd: &'a FrontierDbCmd, backend: Arc<fc_db::kv::Backend<B, C>>) -> Self {
-		Self { cmd, backend }
+	pub fn new(operation: &'a Operation, backend: Arc<fc_db::kv::Backend<B, C>>) -> Self {
+		Self { operation, backend }
 	}
 
 	pub fn query(&self, key: &MetaKey, value: &Option<MetaValue<B::Hash>>) -> sc_cli::Result<()> {
-		match self.cmd.operation {
+		match self.operation {
 			Operation::Create => match (key, value) {
 				// Insert data to the meta column, static tips key.
 				(MetaKey::Tips, Some(MetaValue::Tips(hashes))) => {
@@ -111,7 +111,7 @@ impl<'a, B: BlockT, C: HeaderBackend<B>> MetaDb<'a, B, C> {
 				// Update the static tips key's value.
 				(MetaKey::Tips, Some(MetaValue::Tips(new_value))) => {
 					let value = self.backend.meta().current_syncing_tips()?;
-					self.confirmation_prompt(&self.cmd.operation, key, &value, new_value)?;
+					self.confirmation_prompt(self.operation, key, &value, new_value)?;
 					self.backend
 						.meta()
 						.write_current_syncing_tips(new_value.clone())?;
@@ -124,7 +124,7 @@ impl<'a, B: BlockT, C: HeaderBackend<B>> MetaDb<'a, B, C> {
 						.map(|(key, value)| (*value, *key))
 						.collect::<Vec<(fp_storage::EthereumStorageSchema, H256)>>();
 					self.confirmation_prompt(
-						&self.cmd.operation,
+						self.operation,
 						key,
 						&value,
 						&Some(new_value.clone()),
@@ -137,13 +137,13 @@ impl<'a, B: BlockT, C: HeaderBackend<B>> MetaDb<'a, B, C> {
 				// Deletes the static tips key's value.
 				MetaKey::Tips => {
 					let value = self.backend.meta().current_syncing_tips()?;
-					self.confirmation_prompt(&self.cmd.operation, key, &value, &vec![])?;
+					self.confirmation_prompt(self.operation, key, &value, &vec![])?;
 					self.backend.meta().write_current_syncing_tips(vec![])?;
 				}
 				// Deletes the static schema cache key's value.
 				MetaKey::Schema => {
 					let value = self.backend.meta().ethereum_schema()?;
-					self.confirmation_prompt(&self.cmd.operation, key, &value, &Some(vec![]))?;
+					self.confirmation_prompt(self.operation, key, &value, &Some(vec![]))?;
 					self.backend.meta().write_ethereum_schema(vec![])?;
 				}
 			},
@@ -0,0 +1,121 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::sync::Arc;
+
+use ethereum_types::H256;
+// Substrate
+use sc_client_api::backend::{Backend as BackendT, StorageProvider};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::{Block as BlockT, UniqueSaturatedInto};
+// Frontier
+use fp_rpc::EthereumRuntimeRPCApi;
+
+/// Number of migrated blocks between two progress reports.
+const PROGRESS_INTERVAL: u32 = 10_000;
+
+/// Migrates the blocks mapped by the key-value db to the SQL backend, re-deriving their
+/// metadata and logs from the runtime storage.
+///
+/// The blocks already indexed by the SQL backend are skipped, so an interrupted migration
+/// resumes where it stopped.
+#[derive(Debug, Clone, clap::Args)]
+pub struct MigrateToSqlCmd {
+	/// First block to migrate, defaults to the block following the last one indexed by the
+	/// SQL backend.
+	#[arg(long)]
+	pub from: Option<u32>,
+
+	/// Last block to migrate, defaults to the best block.
+	#[arg(long)]
+	pub to: Option<u32>,
+}
+
+impl MigrateToSqlCmd {
+	pub async fn run<B, C, BE>(
+		&self,
+		client: Arc<C>,
+		kv_backend: Arc<fc_db::kv::Backend<B, C>>,
+		sql_backend: Arc<fc_db::sql::Backend<B>>,
+	) -> sc_cli::Result<()>
+	where
+		B: BlockT<Hash = H256>,
+		C: ProvideRuntimeApi<B> + HeaderBackend<B> + StorageProvider<B, BE> + 'static,
+		C::Api: EthereumRuntimeRPCApi<B>,
+		BE: BackendT<B> + 'static,
+	{
+		let from = match self.from {
+			Some(from) => from,
+			None => match sql_backend.last_indexed_canon_block().await {
+				Ok(hash) => client
+					.number(hash)?
+					.map(|number| UniqueSaturatedInto::<u32>::unique_saturated_into(number) + 1)
+					.unwrap_or_default(),
+				Err(_) => 0,
+			},
+		};
+		let to = self
+			.to
+			.unwrap_or_else(|| client.info().best_number.unique_saturated_into());
+		println!("Migrating blocks #{from}..=#{to} to the SQL backend");
+
+		let (mut migrated, mut unmapped, mut failed) = (0u32, 0u32, 0u32);
+		for number in from..=to {
+			let Some(hash) = client.hash(number.unique_saturated_into())? else {
+				break;
+			};
+			// The blocks not mapped yet are left to the sync worker.
+			if !kv_backend.mapping().is_synced(&hash)? {
+				unmapped += 1;
+				continue;
+			}
+			if sql_backend.is_block_indexed(hash).await {
+				continue;
+			}
+			if number == 0 {
+				if let Err(err) = sql_backend
+					.insert_genesis_block_metadata(client.clone())
+					.await
+				{
+					return Err(format!("Cannot migrate the genesis block: {err}").into());
+				}
+				migrated += 1;
+				continue;
+			}
+			if let Err(err) = sql_backend
+				.insert_block_metadata(client.clone(), hash)
+				.await
+			{
+				eprintln!("Cannot migrate block #{number} {hash:?}: {err}");
+				failed += 1;
+				continue;
+			}
+			sql_backend.index_block_logs(hash).await;
+			migrated += 1;
+			if number % PROGRESS_INTERVAL == 0 {
+				println!("Migrated up to block #{number}");
+			}
+		}
+
+		println!(
+			"Migrated {migrated} blocks, {unmapped} not mapped by the key-value db, {failed} failed"
+		);
+		Ok(())
+	}
+}
@@ -16,11 +16,20 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
+#[cfg(feature = "sql")]
+mod export_logs;
 mod mapping_db;
 mod meta_db;
+#[cfg(feature = "sql")]
+mod migrate_to_sql;
+mod prune;
+mod reindex;
+mod repair_mapping;
+mod stats;
 #[cfg(test)]
 mod tests;
 pub(crate) mod utils;
+mod verify_mapping;
 
 use std::{path::PathBuf, str::FromStr, sync::Arc};
 
@@ -33,29 +42,46 @@ use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
 use sp_runtime::traits::Block as BlockT;
 
+#[cfg(feature = "sql")]
+pub use self::{
+	export_logs::{ExportFormat, ExportLogsCmd},
+	migrate_to_sql::MigrateToSqlCmd,
+};
 use self::{
 	mapping_db::{MappingDb, MappingKey, MappingValue},
 	meta_db::{MetaDb, MetaKey, MetaValue},
 };
+pub use self::{
+	prune::PruneCmd,
+	reindex::ReindexCmd,
+	repair_mapping::{RepairMappingCmd, RepairMode},
+	stats::StatsCmd,
+	verify_mapping::VerifyMappingCmd,
+};
 
 /// Cli tool to interact with the Frontier backend db
 #[derive(Debug, Clone, clap::Parser)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 pub struct FrontierDbCmd {
+	/// Maintenance command to run on the whole db instead of a single key operation.
+	#[command(subcommand)]
+	pub command: Option<FrontierDbSubcommand>,
+
 	/// Specify the operation to perform.
 	///
 	/// Can be one of `create | read | update | delete`.
 	#[arg(value_enum, ignore_case = true, required = true)]
-	pub operation: Operation,
+	pub operation: Option<Operation>,
 
 	/// Specify the column to query.
 	///
 	/// Can be one of `meta | block | transaction`.
 	#[arg(value_enum, ignore_case = true, required = true)]
-	pub column: Column,
+	pub column: Option<Column>,
 
 	/// Specify the key to either read or write.
 	#[arg(short('k'), long, required = true)]
-	pub key: String,
+	pub key: Option<String>,
 
 	/// Specify the value to write.
 	///
@@ -75,6 +101,27 @@ pub struct FrontierDbCmd {
 	pub pruning_params: PruningParams,
 }
 
+/// Maintenance commands of the Frontier backend db.
+#[derive(Debug, Clone, clap::Subcommand)]
+pub enum FrontierDbSubcommand {
+	/// Migrates the key-value mapping db to the SQL backend.
+	#[cfg(feature = "sql")]
+	MigrateToSql(MigrateToSqlCmd),
+	/// Removes or re-derives the key-value mappings of unknown substrate blocks.
+	RepairMapping(RepairMappingCmd),
+	/// Deletes the mappings and indexed logs of the blocks below a given height.
+	Prune(PruneCmd),
+	/// Prints the size and contents of the database.
+	Stats(StatsCmd),
+	/// Compares the key-value mapping db to the hashes recomputed from the runtime.
+	VerifyMapping(VerifyMappingCmd),
+	/// Deletes the mappings or indexed data of a block range and indexes it again.
+	Reindex(ReindexCmd),
+	/// Exports the logs indexed by the SQL backend to a CSV or Parquet file.
+	#[cfg(feature = "sql")]
+	ExportLogs(ExportLogsCmd),
+}
+
 #[derive(ValueEnum, Debug, Clone)]
 pub enum Operation {
 	Create,
@@ -108,42 +155,42 @@ impl FrontierDbCmd {
 		C: HeaderBackend<B> + ProvideRuntimeApi<B>,
 		C::Api: fp_rpc::EthereumRuntimeRPCApi<B>,
 	{
-		match self.column {
+		let (Some(operation), Some(column), Some(key)) = (&self.operation, &self.column, &self.key)
+		else {
+			return Err("An operation, a column and a key are required".into());
+		};
+		match column {
 			Column::Meta => {
 				// New meta db handler
-				let meta_db = MetaDb::new(self, backend);
+				let meta_db = MetaDb::new(operation, backend);
 				// Maybe get a MetaKey
-				let key = MetaKey::from_str(&self.key)?;
+				let key = MetaKey::from_str(key)?;
 				// Maybe get a MetaValue
-				let value = match utils::maybe_deserialize_value::<B>(
-					&self.operation,
-					self.value.as_ref(),
-				)? {
-					Some(DbValue::Meta(value)) => Some(value),
-					None => None,
-					_ => return Err(format!("Unexpected `{:?}` value", self.value).into()),
-				};
+				let value =
+					match utils::maybe_deserialize_value::<B>(operation, self.value.as_ref())? {
+						Some(DbValue::Meta(value)) => Some(value),
+						None => None,
+						_ => return Err(format!("Unexpected `{:?}` value", self.value).into()),
+					};
 				// Run the query
 				meta_db.query(&key, &value)?
 			}
 			Column::Block | Column::Transaction => {
 				// New mapping db handler
-				let mapping_db = MappingDb::new(self, client, backend);
+				let mapping_db = MappingDb::new(operation, client, backend);
 				// Maybe get a MappingKey
 				let key = MappingKey::EthBlockOrTransactionHash(
-					H256::from_str(&self.key).expect("H256 provided key"),
+					H256::from_str(key).expect("H256 provided key"),
 				);
 				// Maybe get a MappingValue
-				let value = match utils::maybe_deserialize_value::<B>(
-					&self.operation,
-					self.value.as_ref(),
-				)? {
-					Some(DbValue::Mapping(value)) => Some(value),
-					None => None,
-					_ => return Err(format!("Unexpected `{:?}` value", self.value).into()),
-				};
+				let value =
+					match utils::maybe_deserialize_value::<B>(operation, self.value.as_ref())? {
+						Some(DbValue::Mapping(value)) => Some(value),
+						None => None,
+						_ => return Err(format!("Unexpected `{:?}` value", self.value).into()),
+					};
 				// Run the query
-				mapping_db.query(&self.column, &key, &value)?
+				mapping_db.query(column, &key, &value)?
 			}
 		}
 		Ok(())
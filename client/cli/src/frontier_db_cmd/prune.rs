@@ -0,0 +1,95 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::sync::Arc;
+
+// Substrate
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+/// Number of blocks pruned per SQL transaction.
+#[cfg(feature = "sql")]
+const PRUNE_BATCH_SIZE: u32 = 1_000;
+
+/// Deletes the mappings and indexed logs of the blocks below a given height.
+#[derive(Debug, Clone, clap::Args)]
+pub struct PruneCmd {
+	/// Blocks with a lower number are pruned.
+	#[arg(long)]
+	pub below: u32,
+
+	/// Only report what would be removed.
+	#[arg(long)]
+	pub dry_run: bool,
+}
+
+impl PruneCmd {
+	/// Prunes the block and transaction mappings of the key-value backend.
+	pub fn run_kv<B, C>(&self, backend: Arc<fc_db::kv::Backend<B, C>>) -> sc_cli::Result<()>
+	where
+		B: BlockT,
+		C: HeaderBackend<B>,
+	{
+		let report = backend.prune_blocks_before(self.below, self.dry_run)?;
+		println!(
+			"{} ethereum block hashes and {} transaction hashes mapped below #{}{}",
+			report.blocks,
+			report.transactions,
+			self.below,
+			self.removed(),
+		);
+		Ok(())
+	}
+
+	/// Prunes the indexed transactions and logs of the SQL backend.
+	#[cfg(feature = "sql")]
+	pub async fn run_sql<B: BlockT>(
+		&self,
+		backend: Arc<fc_db::sql::Backend<B>>,
+	) -> sc_cli::Result<()> {
+		let report = backend
+			.prunable_rows_before(self.below)
+			.await
+			.map_err(|e| format!("{e:?}"))?;
+		if !self.dry_run {
+			while backend
+				.prune_blocks_before(self.below, PRUNE_BATCH_SIZE)
+				.await
+				.map_err(|e| format!("{e:?}"))?
+				> 0
+			{}
+		}
+		println!(
+			"{} blocks, {} transactions and {} logs indexed below #{}{}",
+			report.blocks,
+			report.transactions,
+			report.logs,
+			self.below,
+			self.removed(),
+		);
+		Ok(())
+	}
+
+	fn removed(&self) -> &'static str {
+		if self.dry_run {
+			""
+		} else {
+			", removed"
+		}
+	}
+}
@@ -0,0 +1,135 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::sync::Arc;
+
+// Substrate
+#[cfg(feature = "sql")]
+use sc_client_api::backend::{Backend as BackendT, StorageProvider};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::{Block as BlockT, UniqueSaturatedInto};
+// Frontier
+use fc_storage::StorageOverride;
+use fp_rpc::EthereumRuntimeRPCApi;
+
+/// Deletes the mappings or indexed data of a block range and indexes it again.
+#[derive(Debug, Clone, clap::Args)]
+pub struct ReindexCmd {
+	/// First block to re-index.
+	#[arg(long)]
+	pub from: u32,
+
+	/// Last block to re-index.
+	#[arg(long)]
+	pub to: u32,
+}
+
+impl ReindexCmd {
+	/// Deletes and rebuilds the key-value mappings of the canonical blocks in the range.
+	pub fn run_kv<B, C>(
+		&self,
+		client: Arc<C>,
+		backend: Arc<fc_db::kv::Backend<B, C>>,
+		storage_override: Arc<dyn StorageOverride<B>>,
+	) -> sc_cli::Result<()>
+	where
+		B: BlockT,
+		C: ProvideRuntimeApi<B> + HeaderBackend<B>,
+		C::Api: EthereumRuntimeRPCApi<B>,
+	{
+		let (mut reindexed, mut failed) = (0u32, 0u32);
+		for number in self.from..=self.to {
+			let Some(hash) = client.hash(number.unique_saturated_into())? else {
+				break;
+			};
+			let Some(header) = client.header(hash)? else {
+				break;
+			};
+			let result =
+				fc_mapping_sync::kv::remove_block(storage_override.clone(), &backend, &header)
+					.and_then(|()| {
+						if number == 0 {
+							fc_mapping_sync::kv::sync_genesis_block(
+								client.as_ref(),
+								&backend,
+								&header,
+							)
+						} else {
+							fc_mapping_sync::kv::sync_block(
+								storage_override.clone(),
+								&backend,
+								&header,
+							)
+						}
+					});
+			match result {
+				Ok(()) => reindexed += 1,
+				Err(err) => {
+					eprintln!("Cannot re-index block #{number} {hash:?}: {err}");
+					failed += 1;
+				}
+			}
+		}
+		self.report(reindexed, failed)
+	}
+
+	/// Deletes and indexes again the SQL rows of the canonical blocks in the range.
+	#[cfg(feature = "sql")]
+	pub async fn run_sql<B, C, BE>(
+		&self,
+		client: Arc<C>,
+		backend: Arc<fc_db::sql::Backend<B>>,
+	) -> sc_cli::Result<()>
+	where
+		B: BlockT<Hash = ethereum_types::H256>,
+		C: ProvideRuntimeApi<B> + HeaderBackend<B> + StorageProvider<B, BE> + 'static,
+		C::Api: EthereumRuntimeRPCApi<B>,
+		BE: BackendT<B> + 'static,
+	{
+		let (mut reindexed, mut failed) = (0u32, 0u32);
+		for number in self.from..=self.to {
+			let Some(hash) = client.hash(number.unique_saturated_into())? else {
+				break;
+			};
+			match backend.reindex_block(client.clone(), hash).await {
+				Ok(true) => reindexed += 1,
+				Ok(false) => {
+					eprintln!("Cannot index the logs of block #{number} {hash:?}");
+					failed += 1;
+				}
+				Err(err) => {
+					eprintln!("Cannot re-index block #{number} {hash:?}: {err}");
+					failed += 1;
+				}
+			}
+		}
+		self.report(reindexed, failed)
+	}
+
+	fn report(&self, reindexed: u32, failed: u32) -> sc_cli::Result<()> {
+		println!(
+			"Re-indexed {reindexed} blocks of #{}..=#{}, {failed} failed",
+			self.from, self.to
+		);
+		if failed > 0 {
+			return Err(format!("{failed} blocks could not be re-indexed").into());
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,78 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::sync::Arc;
+
+use clap::ValueEnum;
+// Substrate
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+// Frontier
+use fc_db::kv::MappingRepair;
+
+/// Scans the key-value mapping db for ethereum hashes mapped to substrate blocks which are
+/// no longer known, and repairs them.
+#[derive(Debug, Clone, clap::Args)]
+pub struct RepairMappingCmd {
+	/// What to do with the orphaned mappings.
+	///
+	/// Can be one of `dry-run | remove | rederive`.
+	#[arg(long, value_enum, ignore_case = true, default_value = "dry-run")]
+	pub mode: RepairMode,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+pub enum RepairMode {
+	/// Only report the orphaned mappings.
+	DryRun,
+	/// Remove the orphaned mappings.
+	Remove,
+	/// Remove the orphaned mappings and map the unmapped canonical blocks from their headers.
+	Rederive,
+}
+
+impl RepairMappingCmd {
+	pub fn run<B, C>(&self, backend: Arc<fc_db::kv::Backend<B, C>>) -> sc_cli::Result<()>
+	where
+		B: BlockT,
+		C: HeaderBackend<B>,
+	{
+		let repair = match self.mode {
+			RepairMode::DryRun => MappingRepair::DryRun,
+			RepairMode::Remove => MappingRepair::Remove,
+			RepairMode::Rederive => MappingRepair::Rederive,
+		};
+		let report = backend.repair_mapping(repair)?;
+		println!(
+			"{} ethereum block hashes and {} transaction hashes mapped to unknown blocks{}",
+			report.orphaned_blocks,
+			report.orphaned_transactions,
+			match repair {
+				MappingRepair::DryRun => "",
+				_ => ", removed",
+			},
+		);
+		if repair == MappingRepair::Rederive {
+			println!(
+				"{} canonical blocks mapped from their headers",
+				report.rederived
+			);
+		}
+		Ok(())
+	}
+}
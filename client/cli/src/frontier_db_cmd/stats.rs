@@ -0,0 +1,99 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{fs, io, path::Path, sync::Arc};
+
+// Substrate
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+/// Prints the size and contents of the frontier database.
+#[derive(Debug, Clone, clap::Args)]
+pub struct StatsCmd {
+	/// Maximum number of missing block ranges to report.
+	#[arg(long, default_value_t = 20)]
+	pub max_gaps: u32,
+}
+
+impl StatsCmd {
+	/// Prints the size of the key-value database found in `path` and its keys per column.
+	pub fn run_kv<B, C>(
+		&self,
+		backend: Arc<fc_db::kv::Backend<B, C>>,
+		path: &Path,
+	) -> sc_cli::Result<()>
+	where
+		B: BlockT,
+		C: HeaderBackend<B>,
+	{
+		println!("Size: {} bytes", dir_size(path)?);
+		println!("Keys:");
+		for (column, count) in backend.key_counts() {
+			match count {
+				Some(count) => println!("  {column}: {count}"),
+				None => println!("  {column}: not iterable"),
+			}
+		}
+		Ok(())
+	}
+
+	/// Prints the size of the SQL database, its rows per table and the indexed blocks.
+	#[cfg(feature = "sql")]
+	pub async fn run_sql<B: BlockT>(
+		&self,
+		backend: Arc<fc_db::sql::Backend<B>>,
+	) -> sc_cli::Result<()> {
+		let stats = backend
+			.stats(self.max_gaps)
+			.await
+			.map_err(|e| format!("{e:?}"))?;
+		println!("Size: {} bytes", stats.size);
+		println!("Rows:");
+		for (table, count) in stats.rows {
+			println!("  {table}: {count}");
+		}
+		match stats.indexed_range {
+			Some((from, to)) => println!("Indexed blocks: #{from}..=#{to}"),
+			None => println!("Indexed blocks: none"),
+		}
+		println!("Non-canonical blocks: {}", stats.non_canon_blocks);
+		if stats.gaps.is_empty() {
+			println!("Gaps: none");
+		} else {
+			println!("Gaps:");
+			for (from, to) in stats.gaps {
+				println!("  #{from}..=#{to}");
+			}
+		}
+		Ok(())
+	}
+}
+
+fn dir_size(path: &Path) -> io::Result<u64> {
+	let mut size = 0;
+	for entry in fs::read_dir(path)? {
+		let entry = entry?;
+		let metadata = entry.metadata()?;
+		size += if metadata.is_dir() {
+			dir_size(&entry.path())?
+		} else {
+			metadata.len()
+		};
+	}
+	Ok(size)
+}
@@ -75,9 +75,10 @@ enum TestValue {
 
 fn cmd(key: String, value: Option<PathBuf>, operation: Operation, column: Column) -> FrontierDbCmd {
 	FrontierDbCmd {
-		operation,
-		column,
-		key,
+		command: None,
+		operation: Some(operation),
+		column: Some(column),
+		key: Some(key),
 		value,
 		shared_params: sc_cli::SharedParams {
 			chain: None,
@@ -0,0 +1,110 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::sync::Arc;
+
+// Substrate
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::{Block as BlockT, UniqueSaturatedInto};
+// Frontier
+use fc_storage::StorageOverride;
+use fp_consensus::Hashes;
+
+/// Recomputes the ethereum block and transaction hashes of a block range from the runtime
+/// storage, and compares them to the key-value mapping db.
+#[derive(Debug, Clone, clap::Args)]
+pub struct VerifyMappingCmd {
+	/// First block to verify.
+	#[arg(long, default_value_t = 0)]
+	pub from: u32,
+
+	/// Last block to verify, defaults to the best block.
+	#[arg(long)]
+	pub to: Option<u32>,
+}
+
+impl VerifyMappingCmd {
+	pub fn run<B, C>(
+		&self,
+		client: Arc<C>,
+		backend: Arc<fc_db::kv::Backend<B, C>>,
+		storage_override: Arc<dyn StorageOverride<B>>,
+	) -> sc_cli::Result<()>
+	where
+		B: BlockT,
+		C: HeaderBackend<B>,
+	{
+		let to = self
+			.to
+			.unwrap_or_else(|| client.info().best_number.unique_saturated_into());
+		let mapping = backend.mapping();
+
+		let (mut verified, mut skipped, mut mismatches) = (0u32, 0u32, 0u32);
+		for number in self.from..=to {
+			let Some(hash) = client.hash(number.unique_saturated_into())? else {
+				skipped += 1;
+				continue;
+			};
+			// Blocks not synced yet or whose state is pruned cannot be compared.
+			if !mapping.is_synced(&hash)? {
+				skipped += 1;
+				continue;
+			}
+			let Some(block) = storage_override.current_block(hash) else {
+				skipped += 1;
+				continue;
+			};
+			verified += 1;
+
+			let hashes = Hashes::from_block(block);
+			if !mapping
+				.block_hash(&hashes.block_hash)?
+				.is_some_and(|mapped| mapped.contains(&hash))
+			{
+				mismatches += 1;
+				println!(
+					"#{number}: ethereum block {:?} is not mapped to {hash:?}",
+					hashes.block_hash
+				);
+			}
+			for (index, transaction_hash) in hashes.transaction_hashes.iter().enumerate() {
+				let mapped = mapping.transaction_metadata(transaction_hash)?;
+				if !mapped.iter().any(|metadata| {
+					metadata.substrate_block_hash == hash
+						&& metadata.ethereum_block_hash == hashes.block_hash
+						&& metadata.ethereum_index as usize == index
+				}) {
+					mismatches += 1;
+					println!(
+						"#{number}: ethereum transaction {transaction_hash:?} is not mapped to \
+						index {index} of {hash:?}"
+					);
+				}
+			}
+		}
+
+		println!(
+			"Verified {verified} blocks of #{}..=#{to}, {skipped} skipped, {mismatches} mismatches",
+			self.from
+		);
+		if mismatches > 0 {
+			return Err(format!("{mismatches} mismatches found in the mapping db").into());
+		}
+		Ok(())
+	}
+}
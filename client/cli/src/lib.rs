@@ -20,4 +20,9 @@
 
 mod frontier_db_cmd;
 
-pub use self::frontier_db_cmd::FrontierDbCmd;
+#[cfg(feature = "sql")]
+pub use self::frontier_db_cmd::{ExportFormat, ExportLogsCmd, MigrateToSqlCmd};
+pub use self::frontier_db_cmd::{
+	FrontierDbCmd, FrontierDbSubcommand, PruneCmd, ReindexCmd, RepairMappingCmd, RepairMode,
+	StatsCmd, VerifyMappingCmd,
+};
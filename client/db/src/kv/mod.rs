@@ -17,6 +17,8 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 mod parity_db_adapter;
+#[cfg(feature = "rocksdb")]
+mod rocksdb_adapter;
 mod upgrade;
 mod utils;
 
@@ -33,15 +35,26 @@ pub use sc_client_db::DatabaseSource;
 use sp_blockchain::HeaderBackend;
 use sp_core::{H160, H256};
 pub use sp_database::Database;
-use sp_runtime::traits::Block as BlockT;
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT, UniqueSaturatedInto};
 // Frontier
-use fc_api::{FilteredLog, TransactionMetadata};
+use fc_api::{FeeHistoryEntry, FilteredLog, LogIndexerError, TransactionMetadata};
+use fp_consensus::{Hashes, Log as ConsensusLog, PostLog, PreLog};
 use fp_storage::{EthereumStorageSchema, PALLET_ETHEREUM_SCHEMA_CACHE};
 
 const DB_HASH_LEN: usize = 32;
 /// Hash type that this backend uses for the database.
 pub type DbHash = [u8; DB_HASH_LEN];
 
+/// Database of the backend, whose columns can be iterated unlike a [`Database`].
+pub(crate) trait KeyValueDatabase: Database<DbHash> {
+	/// Calls `f` with the key and value of each entry of `column` until it returns `false`.
+	fn iter_column_while(
+		&self,
+		column: u32,
+		f: &mut dyn FnMut(&[u8], &[u8]) -> bool,
+	) -> Result<(), String>;
+}
+
 /// Database settings.
 pub struct DatabaseSettings {
 	/// Where to find the database.
@@ -59,6 +72,9 @@ pub(crate) mod columns {
 
 pub mod static_keys {
 	pub const CURRENT_SYNCING_TIPS: &[u8] = b"CURRENT_SYNCING_TIPS";
+	pub const HISTORY_HORIZON: &[u8] = b"HISTORY_HORIZON";
+	/// Prefix of the fee history keys, followed by the big-endian block number.
+	pub const FEE_HISTORY: &[u8] = b"FEE_HISTORY";
 }
 
 #[derive(Clone)]
@@ -93,6 +109,40 @@ impl<Block: BlockT, C: HeaderBackend<Block>> fc_api::Backend<Block> for Backend<
 	async fn latest_block_hash(&self) -> Result<Block::Hash, String> {
 		Ok(self.client.info().best_hash)
 	}
+
+	async fn history_horizon(&self) -> Result<u64, String> {
+		self.meta().history_horizon()
+	}
+}
+
+#[async_trait::async_trait]
+impl<Block: BlockT, C: HeaderBackend<Block>> fc_api::FeeHistoryBackend for Backend<Block, C> {
+	async fn write_fee_history(
+		&self,
+		block_number: u64,
+		entry: &FeeHistoryEntry,
+	) -> Result<(), String> {
+		self.meta().write_fee_history(block_number, entry)
+	}
+
+	async fn remove_fee_history(&self, block_numbers: &[u64]) -> Result<(), String> {
+		self.meta().remove_fee_history(block_numbers)
+	}
+
+	async fn fee_history(
+		&self,
+		from_block: u64,
+		to_block: u64,
+	) -> Result<Vec<(u64, FeeHistoryEntry)>, String> {
+		(from_block..=to_block)
+			.filter_map(|block_number| {
+				self.meta()
+					.fee_history(block_number)
+					.map(|entry| entry.map(|entry| (block_number, entry)))
+					.transpose()
+			})
+			.collect()
+	}
 }
 
 #[derive(Clone, Default)]
@@ -110,8 +160,10 @@ impl<Block: BlockT> fc_api::LogIndexerBackend<Block> for LogIndexerBackend<Block
 		_to_block: u64,
 		_addresses: Vec<H160>,
 		_topics: Vec<Vec<Option<H256>>>,
-	) -> Result<Vec<FilteredLog<Block>>, String> {
-		Err("KeyValue db does not index logs".into())
+	) -> Result<Vec<FilteredLog<Block>>, LogIndexerError> {
+		Err(LogIndexerError::Other(
+			"KeyValue db does not index logs".into(),
+		))
 	}
 }
 
@@ -178,10 +230,152 @@ impl<Block: BlockT, C: HeaderBackend<Block>> Backend<Block, C> {
 	pub fn meta(&self) -> &Arc<MetaDb<Block>> {
 		&self.meta
 	}
+
+	/// Counts the keys of each column, `None` for the columns which cannot be iterated.
+	pub fn key_counts(&self) -> Vec<(&'static str, Option<u64>)> {
+		[
+			("meta", columns::META),
+			("block_mapping", columns::BLOCK_MAPPING),
+			("transaction_mapping", columns::TRANSACTION_MAPPING),
+			("synced_mapping", columns::SYNCED_MAPPING),
+		]
+		.into_iter()
+		.map(|(name, column)| {
+			let mut count = 0;
+			let counted = self.mapping.db.iter_column_while(column, &mut |_, _| {
+				count += 1;
+				true
+			});
+			(name, counted.ok().map(|()| count))
+		})
+		.collect()
+	}
+
+	/// Scans the mapping db for ethereum hashes mapped to substrate blocks which the client
+	/// no longer knows, as left behind by an unclean shutdown.
+	pub fn repair_mapping(&self, repair: MappingRepair) -> Result<MappingRepairReport, String> {
+		let is_orphan = |hash: &Block::Hash| !matches!(self.client.number(*hash), Ok(Some(_)));
+		let (orphaned_blocks, orphaned_transactions) = self
+			.mapping
+			.remove_orphans(is_orphan, repair == MappingRepair::DryRun)?;
+		let mut report = MappingRepairReport {
+			orphaned_blocks,
+			orphaned_transactions,
+			rederived: 0,
+		};
+		if repair == MappingRepair::Rederive {
+			let best_number: u32 = self.client.info().best_number.unique_saturated_into();
+			for number in 0..=best_number {
+				if self.rederive_mapping(number)? {
+					report.rederived += 1;
+				}
+			}
+		}
+		Ok(report)
+	}
+
+	/// Removes the block and transaction mappings of the blocks with a number lower than
+	/// `block_number`, or only counts them if `dry_run` is set.
+	/// The synced marks are kept, so the pruned blocks are not picked up again by the sync.
+	pub fn prune_blocks_before(
+		&self,
+		block_number: u32,
+		dry_run: bool,
+	) -> Result<MappingPruneReport, String> {
+		let is_pruned = |hash: &Block::Hash| {
+			matches!(
+				self.client.number(*hash),
+				Ok(Some(number)) if number < block_number.unique_saturated_into()
+			)
+		};
+		let (blocks, transactions) = self.mapping.remove_orphans(is_pruned, dry_run)?;
+		Ok(MappingPruneReport {
+			blocks,
+			transactions,
+		})
+	}
+
+	/// Maps the canonical block `number` from the hashes of its header digest if it is not
+	/// mapped yet, returning whether it was.
+	fn rederive_mapping(&self, number: u32) -> Result<bool, String> {
+		let Some(hash) = self
+			.client
+			.hash(number.unique_saturated_into())
+			.map_err(|err| format!("{:?}", err))?
+		else {
+			return Ok(false);
+		};
+		let Some(header) = self
+			.client
+			.header(hash)
+			.map_err(|err| format!("{:?}", err))?
+		else {
+			return Ok(false);
+		};
+		// The blocks only logging their ethereum block hash need the runtime storage to be
+		// mapped, they are left to the sync worker.
+		let hashes = match fp_consensus::find_log(header.digest()) {
+			Ok(ConsensusLog::Pre(PreLog::Block(block)))
+			| Ok(ConsensusLog::Post(PostLog::Block(block))) => Hashes::from_block(block),
+			Ok(ConsensusLog::Post(PostLog::Hashes(hashes)))
+			| Ok(ConsensusLog::Post(PostLog::HashesWithReceiptsRoot(hashes, _))) => hashes,
+			_ => return Ok(false),
+		};
+		if self
+			.mapping
+			.block_hash(&hashes.block_hash)?
+			.is_some_and(|mapped| mapped.contains(&hash))
+		{
+			return Ok(false);
+		}
+		self.mapping.write_hashes(MappingCommitment {
+			block_hash: hash,
+			ethereum_block_hash: hashes.block_hash,
+			ethereum_transaction_hashes: hashes.transaction_hashes,
+		})?;
+		Ok(true)
+	}
+}
+
+/// What a repair of the mapping db does with the orphaned mappings.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MappingRepair {
+	/// Only report them.
+	DryRun,
+	/// Remove them.
+	Remove,
+	/// Remove them and map the canonical blocks which are not mapped from their headers.
+	Rederive,
+}
+
+/// Outcome of a repair of the mapping db.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MappingRepairReport {
+	/// Number of ethereum block hashes mapped to unknown substrate blocks.
+	pub orphaned_blocks: u64,
+	/// Number of ethereum transaction hashes mapped to unknown substrate blocks.
+	pub orphaned_transactions: u64,
+	/// Number of canonical blocks mapped from their headers.
+	pub rederived: u64,
+}
+
+/// Outcome of a pruning of the mapping db.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MappingPruneReport {
+	/// Number of ethereum block hashes mapped to pruned substrate blocks.
+	pub blocks: u64,
+	/// Number of ethereum transaction hashes mapped to pruned substrate blocks.
+	pub transactions: u64,
+}
+
+fn fee_history_key(block_number: u64) -> Vec<u8> {
+	let mut key = static_keys::FEE_HISTORY.to_vec();
+	key.extend_from_slice(&block_number.to_be_bytes());
+	key
 }
 
 pub struct MetaDb<Block> {
-	db: Arc<dyn Database<DbHash>>,
+	db: Arc<dyn KeyValueDatabase>,
 	_marker: PhantomData<Block>,
 }
 
@@ -210,6 +404,79 @@ impl<Block: BlockT> MetaDb<Block> {
 		Ok(())
 	}
 
+	/// First block mapped by the mapping sync worker, `0` when none was recorded.
+	pub fn history_horizon(&self) -> Result<u64, String> {
+		match self.db.get(columns::META, static_keys::HISTORY_HORIZON) {
+			Some(raw) => Ok(u64::decode(&mut &raw[..]).map_err(|e| e.to_string())?),
+			None => Ok(0),
+		}
+	}
+
+	pub fn write_history_horizon(&self, block_number: u64) -> Result<(), String> {
+		let mut transaction = sp_database::Transaction::new();
+
+		transaction.set(
+			columns::META,
+			static_keys::HISTORY_HORIZON,
+			&block_number.encode(),
+		);
+
+		self.db.commit(transaction).map_err(|e| e.to_string())?;
+
+		Ok(())
+	}
+
+	pub fn fee_history(&self, block_number: u64) -> Result<Option<FeeHistoryEntry>, String> {
+		match self.db.get(columns::META, &fee_history_key(block_number)) {
+			Some(raw) => {
+				let (base_fee, gas_used_ratio, rewards) =
+					<(u64, u64, Vec<u64>)>::decode(&mut &raw[..]).map_err(|e| e.to_string())?;
+				Ok(Some(FeeHistoryEntry {
+					base_fee,
+					gas_used_ratio: f64::from_bits(gas_used_ratio),
+					rewards,
+				}))
+			}
+			None => Ok(None),
+		}
+	}
+
+	pub fn write_fee_history(
+		&self,
+		block_number: u64,
+		entry: &FeeHistoryEntry,
+	) -> Result<(), String> {
+		let mut transaction = sp_database::Transaction::new();
+
+		// SCALE has no floats, the ratio is stored as its bit pattern.
+		transaction.set(
+			columns::META,
+			&fee_history_key(block_number),
+			&(
+				entry.base_fee,
+				entry.gas_used_ratio.to_bits(),
+				&entry.rewards,
+			)
+				.encode(),
+		);
+
+		self.db.commit(transaction).map_err(|e| e.to_string())?;
+
+		Ok(())
+	}
+
+	pub fn remove_fee_history(&self, block_numbers: &[u64]) -> Result<(), String> {
+		let mut transaction = sp_database::Transaction::new();
+
+		for block_number in block_numbers {
+			transaction.remove(columns::META, &fee_history_key(*block_number));
+		}
+
+		self.db.commit(transaction).map_err(|e| e.to_string())?;
+
+		Ok(())
+	}
+
 	pub fn ethereum_schema(&self) -> Result<Option<Vec<(EthereumStorageSchema, H256)>>, String> {
 		match self
 			.db
@@ -248,7 +515,7 @@ pub struct MappingCommitment<Block: BlockT> {
 }
 
 pub struct MappingDb<Block> {
-	db: Arc<dyn Database<DbHash>>,
+	db: Arc<dyn KeyValueDatabase>,
 	write_lock: Arc<Mutex<()>>,
 	_marker: PhantomData<Block>,
 }
@@ -361,4 +628,392 @@ impl<Block: BlockT> MappingDb<Block> {
 
 		Ok(())
 	}
+
+	/// Calls `f` with each ethereum block hash and the substrate blocks it maps to, until
+	/// `f` returns `false`.
+	pub fn for_each_block_mapping(
+		&self,
+		mut f: impl FnMut(H256, Vec<Block::Hash>) -> bool,
+	) -> Result<(), String> {
+		self.for_each_decoded(columns::BLOCK_MAPPING, &mut f)
+	}
+
+	/// Calls `f` with each ethereum transaction hash and its metadata, until `f` returns
+	/// `false`.
+	///
+	/// Not supported by ParityDB, which does not order this column.
+	pub fn for_each_transaction_metadata(
+		&self,
+		mut f: impl FnMut(H256, Vec<TransactionMetadata<Block>>) -> bool,
+	) -> Result<(), String> {
+		self.for_each_decoded(columns::TRANSACTION_MAPPING, &mut f)
+	}
+
+	/// Calls `f` with each substrate block hash visited by the mapping sync, until `f`
+	/// returns `false`.
+	///
+	/// Not supported by ParityDB, which does not order this column.
+	pub fn for_each_synced_block(
+		&self,
+		mut f: impl FnMut(Block::Hash, bool) -> bool,
+	) -> Result<(), String> {
+		self.for_each_decoded(columns::SYNCED_MAPPING, &mut f)
+	}
+
+	fn for_each_decoded<K: Decode, V: Decode>(
+		&self,
+		column: u32,
+		f: &mut dyn FnMut(K, V) -> bool,
+	) -> Result<(), String> {
+		let mut error = None;
+		self.db.iter_column_while(column, &mut |key, value| match (
+			K::decode(&mut &key[..]),
+			V::decode(&mut &value[..]),
+		) {
+			(Ok(key), Ok(value)) => f(key, value),
+			(Err(e), _) | (_, Err(e)) => {
+				error = Some(format!("Entry {key:?} of column {column}: {e}"));
+				false
+			}
+		})?;
+		error.map_or(Ok(()), Err)
+	}
+
+	/// Drops the synced mark of a block which has no ethereum mappings.
+	pub fn remove_none(&self, block_hash: Block::Hash) -> Result<(), String> {
+		let _lock = self.write_lock.lock();
+
+		let mut transaction = sp_database::Transaction::new();
+
+		transaction.remove(columns::SYNCED_MAPPING, &block_hash.encode());
+
+		self.db.commit(transaction).map_err(|e| e.to_string())?;
+
+		Ok(())
+	}
+
+	/// Drops the mappings written by [`Self::write_hashes`] for the same commitment.
+	pub fn remove_hashes(&self, commitment: MappingCommitment<Block>) -> Result<(), String> {
+		let _lock = self.write_lock.lock();
+
+		let mut transaction = sp_database::Transaction::new();
+
+		if let Some(mut substrate_hashes) = self.block_hash(&commitment.ethereum_block_hash)? {
+			substrate_hashes.retain(|hash| hash != &commitment.block_hash);
+			if substrate_hashes.is_empty() {
+				transaction.remove(
+					columns::BLOCK_MAPPING,
+					&commitment.ethereum_block_hash.encode(),
+				);
+			} else {
+				transaction.set(
+					columns::BLOCK_MAPPING,
+					&commitment.ethereum_block_hash.encode(),
+					&substrate_hashes.encode(),
+				);
+			}
+		}
+
+		for ethereum_transaction_hash in commitment.ethereum_transaction_hashes {
+			let mut metadata = self.transaction_metadata(&ethereum_transaction_hash)?;
+			metadata.retain(|metadata| metadata.substrate_block_hash != commitment.block_hash);
+			if metadata.is_empty() {
+				transaction.remove(
+					columns::TRANSACTION_MAPPING,
+					&ethereum_transaction_hash.encode(),
+				);
+			} else {
+				transaction.set(
+					columns::TRANSACTION_MAPPING,
+					&ethereum_transaction_hash.encode(),
+					&metadata.encode(),
+				);
+			}
+		}
+
+		transaction.remove(columns::SYNCED_MAPPING, &commitment.block_hash.encode());
+
+		self.db.commit(transaction).map_err(|e| e.to_string())?;
+
+		Ok(())
+	}
+
+	/// Drops the substrate blocks matching `is_orphan` from the block and transaction
+	/// mappings, returning the number of ethereum block and transaction hashes affected.
+	/// Used for both the orphaned and the pruned blocks.
+	fn remove_orphans(
+		&self,
+		is_orphan: impl Fn(&Block::Hash) -> bool,
+		dry_run: bool,
+	) -> Result<(u64, u64), String> {
+		let _lock = self.write_lock.lock();
+
+		let mut transaction = sp_database::Transaction::new();
+		let mut orphaned_blocks = 0;
+		self.db
+			.iter_column_while(columns::BLOCK_MAPPING, &mut |key, value| {
+				let Ok(mut substrate_hashes) = Vec::<Block::Hash>::decode(&mut &value[..]) else {
+					return true;
+				};
+				let mapped = substrate_hashes.len();
+				substrate_hashes.retain(|hash| !is_orphan(hash));
+				if substrate_hashes.len() < mapped {
+					orphaned_blocks += 1;
+					if substrate_hashes.is_empty() {
+						transaction.remove(columns::BLOCK_MAPPING, key);
+					} else {
+						transaction.set(columns::BLOCK_MAPPING, key, &substrate_hashes.encode());
+					}
+				}
+				true
+			})?;
+
+		let mut orphaned_transactions = 0;
+		let scanned = self
+			.db
+			.iter_column_while(columns::TRANSACTION_MAPPING, &mut |key, value| {
+				let Ok(mut metadata) = Vec::<TransactionMetadata<Block>>::decode(&mut &value[..])
+				else {
+					return true;
+				};
+				let mapped = metadata.len();
+				metadata.retain(|metadata| !is_orphan(&metadata.substrate_block_hash));
+				if metadata.len() < mapped {
+					orphaned_transactions += 1;
+					if metadata.is_empty() {
+						transaction.remove(columns::TRANSACTION_MAPPING, key);
+					} else {
+						transaction.set(columns::TRANSACTION_MAPPING, key, &metadata.encode());
+					}
+				}
+				true
+			});
+		// Only the ordered columns of a ParityDB database can be iterated.
+		if let Err(err) = scanned {
+			log::warn!(target: "fc-db", "Transaction mapping not scanned for orphans: {err}");
+		}
+
+		if !dry_run {
+			self.db.commit(transaction).map_err(|e| e.to_string())?;
+		}
+		Ok((orphaned_blocks, orphaned_transactions))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use tempfile::tempdir;
+	// Substrate
+	use sp_runtime::{
+		generic::{Block, Header},
+		traits::BlakeTwo256,
+	};
+	use substrate_test_runtime_client::{
+		prelude::*, DefaultTestClientBuilderExt, TestClientBuilder,
+	};
+
+	use super::*;
+
+	type OpaqueBlock =
+		Block<Header<u64, BlakeTwo256>, substrate_test_runtime_client::runtime::Extrinsic>;
+
+	#[test]
+	fn repair_mapping_removes_orphaned_mappings() {
+		let tmp = tempdir().expect("create a temporary directory");
+		let (client, _) = TestClientBuilder::new()
+			.build_with_native_executor::<substrate_test_runtime_client::runtime::RuntimeApi, _>(
+			None,
+		);
+		let client = Arc::new(client);
+		let backend = Backend::<OpaqueBlock, _>::new(
+			client.clone(),
+			&DatabaseSettings {
+				source: DatabaseSource::ParityDb {
+					path: tmp.path().to_owned(),
+				},
+			},
+		)
+		.expect("frontier backend to be opened");
+
+		// The genesis block is known by the client, the other one is not.
+		let genesis_hash = client.info().genesis_hash;
+		let orphan_hash = H256::repeat_byte(0x01);
+		let ethereum_block_hash = H256::repeat_byte(0x02);
+		for block_hash in [genesis_hash, orphan_hash] {
+			backend
+				.mapping()
+				.write_hashes(MappingCommitment {
+					block_hash,
+					ethereum_block_hash,
+					ethereum_transaction_hashes: vec![],
+				})
+				.expect("mapping to be written");
+		}
+
+		let expected = MappingRepairReport {
+			orphaned_blocks: 1,
+			..Default::default()
+		};
+		assert_eq!(
+			backend.repair_mapping(MappingRepair::DryRun),
+			Ok(expected.clone())
+		);
+		assert_eq!(
+			backend.mapping().block_hash(&ethereum_block_hash),
+			Ok(Some(vec![genesis_hash, orphan_hash]))
+		);
+
+		assert_eq!(backend.repair_mapping(MappingRepair::Remove), Ok(expected));
+		assert_eq!(
+			backend.mapping().block_hash(&ethereum_block_hash),
+			Ok(Some(vec![genesis_hash]))
+		);
+		assert_eq!(
+			backend.repair_mapping(MappingRepair::Remove),
+			Ok(MappingRepairReport::default())
+		);
+	}
+
+	#[test]
+	fn prune_blocks_before_removes_mappings_below_the_height() {
+		let tmp = tempdir().expect("create a temporary directory");
+		let (client, _) = TestClientBuilder::new()
+			.build_with_native_executor::<substrate_test_runtime_client::runtime::RuntimeApi, _>(
+			None,
+		);
+		let client = Arc::new(client);
+		let backend = Backend::<OpaqueBlock, _>::new(
+			client.clone(),
+			&DatabaseSettings {
+				source: DatabaseSource::ParityDb {
+					path: tmp.path().to_owned(),
+				},
+			},
+		)
+		.expect("frontier backend to be opened");
+
+		// The genesis block is pruned, the unknown one is left to the repair.
+		let genesis_hash = client.info().genesis_hash;
+		let unknown_hash = H256::repeat_byte(0x01);
+		for (block_hash, ethereum_block_hash) in [
+			(genesis_hash, H256::repeat_byte(0x02)),
+			(unknown_hash, H256::repeat_byte(0x03)),
+		] {
+			backend
+				.mapping()
+				.write_hashes(MappingCommitment {
+					block_hash,
+					ethereum_block_hash,
+					ethereum_transaction_hashes: vec![],
+				})
+				.expect("mapping to be written");
+		}
+
+		let expected = MappingPruneReport {
+			blocks: 1,
+			..Default::default()
+		};
+		assert_eq!(
+			backend.prune_blocks_before(0, false),
+			Ok(Default::default())
+		);
+		assert_eq!(backend.prune_blocks_before(1, true), Ok(expected.clone()));
+		assert_eq!(backend.prune_blocks_before(1, false), Ok(expected));
+		assert_eq!(
+			backend.mapping().block_hash(&H256::repeat_byte(0x02)),
+			Ok(None)
+		);
+		assert_eq!(
+			backend.mapping().block_hash(&H256::repeat_byte(0x03)),
+			Ok(Some(vec![unknown_hash]))
+		);
+		// The synced mark is kept.
+		assert_eq!(backend.mapping().is_synced(&genesis_hash), Ok(true));
+	}
+
+	#[cfg(feature = "rocksdb")]
+	#[test]
+	fn mapping_columns_can_be_iterated() {
+		let tmp = tempdir().expect("create a temporary directory");
+		let (client, _) = TestClientBuilder::new()
+			.build_with_native_executor::<substrate_test_runtime_client::runtime::RuntimeApi, _>(
+			None,
+		);
+		let client = Arc::new(client);
+		let backend = Backend::<OpaqueBlock, _>::new(
+			client,
+			&DatabaseSettings {
+				source: DatabaseSource::RocksDb {
+					path: tmp.path().to_owned(),
+					cache_size: 0,
+				},
+			},
+		)
+		.expect("frontier backend to be opened");
+
+		let mapping = backend.mapping();
+		let block_hash = H256::repeat_byte(0x01);
+		let ethereum_block_hash = H256::repeat_byte(0x02);
+		let ethereum_transaction_hash = H256::repeat_byte(0x03);
+		mapping
+			.write_hashes(MappingCommitment {
+				block_hash,
+				ethereum_block_hash,
+				ethereum_transaction_hashes: vec![ethereum_transaction_hash],
+			})
+			.expect("mapping to be written");
+		mapping
+			.write_none(H256::repeat_byte(0x04))
+			.expect("mapping to be written");
+
+		let mut block_mappings = Vec::new();
+		mapping
+			.for_each_block_mapping(|hash, substrate_hashes| {
+				block_mappings.push((hash, substrate_hashes));
+				true
+			})
+			.expect("block mappings to be iterated");
+		assert_eq!(
+			block_mappings,
+			vec![(ethereum_block_hash, vec![block_hash])]
+		);
+
+		let mut transactions = Vec::new();
+		mapping
+			.for_each_transaction_metadata(|hash, metadata| {
+				transactions.push((hash, metadata));
+				true
+			})
+			.expect("transaction metadata to be iterated");
+		assert_eq!(
+			transactions,
+			vec![(
+				ethereum_transaction_hash,
+				vec![TransactionMetadata {
+					substrate_block_hash: block_hash,
+					ethereum_block_hash,
+					ethereum_index: 0,
+				}]
+			)]
+		);
+
+		// Iteration stops as soon as the callback returns false.
+		let mut synced = Vec::new();
+		mapping
+			.for_each_synced_block(|hash, _| {
+				synced.push(hash);
+				false
+			})
+			.expect("synced blocks to be iterated");
+		assert_eq!(synced, vec![block_hash]);
+
+		assert_eq!(
+			backend.key_counts()[1..],
+			[
+				("block_mapping", Some(1)),
+				("transaction_mapping", Some(1)),
+				("synced_mapping", Some(2)),
+			]
+		);
+	}
 }
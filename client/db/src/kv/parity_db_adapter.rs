@@ -19,6 +19,8 @@
 // Substrate
 use sp_database::{error::DatabaseError, Change, ColumnId, Database, Transaction};
 
+use super::KeyValueDatabase;
+
 fn handle_err<T>(result: parity_db::Result<T>) -> T {
 	match result {
 		Ok(r) => r,
@@ -64,3 +66,24 @@ impl<H: Clone + AsRef<[u8]>> Database<H> for DbAdapter {
 		let _prefix = key.drain(0..key.len() - super::DB_HASH_LEN);
 	}
 }
+
+impl KeyValueDatabase for DbAdapter {
+	fn iter_column_while(
+		&self,
+		column: u32,
+		f: &mut dyn FnMut(&[u8], &[u8]) -> bool,
+	) -> Result<(), String> {
+		// Only the columns with a btree index can be iterated.
+		let mut iter = self
+			.0
+			.iter(column as u8)
+			.map_err(|err| format!("{:?}", err))?;
+		iter.seek_to_first().map_err(|err| format!("{:?}", err))?;
+		while let Some((key, value)) = iter.next().map_err(|err| format!("{:?}", err))? {
+			if !f(&key[..], &value[..]) {
+				break;
+			}
+		}
+		Ok(())
+	}
+}
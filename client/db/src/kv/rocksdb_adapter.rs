@@ -0,0 +1,67 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+// Substrate
+use sp_database::{error::DatabaseError, Change, ColumnId, Database, Transaction};
+
+use super::KeyValueDatabase;
+
+fn handle_err<T>(result: std::io::Result<T>) -> T {
+	match result {
+		Ok(r) => r,
+		Err(e) => {
+			panic!("Critical database error: {:?}", e);
+		}
+	}
+}
+
+pub struct DbAdapter(pub kvdb_rocksdb::Database);
+
+impl<H: Clone + AsRef<[u8]>> Database<H> for DbAdapter {
+	fn commit(&self, transaction: Transaction<H>) -> Result<(), DatabaseError> {
+		let mut tx = self.0.transaction();
+		for change in transaction.0 {
+			match change {
+				Change::Set(col, key, value) => tx.put_vec(col, &key, value),
+				Change::Remove(col, key) => tx.delete(col, &key),
+				_ => unimplemented!(),
+			}
+		}
+		self.0.write(tx).map_err(|err| DatabaseError(Box::new(err)))
+	}
+
+	fn get(&self, col: ColumnId, key: &[u8]) -> Option<Vec<u8>> {
+		handle_err(self.0.get(col, key))
+	}
+}
+
+impl KeyValueDatabase for DbAdapter {
+	fn iter_column_while(
+		&self,
+		column: u32,
+		f: &mut dyn FnMut(&[u8], &[u8]) -> bool,
+	) -> Result<(), String> {
+		for entry in self.0.iter(column) {
+			let (key, value) = entry.map_err(|err| err.to_string())?;
+			if !f(&key[..], &value[..]) {
+				break;
+			}
+		}
+		Ok(())
+	}
+}
@@ -22,13 +22,13 @@ use std::{path::Path, sync::Arc};
 use sp_blockchain::HeaderBackend;
 use sp_runtime::traits::Block as BlockT;
 
-use super::{Database, DatabaseSettings, DatabaseSource, DbHash};
+use super::{DatabaseSettings, DatabaseSource, KeyValueDatabase};
 
 pub fn open_database<Block: BlockT, C: HeaderBackend<Block>>(
 	client: Arc<C>,
 	config: &DatabaseSettings,
-) -> Result<Arc<dyn Database<DbHash>>, String> {
-	let db: Arc<dyn Database<DbHash>> = match &config.source {
+) -> Result<Arc<dyn KeyValueDatabase>, String> {
+	let db: Arc<dyn KeyValueDatabase> = match &config.source {
 		DatabaseSource::Auto {
 			paritydb_path,
 			rocksdb_path,
@@ -59,7 +59,7 @@ fn open_kvdb_rocksdb<Block: BlockT, C: HeaderBackend<Block>>(
 	path: &Path,
 	create: bool,
 	_source: &DatabaseSource,
-) -> Result<Arc<dyn Database<DbHash>>, String> {
+) -> Result<Arc<dyn KeyValueDatabase>, String> {
 	// first upgrade database to required version
 	#[cfg(not(test))]
 	match super::upgrade::upgrade_db::<Block, C>(client, path, _source) {
@@ -74,7 +74,7 @@ fn open_kvdb_rocksdb<Block: BlockT, C: HeaderBackend<Block>>(
 	// write database version only after the database is successfully opened
 	#[cfg(not(test))]
 	super::upgrade::update_version(path).map_err(|_| "Cannot update db version".to_string())?;
-	Ok(sp_database::as_database(db))
+	Ok(Arc::new(super::rocksdb_adapter::DbAdapter(db)))
 }
 
 #[cfg(not(feature = "rocksdb"))]
@@ -83,7 +83,7 @@ fn open_kvdb_rocksdb<Block: BlockT, C: HeaderBackend<Block>>(
 	_path: &Path,
 	_create: bool,
 	_source: &DatabaseSource,
-) -> Result<Arc<dyn Database<DbHash>>, String> {
+) -> Result<Arc<dyn KeyValueDatabase>, String> {
 	Err("Missing feature flags `rocksdb`".to_string())
 }
 
@@ -92,15 +92,24 @@ fn open_parity_db<Block: BlockT, C: HeaderBackend<Block>>(
 	client: Arc<C>,
 	path: &Path,
 	_source: &DatabaseSource,
-) -> Result<Arc<dyn Database<DbHash>>, String> {
+) -> Result<Arc<dyn KeyValueDatabase>, String> {
 	// first upgrade database to required version
 	#[cfg(not(test))]
 	match super::upgrade::upgrade_db::<Block, C>(client, path, _source) {
 		Ok(_) => (),
 		Err(_) => return Err("Frontier DB upgrade error".to_string()),
 	}
-	let mut config = parity_db::Options::with_columns(path, super::columns::NUM_COLUMNS as u8);
-	config.columns[super::columns::BLOCK_MAPPING as usize].btree_index = true;
+	let mut config = parity_db_options(path);
+	// The column layout of an existing database cannot change, it is kept as is.
+	if let Ok(Some(metadata)) = config.load_metadata() {
+		if metadata.columns != config.columns {
+			log::info!(
+				target: "frontier-db",
+				"Opening Frontier ParityDB with its legacy column layout, resync it to use the current one",
+			);
+			config.columns = metadata.columns;
+		}
+	}
 
 	let db = parity_db::Db::open_or_create(&config).map_err(|err| format!("{}", err))?;
 	// write database version only after the database is successfully opened
@@ -108,3 +117,81 @@ fn open_parity_db<Block: BlockT, C: HeaderBackend<Block>>(
 	super::upgrade::update_version(path).map_err(|_| "Cannot update db version".to_string())?;
 	Ok(Arc::new(super::parity_db_adapter::DbAdapter(db)))
 }
+
+/// Column layout of the ParityDB database.
+pub(crate) fn parity_db_options(path: &Path) -> parity_db::Options {
+	use super::columns;
+
+	let mut options = parity_db::Options::with_columns(path, columns::NUM_COLUMNS as u8);
+	// Few small entries keyed by static keys, the schema cache growing with each upgrade.
+	let meta = &mut options.columns[columns::META as usize];
+	meta.compression = parity_db::CompressionType::Lz4;
+	// Ethereum block hash to substrate block hashes, iterated by the upgrades.
+	let block_mapping = &mut options.columns[columns::BLOCK_MAPPING as usize];
+	block_mapping.btree_index = true;
+	// Ethereum transaction hash to transaction metadata, mostly made of hashes which do not
+	// compress.
+	let transaction_mapping = &mut options.columns[columns::TRANSACTION_MAPPING as usize];
+	transaction_mapping.uniform = true;
+	// Substrate block hash to sync flag.
+	let synced_mapping = &mut options.columns[columns::SYNCED_MAPPING as usize];
+	synced_mapping.uniform = true;
+	// Entries are overwritten and removed, never reference counted.
+	for column in options.columns.iter_mut() {
+		column.ref_counted = false;
+	}
+	options
+}
+
+#[cfg(test)]
+mod tests {
+	use tempfile::tempdir;
+	// Substrate
+	use sp_runtime::{
+		generic::{Block, Header},
+		traits::BlakeTwo256,
+	};
+	use substrate_test_runtime_client::{
+		prelude::*, DefaultTestClientBuilderExt, TestClientBuilder,
+	};
+
+	use super::*;
+	use crate::kv::columns;
+
+	type OpaqueBlock =
+		Block<Header<u64, BlakeTwo256>, substrate_test_runtime_client::runtime::Extrinsic>;
+
+	#[test]
+	fn parity_db_new_database_uses_column_layout() {
+		let tmp = tempdir().expect("create a temporary directory");
+		let options = parity_db_options(tmp.path());
+		parity_db::Db::open_or_create(&options).expect("database to be created");
+
+		let metadata = options
+			.load_metadata()
+			.expect("metadata to be readable")
+			.expect("metadata to exist");
+		assert_eq!(metadata.columns, options.columns);
+	}
+
+	#[test]
+	fn parity_db_legacy_column_layout_is_kept() {
+		let tmp = tempdir().expect("create a temporary directory");
+		let mut legacy = parity_db::Options::with_columns(tmp.path(), columns::NUM_COLUMNS as u8);
+		legacy.columns[columns::BLOCK_MAPPING as usize].btree_index = true;
+		drop(parity_db::Db::open_or_create(&legacy).expect("legacy database to be created"));
+
+		let (client, _) = TestClientBuilder::new()
+			.build_with_native_executor::<substrate_test_runtime_client::runtime::RuntimeApi, _>(
+			None,
+		);
+		open_parity_db::<OpaqueBlock, _>(
+			Arc::new(client),
+			tmp.path(),
+			&DatabaseSource::ParityDb {
+				path: tmp.path().to_owned(),
+			},
+		)
+		.expect("legacy database to be opened");
+	}
+}
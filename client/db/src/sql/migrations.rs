@@ -0,0 +1,240 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Versioned schema migrations of the SQL backend.
+
+use sqlx::{sqlite::SqlitePool, Error, Row};
+
+/// A schema migration, applied once in a single db transaction.
+pub(crate) struct Migration {
+	/// Version of the schema after applying the migration.
+	pub version: u32,
+	/// Short description of the migration.
+	pub description: &'static str,
+	/// The statements to execute.
+	pub sql: &'static str,
+}
+
+/// The ordered schema migrations. New migrations must be appended with the next version,
+/// applied migrations must never be modified.
+pub(crate) const MIGRATIONS: &[Migration] = &[
+	Migration {
+		version: 1,
+		description: "create tables",
+		sql: "CREATE TABLE IF NOT EXISTS logs (
+			id INTEGER PRIMARY KEY,
+			address BLOB NOT NULL,
+			topic_1 BLOB,
+			topic_2 BLOB,
+			topic_3 BLOB,
+			topic_4 BLOB,
+			log_index INTEGER NOT NULL,
+			transaction_index INTEGER NOT NULL,
+			substrate_block_hash BLOB NOT NULL,
+			UNIQUE (
+				log_index,
+				transaction_index,
+				substrate_block_hash
+			)
+		);
+		CREATE TABLE IF NOT EXISTS sync_status (
+			id INTEGER PRIMARY KEY,
+			substrate_block_hash BLOB NOT NULL,
+			status INTEGER DEFAULT 0 NOT NULL,
+			UNIQUE (
+				substrate_block_hash
+			)
+		);
+		CREATE TABLE IF NOT EXISTS blocks (
+			id INTEGER PRIMARY KEY,
+			block_number INTEGER NOT NULL,
+			ethereum_block_hash BLOB NOT NULL,
+			substrate_block_hash BLOB NOT NULL,
+			ethereum_storage_schema BLOB NOT NULL,
+			is_canon INTEGER NOT NULL,
+			UNIQUE (
+				ethereum_block_hash,
+				substrate_block_hash
+			)
+		);
+		CREATE TABLE IF NOT EXISTS transactions (
+			id INTEGER PRIMARY KEY,
+			ethereum_transaction_hash BLOB NOT NULL,
+			substrate_block_hash BLOB NOT NULL,
+			ethereum_block_hash BLOB NOT NULL,
+			ethereum_transaction_index INTEGER NOT NULL,
+			UNIQUE (
+				ethereum_transaction_hash,
+				substrate_block_hash
+			)
+		);",
+	},
+	Migration {
+		version: 2,
+		description: "create indexes",
+		sql: "CREATE INDEX IF NOT EXISTS logs_main_idx ON logs (
+			address,
+			topic_1,
+			topic_2,
+			topic_3,
+			topic_4
+		);
+		CREATE INDEX IF NOT EXISTS logs_substrate_index ON logs (
+			substrate_block_hash
+		);
+		CREATE INDEX IF NOT EXISTS blocks_number_index ON blocks (
+			block_number
+		);
+		CREATE INDEX IF NOT EXISTS blocks_substrate_index ON blocks (
+			substrate_block_hash
+		);
+		CREATE INDEX IF NOT EXISTS eth_block_hash_idx ON blocks (
+			ethereum_block_hash
+		);
+		CREATE INDEX IF NOT EXISTS eth_tx_hash_idx ON transactions (
+			ethereum_transaction_hash
+		);
+		CREATE INDEX IF NOT EXISTS eth_tx_hash_2_idx ON transactions (
+			ethereum_block_hash,
+			ethereum_transaction_index
+		);",
+	},
+	Migration {
+		version: 3,
+		description: "create event signatures table",
+		sql: "CREATE TABLE IF NOT EXISTS event_signatures (
+			topic BLOB PRIMARY KEY,
+			signature TEXT NOT NULL
+		);",
+	},
+	Migration {
+		version: 4,
+		description: "create backfill ranges table",
+		sql: "CREATE TABLE IF NOT EXISTS backfill_ranges (
+			id INTEGER PRIMARY KEY,
+			start_block INTEGER NOT NULL,
+			end_block INTEGER NOT NULL,
+			status INTEGER DEFAULT 0 NOT NULL,
+			UNIQUE (
+				start_block
+			)
+		);",
+	},
+	Migration {
+		version: 5,
+		description: "add receipt columns to transactions",
+		sql: "ALTER TABLE transactions ADD COLUMN status INTEGER;
+		ALTER TABLE transactions ADD COLUMN cumulative_gas_used INTEGER;
+		ALTER TABLE transactions ADD COLUMN gas_used INTEGER;
+		ALTER TABLE transactions ADD COLUMN effective_gas_price BLOB;
+		ALTER TABLE transactions ADD COLUMN contract_address BLOB;",
+	},
+	Migration {
+		version: 6,
+		description: "add logs bloom column to blocks",
+		sql: "ALTER TABLE blocks ADD COLUMN logs_bloom BLOB;",
+	},
+	Migration {
+		version: 7,
+		description: "add sender, recipient and value columns to transactions",
+		sql: "ALTER TABLE transactions ADD COLUMN from_address BLOB;
+		ALTER TABLE transactions ADD COLUMN to_address BLOB;
+		ALTER TABLE transactions ADD COLUMN value BLOB;
+		CREATE INDEX IF NOT EXISTS transaction_from_address_idx ON transactions (
+			from_address
+		);
+		CREATE INDEX IF NOT EXISTS transaction_to_address_idx ON transactions (
+			to_address
+		);",
+	},
+	Migration {
+		version: 8,
+		description: "index transactions by created contract address",
+		sql: "CREATE INDEX IF NOT EXISTS transaction_contract_address_idx ON transactions (
+			contract_address
+		);",
+	},
+	Migration {
+		version: 9,
+		description: "create history horizon table",
+		sql: "CREATE TABLE IF NOT EXISTS history_horizon (
+			id INTEGER PRIMARY KEY CHECK (id = 0),
+			block_number INTEGER NOT NULL
+		);",
+	},
+	Migration {
+		version: 10,
+		description: "create fee history table",
+		sql: "CREATE TABLE IF NOT EXISTS fee_history (
+			block_number INTEGER PRIMARY KEY,
+			base_fee INTEGER NOT NULL,
+			gas_used_ratio REAL NOT NULL,
+			rewards BLOB NOT NULL
+		);",
+	},
+];
+
+/// Returns the current schema version, `0` for a new database.
+pub(crate) async fn schema_version(pool: &SqlitePool) -> Result<u32, Error> {
+	let version: Option<i64> = sqlx::query("SELECT MAX(version) FROM migrations")
+		.fetch_one(pool)
+		.await?
+		.get(0);
+	Ok(version.unwrap_or_default() as u32)
+}
+
+/// Applies the pending migrations in order.
+///
+/// Databases created before the migrations were introduced are at version `0`, the first
+/// migrations only create the missing tables and indexes so they are safe to apply on them.
+pub(crate) async fn migrate(pool: &SqlitePool) -> Result<(), Error> {
+	sqlx::query(
+		"CREATE TABLE IF NOT EXISTS migrations (
+			version INTEGER PRIMARY KEY,
+			description TEXT NOT NULL,
+			applied_at INTEGER NOT NULL
+		)",
+	)
+	.execute(pool)
+	.await?;
+
+	let current_version = schema_version(pool).await?;
+	for migration in MIGRATIONS
+		.iter()
+		.filter(|migration| migration.version > current_version)
+	{
+		log::info!(
+			target: "frontier-sql",
+			"📑 Applying migration {}: {}",
+			migration.version,
+			migration.description,
+		);
+		let mut tx = pool.begin().await?;
+		sqlx::query(migration.sql).execute(&mut *tx).await?;
+		sqlx::query(
+			"INSERT INTO migrations(version, description, applied_at)
+			VALUES (?, ?, strftime('%s', 'now'))",
+		)
+		.bind(migration.version)
+		.bind(migration.description)
+		.execute(&mut *tx)
+		.await?;
+		tx.commit().await?;
+	}
+	Ok(())
+}
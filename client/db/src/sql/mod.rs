@@ -16,36 +16,73 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use std::{cmp::Ordering, collections::HashSet, num::NonZeroU32, str::FromStr, sync::Arc};
+use std::{
+	cmp::Ordering,
+	collections::{HashSet, VecDeque},
+	num::NonZeroU32,
+	str::FromStr,
+	sync::{
+		atomic::{AtomicU32, AtomicU64, Ordering as AtomicOrdering},
+		Arc,
+	},
+	time::{Duration, Instant},
+};
 
 use futures::TryStreamExt;
+use parking_lot::Mutex;
 use scale_codec::{Decode, Encode};
+pub use sqlx::sqlite::{SqliteJournalMode, SqliteSynchronous};
 use sqlx::{
 	query::Query,
 	sqlite::{
-		SqliteArguments, SqliteConnectOptions, SqlitePool, SqlitePoolOptions, SqliteQueryResult,
+		SqliteArguments, SqliteAutoVacuum, SqliteConnectOptions, SqlitePool, SqlitePoolOptions,
+		SqliteQueryResult, SqliteRow,
 	},
-	ConnectOptions, Error, Execute, QueryBuilder, Row, Sqlite,
+	ConnectOptions, Connection, Error, Execute, QueryBuilder, Row, Sqlite,
 };
 // Substrate
 use sc_client_api::backend::{Backend as BackendT, StorageProvider};
 use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
-use sp_core::{H160, H256};
+use sp_core::{H160, H256, U256};
 use sp_runtime::{
 	generic::BlockId,
 	traits::{Block as BlockT, Header as HeaderT, UniqueSaturatedInto, Zero},
 };
 // Frontier
-use fc_api::{FilteredLog, TransactionMetadata};
+use fc_api::{
+	AddressTransaction, ContractCreation, EventSignature, FeeHistoryEntry, FilteredLog,
+	LogIndexerError, TransactionMetadata, TransactionReceiptMetadata,
+};
 use fc_storage::{StorageOverride, StorageQuerier};
 use fp_consensus::{FindLogError, Hashes, Log as ConsensusLog, PostLog, PreLog};
 use fp_rpc::EthereumRuntimeRPCApi;
 use fp_storage::EthereumStorageSchema;
 
+mod migrations;
+
 /// Maximum number to topics allowed to be filtered upon
 const MAX_TOPIC_COUNT: u16 = 4;
 
+/// Maximum number of blocks passing the bloom pre-check for the log query to be restricted to
+/// them, above it the range is scanned as is.
+const MAX_BLOOM_CANDIDATES: usize = 1000;
+
+/// Size in bytes of an Ethereum logs bloom.
+const BLOOM_SIZE: usize = 256;
+
+/// Default number of logs inserted by a single statement.
+const DEFAULT_LOG_INSERT_CHUNK_SIZE: usize = 1000;
+/// Number of bound values per inserted log.
+const LOG_INSERT_COLUMNS: usize = 8;
+/// Maximum number of bound values in a statement.
+/// https://www.sqlite.org/limits.html#max_variable_number
+const MAX_VARIABLE_NUMBER: usize = 32766;
+
+/// Maximum number of blocks of a query range indexed ahead of the others, larger ranges are
+/// left to the regular indexing.
+const MAX_PRIORITY_RANGE: u64 = 1000;
+
 /// Represents a log item.
 #[derive(Debug, Eq, PartialEq)]
 pub struct Log {
@@ -67,6 +104,20 @@ struct BlockMetadata {
 	pub post_hashes: Hashes,
 	pub schema: EthereumStorageSchema,
 	pub is_canon: i32,
+	/// The logs bloom of the Ethereum block, if available in the state.
+	pub logs_bloom: Option<Vec<u8>>,
+	/// The receipt data of the block transactions, if available in the state.
+	pub receipts: Option<Vec<TransactionReceiptMetadata>>,
+	/// The sender, recipient and value of the block transactions, if available in the state.
+	pub parties: Option<Vec<TransactionParties>>,
+}
+
+/// Represents the sender, recipient and value of a transaction.
+#[derive(Eq, PartialEq)]
+struct TransactionParties {
+	pub from: H160,
+	pub to: Option<H160>,
+	pub value: U256,
 }
 
 /// Represents the Sqlite connection options that are
@@ -77,6 +128,79 @@ pub struct SqliteBackendConfig<'a> {
 	pub create_if_missing: bool,
 	pub thread_count: u32,
 	pub cache_size: u64,
+	/// Opens the database read-only, sharing the index written by another process.
+	/// The schema is neither created nor migrated and every write fails.
+	pub read_only: bool,
+	/// How long a connection waits for a lock held by another one.
+	pub busy_timeout: Duration,
+	/// Journal mode of the database, ignored in read-only mode.
+	pub journal_mode: SqliteJournalMode,
+	/// Durability of the commits.
+	pub synchronous: SqliteSynchronous,
+	/// Maximum number of rows scanned per index by `ANALYZE`, `0` for no limit.
+	pub analysis_limit: u32,
+	/// Page size in bytes, only applied when the database is created.
+	pub page_size: u32,
+	/// Number of prepared statements kept by each connection of the pool.
+	pub statement_cache_capacity: usize,
+}
+
+impl Default for SqliteBackendConfig<'_> {
+	fn default() -> Self {
+		Self {
+			path: "",
+			create_if_missing: true,
+			thread_count: 4,
+			cache_size: 209715200,
+			read_only: false,
+			busy_timeout: Duration::from_secs(8),
+			journal_mode: SqliteJournalMode::Wal,
+			synchronous: SqliteSynchronous::Normal,
+			analysis_limit: 1000,
+			page_size: 4096,
+			statement_cache_capacity: 100,
+		}
+	}
+}
+
+/// Prepared statement cache usage of the hot queries.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct StatementCacheStats {
+	/// Executions reusing a statement prepared by the connection.
+	pub hits: u64,
+	/// Executions which had to prepare their statement.
+	pub misses: u64,
+}
+
+impl StatementCacheStats {
+	/// Share of the executions reusing a prepared statement, `0` when nothing was executed.
+	pub fn hit_rate(&self) -> f64 {
+		match self.hits + self.misses {
+			0 => 0.0,
+			total => self.hits as f64 / total as f64,
+		}
+	}
+}
+
+#[derive(Default)]
+struct StatementCacheCounters {
+	hits: AtomicU64,
+	misses: AtomicU64,
+}
+
+impl StatementCacheCounters {
+	/// Records an execution from the size of the connection statement cache before and after
+	/// it, the statement was prepared if the cache grew.
+	///
+	/// A statement prepared while the cache is full evicts another one and is counted as a
+	/// hit, the capacity is expected to exceed the number of distinct persistent statements.
+	fn record(&self, cached_before: usize, cached_after: usize) {
+		if cached_after > cached_before {
+			self.misses.fetch_add(1, AtomicOrdering::Relaxed);
+		} else {
+			self.hits.fetch_add(1, AtomicOrdering::Relaxed);
+		}
+	}
 }
 
 /// Represents the indexed status of a block and if it's canon or not.
@@ -86,6 +210,60 @@ pub struct BlockIndexedStatus {
 	pub canon: bool,
 }
 
+/// Outcome of an integrity check of the indexed data.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct IntegrityReport {
+	/// Number of blocks checked.
+	pub checked: u32,
+	/// Canonical blocks that are not indexed.
+	pub missing: Vec<u32>,
+	/// Indexed blocks whose ethereum block hash, transaction count or canon status differ
+	/// from the chain.
+	pub mismatched: Vec<u32>,
+	/// Number of missing or mismatched blocks re-queued for indexing.
+	pub repaired: u32,
+}
+
+/// A canonical log along with the metadata of its block and transaction.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExportedLog {
+	pub block_number: u32,
+	pub ethereum_block_hash: H256,
+	pub substrate_block_hash: H256,
+	/// `None` if the transaction metadata is not indexed.
+	pub transaction_hash: Option<H256>,
+	pub transaction_index: u32,
+	pub log_index: u32,
+	pub address: H160,
+	pub topics: Vec<H256>,
+}
+
+/// Indexed data of the blocks a pruning removes.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct PruneReport {
+	/// Number of indexed blocks.
+	pub blocks: u64,
+	/// Number of indexed transactions.
+	pub transactions: u64,
+	/// Number of indexed logs.
+	pub logs: u64,
+}
+
+/// Size and contents of the database.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DatabaseStats {
+	/// Size of the database in bytes, excluding the write-ahead log.
+	pub size: u64,
+	/// Number of rows of each table.
+	pub rows: Vec<(&'static str, u64)>,
+	/// Lowest and highest canonical blocks whose logs are indexed.
+	pub indexed_range: Option<(u32, u32)>,
+	/// Number of non-canonical blocks.
+	pub non_canon_blocks: u64,
+	/// Ranges of canonical blocks missing within the indexed range.
+	pub gaps: Vec<(u32, u32)>,
+}
+
 /// Represents the backend configurations.
 #[derive(Debug)]
 pub enum BackendConfig<'a> {
@@ -102,6 +280,35 @@ pub struct Backend<Block> {
 	/// The number of allowed operations for the Sqlite filter call.
 	/// A value of `0` disables the timeout.
 	num_ops_timeout: i32,
+
+	/// The maximum number of logs returned by the Sqlite filter call.
+	max_log_results: u32,
+
+	/// Block ranges targeted by queries, to be indexed ahead of the others.
+	priority_ranges: Arc<Mutex<VecDeque<(u32, u32)>>>,
+	/// Wakes the sync worker up when a priority range is requested.
+	priority_requested: Arc<tokio::sync::Notify>,
+	/// Wakes the queries up when a priority range has been indexed.
+	priority_indexed: Arc<tokio::sync::Notify>,
+	/// How long a query waits for its priority range to be indexed.
+	priority_timeout: Option<Duration>,
+
+	/// Whether the database is opened read-only.
+	read_only: bool,
+
+	/// Number of logs inserted by a single statement.
+	log_insert_chunk_size: usize,
+
+	/// Prepared statement cache usage of the hot queries.
+	statement_cache: Arc<StatementCacheCounters>,
+
+	/// Number of blocks whose logs were indexed since the last maintenance.
+	indexed_since_maintenance: Arc<AtomicU64>,
+	/// Number of blocks whose logs were indexed since the backend was created.
+	indexed_blocks: Arc<AtomicU64>,
+
+	/// First block indexed, the history below it is not served.
+	history_horizon: Arc<AtomicU32>,
 }
 
 impl<Block> Backend<Block>
@@ -113,13 +320,30 @@ where
 		config: BackendConfig<'_>,
 		pool_size: u32,
 		num_ops_timeout: Option<NonZeroU32>,
+		max_log_results: u32,
 		storage_override: Arc<dyn StorageOverride<Block>>,
 	) -> Result<Self, Error> {
+		let read_only = match &config {
+			BackendConfig::Sqlite(config) => config.read_only,
+		};
 		let any_pool = SqlitePoolOptions::new()
 			.max_connections(pool_size)
 			.connect_lazy_with(Self::connect_options(&config)?.disable_statement_logging());
-		let _ = Self::create_database_if_not_exists(&any_pool).await?;
-		let _ = Self::create_indexes_if_not_exist(&any_pool).await?;
+		if read_only {
+			// The schema is maintained by the indexing process.
+			let version = migrations::schema_version(&any_pool).await?;
+			log::info!(target: "frontier-sql", "📑 Opened read-only database at schema version {version}");
+		} else {
+			migrations::migrate(&any_pool).await?;
+		}
+		// Databases migrated by an older indexing process have no horizon.
+		let history_horizon: Option<i64> =
+			sqlx::query("SELECT block_number FROM history_horizon WHERE id = 0")
+				.fetch_optional(&any_pool)
+				.await
+				.ok()
+				.flatten()
+				.map(|row| row.get(0));
 		Ok(Self {
 			pool: any_pool,
 			storage_override,
@@ -128,30 +352,105 @@ where
 				.unwrap_or(0)
 				.try_into()
 				.unwrap_or(i32::MAX),
+			max_log_results,
+			priority_ranges: Default::default(),
+			priority_requested: Default::default(),
+			priority_indexed: Default::default(),
+			priority_timeout: None,
+			read_only,
+			log_insert_chunk_size: DEFAULT_LOG_INSERT_CHUNK_SIZE,
+			statement_cache: Default::default(),
+			indexed_since_maintenance: Default::default(),
+			indexed_blocks: Default::default(),
+			history_horizon: Arc::new(AtomicU32::new(history_horizon.unwrap_or_default() as u32)),
 		})
 	}
 
+	/// Prepared statement cache usage of the hot queries (`block_hash`,
+	/// `transaction_metadata` and the log inserts) since the backend was created.
+	pub fn statement_cache_stats(&self) -> StatementCacheStats {
+		StatementCacheStats {
+			hits: self.statement_cache.hits.load(AtomicOrdering::Relaxed),
+			misses: self.statement_cache.misses.load(AtomicOrdering::Relaxed),
+		}
+	}
+
+	/// Sets the number of logs inserted by a single statement when indexing a block, capped
+	/// by the maximum number of variables of a statement.
+	pub fn with_log_insert_chunk_size(mut self, chunk_size: usize) -> Self {
+		self.log_insert_chunk_size = chunk_size.clamp(1, MAX_VARIABLE_NUMBER / LOG_INSERT_COLUMNS);
+		self
+	}
+
+	/// Whether the database is opened read-only, in which case it must be indexed by another
+	/// process.
+	pub fn is_read_only(&self) -> bool {
+		self.read_only
+	}
+
+	/// First block indexed by the backend, the history below it is not served. A read-only
+	/// backend reads the horizon recorded by the indexing process when it is created.
+	pub fn history_horizon(&self) -> u32 {
+		self.history_horizon.load(AtomicOrdering::Relaxed)
+	}
+
+	/// Records the first block to index. The blocks below it are neither indexed nor
+	/// reported as missing.
+	pub async fn set_history_horizon(&self, block_number: u32) -> Result<(), Error> {
+		sqlx::query("INSERT OR REPLACE INTO history_horizon(id, block_number) VALUES (0, ?)")
+			.bind(block_number as i64)
+			.execute(self.pool())
+			.await?;
+		self.history_horizon
+			.store(block_number, AtomicOrdering::Relaxed);
+		Ok(())
+	}
+
+	/// Makes the log queries targeting blocks which are not indexed yet wait up to `timeout`
+	/// for the sync worker to index them.
+	pub fn with_priority_indexing_timeout(mut self, timeout: Duration) -> Self {
+		self.priority_timeout = Some(timeout);
+		self
+	}
+
 	fn connect_options(config: &BackendConfig) -> Result<SqliteConnectOptions, Error> {
 		match config {
 			BackendConfig::Sqlite(config) => {
 				log::info!(target: "frontier-sql", "📑 Connection configuration: {config:?}");
-				let config = sqlx::sqlite::SqliteConnectOptions::from_str(config.path)?
-					.create_if_missing(config.create_if_missing)
+				let options = sqlx::sqlite::SqliteConnectOptions::from_str(config.path)?;
+				// https://www.sqlite.org/c3ref/open.html
+				let options = if config.read_only {
+					options.read_only(true)
+				} else {
+					options.create_if_missing(config.create_if_missing)
+				};
+				let options = options
+					// https://www.sqlite.org/pragma.html#pragma_page_size
+					.page_size(config.page_size)
 					// https://www.sqlite.org/pragma.html#pragma_busy_timeout
-					.busy_timeout(std::time::Duration::from_secs(8))
+					.busy_timeout(config.busy_timeout)
 					// 200MB, https://www.sqlite.org/pragma.html#pragma_cache_size
 					.pragma("cache_size", format!("-{}", config.cache_size))
 					// https://www.sqlite.org/pragma.html#pragma_analysis_limit
-					.pragma("analysis_limit", "1000")
+					.pragma("analysis_limit", config.analysis_limit.to_string())
 					// https://www.sqlite.org/pragma.html#pragma_threads
 					.pragma("threads", config.thread_count.to_string())
 					// https://www.sqlite.org/pragma.html#pragma_threads
 					.pragma("temp_store", "memory")
-					// https://www.sqlite.org/wal.html
-					.journal_mode(sqlx::sqlite::SqliteJournalMode::Wal)
 					// https://www.sqlite.org/pragma.html#pragma_synchronous
-					.synchronous(sqlx::sqlite::SqliteSynchronous::Normal);
-				Ok(config)
+					.synchronous(config.synchronous)
+					.statement_cache_capacity(config.statement_cache_capacity);
+				if config.read_only {
+					// The journal and vacuum modes are persistent and set by the writer.
+					return Ok(options);
+				}
+				Ok(options
+					// https://www.sqlite.org/pragma.html#pragma_auto_vacuum
+					// Only effective for new databases, `incremental_vacuum` is a no-op on
+					// the others.
+					.auto_vacuum(SqliteAutoVacuum::Incremental)
+					// https://www.sqlite.org/pragma.html#pragma_journal_mode
+					.journal_mode(config.journal_mode))
 			}
 		}
 	}
@@ -238,6 +537,7 @@ where
 				let substrate_block_hash = substrate_genesis_hash.as_bytes();
 				let block_number = 0i32;
 				let is_canon = 1i32;
+				let logs_bloom = ethereum_block.header.logs_bloom.as_bytes().to_owned();
 
 				let _ = sqlx::query(
 					"INSERT OR IGNORE INTO blocks(
@@ -245,14 +545,16 @@ where
 						substrate_block_hash,
 						block_number,
 						ethereum_storage_schema,
-						is_canon)
-					VALUES (?, ?, ?, ?, ?)",
+						is_canon,
+						logs_bloom)
+					VALUES (?, ?, ?, ?, ?, ?)",
 				)
 				.bind(ethereum_block_hash)
 				.bind(substrate_block_hash)
 				.bind(block_number)
 				.bind(schema)
 				.bind(is_canon)
+				.bind(logs_bloom)
 				.execute(self.pool())
 				.await?;
 			}
@@ -270,17 +572,22 @@ where
 	) -> Result<BlockMetadata, Error>
 	where
 		Client: StorageProvider<Block, BE> + HeaderBackend<Block> + 'static,
+		Client: ProvideRuntimeApi<Block>,
+		Client::Api: EthereumRuntimeRPCApi<Block>,
 		BE: BackendT<Block> + 'static,
 	{
 		log::trace!(target: "frontier-sql", "🛠️  [Metadata] Retrieving digest data for block {hash:?}");
 		if let Ok(Some(header)) = client.header(hash) {
 			match fp_consensus::find_log(header.digest()) {
 				Ok(log) => {
-					let schema = StorageQuerier::new(client.clone())
+					// Prefer the storage override, which may have the schema cached.
+					let schema = storage_override
 						.storage_schema(hash)
+						.or_else(|| StorageQuerier::new(client.clone()).storage_schema(hash))
 						.unwrap_or(EthereumStorageSchema::V3);
 					let log_hashes = match log {
-						ConsensusLog::Post(PostLog::Hashes(post_hashes)) => post_hashes,
+						ConsensusLog::Post(PostLog::Hashes(post_hashes))
+						| ConsensusLog::Post(PostLog::HashesWithReceiptsRoot(post_hashes, _)) => post_hashes,
 						ConsensusLog::Post(PostLog::Block(block)) => Hashes::from_block(block),
 						ConsensusLog::Post(PostLog::BlockHash(expect_eth_block_hash)) => {
 							let ethereum_block = storage_override.current_block(hash);
@@ -325,6 +632,28 @@ where
 						}
 					};
 
+					let base_fee_hash = if header_number.is_zero() {
+						hash
+					} else {
+						*header.parent_hash()
+					};
+					let ethereum_block = storage_override.current_block(hash);
+					let logs_bloom = ethereum_block
+						.as_ref()
+						.map(|block| block.header.logs_bloom.as_bytes().to_vec());
+					let parties = ethereum_block
+						.as_ref()
+						.and_then(|block| Self::transaction_parties(hash, block, storage_override));
+					let receipts = ethereum_block.and_then(|block| {
+						Self::receipts_metadata(
+							&*client,
+							hash,
+							base_fee_hash,
+							&block,
+							storage_override,
+						)
+					});
+
 					log::trace!(
 						target: "frontier-sql",
 						"[Metadata] Prepared block metadata for #{block_number} ({hash:?}) canon={is_canon}",
@@ -335,6 +664,9 @@ where
 						post_hashes: log_hashes,
 						schema,
 						is_canon,
+						logs_bloom,
+						receipts,
+						parties,
 					})
 				}
 				Err(FindLogError::NotFound) => Err(Error::Protocol(format!(
@@ -351,6 +683,126 @@ where
 		}
 	}
 
+	/// Derive the receipt data of the block transactions, the same way the receipt RPC does.
+	///
+	/// The effective gas price is the one kept by the storage schema of the block, or else is
+	/// computed for EIP-1559 transactions from the base fee at `base_fee_hash`, the parent of
+	/// the block.
+	fn receipts_metadata<Client>(
+		client: &Client,
+		hash: H256,
+		base_fee_hash: H256,
+		block: &ethereum::BlockV2,
+		storage_override: &dyn StorageOverride<Block>,
+	) -> Option<Vec<TransactionReceiptMetadata>>
+	where
+		Client: ProvideRuntimeApi<Block>,
+		Client::Api: EthereumRuntimeRPCApi<Block>,
+	{
+		let receipts = storage_override.current_receipts(hash)?;
+		let statuses = storage_override.current_transaction_statuses(hash)?;
+		if block.transactions.len() != receipts.len() || receipts.len() != statuses.len() {
+			log::debug!(
+				target: "frontier-sql",
+				"[Metadata] Inconsistent receipts for block {hash:?}, skipping receipt data",
+			);
+			return None;
+		}
+		if block.transactions.is_empty() {
+			return Some(Vec::new());
+		}
+
+		// Pre-london frontier update stored receipts hold the gas used by each transaction,
+		// later ones the cumulative gas used in the block.
+		let is_eip1559 = storage_override.is_eip1559(hash);
+		let effective_gas_prices = match storage_override.current_effective_gas_prices(hash) {
+			Some(effective_gas_prices) if effective_gas_prices.len() == receipts.len() => {
+				effective_gas_prices
+			}
+			_ => {
+				let base_fee = client
+					.runtime_api()
+					.gas_price(base_fee_hash)
+					.unwrap_or_default();
+				block
+					.transactions
+					.iter()
+					.map(|transaction| match transaction {
+						ethereum::TransactionV2::Legacy(t) => t.gas_price,
+						ethereum::TransactionV2::EIP2930(t) => t.gas_price,
+						ethereum::TransactionV2::EIP1559(t) => base_fee
+							.checked_add(t.max_priority_fee_per_gas)
+							.unwrap_or_else(U256::max_value)
+							.min(t.max_fee_per_gas),
+					})
+					.collect()
+			}
+		};
+		let mut previous_cumulative_gas_used = U256::zero();
+		let out = receipts
+			.iter()
+			.zip(statuses.iter())
+			.zip(effective_gas_prices)
+			.map(|((receipt, status), effective_gas_price)| {
+				let data = match receipt {
+					ethereum::ReceiptV3::Legacy(d)
+					| ethereum::ReceiptV3::EIP2930(d)
+					| ethereum::ReceiptV3::EIP1559(d) => d,
+				};
+				let (cumulative_gas_used, gas_used) = if is_eip1559 {
+					(
+						data.used_gas,
+						data.used_gas.saturating_sub(previous_cumulative_gas_used),
+					)
+				} else {
+					(
+						previous_cumulative_gas_used.saturating_add(data.used_gas),
+						data.used_gas,
+					)
+				};
+				previous_cumulative_gas_used = cumulative_gas_used;
+				TransactionReceiptMetadata {
+					status: data.status_code,
+					cumulative_gas_used,
+					gas_used,
+					effective_gas_price,
+					contract_address: status.contract_address,
+				}
+			})
+			.collect();
+		Some(out)
+	}
+
+	/// Derive the sender, recipient and value of the block transactions from their statuses.
+	fn transaction_parties(
+		hash: H256,
+		block: &ethereum::BlockV2,
+		storage_override: &dyn StorageOverride<Block>,
+	) -> Option<Vec<TransactionParties>> {
+		let statuses = storage_override.current_transaction_statuses(hash)?;
+		if block.transactions.len() != statuses.len() {
+			return None;
+		}
+		let out = block
+			.transactions
+			.iter()
+			.zip(statuses.iter())
+			.map(|(transaction, status)| {
+				let value = match transaction {
+					ethereum::TransactionV2::Legacy(t) => t.value,
+					ethereum::TransactionV2::EIP2930(t) => t.value,
+					ethereum::TransactionV2::EIP1559(t) => t.value,
+				};
+				TransactionParties {
+					from: status.from,
+					to: status.to,
+					value,
+				}
+			})
+			.collect();
+		Some(out)
+	}
+
 	/// Insert the block metadata for the provided block hashes.
 	pub async fn insert_block_metadata<Client, BE>(
 		&self,
@@ -359,6 +811,8 @@ where
 	) -> Result<(), Error>
 	where
 		Client: StorageProvider<Block, BE> + HeaderBackend<Block> + 'static,
+		Client: ProvideRuntimeApi<Block>,
+		Client::Api: EthereumRuntimeRPCApi<Block>,
 		BE: BackendT<Block> + 'static,
 	{
 		// Spawn a blocking task to get block metadata from substrate backend.
@@ -381,6 +835,9 @@ where
 		let schema = metadata.schema.encode();
 		let block_number = metadata.block_number;
 		let is_canon = metadata.is_canon;
+		let logs_bloom = metadata.logs_bloom;
+		let receipts = metadata.receipts.unwrap_or_default();
+		let parties = metadata.parties.unwrap_or_default();
 
 		let _ = sqlx::query(
 			"INSERT OR IGNORE INTO blocks(
@@ -388,14 +845,16 @@ where
 					substrate_block_hash,
 					block_number,
 					ethereum_storage_schema,
-					is_canon)
-				VALUES (?, ?, ?, ?, ?)",
+					is_canon,
+					logs_bloom)
+				VALUES (?, ?, ?, ?, ?, ?)",
 		)
 		.bind(ethereum_block_hash)
 		.bind(substrate_block_hash)
 		.bind(block_number)
 		.bind(schema)
 		.bind(is_canon)
+		.bind(logs_bloom)
 		.execute(&mut *tx)
 		.await?;
 		for (i, &transaction_hash) in post_hashes.transaction_hashes.iter().enumerate() {
@@ -405,18 +864,36 @@ where
 				target: "frontier-sql",
 				"[Metadata] Inserting TX for block #{block_number} - {transaction_hash:?} index {ethereum_transaction_index}",
 			);
+			let receipt = receipts.get(i);
+			let transaction_parties = parties.get(i);
 			let _ = sqlx::query(
 				"INSERT OR IGNORE INTO transactions(
 						ethereum_transaction_hash,
 						substrate_block_hash,
 						ethereum_block_hash,
-						ethereum_transaction_index)
-					VALUES (?, ?, ?, ?)",
+						ethereum_transaction_index,
+						status,
+						cumulative_gas_used,
+						gas_used,
+						effective_gas_price,
+						contract_address,
+						from_address,
+						to_address,
+						value)
+					VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
 			)
 			.bind(ethereum_transaction_hash)
 			.bind(substrate_block_hash)
 			.bind(ethereum_block_hash)
 			.bind(ethereum_transaction_index)
+			.bind(receipt.map(|r| r.status as i32))
+			.bind(receipt.map(|r| gas_to_i64(r.cumulative_gas_used)))
+			.bind(receipt.map(|r| gas_to_i64(r.gas_used)))
+			.bind(receipt.map(|r| u256_to_bytes(r.effective_gas_price)))
+			.bind(receipt.and_then(|r| r.contract_address.map(|a| a.as_bytes().to_owned())))
+			.bind(transaction_parties.map(|p| p.from.as_bytes().to_owned()))
+			.bind(transaction_parties.and_then(|p| p.to.map(|a| a.as_bytes().to_owned())))
+			.bind(transaction_parties.map(|p| u256_to_bytes(p.value)))
 			.execute(&mut *tx)
 			.await?;
 		}
@@ -434,7 +911,9 @@ where
 	pub async fn index_block_logs(&self, block_hash: Block::Hash) {
 		let pool = self.pool().clone();
 		let storage_override = self.storage_override.clone();
-		let _ = async {
+		let chunk_size = self.log_insert_chunk_size;
+		let statement_cache = self.statement_cache.clone();
+		let indexed = async {
 			// The overarching db transaction for the task.
 			// Due to the async nature of this task, the same work is likely to happen
 			// more than once. For example when a new batch is scheduled when the previous one
@@ -465,8 +944,8 @@ where
 					.await
 					.map_err(|_| Error::Protocol("tokio blocking task failed".to_string()))?;
 
-					for log in logs {
-						let _ = sqlx::query(
+					for chunk in logs.chunks(chunk_size) {
+						let mut builder: QueryBuilder<Sqlite> = QueryBuilder::new(
 							"INSERT OR IGNORE INTO logs(
 						address,
 						topic_1,
@@ -475,19 +954,30 @@ where
 						topic_4,
 						log_index,
 						transaction_index,
-						substrate_block_hash)
-					VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
-						)
-						.bind(log.address)
-						.bind(log.topic_1)
-						.bind(log.topic_2)
-						.bind(log.topic_3)
-						.bind(log.topic_4)
-						.bind(log.log_index)
-						.bind(log.transaction_index)
-						.bind(log.substrate_block_hash)
-						.execute(&mut *tx)
-						.await?;
+						substrate_block_hash) ",
+						);
+						builder.push_values(chunk, |mut b, log| {
+							b.push_bind(&log.address)
+								.push_bind(&log.topic_1)
+								.push_bind(&log.topic_2)
+								.push_bind(&log.topic_3)
+								.push_bind(&log.topic_4)
+								.push_bind(log.log_index)
+								.push_bind(log.transaction_index)
+								.push_bind(&log.substrate_block_hash);
+						});
+						// Only the full chunks share their statement, the last one is prepared
+						// once and not cached.
+						let persistent = chunk.len() == chunk_size;
+						let cached_before = tx.cached_statements_size();
+						builder
+							.build()
+							.persistent(persistent)
+							.execute(&mut *tx)
+							.await?;
+						if persistent {
+							statement_cache.record(cached_before, tx.cached_statements_size());
+						}
 					}
 					Ok(tx.commit().await?)
 				}
@@ -497,7 +987,13 @@ where
 		.await
 		.map_err(|e| {
 			log::error!(target: "frontier-sql", "{e}");
-		});
+		})
+		.is_ok();
+		if indexed {
+			self.indexed_since_maintenance
+				.fetch_add(1, AtomicOrdering::Relaxed);
+			self.indexed_blocks.fetch_add(1, AtomicOrdering::Relaxed);
+		}
 		// https://www.sqlite.org/pragma.html#pragma_optimize
 		let _ = sqlx::query("PRAGMA optimize").execute(&pool).await;
 		log::debug!(target: "frontier-sql", "Batch committed");
@@ -591,13 +1087,14 @@ where
 	}
 
 	/// Retrieves the first missing canonical block number in decreasing order that hasn't been indexed yet.
+	/// Blocks below the history horizon are never reported as missing.
 	/// If no unindexed block exists or the table or the rows do not exist, then the function
 	/// returns `None`.
 	pub async fn get_first_missing_canon_block(&self) -> Option<u32> {
 		match sqlx::query(
 			"SELECT b1.block_number-1
 			FROM blocks as b1
-			WHERE b1.block_number > 0 AND b1.is_canon=1 AND NOT EXISTS (
+			WHERE b1.block_number > ? AND b1.is_canon=1 AND NOT EXISTS (
 				SELECT 1 FROM blocks AS b2
 				WHERE b2.block_number = b1.block_number-1
 				AND b1.is_canon=1
@@ -605,6 +1102,7 @@ where
 			)
 			ORDER BY block_number LIMIT 1",
 		)
+		.bind(self.history_horizon() as i64)
 		.fetch_optional(self.pool())
 		.await
 		{
@@ -667,556 +1165,1492 @@ where
 		))
 	}
 
-	/// Create the Sqlite database if it does not already exist.
-	async fn create_database_if_not_exists(pool: &SqlitePool) -> Result<SqliteQueryResult, Error> {
-		sqlx::query(
-			"BEGIN;
-			CREATE TABLE IF NOT EXISTS logs (
-				id INTEGER PRIMARY KEY,
-				address BLOB NOT NULL,
-				topic_1 BLOB,
-				topic_2 BLOB,
-				topic_3 BLOB,
-				topic_4 BLOB,
-				log_index INTEGER NOT NULL,
-				transaction_index INTEGER NOT NULL,
-				substrate_block_hash BLOB NOT NULL,
-				UNIQUE (
-					log_index,
-					transaction_index,
-					substrate_block_hash
-				)
-			);
-			CREATE TABLE IF NOT EXISTS sync_status (
-				id INTEGER PRIMARY KEY,
-				substrate_block_hash BLOB NOT NULL,
-				status INTEGER DEFAULT 0 NOT NULL,
-				UNIQUE (
-					substrate_block_hash
-				)
-			);
-			CREATE TABLE IF NOT EXISTS blocks (
-				id INTEGER PRIMARY KEY,
-				block_number INTEGER NOT NULL,
-				ethereum_block_hash BLOB NOT NULL,
-				substrate_block_hash BLOB NOT NULL,
-				ethereum_storage_schema BLOB NOT NULL,
-				is_canon INTEGER NOT NULL,
-				UNIQUE (
-					ethereum_block_hash,
-					substrate_block_hash
-				)
-			);
-			CREATE TABLE IF NOT EXISTS transactions (
-				id INTEGER PRIMARY KEY,
-				ethereum_transaction_hash BLOB NOT NULL,
-				substrate_block_hash BLOB NOT NULL,
-				ethereum_block_hash BLOB NOT NULL,
-				ethereum_transaction_index INTEGER NOT NULL,
-				UNIQUE (
-					ethereum_transaction_hash,
-					substrate_block_hash
-				)
-			);
-			COMMIT;",
-		)
-		.execute(pool)
-		.await
+	/// Number of blocks whose logs were indexed since the last maintenance.
+	pub fn indexed_blocks_since_maintenance(&self) -> u64 {
+		self.indexed_since_maintenance.load(AtomicOrdering::Relaxed)
 	}
 
-	/// Create the Sqlite database indices if it does not already exist.
-	async fn create_indexes_if_not_exist(pool: &SqlitePool) -> Result<SqliteQueryResult, Error> {
-		sqlx::query(
-			"BEGIN;
-			CREATE INDEX IF NOT EXISTS logs_main_idx ON logs (
-				address,
-				topic_1,
-				topic_2,
-				topic_3,
-				topic_4
-			);
-			CREATE INDEX IF NOT EXISTS logs_substrate_index ON logs (
-				substrate_block_hash
-			);
-			CREATE INDEX IF NOT EXISTS blocks_number_index ON blocks (
-				block_number
-			);
-			CREATE INDEX IF NOT EXISTS blocks_substrate_index ON blocks (
-				substrate_block_hash
-			);
-			CREATE INDEX IF NOT EXISTS eth_block_hash_idx ON blocks (
-				ethereum_block_hash
-			);
-			CREATE INDEX IF NOT EXISTS eth_tx_hash_idx ON transactions (
-				ethereum_transaction_hash
-			);
-			CREATE INDEX IF NOT EXISTS eth_tx_hash_2_idx ON transactions (
-				ethereum_block_hash,
-				ethereum_transaction_index
-			);
-			COMMIT;",
-		)
-		.execute(pool)
-		.await
+	/// Number of blocks whose logs were indexed since the backend was created.
+	pub fn indexed_blocks(&self) -> u64 {
+		self.indexed_blocks.load(AtomicOrdering::Relaxed)
 	}
-}
 
-#[async_trait::async_trait]
-impl<Block: BlockT<Hash = H256>> fc_api::Backend<Block> for Backend<Block> {
-	async fn block_hash(
-		&self,
-		ethereum_block_hash: &H256,
-	) -> Result<Option<Vec<Block::Hash>>, String> {
-		let ethereum_block_hash = ethereum_block_hash.as_bytes();
-		let res =
-			sqlx::query("SELECT substrate_block_hash FROM blocks WHERE ethereum_block_hash = ?")
-				.bind(ethereum_block_hash)
-				.fetch_all(&self.pool)
-				.await
-				.ok()
-				.map(|rows| {
-					rows.iter()
-						.map(|row| {
-							H256::from_slice(&row.try_get::<Vec<u8>, _>(0).unwrap_or_default()[..])
-						})
-						.collect()
-				});
-		Ok(res)
+	/// Reclaims the free pages, refreshes the statistics of the query planner and
+	/// checkpoints the write-ahead log.
+	pub async fn run_maintenance(&self) -> Result<(), Error> {
+		let started = Instant::now();
+		let indexed = self
+			.indexed_since_maintenance
+			.swap(0, AtomicOrdering::Relaxed);
+		let mut conn = self.pool.acquire().await?;
+		// https://www.sqlite.org/pragma.html#pragma_incremental_vacuum
+		sqlx::query("PRAGMA incremental_vacuum")
+			.execute(&mut *conn)
+			.await?;
+		// https://www.sqlite.org/lang_analyze.html
+		sqlx::query("ANALYZE").execute(&mut *conn).await?;
+		// https://www.sqlite.org/pragma.html#pragma_wal_checkpoint
+		sqlx::query("PRAGMA wal_checkpoint(TRUNCATE)")
+			.execute(&mut *conn)
+			.await?;
+		log::debug!(
+			target: "frontier-sql",
+			"🧹 Maintenance done in {:?}, {indexed} blocks indexed since the last one",
+			started.elapsed(),
+		);
+		Ok(())
 	}
 
-	async fn transaction_metadata(
-		&self,
-		ethereum_transaction_hash: &H256,
-	) -> Result<Vec<TransactionMetadata<Block>>, String> {
-		let ethereum_transaction_hash = ethereum_transaction_hash.as_bytes();
-		let out = sqlx::query(
-			"SELECT
-				substrate_block_hash, ethereum_block_hash, ethereum_transaction_index
-			FROM transactions WHERE ethereum_transaction_hash = ?",
+	/// Collects the size and contents of the database, reporting at most `max_gaps` ranges
+	/// of blocks missing within the indexed range.
+	pub async fn stats(&self, max_gaps: u32) -> Result<DatabaseStats, Error> {
+		let size = sqlx::query(
+			"SELECT page_count * page_size FROM pragma_page_count(), pragma_page_size()",
 		)
-		.bind(ethereum_transaction_hash)
-		.fetch_all(&self.pool)
+		.fetch_one(self.pool())
+		.await?
+		.get::<i64, _>(0) as u64;
+
+		let mut rows = Vec::new();
+		for table in [
+			"blocks",
+			"transactions",
+			"logs",
+			"sync_status",
+			"event_signatures",
+			"backfill_ranges",
+		] {
+			let count = sqlx::query(&format!("SELECT COUNT(*) FROM {table}"))
+				.fetch_one(self.pool())
+				.await?
+				.get::<i64, _>(0);
+			rows.push((table, count as u64));
+		}
+
+		let indexed_range = sqlx::query(
+			"SELECT MIN(b.block_number), MAX(b.block_number) FROM blocks AS b
+			INNER JOIN sync_status AS s
+			ON s.substrate_block_hash = b.substrate_block_hash
+			WHERE b.is_canon = 1 AND s.status = 1",
+		)
+		.fetch_one(self.pool())
 		.await
-		.unwrap_or_default()
+		.map(
+			|row| match (row.get::<Option<i64>, _>(0), row.get::<Option<i64>, _>(1)) {
+				(Some(min), Some(max)) => Some((min as u32, max as u32)),
+				_ => None,
+			},
+		)?;
+
+		let non_canon_blocks = sqlx::query("SELECT COUNT(*) FROM blocks WHERE is_canon = 0")
+			.fetch_one(self.pool())
+			.await?
+			.get::<i64, _>(0) as u64;
+
+		let gaps = sqlx::query(
+			"SELECT previous + 1, block_number - 1 FROM (
+				SELECT b.block_number,
+				LAG(b.block_number) OVER (ORDER BY b.block_number) AS previous
+				FROM blocks AS b
+				INNER JOIN sync_status AS s
+				ON s.substrate_block_hash = b.substrate_block_hash
+				WHERE b.is_canon = 1 AND s.status = 1
+			)
+			WHERE block_number > previous + 1
+			LIMIT ?",
+		)
+		.bind(max_gaps as i64)
+		.fetch_all(self.pool())
+		.await?
 		.iter()
-		.map(|row| {
-			let substrate_block_hash =
-				H256::from_slice(&row.try_get::<Vec<u8>, _>(0).unwrap_or_default()[..]);
-			let ethereum_block_hash =
-				H256::from_slice(&row.try_get::<Vec<u8>, _>(1).unwrap_or_default()[..]);
-			let ethereum_transaction_index = row.try_get::<i32, _>(2).unwrap_or_default() as u32;
-			TransactionMetadata {
-				substrate_block_hash,
-				ethereum_block_hash,
-				ethereum_index: ethereum_transaction_index,
-			}
-		})
+		.map(|row| (row.get::<i64, _>(0) as u32, row.get::<i64, _>(1) as u32))
 		.collect();
 
-		Ok(out)
+		Ok(DatabaseStats {
+			size,
+			rows,
+			indexed_range,
+			non_canon_blocks,
+			gaps,
+		})
 	}
 
-	fn log_indexer(&self) -> &dyn fc_api::LogIndexerBackend<Block> {
-		self
+	/// Fetches the rows of a hot query, recording whether its statement was already prepared
+	/// by the pooled connection running it.
+	async fn fetch_all_cached<'q>(
+		&self,
+		query: Query<'q, Sqlite, SqliteArguments<'q>>,
+	) -> Result<Vec<SqliteRow>, Error> {
+		let mut conn = self.pool.acquire().await?;
+		let cached_before = conn.cached_statements_size();
+		let rows = query.fetch_all(&mut *conn).await?;
+		self.statement_cache
+			.record(cached_before, conn.cached_statements_size());
+		Ok(rows)
 	}
 
-	async fn latest_block_hash(&self) -> Result<Block::Hash, String> {
-		// Retrieves the block hash for the latest indexed block, maybe it's not canon.
-		sqlx::query("SELECT substrate_block_hash FROM blocks ORDER BY block_number DESC LIMIT 1")
-			.fetch_one(self.pool())
-			.await
-			.map(|row| H256::from_slice(&row.get::<Vec<u8>, _>(0)[..]))
-			.map_err(|e| format!("Failed to fetch best hash: {}", e))
+	/// Notified when a block range is requested to be indexed ahead of the others.
+	pub fn priority_requested(&self) -> Arc<tokio::sync::Notify> {
+		self.priority_requested.clone()
 	}
-}
 
-#[async_trait::async_trait]
-impl<Block: BlockT<Hash = H256>> fc_api::LogIndexerBackend<Block> for Backend<Block> {
-	fn is_indexed(&self) -> bool {
-		true
+	/// Takes the next block range requested to be indexed ahead of the others.
+	pub fn pop_priority_range(&self) -> Option<(u32, u32)> {
+		self.priority_ranges.lock().pop_front()
 	}
 
-	async fn filter_logs(
+	/// Wakes up the queries waiting for their block range to be indexed.
+	pub fn notify_priority_indexed(&self) {
+		self.priority_indexed.notify_waiters();
+	}
+
+	/// Checks whether every canonical block in `[from_block, to_block]` has its logs indexed,
+	/// or pruned.
+	async fn is_range_indexed(&self, from_block: u32, to_block: u32) -> bool {
+		sqlx::query(
+			"SELECT COUNT(DISTINCT b.block_number) FROM blocks AS b
+			INNER JOIN sync_status AS s
+			ON s.substrate_block_hash = b.substrate_block_hash
+			WHERE b.block_number BETWEEN ? AND ? AND b.is_canon = 1 AND s.status IN (1, 2)",
+		)
+		.bind(from_block as i64)
+		.bind(to_block as i64)
+		.fetch_one(self.pool())
+		.await
+		.map(|row| row.get::<i64, _>(0) > i64::from(to_block - from_block))
+		.unwrap_or(false)
+	}
+
+	/// Deletes the indexed logs and transactions of up to `batch_size` blocks with a block
+	/// number lower than `block_number`, in a single db transaction.
+	/// The block metadata is kept and the blocks are marked as pruned in `sync_status`, so
+	/// they are not picked up again by the indexer or the backfill.
+	/// Returns the number of pruned blocks.
+	pub async fn prune_blocks_before(
 		&self,
-		from_block: u64,
-		to_block: u64,
-		addresses: Vec<H160>,
-		topics: Vec<Vec<Option<H256>>>,
-	) -> Result<Vec<FilteredLog<Block>>, String> {
-		let mut unique_topics: [HashSet<H256>; 4] = [
-			HashSet::new(),
-			HashSet::new(),
-			HashSet::new(),
-			HashSet::new(),
-		];
-		for topic_combination in topics.into_iter() {
-			for (topic_index, topic) in topic_combination.into_iter().enumerate() {
-				if topic_index == MAX_TOPIC_COUNT as usize {
-					return Err("Invalid topic input. Maximum length is 4.".to_string());
-				}
+		block_number: u32,
+		batch_size: u32,
+	) -> Result<usize, Error> {
+		if block_number == 0 {
+			return Ok(0);
+		}
+		self.prune_block_range(0, block_number - 1, batch_size)
+			.await
+	}
 
-				if let Some(topic) = topic {
-					unique_topics[topic_index].insert(topic);
-				}
+	/// Fetches the canonical logs in `[from_block, to_block]` emitted by one of `addresses`,
+	/// or any address if empty, whose first topic is `topic`, or any topic if `None`.
+	/// The logs are ordered as in the chain.
+	pub async fn export_logs(
+		&self,
+		from_block: u32,
+		to_block: u32,
+		addresses: &[H160],
+		topic: Option<H256>,
+	) -> Result<Vec<ExportedLog>, Error> {
+		let mut qb = QueryBuilder::<Sqlite>::new(
+			"SELECT
+				b.block_number,
+				b.ethereum_block_hash,
+				l.substrate_block_hash,
+				t.ethereum_transaction_hash,
+				l.transaction_index,
+				l.log_index,
+				l.address,
+				l.topic_1,
+				l.topic_2,
+				l.topic_3,
+				l.topic_4
+			FROM logs AS l
+			INNER JOIN blocks AS b
+			ON b.substrate_block_hash = l.substrate_block_hash AND b.is_canon = 1
+			LEFT JOIN transactions AS t
+			ON t.substrate_block_hash = l.substrate_block_hash
+			AND t.ethereum_transaction_index = l.transaction_index
+			WHERE b.block_number BETWEEN ",
+		);
+		qb.push_bind(from_block as i64)
+			.push(" AND ")
+			.push_bind(to_block as i64);
+		if !addresses.is_empty() {
+			qb.push(" AND l.address IN (");
+			let mut qb_addr = qb.separated(", ");
+			for address in addresses {
+				qb_addr.push_bind(address.as_bytes().to_owned());
 			}
+			qb_addr.push_unseparated(")");
+		}
+		if let Some(topic) = topic {
+			qb.push(" AND l.topic_1 = ")
+				.push_bind(topic.as_bytes().to_owned());
 		}
+		qb.push(" ORDER BY b.block_number, l.transaction_index, l.log_index");
 
-		let log_key = format!("{from_block}-{to_block}-{addresses:?}-{unique_topics:?}");
-		let mut qb = QueryBuilder::new("");
-		let query = build_query(&mut qb, from_block, to_block, addresses, unique_topics);
-		let sql = query.sql();
+		let rows = qb.build().fetch_all(self.pool()).await?;
+		Ok(rows
+			.iter()
+			.map(|row| ExportedLog {
+				block_number: row.get::<i64, _>(0) as u32,
+				ethereum_block_hash: H256::from_slice(&row.get::<Vec<u8>, _>(1)[..]),
+				substrate_block_hash: H256::from_slice(&row.get::<Vec<u8>, _>(2)[..]),
+				transaction_hash: row
+					.get::<Option<Vec<u8>>, _>(3)
+					.map(|hash| H256::from_slice(&hash[..])),
+				transaction_index: row.get::<i64, _>(4) as u32,
+				log_index: row.get::<i64, _>(5) as u32,
+				address: H160::from_slice(&row.get::<Vec<u8>, _>(6)[..]),
+				topics: (7..11)
+					.map_while(|i| row.get::<Option<Vec<u8>>, _>(i))
+					.map(|topic| H256::from_slice(&topic[..]))
+					.collect(),
+			})
+			.collect())
+	}
 
-		let mut conn = self
-			.pool()
-			.acquire()
-			.await
-			.map_err(|err| format!("failed acquiring sqlite connection: {}", err))?;
-		let log_key2 = log_key.clone();
-		conn.lock_handle()
-			.await
-			.map_err(|err| format!("{:?}", err))?
-			.set_progress_handler(self.num_ops_timeout, move || {
-				log::debug!(target: "frontier-sql", "Sqlite progress_handler triggered for {log_key2}");
-				false
-			});
-		log::debug!(target: "frontier-sql", "Query: {sql:?} - {log_key}");
+	/// Counts the indexed blocks, transactions and logs [`Self::prune_blocks_before`] deletes.
+	pub async fn prunable_rows_before(&self, block_number: u32) -> Result<PruneReport, Error> {
+		let row = sqlx::query(
+			"WITH pruned AS (
+				SELECT s.substrate_block_hash FROM sync_status AS s
+				INNER JOIN blocks AS b
+				ON s.substrate_block_hash = b.substrate_block_hash
+				WHERE b.block_number < ? AND s.status = 1
+			)
+			SELECT
+				(SELECT COUNT(*) FROM pruned),
+				(SELECT COUNT(*) FROM transactions
+					WHERE substrate_block_hash IN (SELECT substrate_block_hash FROM pruned)),
+				(SELECT COUNT(*) FROM logs
+					WHERE substrate_block_hash IN (SELECT substrate_block_hash FROM pruned))",
+		)
+		.bind(block_number as i64)
+		.fetch_one(self.pool())
+		.await?;
+		Ok(PruneReport {
+			blocks: row.get::<i64, _>(0) as u64,
+			transactions: row.get::<i64, _>(1) as u64,
+			logs: row.get::<i64, _>(2) as u64,
+		})
+	}
 
-		let mut out: Vec<FilteredLog<Block>> = vec![];
-		let mut rows = query.fetch(&mut *conn);
-		let maybe_err = loop {
-			match rows.try_next().await {
-				Ok(Some(row)) => {
-					// Substrate block hash
-					let substrate_block_hash =
-						H256::from_slice(&row.try_get::<Vec<u8>, _>(0).unwrap_or_default()[..]);
-					// Ethereum block hash
-					let ethereum_block_hash =
-						H256::from_slice(&row.try_get::<Vec<u8>, _>(1).unwrap_or_default()[..]);
-					// Block number
-					let block_number = row.try_get::<i32, _>(2).unwrap_or_default() as u32;
-					// Ethereum storage schema
-					let ethereum_storage_schema: EthereumStorageSchema =
-						Decode::decode(&mut &row.try_get::<Vec<u8>, _>(3).unwrap_or_default()[..])
-							.map_err(|_| {
-								"Cannot decode EthereumStorageSchema for block".to_string()
-							})?;
-					// Transaction index
-					let transaction_index = row.try_get::<i32, _>(4).unwrap_or_default() as u32;
-					// Log index
-					let log_index = row.try_get::<i32, _>(5).unwrap_or_default() as u32;
-					out.push(FilteredLog {
-						substrate_block_hash,
-						ethereum_block_hash,
-						block_number,
-						ethereum_storage_schema,
-						transaction_index,
-						log_index,
-					});
-				}
-				Ok(None) => break None, // no more rows
-				Err(err) => break Some(err),
-			};
-		};
-		drop(rows);
-		conn.lock_handle()
-			.await
-			.map_err(|err| format!("{:?}", err))?
-			.remove_progress_handler();
+	/// Deletes the indexed logs and transactions of up to `batch_size` blocks in
+	/// `[from_block, to_block]`, in a single db transaction.
+	/// The block metadata is kept and the blocks are marked as pruned in `sync_status`, so
+	/// they are not picked up again by the indexer or the backfill.
+	/// Returns the number of pruned blocks.
+	pub async fn prune_block_range(
+		&self,
+		from_block: u32,
+		to_block: u32,
+		batch_size: u32,
+	) -> Result<usize, Error> {
+		let mut tx = self.pool().begin().await?;
 
-		if let Some(err) = maybe_err {
-			log::error!(target: "frontier-sql", "Failed to query sql db: {err:?} - {log_key}");
-			return Err("Failed to query sql db with statement".to_string());
+		let hashes: Vec<Vec<u8>> = sqlx::query(
+			"SELECT s.substrate_block_hash FROM sync_status AS s
+			INNER JOIN blocks AS b
+			ON s.substrate_block_hash = b.substrate_block_hash
+			WHERE b.block_number BETWEEN ? AND ? AND s.status = 1
+			ORDER BY b.block_number LIMIT ?",
+		)
+		.bind(from_block as i64)
+		.bind(to_block as i64)
+		.bind(batch_size as i64)
+		.fetch_all(&mut *tx)
+		.await?
+		.iter()
+		.map(|row| row.get::<Vec<u8>, _>(0))
+		.collect();
+
+		if hashes.is_empty() {
+			return Ok(0);
 		}
 
-		log::info!(target: "frontier-sql", "FILTER remove handler - {log_key}");
-		Ok(out)
+		for statement in [
+			"DELETE FROM logs",
+			"DELETE FROM transactions",
+			"UPDATE sync_status SET status = 2",
+		] {
+			let mut builder: QueryBuilder<Sqlite> =
+				QueryBuilder::new(format!("{statement} WHERE substrate_block_hash IN ("));
+			let mut pruned_hashes = builder.separated(", ");
+			for hash in hashes.iter() {
+				pruned_hashes.push_bind(hash);
+			}
+			pruned_hashes.push_unseparated(")");
+			builder.build().execute(&mut *tx).await?;
+		}
+
+		tx.commit().await?;
+		log::debug!(
+			target: "frontier-sql",
+			"Pruned {} blocks in #{from_block}..=#{to_block}",
+			hashes.len(),
+		);
+		Ok(hashes.len())
 	}
-}
 
-/// Build a SQL query to retrieve a list of logs given certain constraints.
-fn build_query<'a>(
-	qb: &'a mut QueryBuilder<Sqlite>,
-	from_block: u64,
-	to_block: u64,
-	addresses: Vec<H160>,
-	topics: [HashSet<H256>; 4],
-) -> Query<'a, Sqlite, SqliteArguments<'a>> {
-	qb.push(
-		"
-SELECT
-	l.substrate_block_hash,
-	b.ethereum_block_hash,
-	b.block_number,
-	b.ethereum_storage_schema,
-	l.transaction_index,
-	l.log_index
-FROM logs AS l
-INNER JOIN blocks AS b
-ON (b.block_number BETWEEN ",
-	);
-	qb.separated(" AND ")
-		.push_bind(from_block as i64)
-		.push_bind(to_block as i64)
-		.push_unseparated(")");
-	qb.push(" AND b.substrate_block_hash = l.substrate_block_hash")
-		.push(" AND b.is_canon = 1")
-		.push("\nWHERE 1");
+	/// Deletes up to `batch_size` non-canonical blocks with a block number lower than
+	/// `block_number`, along with their logs, transactions and sync status, in a single
+	/// db transaction. Meant to be called with the finalized block number, below which
+	/// the retracted blocks can no longer become canonical again.
+	/// The stored canon status is checked against the chain first: blocks found to be
+	/// canonical get their status fixed instead of being deleted, and blocks whose canonical
+	/// hash can't be read are kept.
+	/// Returns the number of pruned blocks.
+	pub async fn prune_non_canon_blocks_before<Client>(
+		&self,
+		client: &Client,
+		block_number: u32,
+		batch_size: u32,
+	) -> Result<usize, Error>
+	where
+		Client: HeaderBackend<Block>,
+	{
+		let mut tx = self.pool().begin().await?;
 
-	if !addresses.is_empty() {
-		qb.push(" AND l.address IN (");
-		let mut qb_addr = qb.separated(", ");
-		addresses.iter().for_each(|addr| {
-			qb_addr.push_bind(addr.as_bytes().to_owned());
-		});
-		qb_addr.push_unseparated(")");
-	}
+		let rows: Vec<(i64, Vec<u8>)> = sqlx::query(
+			"SELECT block_number, substrate_block_hash FROM blocks
+			WHERE block_number < ? AND is_canon = 0
+			ORDER BY block_number LIMIT ?",
+		)
+		.bind(block_number as i64)
+		.bind(batch_size as i64)
+		.fetch_all(&mut *tx)
+		.await?
+		.iter()
+		.map(|row| (row.get::<i64, _>(0), row.get::<Vec<u8>, _>(1)))
+		.collect();
 
-	for (i, topic_options) in topics.iter().enumerate() {
-		match topic_options.len().cmp(&1) {
-			Ordering::Greater => {
-				qb.push(format!(" AND l.topic_{} IN (", i + 1));
-				let mut qb_topic = qb.separated(", ");
-				topic_options.iter().for_each(|t| {
-					qb_topic.push_bind(t.as_bytes().to_owned());
-				});
-				qb_topic.push_unseparated(")");
+		let mut hashes = Vec::with_capacity(rows.len());
+		for (number, hash) in rows {
+			let number = number as u32;
+			match client.hash(number.unique_saturated_into()) {
+				Ok(Some(canon_hash)) if canon_hash.as_bytes() == &hash[..] => {
+					log::warn!(
+						target: "frontier-sql",
+						"Block #{number} {canon_hash:?} is canonical, fixing its stale canon status",
+					);
+					sqlx::query("UPDATE blocks SET is_canon = 1 WHERE substrate_block_hash = ?")
+						.bind(hash)
+						.execute(&mut *tx)
+						.await?;
+				}
+				Ok(_) => hashes.push(hash),
+				Err(err) => log::debug!(
+					target: "frontier-sql",
+					"Keeping non-canonical block #{number}, failed to read the canonical hash: {err:?}",
+				),
 			}
-			Ordering::Equal => {
-				qb.push(format!(" AND l.topic_{} = ", i + 1)).push_bind(
-					topic_options
-						.iter()
-						.next()
-						.expect("length is 1, must exist; qed")
-						.as_bytes()
-						.to_owned(),
-				);
+		}
+
+		if !hashes.is_empty() {
+			for table in ["logs", "transactions", "sync_status", "blocks"] {
+				let mut builder: QueryBuilder<Sqlite> = QueryBuilder::new(format!(
+					"DELETE FROM {table} WHERE substrate_block_hash IN ("
+				));
+				let mut pruned_hashes = builder.separated(", ");
+				for hash in hashes.iter() {
+					pruned_hashes.push_bind(hash);
+				}
+				pruned_hashes.push_unseparated(")");
+				builder.build().execute(&mut *tx).await?;
 			}
-			Ordering::Less => {}
 		}
+
+		tx.commit().await?;
+		if !hashes.is_empty() {
+			log::debug!(
+				target: "frontier-sql",
+				"Pruned {} non-canonical blocks below #{block_number}",
+				hashes.len(),
+			);
+		}
+		Ok(hashes.len())
 	}
 
-	qb.push(
-		"
-ORDER BY b.block_number ASC, l.transaction_index ASC, l.log_index ASC
-LIMIT 10001",
-	);
+	/// Cross-verifies the indexed data of the canonical blocks in `[from_block, to_block]`
+	/// against the chain, checking `sample` blocks evenly spread over the range, or all of
+	/// them if `None`. The ethereum block hash, transaction count and canon status of every
+	/// checked block are compared to the ones derived from its frontier digest.
+	/// When `repair` is set, the missing and mismatched blocks are indexed again, their logs
+	/// being re-queued through `sync_status`.
+	pub async fn check_integrity<Client, BE>(
+		&self,
+		client: Arc<Client>,
+		from_block: u32,
+		to_block: u32,
+		sample: Option<u32>,
+		repair: bool,
+	) -> Result<IntegrityReport, Error>
+	where
+		Client: StorageProvider<Block, BE> + HeaderBackend<Block> + 'static,
+		Client: ProvideRuntimeApi<Block>,
+		Client::Api: EthereumRuntimeRPCApi<Block>,
+		BE: BackendT<Block> + 'static,
+	{
+		let mut report = IntegrityReport::default();
+		if from_block > to_block {
+			return Ok(report);
+		}
+		let step = match sample {
+			Some(sample) if sample > 0 => ((to_block - from_block) / sample).max(1),
+			_ => 1,
+		};
 
-	qb.build()
-}
+		let mut block_number = from_block;
+		while block_number <= to_block {
+			if let Ok(Some(hash)) = client.hash(block_number.unique_saturated_into()) {
+				report.checked += 1;
+				let is_damaged = match self.check_block_integrity(client.clone(), hash).await {
+					Some(true) => false,
+					Some(false) => {
+						report.mismatched.push(block_number);
+						true
+					}
+					None => {
+						report.missing.push(block_number);
+						true
+					}
+				};
+				if is_damaged && repair {
+					self.delete_block(hash).await?;
+					match self.insert_block_metadata(client.clone(), hash).await {
+						Ok(_) => report.repaired += 1,
+						Err(err) => {
+							log::error!(target: "frontier-sql", "💔  Cannot repair block #{block_number}: {err}");
+						}
+					}
+				}
+			}
+			block_number = match block_number.checked_add(step) {
+				Some(next) => next,
+				None => break,
+			};
+		}
 
-#[cfg(test)]
-mod test {
-	use super::*;
+		log::debug!(
+			target: "frontier-sql",
+			"Integrity check of #{from_block}..=#{to_block}: {report:?}",
+		);
+		Ok(report)
+	}
 
-	use std::path::Path;
+	/// Compares the indexed data of a canonical block to its expected metadata. Returns
+	/// `None` if the block is not indexed, and whether it matches otherwise. Blocks without
+	/// a frontier digest are reported as matching.
+	async fn check_block_integrity<Client, BE>(
+		&self,
+		client: Arc<Client>,
+		hash: H256,
+	) -> Option<bool>
+	where
+		Client: StorageProvider<Block, BE> + HeaderBackend<Block> + 'static,
+		Client: ProvideRuntimeApi<Block>,
+		Client::Api: EthereumRuntimeRPCApi<Block>,
+		BE: BackendT<Block> + 'static,
+	{
+		let storage_override = self.storage_override.clone();
+		let expected = tokio::task::spawn_blocking(move || {
+			Self::insert_block_metadata_inner(client, hash, &*storage_override)
+		})
+		.await
+		.ok()?;
+		let Ok(expected) = expected else {
+			return Some(true);
+		};
 
-	use maplit::hashset;
-	use scale_codec::Encode;
-	use sqlx::{sqlite::SqliteRow, QueryBuilder, Row, SqlitePool};
-	use tempfile::tempdir;
-	// Substrate
-	use sp_core::{H160, H256};
-	use sp_runtime::{
-		generic::{Block, Header},
-		traits::BlakeTwo256,
-	};
-	use substrate_test_runtime_client::{
-		DefaultTestClientBuilderExt, TestClientBuilder, TestClientBuilderExt,
-	};
-	// Frontier
-	use fc_api::Backend as BackendT;
-	use fc_storage::SchemaV3StorageOverride;
-	use fp_storage::{EthereumStorageSchema, PALLET_ETHEREUM_SCHEMA};
+		let row = sqlx::query(
+			"SELECT b.ethereum_block_hash, b.is_canon,
+				(SELECT COUNT(1) FROM transactions AS t WHERE t.substrate_block_hash = b.substrate_block_hash),
+				s.status
+			FROM blocks AS b
+			INNER JOIN sync_status AS s
+			ON s.substrate_block_hash = b.substrate_block_hash
+			WHERE b.substrate_block_hash = ?",
+		)
+		.bind(hash.as_bytes())
+		.fetch_optional(self.pool())
+		.await
+		.ok()??;
 
-	type OpaqueBlock =
-		Block<Header<u64, BlakeTwo256>, substrate_test_runtime_client::runtime::Extrinsic>;
+		let ethereum_block_hash = row.get::<Vec<u8>, _>(0);
+		let is_canon = row.get::<i32, _>(1);
+		let transaction_count = row.get::<i64, _>(2);
+		// The transactions of pruned blocks are deleted.
+		let is_pruned = row.get::<i32, _>(3) == 2;
+		Some(
+			ethereum_block_hash == expected.post_hashes.block_hash.as_bytes()
+				&& is_canon == expected.is_canon
+				&& (is_pruned
+					|| transaction_count == expected.post_hashes.transaction_hashes.len() as i64),
+		)
+	}
 
-	struct TestFilter {
-		pub from_block: u64,
-		pub to_block: u64,
-		pub addresses: Vec<H160>,
-		pub topics: Vec<Vec<Option<H256>>>,
-		pub expected_result: Vec<FilteredLog<OpaqueBlock>>,
+	/// Deletes every indexed row of the block `hash` and indexes it again through the same
+	/// path as the sync worker, returning whether its logs are indexed.
+	pub async fn reindex_block<Client, BE>(
+		&self,
+		client: Arc<Client>,
+		hash: H256,
+	) -> Result<bool, Error>
+	where
+		Client: StorageProvider<Block, BE> + HeaderBackend<Block> + 'static,
+		Client: ProvideRuntimeApi<Block>,
+		Client::Api: EthereumRuntimeRPCApi<Block>,
+		BE: BackendT<Block> + 'static,
+	{
+		self.delete_block(hash).await?;
+		// The genesis block has no logs to index.
+		if client.info().genesis_hash == hash {
+			self.insert_genesis_block_metadata(client).await?;
+			return Ok(true);
+		}
+		self.insert_block_metadata(client, hash).await?;
+		self.index_block_logs(hash).await;
+		Ok(self.is_block_indexed(hash).await)
 	}
 
-	#[derive(Debug, Clone)]
-	struct Log {
-		block_number: u32,
-		address: H160,
-		topics: [H256; 4],
-		substrate_block_hash: H256,
-		ethereum_block_hash: H256,
-		transaction_index: u32,
-		log_index: u32,
+	/// Deletes every indexed row of a block.
+	async fn delete_block(&self, hash: H256) -> Result<(), Error> {
+		let mut tx = self.pool().begin().await?;
+		for table in ["logs", "transactions", "sync_status", "blocks"] {
+			sqlx::query(&format!(
+				"DELETE FROM {table} WHERE substrate_block_hash = ?"
+			))
+			.bind(hash.as_bytes())
+			.execute(&mut *tx)
+			.await?;
+		}
+		tx.commit().await
 	}
 
-	#[allow(unused)]
-	struct TestData {
-		backend: Backend<OpaqueBlock>,
-		alice: H160,
-		bob: H160,
-		topics_a: H256,
-		topics_b: H256,
-		topics_c: H256,
-		topics_d: H256,
-		substrate_hash_1: H256,
-		substrate_hash_2: H256,
-		substrate_hash_3: H256,
-		ethereum_hash_1: H256,
-		ethereum_hash_2: H256,
-		ethereum_hash_3: H256,
-		log_1_abcd_0_0_alice: Log,
-		log_1_dcba_1_0_alice: Log,
-		log_1_badc_2_0_alice: Log,
-		log_2_abcd_0_0_bob: Log,
-		log_2_dcba_1_0_bob: Log,
-		log_2_badc_2_0_bob: Log,
-		log_3_abcd_0_0_bob: Log,
-		log_3_dcba_1_0_bob: Log,
-		log_3_badc_2_0_bob: Log,
+	/// Splits the block range `[history_horizon, to_block]` in ranges of `range_size` blocks to be claimed by
+	/// the backfill workers. Only the blocks after the already scheduled ranges are added, and
+	/// ranges claimed by a previous run which did not complete are released.
+	pub async fn schedule_backfill(&self, to_block: u32, range_size: u32) -> Result<(), Error> {
+		let range_size = range_size.max(1);
+		let mut tx = self.pool().begin().await?;
+		sqlx::query("UPDATE backfill_ranges SET status = 0 WHERE status = 1")
+			.execute(&mut *tx)
+			.await?;
+		let scheduled_to: Option<i64> = sqlx::query("SELECT MAX(end_block) FROM backfill_ranges")
+			.fetch_one(&mut *tx)
+			.await?
+			.get(0);
+		let mut start_block = match scheduled_to {
+			Some(scheduled_to) if scheduled_to as u32 >= to_block => return tx.commit().await,
+			Some(scheduled_to) => scheduled_to as u32 + 1,
+			None => self.history_horizon(),
+		};
+		loop {
+			let end_block = start_block.saturating_add(range_size - 1).min(to_block);
+			sqlx::query(
+				"INSERT OR IGNORE INTO backfill_ranges(start_block, end_block) VALUES (?, ?)",
+			)
+			.bind(start_block as i64)
+			.bind(end_block as i64)
+			.execute(&mut *tx)
+			.await?;
+			if end_block >= to_block {
+				break;
+			}
+			start_block = end_block + 1;
+		}
+		tx.commit().await
 	}
 
-	impl From<Log> for FilteredLog<OpaqueBlock> {
-		fn from(value: Log) -> Self {
-			Self {
-				substrate_block_hash: value.substrate_block_hash,
-				ethereum_block_hash: value.ethereum_block_hash,
-				block_number: value.block_number,
-				ethereum_storage_schema: EthereumStorageSchema::V3,
-				transaction_index: value.transaction_index,
-				log_index: value.log_index,
+	/// Claims the most recent pending backfill range, returning its first and last block numbers.
+	/// A range is only ever claimed by a single worker.
+	pub async fn claim_backfill_range(&self) -> Result<Option<(u32, u32)>, Error> {
+		let row = sqlx::query(
+			"UPDATE backfill_ranges
+			SET status = 1
+			WHERE id = (
+				SELECT id FROM backfill_ranges
+				WHERE status = 0
+				ORDER BY start_block DESC LIMIT 1
+			) RETURNING start_block, end_block",
+		)
+		.fetch_optional(self.pool())
+		.await?;
+		Ok(row.map(|row| (row.get::<i64, _>(0) as u32, row.get::<i64, _>(1) as u32)))
+	}
+
+	/// Marks the backfill range starting at `start_block` as completed.
+	pub async fn complete_backfill_range(&self, start_block: u32) -> Result<(), Error> {
+		sqlx::query("UPDATE backfill_ranges SET status = 2 WHERE start_block = ?")
+			.bind(start_block as i64)
+			.execute(self.pool())
+			.await
+			.map(|_| ())
+	}
+
+	/// Registers the given event ABI fragments, keyed by their `topic0`. Registering a fragment
+	/// for an already known `topic0` replaces the previous one.
+	/// Run the bloom pre-check of a log query, returning the canon block numbers in range whose
+	/// logs bloom may match the filter.
+	///
+	/// Returns `None` when the query cannot be narrowed, either because the filter matches
+	/// everything or because too many blocks pass the pre-check.
+	async fn bloom_candidates(
+		&self,
+		from_block: u64,
+		to_block: u64,
+		addresses: &[H160],
+		topics: &[HashSet<H256>; 4],
+	) -> Result<Option<Vec<i64>>, Error> {
+		if addresses.is_empty() && topics.iter().all(|options| options.is_empty()) {
+			return Ok(None);
+		}
+
+		let mut candidates = Vec::new();
+		let mut rows = sqlx::query(
+			"SELECT block_number, logs_bloom FROM blocks
+			WHERE block_number BETWEEN ? AND ? AND is_canon = 1",
+		)
+		.bind(from_block as i64)
+		.bind(to_block as i64)
+		.fetch(self.pool());
+		while let Some(row) = rows.try_next().await? {
+			let block_number = row.try_get::<i64, _>(0)?;
+			let may_match = match row.try_get::<Option<Vec<u8>>, _>(1)? {
+				// Blocks indexed without a bloom are always scanned.
+				Some(bloom) if bloom.len() == BLOOM_SIZE => {
+					(addresses.is_empty()
+						|| addresses
+							.iter()
+							.any(|address| bloom_contains(&bloom, address.as_bytes())))
+						&& topics.iter().all(|options| {
+							options.is_empty()
+								|| options
+									.iter()
+									.any(|topic| bloom_contains(&bloom, topic.as_bytes()))
+						})
+				}
+				_ => true,
+			};
+			if may_match {
+				candidates.push(block_number);
+				if candidates.len() > MAX_BLOOM_CANDIDATES {
+					return Ok(None);
+				}
 			}
 		}
+		Ok(Some(candidates))
 	}
 
-	async fn prepare() -> TestData {
-		let tmp = tempdir().expect("create a temporary directory");
-		// Initialize storage with schema V3
-		let builder = TestClientBuilder::new().add_extra_storage(
-			PALLET_ETHEREUM_SCHEMA.to_vec(),
-			Encode::encode(&EthereumStorageSchema::V3),
-		);
-		// Client
-		let (client, _) = builder
-			.build_with_native_executor::<substrate_test_runtime_client::runtime::RuntimeApi, _>(
-				None,
-			);
-		let client = Arc::new(client);
-		// Overrides
-		let storage_override = Arc::new(SchemaV3StorageOverride::new(client.clone()));
-		// Indexer backend
-		let indexer_backend = Backend::new(
-			BackendConfig::Sqlite(SqliteBackendConfig {
-				path: Path::new("sqlite:///")
-					.join(tmp.path())
-					.join("test.db3")
-					.to_str()
-					.unwrap(),
-				create_if_missing: true,
-				cache_size: 20480,
-				thread_count: 4,
-			}),
-			1,
-			None,
-			storage_override.clone(),
+	pub async fn register_event_signatures(&self, fragments: &[String]) -> Result<(), Error> {
+		let signatures = fragments
+			.iter()
+			.map(|fragment| {
+				EventSignature::parse(fragment)
+					.map(|signature| (signature.topic(), fragment.trim().to_string()))
+					.map_err(Error::Protocol)
+			})
+			.collect::<Result<Vec<_>, Error>>()?;
+
+		let mut tx = self.pool().begin().await?;
+		for (topic, fragment) in signatures {
+			sqlx::query("INSERT OR REPLACE INTO event_signatures(topic, signature) VALUES (?, ?)")
+				.bind(topic.as_bytes())
+				.bind(fragment)
+				.execute(&mut *tx)
+				.await?;
+		}
+		tx.commit().await
+	}
+}
+
+#[async_trait::async_trait]
+impl<Block: BlockT<Hash = H256>> fc_api::Backend<Block> for Backend<Block> {
+	async fn block_hash(
+		&self,
+		ethereum_block_hash: &H256,
+	) -> Result<Option<Vec<Block::Hash>>, String> {
+		let ethereum_block_hash = ethereum_block_hash.as_bytes();
+		let res = self
+			.fetch_all_cached(
+				sqlx::query(
+					"SELECT substrate_block_hash FROM blocks WHERE ethereum_block_hash = ?",
+				)
+				.bind(ethereum_block_hash),
+			)
+			.await
+			.ok()
+			.map(|rows| {
+				rows.iter()
+					.map(|row| {
+						H256::from_slice(&row.try_get::<Vec<u8>, _>(0).unwrap_or_default()[..])
+					})
+					.collect()
+			});
+		Ok(res)
+	}
+
+	async fn transaction_metadata(
+		&self,
+		ethereum_transaction_hash: &H256,
+	) -> Result<Vec<TransactionMetadata<Block>>, String> {
+		let ethereum_transaction_hash = ethereum_transaction_hash.as_bytes();
+		let out = self
+			.fetch_all_cached(
+				sqlx::query(
+					"SELECT
+				substrate_block_hash, ethereum_block_hash, ethereum_transaction_index
+			FROM transactions WHERE ethereum_transaction_hash = ?",
+				)
+				.bind(ethereum_transaction_hash),
+			)
+			.await
+			.unwrap_or_default()
+			.iter()
+			.map(|row| {
+				let substrate_block_hash =
+					H256::from_slice(&row.try_get::<Vec<u8>, _>(0).unwrap_or_default()[..]);
+				let ethereum_block_hash =
+					H256::from_slice(&row.try_get::<Vec<u8>, _>(1).unwrap_or_default()[..]);
+				let ethereum_transaction_index =
+					row.try_get::<i32, _>(2).unwrap_or_default() as u32;
+				TransactionMetadata {
+					substrate_block_hash,
+					ethereum_block_hash,
+					ethereum_index: ethereum_transaction_index,
+				}
+			})
+			.collect();
+
+		Ok(out)
+	}
+
+	async fn transaction_receipt_metadata(
+		&self,
+		ethereum_transaction_hash: &H256,
+		substrate_block_hash: &Block::Hash,
+	) -> Result<Option<TransactionReceiptMetadata>, String> {
+		let row = sqlx::query(
+			"SELECT
+				status, cumulative_gas_used, gas_used, effective_gas_price, contract_address
+			FROM transactions
+			WHERE ethereum_transaction_hash = ? AND substrate_block_hash = ? AND status IS NOT NULL",
 		)
+		.bind(ethereum_transaction_hash.as_bytes())
+		.bind(substrate_block_hash.as_bytes())
+		.fetch_optional(&self.pool)
 		.await
-		.expect("indexer pool to be created");
-
-		// Prepare test db data
-		// Addresses
-		let alice = H160::repeat_byte(0x01);
-		let bob = H160::repeat_byte(0x02);
-		// Topics
-		let topics_a = H256::repeat_byte(0x01);
-		let topics_b = H256::repeat_byte(0x02);
-		let topics_c = H256::repeat_byte(0x03);
-		let topics_d = H256::repeat_byte(0x04);
-		// Substrate block hashes
-		let substrate_hash_1 = H256::repeat_byte(0x05);
-		let substrate_hash_2 = H256::repeat_byte(0x06);
-		let substrate_hash_3 = H256::repeat_byte(0x07);
-		// Ethereum block hashes
-		let ethereum_hash_1 = H256::repeat_byte(0x08);
-		let ethereum_hash_2 = H256::repeat_byte(0x09);
-		let ethereum_hash_3 = H256::repeat_byte(0x0a);
-		// Ethereum storage schema
-		let ethereum_storage_schema = EthereumStorageSchema::V3;
+		.map_err(|err| format!("{:?}", err))?;
 
-		let block_entries = vec![
-			// Block 1
-			(
-				1i32,
-				ethereum_hash_1,
-				substrate_hash_1,
-				ethereum_storage_schema,
-			),
-			// Block 2
-			(
-				2i32,
-				ethereum_hash_2,
-				substrate_hash_2,
-				ethereum_storage_schema,
-			),
-			// Block 3
-			(
-				3i32,
-				ethereum_hash_3,
-				substrate_hash_3,
-				ethereum_storage_schema,
+		Ok(row.map(|row| TransactionReceiptMetadata {
+			status: row.try_get::<i32, _>(0).unwrap_or_default() as u8,
+			cumulative_gas_used: U256::from(row.try_get::<i64, _>(1).unwrap_or_default() as u64),
+			gas_used: U256::from(row.try_get::<i64, _>(2).unwrap_or_default() as u64),
+			effective_gas_price: U256::from_big_endian(
+				&row.try_get::<Vec<u8>, _>(3).unwrap_or_default()[..],
 			),
-		];
-		let mut builder = QueryBuilder::new(
-			"INSERT INTO blocks(
-				block_number,
-				ethereum_block_hash,
-				substrate_block_hash,
-				ethereum_storage_schema,
-				is_canon
-			)",
-		);
-		builder.push_values(block_entries, |mut b, entry| {
-			let block_number = entry.0;
-			let ethereum_block_hash = entry.1.as_bytes().to_owned();
-			let substrate_block_hash = entry.2.as_bytes().to_owned();
-			let ethereum_storage_schema = entry.3.encode();
+			contract_address: row
+				.try_get::<Option<Vec<u8>>, _>(4)
+				.unwrap_or_default()
+				.map(|address| H160::from_slice(&address[..])),
+		}))
+	}
 
-			b.push_bind(block_number);
-			b.push_bind(ethereum_block_hash);
-			b.push_bind(substrate_block_hash);
-			b.push_bind(ethereum_storage_schema);
-			b.push_bind(1i32);
-		});
-		let query = builder.build();
-		let _ = query
-			.execute(indexer_backend.pool())
+	async fn transactions_by_address(
+		&self,
+		address: &H160,
+		offset: u32,
+		limit: u32,
+	) -> Result<Vec<AddressTransaction<Block>>, String> {
+		let address = address.as_bytes();
+		sqlx::query(
+			"SELECT
+				t.ethereum_transaction_hash, t.substrate_block_hash, t.ethereum_block_hash,
+				b.block_number, t.ethereum_transaction_index, t.from_address, t.to_address, t.value
+			FROM transactions AS t
+			INNER JOIN blocks AS b
+			ON b.substrate_block_hash = t.substrate_block_hash AND b.is_canon = 1
+			WHERE t.from_address = ? OR t.to_address = ?
+			ORDER BY b.block_number DESC, t.ethereum_transaction_index DESC
+			LIMIT ? OFFSET ?",
+		)
+		.bind(address)
+		.bind(address)
+		.bind(limit as i64)
+		.bind(offset as i64)
+		.fetch_all(&self.pool)
+		.await
+		.map(|rows| {
+			rows.iter()
+				.map(|row| AddressTransaction {
+					ethereum_transaction_hash: H256::from_slice(&row.get::<Vec<u8>, _>(0)[..]),
+					substrate_block_hash: H256::from_slice(&row.get::<Vec<u8>, _>(1)[..]),
+					ethereum_block_hash: H256::from_slice(&row.get::<Vec<u8>, _>(2)[..]),
+					block_number: row.get::<i32, _>(3) as u32,
+					ethereum_index: row.get::<i32, _>(4) as u32,
+					from: H160::from_slice(&row.get::<Vec<u8>, _>(5)[..]),
+					to: row
+						.get::<Option<Vec<u8>>, _>(6)
+						.map(|to| H160::from_slice(&to[..])),
+					value: U256::from_big_endian(&row.get::<Vec<u8>, _>(7)[..]),
+				})
+				.collect()
+		})
+		.map_err(|err| format!("{:?}", err))
+	}
+
+	async fn contract_creation(
+		&self,
+		address: &H160,
+	) -> Result<Option<ContractCreation<Block>>, String> {
+		sqlx::query(
+			"SELECT
+				t.ethereum_transaction_hash, t.substrate_block_hash, t.ethereum_block_hash,
+				b.block_number, t.ethereum_transaction_index, t.from_address
+			FROM transactions AS t
+			INNER JOIN blocks AS b
+			ON b.substrate_block_hash = t.substrate_block_hash AND b.is_canon = 1
+			WHERE t.contract_address = ? AND t.status = 1
+			ORDER BY b.block_number ASC
+			LIMIT 1",
+		)
+		.bind(address.as_bytes())
+		.fetch_optional(&self.pool)
+		.await
+		.map(|row| {
+			row.map(|row| ContractCreation {
+				ethereum_transaction_hash: H256::from_slice(&row.get::<Vec<u8>, _>(0)[..]),
+				substrate_block_hash: H256::from_slice(&row.get::<Vec<u8>, _>(1)[..]),
+				ethereum_block_hash: H256::from_slice(&row.get::<Vec<u8>, _>(2)[..]),
+				block_number: row.get::<i32, _>(3) as u32,
+				ethereum_index: row.get::<i32, _>(4) as u32,
+				creator: row
+					.get::<Option<Vec<u8>>, _>(5)
+					.map(|from| H160::from_slice(&from[..]))
+					.unwrap_or_default(),
+			})
+		})
+		.map_err(|err| format!("{:?}", err))
+	}
+
+	fn log_indexer(&self) -> &dyn fc_api::LogIndexerBackend<Block> {
+		self
+	}
+
+	async fn latest_block_hash(&self) -> Result<Block::Hash, String> {
+		// Retrieves the block hash for the latest indexed block, maybe it's not canon.
+		sqlx::query("SELECT substrate_block_hash FROM blocks ORDER BY block_number DESC LIMIT 1")
+			.fetch_one(self.pool())
 			.await
-			.expect("insert should succeed");
+			.map(|row| H256::from_slice(&row.get::<Vec<u8>, _>(0)[..]))
+			.map_err(|e| format!("Failed to fetch best hash: {}", e))
+	}
 
-		// log_{BLOCK}_{TOPICS}_{LOG_INDEX}_{TX_INDEX}
-		let log_1_abcd_0_0_alice = Log {
-			block_number: 1,
-			address: alice,
-			topics: [topics_a, topics_b, topics_c, topics_d],
-			log_index: 0,
-			transaction_index: 0,
-			substrate_block_hash: substrate_hash_1,
-			ethereum_block_hash: ethereum_hash_1,
-		};
-		let log_1_dcba_1_0_alice = Log {
-			block_number: 1,
-			address: alice,
-			topics: [topics_d, topics_c, topics_b, topics_a],
-			log_index: 1,
-			transaction_index: 0,
-			substrate_block_hash: substrate_hash_1,
-			ethereum_block_hash: ethereum_hash_1,
-		};
-		let log_1_badc_2_0_alice = Log {
-			block_number: 1,
-			address: alice,
-			topics: [topics_b, topics_a, topics_d, topics_c],
-			log_index: 2,
+	async fn history_horizon(&self) -> Result<u64, String> {
+		Ok(self.history_horizon().into())
+	}
+}
+
+#[async_trait::async_trait]
+impl<Block: BlockT<Hash = H256>> fc_api::FeeHistoryBackend for Backend<Block> {
+	async fn write_fee_history(
+		&self,
+		block_number: u64,
+		entry: &FeeHistoryEntry,
+	) -> Result<(), String> {
+		// The fee history is maintained by the read-write instance.
+		if self.read_only {
+			return Ok(());
+		}
+		// The base fee is stored as its two's complement to fit the INTEGER column.
+		sqlx::query(
+			"INSERT OR REPLACE INTO fee_history(block_number, base_fee, gas_used_ratio, rewards)
+			VALUES (?, ?, ?, ?)",
+		)
+		.bind(block_number as i64)
+		.bind(entry.base_fee as i64)
+		.bind(entry.gas_used_ratio)
+		.bind(entry.rewards.encode())
+		.execute(self.pool())
+		.await
+		.map(|_| ())
+		.map_err(|e| format!("Failed to write fee history: {}", e))
+	}
+
+	async fn remove_fee_history(&self, block_numbers: &[u64]) -> Result<(), String> {
+		if self.read_only || block_numbers.is_empty() {
+			return Ok(());
+		}
+		let mut builder: QueryBuilder<Sqlite> =
+			QueryBuilder::new("DELETE FROM fee_history WHERE block_number IN (");
+		let mut separated = builder.separated(", ");
+		for block_number in block_numbers {
+			separated.push_bind(*block_number as i64);
+		}
+		separated.push_unseparated(")");
+		builder
+			.build()
+			.execute(self.pool())
+			.await
+			.map(|_| ())
+			.map_err(|e| format!("Failed to remove fee history: {}", e))
+	}
+
+	async fn fee_history(
+		&self,
+		from_block: u64,
+		to_block: u64,
+	) -> Result<Vec<(u64, FeeHistoryEntry)>, String> {
+		sqlx::query(
+			"SELECT block_number, base_fee, gas_used_ratio, rewards FROM fee_history
+			WHERE block_number BETWEEN ? AND ?
+			ORDER BY block_number ASC",
+		)
+		.bind(from_block as i64)
+		.bind(to_block as i64)
+		.fetch_all(self.pool())
+		.await
+		.map_err(|e| format!("Failed to fetch fee history: {}", e))?
+		.into_iter()
+		.map(|row| {
+			let rewards = Vec::<u64>::decode(&mut &row.get::<Vec<u8>, _>(3)[..])
+				.map_err(|e| e.to_string())?;
+			Ok((
+				row.get::<i64, _>(0) as u64,
+				FeeHistoryEntry {
+					base_fee: row.get::<i64, _>(1) as u64,
+					gas_used_ratio: row.get::<f64, _>(2),
+					rewards,
+				},
+			))
+		})
+		.collect()
+	}
+}
+
+#[async_trait::async_trait]
+impl<Block: BlockT<Hash = H256>> fc_api::LogIndexerBackend<Block> for Backend<Block> {
+	fn is_indexed(&self) -> bool {
+		true
+	}
+
+	async fn filter_logs(
+		&self,
+		from_block: u64,
+		to_block: u64,
+		addresses: Vec<H160>,
+		topics: Vec<Vec<Option<H256>>>,
+	) -> Result<Vec<FilteredLog<Block>>, LogIndexerError> {
+		let mut unique_topics: [HashSet<H256>; 4] = [
+			HashSet::new(),
+			HashSet::new(),
+			HashSet::new(),
+			HashSet::new(),
+		];
+		for topic_combination in topics.into_iter() {
+			for (topic_index, topic) in topic_combination.into_iter().enumerate() {
+				if topic_index == MAX_TOPIC_COUNT as usize {
+					return Err("Invalid topic input. Maximum length is 4."
+						.to_string()
+						.into());
+				}
+
+				if let Some(topic) = topic {
+					unique_topics[topic_index].insert(topic);
+				}
+			}
+		}
+
+		let log_key = format!("{from_block}-{to_block}-{addresses:?}-{unique_topics:?}");
+		let candidate_blocks = self
+			.bloom_candidates(from_block, to_block, &addresses, &unique_topics)
+			.await
+			.map_err(|err| format!("failed running bloom pre-check: {}", err))?;
+		if let Some(candidate_blocks) = &candidate_blocks {
+			log::debug!(
+				target: "frontier-sql",
+				"Bloom pre-check left {} candidate blocks - {log_key}",
+				candidate_blocks.len(),
+			);
+			if candidate_blocks.is_empty() {
+				return Ok(Vec::new());
+			}
+		}
+		let mut qb = QueryBuilder::new("");
+		let query = build_query(
+			&mut qb,
+			from_block,
+			to_block,
+			addresses,
+			unique_topics,
+			candidate_blocks,
+			self.max_log_results,
+		)
+		// Each filter has its own statement, caching it would evict the hot ones.
+		.persistent(false);
+		let sql = query.sql();
+
+		let mut conn = self
+			.pool()
+			.acquire()
+			.await
+			.map_err(|err| format!("failed acquiring sqlite connection: {}", err))?;
+		let log_key2 = log_key.clone();
+		conn.lock_handle()
+			.await
+			.map_err(|err| format!("{:?}", err))?
+			.set_progress_handler(self.num_ops_timeout, move || {
+				log::debug!(target: "frontier-sql", "Sqlite progress_handler triggered for {log_key2}");
+				false
+			});
+		log::debug!(target: "frontier-sql", "Query: {sql:?} - {log_key}");
+
+		let mut out: Vec<FilteredLog<Block>> = vec![];
+		let mut rows = query.fetch(&mut *conn);
+		let maybe_err = loop {
+			match rows.try_next().await {
+				Ok(Some(row)) => {
+					// Substrate block hash
+					let substrate_block_hash =
+						H256::from_slice(&row.try_get::<Vec<u8>, _>(0).unwrap_or_default()[..]);
+					// Ethereum block hash
+					let ethereum_block_hash =
+						H256::from_slice(&row.try_get::<Vec<u8>, _>(1).unwrap_or_default()[..]);
+					// Block number
+					let block_number = row.try_get::<i32, _>(2).unwrap_or_default() as u32;
+					// Ethereum storage schema
+					let ethereum_storage_schema: EthereumStorageSchema =
+						Decode::decode(&mut &row.try_get::<Vec<u8>, _>(3).unwrap_or_default()[..])
+							.map_err(|_| {
+								"Cannot decode EthereumStorageSchema for block".to_string()
+							})?;
+					// Transaction index
+					let transaction_index = row.try_get::<i32, _>(4).unwrap_or_default() as u32;
+					// Log index
+					let log_index = row.try_get::<i32, _>(5).unwrap_or_default() as u32;
+					out.push(FilteredLog {
+						substrate_block_hash,
+						ethereum_block_hash,
+						block_number,
+						ethereum_storage_schema,
+						transaction_index,
+						log_index,
+					});
+				}
+				Ok(None) => break None, // no more rows
+				Err(err) => break Some(err),
+			};
+		};
+		drop(rows);
+		conn.lock_handle()
+			.await
+			.map_err(|err| format!("{:?}", err))?
+			.remove_progress_handler();
+
+		if let Some(err) = maybe_err {
+			log::error!(target: "frontier-sql", "Failed to query sql db: {err:?} - {log_key}");
+			return Err("Failed to query sql db with statement".to_string().into());
+		}
+		if out.len() > self.max_log_results as usize {
+			log::debug!(target: "frontier-sql", "Query exceeded {} results - {log_key}", self.max_log_results);
+			return Err(LogIndexerError::TooManyResults {
+				limit: self.max_log_results,
+			});
+		}
+
+		log::info!(target: "frontier-sql", "FILTER remove handler - {log_key}");
+		Ok(out)
+	}
+
+	async fn prioritize_range(&self, from_block: u64, to_block: u64) {
+		// No sync worker is running against a read-only database.
+		if self.read_only || from_block > to_block || to_block - from_block >= MAX_PRIORITY_RANGE {
+			return;
+		}
+		let (from_block, to_block) = (from_block as u32, to_block as u32);
+		if self.is_range_indexed(from_block, to_block).await {
+			return;
+		}
+
+		log::debug!(target: "frontier-sql", "Prioritizing blocks #{from_block}..=#{to_block}");
+		let Some(timeout) = self.priority_timeout else {
+			self.priority_ranges
+				.lock()
+				.push_back((from_block, to_block));
+			self.priority_requested.notify_one();
+			return;
+		};
+		let deadline = Instant::now() + timeout;
+		let mut requested = false;
+		loop {
+			// Register before checking the range, so a notification sent in between is not lost.
+			let notified = self.priority_indexed.notified();
+			tokio::pin!(notified);
+			notified.as_mut().enable();
+			if !requested {
+				self.priority_ranges
+					.lock()
+					.push_back((from_block, to_block));
+				self.priority_requested.notify_one();
+				requested = true;
+			} else if self.is_range_indexed(from_block, to_block).await {
+				return;
+			}
+			let remaining = deadline.saturating_duration_since(Instant::now());
+			if tokio::time::timeout(remaining, notified).await.is_err() {
+				return;
+			}
+		}
+	}
+
+	async fn event_signatures(&self, topics: &[H256]) -> Result<Vec<(H256, String)>, String> {
+		if topics.is_empty() {
+			return Ok(Vec::new());
+		}
+
+		let mut builder: QueryBuilder<Sqlite> =
+			QueryBuilder::new("SELECT topic, signature FROM event_signatures WHERE topic IN (");
+		let mut topic_binds = builder.separated(", ");
+		for topic in topics.iter() {
+			topic_binds.push_bind(topic.as_bytes());
+		}
+		topic_binds.push_unseparated(")");
+		let rows = builder
+			.build()
+			.fetch_all(self.pool())
+			.await
+			.map_err(|err| format!("Failed to fetch event signatures: {err}"))?;
+
+		Ok(rows
+			.iter()
+			.map(|row| {
+				(
+					H256::from_slice(&row.get::<Vec<u8>, _>(0)[..]),
+					row.get::<String, _>(1),
+				)
+			})
+			.collect())
+	}
+}
+
+/// Build a SQL query to retrieve a list of logs given certain constraints.
+/// Gas values are stored as integers, saturating at `i64::MAX`.
+fn gas_to_i64(gas: U256) -> i64 {
+	gas.min(U256::from(i64::MAX as u64)).as_u64() as i64
+}
+
+/// Check whether the input may be in the logs bloom, following the Ethereum `M3:2048` scheme.
+fn bloom_contains(bloom: &[u8], input: &[u8]) -> bool {
+	let hash = sp_core::hashing::keccak_256(input);
+	(0..3).all(|i| {
+		let bit = (((hash[2 * i] as usize) << 8) | hash[2 * i + 1] as usize) & 2047;
+		bloom[BLOOM_SIZE - 1 - bit / 8] & (1 << (bit % 8)) != 0
+	})
+}
+
+/// Big-endian encoding of a `U256` value.
+fn u256_to_bytes(value: U256) -> Vec<u8> {
+	let mut bytes = [0u8; 32];
+	value.to_big_endian(&mut bytes);
+	bytes.to_vec()
+}
+
+fn build_query<'a>(
+	qb: &'a mut QueryBuilder<Sqlite>,
+	from_block: u64,
+	to_block: u64,
+	addresses: Vec<H160>,
+	topics: [HashSet<H256>; 4],
+	candidate_blocks: Option<Vec<i64>>,
+	max_results: u32,
+) -> Query<'a, Sqlite, SqliteArguments<'a>> {
+	qb.push(
+		"
+SELECT
+	l.substrate_block_hash,
+	b.ethereum_block_hash,
+	b.block_number,
+	b.ethereum_storage_schema,
+	l.transaction_index,
+	l.log_index
+FROM logs AS l
+INNER JOIN blocks AS b
+ON (b.block_number BETWEEN ",
+	);
+	qb.separated(" AND ")
+		.push_bind(from_block as i64)
+		.push_bind(to_block as i64)
+		.push_unseparated(")");
+	qb.push(" AND b.substrate_block_hash = l.substrate_block_hash")
+		.push(" AND b.is_canon = 1")
+		.push("\nWHERE 1");
+
+	if let Some(candidate_blocks) = candidate_blocks {
+		qb.push(" AND b.block_number IN (");
+		let mut qb_blocks = qb.separated(", ");
+		candidate_blocks.into_iter().for_each(|number| {
+			qb_blocks.push_bind(number);
+		});
+		qb_blocks.push_unseparated(")");
+	}
+
+	if !addresses.is_empty() {
+		qb.push(" AND l.address IN (");
+		let mut qb_addr = qb.separated(", ");
+		addresses.iter().for_each(|addr| {
+			qb_addr.push_bind(addr.as_bytes().to_owned());
+		});
+		qb_addr.push_unseparated(")");
+	}
+
+	for (i, topic_options) in topics.iter().enumerate() {
+		match topic_options.len().cmp(&1) {
+			Ordering::Greater => {
+				qb.push(format!(" AND l.topic_{} IN (", i + 1));
+				let mut qb_topic = qb.separated(", ");
+				topic_options.iter().for_each(|t| {
+					qb_topic.push_bind(t.as_bytes().to_owned());
+				});
+				qb_topic.push_unseparated(")");
+			}
+			Ordering::Equal => {
+				qb.push(format!(" AND l.topic_{} = ", i + 1)).push_bind(
+					topic_options
+						.iter()
+						.next()
+						.expect("length is 1, must exist; qed")
+						.as_bytes()
+						.to_owned(),
+				);
+			}
+			Ordering::Less => {}
+		}
+	}
+
+	// One more row than allowed is fetched to detect the limit is exceeded.
+	qb.push(format!(
+		"
+ORDER BY b.block_number ASC, l.transaction_index ASC, l.log_index ASC
+LIMIT {}",
+		max_results.saturating_add(1)
+	));
+
+	qb.build()
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	use std::path::Path;
+
+	use maplit::hashset;
+	use scale_codec::Encode;
+	use sqlx::{sqlite::SqliteRow, QueryBuilder, Row, SqlitePool};
+	use tempfile::tempdir;
+	// Substrate
+	use sp_core::{H160, H256, U256};
+	use sp_runtime::{
+		generic::{Block, Header},
+		traits::BlakeTwo256,
+	};
+	use substrate_test_runtime_client::{
+		DefaultTestClientBuilderExt, TestClientBuilder, TestClientBuilderExt,
+	};
+	// Frontier
+	use fc_api::Backend as BackendT;
+	use fc_storage::SchemaV3StorageOverride;
+	use fp_storage::{EthereumStorageSchema, PALLET_ETHEREUM_SCHEMA};
+
+	type OpaqueBlock =
+		Block<Header<u64, BlakeTwo256>, substrate_test_runtime_client::runtime::Extrinsic>;
+
+	struct TestFilter {
+		pub from_block: u64,
+		pub to_block: u64,
+		pub addresses: Vec<H160>,
+		pub topics: Vec<Vec<Option<H256>>>,
+		pub expected_result: Vec<FilteredLog<OpaqueBlock>>,
+	}
+
+	#[derive(Debug, Clone)]
+	struct Log {
+		block_number: u32,
+		address: H160,
+		topics: [H256; 4],
+		substrate_block_hash: H256,
+		ethereum_block_hash: H256,
+		transaction_index: u32,
+		log_index: u32,
+	}
+
+	#[allow(unused)]
+	struct TestData {
+		backend: Backend<OpaqueBlock>,
+		alice: H160,
+		bob: H160,
+		topics_a: H256,
+		topics_b: H256,
+		topics_c: H256,
+		topics_d: H256,
+		substrate_hash_1: H256,
+		substrate_hash_2: H256,
+		substrate_hash_3: H256,
+		ethereum_hash_1: H256,
+		ethereum_hash_2: H256,
+		ethereum_hash_3: H256,
+		log_1_abcd_0_0_alice: Log,
+		log_1_dcba_1_0_alice: Log,
+		log_1_badc_2_0_alice: Log,
+		log_2_abcd_0_0_bob: Log,
+		log_2_dcba_1_0_bob: Log,
+		log_2_badc_2_0_bob: Log,
+		log_3_abcd_0_0_bob: Log,
+		log_3_dcba_1_0_bob: Log,
+		log_3_badc_2_0_bob: Log,
+	}
+
+	impl From<Log> for FilteredLog<OpaqueBlock> {
+		fn from(value: Log) -> Self {
+			Self {
+				substrate_block_hash: value.substrate_block_hash,
+				ethereum_block_hash: value.ethereum_block_hash,
+				block_number: value.block_number,
+				ethereum_storage_schema: EthereumStorageSchema::V3,
+				transaction_index: value.transaction_index,
+				log_index: value.log_index,
+			}
+		}
+	}
+
+	async fn prepare() -> TestData {
+		let tmp = tempdir().expect("create a temporary directory");
+		// Initialize storage with schema V3
+		let builder = TestClientBuilder::new().add_extra_storage(
+			PALLET_ETHEREUM_SCHEMA.to_vec(),
+			Encode::encode(&EthereumStorageSchema::V3),
+		);
+		// Client
+		let (client, _) = builder
+			.build_with_native_executor::<substrate_test_runtime_client::runtime::RuntimeApi, _>(
+				None,
+			);
+		let client = Arc::new(client);
+		// Overrides
+		let storage_override = Arc::new(SchemaV3StorageOverride::new(client.clone()));
+		// Indexer backend
+		let indexer_backend = Backend::new(
+			BackendConfig::Sqlite(SqliteBackendConfig {
+				path: Path::new("sqlite:///")
+					.join(tmp.path())
+					.join("test.db3")
+					.to_str()
+					.unwrap(),
+				create_if_missing: true,
+				cache_size: 20480,
+				thread_count: 4,
+				..Default::default()
+			}),
+			1,
+			None,
+			10000,
+			storage_override.clone(),
+		)
+		.await
+		.expect("indexer pool to be created");
+
+		// Prepare test db data
+		// Addresses
+		let alice = H160::repeat_byte(0x01);
+		let bob = H160::repeat_byte(0x02);
+		// Topics
+		let topics_a = H256::repeat_byte(0x01);
+		let topics_b = H256::repeat_byte(0x02);
+		let topics_c = H256::repeat_byte(0x03);
+		let topics_d = H256::repeat_byte(0x04);
+		// Substrate block hashes
+		let substrate_hash_1 = H256::repeat_byte(0x05);
+		let substrate_hash_2 = H256::repeat_byte(0x06);
+		let substrate_hash_3 = H256::repeat_byte(0x07);
+		// Ethereum block hashes
+		let ethereum_hash_1 = H256::repeat_byte(0x08);
+		let ethereum_hash_2 = H256::repeat_byte(0x09);
+		let ethereum_hash_3 = H256::repeat_byte(0x0a);
+		// Ethereum storage schema
+		let ethereum_storage_schema = EthereumStorageSchema::V3;
+
+		let block_entries = vec![
+			// Block 1
+			(
+				1i32,
+				ethereum_hash_1,
+				substrate_hash_1,
+				ethereum_storage_schema,
+			),
+			// Block 2
+			(
+				2i32,
+				ethereum_hash_2,
+				substrate_hash_2,
+				ethereum_storage_schema,
+			),
+			// Block 3
+			(
+				3i32,
+				ethereum_hash_3,
+				substrate_hash_3,
+				ethereum_storage_schema,
+			),
+		];
+		let mut builder = QueryBuilder::new(
+			"INSERT INTO blocks(
+				block_number,
+				ethereum_block_hash,
+				substrate_block_hash,
+				ethereum_storage_schema,
+				is_canon
+			)",
+		);
+		builder.push_values(block_entries, |mut b, entry| {
+			let block_number = entry.0;
+			let ethereum_block_hash = entry.1.as_bytes().to_owned();
+			let substrate_block_hash = entry.2.as_bytes().to_owned();
+			let ethereum_storage_schema = entry.3.encode();
+
+			b.push_bind(block_number);
+			b.push_bind(ethereum_block_hash);
+			b.push_bind(substrate_block_hash);
+			b.push_bind(ethereum_storage_schema);
+			b.push_bind(1i32);
+		});
+		let query = builder.build();
+		let _ = query
+			.execute(indexer_backend.pool())
+			.await
+			.expect("insert should succeed");
+
+		// log_{BLOCK}_{TOPICS}_{LOG_INDEX}_{TX_INDEX}
+		let log_1_abcd_0_0_alice = Log {
+			block_number: 1,
+			address: alice,
+			topics: [topics_a, topics_b, topics_c, topics_d],
+			log_index: 0,
+			transaction_index: 0,
+			substrate_block_hash: substrate_hash_1,
+			ethereum_block_hash: ethereum_hash_1,
+		};
+		let log_1_dcba_1_0_alice = Log {
+			block_number: 1,
+			address: alice,
+			topics: [topics_d, topics_c, topics_b, topics_a],
+			log_index: 1,
+			transaction_index: 0,
+			substrate_block_hash: substrate_hash_1,
+			ethereum_block_hash: ethereum_hash_1,
+		};
+		let log_1_badc_2_0_alice = Log {
+			block_number: 1,
+			address: alice,
+			topics: [topics_b, topics_a, topics_d, topics_c],
+			log_index: 2,
 			transaction_index: 0,
 			substrate_block_hash: substrate_hash_1,
 			ethereum_block_hash: ethereum_hash_1,
@@ -1239,538 +2673,1461 @@ mod test {
 			substrate_block_hash: substrate_hash_2,
 			ethereum_block_hash: ethereum_hash_2,
 		};
-		let log_2_badc_2_0_bob = Log {
-			block_number: 2,
-			address: bob,
-			topics: [topics_b, topics_a, topics_d, topics_c],
-			log_index: 2,
-			transaction_index: 0,
-			substrate_block_hash: substrate_hash_2,
-			ethereum_block_hash: ethereum_hash_2,
+		let log_2_badc_2_0_bob = Log {
+			block_number: 2,
+			address: bob,
+			topics: [topics_b, topics_a, topics_d, topics_c],
+			log_index: 2,
+			transaction_index: 0,
+			substrate_block_hash: substrate_hash_2,
+			ethereum_block_hash: ethereum_hash_2,
+		};
+
+		let log_3_abcd_0_0_bob = Log {
+			block_number: 3,
+			address: bob,
+			topics: [topics_a, topics_b, topics_c, topics_d],
+			log_index: 0,
+			transaction_index: 0,
+			substrate_block_hash: substrate_hash_3,
+			ethereum_block_hash: ethereum_hash_3,
+		};
+		let log_3_dcba_1_0_bob = Log {
+			block_number: 3,
+			address: bob,
+			topics: [topics_d, topics_c, topics_b, topics_a],
+			log_index: 1,
+			transaction_index: 0,
+			substrate_block_hash: substrate_hash_3,
+			ethereum_block_hash: ethereum_hash_3,
+		};
+		let log_3_badc_2_0_bob = Log {
+			block_number: 3,
+			address: bob,
+			topics: [topics_b, topics_a, topics_d, topics_c],
+			log_index: 2,
+			transaction_index: 0,
+			substrate_block_hash: substrate_hash_3,
+			ethereum_block_hash: ethereum_hash_3,
+		};
+
+		let log_entries = vec![
+			// Block 1
+			log_1_abcd_0_0_alice.clone(),
+			log_1_dcba_1_0_alice.clone(),
+			log_1_badc_2_0_alice.clone(),
+			// Block 2
+			log_2_abcd_0_0_bob.clone(),
+			log_2_dcba_1_0_bob.clone(),
+			log_2_badc_2_0_bob.clone(),
+			// Block 3
+			log_3_abcd_0_0_bob.clone(),
+			log_3_dcba_1_0_bob.clone(),
+			log_3_badc_2_0_bob.clone(),
+		];
+
+		let mut builder: QueryBuilder<sqlx::Sqlite> = QueryBuilder::new(
+			"INSERT INTO logs(
+				address,
+				topic_1,
+				topic_2,
+				topic_3,
+				topic_4,
+				log_index,
+				transaction_index,
+				substrate_block_hash
+			)",
+		);
+		builder.push_values(log_entries, |mut b, entry| {
+			let address = entry.address.as_bytes().to_owned();
+			let topic_1 = entry.topics[0].as_bytes().to_owned();
+			let topic_2 = entry.topics[1].as_bytes().to_owned();
+			let topic_3 = entry.topics[2].as_bytes().to_owned();
+			let topic_4 = entry.topics[3].as_bytes().to_owned();
+			let log_index = entry.log_index;
+			let transaction_index = entry.transaction_index;
+			let substrate_block_hash = entry.substrate_block_hash.as_bytes().to_owned();
+
+			b.push_bind(address);
+			b.push_bind(topic_1);
+			b.push_bind(topic_2);
+			b.push_bind(topic_3);
+			b.push_bind(topic_4);
+			b.push_bind(log_index);
+			b.push_bind(transaction_index);
+			b.push_bind(substrate_block_hash);
+		});
+		let query = builder.build();
+		let _ = query.execute(indexer_backend.pool()).await;
+
+		TestData {
+			alice,
+			bob,
+			topics_a,
+			topics_b,
+			topics_c,
+			topics_d,
+			substrate_hash_1,
+			substrate_hash_2,
+			substrate_hash_3,
+			ethereum_hash_1,
+			ethereum_hash_2,
+			ethereum_hash_3,
+			backend: indexer_backend,
+			log_1_abcd_0_0_alice,
+			log_1_dcba_1_0_alice,
+			log_1_badc_2_0_alice,
+			log_2_abcd_0_0_bob,
+			log_2_dcba_1_0_bob,
+			log_2_badc_2_0_bob,
+			log_3_abcd_0_0_bob,
+			log_3_dcba_1_0_bob,
+			log_3_badc_2_0_bob,
+		}
+	}
+
+	async fn run_test_case(
+		backend: Backend<OpaqueBlock>,
+		test_case: &TestFilter,
+	) -> Result<Vec<FilteredLog<OpaqueBlock>>, LogIndexerError> {
+		backend
+			.log_indexer()
+			.filter_logs(
+				test_case.from_block,
+				test_case.to_block,
+				test_case.addresses.clone(),
+				test_case.topics.clone(),
+			)
+			.await
+	}
+
+	async fn assert_blocks_canon(pool: &SqlitePool, expected: Vec<(H256, u32)>) {
+		let actual: Vec<(H256, u32)> =
+			sqlx::query("SELECT substrate_block_hash, is_canon FROM blocks")
+				.map(|row: SqliteRow| (H256::from_slice(&row.get::<Vec<u8>, _>(0)[..]), row.get(1)))
+				.fetch_all(pool)
+				.await
+				.expect("sql query must succeed");
+		assert_eq!(expected, actual);
+	}
+
+	#[tokio::test]
+	async fn genesis_works() {
+		let TestData { backend, .. } = prepare().await;
+		let filter = TestFilter {
+			from_block: 0,
+			to_block: 0,
+			addresses: vec![],
+			topics: vec![],
+			expected_result: vec![],
+		};
+		let result = run_test_case(backend, &filter).await.expect("must succeed");
+		assert_eq!(result, filter.expected_result);
+	}
+
+	#[tokio::test]
+	async fn unsanitized_input_works() {
+		let TestData { backend, .. } = prepare().await;
+		let filter = TestFilter {
+			from_block: 0,
+			to_block: 0,
+			addresses: vec![],
+			topics: vec![vec![None], vec![None, None, None]],
+			expected_result: vec![],
+		};
+		let result = run_test_case(backend, &filter).await.expect("must succeed");
+		assert_eq!(result, filter.expected_result);
+	}
+
+	#[tokio::test]
+	async fn invalid_topic_input_size_fails() {
+		let TestData {
+			backend, topics_a, ..
+		} = prepare().await;
+		let filter = TestFilter {
+			from_block: 0,
+			to_block: 0,
+			addresses: vec![],
+			topics: vec![
+				vec![Some(topics_a), None, None, None, None],
+				vec![Some(topics_a), None, None, None],
+			],
+			expected_result: vec![],
+		};
+		run_test_case(backend, &filter)
+			.await
+			.expect_err("Invalid topic input. Maximum length is 4.");
+	}
+
+	#[tokio::test]
+	async fn test_malformed_topic_cleans_invalid_options() {
+		let TestData {
+			backend,
+			topics_a,
+			topics_b,
+			topics_d,
+			log_1_badc_2_0_alice,
+			..
+		} = prepare().await;
+
+		// [(a,null,b), (a, null), (d,null), null] -> [(a,b), a, d]
+		let filter = TestFilter {
+			from_block: 0,
+			to_block: 1,
+			addresses: vec![],
+			topics: vec![
+				vec![Some(topics_a), None, Some(topics_d)],
+				vec![None], // not considered
+				vec![Some(topics_b), Some(topics_a), None],
+				vec![None, None, None, None], // not considered
+			],
+			expected_result: vec![log_1_badc_2_0_alice.into()],
+		};
+		let result = run_test_case(backend, &filter).await.expect("must succeed");
+		assert_eq!(result, filter.expected_result);
+	}
+
+	#[tokio::test]
+	async fn block_range_works() {
+		let TestData {
+			backend,
+			log_1_abcd_0_0_alice,
+			log_1_dcba_1_0_alice,
+			log_1_badc_2_0_alice,
+			log_2_abcd_0_0_bob,
+			log_2_dcba_1_0_bob,
+			log_2_badc_2_0_bob,
+			..
+		} = prepare().await;
+
+		let filter = TestFilter {
+			from_block: 0,
+			to_block: 2,
+			addresses: vec![],
+			topics: vec![],
+			expected_result: vec![
+				log_1_abcd_0_0_alice.into(),
+				log_1_dcba_1_0_alice.into(),
+				log_1_badc_2_0_alice.into(),
+				log_2_abcd_0_0_bob.into(),
+				log_2_dcba_1_0_bob.into(),
+				log_2_badc_2_0_bob.into(),
+			],
+		};
+		let result = run_test_case(backend, &filter).await.expect("must succeed");
+		assert_eq!(result, filter.expected_result);
+	}
+
+	#[tokio::test]
+	async fn exceeding_max_log_results_fails() {
+		let TestData { backend, .. } = prepare().await;
+		let filter = TestFilter {
+			from_block: 0,
+			to_block: 2,
+			addresses: vec![],
+			topics: vec![],
+			expected_result: vec![],
 		};
 
-		let log_3_abcd_0_0_bob = Log {
-			block_number: 3,
-			address: bob,
-			topics: [topics_a, topics_b, topics_c, topics_d],
-			log_index: 0,
-			transaction_index: 0,
-			substrate_block_hash: substrate_hash_3,
-			ethereum_block_hash: ethereum_hash_3,
+		let limited = Backend {
+			max_log_results: 6,
+			..backend.clone()
 		};
-		let log_3_dcba_1_0_bob = Log {
-			block_number: 3,
-			address: bob,
-			topics: [topics_d, topics_c, topics_b, topics_a],
-			log_index: 1,
-			transaction_index: 0,
-			substrate_block_hash: substrate_hash_3,
-			ethereum_block_hash: ethereum_hash_3,
+		assert_eq!(
+			run_test_case(limited, &filter)
+				.await
+				.expect("must succeed")
+				.len(),
+			6
+		);
+
+		let limited = Backend {
+			max_log_results: 5,
+			..backend
 		};
-		let log_3_badc_2_0_bob = Log {
-			block_number: 3,
-			address: bob,
-			topics: [topics_b, topics_a, topics_d, topics_c],
-			log_index: 2,
-			transaction_index: 0,
-			substrate_block_hash: substrate_hash_3,
-			ethereum_block_hash: ethereum_hash_3,
+		assert_eq!(
+			run_test_case(limited, &filter).await,
+			Err(LogIndexerError::TooManyResults { limit: 5 })
+		);
+	}
+
+	#[tokio::test]
+	async fn address_filter_works() {
+		let TestData {
+			backend,
+			alice,
+			log_1_abcd_0_0_alice,
+			log_1_dcba_1_0_alice,
+			log_1_badc_2_0_alice,
+			..
+		} = prepare().await;
+		let filter = TestFilter {
+			from_block: 0,
+			to_block: 3,
+			addresses: vec![alice],
+			topics: vec![],
+			expected_result: vec![
+				log_1_abcd_0_0_alice.into(),
+				log_1_dcba_1_0_alice.into(),
+				log_1_badc_2_0_alice.into(),
+			],
 		};
+		let result = run_test_case(backend, &filter).await.expect("must succeed");
+		assert_eq!(result, filter.expected_result);
+	}
 
-		let log_entries = vec![
-			// Block 1
-			log_1_abcd_0_0_alice.clone(),
-			log_1_dcba_1_0_alice.clone(),
-			log_1_badc_2_0_alice.clone(),
-			// Block 2
-			log_2_abcd_0_0_bob.clone(),
-			log_2_dcba_1_0_bob.clone(),
-			log_2_badc_2_0_bob.clone(),
-			// Block 3
-			log_3_abcd_0_0_bob.clone(),
-			log_3_dcba_1_0_bob.clone(),
-			log_3_badc_2_0_bob.clone(),
-		];
+	fn bloom_of(inputs: &[&[u8]]) -> Vec<u8> {
+		let mut bloom = vec![0u8; BLOOM_SIZE];
+		for input in inputs {
+			let hash = sp_core::hashing::keccak_256(input);
+			for i in 0..3 {
+				let bit = (((hash[2 * i] as usize) << 8) | hash[2 * i + 1] as usize) & 2047;
+				bloom[BLOOM_SIZE - 1 - bit / 8] |= 1 << (bit % 8);
+			}
+		}
+		bloom
+	}
 
-		let mut builder: QueryBuilder<sqlx::Sqlite> = QueryBuilder::new(
-			"INSERT INTO logs(
-				address,
-				topic_1,
-				topic_2,
-				topic_3,
-				topic_4,
-				log_index,
-				transaction_index,
-				substrate_block_hash
-			)",
+	#[tokio::test]
+	async fn bloom_pre_check_skips_unmatched_blocks() {
+		let TestData {
+			backend,
+			alice,
+			bob,
+			topics_a,
+			topics_b,
+			topics_c,
+			topics_d,
+			log_1_abcd_0_0_alice,
+			log_1_dcba_1_0_alice,
+			log_1_badc_2_0_alice,
+			..
+		} = prepare().await;
+		let topics = [topics_a, topics_b, topics_c, topics_d];
+		for (block_number, address) in [(1i32, alice), (2, bob), (3, bob)] {
+			let mut inputs = vec![address.as_bytes()];
+			inputs.extend(topics.iter().map(|topic| topic.as_bytes()));
+			sqlx::query("UPDATE blocks SET logs_bloom = ? WHERE block_number = ?")
+				.bind(bloom_of(&inputs))
+				.bind(block_number)
+				.execute(backend.pool())
+				.await
+				.expect("sql query must succeed");
+		}
+		let no_topics: [HashSet<H256>; 4] = Default::default();
+
+		assert_eq!(
+			backend
+				.bloom_candidates(0, 3, &[], &no_topics)
+				.await
+				.expect("must succeed"),
+			None
+		);
+		assert_eq!(
+			backend
+				.bloom_candidates(0, 3, &[alice], &no_topics)
+				.await
+				.expect("must succeed"),
+			Some(vec![1])
+		);
+		assert_eq!(
+			backend
+				.bloom_candidates(0, 3, &[H160::repeat_byte(0x03)], &no_topics)
+				.await
+				.expect("must succeed"),
+			Some(vec![])
 		);
-		builder.push_values(log_entries, |mut b, entry| {
-			let address = entry.address.as_bytes().to_owned();
-			let topic_1 = entry.topics[0].as_bytes().to_owned();
-			let topic_2 = entry.topics[1].as_bytes().to_owned();
-			let topic_3 = entry.topics[2].as_bytes().to_owned();
-			let topic_4 = entry.topics[3].as_bytes().to_owned();
-			let log_index = entry.log_index;
-			let transaction_index = entry.transaction_index;
-			let substrate_block_hash = entry.substrate_block_hash.as_bytes().to_owned();
 
-			b.push_bind(address);
-			b.push_bind(topic_1);
-			b.push_bind(topic_2);
-			b.push_bind(topic_3);
-			b.push_bind(topic_4);
-			b.push_bind(log_index);
-			b.push_bind(transaction_index);
-			b.push_bind(substrate_block_hash);
-		});
-		let query = builder.build();
-		let _ = query.execute(indexer_backend.pool()).await;
+		let filter = TestFilter {
+			from_block: 0,
+			to_block: 3,
+			addresses: vec![alice],
+			topics: vec![],
+			expected_result: vec![
+				log_1_abcd_0_0_alice.into(),
+				log_1_dcba_1_0_alice.into(),
+				log_1_badc_2_0_alice.into(),
+			],
+		};
+		let result = run_test_case(backend.clone(), &filter)
+			.await
+			.expect("must succeed");
+		assert_eq!(result, filter.expected_result);
+
+		let filter = TestFilter {
+			from_block: 0,
+			to_block: 3,
+			addresses: vec![H160::repeat_byte(0x03)],
+			topics: vec![],
+			expected_result: vec![],
+		};
+		let result = run_test_case(backend, &filter).await.expect("must succeed");
+		assert_eq!(result, filter.expected_result);
+	}
+
+	#[tokio::test]
+	async fn topic_filter_works() {
+		let TestData {
+			backend,
+			topics_d,
+			log_1_dcba_1_0_alice,
+			log_2_dcba_1_0_bob,
+			log_3_dcba_1_0_bob,
+			..
+		} = prepare().await;
+		let filter = TestFilter {
+			from_block: 0,
+			to_block: 3,
+			addresses: vec![],
+			topics: vec![vec![Some(topics_d)]],
+			expected_result: vec![
+				log_1_dcba_1_0_alice.into(),
+				log_2_dcba_1_0_bob.into(),
+				log_3_dcba_1_0_bob.into(),
+			],
+		};
+		let result = run_test_case(backend, &filter).await.expect("must succeed");
+		assert_eq!(result, filter.expected_result);
+	}
+
+	#[tokio::test]
+	async fn test_filters_address_and_topic() {
+		let TestData {
+			backend,
+			bob,
+			topics_b,
+			log_2_badc_2_0_bob,
+			log_3_badc_2_0_bob,
+			..
+		} = prepare().await;
+		let filter = TestFilter {
+			from_block: 0,
+			to_block: 3,
+			addresses: vec![bob],
+			topics: vec![vec![Some(topics_b)]],
+			expected_result: vec![log_2_badc_2_0_bob.into(), log_3_badc_2_0_bob.into()],
+		};
+		let result = run_test_case(backend, &filter).await.expect("must succeed");
+		assert_eq!(result, filter.expected_result);
+	}
+
+	#[tokio::test]
+	async fn test_filters_multi_address_and_topic() {
+		let TestData {
+			backend,
+			alice,
+			bob,
+			topics_b,
+			log_1_badc_2_0_alice,
+			log_2_badc_2_0_bob,
+			log_3_badc_2_0_bob,
+			..
+		} = prepare().await;
+		let filter = TestFilter {
+			from_block: 0,
+			to_block: 3,
+			addresses: vec![alice, bob],
+			topics: vec![vec![Some(topics_b)]],
+			expected_result: vec![
+				log_1_badc_2_0_alice.into(),
+				log_2_badc_2_0_bob.into(),
+				log_3_badc_2_0_bob.into(),
+			],
+		};
+		let result = run_test_case(backend, &filter).await.expect("must succeed");
+		assert_eq!(result, filter.expected_result);
+	}
 
-		TestData {
+	#[tokio::test]
+	async fn test_filters_multi_address_and_multi_topic() {
+		let TestData {
+			backend,
 			alice,
 			bob,
 			topics_a,
 			topics_b,
-			topics_c,
-			topics_d,
-			substrate_hash_1,
-			substrate_hash_2,
-			substrate_hash_3,
-			ethereum_hash_1,
-			ethereum_hash_2,
-			ethereum_hash_3,
-			backend: indexer_backend,
 			log_1_abcd_0_0_alice,
-			log_1_dcba_1_0_alice,
-			log_1_badc_2_0_alice,
 			log_2_abcd_0_0_bob,
-			log_2_dcba_1_0_bob,
-			log_2_badc_2_0_bob,
 			log_3_abcd_0_0_bob,
-			log_3_dcba_1_0_bob,
-			log_3_badc_2_0_bob,
-		}
-	}
-
-	async fn run_test_case(
-		backend: Backend<OpaqueBlock>,
-		test_case: &TestFilter,
-	) -> Result<Vec<FilteredLog<OpaqueBlock>>, String> {
-		backend
-			.log_indexer()
-			.filter_logs(
-				test_case.from_block,
-				test_case.to_block,
-				test_case.addresses.clone(),
-				test_case.topics.clone(),
-			)
-			.await
-	}
-
-	async fn assert_blocks_canon(pool: &SqlitePool, expected: Vec<(H256, u32)>) {
-		let actual: Vec<(H256, u32)> =
-			sqlx::query("SELECT substrate_block_hash, is_canon FROM blocks")
-				.map(|row: SqliteRow| (H256::from_slice(&row.get::<Vec<u8>, _>(0)[..]), row.get(1)))
-				.fetch_all(pool)
-				.await
-				.expect("sql query must succeed");
-		assert_eq!(expected, actual);
+			..
+		} = prepare().await;
+		let filter = TestFilter {
+			from_block: 0,
+			to_block: 3,
+			addresses: vec![alice, bob],
+			topics: vec![vec![Some(topics_a), Some(topics_b)]],
+			expected_result: vec![
+				log_1_abcd_0_0_alice.into(),
+				log_2_abcd_0_0_bob.into(),
+				log_3_abcd_0_0_bob.into(),
+			],
+		};
+		let result = run_test_case(backend, &filter).await.expect("must succeed");
+		assert_eq!(result, filter.expected_result);
 	}
 
 	#[tokio::test]
-	async fn genesis_works() {
-		let TestData { backend, .. } = prepare().await;
+	async fn filter_with_topic_wildcards_works() {
+		let TestData {
+			backend,
+			alice,
+			bob,
+			topics_d,
+			topics_b,
+			log_1_dcba_1_0_alice,
+			log_2_dcba_1_0_bob,
+			log_3_dcba_1_0_bob,
+			..
+		} = prepare().await;
 		let filter = TestFilter {
 			from_block: 0,
-			to_block: 0,
-			addresses: vec![],
-			topics: vec![],
-			expected_result: vec![],
+			to_block: 3,
+			addresses: vec![alice, bob],
+			topics: vec![vec![Some(topics_d), None, Some(topics_b)]],
+			expected_result: vec![
+				log_1_dcba_1_0_alice.into(),
+				log_2_dcba_1_0_bob.into(),
+				log_3_dcba_1_0_bob.into(),
+			],
 		};
 		let result = run_test_case(backend, &filter).await.expect("must succeed");
 		assert_eq!(result, filter.expected_result);
 	}
 
 	#[tokio::test]
-	async fn unsanitized_input_works() {
-		let TestData { backend, .. } = prepare().await;
+	async fn trailing_wildcard_is_useless_but_works() {
+		let TestData {
+			alice,
+			backend,
+			topics_b,
+			log_1_dcba_1_0_alice,
+			..
+		} = prepare().await;
 		let filter = TestFilter {
 			from_block: 0,
-			to_block: 0,
-			addresses: vec![],
-			topics: vec![vec![None], vec![None, None, None]],
-			expected_result: vec![],
+			to_block: 1,
+			addresses: vec![alice],
+			topics: vec![vec![None, None, Some(topics_b), None]],
+			expected_result: vec![log_1_dcba_1_0_alice.into()],
 		};
 		let result = run_test_case(backend, &filter).await.expect("must succeed");
 		assert_eq!(result, filter.expected_result);
 	}
 
 	#[tokio::test]
-	async fn invalid_topic_input_size_fails() {
+	async fn filter_with_multi_topic_options_works() {
 		let TestData {
-			backend, topics_a, ..
+			backend,
+			topics_a,
+			topics_d,
+			log_1_abcd_0_0_alice,
+			log_1_dcba_1_0_alice,
+			log_2_abcd_0_0_bob,
+			log_2_dcba_1_0_bob,
+			log_3_abcd_0_0_bob,
+			log_3_dcba_1_0_bob,
+			..
 		} = prepare().await;
 		let filter = TestFilter {
 			from_block: 0,
-			to_block: 0,
+			to_block: 3,
 			addresses: vec![],
 			topics: vec![
-				vec![Some(topics_a), None, None, None, None],
-				vec![Some(topics_a), None, None, None],
+				vec![Some(topics_a)],
+				vec![Some(topics_d)],
+				vec![Some(topics_d)], // duplicate, ignored
+			],
+			expected_result: vec![
+				log_1_abcd_0_0_alice.into(),
+				log_1_dcba_1_0_alice.into(),
+				log_2_abcd_0_0_bob.into(),
+				log_2_dcba_1_0_bob.into(),
+				log_3_abcd_0_0_bob.into(),
+				log_3_dcba_1_0_bob.into(),
 			],
-			expected_result: vec![],
 		};
-		run_test_case(backend, &filter)
-			.await
-			.expect_err("Invalid topic input. Maximum length is 4.");
+		let result = run_test_case(backend, &filter).await.expect("must succeed");
+		assert_eq!(result, filter.expected_result);
 	}
 
 	#[tokio::test]
-	async fn test_malformed_topic_cleans_invalid_options() {
+	async fn filter_with_multi_topic_options_and_wildcards_works() {
 		let TestData {
 			backend,
+			bob,
 			topics_a,
 			topics_b,
+			topics_c,
 			topics_d,
-			log_1_badc_2_0_alice,
+			log_2_dcba_1_0_bob,
+			log_2_badc_2_0_bob,
+			log_3_dcba_1_0_bob,
+			log_3_badc_2_0_bob,
 			..
 		} = prepare().await;
-
-		// [(a,null,b), (a, null), (d,null), null] -> [(a,b), a, d]
 		let filter = TestFilter {
 			from_block: 0,
-			to_block: 1,
-			addresses: vec![],
+			to_block: 3,
+			addresses: vec![bob],
+			// Product on input [null,null,(b,d),(a,c)].
 			topics: vec![
-				vec![Some(topics_a), None, Some(topics_d)],
-				vec![None], // not considered
-				vec![Some(topics_b), Some(topics_a), None],
-				vec![None, None, None, None], // not considered
+				vec![None, None, Some(topics_b), Some(topics_a)],
+				vec![None, None, Some(topics_b), Some(topics_c)],
+				vec![None, None, Some(topics_d), Some(topics_a)],
+				vec![None, None, Some(topics_d), Some(topics_c)],
+			],
+			expected_result: vec![
+				log_2_dcba_1_0_bob.into(),
+				log_2_badc_2_0_bob.into(),
+				log_3_dcba_1_0_bob.into(),
+				log_3_badc_2_0_bob.into(),
+			],
+		};
+		let result = run_test_case(backend, &filter).await.expect("must succeed");
+		assert_eq!(result, filter.expected_result);
+	}
+
+	#[tokio::test]
+	async fn test_canonicalize_sets_canon_flag_for_redacted_and_enacted_blocks_correctly() {
+		let TestData {
+			backend,
+			substrate_hash_1,
+			substrate_hash_2,
+			substrate_hash_3,
+			..
+		} = prepare().await;
+
+		// set block #1 to non canon
+		sqlx::query("UPDATE blocks SET is_canon = 0 WHERE substrate_block_hash = ?")
+			.bind(substrate_hash_1.as_bytes())
+			.execute(backend.pool())
+			.await
+			.expect("sql query must succeed");
+		assert_blocks_canon(
+			backend.pool(),
+			vec![
+				(substrate_hash_1, 0),
+				(substrate_hash_2, 1),
+				(substrate_hash_3, 1),
+			],
+		)
+		.await;
+
+		backend
+			.canonicalize(&[substrate_hash_2], &[substrate_hash_1])
+			.await
+			.expect("must succeed");
+
+		assert_blocks_canon(
+			backend.pool(),
+			vec![
+				(substrate_hash_1, 1),
+				(substrate_hash_2, 0),
+				(substrate_hash_3, 1),
+			],
+		)
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_prune_blocks_before_deletes_indexed_data_in_batches() {
+		let TestData {
+			backend,
+			substrate_hash_1,
+			substrate_hash_2,
+			substrate_hash_3,
+			..
+		} = prepare().await;
+
+		for hash in [substrate_hash_1, substrate_hash_2, substrate_hash_3] {
+			sqlx::query("INSERT INTO sync_status(substrate_block_hash, status) VALUES (?, 1)")
+				.bind(hash.as_bytes())
+				.execute(backend.pool())
+				.await
+				.expect("sql query must succeed");
+		}
+
+		let pruned_logs = sqlx::query("SELECT COUNT(*) FROM logs WHERE substrate_block_hash != ?")
+			.bind(substrate_hash_3.as_bytes())
+			.fetch_one(backend.pool())
+			.await
+			.expect("sql query must succeed")
+			.get::<i64, _>(0) as u64;
+		assert_eq!(
+			backend.prunable_rows_before(3).await.expect("must succeed"),
+			PruneReport {
+				blocks: 2,
+				transactions: 0,
+				logs: pruned_logs,
+			}
+		);
+
+		assert_eq!(
+			backend
+				.prune_blocks_before(3, 1)
+				.await
+				.expect("must succeed"),
+			1
+		);
+		assert_eq!(
+			backend
+				.prune_blocks_before(3, 1)
+				.await
+				.expect("must succeed"),
+			1
+		);
+		assert_eq!(
+			backend
+				.prune_blocks_before(3, 1)
+				.await
+				.expect("must succeed"),
+			0
+		);
+		assert_eq!(
+			backend.prunable_rows_before(3).await.expect("must succeed"),
+			PruneReport::default()
+		);
+
+		// The pruned blocks are marked as such, so they are not indexed again.
+		let statuses: Vec<(H256, i32)> =
+			sqlx::query("SELECT substrate_block_hash, status FROM sync_status ORDER BY id")
+				.map(|row: SqliteRow| {
+					(
+						H256::from_slice(&row.get::<Vec<u8>, _>(0)[..]),
+						row.get::<i32, _>(1),
+					)
+				})
+				.fetch_all(backend.pool())
+				.await
+				.expect("sql query must succeed");
+		assert_eq!(
+			statuses,
+			vec![
+				(substrate_hash_1, 2),
+				(substrate_hash_2, 2),
+				(substrate_hash_3, 1)
+			]
+		);
+		assert!(backend.is_block_indexed(substrate_hash_1).await);
+		assert!(backend.is_range_indexed(1, 3).await);
+
+		let logs: Vec<H256> = sqlx::query("SELECT DISTINCT substrate_block_hash FROM logs")
+			.map(|row: SqliteRow| H256::from_slice(&row.get::<Vec<u8>, _>(0)[..]))
+			.fetch_all(backend.pool())
+			.await
+			.expect("sql query must succeed");
+		assert_eq!(logs, vec![substrate_hash_3]);
+
+		// The block metadata is kept.
+		assert_blocks_canon(
+			backend.pool(),
+			vec![
+				(substrate_hash_1, 1),
+				(substrate_hash_2, 1),
+				(substrate_hash_3, 1),
 			],
-			expected_result: vec![log_1_badc_2_0_alice.into()],
-		};
-		let result = run_test_case(backend, &filter).await.expect("must succeed");
-		assert_eq!(result, filter.expected_result);
+		)
+		.await;
 	}
 
 	#[tokio::test]
-	async fn block_range_works() {
+	async fn test_prune_non_canon_blocks_before_deletes_retracted_blocks() {
 		let TestData {
 			backend,
-			log_1_abcd_0_0_alice,
-			log_1_dcba_1_0_alice,
-			log_1_badc_2_0_alice,
-			log_2_abcd_0_0_bob,
-			log_2_dcba_1_0_bob,
-			log_2_badc_2_0_bob,
+			substrate_hash_1,
+			substrate_hash_2,
+			substrate_hash_3,
 			..
 		} = prepare().await;
 
-		let filter = TestFilter {
-			from_block: 0,
-			to_block: 2,
-			addresses: vec![],
-			topics: vec![],
-			expected_result: vec![
-				log_1_abcd_0_0_alice.into(),
-				log_1_dcba_1_0_alice.into(),
-				log_1_badc_2_0_alice.into(),
-				log_2_abcd_0_0_bob.into(),
-				log_2_dcba_1_0_bob.into(),
-				log_2_badc_2_0_bob.into(),
-			],
-		};
-		let result = run_test_case(backend, &filter).await.expect("must succeed");
-		assert_eq!(result, filter.expected_result);
+		sqlx::query("UPDATE blocks SET is_canon = 0 WHERE substrate_block_hash = ?")
+			.bind(substrate_hash_2.as_bytes())
+			.execute(backend.pool())
+			.await
+			.expect("sql query must succeed");
+
+		// Non-canonical blocks at or above the finalized height are kept.
+		let client = TestClientBuilder::new().build();
+		assert_eq!(
+			backend
+				.prune_non_canon_blocks_before(&client, 2, 10)
+				.await
+				.expect("must succeed"),
+			0
+		);
+		assert_eq!(
+			backend
+				.prune_non_canon_blocks_before(&client, 3, 10)
+				.await
+				.expect("must succeed"),
+			1
+		);
+
+		let logs: Vec<H256> = sqlx::query("SELECT DISTINCT substrate_block_hash FROM logs")
+			.map(|row: SqliteRow| H256::from_slice(&row.get::<Vec<u8>, _>(0)[..]))
+			.fetch_all(backend.pool())
+			.await
+			.expect("sql query must succeed");
+		assert!(!logs.contains(&substrate_hash_2));
+
+		assert_blocks_canon(
+			backend.pool(),
+			vec![(substrate_hash_1, 1), (substrate_hash_3, 1)],
+		)
+		.await;
 	}
 
 	#[tokio::test]
-	async fn address_filter_works() {
+	async fn test_prune_non_canon_blocks_before_keeps_canonical_blocks() {
 		let TestData {
 			backend,
-			alice,
-			log_1_abcd_0_0_alice,
-			log_1_dcba_1_0_alice,
-			log_1_badc_2_0_alice,
+			substrate_hash_1,
+			substrate_hash_2,
+			substrate_hash_3,
 			..
 		} = prepare().await;
-		let filter = TestFilter {
-			from_block: 0,
-			to_block: 3,
-			addresses: vec![alice],
-			topics: vec![],
-			expected_result: vec![
-				log_1_abcd_0_0_alice.into(),
-				log_1_dcba_1_0_alice.into(),
-				log_1_badc_2_0_alice.into(),
+
+		// The genesis block of the client, indexed with a stale canon status.
+		let client = TestClientBuilder::new().build();
+		let genesis_hash = client.info().genesis_hash;
+		sqlx::query(
+			"INSERT INTO blocks(
+				block_number,
+				ethereum_block_hash,
+				substrate_block_hash,
+				ethereum_storage_schema,
+				is_canon
+			) VALUES (0, ?, ?, ?, 0)",
+		)
+		.bind(H256::repeat_byte(0x0b).as_bytes())
+		.bind(genesis_hash.as_bytes())
+		.bind(EthereumStorageSchema::V3.encode())
+		.execute(backend.pool())
+		.await
+		.expect("sql query must succeed");
+
+		assert_eq!(
+			backend
+				.prune_non_canon_blocks_before(&client, 1, 10)
+				.await
+				.expect("must succeed"),
+			0
+		);
+		assert_blocks_canon(
+			backend.pool(),
+			vec![
+				(substrate_hash_1, 1),
+				(substrate_hash_2, 1),
+				(substrate_hash_3, 1),
+				(genesis_hash, 1),
 			],
-		};
-		let result = run_test_case(backend, &filter).await.expect("must succeed");
-		assert_eq!(result, filter.expected_result);
+		)
+		.await;
 	}
 
 	#[tokio::test]
-	async fn topic_filter_works() {
+	async fn test_export_logs_filters_and_orders_canonical_logs() {
 		let TestData {
 			backend,
+			bob,
+			topics_a,
+			topics_b,
+			topics_c,
 			topics_d,
-			log_1_dcba_1_0_alice,
-			log_2_dcba_1_0_bob,
-			log_3_dcba_1_0_bob,
+			substrate_hash_2,
+			substrate_hash_3,
+			ethereum_hash_3,
 			..
 		} = prepare().await;
-		let filter = TestFilter {
-			from_block: 0,
-			to_block: 3,
-			addresses: vec![],
-			topics: vec![vec![Some(topics_d)]],
-			expected_result: vec![
-				log_1_dcba_1_0_alice.into(),
-				log_2_dcba_1_0_bob.into(),
-				log_3_dcba_1_0_bob.into(),
-			],
-		};
-		let result = run_test_case(backend, &filter).await.expect("must succeed");
-		assert_eq!(result, filter.expected_result);
+
+		sqlx::query("UPDATE blocks SET is_canon = 0 WHERE substrate_block_hash = ?")
+			.bind(substrate_hash_2.as_bytes())
+			.execute(backend.pool())
+			.await
+			.expect("sql query must succeed");
+
+		let logs = backend
+			.export_logs(1, 3, &[bob], Some(topics_a))
+			.await
+			.expect("must succeed");
+		assert_eq!(
+			logs,
+			vec![ExportedLog {
+				block_number: 3,
+				ethereum_block_hash: ethereum_hash_3,
+				substrate_block_hash: substrate_hash_3,
+				transaction_hash: None,
+				transaction_index: 0,
+				log_index: 0,
+				address: bob,
+				topics: vec![topics_a, topics_b, topics_c, topics_d],
+			}]
+		);
+
+		let logs = backend
+			.export_logs(1, 3, &[], None)
+			.await
+			.expect("must succeed");
+		assert_eq!(
+			logs.iter()
+				.map(|log| (log.block_number, log.log_index))
+				.collect::<Vec<_>>(),
+			vec![(1, 0), (1, 1), (1, 2), (3, 0), (3, 1), (3, 2)]
+		);
 	}
 
 	#[tokio::test]
-	async fn test_filters_address_and_topic() {
+	async fn test_stats_report_indexed_range_and_gaps() {
 		let TestData {
 			backend,
-			bob,
-			topics_b,
-			log_2_badc_2_0_bob,
-			log_3_badc_2_0_bob,
+			substrate_hash_1,
+			substrate_hash_2,
+			substrate_hash_3,
 			..
 		} = prepare().await;
-		let filter = TestFilter {
-			from_block: 0,
-			to_block: 3,
-			addresses: vec![bob],
-			topics: vec![vec![Some(topics_b)]],
-			expected_result: vec![log_2_badc_2_0_bob.into(), log_3_badc_2_0_bob.into()],
-		};
-		let result = run_test_case(backend, &filter).await.expect("must succeed");
-		assert_eq!(result, filter.expected_result);
+
+		for hash in [substrate_hash_1, substrate_hash_3] {
+			sqlx::query("INSERT INTO sync_status(substrate_block_hash, status) VALUES (?, 1)")
+				.bind(hash.as_bytes())
+				.execute(backend.pool())
+				.await
+				.expect("sql query must succeed");
+		}
+		sqlx::query("UPDATE blocks SET is_canon = 0 WHERE substrate_block_hash = ?")
+			.bind(substrate_hash_2.as_bytes())
+			.execute(backend.pool())
+			.await
+			.expect("sql query must succeed");
+
+		let stats = backend.stats(10).await.expect("must succeed");
+		assert!(stats.size > 0);
+		assert!(stats.rows.contains(&("blocks", 3)));
+		assert!(stats.rows.contains(&("sync_status", 2)));
+		assert_eq!(stats.indexed_range, Some((1, 3)));
+		assert_eq!(stats.non_canon_blocks, 1);
+		assert_eq!(stats.gaps, vec![(2, 2)]);
 	}
 
 	#[tokio::test]
-	async fn test_filters_multi_address_and_topic() {
+	async fn test_prioritize_range_queues_blocks_not_indexed() {
+		use fc_api::LogIndexerBackend;
+
 		let TestData {
 			backend,
-			alice,
-			bob,
-			topics_b,
-			log_1_badc_2_0_alice,
-			log_2_badc_2_0_bob,
-			log_3_badc_2_0_bob,
+			substrate_hash_1,
+			substrate_hash_2,
+			substrate_hash_3,
 			..
 		} = prepare().await;
-		let filter = TestFilter {
-			from_block: 0,
-			to_block: 3,
-			addresses: vec![alice, bob],
-			topics: vec![vec![Some(topics_b)]],
-			expected_result: vec![
-				log_1_badc_2_0_alice.into(),
-				log_2_badc_2_0_bob.into(),
-				log_3_badc_2_0_bob.into(),
-			],
-		};
-		let result = run_test_case(backend, &filter).await.expect("must succeed");
-		assert_eq!(result, filter.expected_result);
+
+		backend.prioritize_range(1, 3).await;
+		assert_eq!(backend.pop_priority_range(), Some((1, 3)));
+		assert_eq!(backend.pop_priority_range(), None);
+
+		// Ranges too large are left to the regular indexing.
+		backend.prioritize_range(1, MAX_PRIORITY_RANGE + 1).await;
+		assert_eq!(backend.pop_priority_range(), None);
+
+		for hash in [substrate_hash_1, substrate_hash_2, substrate_hash_3] {
+			sqlx::query("INSERT INTO sync_status(substrate_block_hash, status) VALUES (?, 1)")
+				.bind(hash.as_bytes())
+				.execute(backend.pool())
+				.await
+				.expect("sql query must succeed");
+		}
+
+		backend.prioritize_range(1, 3).await;
+		assert_eq!(backend.pop_priority_range(), None);
 	}
 
 	#[tokio::test]
-	async fn test_filters_multi_address_and_multi_topic() {
-		let TestData {
-			backend,
-			alice,
-			bob,
-			topics_a,
-			topics_b,
-			log_1_abcd_0_0_alice,
-			log_2_abcd_0_0_bob,
-			log_3_abcd_0_0_bob,
-			..
-		} = prepare().await;
-		let filter = TestFilter {
-			from_block: 0,
-			to_block: 3,
-			addresses: vec![alice, bob],
-			topics: vec![vec![Some(topics_a), Some(topics_b)]],
-			expected_result: vec![
-				log_1_abcd_0_0_alice.into(),
-				log_2_abcd_0_0_bob.into(),
-				log_3_abcd_0_0_bob.into(),
-			],
-		};
-		let result = run_test_case(backend, &filter).await.expect("must succeed");
-		assert_eq!(result, filter.expected_result);
+	async fn test_event_signatures_are_registered_by_topic() {
+		let TestData { backend, .. } = prepare().await;
+		let transfer = "Transfer(address indexed from, address indexed to, uint256 value)";
+		backend
+			.register_event_signatures(&[transfer.to_string()])
+			.await
+			.expect("must succeed");
+		assert!(backend
+			.register_event_signatures(&["Transfer(address".to_string()])
+			.await
+			.is_err());
+
+		let topic = EventSignature::parse(transfer)
+			.expect("valid fragment")
+			.topic();
+		let signatures = backend
+			.log_indexer()
+			.event_signatures(&[topic, H256::repeat_byte(0x01)])
+			.await
+			.expect("must succeed");
+		assert_eq!(signatures, vec![(topic, transfer.to_string())]);
 	}
 
 	#[tokio::test]
-	async fn filter_with_topic_wildcards_works() {
-		let TestData {
-			backend,
-			alice,
-			bob,
-			topics_d,
-			topics_b,
-			log_1_dcba_1_0_alice,
-			log_2_dcba_1_0_bob,
-			log_3_dcba_1_0_bob,
-			..
-		} = prepare().await;
-		let filter = TestFilter {
-			from_block: 0,
-			to_block: 3,
-			addresses: vec![alice, bob],
-			topics: vec![vec![Some(topics_d), None, Some(topics_b)]],
-			expected_result: vec![
-				log_1_dcba_1_0_alice.into(),
-				log_2_dcba_1_0_bob.into(),
-				log_3_dcba_1_0_bob.into(),
-			],
-		};
-		let result = run_test_case(backend, &filter).await.expect("must succeed");
-		assert_eq!(result, filter.expected_result);
+	async fn test_backfill_ranges_are_claimed_once() {
+		let TestData { backend, .. } = prepare().await;
+		backend
+			.schedule_backfill(24, 10)
+			.await
+			.expect("must succeed");
+
+		let mut claimed = Vec::new();
+		while let Some(range) = backend.claim_backfill_range().await.expect("must succeed") {
+			claimed.push(range);
+		}
+		assert_eq!(claimed, vec![(20, 24), (10, 19), (0, 9)]);
+
+		// Completed ranges are not released on reschedule, unfinished claims are.
+		backend
+			.complete_backfill_range(20)
+			.await
+			.expect("must succeed");
+		backend
+			.schedule_backfill(30, 10)
+			.await
+			.expect("must succeed");
+		assert_eq!(
+			backend.claim_backfill_range().await.expect("must succeed"),
+			Some((25, 30))
+		);
+		assert_eq!(
+			backend.claim_backfill_range().await.expect("must succeed"),
+			Some((10, 19))
+		);
 	}
 
 	#[tokio::test]
-	async fn trailing_wildcard_is_useless_but_works() {
-		let TestData {
-			alice,
-			backend,
-			topics_b,
-			log_1_dcba_1_0_alice,
-			..
-		} = prepare().await;
-		let filter = TestFilter {
-			from_block: 0,
-			to_block: 1,
-			addresses: vec![alice],
-			topics: vec![vec![None, None, Some(topics_b), None]],
-			expected_result: vec![log_1_dcba_1_0_alice.into()],
+	async fn test_history_horizon_bounds_missing_blocks_and_backfill() {
+		let TestData { backend, .. } = prepare().await;
+		assert_eq!(backend.history_horizon(), 0);
+		assert_eq!(backend.get_first_missing_canon_block().await, Some(0));
+
+		backend.set_history_horizon(1).await.expect("must succeed");
+		assert_eq!(backend.history_horizon(), 1);
+		assert_eq!(fc_api::Backend::history_horizon(&backend).await, Ok(1));
+		assert_eq!(backend.get_first_missing_canon_block().await, None);
+
+		backend
+			.schedule_backfill(24, 10)
+			.await
+			.expect("must succeed");
+		let mut claimed = Vec::new();
+		while let Some(range) = backend.claim_backfill_range().await.expect("must succeed") {
+			claimed.push(range);
+		}
+		assert_eq!(claimed, vec![(21, 24), (11, 20), (1, 10)]);
+	}
+
+	#[tokio::test]
+	async fn test_fee_history_is_persisted() {
+		use fc_api::FeeHistoryBackend;
+
+		let TestData { backend, .. } = prepare().await;
+		let entry = |base_fee: u64| FeeHistoryEntry {
+			base_fee,
+			gas_used_ratio: 0.5,
+			rewards: vec![0, base_fee, u64::MAX],
 		};
-		let result = run_test_case(backend, &filter).await.expect("must succeed");
-		assert_eq!(result, filter.expected_result);
+		for block_number in 1..=3 {
+			backend
+				.write_fee_history(block_number, &entry(block_number * 10))
+				.await
+				.expect("must succeed");
+		}
+		// Overwrites the entry of a re-organized block.
+		backend
+			.write_fee_history(3, &entry(u64::MAX))
+			.await
+			.expect("must succeed");
+		backend
+			.remove_fee_history(&[1])
+			.await
+			.expect("must succeed");
+
+		assert_eq!(
+			backend.fee_history(0, 10).await,
+			Ok(vec![(2, entry(20)), (3, entry(u64::MAX))])
+		);
+		assert_eq!(
+			backend.fee_history(3, 3).await,
+			Ok(vec![(3, entry(u64::MAX))])
+		);
 	}
 
 	#[tokio::test]
-	async fn filter_with_multi_topic_options_works() {
+	async fn test_transaction_receipt_metadata_works() {
 		let TestData {
 			backend,
-			topics_a,
-			topics_d,
-			log_1_abcd_0_0_alice,
-			log_1_dcba_1_0_alice,
-			log_2_abcd_0_0_bob,
-			log_2_dcba_1_0_bob,
-			log_3_abcd_0_0_bob,
-			log_3_dcba_1_0_bob,
+			substrate_hash_1,
+			ethereum_hash_1,
 			..
 		} = prepare().await;
-		let filter = TestFilter {
-			from_block: 0,
-			to_block: 3,
-			addresses: vec![],
-			topics: vec![
-				vec![Some(topics_a)],
-				vec![Some(topics_d)],
-				vec![Some(topics_d)], // duplicate, ignored
-			],
-			expected_result: vec![
-				log_1_abcd_0_0_alice.into(),
-				log_1_dcba_1_0_alice.into(),
-				log_2_abcd_0_0_bob.into(),
-				log_2_dcba_1_0_bob.into(),
-				log_3_abcd_0_0_bob.into(),
-				log_3_dcba_1_0_bob.into(),
-			],
+
+		let receipt = TransactionReceiptMetadata {
+			status: 1,
+			cumulative_gas_used: U256::from(42_000),
+			gas_used: U256::from(21_000),
+			effective_gas_price: U256::from(1_000_000_000u64),
+			contract_address: Some(H160::repeat_byte(0x05)),
 		};
-		let result = run_test_case(backend, &filter).await.expect("must succeed");
-		assert_eq!(result, filter.expected_result);
+		let with_receipt = H256::repeat_byte(0x10);
+		let without_receipt = H256::repeat_byte(0x11);
+		sqlx::query(
+			"INSERT INTO transactions(
+				ethereum_transaction_hash,
+				substrate_block_hash,
+				ethereum_block_hash,
+				ethereum_transaction_index,
+				status,
+				cumulative_gas_used,
+				gas_used,
+				effective_gas_price,
+				contract_address)
+			VALUES (?, ?, ?, 0, ?, ?, ?, ?, ?)",
+		)
+		.bind(with_receipt.as_bytes())
+		.bind(substrate_hash_1.as_bytes())
+		.bind(ethereum_hash_1.as_bytes())
+		.bind(receipt.status as i32)
+		.bind(gas_to_i64(receipt.cumulative_gas_used))
+		.bind(gas_to_i64(receipt.gas_used))
+		.bind(u256_to_bytes(receipt.effective_gas_price))
+		.bind(H160::repeat_byte(0x05).as_bytes())
+		.execute(backend.pool())
+		.await
+		.expect("sql query must succeed");
+		sqlx::query(
+			"INSERT INTO transactions(
+				ethereum_transaction_hash,
+				substrate_block_hash,
+				ethereum_block_hash,
+				ethereum_transaction_index)
+			VALUES (?, ?, ?, 1)",
+		)
+		.bind(without_receipt.as_bytes())
+		.bind(substrate_hash_1.as_bytes())
+		.bind(ethereum_hash_1.as_bytes())
+		.execute(backend.pool())
+		.await
+		.expect("sql query must succeed");
+
+		assert_eq!(
+			backend
+				.transaction_receipt_metadata(&with_receipt, &substrate_hash_1)
+				.await
+				.expect("must succeed"),
+			Some(receipt)
+		);
+		// Rows indexed before the receipt columns were introduced have no receipt data.
+		assert_eq!(
+			backend
+				.transaction_receipt_metadata(&without_receipt, &substrate_hash_1)
+				.await
+				.expect("must succeed"),
+			None
+		);
 	}
 
 	#[tokio::test]
-	async fn filter_with_multi_topic_options_and_wildcards_works() {
+	async fn test_transactions_by_address_works() {
 		let TestData {
 			backend,
+			alice,
 			bob,
-			topics_a,
-			topics_b,
-			topics_c,
-			topics_d,
-			log_2_dcba_1_0_bob,
-			log_2_badc_2_0_bob,
-			log_3_dcba_1_0_bob,
-			log_3_badc_2_0_bob,
+			substrate_hash_1,
+			substrate_hash_2,
+			substrate_hash_3,
+			ethereum_hash_1,
+			ethereum_hash_2,
+			ethereum_hash_3,
 			..
 		} = prepare().await;
-		let filter = TestFilter {
-			from_block: 0,
-			to_block: 3,
-			addresses: vec![bob],
-			// Product on input [null,null,(b,d),(a,c)].
-			topics: vec![
-				vec![None, None, Some(topics_b), Some(topics_a)],
-				vec![None, None, Some(topics_b), Some(topics_c)],
-				vec![None, None, Some(topics_d), Some(topics_a)],
-				vec![None, None, Some(topics_d), Some(topics_c)],
-			],
-			expected_result: vec![
-				log_2_dcba_1_0_bob.into(),
-				log_2_badc_2_0_bob.into(),
-				log_3_dcba_1_0_bob.into(),
-				log_3_badc_2_0_bob.into(),
-			],
+
+		// Alice sends to Bob in block 1, Bob to Alice in block 2 and Bob creates a contract
+		// in block 3.
+		let transactions = [
+			(
+				H256::repeat_byte(0x21),
+				substrate_hash_1,
+				ethereum_hash_1,
+				alice,
+				Some(bob),
+			),
+			(
+				H256::repeat_byte(0x22),
+				substrate_hash_2,
+				ethereum_hash_2,
+				bob,
+				Some(alice),
+			),
+			(
+				H256::repeat_byte(0x23),
+				substrate_hash_3,
+				ethereum_hash_3,
+				bob,
+				None,
+			),
+		];
+		for (hash, substrate_hash, ethereum_hash, from, to) in transactions {
+			sqlx::query(
+				"INSERT INTO transactions(
+					ethereum_transaction_hash,
+					substrate_block_hash,
+					ethereum_block_hash,
+					ethereum_transaction_index,
+					from_address,
+					to_address,
+					value)
+				VALUES (?, ?, ?, 0, ?, ?, ?)",
+			)
+			.bind(hash.as_bytes())
+			.bind(substrate_hash.as_bytes())
+			.bind(ethereum_hash.as_bytes())
+			.bind(from.as_bytes())
+			.bind(to.map(|to| to.as_bytes().to_owned()))
+			.bind(u256_to_bytes(U256::from(100)))
+			.execute(backend.pool())
+			.await
+			.expect("sql query must succeed");
+		}
+
+		let hashes = |transactions: Vec<AddressTransaction<OpaqueBlock>>| {
+			transactions
+				.into_iter()
+				.map(|transaction| transaction.ethereum_transaction_hash)
+				.collect::<Vec<_>>()
 		};
-		let result = run_test_case(backend, &filter).await.expect("must succeed");
-		assert_eq!(result, filter.expected_result);
+		let alice_transactions = backend
+			.transactions_by_address(&alice, 0, 10)
+			.await
+			.expect("must succeed");
+		assert_eq!(alice_transactions[0].value, U256::from(100));
+		assert_eq!(
+			hashes(alice_transactions),
+			vec![H256::repeat_byte(0x22), H256::repeat_byte(0x21)]
+		);
+		// Paginated, most recent first.
+		assert_eq!(
+			hashes(
+				backend
+					.transactions_by_address(&bob, 1, 1)
+					.await
+					.expect("must succeed")
+			),
+			vec![H256::repeat_byte(0x22)]
+		);
+		// Non-canonical blocks are skipped.
+		sqlx::query("UPDATE blocks SET is_canon = 0 WHERE substrate_block_hash = ?")
+			.bind(substrate_hash_3.as_bytes())
+			.execute(backend.pool())
+			.await
+			.expect("sql query must succeed");
+		assert_eq!(
+			hashes(
+				backend
+					.transactions_by_address(&bob, 0, 10)
+					.await
+					.expect("must succeed")
+			),
+			vec![H256::repeat_byte(0x22), H256::repeat_byte(0x21)]
+		);
 	}
 
 	#[tokio::test]
-	async fn test_canonicalize_sets_canon_flag_for_redacted_and_enacted_blocks_correctly() {
+	async fn test_contract_creation_works() {
 		let TestData {
 			backend,
-			substrate_hash_1,
+			alice,
 			substrate_hash_2,
 			substrate_hash_3,
+			ethereum_hash_2,
+			ethereum_hash_3,
 			..
 		} = prepare().await;
 
-		// set block #1 to non canon
-		sqlx::query("UPDATE blocks SET is_canon = 0 WHERE substrate_block_hash = ?")
-			.bind(substrate_hash_1.as_bytes())
+		// The contract is deployed in block 2, the failed deployment in block 3 is ignored.
+		let contract = H160::repeat_byte(0x05);
+		let deployments = [
+			(
+				H256::repeat_byte(0x31),
+				substrate_hash_2,
+				ethereum_hash_2,
+				1,
+			),
+			(
+				H256::repeat_byte(0x32),
+				substrate_hash_3,
+				ethereum_hash_3,
+				0,
+			),
+		];
+		for (hash, substrate_hash, ethereum_hash, status) in deployments {
+			sqlx::query(
+				"INSERT INTO transactions(
+					ethereum_transaction_hash,
+					substrate_block_hash,
+					ethereum_block_hash,
+					ethereum_transaction_index,
+					status,
+					contract_address,
+					from_address)
+				VALUES (?, ?, ?, 0, ?, ?, ?)",
+			)
+			.bind(hash.as_bytes())
+			.bind(substrate_hash.as_bytes())
+			.bind(ethereum_hash.as_bytes())
+			.bind(status)
+			.bind(contract.as_bytes())
+			.bind(alice.as_bytes())
 			.execute(backend.pool())
 			.await
 			.expect("sql query must succeed");
-		assert_blocks_canon(
-			backend.pool(),
-			vec![
-				(substrate_hash_1, 0),
-				(substrate_hash_2, 1),
-				(substrate_hash_3, 1),
-			],
-		)
-		.await;
+		}
 
-		backend
-			.canonicalize(&[substrate_hash_2], &[substrate_hash_1])
+		assert_eq!(
+			backend
+				.contract_creation(&contract)
+				.await
+				.expect("must succeed"),
+			Some(ContractCreation {
+				ethereum_transaction_hash: H256::repeat_byte(0x31),
+				substrate_block_hash: substrate_hash_2,
+				ethereum_block_hash: ethereum_hash_2,
+				block_number: 2,
+				ethereum_index: 0,
+				creator: alice,
+			})
+		);
+		assert_eq!(
+			backend
+				.contract_creation(&H160::repeat_byte(0x06))
+				.await
+				.expect("must succeed"),
+			None
+		);
+	}
+
+	#[tokio::test]
+	async fn test_read_only_backend_reads_but_rejects_writes() {
+		let tmp = tempdir().expect("create a temporary directory");
+		let (client, _) = TestClientBuilder::new()
+			.add_extra_storage(
+				PALLET_ETHEREUM_SCHEMA.to_vec(),
+				Encode::encode(&EthereumStorageSchema::V3),
+			)
+			.build_with_native_executor::<substrate_test_runtime_client::runtime::RuntimeApi, _>(
+				None,
+			);
+		let storage_override = Arc::new(SchemaV3StorageOverride::new(Arc::new(client)));
+		let path = Path::new("sqlite:///").join(tmp.path()).join("test.db3");
+		let backend = |read_only| {
+			Backend::<OpaqueBlock>::new(
+				BackendConfig::Sqlite(SqliteBackendConfig {
+					path: path.to_str().unwrap(),
+					create_if_missing: true,
+					cache_size: 20480,
+					thread_count: 4,
+					read_only,
+					..Default::default()
+				}),
+				1,
+				None,
+				10000,
+				storage_override.clone(),
+			)
+		};
+
+		// The database must be created by a writer first.
+		assert!(backend(true).await.is_err());
+		let writer = backend(false).await.expect("indexer pool to be created");
+		let transfer = "Transfer(address indexed from, address indexed to, uint256 value)";
+		writer
+			.register_event_signatures(&[transfer.to_string()])
 			.await
 			.expect("must succeed");
 
-		assert_blocks_canon(
-			backend.pool(),
-			vec![
-				(substrate_hash_1, 1),
-				(substrate_hash_2, 0),
-				(substrate_hash_3, 1),
-			],
-		)
-		.await;
+		let reader = backend(true).await.expect("indexer pool to be created");
+		assert!(reader.is_read_only());
+		let topic = EventSignature::parse(transfer)
+			.expect("valid fragment")
+			.topic();
+		assert_eq!(
+			reader
+				.log_indexer()
+				.event_signatures(&[topic])
+				.await
+				.expect("must succeed"),
+			vec![(topic, transfer.to_string())]
+		);
+		assert!(reader
+			.register_event_signatures(&[transfer.to_string()])
+			.await
+			.is_err());
+	}
+
+	#[tokio::test]
+	async fn test_migrations_are_applied_once() {
+		let TestData { backend, .. } = prepare().await;
+		let latest_version = migrations::MIGRATIONS
+			.last()
+			.expect("migrations are not empty")
+			.version;
+		assert_eq!(
+			migrations::schema_version(backend.pool())
+				.await
+				.expect("must succeed"),
+			latest_version
+		);
+
+		migrations::migrate(backend.pool())
+			.await
+			.expect("must succeed");
+		let applied: Vec<u32> = sqlx::query("SELECT version FROM migrations ORDER BY version")
+			.map(|row: SqliteRow| row.get(0))
+			.fetch_all(backend.pool())
+			.await
+			.expect("sql query must succeed");
+		assert_eq!(applied, (1..=latest_version).collect::<Vec<_>>());
+	}
+
+	#[tokio::test]
+	async fn test_maintenance_analyzes_the_database() {
+		let TestData { backend, .. } = prepare().await;
+
+		backend.run_maintenance().await.expect("maintenance to run");
+
+		// `ANALYZE` stores the index statistics used by the query planner.
+		let analyzed: i64 = sqlx::query("SELECT COUNT(*) FROM sqlite_stat1")
+			.fetch_one(backend.pool())
+			.await
+			.expect("statistics to be stored")
+			.get(0);
+		assert!(analyzed > 0);
+		assert_eq!(backend.indexed_blocks_since_maintenance(), 0);
+	}
+
+	#[tokio::test]
+	async fn test_statement_cache_stats_count_prepared_statements() {
+		let TestData {
+			backend,
+			ethereum_hash_1,
+			..
+		} = prepare().await;
+		assert_eq!(
+			backend.statement_cache_stats(),
+			StatementCacheStats::default()
+		);
+
+		// The pool has a single connection, preparing each statement once.
+		for _ in 0..3 {
+			let _ = backend.block_hash(&ethereum_hash_1).await;
+		}
+		let _ = backend.transaction_metadata(&H256::zero()).await;
+
+		let stats = backend.statement_cache_stats();
+		assert_eq!(stats, StatementCacheStats { hits: 2, misses: 2 });
+		assert_eq!(stats.hit_rate(), 0.5);
 	}
 
 	#[test]
@@ -1811,7 +4168,10 @@ ORDER BY b.block_number ASC, l.transaction_index ASC, l.log_index ASC
 LIMIT 10001";
 
 		let mut qb = QueryBuilder::new("");
-		let actual_query_sql = build_query(&mut qb, from_block, to_block, addresses, topics).sql();
+		let actual_query_sql = build_query(
+			&mut qb, from_block, to_block, addresses, topics, None, 10000,
+		)
+		.sql();
 		assert_eq!(expected_query_sql, actual_query_sql);
 	}
 }
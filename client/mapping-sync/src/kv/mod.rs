@@ -22,7 +22,10 @@ mod worker;
 
 pub use worker::MappingSyncWorker;
 
-use std::sync::Arc;
+use std::{
+	collections::{HashSet, VecDeque},
+	sync::Arc,
+};
 
 // Substrate
 use sc_client_api::backend::{Backend, StorageProvider};
@@ -42,6 +45,19 @@ pub fn sync_block<Block: BlockT, C: HeaderBackend<Block>>(
 	backend: &fc_db::kv::Backend<Block, C>,
 	header: &Block::Header,
 ) -> Result<(), String> {
+	match mapping_commitment(&*storage_override, header)? {
+		Some(mapping_commitment) => backend.mapping().write_hashes(mapping_commitment),
+		None => backend.mapping().write_none(header.hash()),
+	}
+}
+
+/// Derives the mapping of a block from its frontier digest, reading the ethereum block from
+/// the runtime storage if the digest only holds its hash. Returns `None` for the blocks
+/// without an ethereum block.
+fn mapping_commitment<Block: BlockT>(
+	storage_override: &dyn StorageOverride<Block>,
+	header: &Block::Header,
+) -> Result<Option<fc_db::kv::MappingCommitment<Block>>, String> {
 	let substrate_block_hash = header.hash();
 	match fp_consensus::find_log(header.digest()) {
 		Ok(log) => {
@@ -58,19 +74,12 @@ pub fn sync_block<Block: BlockT, C: HeaderBackend<Block>>(
 			};
 
 			match log {
-				Log::Pre(PreLog::Block(block)) => {
-					let mapping_commitment = gen_from_block(block);
-					backend.mapping().write_hashes(mapping_commitment)
-				}
+				Log::Pre(PreLog::Block(block)) => Ok(Some(gen_from_block(block))),
 				Log::Post(post_log) => match post_log {
-					PostLog::Hashes(hashes) => {
-						let mapping_commitment = gen_from_hashes(hashes);
-						backend.mapping().write_hashes(mapping_commitment)
-					}
-					PostLog::Block(block) => {
-						let mapping_commitment = gen_from_block(block);
-						backend.mapping().write_hashes(mapping_commitment)
+					PostLog::Hashes(hashes) | PostLog::HashesWithReceiptsRoot(hashes, _) => {
+						Ok(Some(gen_from_hashes(hashes)))
 					}
+					PostLog::Block(block) => Ok(Some(gen_from_block(block))),
 					PostLog::BlockHash(expect_eth_block_hash) => {
 						let ethereum_block = storage_override.current_block(substrate_block_hash);
 						match ethereum_block {
@@ -83,21 +92,85 @@ pub fn sync_block<Block: BlockT, C: HeaderBackend<Block>>(
 										db state ({got_eth_block_hash:?})"
 									))
 								} else {
-									let mapping_commitment = gen_from_block(block);
-									backend.mapping().write_hashes(mapping_commitment)
+									Ok(Some(gen_from_block(block)))
 								}
 							}
-							None => backend.mapping().write_none(substrate_block_hash),
+							None => Ok(None),
 						}
 					}
 				},
 			}
 		}
-		Err(FindLogError::NotFound) => backend.mapping().write_none(substrate_block_hash),
+		Err(FindLogError::NotFound) => Ok(None),
 		Err(FindLogError::MultipleLogs) => Err("Multiple logs found".to_string()),
 	}
 }
 
+/// Removes the mappings of the non-canonical block `header`.
+pub fn remove_block<Block: BlockT, C: HeaderBackend<Block>>(
+	storage_override: Arc<dyn StorageOverride<Block>>,
+	backend: &fc_db::kv::Backend<Block, C>,
+	header: &Block::Header,
+) -> Result<(), String> {
+	let substrate_block_hash = header.hash();
+	let hashes = match fp_consensus::find_log(header.digest()) {
+		Ok(Log::Pre(PreLog::Block(block))) | Ok(Log::Post(PostLog::Block(block))) => {
+			Hashes::from_block(block)
+		}
+		Ok(Log::Post(PostLog::Hashes(hashes) | PostLog::HashesWithReceiptsRoot(hashes, _))) => {
+			hashes
+		}
+		Ok(Log::Post(PostLog::BlockHash(block_hash))) => Hashes {
+			block_hash,
+			// The state of a pruned fork may be gone, its transactions then stay mapped.
+			transaction_hashes: storage_override
+				.current_block(substrate_block_hash)
+				.map(|block| Hashes::from_block(block).transaction_hashes)
+				.unwrap_or_default(),
+		},
+		Err(FindLogError::NotFound) => return backend.mapping().remove_none(substrate_block_hash),
+		Err(FindLogError::MultipleLogs) => return Err("Multiple logs found".to_string()),
+	};
+	backend
+		.mapping()
+		.remove_hashes(fc_db::kv::MappingCommitment {
+			block_hash: substrate_block_hash,
+			ethereum_block_hash: hashes.block_hash,
+			ethereum_transaction_hashes: hashes.transaction_hashes,
+		})
+}
+
+/// Removes the mappings of at most `limit` non-canonical blocks, walking each of the
+/// `stale_blocks` back to the canonical chain. The blocks left to walk stay queued.
+pub fn remove_non_canonical_blocks<Block: BlockT, C: HeaderBackend<Block>>(
+	client: &C,
+	storage_override: Arc<dyn StorageOverride<Block>>,
+	backend: &fc_db::kv::Backend<Block, C>,
+	stale_blocks: &mut VecDeque<Block::Hash>,
+	limit: usize,
+) -> Result<usize, String> {
+	let mut removed = 0;
+	while removed < limit {
+		let Some(hash) = stale_blocks.pop_front() else {
+			break;
+		};
+		let Some(header) = client.header(hash).map_err(|e| format!("{:?}", e))? else {
+			continue;
+		};
+		if client
+			.hash(*header.number())
+			.map_err(|e| format!("{:?}", e))?
+			== Some(hash)
+		{
+			continue;
+		}
+		remove_block(storage_override.clone(), backend, &header)?;
+		removed += 1;
+		stale_blocks.push_front(*header.parent_hash());
+	}
+	Ok(removed)
+}
+
 pub fn sync_genesis_block<Block: BlockT, C>(
 	client: &C,
 	backend: &fc_db::kv::Backend<Block, C>,
@@ -216,12 +289,26 @@ where
 			.meta()
 			.write_current_syncing_tips(current_syncing_tips)?;
 	}
+	notify_synced(
+		client,
+		&*sync_oracle,
+		&pubsub_notification_sinks,
+		operating_header.hash(),
+	);
+	Ok(true)
+}
+
+fn notify_synced<Block: BlockT, C: HeaderBackend<Block>>(
+	client: &C,
+	sync_oracle: &(dyn SyncOracle + Send + Sync + 'static),
+	pubsub_notification_sinks: &EthereumBlockNotificationSinks<EthereumBlockNotification<Block>>,
+	hash: Block::Hash,
+) {
 	// Notify on import and remove closed channels.
 	// Only notify when the node is node in major syncing.
 	let sinks = &mut pubsub_notification_sinks.lock();
 	sinks.retain(|sink| {
 		if !sync_oracle.is_major_syncing() {
-			let hash = operating_header.hash();
 			let is_new_best = client.info().best_hash == hash;
 			sink.unbounded_send(EthereumBlockNotification { is_new_best, hash })
 				.is_ok()
@@ -230,7 +317,6 @@ where
 			false
 		}
 	});
-	Ok(true)
 }
 
 pub fn sync_blocks<Block: BlockT, C, BE>(
@@ -271,6 +357,159 @@ where
 	Ok(synced_any)
 }
 
+/// Syncs up to `limit` blocks like [`sync_blocks`], deriving their mappings on `workers`
+/// threads while a single writer commits them in the order of the sequential sync.
+/// The genesis block and a batch cut short are left to the sequential sync.
+///
+/// Returns the number of blocks synced.
+pub fn sync_blocks_parallel<Block: BlockT, C, BE>(
+	client: &C,
+	substrate_backend: &BE,
+	storage_override: Arc<dyn StorageOverride<Block>>,
+	frontier_backend: &fc_db::kv::Backend<Block, C>,
+	limit: usize,
+	sync_from: <Block::Header as HeaderT>::Number,
+	strategy: SyncStrategy,
+	sync_oracle: Arc<dyn SyncOracle + Send + Sync + 'static>,
+	pubsub_notification_sinks: Arc<
+		EthereumBlockNotificationSinks<EthereumBlockNotification<Block>>,
+	>,
+	workers: usize,
+) -> Result<usize, String>
+where
+	C: ProvideRuntimeApi<Block>,
+	C::Api: EthereumRuntimeRPCApi<Block>,
+	C: HeaderBackend<Block> + StorageProvider<Block, BE>,
+	BE: Backend<Block>,
+{
+	let batch = if workers > 1 {
+		next_sync_batch(
+			client,
+			substrate_backend,
+			frontier_backend,
+			limit,
+			sync_from,
+			strategy,
+		)?
+	} else {
+		Vec::new()
+	};
+	if batch.is_empty() {
+		return sync_blocks(
+			client,
+			substrate_backend,
+			storage_override,
+			frontier_backend,
+			limit,
+			sync_from,
+			strategy,
+			sync_oracle,
+			pubsub_notification_sinks,
+		)
+		.map(usize::from);
+	}
+
+	let synced = batch.len();
+	let storage_override = &*storage_override;
+	let chunk_size = batch.len().div_ceil(workers);
+	let mapping_commitments = std::thread::scope(|scope| {
+		let handles = batch
+			.chunks(chunk_size)
+			.map(|chunk| {
+				scope.spawn(move || {
+					chunk
+						.iter()
+						.map(|(header, _)| mapping_commitment(storage_override, header))
+						.collect::<Vec<_>>()
+				})
+			})
+			.collect::<Vec<_>>();
+		handles
+			.into_iter()
+			.flat_map(|handle| {
+				handle
+					.join()
+					.expect("mapping derivation does not panic; qed")
+			})
+			.collect::<Vec<_>>()
+	});
+
+	for ((header, current_syncing_tips), mapping_commitment) in
+		batch.into_iter().zip(mapping_commitments)
+	{
+		match mapping_commitment? {
+			Some(mapping_commitment) => frontier_backend
+				.mapping()
+				.write_hashes(mapping_commitment)?,
+			None => frontier_backend.mapping().write_none(header.hash())?,
+		}
+		frontier_backend
+			.meta()
+			.write_current_syncing_tips(current_syncing_tips)?;
+		notify_synced(
+			client,
+			&*sync_oracle,
+			&pubsub_notification_sinks,
+			header.hash(),
+		);
+	}
+	Ok(synced)
+}
+
+/// Walks the syncing tips as the sequential sync would, without writing, returning up to
+/// `limit` headers to sync along with the syncing tips once each of them is synced.
+fn next_sync_batch<Block: BlockT, C, BE>(
+	client: &C,
+	substrate_backend: &BE,
+	frontier_backend: &fc_db::kv::Backend<Block, C>,
+	limit: usize,
+	sync_from: <Block::Header as HeaderT>::Number,
+	strategy: SyncStrategy,
+) -> Result<Vec<(Block::Header, Vec<Block::Hash>)>, String>
+where
+	C: HeaderBackend<Block>,
+	BE: Backend<Block>,
+{
+	let mut current_syncing_tips = frontier_backend.meta().current_syncing_tips()?;
+	if current_syncing_tips.is_empty() {
+		let mut leaves = substrate_backend
+			.blockchain()
+			.leaves()
+			.map_err(|e| format!("{:?}", e))?;
+		current_syncing_tips.append(&mut leaves);
+	}
+
+	let mut batch = Vec::new();
+	let mut batched = HashSet::new();
+	while batch.len() < limit {
+		let Some(checking_tip) = current_syncing_tips.pop() else {
+			break;
+		};
+		// Forks joining on a block not synced yet.
+		if batched.contains(&checking_tip) {
+			continue;
+		}
+		let Some(header) = fetch_header(
+			substrate_backend.blockchain(),
+			frontier_backend,
+			checking_tip,
+			sync_from,
+		)?
+		else {
+			continue;
+		};
+		if header.number() == &Zero::zero()
+			|| (SyncStrategy::Parachain == strategy && header.number() > &client.info().best_number)
+		{
+			break;
+		}
+		current_syncing_tips.push(*header.parent_hash());
+		batched.insert(checking_tip);
+		batch.push((header, current_syncing_tips.clone()));
+	}
+	Ok(batch)
+}
+
 pub fn fetch_header<Block: BlockT, C, BE>(
 	substrate_backend: &BE,
 	frontier_backend: &fc_db::kv::Backend<Block, C>,
@@ -16,7 +16,12 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use std::{pin::Pin, sync::Arc, time::Duration};
+use std::{
+	collections::VecDeque,
+	pin::Pin,
+	sync::Arc,
+	time::{Duration, Instant},
+};
 
 use futures::{
 	prelude::*,
@@ -27,17 +32,20 @@ use log::debug;
 // Substrate
 use sc_client_api::{
 	backend::{Backend, StorageProvider},
-	client::ImportNotifications,
+	client::{FinalityNotifications, ImportNotifications},
 };
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
 use sp_consensus::SyncOracle;
-use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT, UniqueSaturatedInto};
 // Frontier
 use fc_storage::StorageOverride;
 use fp_rpc::EthereumRuntimeRPCApi;
 
-use crate::SyncStrategy;
+use crate::{MappingSyncMetrics, SyncProgressReporter, SyncProgressSinks, SyncStrategy};
+
+/// Number of blocks synced per worker each time the worker fires, when syncing in parallel.
+const BLOCKS_PER_WORKER: usize = 16;
 
 pub struct MappingSyncWorker<Block: BlockT, C, BE> {
 	import_notifications: ImportNotifications<Block>,
@@ -57,6 +65,16 @@ pub struct MappingSyncWorker<Block: BlockT, C, BE> {
 	sync_oracle: Arc<dyn SyncOracle + Send + Sync + 'static>,
 	pubsub_notification_sinks:
 		Arc<crate::EthereumBlockNotificationSinks<crate::EthereumBlockNotification<Block>>>,
+
+	finality_notifications: Option<FinalityNotifications<Block>>,
+	stale_blocks: VecDeque<Block::Hash>,
+	cleanup_limit: usize,
+
+	workers: usize,
+	batch_size: Option<usize>,
+	time_budget: Option<Duration>,
+
+	progress: SyncProgressReporter,
 }
 
 impl<Block: BlockT, C, BE> Unpin for MappingSyncWorker<Block, C, BE> {}
@@ -77,6 +95,14 @@ impl<Block: BlockT, C, BE> MappingSyncWorker<Block, C, BE> {
 			crate::EthereumBlockNotificationSinks<crate::EthereumBlockNotification<Block>>,
 		>,
 	) -> Self {
+		// Recorded for the RPC to tell the blocks not mapped yet from the ones never mapped.
+		if let Err(err) = frontier_backend
+			.meta()
+			.write_history_horizon(sync_from.unique_saturated_into())
+		{
+			log::warn!(target: "mapping-sync", "Failed to record the history horizon: {err}");
+		}
+
 		Self {
 			import_notifications,
 			timeout,
@@ -94,7 +120,141 @@ impl<Block: BlockT, C, BE> MappingSyncWorker<Block, C, BE> {
 
 			sync_oracle,
 			pubsub_notification_sinks,
+
+			finality_notifications: None,
+			stale_blocks: VecDeque::new(),
+			cleanup_limit: 0,
+
+			workers: 1,
+			batch_size: None,
+			time_budget: None,
+
+			progress: Default::default(),
+		}
+	}
+
+	/// Sends the sync progress to the subscribers of `sinks` each time blocks are synced.
+	pub fn with_sync_progress(mut self, sinks: Arc<SyncProgressSinks>) -> Self {
+		self.progress.set_sinks(sinks);
+		self
+	}
+
+	/// Reports the sync progress, the cleaned up forks and the failures to `metrics`.
+	pub fn with_metrics(mut self, metrics: MappingSyncMetrics) -> Self {
+		self.progress.set_metrics(metrics);
+		self
+	}
+
+	/// Derives the mappings of the synced blocks on `workers` threads, a single writer
+	/// committing them in order.
+	pub fn with_workers(mut self, workers: usize) -> Self {
+		self.workers = workers.max(1);
+		self
+	}
+
+	/// Syncs up to `batch_size` blocks each time the worker fires, instead of the retry times,
+	/// or of 16 blocks per worker when syncing in parallel.
+	pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+		self.batch_size = Some(batch_size.max(1));
+		self
+	}
+
+	/// Keeps syncing batches each time the worker fires until `budget` is spent or no block
+	/// is left, instead of a single batch. The import notifications received meanwhile are
+	/// coalesced into the next firing.
+	pub fn with_time_budget(mut self, budget: Duration) -> Self {
+		self.time_budget = Some(budget);
+		self
+	}
+
+	/// Removes the mappings of the forks pruned on finality, at most `limit` blocks each
+	/// time the worker fires so that a long fork does not stall the syncing.
+	pub fn with_non_canonical_cleanup(
+		mut self,
+		finality_notifications: FinalityNotifications<Block>,
+		limit: usize,
+	) -> Self {
+		self.finality_notifications = Some(finality_notifications);
+		self.cleanup_limit = limit;
+		self
+	}
+}
+
+impl<Block, C, BE> MappingSyncWorker<Block, C, BE>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block>,
+	C::Api: EthereumRuntimeRPCApi<Block>,
+	C: HeaderBackend<Block> + StorageProvider<Block, BE>,
+	BE: Backend<Block>,
+{
+	/// Syncs a batch of blocks, then more while the time budget allows, returning the number
+	/// of blocks synced.
+	fn sync_within_budget(&self) -> Result<usize, String> {
+		let limit = match self.batch_size {
+			Some(batch_size) => batch_size,
+			None if self.workers > 1 => self.retry_times.max(self.workers * BLOCKS_PER_WORKER),
+			None => self.retry_times,
+		};
+		let started = Instant::now();
+		let mut synced = 0;
+		loop {
+			let batch = crate::kv::sync_blocks_parallel(
+				self.client.as_ref(),
+				self.substrate_backend.as_ref(),
+				self.storage_override.clone(),
+				self.frontier_backend.as_ref(),
+				limit,
+				self.sync_from,
+				self.strategy,
+				self.sync_oracle.clone(),
+				self.pubsub_notification_sinks.clone(),
+				self.workers,
+			)?;
+			synced += batch;
+			let within_budget = self
+				.time_budget
+				.is_some_and(|budget| started.elapsed() < budget);
+			if batch == 0 || !within_budget {
+				return Ok(synced);
+			}
+		}
+	}
+
+	fn report_progress(&mut self, synced: u64) {
+		if !self.progress.is_enabled() {
+			return;
 		}
+		let best_block: u64 = self.client.info().best_number.unique_saturated_into();
+		let sync_from: u64 = self.sync_from.unique_saturated_into();
+		let tips = self
+			.frontier_backend
+			.meta()
+			.current_syncing_tips()
+			.unwrap_or_default()
+			.into_iter()
+			.filter_map(|tip| {
+				self.substrate_backend
+					.blockchain()
+					.number(tip)
+					.ok()
+					.flatten()
+			})
+			.map(UniqueSaturatedInto::<u64>::unique_saturated_into)
+			.collect::<Vec<_>>();
+		// The tips are walked down to their first synced ancestor, which is not known in
+		// advance: the backlog is an upper bound.
+		let backlog = tips
+			.iter()
+			.map(|number| (number + 1).saturating_sub(sync_from))
+			.sum();
+		let current_block = tips
+			.iter()
+			.max()
+			.map(|number| number + 1)
+			.unwrap_or(best_block);
+		self.progress
+			.report(synced, current_block, best_block, backlog);
 	}
 }
 
@@ -121,6 +281,18 @@ where
 			}
 		}
 
+		while let Some(finality_notifications) = self.finality_notifications.as_mut() {
+			match Stream::poll_next(Pin::new(finality_notifications), cx) {
+				Poll::Pending => break,
+				Poll::Ready(Some(notification)) => {
+					self.stale_blocks
+						.extend(notification.stale_heads.iter().copied());
+					fire = true;
+				}
+				Poll::Ready(None) => self.finality_notifications = None,
+			}
+		}
+
 		let timeout = self.timeout;
 		let inner_delay = self.inner_delay.get_or_insert_with(|| Delay::new(timeout));
 
@@ -138,23 +310,42 @@ where
 		if fire {
 			self.inner_delay = None;
 
-			match crate::kv::sync_blocks(
-				self.client.as_ref(),
-				self.substrate_backend.as_ref(),
-				self.storage_override.clone(),
-				self.frontier_backend.as_ref(),
-				self.retry_times,
-				self.sync_from,
-				self.strategy,
-				self.sync_oracle.clone(),
-				self.pubsub_notification_sinks.clone(),
-			) {
-				Ok(have_next) => {
-					self.have_next = have_next;
+			match self.sync_within_budget() {
+				Ok(synced) => {
+					self.have_next = synced > 0;
+					if synced > 0 {
+						self.report_progress(synced as u64);
+					}
+					if !self.stale_blocks.is_empty() {
+						let this = &mut *self;
+						match crate::kv::remove_non_canonical_blocks(
+							this.client.as_ref(),
+							this.storage_override.clone(),
+							this.frontier_backend.as_ref(),
+							&mut this.stale_blocks,
+							this.cleanup_limit,
+						) {
+							Ok(removed) => {
+								if let Some(metrics) = this.progress.metrics() {
+									metrics.report_reorg(0, removed);
+								}
+							}
+							Err(e) => {
+								if let Some(metrics) = this.progress.metrics() {
+									metrics.report_error();
+								}
+								debug!(target: "mapping-sync", "Non-canonical cleanup failed with error {:?}", e);
+							}
+						}
+						self.have_next |= !self.stale_blocks.is_empty();
+					}
 					Poll::Ready(Some(()))
 				}
 				Err(e) => {
 					self.have_next = false;
+					if let Some(metrics) = self.progress.metrics() {
+						metrics.report_error();
+					}
 					debug!(target: "mapping-sync", "Syncing failed with error {:?}, retrying.", e);
 					Poll::Ready(Some(()))
 				}
@@ -178,7 +369,8 @@ mod tests {
 	use sp_core::{H160, H256, U256};
 	use sp_runtime::{generic::Header, traits::BlakeTwo256, Digest};
 	use substrate_test_runtime_client::{
-		ClientBlockImportExt, DefaultTestClientBuilderExt, TestClientBuilder, TestClientBuilderExt,
+		ClientBlockImportExt, ClientExt, DefaultTestClientBuilderExt, TestClientBuilder,
+		TestClientBuilderExt,
 	};
 	use tempfile::tempdir;
 
@@ -467,4 +659,236 @@ mod tests {
 			assert_eq!(sinks.len(), 0);
 		}
 	}
+
+	#[tokio::test]
+	async fn finalized_forks_mappings_are_removed() {
+		let tmp = tempdir().expect("create a temporary directory");
+		let builder = TestClientBuilder::new().add_extra_storage(
+			PALLET_ETHEREUM_SCHEMA.to_vec(),
+			Encode::encode(&EthereumStorageSchema::V3),
+		);
+		// Client
+		let (client, _) =
+			builder.build_with_native_executor::<frontier_template_runtime::RuntimeApi, _>(None);
+		let mut client = Arc::new(client);
+		// Overrides
+		let storage_override = Arc::new(SchemaV3StorageOverride::new(client.clone()));
+
+		let frontier_backend = Arc::new(
+			fc_db::kv::Backend::<OpaqueBlock, _>::new(
+				client.clone(),
+				&fc_db::kv::DatabaseSettings {
+					source: sc_client_db::DatabaseSource::RocksDb {
+						path: tmp.path().to_path_buf(),
+						cache_size: 0,
+					},
+				},
+			)
+			.expect("frontier backend"),
+		);
+
+		// Two forks of one block on top of genesis.
+		let genesis_hash = client.chain_info().genesis_hash;
+		let mut headers = Vec::new();
+		for _ in 0..2 {
+			let builder = BlockBuilderBuilder::new(&*client)
+				.on_parent_block(genesis_hash)
+				.with_parent_block_number(0)
+				.with_inherent_digests(ethereum_digest())
+				.build()
+				.unwrap();
+			let block = builder.build().unwrap().block;
+			headers.push(block.header.clone());
+			client.import(BlockOrigin::Own, block).await.unwrap();
+		}
+		for header in &headers {
+			crate::kv::sync_block(storage_override.clone(), &frontier_backend, header).unwrap();
+		}
+		let ethereum_block_hash =
+			|header: &Header<u64, BlakeTwo256>| match fp_consensus::find_log(header.digest()) {
+				Ok(fp_consensus::Log::Post(fp_consensus::PostLog::Hashes(hashes))) => {
+					hashes.block_hash
+				}
+				_ => panic!("a frontier post log"),
+			};
+		let (canonical, stale) = (&headers[0], &headers[1]);
+
+		client.finalize_block(canonical.hash(), None).unwrap();
+
+		let mut stale_blocks = VecDeque::from([stale.hash()]);
+		assert_eq!(
+			crate::kv::remove_non_canonical_blocks(
+				client.as_ref(),
+				storage_override.clone(),
+				&frontier_backend,
+				&mut stale_blocks,
+				8,
+			),
+			Ok(1)
+		);
+		// The walk stops at the canonical genesis.
+		assert!(stale_blocks.is_empty());
+
+		let mapping = frontier_backend.mapping();
+		assert_eq!(mapping.block_hash(&ethereum_block_hash(stale)), Ok(None));
+		assert_eq!(mapping.is_synced(&stale.hash()), Ok(false));
+		assert_eq!(
+			mapping.block_hash(&ethereum_block_hash(canonical)),
+			Ok(Some(vec![canonical.hash()]))
+		);
+		assert_eq!(mapping.is_synced(&canonical.hash()), Ok(true));
+	}
+
+	#[tokio::test]
+	async fn parallel_sync_maps_blocks_in_order() {
+		let tmp = tempdir().expect("create a temporary directory");
+		let builder = TestClientBuilder::new().add_extra_storage(
+			PALLET_ETHEREUM_SCHEMA.to_vec(),
+			Encode::encode(&EthereumStorageSchema::V3),
+		);
+		// Backend
+		let backend = builder.backend();
+		// Client
+		let (client, _) =
+			builder.build_with_native_executor::<frontier_template_runtime::RuntimeApi, _>(None);
+		let mut client = Arc::new(client);
+		// Overrides
+		let storage_override = Arc::new(SchemaV3StorageOverride::new(client.clone()));
+
+		let frontier_backend = Arc::new(
+			fc_db::kv::Backend::<OpaqueBlock, _>::new(
+				client.clone(),
+				&fc_db::kv::DatabaseSettings {
+					source: sc_client_db::DatabaseSource::RocksDb {
+						path: tmp.path().to_path_buf(),
+						cache_size: 0,
+					},
+				},
+			)
+			.expect("frontier backend"),
+		);
+
+		let mut headers = Vec::new();
+		for _ in 0..5 {
+			let chain_info = client.chain_info();
+			let builder = BlockBuilderBuilder::new(&*client)
+				.on_parent_block(chain_info.best_hash)
+				.with_parent_block_number(chain_info.best_number)
+				.with_inherent_digests(ethereum_digest())
+				.build()
+				.unwrap();
+			let block = builder.build().unwrap().block;
+			headers.push(block.header.clone());
+			client.import(BlockOrigin::Own, block).await.unwrap();
+		}
+
+		let sync = || {
+			crate::kv::sync_blocks_parallel(
+				client.as_ref(),
+				backend.as_ref(),
+				storage_override.clone(),
+				&frontier_backend,
+				8,
+				0,
+				SyncStrategy::Normal,
+				Arc::new(TestSyncOracleNotSyncing {}),
+				Default::default(),
+				3,
+			)
+		};
+		// The blocks down to the genesis one, which is synced on its own.
+		assert_eq!(sync(), Ok(5));
+		for header in &headers {
+			let ethereum_block_hash = match fp_consensus::find_log(header.digest()) {
+				Ok(fp_consensus::Log::Post(fp_consensus::PostLog::Hashes(hashes))) => {
+					hashes.block_hash
+				}
+				_ => panic!("a frontier post log"),
+			};
+			assert_eq!(
+				frontier_backend.mapping().block_hash(&ethereum_block_hash),
+				Ok(Some(vec![header.hash()]))
+			);
+		}
+		let genesis_hash = client.chain_info().genesis_hash;
+		assert_eq!(
+			frontier_backend.meta().current_syncing_tips(),
+			Ok(vec![genesis_hash])
+		);
+
+		assert_eq!(sync(), Ok(1));
+		assert_eq!(
+			frontier_backend.mapping().is_synced(&genesis_hash),
+			Ok(true)
+		);
+	}
+
+	#[tokio::test]
+	async fn time_budget_syncs_batches_until_done() {
+		let tmp = tempdir().expect("create a temporary directory");
+		let builder = TestClientBuilder::new().add_extra_storage(
+			PALLET_ETHEREUM_SCHEMA.to_vec(),
+			Encode::encode(&EthereumStorageSchema::V3),
+		);
+		// Backend
+		let backend = builder.backend();
+		// Client
+		let (client, _) =
+			builder.build_with_native_executor::<frontier_template_runtime::RuntimeApi, _>(None);
+		let mut client = Arc::new(client);
+		// Overrides
+		let storage_override = Arc::new(SchemaV3StorageOverride::new(client.clone()));
+
+		let frontier_backend = Arc::new(
+			fc_db::kv::Backend::<OpaqueBlock, _>::new(
+				client.clone(),
+				&fc_db::kv::DatabaseSettings {
+					source: sc_client_db::DatabaseSource::RocksDb {
+						path: tmp.path().to_path_buf(),
+						cache_size: 0,
+					},
+				},
+			)
+			.expect("frontier backend"),
+		);
+
+		for _ in 0..5 {
+			let chain_info = client.chain_info();
+			let builder = BlockBuilderBuilder::new(&*client)
+				.on_parent_block(chain_info.best_hash)
+				.with_parent_block_number(chain_info.best_number)
+				.with_inherent_digests(ethereum_digest())
+				.build()
+				.unwrap();
+			let block = builder.build().unwrap().block;
+			client.import(BlockOrigin::Own, block).await.unwrap();
+		}
+
+		let worker = MappingSyncWorker::new(
+			client.import_notification_stream(),
+			Duration::new(6, 0),
+			client.clone(),
+			backend,
+			storage_override,
+			frontier_backend.clone(),
+			3,
+			0,
+			SyncStrategy::Normal,
+			Arc::new(TestSyncOracleNotSyncing {}),
+			Default::default(),
+		)
+		.with_batch_size(1);
+
+		// A single batch without a time budget.
+		assert_eq!(worker.sync_within_budget(), Ok(1));
+		// The remaining blocks, genesis included, within the budget.
+		let worker = worker.with_time_budget(Duration::from_secs(60));
+		assert_eq!(worker.sync_within_budget(), Ok(5));
+		assert_eq!(
+			frontier_backend
+				.mapping()
+				.is_synced(&client.chain_info().genesis_hash),
+			Ok(true)
+		);
+	}
 }
@@ -20,9 +20,17 @@
 #![allow(clippy::too_many_arguments)]
 
 pub mod kv;
+mod metrics;
 #[cfg(feature = "sql")]
 pub mod sql;
 
+pub use self::metrics::MappingSyncMetrics;
+
+use std::{
+	sync::Arc,
+	time::{Duration, Instant},
+};
+
 use sp_runtime::traits::Block as BlockT;
 
 #[derive(Copy, Clone, Eq, PartialEq)]
@@ -39,3 +47,156 @@ pub struct EthereumBlockNotification<Block: BlockT> {
 	pub is_new_best: bool,
 	pub hash: Block::Hash,
 }
+
+/// Progress of a mapping sync worker, sent to the subscribers each time it synced blocks.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct SyncProgress {
+	/// Number of blocks synced since the worker started.
+	pub blocks_synced: u64,
+	/// Number of the block the worker is syncing from.
+	pub current_block: u64,
+	/// Number of the best block of the chain.
+	pub best_block: u64,
+	/// Estimated number of blocks left to sync.
+	pub backlog: u64,
+	/// Estimated time to sync the backlog at the rate since the worker started.
+	pub eta: Option<Duration>,
+}
+
+pub type SyncProgressSinks =
+	parking_lot::Mutex<Vec<sc_utils::mpsc::TracingUnboundedSender<SyncProgress>>>;
+
+/// Subscribes to the progress reported by the mapping sync workers sharing `sinks`.
+pub fn subscribe_sync_progress(
+	sinks: &SyncProgressSinks,
+) -> sc_utils::mpsc::TracingUnboundedReceiver<SyncProgress> {
+	let (sink, stream) = sc_utils::mpsc::tracing_unbounded("mapping_sync_progress_stream", 1_000);
+	sinks.lock().push(sink);
+	stream
+}
+
+/// Tracks the sync rate of a worker and sends its progress to the subscribers and metrics.
+pub(crate) struct SyncProgressReporter {
+	sinks: Option<Arc<SyncProgressSinks>>,
+	metrics: Option<MappingSyncMetrics>,
+	started: Instant,
+	last_report: Instant,
+	blocks_synced: u64,
+}
+
+impl Default for SyncProgressReporter {
+	fn default() -> Self {
+		Self {
+			sinks: None,
+			metrics: None,
+			started: Instant::now(),
+			last_report: Instant::now(),
+			blocks_synced: 0,
+		}
+	}
+}
+
+impl SyncProgressReporter {
+	pub(crate) fn new(
+		sinks: Option<Arc<SyncProgressSinks>>,
+		metrics: Option<MappingSyncMetrics>,
+	) -> Self {
+		Self {
+			sinks,
+			metrics,
+			..Default::default()
+		}
+	}
+
+	pub(crate) fn set_sinks(&mut self, sinks: Arc<SyncProgressSinks>) {
+		self.sinks = Some(sinks);
+	}
+
+	pub(crate) fn set_metrics(&mut self, metrics: MappingSyncMetrics) {
+		self.metrics = Some(metrics);
+	}
+
+	/// Whether the progress is reported anywhere.
+	pub(crate) fn is_enabled(&self) -> bool {
+		self.sinks.is_some() || self.metrics.is_some()
+	}
+
+	pub(crate) fn metrics(&self) -> Option<&MappingSyncMetrics> {
+		self.metrics.as_ref()
+	}
+
+	/// Records `synced` more blocks and reports the progress, removing the closed subscriptions.
+	pub(crate) fn report(
+		&mut self,
+		synced: u64,
+		current_block: u64,
+		best_block: u64,
+		backlog: u64,
+	) {
+		self.blocks_synced = self.blocks_synced.saturating_add(synced);
+		let eta = (self.blocks_synced > 0).then(|| {
+			self.started
+				.elapsed()
+				.mul_f64(backlog as f64 / self.blocks_synced as f64)
+		});
+		let progress = SyncProgress {
+			blocks_synced: self.blocks_synced,
+			current_block,
+			best_block,
+			backlog,
+			eta,
+		};
+		if let Some(metrics) = &self.metrics {
+			let elapsed = self.last_report.elapsed().as_secs_f64();
+			let blocks_per_second = if elapsed > 0.0 {
+				synced as f64 / elapsed
+			} else {
+				0.0
+			};
+			metrics.report_progress(&progress, blocks_per_second);
+		}
+		self.last_report = Instant::now();
+		if let Some(sinks) = &self.sinks {
+			sinks
+				.lock()
+				.retain(|sink| sink.unbounded_send(progress).is_ok());
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn sync_progress_is_sent_to_the_subscribers() {
+		let sinks = Arc::new(SyncProgressSinks::default());
+		let mut stream = subscribe_sync_progress(&sinks);
+		let mut reporter = SyncProgressReporter::new(Some(sinks.clone()), None);
+
+		reporter.report(0, 0, 10, 10);
+		reporter.report(4, 4, 10, 6);
+		assert_eq!(
+			stream
+				.try_recv()
+				.ok()
+				.map(|progress| (progress.blocks_synced, progress.eta)),
+			Some((0, None))
+		);
+		let progress = stream.try_recv().expect("progress is sent");
+		assert_eq!(
+			(
+				progress.blocks_synced,
+				progress.current_block,
+				progress.backlog
+			),
+			(4, 4, 6)
+		);
+		assert!(progress.eta.is_some());
+
+		// Closed subscriptions are removed.
+		drop(stream);
+		reporter.report(1, 5, 10, 5);
+		assert!(sinks.lock().is_empty());
+	}
+}
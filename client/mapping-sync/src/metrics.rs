@@ -0,0 +1,122 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use prometheus_endpoint::{register, Counter, Gauge, PrometheusError, Registry, F64, U64};
+
+use crate::SyncProgress;
+
+/// Prometheus metrics of the mapping sync worker.
+#[derive(Clone)]
+pub struct MappingSyncMetrics {
+	indexed_height: Gauge<U64>,
+	best_block_lag: Gauge<U64>,
+	blocks_per_second: Gauge<F64>,
+	enacted_blocks: Counter<U64>,
+	retracted_blocks: Counter<U64>,
+	errors: Counter<U64>,
+}
+
+impl MappingSyncMetrics {
+	pub fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			indexed_height: register(
+				Gauge::new(
+					"frontier_mapping_sync_indexed_height",
+					"Number of the block the mapping sync is syncing from.",
+				)?,
+				registry,
+			)?,
+			best_block_lag: register(
+				Gauge::new(
+					"frontier_mapping_sync_best_block_lag",
+					"Estimated number of blocks the mapping sync is behind the best block.",
+				)?,
+				registry,
+			)?,
+			blocks_per_second: register(
+				Gauge::new(
+					"frontier_mapping_sync_blocks_per_second",
+					"Number of blocks synced per second since the previous report.",
+				)?,
+				registry,
+			)?,
+			enacted_blocks: register(
+				Counter::new(
+					"frontier_mapping_sync_enacted_blocks",
+					"Number of blocks made canonical by a re-org.",
+				)?,
+				registry,
+			)?,
+			retracted_blocks: register(
+				Counter::new(
+					"frontier_mapping_sync_retracted_blocks",
+					"Number of blocks retracted by a re-org or pruned on finality.",
+				)?,
+				registry,
+			)?,
+			errors: register(
+				Counter::new(
+					"frontier_mapping_sync_errors",
+					"Number of failed sync attempts.",
+				)?,
+				registry,
+			)?,
+		})
+	}
+
+	pub(crate) fn report_progress(&self, progress: &SyncProgress, blocks_per_second: f64) {
+		self.indexed_height.set(progress.current_block);
+		self.best_block_lag.set(progress.backlog);
+		self.blocks_per_second.set(blocks_per_second);
+	}
+
+	pub(crate) fn report_reorg(&self, enacted: usize, retracted: usize) {
+		self.enacted_blocks.inc_by(enacted as u64);
+		self.retracted_blocks.inc_by(retracted as u64);
+	}
+
+	pub(crate) fn report_error(&self) {
+		self.errors.inc();
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::SyncProgressReporter;
+
+	#[test]
+	fn progress_is_reported_to_the_metrics() {
+		let registry = Registry::new();
+		let metrics = MappingSyncMetrics::register(&registry).expect("metrics are registered");
+		let mut reporter = SyncProgressReporter::new(None, Some(metrics.clone()));
+
+		reporter.report(3, 7, 10, 3);
+		metrics.report_reorg(1, 2);
+		metrics.report_error();
+
+		assert_eq!(metrics.indexed_height.get(), 7);
+		assert_eq!(metrics.best_block_lag.get(), 3);
+		assert!(metrics.blocks_per_second.get() > 0.0);
+		assert_eq!(metrics.enacted_blocks.get(), 1);
+		assert_eq!(metrics.retracted_blocks.get(), 2);
+		assert_eq!(metrics.errors.get(), 1);
+		// The metrics are registered once.
+		assert!(MappingSyncMetrics::register(&registry).is_err());
+	}
+}
@@ -29,7 +29,10 @@ use sp_runtime::traits::{Block as BlockT, Header as HeaderT, UniqueSaturatedInto
 // Frontier
 use fp_rpc::EthereumRuntimeRPCApi;
 
-use crate::{EthereumBlockNotification, EthereumBlockNotificationSinks, SyncStrategy};
+use crate::{
+	EthereumBlockNotification, EthereumBlockNotificationSinks, MappingSyncMetrics,
+	SyncProgressReporter, SyncProgressSinks, SyncStrategy,
+};
 
 /// Defines the commands for the sync worker.
 #[derive(Debug)]
@@ -40,6 +43,9 @@ pub enum WorkerCommand {
 	IndexLeaves(Vec<H256>),
 	/// Index the best block known so far via import notifications.
 	IndexBestBlock(H256),
+	/// Index the canonical blocks in `[from_block, to_block]`, in place of the import
+	/// notifications received while major syncing.
+	IndexCanonicalRange { from_block: u32, to_block: u32 },
 	/// Canonicalize the enacted and retracted blocks reported via import notifications.
 	Canonicalize {
 		common: H256,
@@ -50,14 +56,69 @@ pub enum WorkerCommand {
 	/// Check for any canon blocks that haven't had their logs indexed.
 	/// Check for any missing parent blocks from the latest canon block.
 	CheckIndexedBlocks,
+	/// Prune the indexed data falling out of the retention policy, in batches of
+	/// `batch_size` blocks.
+	PruneHistory {
+		policy: RetentionPolicy,
+		batch_size: u32,
+	},
+	/// Delete the retracted blocks below the finalized block, in batches of `batch_size`
+	/// blocks.
+	PruneNonCanon { batch_size: u32 },
+	/// Index the block ranges targeted by queries ahead of the others.
+	IndexPriorityRanges,
+	/// Vacuum, analyze and checkpoint the database.
+	Maintenance,
+}
+
+/// Defines how long the indexed data is kept around.
+#[derive(Clone, Copy, Debug)]
+pub enum RetentionPolicy {
+	/// Keep the data of the last `n` blocks.
+	Blocks(u32),
+	/// Keep the data of the blocks produced in the last `n` days.
+	Days(u32),
+}
+
+/// Defines when the database maintenance runs.
+#[derive(Clone, Copy, Debug)]
+pub enum MaintenanceSchedule {
+	/// Run the maintenance at the given interval.
+	Interval(Duration),
+	/// Run the maintenance once the given number of blocks were indexed since the last one.
+	IndexedBlocks(u64),
 }
 
 /// Config parameters for the SyncWorker.
 pub struct SyncWorkerConfig {
 	pub check_indexed_blocks_interval: Duration,
 	pub read_notification_timeout: Duration,
+	/// Retention policy of the indexed data. `None` keeps the whole history.
+	pub retention_policy: Option<RetentionPolicy>,
+	/// Maximum number of blocks pruned in a single db transaction.
+	pub prune_batch_size: u32,
+	/// Number of tasks indexing the historical blocks concurrently with the live indexing.
+	/// `0` disables the backfill, leaving the history to the missing blocks check.
+	pub backfill_workers: u32,
+	/// Schedule of the database maintenance. `None` leaves it to `PRAGMA optimize`.
+	pub maintenance: Option<MaintenanceSchedule>,
+	/// First block to index, the history below it is neither indexed nor served.
+	pub sync_from: u32,
+	/// Subscriptions to the indexing progress, reported each time a best block is indexed.
+	pub progress_sinks: Option<Arc<SyncProgressSinks>>,
+	/// Metrics of the indexing progress, re-orgs and failures.
+	pub metrics: Option<MappingSyncMetrics>,
+	/// Number of commands queued for the indexing task. Once full, the best block
+	/// notifications are coalesced until it catches up.
+	pub command_buffer_size: usize,
 }
 
+/// Number of blocks claimed at once by a backfill worker.
+const BACKFILL_RANGE_SIZE: u32 = 1000;
+
+/// Minimum number of canonical blocks indexed at once while major syncing.
+const MAJOR_SYNC_RANGE_SIZE: u32 = 256;
+
 /// Implements an indexer that imports blocks and their transactions.
 pub struct SyncWorker<Block, Backend, Client> {
 	_phantom: std::marker::PhantomData<(Block, Backend, Client)>,
@@ -71,8 +132,9 @@ where
 	Client: HeaderBackend<Block> + StorageProvider<Block, Backend> + 'static,
 	Backend: BackendT<Block> + 'static,
 {
-	/// Spawn the indexing worker. The worker can be given commands via the sender channel.
-	/// Once the buffer is full, attempts to send new messages will wait until a message is read from the channel.
+	/// Spawn the indexing worker. The worker can be given commands via the sender channel,
+	/// buffering up to `buffer_size` of them. Once the buffer is full, attempts to send new
+	/// messages will wait until a message is read from the channel.
 	pub async fn spawn_worker(
 		client: Arc<Client>,
 		substrate_backend: Arc<Backend>,
@@ -80,9 +142,14 @@ where
 		pubsub_notification_sinks: Arc<
 			EthereumBlockNotificationSinks<EthereumBlockNotification<Block>>,
 		>,
+		progress_sinks: Option<Arc<SyncProgressSinks>>,
+		metrics: Option<MappingSyncMetrics>,
+		buffer_size: usize,
 	) -> tokio::sync::mpsc::Sender<WorkerCommand> {
-		let (tx, mut rx) = tokio::sync::mpsc::channel(100);
+		let (tx, mut rx) = tokio::sync::mpsc::channel(buffer_size.max(1));
 		tokio::task::spawn(async move {
+			let mut progress = SyncProgressReporter::new(progress_sinks, metrics);
+			let mut indexed_blocks = indexer_backend.indexed_blocks();
 			while let Some(cmd) = rx.recv().await {
 				log::debug!(target: "frontier-sql", "💬 Recv Worker Command {cmd:?}");
 				match cmd {
@@ -107,7 +174,21 @@ where
 								}
 							}
 							None => {
-								index_genesis_block(client.clone(), indexer_backend.clone()).await;
+								let horizon = indexer_backend.history_horizon();
+								if horizon == 0 {
+									index_genesis_block(client.clone(), indexer_backend.clone())
+										.await;
+								} else if let Ok(Some(hash)) =
+									client.hash(horizon.unique_saturated_into())
+								{
+									index_canonical_block_and_ancestors(
+										client.clone(),
+										substrate_backend.clone(),
+										indexer_backend.clone(),
+										hash,
+									)
+									.await;
+								}
 							}
 						};
 					}
@@ -130,6 +211,24 @@ where
 							block_hash,
 						)
 						.await;
+						if !indexer_backend.is_block_indexed(block_hash).await {
+							if let Some(metrics) = progress.metrics() {
+								metrics.report_error();
+							}
+						}
+						let current_block = client
+							.number(block_hash)
+							.ok()
+							.flatten()
+							.map(UniqueSaturatedInto::unique_saturated_into)
+							.unwrap_or_default();
+						report_progress(
+							&mut progress,
+							client.as_ref(),
+							&indexer_backend,
+							&mut indexed_blocks,
+							current_block,
+						);
 						let sinks = &mut pubsub_notification_sinks.lock();
 						for sink in sinks.iter() {
 							let _ = sink.unbounded_send(EthereumBlockNotification {
@@ -143,8 +242,21 @@ where
 						enacted,
 						retracted,
 					} => {
-						canonicalize_blocks(indexer_backend.clone(), common, enacted, retracted)
-							.await;
+						let (enacted_count, retracted_count) = (enacted.len(), retracted.len());
+						let canonicalized = canonicalize_blocks(
+							indexer_backend.clone(),
+							common,
+							enacted,
+							retracted,
+						)
+						.await;
+						if let Some(metrics) = progress.metrics() {
+							if canonicalized {
+								metrics.report_reorg(enacted_count, retracted_count);
+							} else {
+								metrics.report_error();
+							}
+						}
 					}
 					WorkerCommand::CheckIndexedBlocks => {
 						// Fix any indexed blocks that did not have their logs indexed
@@ -163,6 +275,42 @@ where
 						)
 						.await;
 					}
+					WorkerCommand::PruneHistory { policy, batch_size } => {
+						prune_history(client.clone(), indexer_backend.clone(), policy, batch_size)
+							.await;
+					}
+					WorkerCommand::PruneNonCanon { batch_size } => {
+						prune_non_canon_blocks(client.clone(), indexer_backend.clone(), batch_size)
+							.await;
+					}
+					WorkerCommand::IndexCanonicalRange {
+						from_block,
+						to_block,
+					} => {
+						log::debug!(target: "frontier-sql", "Indexing canonical blocks #{from_block}..=#{to_block}");
+						index_canonical_range(
+							client.clone(),
+							indexer_backend.clone(),
+							from_block,
+							to_block,
+						)
+						.await;
+						report_progress(
+							&mut progress,
+							client.as_ref(),
+							&indexer_backend,
+							&mut indexed_blocks,
+							to_block.into(),
+						);
+					}
+					WorkerCommand::IndexPriorityRanges => {
+						index_priority_ranges(client.clone(), indexer_backend.clone()).await;
+					}
+					WorkerCommand::Maintenance => {
+						if let Err(err) = indexer_backend.run_maintenance().await {
+							log::warn!(target: "frontier-sql", "Database maintenance failed: {err:?}");
+						}
+					}
 				}
 			}
 		});
@@ -188,35 +336,110 @@ where
 			substrate_backend.clone(),
 			indexer_backend.clone(),
 			pubsub_notification_sinks.clone(),
+			worker_config.progress_sinks.clone(),
+			worker_config.metrics.clone(),
+			worker_config.command_buffer_size,
 		)
 		.await;
 
+		if let Err(err) = indexer_backend
+			.set_history_horizon(worker_config.sync_from)
+			.await
+		{
+			log::error!(target: "frontier-sql", "💔  Cannot record the history horizon: {err:?}");
+		}
 		// Resume sync from the last indexed block until we reach an already indexed parent
 		tx.send(WorkerCommand::ResumeSync).await.ok();
-		// check missing blocks every interval
+		// Index the history in parallel
+		if worker_config.backfill_workers > 0 {
+			spawn_backfill(
+				client.clone(),
+				indexer_backend.clone(),
+				worker_config.backfill_workers,
+				worker_config.retention_policy,
+			)
+			.await;
+		}
+		// check missing blocks, prune stale forks and history every interval
 		let tx2 = tx.clone();
+		let retention_policy = worker_config.retention_policy;
+		let prune_batch_size = worker_config.prune_batch_size;
+		let maintenance = worker_config.maintenance;
+		let indexer_backend2 = indexer_backend.clone();
 		tokio::task::spawn(async move {
 			loop {
 				futures_timer::Delay::new(worker_config.check_indexed_blocks_interval).await;
 				tx2.send(WorkerCommand::CheckIndexedBlocks).await.ok();
+				tx2.send(WorkerCommand::PruneNonCanon {
+					batch_size: prune_batch_size,
+				})
+				.await
+				.ok();
+				if let Some(policy) = retention_policy {
+					tx2.send(WorkerCommand::PruneHistory {
+						policy,
+						batch_size: prune_batch_size,
+					})
+					.await
+					.ok();
+				}
+				if let Some(MaintenanceSchedule::IndexedBlocks(blocks)) = maintenance {
+					if indexer_backend2.indexed_blocks_since_maintenance() >= blocks {
+						tx2.send(WorkerCommand::Maintenance).await.ok();
+					}
+				}
 			}
 		});
+		if let Some(MaintenanceSchedule::Interval(interval)) = maintenance {
+			let tx3 = tx.clone();
+			tokio::task::spawn(async move {
+				loop {
+					futures_timer::Delay::new(interval).await;
+					tx3.send(WorkerCommand::Maintenance).await.ok();
+				}
+			});
+		}
 
 		// check notifications
 		let mut notifications = import_notifications.fuse();
+		let priority_requested = indexer_backend.priority_requested();
+		// The best block waiting for room in the command buffer. A newer best block replaces
+		// it, the indexing of its ancestors covering the skipped one.
+		let mut pending_best_block = None;
+		// The first block of the next canonical range to index while major syncing, the import
+		// notifications being coalesced into ranges rather than walked one by one.
+		let mut major_sync_next_block: Option<u32> = None;
 		loop {
+			if let Some(hash) = pending_best_block.take() {
+				if tx.try_send(WorkerCommand::IndexBestBlock(hash)).is_err() {
+					pending_best_block = Some(hash);
+				}
+			}
 			let mut timeout =
 				futures_timer::Delay::new(worker_config.read_notification_timeout).fuse();
+			let priority = priority_requested.notified().fuse();
+			futures::pin_mut!(priority);
 			futures::select! {
+				_ = priority => {
+					tx.send(WorkerCommand::IndexPriorityRanges).await.ok();
+				}
 				_ = timeout => {
-					if let Ok(leaves) = substrate_backend.blockchain().leaves() {
-						tx.send(WorkerCommand::IndexLeaves(leaves)).await.ok();
-					}
 					if sync_oracle.is_major_syncing() {
 						let sinks = &mut pubsub_notification_sinks.lock();
 						if !sinks.is_empty() {
 							*sinks.deref_mut() = vec![];
 						}
+						// Index the rest of the range, the leaves being walked once synced.
+						if let Some(from_block) = major_sync_next_block {
+							let to_block: u32 = client.info().best_number.unique_saturated_into();
+							if to_block >= from_block {
+								tx.send(WorkerCommand::IndexCanonicalRange { from_block, to_block }).await.ok();
+								major_sync_next_block = Some(to_block + 1);
+							}
+						}
+					// Skipped while the indexing is behind, the next timeout retrying.
+					} else if let Ok(leaves) = substrate_backend.blockchain().leaves() {
+						tx.try_send(WorkerCommand::IndexLeaves(leaves)).ok();
 					}
 				}
 				notification = notifications.next() => if let Some(notification) = notification {
@@ -254,7 +477,26 @@ where
 							}).await.ok();
 						}
 
-						tx.send(WorkerCommand::IndexBestBlock(notification.hash)).await.ok();
+						pending_best_block = None;
+						if sync_oracle.is_major_syncing() {
+							let best_block: u32 = (*notification.header.number()).unique_saturated_into();
+							let from_block = match major_sync_next_block {
+								Some(from_block) => from_block,
+								None => first_unindexed_canon_block(client.as_ref(), &indexer_backend).await,
+							};
+							if best_block.saturating_add(1) >= from_block.saturating_add(MAJOR_SYNC_RANGE_SIZE) {
+								tx.send(WorkerCommand::IndexCanonicalRange { from_block, to_block: best_block }).await.ok();
+								major_sync_next_block = Some(best_block + 1);
+							} else {
+								major_sync_next_block = Some(from_block);
+							}
+						} else {
+							major_sync_next_block = None;
+							if tx.try_send(WorkerCommand::IndexBestBlock(notification.hash)).is_err() {
+								log::debug!(target: "frontier-sql", "Indexing is behind, coalescing best block {:?}", notification.hash);
+								pending_best_block = Some(notification.hash);
+							}
+						}
 					}
 				}
 			}
@@ -262,8 +504,27 @@ where
 	}
 }
 
+/// Whether the block is below the history horizon of the indexer, in which case it must not
+/// be indexed.
+fn is_below_history_horizon<Block, B>(
+	blockchain_backend: &B,
+	indexer_backend: &fc_db::sql::Backend<Block>,
+	hash: H256,
+) -> bool
+where
+	Block: BlockT<Hash = H256>,
+	B: HeaderBackend<Block>,
+{
+	let horizon = indexer_backend.history_horizon();
+	horizon > 0
+		&& matches!(
+			blockchain_backend.number(hash),
+			Ok(Some(number)) if UniqueSaturatedInto::<u32>::unique_saturated_into(number) < horizon
+		)
+}
+
 /// Index the provided blocks. The function loops over the ancestors of the provided nodes
-/// until it encounters the genesis block, or a block that has already been imported, or
+/// until it encounters the genesis block, the history horizon, or a block that has already been imported, or
 /// is already in the active set. The `hashes` parameter is populated with any parent blocks
 /// that is scheduled to be indexed.
 async fn index_block_and_ancestors<Block, Backend, Client>(
@@ -286,6 +547,11 @@ async fn index_block_and_ancestors<Block, Backend, Client>(
 			break;
 		}
 
+		// exit if the history horizon is reached
+		if is_below_history_horizon(blockchain_backend, &indexer_backend, hash) {
+			break;
+		}
+
 		// exit if block is already imported
 		if indexer_backend.is_block_indexed(hash).await {
 			log::debug!(target: "frontier-sql", "🔴 Block {hash:?} already imported");
@@ -310,7 +576,7 @@ async fn index_block_and_ancestors<Block, Backend, Client>(
 }
 
 /// Index the provided known canonical blocks. The function loops over the ancestors of the provided nodes
-/// until it encounters the genesis block, or a block that has already been imported, or
+/// until it encounters the genesis block, the history horizon, or a block that has already been imported, or
 /// is already in the active set. The `hashes` parameter is populated with any parent blocks
 /// that is scheduled to be indexed.
 async fn index_canonical_block_and_ancestors<Block, Backend, Client>(
@@ -333,6 +599,11 @@ async fn index_canonical_block_and_ancestors<Block, Backend, Client>(
 			break;
 		}
 
+		// exit if the history horizon is reached
+		if is_below_history_horizon(blockchain_backend, &indexer_backend, hash) {
+			break;
+		}
+
 		let status = indexer_backend.block_indexed_and_canon_status(hash).await;
 
 		// exit if canonical block is already imported
@@ -376,14 +647,40 @@ async fn index_canonical_block_and_ancestors<Block, Backend, Client>(
 	}
 }
 
+/// Reports the indexing progress up to `current_block`, along with the blocks indexed since the
+/// previous report.
+fn report_progress<Block, Client>(
+	progress: &mut SyncProgressReporter,
+	client: &Client,
+	indexer_backend: &fc_db::sql::Backend<Block>,
+	indexed_blocks: &mut u64,
+	current_block: u64,
+) where
+	Block: BlockT<Hash = H256>,
+	Client: HeaderBackend<Block>,
+{
+	if !progress.is_enabled() {
+		return;
+	}
+	let synced = indexer_backend.indexed_blocks();
+	let best_block: u64 = client.info().best_number.unique_saturated_into();
+	progress.report(
+		synced.saturating_sub(*indexed_blocks),
+		current_block,
+		best_block,
+		best_block.saturating_sub(current_block),
+	);
+	*indexed_blocks = synced;
+}
+
 /// Canonicalizes the database by setting the `is_canon` field for the retracted blocks to `0`,
-/// and `1` if they are enacted.
+/// and `1` if they are enacted. Returns whether the canonicalization succeeded.
 async fn canonicalize_blocks<Block: BlockT<Hash = H256>>(
 	indexer_backend: Arc<fc_db::sql::Backend<Block>>,
 	common: H256,
 	enacted: Vec<H256>,
 	retracted: Vec<H256>,
-) {
+) -> bool {
 	if (indexer_backend.canonicalize(&retracted, &enacted).await).is_err() {
 		log::error!(
 			target: "frontier-sql",
@@ -392,7 +689,9 @@ async fn canonicalize_blocks<Block: BlockT<Hash = H256>>(
 			retracted,
 			enacted,
 		);
+		return false;
 	}
+	true
 }
 
 /// Attempts to index any missing blocks that are in the past. This fixes any gaps that may
@@ -460,6 +759,283 @@ async fn index_genesis_block<Block, Client, Backend>(
 	}
 }
 
+/// Schedules the historical blocks up to the current best block for backfill, and spawns
+/// `workers` tasks claiming and indexing disjoint block ranges until none is left.
+/// The blocks falling out of the `retention_policy` are skipped.
+pub async fn spawn_backfill<Block, Client, Backend>(
+	client: Arc<Client>,
+	indexer_backend: Arc<fc_db::sql::Backend<Block>>,
+	workers: u32,
+	retention_policy: Option<RetentionPolicy>,
+) where
+	Block: BlockT<Hash = H256>,
+	Client: ProvideRuntimeApi<Block>,
+	Client::Api: EthereumRuntimeRPCApi<Block>,
+	Client: HeaderBackend<Block> + StorageProvider<Block, Backend> + 'static,
+	Backend: BackendT<Block> + 'static,
+{
+	let best_number: u32 = client.info().best_number.unique_saturated_into();
+	if let Err(err) = indexer_backend
+		.schedule_backfill(best_number, BACKFILL_RANGE_SIZE)
+		.await
+	{
+		log::error!(target: "frontier-sql", "💔  Cannot schedule backfill: {err:?}");
+		return;
+	}
+
+	let retained_from = retention_policy
+		.map(|policy| retention_cutoff(client.as_ref(), policy))
+		.unwrap_or_default();
+
+	log::info!(target: "frontier-sql", "Backfilling up to block #{best_number} with {workers} workers");
+	for worker in 0..workers {
+		let client = client.clone();
+		let indexer_backend = indexer_backend.clone();
+		tokio::task::spawn(async move {
+			while let Ok(Some((start_block, end_block))) =
+				indexer_backend.claim_backfill_range().await
+			{
+				log::debug!(
+					target: "frontier-sql",
+					"[Backfill {worker}] Indexing blocks #{start_block}..=#{end_block}",
+				);
+				let first_block = start_block
+					.max(indexer_backend.history_horizon())
+					.max(retained_from);
+				for block_number in first_block..=end_block {
+					let Ok(Some(hash)) = client.hash(block_number.unique_saturated_into()) else {
+						continue;
+					};
+					if indexer_backend.is_block_indexed(hash).await {
+						continue;
+					}
+					if block_number == 0 {
+						index_genesis_block(client.clone(), indexer_backend.clone()).await;
+						continue;
+					}
+					if let Err(err) = indexer_backend
+						.insert_block_metadata(client.clone(), hash)
+						.await
+					{
+						log::error!(target: "frontier-sql", "[Backfill {worker}] {err}");
+						continue;
+					}
+					indexer_backend.index_block_logs(hash).await;
+				}
+				if let Err(err) = indexer_backend.complete_backfill_range(start_block).await {
+					log::error!(target: "frontier-sql", "[Backfill {worker}] {err:?}");
+				}
+			}
+			log::debug!(target: "frontier-sql", "[Backfill {worker}] Done");
+		});
+	}
+}
+
+/// Deletes the indexed data of the blocks falling out of the retention policy, one batch
+/// of `batch_size` blocks at a time so the db is not locked for too long.
+async fn prune_history<Block, Client, Backend>(
+	client: Arc<Client>,
+	indexer_backend: Arc<fc_db::sql::Backend<Block>>,
+	policy: RetentionPolicy,
+	batch_size: u32,
+) where
+	Block: BlockT<Hash = H256>,
+	Client: ProvideRuntimeApi<Block>,
+	Client::Api: EthereumRuntimeRPCApi<Block>,
+	Client: HeaderBackend<Block> + StorageProvider<Block, Backend> + 'static,
+	Backend: BackendT<Block> + 'static,
+{
+	let prune_before = retention_cutoff(client.as_ref(), policy);
+	if prune_before == 0 {
+		return;
+	}
+
+	log::debug!(target: "frontier-sql", "Pruning indexed data before block #{prune_before}");
+	loop {
+		match indexer_backend
+			.prune_blocks_before(prune_before, batch_size)
+			.await
+		{
+			Ok(pruned) if pruned == batch_size as usize => continue,
+			Ok(_) => break,
+			Err(err) => {
+				log::error!(target: "frontier-sql", "💔  Failed pruning indexed data: {err:?}");
+				break;
+			}
+		}
+	}
+}
+
+/// Indexes the canonical blocks of the ranges targeted by queries which are not indexed yet,
+/// then wakes the waiting queries up.
+async fn index_priority_ranges<Block, Client, Backend>(
+	client: Arc<Client>,
+	indexer_backend: Arc<fc_db::sql::Backend<Block>>,
+) where
+	Block: BlockT<Hash = H256>,
+	Client: ProvideRuntimeApi<Block>,
+	Client::Api: EthereumRuntimeRPCApi<Block>,
+	Client: HeaderBackend<Block> + StorageProvider<Block, Backend> + 'static,
+	Backend: BackendT<Block> + 'static,
+{
+	while let Some((from_block, to_block)) = indexer_backend.pop_priority_range() {
+		log::debug!(target: "frontier-sql", "Indexing priority blocks #{from_block}..=#{to_block}");
+		index_canonical_range(
+			client.clone(),
+			indexer_backend.clone(),
+			from_block,
+			to_block,
+		)
+		.await;
+	}
+	indexer_backend.notify_priority_indexed();
+}
+
+/// Indexes the canonical blocks in `[from_block, to_block]` above the history horizon,
+/// marking the ones indexed as non-canonical as canonical.
+async fn index_canonical_range<Block, Client, Backend>(
+	client: Arc<Client>,
+	indexer_backend: Arc<fc_db::sql::Backend<Block>>,
+	from_block: u32,
+	to_block: u32,
+) where
+	Block: BlockT<Hash = H256>,
+	Client: ProvideRuntimeApi<Block>,
+	Client::Api: EthereumRuntimeRPCApi<Block>,
+	Client: HeaderBackend<Block> + StorageProvider<Block, Backend> + 'static,
+	Backend: BackendT<Block> + 'static,
+{
+	let from_block = from_block.max(indexer_backend.history_horizon());
+	for block_number in from_block..=to_block {
+		let Ok(Some(hash)) = client.hash(block_number.unique_saturated_into()) else {
+			continue;
+		};
+		let status = indexer_backend.block_indexed_and_canon_status(hash).await;
+		if block_number == 0 {
+			if !status.indexed {
+				index_genesis_block(client.clone(), indexer_backend.clone()).await;
+			}
+			continue;
+		}
+		if !status.indexed {
+			if let Err(err) = indexer_backend
+				.insert_block_metadata(client.clone(), hash)
+				.await
+			{
+				log::error!(target: "frontier-sql", "💔  Cannot index canonical block #{block_number}: {err}");
+				continue;
+			}
+		} else if !status.canon {
+			if let Err(err) = indexer_backend.set_block_as_canon(hash).await {
+				log::error!(target: "frontier-sql", "Failed setting block {hash:?} as canon: {err:?}");
+			}
+		}
+		// Does nothing if the logs are already indexed.
+		indexer_backend.index_block_logs(hash).await;
+	}
+}
+
+/// The block following the last indexed canonical block, or the history horizon when none
+/// is indexed.
+async fn first_unindexed_canon_block<Block, Client>(
+	client: &Client,
+	indexer_backend: &fc_db::sql::Backend<Block>,
+) -> u32
+where
+	Block: BlockT<Hash = H256>,
+	Client: HeaderBackend<Block>,
+{
+	let last_indexed = match indexer_backend.last_indexed_canon_block().await {
+		Ok(hash) => client
+			.number(hash)
+			.ok()
+			.flatten()
+			.map(|number| UniqueSaturatedInto::<u32>::unique_saturated_into(number) + 1),
+		Err(_) => None,
+	};
+	last_indexed
+		.unwrap_or_default()
+		.max(indexer_backend.history_horizon())
+}
+
+/// Deletes the indexed data of the non-canonical blocks below the finalized block, one batch
+/// of `batch_size` blocks at a time. Such blocks can no longer be enacted, so keeping them
+/// only slows down the queries.
+async fn prune_non_canon_blocks<Block, Client>(
+	client: Arc<Client>,
+	indexer_backend: Arc<fc_db::sql::Backend<Block>>,
+	batch_size: u32,
+) where
+	Block: BlockT<Hash = H256>,
+	Client: HeaderBackend<Block> + 'static,
+{
+	let finalized_number: u32 = client.info().finalized_number.unique_saturated_into();
+	if finalized_number == 0 || batch_size == 0 {
+		return;
+	}
+
+	loop {
+		match indexer_backend
+			.prune_non_canon_blocks_before(client.as_ref(), finalized_number, batch_size)
+			.await
+		{
+			Ok(pruned) if pruned == batch_size as usize => continue,
+			Ok(_) => break,
+			Err(err) => {
+				log::error!(target: "frontier-sql", "💔  Failed pruning non-canonical blocks: {err:?}");
+				break;
+			}
+		}
+	}
+}
+
+/// Returns the first block whose indexed data is kept by the retention `policy`.
+fn retention_cutoff<Block, Client>(client: &Client, policy: RetentionPolicy) -> u32
+where
+	Block: BlockT<Hash = H256>,
+	Client: ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	Client::Api: EthereumRuntimeRPCApi<Block>,
+{
+	let best_number: u32 = client.info().best_number.unique_saturated_into();
+	match policy {
+		RetentionPolicy::Blocks(blocks) => best_number.saturating_sub(blocks),
+		RetentionPolicy::Days(days) => {
+			let now = std::time::SystemTime::now()
+				.duration_since(std::time::UNIX_EPOCH)
+				.map(|now| now.as_millis() as u64)
+				.unwrap_or_default();
+			let cutoff = now.saturating_sub(u64::from(days) * 24 * 60 * 60 * 1000);
+			first_block_since(client, best_number, cutoff)
+		}
+	}
+}
+
+/// Binary searches the canonical chain for the first block whose ethereum timestamp, in
+/// milliseconds, is not lower than `timestamp`.
+fn first_block_since<Block, Client>(client: &Client, best_number: u32, timestamp: u64) -> u32
+where
+	Block: BlockT<Hash = H256>,
+	Client: ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	Client::Api: EthereumRuntimeRPCApi<Block>,
+{
+	let block_timestamp = |number: u32| -> Option<u64> {
+		let hash = client.hash(number.unique_saturated_into()).ok().flatten()?;
+		let block = client.runtime_api().current_block(hash).ok().flatten()?;
+		Some(block.header.timestamp)
+	};
+
+	let (mut low, mut high) = (0u32, best_number);
+	while low < high {
+		let mid = low + (high - low) / 2;
+		match block_timestamp(mid) {
+			Some(block_timestamp) if block_timestamp >= timestamp => high = mid,
+			// Blocks without an ethereum block are treated as old ones.
+			_ => low = mid + 1,
+		}
+	}
+	low
+}
+
 #[cfg(test)]
 mod test {
 	use super::*;
@@ -561,9 +1137,11 @@ mod test {
 				create_if_missing: true,
 				cache_size: 204800,
 				thread_count: 4,
+				..Default::default()
 			}),
 			100,
 			None,
+			10000,
 			storage_override.clone(),
 		)
 		.await
@@ -674,6 +1252,14 @@ mod test {
 				SyncWorkerConfig {
 					read_notification_timeout: Duration::from_secs(1),
 					check_indexed_blocks_interval: Duration::from_secs(60),
+					retention_policy: None,
+					prune_batch_size: 1000,
+					backfill_workers: 0,
+					maintenance: None,
+					sync_from: 0,
+					progress_sinks: None,
+					metrics: None,
+					command_buffer_size: 100,
 				},
 				SyncStrategy::Parachain,
 				Arc::new(test_sync_oracle),
@@ -764,13 +1350,17 @@ mod test {
 				create_if_missing: true,
 				cache_size: 204800,
 				thread_count: 4,
+				..Default::default()
 			}),
 			100,
 			None,
+			10000,
 			storage_override.clone(),
 		)
 		.await
-		.expect("indexer pool to be created");
+		.expect("indexer pool to be created")
+		// Insert the logs of a block in several statements.
+		.with_log_insert_chunk_size(1);
 		// Pool
 		let pool = indexer_backend.pool().clone();
 
@@ -796,6 +1386,14 @@ mod test {
 				SyncWorkerConfig {
 					read_notification_timeout: Duration::from_secs(10),
 					check_indexed_blocks_interval: Duration::from_secs(60),
+					retention_policy: None,
+					prune_batch_size: 1000,
+					backfill_workers: 0,
+					maintenance: None,
+					sync_from: 0,
+					progress_sinks: None,
+					metrics: None,
+					command_buffer_size: 100,
 				},
 				SyncStrategy::Parachain,
 				Arc::new(test_sync_oracle),
@@ -968,9 +1566,11 @@ mod test {
 				create_if_missing: true,
 				cache_size: 204800,
 				thread_count: 4,
+				..Default::default()
 			}),
 			100,
 			None,
+			10000,
 			storage_override.clone(),
 		)
 		.await
@@ -999,6 +1599,14 @@ mod test {
 				SyncWorkerConfig {
 					read_notification_timeout: Duration::from_secs(10),
 					check_indexed_blocks_interval: Duration::from_secs(60),
+					retention_policy: None,
+					prune_batch_size: 1000,
+					backfill_workers: 0,
+					maintenance: None,
+					sync_from: 0,
+					progress_sinks: None,
+					metrics: None,
+					command_buffer_size: 100,
 				},
 				SyncStrategy::Parachain,
 				Arc::new(test_sync_oracle),
@@ -1107,6 +1715,116 @@ mod test {
 		assert!(not_canon.iter().all(|h| hashes_to_be_orphaned.contains(h)));
 	}
 
+	#[tokio::test]
+	async fn integrity_check_reports_and_repairs_damaged_blocks() {
+		let tmp = tempdir().expect("create a temporary directory");
+		// Initialize storage with schema V3
+		let builder = TestClientBuilder::new().add_extra_storage(
+			PALLET_ETHEREUM_SCHEMA.to_vec(),
+			Encode::encode(&EthereumStorageSchema::V3),
+		);
+		// Client
+		let (client, _) =
+			builder.build_with_native_executor::<frontier_template_runtime::RuntimeApi, _>(None);
+		let mut client = Arc::new(client);
+		// Overrides
+		let storage_override = Arc::new(SchemaV3StorageOverride::new(client.clone()));
+		// Indexer backend
+		let indexer_backend = fc_db::sql::Backend::new(
+			fc_db::sql::BackendConfig::Sqlite(fc_db::sql::SqliteBackendConfig {
+				path: Path::new("sqlite:///")
+					.join(tmp.path())
+					.join("test.db3")
+					.to_str()
+					.unwrap(),
+				create_if_missing: true,
+				cache_size: 204800,
+				thread_count: 4,
+				..Default::default()
+			}),
+			100,
+			None,
+			10000,
+			storage_override.clone(),
+		)
+		.await
+		.expect("indexer pool to be created");
+		// Pool
+		let pool = indexer_backend.pool().clone();
+
+		// Create and index 3 blocks
+		let mut block_hashes: Vec<H256> = vec![];
+		for _block_number in 1..=3 {
+			let chain = client.chain_info();
+			let mut builder = BlockBuilderBuilder::new(&*client)
+				.on_parent_block(chain.best_hash)
+				.with_parent_block_number(chain.best_number)
+				.build()
+				.unwrap();
+			builder
+				.push_deposit_log_digest_item(ethereum_digest())
+				.expect("deposit log");
+			let block = builder.build().unwrap().block;
+			let block_hash = block.header.hash();
+			executor::block_on(client.import(BlockOrigin::Own, block)).unwrap();
+			indexer_backend
+				.insert_block_metadata(client.clone(), block_hash)
+				.await
+				.expect("block metadata to be indexed");
+			block_hashes.push(block_hash);
+		}
+
+		let report = indexer_backend
+			.check_integrity(client.clone(), 1, 3, None, false)
+			.await
+			.expect("integrity check must succeed");
+		assert_eq!(report.checked, 3);
+		assert!(report.missing.is_empty());
+		assert!(report.mismatched.is_empty());
+
+		// Lose block #2 and corrupt block #3
+		for table in ["sync_status", "blocks"] {
+			sqlx::query(&format!(
+				"DELETE FROM {table} WHERE substrate_block_hash = ?"
+			))
+			.bind(block_hashes[1].as_bytes())
+			.execute(&pool)
+			.await
+			.expect("sql query must succeed");
+		}
+		sqlx::query("UPDATE blocks SET ethereum_block_hash = ? WHERE substrate_block_hash = ?")
+			.bind(H256::zero().as_bytes())
+			.bind(block_hashes[2].as_bytes())
+			.execute(&pool)
+			.await
+			.expect("sql query must succeed");
+
+		let report = indexer_backend
+			.check_integrity(client.clone(), 1, 3, None, true)
+			.await
+			.expect("integrity check must succeed");
+		assert_eq!(report.missing, vec![2]);
+		assert_eq!(report.mismatched, vec![3]);
+		assert_eq!(report.repaired, 2);
+
+		// The repaired blocks are indexed again
+		let report = indexer_backend
+			.check_integrity(client.clone(), 1, 3, None, false)
+			.await
+			.expect("integrity check must succeed");
+		assert!(report.missing.is_empty());
+		assert!(report.mismatched.is_empty());
+		// Their logs are queued for indexing
+		let status: i32 =
+			sqlx::query("SELECT status FROM sync_status WHERE substrate_block_hash = ?")
+				.bind(block_hashes[1].as_bytes())
+				.fetch_one(&pool)
+				.await
+				.expect("sql query must succeed")
+				.get(0);
+		assert_eq!(status, 0);
+	}
+
 	#[tokio::test]
 	async fn resuming_from_last_indexed_block_works() {
 		let tmp = tempdir().expect("create a temporary directory");
@@ -1134,9 +1852,11 @@ mod test {
 				create_if_missing: true,
 				cache_size: 204800,
 				thread_count: 4,
+				..Default::default()
 			}),
 			100,
 			None,
+			10000,
 			storage_override.clone(),
 		)
 		.await
@@ -1202,6 +1922,14 @@ mod test {
 				SyncWorkerConfig {
 					read_notification_timeout: Duration::from_secs(10),
 					check_indexed_blocks_interval: Duration::from_secs(60),
+					retention_policy: None,
+					prune_batch_size: 1000,
+					backfill_workers: 0,
+					maintenance: None,
+					sync_from: 0,
+					progress_sinks: None,
+					metrics: None,
+					command_buffer_size: 100,
 				},
 				SyncStrategy::Parachain,
 				Arc::new(test_sync_oracle),
@@ -1278,9 +2006,11 @@ mod test {
 				create_if_missing: true,
 				cache_size: 204800,
 				thread_count: 4,
+				..Default::default()
 			}),
 			100,
 			None,
+			10000,
 			storage_override.clone(),
 		)
 		.await
@@ -1306,6 +2036,14 @@ mod test {
 				SyncWorkerConfig {
 					read_notification_timeout: Duration::from_secs(10),
 					check_indexed_blocks_interval: Duration::from_secs(60),
+					retention_policy: None,
+					prune_batch_size: 1000,
+					backfill_workers: 0,
+					maintenance: None,
+					sync_from: 0,
+					progress_sinks: None,
+					metrics: None,
+					command_buffer_size: 100,
 				},
 				SyncStrategy::Normal,
 				Arc::new(sync_oracle),
@@ -1379,9 +2117,11 @@ mod test {
 				create_if_missing: true,
 				cache_size: 204800,
 				thread_count: 4,
+				..Default::default()
 			}),
 			100,
 			None,
+			10000,
 			storage_override.clone(),
 		)
 		.await
@@ -1407,6 +2147,14 @@ mod test {
 				SyncWorkerConfig {
 					read_notification_timeout: Duration::from_secs(10),
 					check_indexed_blocks_interval: Duration::from_secs(60),
+					retention_policy: None,
+					prune_batch_size: 1000,
+					backfill_workers: 0,
+					maintenance: None,
+					sync_from: 0,
+					progress_sinks: None,
+					metrics: None,
+					command_buffer_size: 100,
 				},
 				SyncStrategy::Normal,
 				Arc::new(sync_oracle),
@@ -1494,9 +2242,11 @@ mod test {
 				create_if_missing: true,
 				cache_size: 204800,
 				thread_count: 4,
+				..Default::default()
 			}),
 			100,
 			None,
+			10000,
 			storage_override.clone(),
 		)
 		.await
@@ -1522,6 +2272,14 @@ mod test {
 				SyncWorkerConfig {
 					read_notification_timeout: Duration::from_secs(10),
 					check_indexed_blocks_interval: Duration::from_secs(60),
+					retention_policy: None,
+					prune_batch_size: 1000,
+					backfill_workers: 0,
+					maintenance: None,
+					sync_from: 0,
+					progress_sinks: None,
+					metrics: None,
+					command_buffer_size: 100,
 				},
 				SyncStrategy::Parachain,
 				Arc::new(sync_oracle),
@@ -1595,9 +2353,11 @@ mod test {
 				create_if_missing: true,
 				cache_size: 204800,
 				thread_count: 4,
+				..Default::default()
 			}),
 			100,
 			None,
+			10000,
 			storage_override.clone(),
 		)
 		.await
@@ -1623,6 +2383,14 @@ mod test {
 				SyncWorkerConfig {
 					read_notification_timeout: Duration::from_secs(10),
 					check_indexed_blocks_interval: Duration::from_secs(60),
+					retention_policy: None,
+					prune_batch_size: 1000,
+					backfill_workers: 0,
+					maintenance: None,
+					sync_from: 0,
+					progress_sinks: None,
+					metrics: None,
+					command_buffer_size: 100,
 				},
 				SyncStrategy::Parachain,
 				Arc::new(sync_oracle),
@@ -1710,9 +2478,11 @@ mod test {
 				create_if_missing: true,
 				cache_size: 204800,
 				thread_count: 4,
+				..Default::default()
 			}),
 			100,
 			None,
+			10000,
 			storage_override.clone(),
 		)
 		.await
@@ -1738,6 +2508,14 @@ mod test {
 				SyncWorkerConfig {
 					read_notification_timeout: Duration::from_secs(10),
 					check_indexed_blocks_interval: Duration::from_secs(60),
+					retention_policy: None,
+					prune_batch_size: 1000,
+					backfill_workers: 0,
+					maintenance: None,
+					sync_from: 0,
+					progress_sinks: None,
+					metrics: None,
+					command_buffer_size: 100,
 				},
 				SyncStrategy::Normal,
 				Arc::new(sync_oracle),
@@ -1811,9 +2589,11 @@ mod test {
 				create_if_missing: true,
 				cache_size: 204800,
 				thread_count: 4,
+				..Default::default()
 			}),
 			100,
 			None,
+			10000,
 			storage_override.clone(),
 		)
 		.await
@@ -1839,6 +2619,14 @@ mod test {
 				SyncWorkerConfig {
 					read_notification_timeout: Duration::from_secs(10),
 					check_indexed_blocks_interval: Duration::from_secs(60),
+					retention_policy: None,
+					prune_batch_size: 1000,
+					backfill_workers: 0,
+					maintenance: None,
+					sync_from: 0,
+					progress_sinks: None,
+					metrics: None,
+					command_buffer_size: 100,
 				},
 				SyncStrategy::Parachain,
 				Arc::new(sync_oracle),
@@ -1889,4 +2677,92 @@ mod test {
 		let expected_imported_blocks = Vec::<H256>::new();
 		assert_eq!(expected_imported_blocks, actual_imported_blocks);
 	}
+
+	#[tokio::test]
+	async fn canonical_range_indexing_works() {
+		let tmp = tempdir().expect("create a temporary directory");
+		let builder = TestClientBuilder::new().add_extra_storage(
+			PALLET_ETHEREUM_SCHEMA.to_vec(),
+			Encode::encode(&EthereumStorageSchema::V3),
+		);
+		let (client, _) =
+			builder.build_with_native_executor::<frontier_template_runtime::RuntimeApi, _>(None);
+		let mut client = Arc::new(client);
+		let storage_override = Arc::new(SchemaV3StorageOverride::new(client.clone()));
+		let indexer_backend = Arc::new(
+			fc_db::sql::Backend::new(
+				fc_db::sql::BackendConfig::Sqlite(fc_db::sql::SqliteBackendConfig {
+					path: Path::new("sqlite:///")
+						.join(tmp.path())
+						.join("test.db3")
+						.to_str()
+						.unwrap(),
+					create_if_missing: true,
+					cache_size: 204800,
+					thread_count: 4,
+					..Default::default()
+				}),
+				100,
+				None,
+				10000,
+				storage_override.clone(),
+			)
+			.await
+			.expect("indexer pool to be created"),
+		);
+
+		// Import 4 blocks as part of initial network sync, storing them oldest first.
+		let mut parent_hash = client
+			.hash(sp_runtime::traits::Zero::zero())
+			.unwrap()
+			.expect("genesis hash");
+		let mut block_hashes: Vec<H256> = vec![];
+		for _block_number in 1..=4 {
+			let mut builder = BlockBuilderBuilder::new(&*client)
+				.on_parent_block(parent_hash)
+				.fetch_parent_block_number(&*client)
+				.unwrap()
+				.build()
+				.unwrap();
+			builder
+				.push_deposit_log_digest_item(ethereum_digest())
+				.expect("deposit log");
+			let block = builder.build().unwrap().block;
+			let block_hash = block.header.hash();
+
+			executor::block_on(client.import(BlockOrigin::NetworkInitialSync, block)).unwrap();
+			block_hashes.push(block_hash);
+			parent_hash = block_hash;
+		}
+
+		// A block indexed as non-canonical is marked canonical.
+		indexer_backend
+			.insert_block_metadata(client.clone(), block_hashes[1])
+			.await
+			.expect("block is indexed");
+		sqlx::query("UPDATE blocks SET is_canon = 0")
+			.execute(indexer_backend.pool())
+			.await
+			.expect("test query result");
+
+		index_canonical_range(client.clone(), indexer_backend.clone(), 2, 3).await;
+
+		let actual_imported_blocks =
+			sqlx::query("SELECT substrate_block_hash, is_canon FROM blocks ORDER BY block_number")
+				.fetch_all(indexer_backend.pool())
+				.await
+				.expect("test query result")
+				.iter()
+				.map(|row| {
+					(
+						H256::from_slice(&row.get::<Vec<u8>, _>(0)[..]),
+						row.get::<i32, _>(1),
+					)
+				})
+				.collect::<Vec<_>>();
+		assert_eq!(
+			actual_imported_blocks,
+			vec![(block_hashes[1], 1), (block_hashes[2], 1)]
+		);
+	}
 }
@@ -0,0 +1,59 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! ERC-4337 bundler rpc interface.
+
+use ethereum_types::{H160, H256};
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+
+use crate::types::*;
+
+/// ERC-4337 bundler rpc interface.
+#[rpc(server)]
+#[async_trait]
+pub trait BundlerApi {
+	/// Submits a user operation to the bundler, returning its `userOpHash`.
+	///
+	/// The operation is wrapped alone in an `EntryPoint.handleOps` call which is signed by the
+	/// bundler account and submitted to the transaction pool: operations are not batched.
+	#[method(name = "eth_sendUserOperation")]
+	async fn send_user_operation(
+		&self,
+		user_operation: UserOperation,
+		entry_point: H160,
+	) -> RpcResult<H256>;
+
+	/// Estimates the gas values of a user operation.
+	#[method(name = "eth_estimateUserOperationGas")]
+	async fn estimate_user_operation_gas(
+		&self,
+		user_operation: UserOperation,
+		entry_point: H160,
+	) -> RpcResult<UserOperationGasEstimation>;
+
+	/// Returns the receipt of a user operation submitted through this bundler.
+	///
+	/// Only the latest operations submitted through this node since it started are known:
+	/// `None` is returned for the others, even if they were included on chain.
+	#[method(name = "eth_getUserOperationReceipt")]
+	async fn user_operation_receipt(&self, hash: H256) -> RpcResult<Option<UserOperationReceipt>>;
+
+	/// Returns the entry points supported by the bundler.
+	#[method(name = "eth_supportedEntryPoints")]
+	fn supported_entry_points(&self) -> RpcResult<Vec<H160>>;
+}
@@ -181,6 +181,19 @@ pub trait EthApi {
 		number_or_hash: Option<BlockNumberOrHash>,
 	) -> RpcResult<Bytes>;
 
+	/// Returns the account and the given storage slots at given time (block number), with the
+	/// storage proofs of their runtime storage against the state root.
+	///
+	/// The proofs are Substrate trie nodes, not Ethereum Merkle Patricia trie nodes: they don't
+	/// verify against `storageHash` and can't be checked by Ethereum light clients.
+	#[method(name = "eth_getProof")]
+	async fn proof(
+		&self,
+		address: H160,
+		storage_keys: Vec<H256>,
+		number_or_hash: Option<BlockNumberOrHash>,
+	) -> RpcResult<EthAccount>;
+
 	// ########################################################################
 	// Execute
 	// ########################################################################
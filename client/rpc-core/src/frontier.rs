@@ -0,0 +1,49 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Frontier rpc interface.
+
+use ethereum_types::H160;
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+
+use crate::types::*;
+
+/// Frontier rpc interface, exposing data indexed by the frontier backend.
+#[rpc(server)]
+#[async_trait]
+pub trait FrontierApi {
+	/// Returns the logs matching the given filter, decoded with the event signatures
+	/// registered in the frontier backend.
+	#[method(name = "frontier_getDecodedLogs")]
+	async fn decoded_logs(&self, filter: Filter) -> RpcResult<Vec<DecodedLog>>;
+
+	/// Returns the canonical transactions sent from or to the given address, most recent
+	/// first, skipping the first `offset` ones and returning at most `limit` of them.
+	#[method(name = "frontier_getTransactionsByAddress")]
+	async fn transactions_by_address(
+		&self,
+		address: H160,
+		offset: Option<u32>,
+		limit: Option<u32>,
+	) -> RpcResult<Vec<AddressTransaction>>;
+
+	/// Returns the canonical transaction which deployed the contract at the given address,
+	/// `None` for unknown addresses and contracts created by another contract.
+	#[method(name = "frontier_getContractCreation")]
+	async fn contract_creation(&self, address: H160) -> RpcResult<Option<ContractCreation>>;
+}
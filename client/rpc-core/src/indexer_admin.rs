@@ -0,0 +1,57 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! SQL indexer admin rpc interface.
+
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+
+use crate::types::{IndexerIntegrityReport, IndexerStatementCacheStats};
+
+/// SQL indexer admin rpc interface.
+///
+/// Meant to be served on an internal, authenticated endpoint, separately from the public
+/// Ethereum rpc.
+#[rpc(server)]
+#[async_trait]
+pub trait IndexerAdminApi {
+	/// Schedules the history up to the best block for indexing, spawning `workers` tasks
+	/// to index it in the background.
+	#[method(name = "indexer_backfill")]
+	async fn backfill(&self, workers: u32) -> RpcResult<()>;
+
+	/// Checks the indexed data of the blocks in `[from_block, to_block]` against the chain,
+	/// checking `sample` blocks spread over the range, or all of them if not provided.
+	/// The damaged blocks are re-indexed if `repair` is set.
+	#[method(name = "indexer_checkIntegrity")]
+	async fn check_integrity(
+		&self,
+		from_block: u32,
+		to_block: u32,
+		sample: Option<u32>,
+		repair: Option<bool>,
+	) -> RpcResult<IndexerIntegrityReport>;
+
+	/// Deletes the indexed data of the blocks in `[from_block, to_block]`, returning the
+	/// number of pruned blocks.
+	#[method(name = "indexer_pruneRange")]
+	async fn prune_range(&self, from_block: u32, to_block: u32) -> RpcResult<u32>;
+
+	/// Returns the prepared statement cache usage of the hot queries since the node started.
+	#[method(name = "indexer_statementCacheStats")]
+	fn statement_cache_stats(&self) -> RpcResult<IndexerStatementCacheStats>;
+}
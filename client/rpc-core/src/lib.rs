@@ -21,20 +21,30 @@
 
 pub mod types;
 
+#[cfg(feature = "bundler")]
+mod bundler;
 mod debug;
 mod eth;
 mod eth_pubsub;
+mod frontier;
+#[cfg(feature = "indexer-admin")]
+mod indexer_admin;
 mod net;
 #[cfg(feature = "txpool")]
 mod txpool;
 mod web3;
 
+#[cfg(feature = "bundler")]
+pub use self::bundler::BundlerApiServer;
+#[cfg(feature = "indexer-admin")]
+pub use self::indexer_admin::IndexerAdminApiServer;
 #[cfg(feature = "txpool")]
 pub use self::txpool::TxPoolApiServer;
 pub use self::{
 	debug::DebugApiServer,
 	eth::{EthApiServer, EthFilterApiServer},
 	eth_pubsub::EthPubSubApiServer,
+	frontier::FrontierApiServer,
 	net::NetApiServer,
 	web3::Web3ApiServer,
 };
@@ -0,0 +1,40 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use ethereum_types::{H160, H256, U256};
+use serde::Serialize;
+
+/// Summary of a transaction sent from or to an address.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressTransaction {
+	/// Transaction hash
+	pub hash: H256,
+	/// Block hash
+	pub block_hash: H256,
+	/// Block number
+	pub block_number: U256,
+	/// Transaction index in the block
+	pub transaction_index: U256,
+	/// Sender
+	pub from: H160,
+	/// Recipient, `None` for contract creations
+	pub to: Option<H160>,
+	/// Transferred value
+	pub value: U256,
+}
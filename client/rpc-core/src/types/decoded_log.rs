@@ -0,0 +1,59 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use serde::Serialize;
+
+use crate::types::Log;
+
+/// A log decoded with a known event signature.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedLog {
+	/// The raw log
+	#[serde(flatten)]
+	pub log: Log,
+	/// The decoded event, `None` if the event signature is unknown
+	pub event: Option<DecodedEvent>,
+}
+
+/// An event decoded from a log.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedEvent {
+	/// The event ABI fragment
+	pub signature: String,
+	/// The event name
+	pub name: String,
+	/// The decoded parameters
+	pub params: Vec<DecodedParam>,
+}
+
+/// An event parameter decoded from a log.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedParam {
+	/// The parameter name
+	pub name: String,
+	/// The parameter ABI type
+	#[serde(rename = "type")]
+	pub kind: String,
+	/// Whether the parameter is stored in the log topics
+	pub indexed: bool,
+	/// The human-readable value. Indexed dynamic values are only available as their hash.
+	pub value: String,
+}
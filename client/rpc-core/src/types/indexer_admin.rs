@@ -0,0 +1,45 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use serde::Serialize;
+
+/// Outcome of an integrity check of the SQL indexer.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexerIntegrityReport {
+	/// Number of blocks checked
+	pub checked: u32,
+	/// Canonical blocks that are not indexed
+	pub missing: Vec<u32>,
+	/// Indexed blocks differing from the chain
+	pub mismatched: Vec<u32>,
+	/// Number of blocks re-queued for indexing
+	pub repaired: u32,
+}
+
+/// Prepared statement cache usage of the SQL indexer hot queries.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexerStatementCacheStats {
+	/// Executions reusing a prepared statement
+	pub hits: u64,
+	/// Executions which had to prepare their statement
+	pub misses: u64,
+	/// Share of the executions reusing a prepared statement
+	pub hit_rate: f64,
+}
@@ -19,20 +19,29 @@
 //! RPC types
 
 mod account_info;
+mod address_transaction;
 mod block;
 mod block_number;
 mod bytes;
 mod call_request;
+mod contract_creation;
+mod decoded_log;
 mod fee;
 mod filter;
 mod index;
+#[cfg(feature = "indexer-admin")]
+mod indexer_admin;
 mod log;
 mod receipt;
+#[cfg(feature = "rosetta")]
+mod rosetta;
 mod sync;
 mod transaction;
 mod transaction_request;
 #[cfg(feature = "txpool")]
 mod txpool;
+#[cfg(feature = "bundler")]
+mod user_operation;
 mod work;
 
 pub mod pubsub;
@@ -40,14 +49,30 @@ pub mod pubsub;
 use ethereum::TransactionV2 as EthereumTransaction;
 use ethereum_types::H160;
 
+#[cfg(feature = "indexer-admin")]
+pub use self::indexer_admin::{IndexerIntegrityReport, IndexerStatementCacheStats};
+#[cfg(feature = "rosetta")]
+pub use self::rosetta::{
+	AccountBalance, AccountBalanceRequest, AccountIdentifier, Allow, Amount, BlockIdentifier,
+	BlockRequest, BlockResponse, BlockTransactionRequest, BlockTransactionResponse,
+	ConstructionRequest, Currency, NetworkIdentifier, NetworkListResponse, NetworkOptions,
+	NetworkRequest, NetworkStatus, Operation, OperationIdentifier, OperationStatus,
+	PartialBlockIdentifier, Peer, RosettaBlock, RosettaError, RosettaTransaction,
+	TransactionIdentifier, TransactionIdentifierResponse, Version,
+};
 #[cfg(feature = "txpool")]
 pub use self::txpool::{Summary, TransactionMap, TxPoolResult};
+#[cfg(feature = "bundler")]
+pub use self::user_operation::{UserOperation, UserOperationGasEstimation, UserOperationReceipt};
 pub use self::{
 	account_info::{AccountInfo, EthAccount, ExtAccountInfo, RecoveredAccount, StorageProof},
+	address_transaction::AddressTransaction,
 	block::{Block, BlockTransactions, Header, Rich, RichBlock, RichHeader},
 	block_number::BlockNumberOrHash,
 	bytes::Bytes,
 	call_request::CallStateOverride,
+	contract_creation::ContractCreation,
+	decoded_log::{DecodedEvent, DecodedLog, DecodedParam},
 	fee::{FeeHistory, FeeHistoryCache, FeeHistoryCacheItem, FeeHistoryCacheLimit},
 	filter::{
 		Filter, FilterAddress, FilterChanges, FilterPool, FilterPoolItem, FilterType,
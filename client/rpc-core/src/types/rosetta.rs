@@ -0,0 +1,288 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use ethereum_types::{H160, H256};
+use serde::{Deserialize, Serialize};
+
+use crate::types::Bytes;
+
+/// Identifies the network served by the Rosetta api.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct NetworkIdentifier {
+	/// Name of the blockchain
+	pub blockchain: String,
+	/// Name of the network, e.g. `mainnet`
+	pub network: String,
+}
+
+/// Uniquely identifies a block.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct BlockIdentifier {
+	/// Block number
+	pub index: u64,
+	/// Ethereum block hash
+	pub hash: H256,
+}
+
+/// Identifies a block by number, hash or both. The latest block is used when empty.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct PartialBlockIdentifier {
+	/// Block number
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub index: Option<u64>,
+	/// Ethereum block hash
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub hash: Option<H256>,
+}
+
+/// Uniquely identifies a transaction.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct TransactionIdentifier {
+	/// Ethereum transaction hash
+	pub hash: H256,
+}
+
+/// Identifies an account.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct AccountIdentifier {
+	/// Account address
+	pub address: H160,
+}
+
+/// Native currency of the network.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct Currency {
+	/// Currency symbol
+	pub symbol: String,
+	/// Number of decimals of the smallest unit
+	pub decimals: u32,
+}
+
+/// Amount of currency, as a signed integer in the smallest unit.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct Amount {
+	/// Signed decimal value
+	pub value: String,
+	/// Currency of the amount
+	pub currency: Currency,
+}
+
+/// Position of an operation in its transaction.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct OperationIdentifier {
+	/// Operation index
+	pub index: u64,
+}
+
+/// Balance change of a single account.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct Operation {
+	/// Operation identifier
+	pub operation_identifier: OperationIdentifier,
+	/// Operation type, `FEE` or `CALL`
+	#[serde(rename = "type")]
+	pub operation_type: String,
+	/// Operation status, `SUCCESS`
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub status: Option<String>,
+	/// Account whose balance changes
+	pub account: AccountIdentifier,
+	/// Balance change
+	pub amount: Amount,
+}
+
+/// Transaction and the balance changes it caused.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct RosettaTransaction {
+	/// Transaction identifier
+	pub transaction_identifier: TransactionIdentifier,
+	/// Balance changes
+	pub operations: Vec<Operation>,
+}
+
+/// Block and its transactions.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct RosettaBlock {
+	/// Block identifier
+	pub block_identifier: BlockIdentifier,
+	/// Parent block identifier, the block itself for genesis
+	pub parent_block_identifier: BlockIdentifier,
+	/// Block timestamp in milliseconds
+	pub timestamp: u64,
+	/// Transactions of the block
+	pub transactions: Vec<RosettaTransaction>,
+}
+
+/// Current status of the network.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct NetworkStatus {
+	/// Latest block
+	pub current_block_identifier: BlockIdentifier,
+	/// Latest block timestamp in milliseconds
+	pub current_block_timestamp: u64,
+	/// Genesis block
+	pub genesis_block_identifier: BlockIdentifier,
+	/// Connected peers
+	pub peers: Vec<Peer>,
+}
+
+/// Peer connected to the node.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct Peer {
+	/// Peer id
+	pub peer_id: String,
+}
+
+/// Balance of an account at a given block.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct AccountBalance {
+	/// Block the balance was read at
+	pub block_identifier: BlockIdentifier,
+	/// Balances of the account
+	pub balances: Vec<Amount>,
+}
+
+/// Versions of the Rosetta specification and of the node.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct Version {
+	/// Rosetta specification version
+	pub rosetta_version: String,
+	/// Node version
+	pub node_version: String,
+}
+
+/// Status an operation can have, and whether it changed the balances.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct OperationStatus {
+	/// Status
+	pub status: String,
+	/// Whether the operation changed the balances
+	pub successful: bool,
+}
+
+/// Operation statuses, operation types and errors the node can return.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct Allow {
+	/// Operation statuses
+	pub operation_statuses: Vec<OperationStatus>,
+	/// Operation types
+	pub operation_types: Vec<String>,
+	/// Errors
+	pub errors: Vec<RosettaError>,
+	/// Whether the balance of an account can be read at past blocks
+	pub historical_balance_lookup: bool,
+}
+
+/// Versions and capabilities of the node.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct NetworkOptions {
+	/// Versions
+	pub version: Version,
+	/// Capabilities
+	pub allow: Allow,
+}
+
+/// Error returned by the Rosetta api.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct RosettaError {
+	/// Error code
+	pub code: u32,
+	/// Error message, identical for every error with the same code
+	pub message: String,
+	/// Further details about this error
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub description: Option<String>,
+	/// Whether the request can succeed when retried
+	pub retriable: bool,
+}
+
+/// Response of `/network/list`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct NetworkListResponse {
+	/// Networks served by the node
+	pub network_identifiers: Vec<NetworkIdentifier>,
+}
+
+/// Request of `/network/status` and `/network/options`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct NetworkRequest {
+	/// Network
+	pub network_identifier: NetworkIdentifier,
+}
+
+/// Request of `/block`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct BlockRequest {
+	/// Network
+	pub network_identifier: NetworkIdentifier,
+	/// Block to fetch
+	pub block_identifier: PartialBlockIdentifier,
+}
+
+/// Response of `/block`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct BlockResponse {
+	/// Block and its transactions
+	pub block: RosettaBlock,
+}
+
+/// Request of `/block/transaction`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct BlockTransactionRequest {
+	/// Network
+	pub network_identifier: NetworkIdentifier,
+	/// Block including the transaction
+	pub block_identifier: BlockIdentifier,
+	/// Transaction to fetch
+	pub transaction_identifier: TransactionIdentifier,
+}
+
+/// Response of `/block/transaction`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct BlockTransactionResponse {
+	/// Transaction and the balance changes it caused
+	pub transaction: RosettaTransaction,
+}
+
+/// Request of `/account/balance`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct AccountBalanceRequest {
+	/// Network
+	pub network_identifier: NetworkIdentifier,
+	/// Account
+	pub account_identifier: AccountIdentifier,
+	/// Block to read the balance at, the latest one if `None`
+	#[serde(default)]
+	pub block_identifier: Option<PartialBlockIdentifier>,
+}
+
+/// Request of `/construction/hash` and `/construction/submit`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct ConstructionRequest {
+	/// Network
+	pub network_identifier: NetworkIdentifier,
+	/// Signed EIP-2718 transaction
+	pub signed_transaction: Bytes,
+}
+
+/// Response of `/construction/hash` and `/construction/submit`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct TransactionIdentifierResponse {
+	/// Transaction
+	pub transaction_identifier: TransactionIdentifier,
+}
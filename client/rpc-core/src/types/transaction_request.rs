@@ -20,7 +20,7 @@ use ethereum::{
 	AccessListItem, EIP1559TransactionMessage, EIP2930TransactionMessage, LegacyTransactionMessage,
 	TransactionAction,
 };
-use ethereum_types::{H160, U256, U64};
+use ethereum_types::{H160, H256, U256, U64};
 use serde::{Deserialize, Deserializer};
 
 use crate::types::Bytes;
@@ -47,6 +47,8 @@ pub struct TransactionRequest {
 	pub max_fee_per_gas: Option<U256>,
 	/// Maximum fee per gas the sender is willing to pay to miners in wei
 	pub max_priority_fee_per_gas: Option<U256>,
+	/// EIP-4844 maximum fee per blob gas the sender is willing to pay in wei
+	pub max_fee_per_blob_gas: Option<U256>,
 
 	/// Additional data
 	#[serde(flatten)]
@@ -56,6 +58,8 @@ pub struct TransactionRequest {
 	pub access_list: Option<Vec<AccessListItem>>,
 	/// Chain ID that this transaction is valid on
 	pub chain_id: Option<U64>,
+	/// EIP-4844 versioned hashes of the blobs carried by the transaction
+	pub blob_versioned_hashes: Option<Vec<H256>>,
 
 	/// EIP-2718 type
 	#[serde(rename = "type")]
@@ -63,6 +67,11 @@ pub struct TransactionRequest {
 }
 
 impl TransactionRequest {
+	/// Whether the request is for an EIP-4844 blob transaction.
+	pub fn is_blob_transaction(&self) -> bool {
+		self.max_fee_per_blob_gas.is_some() || self.blob_versioned_hashes.is_some()
+	}
+
 	// We accept "data" and "input" for backwards-compatibility reasons.
 	// "input" is the newer name and should be preferred by clients.
 	/// Return the additional data of the transaction.
@@ -138,6 +147,11 @@ pub enum TransactionMessage {
 
 impl From<TransactionRequest> for Option<TransactionMessage> {
 	fn from(req: TransactionRequest) -> Self {
+		// Blob transactions can't be built until the ethereum crate supports their envelope.
+		if req.is_blob_transaction() {
+			return None;
+		}
+
 		match (req.max_fee_per_gas, &req.access_list, req.gas_price) {
 			// EIP1559
 			// Empty fields fall back to the canonical transaction schema.
@@ -308,4 +322,25 @@ mod tests {
 			}
 		);
 	}
+
+	#[test]
+	fn test_deserialize_blob_transaction() {
+		let data = json!({
+			"from": "0x60be2d1d3665660d22ff9624b7be0551ee1ac91b",
+			"to": "0x13fe2d1d3665660d22ff9624b7be0551ee1ac91b",
+			"maxFeePerGas": "0x20",
+			"maxPriorityFeePerGas": "0x30",
+			"maxFeePerBlobGas": "0x40",
+			"blobVersionedHashes": ["0x01b0a4cdd5f55589f5c5b4d46c76704bb6ce95c0a8c09f77f197a57808dded28"],
+			"gas": "0x40",
+			"input": "0x123abc",
+			"type": "0x3"
+		});
+
+		let args = serde_json::from_value::<TransactionRequest>(data).unwrap();
+		assert_eq!(args.max_fee_per_blob_gas, Some(U256::from(0x40)));
+		assert_eq!(args.blob_versioned_hashes.as_ref().map(Vec::len), Some(1));
+		assert!(args.is_blob_transaction());
+		assert!(Option::<TransactionMessage>::from(args).is_none());
+	}
 }
@@ -0,0 +1,88 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use ethereum_types::{H160, H256, U256};
+use serde::{Deserialize, Serialize};
+
+use crate::types::{Bytes, Log, Receipt};
+
+/// ERC-4337 user operation (EntryPoint v0.6 layout).
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperation {
+	/// The account making the operation
+	pub sender: H160,
+	/// Anti-replay parameter
+	pub nonce: U256,
+	/// Factory address and calldata, only set if the account is not deployed yet
+	pub init_code: Bytes,
+	/// The data to pass to the sender during the main execution call
+	pub call_data: Bytes,
+	/// Gas allocated for the main execution call
+	pub call_gas_limit: U256,
+	/// Gas allocated for the verification step
+	pub verification_gas_limit: U256,
+	/// Gas paid to the bundler for the calldata and pre-verification execution
+	pub pre_verification_gas: U256,
+	/// Maximum fee per gas
+	pub max_fee_per_gas: U256,
+	/// Maximum priority fee per gas
+	pub max_priority_fee_per_gas: U256,
+	/// Paymaster address and data, empty if the account pays for itself
+	pub paymaster_and_data: Bytes,
+	/// Data passed to the account along with the nonce during the verification step
+	pub signature: Bytes,
+}
+
+/// Gas estimation of a user operation.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperationGasEstimation {
+	/// Gas overhead of the operation
+	pub pre_verification_gas: U256,
+	/// Gas needed by the verification step
+	pub verification_gas_limit: U256,
+	/// Gas needed by the main execution call
+	pub call_gas_limit: U256,
+}
+
+/// Receipt of a user operation.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperationReceipt {
+	/// Hash of the user operation
+	pub user_op_hash: H256,
+	/// Entry point the operation was sent to
+	pub entry_point: H160,
+	/// The account making the operation
+	pub sender: H160,
+	/// Anti-replay parameter
+	pub nonce: U256,
+	/// Paymaster, zero if the account paid for itself
+	pub paymaster: H160,
+	/// Actual amount paid for the operation
+	pub actual_gas_cost: U256,
+	/// Actual gas used by the operation
+	pub actual_gas_used: U256,
+	/// Whether the main execution call succeeded
+	pub success: bool,
+	/// Logs emitted by the operation
+	pub logs: Vec<Log>,
+	/// Receipt of the bundle transaction
+	pub receipt: Receipt,
+}
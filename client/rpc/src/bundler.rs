@@ -0,0 +1,524 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::sync::{Arc, Mutex};
+
+use ethereum_types::{H160, H256, U256};
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	types::ErrorObjectOwned,
+};
+use schnellru::{ByLength, LruMap};
+// Substrate
+use sp_core::hashing::keccak_256;
+// Frontier
+use fc_rpc_core::{
+	types::{
+		Bytes, TransactionRequest, UserOperation, UserOperationGasEstimation, UserOperationReceipt,
+	},
+	BundlerApiServer, EthApiServer,
+};
+
+use crate::internal_err;
+
+/// Solidity signature of `EntryPoint.handleOps`.
+const HANDLE_OPS_SIGNATURE: &[u8] =
+	b"handleOps((address,uint256,bytes,bytes,uint256,uint256,uint256,uint256,uint256,bytes,bytes)[],address)";
+/// Solidity signature of `IAccount.validateUserOp`.
+const VALIDATE_USER_OP_SIGNATURE: &[u8] =
+	b"validateUserOp((address,uint256,bytes,bytes,uint256,uint256,uint256,uint256,uint256,bytes,bytes),bytes32,uint256)";
+/// Solidity signature of `EntryPoint.simulateValidation`.
+const SIMULATE_VALIDATION_SIGNATURE: &[u8] =
+	b"simulateValidation((address,uint256,bytes,bytes,uint256,uint256,uint256,uint256,uint256,bytes,bytes))";
+/// Solidity signature of the `EntryPoint.ValidationResult` error, the revert of a successful
+/// `simulateValidation`.
+const VALIDATION_RESULT_SIGNATURE: &[u8] =
+	b"ValidationResult((uint256,uint256,bool,uint48,uint48,bytes),(uint256,uint256),(uint256,uint256),(uint256,uint256))";
+/// Solidity signature of the `EntryPoint.FailedOp` error.
+const FAILED_OP_SIGNATURE: &[u8] = b"FailedOp(uint256,string)";
+/// Solidity signature of `EntryPoint.UserOperationEvent`.
+const USER_OPERATION_EVENT_SIGNATURE: &[u8] =
+	b"UserOperationEvent(bytes32,address,address,uint256,bool,uint256,uint256)";
+
+/// Intrinsic gas of the bundle transaction.
+const TRANSACTION_BASE_GAS: u64 = 21_000;
+/// Calldata gas of a zero byte.
+const TRANSACTION_ZERO_DATA_GAS: u64 = 4;
+/// Calldata gas of a non-zero byte.
+const TRANSACTION_NON_ZERO_DATA_GAS: u64 = 16;
+
+/// Number of submitted user operations whose bundle transaction is remembered for
+/// `eth_getUserOperationReceipt`, the oldest being forgotten first.
+const MAX_USER_OPERATIONS: u32 = 4096;
+
+/// ERC-4337 bundler, submitting user operations in `EntryPoint.handleOps` calls.
+///
+/// Each user operation is submitted right away in its own `handleOps` transaction: operations
+/// are not batched per block, so every operation pays the intrinsic gas of a transaction.
+/// Transactions are submitted through the wrapped eth api with `eth_sendTransaction`, so the
+/// `beneficiary` account must be one of the node's signers. User operations are checked with
+/// `EntryPoint.simulateValidation` first, so that the beneficiary doesn't pay for invalid
+/// operations.
+///
+/// Receipts are looked up from the transaction hashes of the last `MAX_USER_OPERATIONS`
+/// operations submitted through this instance, kept in memory: operations submitted through
+/// another node, or before a restart, have no receipt.
+pub struct Bundler<E> {
+	eth: E,
+	entry_point: H160,
+	beneficiary: H160,
+	/// Bundle transaction hash of the latest user operations submitted through this bundler.
+	user_operations: Arc<Mutex<LruMap<H256, H256, ByLength>>>,
+}
+
+impl<E: Clone> Clone for Bundler<E> {
+	fn clone(&self) -> Self {
+		Self {
+			eth: self.eth.clone(),
+			entry_point: self.entry_point,
+			beneficiary: self.beneficiary,
+			user_operations: self.user_operations.clone(),
+		}
+	}
+}
+
+impl<E> Bundler<E>
+where
+	E: EthApiServer,
+{
+	pub fn new(eth: E, entry_point: H160, beneficiary: H160) -> Self {
+		Self {
+			eth,
+			entry_point,
+			beneficiary,
+			user_operations: Arc::new(Mutex::new(LruMap::new(ByLength::new(MAX_USER_OPERATIONS)))),
+		}
+	}
+
+	fn ensure_entry_point(&self, entry_point: H160) -> RpcResult<()> {
+		if entry_point != self.entry_point {
+			return Err(internal_err(format!(
+				"unsupported entry point {:?}",
+				entry_point
+			)));
+		}
+		Ok(())
+	}
+
+	fn chain_id(&self) -> RpcResult<U256> {
+		self.eth
+			.chain_id()?
+			.map(|chain_id| U256::from(chain_id.as_u64()))
+			.ok_or_else(|| internal_err("chain id not available"))
+	}
+
+	/// Check a user operation with `EntryPoint.simulateValidation`, which always reverts: with
+	/// `ValidationResult` for a valid operation and `FailedOp` otherwise.
+	async fn simulate_validation(&self, user_operation: &UserOperation) -> RpcResult<()> {
+		let mut request = TransactionRequest {
+			from: Some(self.beneficiary),
+			to: Some(self.entry_point),
+			..Default::default()
+		};
+		request.data.input = Some(Bytes::new(encode_simulate_validation(user_operation)));
+		let error = match self.eth.call(request, None, None).await {
+			Ok(_) => return Err(internal_err("simulateValidation did not revert")),
+			Err(error) => error,
+		};
+
+		let data = revert_data(&error).unwrap_or_default();
+		if data.starts_with(&selector(VALIDATION_RESULT_SIGNATURE)) {
+			// `returnInfo.sigFailed`, the third word of the leading dynamic tuple.
+			let sig_failed = abi_word(&data, 0)
+				.and_then(|offset| abi_word(&data, offset.checked_add(2 * 32)?))
+				.map_or(true, |sig_failed| sig_failed != 0);
+			if sig_failed {
+				return Err(internal_err("user operation signature validation failed"));
+			}
+			return Ok(());
+		}
+		if data.starts_with(&selector(FAILED_OP_SIGNATURE)) {
+			let reason = abi_word(&data, 32)
+				.and_then(|offset| abi_string(&data, offset))
+				.unwrap_or_default();
+			return Err(internal_err(format!("user operation rejected: {reason}")));
+		}
+		Err(error)
+	}
+
+	async fn estimate(&self, from: H160, to: H160, data: Vec<u8>) -> RpcResult<U256> {
+		let mut request = TransactionRequest {
+			from: Some(from),
+			to: Some(to),
+			..Default::default()
+		};
+		request.data.input = Some(Bytes::new(data));
+		self.eth.estimate_gas(request, None).await
+	}
+}
+
+#[async_trait]
+impl<E> BundlerApiServer for Bundler<E>
+where
+	E: EthApiServer + Send + Sync + 'static,
+{
+	async fn send_user_operation(
+		&self,
+		user_operation: UserOperation,
+		entry_point: H160,
+	) -> RpcResult<H256> {
+		self.ensure_entry_point(entry_point)?;
+		let user_op_hash = user_operation_hash(&user_operation, entry_point, self.chain_id()?);
+		self.simulate_validation(&user_operation).await?;
+
+		let mut request = TransactionRequest {
+			from: Some(self.beneficiary),
+			to: Some(entry_point),
+			..Default::default()
+		};
+		request.data.input = Some(Bytes::new(encode_handle_ops(
+			&[user_operation],
+			self.beneficiary,
+		)));
+		let transaction_hash = self.eth.send_transaction(request).await?;
+
+		self.user_operations
+			.lock()
+			.map_err(|_| internal_err("bundler state poisoned"))?
+			.insert(user_op_hash, transaction_hash);
+
+		Ok(user_op_hash)
+	}
+
+	async fn estimate_user_operation_gas(
+		&self,
+		user_operation: UserOperation,
+		entry_point: H160,
+	) -> RpcResult<UserOperationGasEstimation> {
+		self.ensure_entry_point(entry_point)?;
+		let user_op_hash = user_operation_hash(&user_operation, entry_point, self.chain_id()?);
+
+		let pre_verification_gas = U256::from(calldata_gas(&encode_handle_ops(
+			&[user_operation.clone()],
+			self.beneficiary,
+		)));
+
+		// Counterfactual deployment is paid out of the verification gas.
+		let mut verification_gas_limit = U256::zero();
+		if user_operation.init_code.0.len() >= 20 {
+			let factory = H160::from_slice(&user_operation.init_code.0[..20]);
+			verification_gas_limit += self
+				.estimate(
+					entry_point,
+					factory,
+					user_operation.init_code.0[20..].to_vec(),
+				)
+				.await?;
+		}
+		verification_gas_limit += self
+			.estimate(
+				entry_point,
+				user_operation.sender,
+				encode_validate_user_op(&user_operation, user_op_hash),
+			)
+			.await?;
+
+		let call_gas_limit = self
+			.estimate(
+				entry_point,
+				user_operation.sender,
+				user_operation.call_data.0.clone(),
+			)
+			.await?;
+
+		Ok(UserOperationGasEstimation {
+			pre_verification_gas,
+			verification_gas_limit,
+			call_gas_limit,
+		})
+	}
+
+	async fn user_operation_receipt(&self, hash: H256) -> RpcResult<Option<UserOperationReceipt>> {
+		let transaction_hash = match self
+			.user_operations
+			.lock()
+			.map_err(|_| internal_err("bundler state poisoned"))?
+			.peek(&hash)
+		{
+			Some(transaction_hash) => *transaction_hash,
+			None => return Ok(None),
+		};
+
+		let receipt = match self.eth.transaction_receipt(transaction_hash).await? {
+			Some(receipt) => receipt,
+			None => return Ok(None),
+		};
+
+		let event_topic = H256::from(keccak_256(USER_OPERATION_EVENT_SIGNATURE));
+		let event_index = receipt.logs.iter().position(|log| {
+			log.address == self.entry_point
+				&& log.topics.len() == 4
+				&& log.topics[0] == event_topic
+				&& log.topics[1] == hash
+				&& log.data.0.len() == 4 * 32
+		});
+		let Some(event_index) = event_index else {
+			return Ok(None);
+		};
+
+		let event = &receipt.logs[event_index];
+		let data = &event.data.0;
+		Ok(Some(UserOperationReceipt {
+			user_op_hash: hash,
+			entry_point: self.entry_point,
+			sender: H160::from(event.topics[2]),
+			nonce: U256::from_big_endian(&data[0..32]),
+			paymaster: H160::from(event.topics[3]),
+			success: !U256::from_big_endian(&data[32..64]).is_zero(),
+			actual_gas_cost: U256::from_big_endian(&data[64..96]),
+			actual_gas_used: U256::from_big_endian(&data[96..128]),
+			logs: receipt.logs[..event_index].to_vec(),
+			receipt,
+		}))
+	}
+
+	fn supported_entry_points(&self) -> RpcResult<Vec<H160>> {
+		Ok(vec![self.entry_point])
+	}
+}
+
+/// Returns the `userOpHash` of a user operation, as computed by `EntryPoint.getUserOpHash`.
+pub fn user_operation_hash(
+	user_operation: &UserOperation,
+	entry_point: H160,
+	chain_id: U256,
+) -> H256 {
+	let mut packed = Vec::with_capacity(10 * 32);
+	packed.extend_from_slice(&address_word(user_operation.sender));
+	packed.extend_from_slice(&u256_word(user_operation.nonce));
+	packed.extend_from_slice(&keccak_256(&user_operation.init_code.0));
+	packed.extend_from_slice(&keccak_256(&user_operation.call_data.0));
+	packed.extend_from_slice(&u256_word(user_operation.call_gas_limit));
+	packed.extend_from_slice(&u256_word(user_operation.verification_gas_limit));
+	packed.extend_from_slice(&u256_word(user_operation.pre_verification_gas));
+	packed.extend_from_slice(&u256_word(user_operation.max_fee_per_gas));
+	packed.extend_from_slice(&u256_word(user_operation.max_priority_fee_per_gas));
+	packed.extend_from_slice(&keccak_256(&user_operation.paymaster_and_data.0));
+
+	let mut encoded = Vec::with_capacity(3 * 32);
+	encoded.extend_from_slice(&keccak_256(&packed));
+	encoded.extend_from_slice(&address_word(entry_point));
+	encoded.extend_from_slice(&u256_word(chain_id));
+	H256::from(keccak_256(&encoded))
+}
+
+/// ABI-encodes a `handleOps(UserOperation[], address)` call.
+fn encode_handle_ops(user_operations: &[UserOperation], beneficiary: H160) -> Vec<u8> {
+	let mut encoded = selector(HANDLE_OPS_SIGNATURE).to_vec();
+	encoded.extend_from_slice(&u256_word(U256::from(2 * 32)));
+	encoded.extend_from_slice(&address_word(beneficiary));
+	encoded.extend_from_slice(&u256_word(U256::from(user_operations.len())));
+
+	let tuples = user_operations
+		.iter()
+		.map(encode_user_operation)
+		.collect::<Vec<_>>();
+	let mut offset = tuples.len() * 32;
+	for tuple in &tuples {
+		encoded.extend_from_slice(&u256_word(U256::from(offset)));
+		offset += tuple.len();
+	}
+	for tuple in tuples {
+		encoded.extend(tuple);
+	}
+	encoded
+}
+
+/// ABI-encodes a `simulateValidation(UserOperation)` call.
+fn encode_simulate_validation(user_operation: &UserOperation) -> Vec<u8> {
+	let mut encoded = selector(SIMULATE_VALIDATION_SIGNATURE).to_vec();
+	encoded.extend_from_slice(&u256_word(U256::from(32)));
+	encoded.extend(encode_user_operation(user_operation));
+	encoded
+}
+
+/// ABI-encodes a `validateUserOp(UserOperation, bytes32, uint256)` call.
+fn encode_validate_user_op(user_operation: &UserOperation, user_op_hash: H256) -> Vec<u8> {
+	let mut encoded = selector(VALIDATE_USER_OP_SIGNATURE).to_vec();
+	encoded.extend_from_slice(&u256_word(U256::from(3 * 32)));
+	encoded.extend_from_slice(user_op_hash.as_bytes());
+	encoded.extend_from_slice(&u256_word(U256::zero()));
+	encoded.extend(encode_user_operation(user_operation));
+	encoded
+}
+
+/// ABI-encodes a user operation tuple.
+fn encode_user_operation(user_operation: &UserOperation) -> Vec<u8> {
+	const HEAD_SIZE: usize = 11 * 32;
+
+	let mut head = Vec::with_capacity(HEAD_SIZE);
+	let mut tail = Vec::new();
+	let mut push_bytes = |head: &mut Vec<u8>, bytes: &[u8]| {
+		head.extend_from_slice(&u256_word(U256::from(HEAD_SIZE + tail.len())));
+		tail.extend_from_slice(&u256_word(U256::from(bytes.len())));
+		tail.extend_from_slice(bytes);
+		tail.resize(tail.len() + (32 - bytes.len() % 32) % 32, 0);
+	};
+
+	head.extend_from_slice(&address_word(user_operation.sender));
+	head.extend_from_slice(&u256_word(user_operation.nonce));
+	push_bytes(&mut head, &user_operation.init_code.0);
+	push_bytes(&mut head, &user_operation.call_data.0);
+	head.extend_from_slice(&u256_word(user_operation.call_gas_limit));
+	head.extend_from_slice(&u256_word(user_operation.verification_gas_limit));
+	head.extend_from_slice(&u256_word(user_operation.pre_verification_gas));
+	head.extend_from_slice(&u256_word(user_operation.max_fee_per_gas));
+	head.extend_from_slice(&u256_word(user_operation.max_priority_fee_per_gas));
+	push_bytes(&mut head, &user_operation.paymaster_and_data.0);
+	push_bytes(&mut head, &user_operation.signature.0);
+
+	head.extend(tail);
+	head
+}
+
+/// Gas paid for the calldata of the bundle transaction.
+fn calldata_gas(data: &[u8]) -> u64 {
+	data.iter().fold(TRANSACTION_BASE_GAS, |gas, byte| {
+		gas + if *byte == 0 {
+			TRANSACTION_ZERO_DATA_GAS
+		} else {
+			TRANSACTION_NON_ZERO_DATA_GAS
+		}
+	})
+}
+
+/// The revert data attached to an `eth_call` error.
+fn revert_data(error: &ErrorObjectOwned) -> Option<Vec<u8>> {
+	let data = error.data()?.get().trim_matches('"');
+	hex::decode(data.strip_prefix("0x").unwrap_or(data)).ok()
+}
+
+/// The word at `offset` of the arguments of an ABI-encoded error, following its selector.
+fn abi_word(data: &[u8], offset: usize) -> Option<usize> {
+	let start = offset.checked_add(4)?;
+	let word = data.get(start..start.checked_add(32)?)?;
+	let value = U256::from_big_endian(word);
+	(value <= U256::from(usize::MAX)).then(|| value.as_usize())
+}
+
+/// The string at `offset` of the arguments of an ABI-encoded error, following its selector.
+fn abi_string(data: &[u8], offset: usize) -> Option<String> {
+	let len = abi_word(data, offset)?;
+	let start = offset.checked_add(4 + 32)?;
+	let bytes = data.get(start..start.checked_add(len)?)?;
+	Some(String::from_utf8_lossy(bytes).into_owned())
+}
+
+fn selector(signature: &[u8]) -> [u8; 4] {
+	let hash = keccak_256(signature);
+	[hash[0], hash[1], hash[2], hash[3]]
+}
+
+fn address_word(address: H160) -> [u8; 32] {
+	H256::from(address).0
+}
+
+fn u256_word(value: U256) -> [u8; 32] {
+	let mut word = [0u8; 32];
+	value.to_big_endian(&mut word);
+	word
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn handle_ops_selector_matches_entry_point() {
+		assert_eq!(selector(HANDLE_OPS_SIGNATURE), [0x1f, 0xad, 0x94, 0x8c]);
+	}
+
+	#[test]
+	fn simulate_validation_selector_matches_entry_point() {
+		assert_eq!(
+			selector(SIMULATE_VALIDATION_SIGNATURE),
+			[0xee, 0x21, 0x94, 0x23]
+		);
+		assert_eq!(selector(FAILED_OP_SIGNATURE), [0x22, 0x02, 0x66, 0xb6]);
+	}
+
+	#[test]
+	fn failed_op_reason_is_decoded() {
+		let mut data = selector(FAILED_OP_SIGNATURE).to_vec();
+		data.extend_from_slice(&u256_word(U256::zero()));
+		data.extend_from_slice(&u256_word(U256::from(64)));
+		data.extend_from_slice(&u256_word(U256::from(13)));
+		data.extend_from_slice(b"AA23 reverted");
+		data.resize(4 + 4 * 32, 0);
+
+		let reason = abi_word(&data, 32).and_then(|offset| abi_string(&data, offset));
+		assert_eq!(reason.as_deref(), Some("AA23 reverted"));
+	}
+
+	#[test]
+	fn user_operation_hash_matches_entry_point() {
+		let user_operation = UserOperation {
+			sender: "0x9406cc6185a346906296840746125a0e44976454"
+				.parse()
+				.unwrap(),
+			nonce: U256::one(),
+			call_data: Bytes::new(vec![0xb6, 0x1d, 0x27, 0xf6]),
+			call_gas_limit: U256::from(100_000),
+			verification_gas_limit: U256::from(150_000),
+			pre_verification_gas: U256::from(21_000),
+			max_fee_per_gas: U256::from(1_000_000_000),
+			max_priority_fee_per_gas: U256::from(1_000_000_000),
+			signature: Bytes::new(vec![0xbb; 65]),
+			..Default::default()
+		};
+		// EntryPoint v0.6 on chain 1. The signature is not part of the hash.
+		let entry_point = "0x5ff137d4b0fdcd49dca30c7cf57e578a026d2789"
+			.parse()
+			.unwrap();
+
+		assert_eq!(
+			user_operation_hash(&user_operation, entry_point, U256::one()),
+			"0x883f9a40717ced216e705c99b654f3a2fb671a491bb4a9f2b8b77a5580f6471d"
+				.parse()
+				.unwrap()
+		);
+	}
+
+	#[test]
+	fn user_operation_encoding_is_word_aligned() {
+		let user_operation = UserOperation {
+			sender: H160::repeat_byte(0x11),
+			call_data: Bytes::new(vec![0xaa; 33]),
+			signature: Bytes::new(vec![0xbb; 65]),
+			..Default::default()
+		};
+		let encoded = encode_user_operation(&user_operation);
+		// Head, plus length words and padded contents of the four dynamic fields.
+		assert_eq!(encoded.len(), 11 * 32 + 4 * 32 + 64 + 96);
+		// The `callData` offset points right after the empty `initCode`.
+		assert_eq!(
+			U256::from_big_endian(&encoded[3 * 32..4 * 32]),
+			U256::from(11 * 32 + 32)
+		);
+	}
+}
@@ -19,8 +19,11 @@
 use scale_codec::Encode;
 use schnellru::{LruMap, Unlimited};
 
+/// LRU cache bounded by the estimated byte size of its values.
+///
+/// The size of a value is estimated by its SCALE encoded size when inserted.
 pub struct LRUCacheByteLimited<K, V> {
-	cache: LruMap<K, V, Unlimited>,
+	cache: LruMap<K, (V, u64), Unlimited>,
 	max_size: u64,
 	metrics: Option<LRUCacheByteLimitedMetrics>,
 	size: u64,
@@ -42,6 +45,9 @@ impl<K: Eq + core::hash::Hash, V: Encode> LRUCacheByteLimited<K, V> {
 			},
 			None => None,
 		};
+		if let Some(metrics) = &metrics {
+			metrics.max_size.set(max_size);
+		}
 
 		Self {
 			cache: LruMap::new(Unlimited),
@@ -51,12 +57,12 @@ impl<K: Eq + core::hash::Hash, V: Encode> LRUCacheByteLimited<K, V> {
 		}
 	}
 	pub fn get(&mut self, k: &K) -> Option<&V> {
-		if let Some(v) = self.cache.get(k) {
+		if let Some((v, _)) = self.cache.get(k) {
 			// Update metrics
 			if let Some(metrics) = &self.metrics {
 				metrics.hits.inc();
 			}
-			Some(v)
+			Some(&*v)
 		} else {
 			// Update metrics
 			if let Some(metrics) = &self.metrics {
@@ -66,23 +72,49 @@ impl<K: Eq + core::hash::Hash, V: Encode> LRUCacheByteLimited<K, V> {
 		}
 	}
 	pub fn put(&mut self, k: K, v: V) {
+		let v_size = v.encoded_size() as u64;
+		// A value larger than the whole budget would flush the cache for nothing.
+		if v_size > self.max_size {
+			return;
+		}
 		// Handle size limit
-		self.size += v.encoded_size() as u64;
-
-		while self.size > self.max_size {
-			if let Some((_, v)) = self.cache.pop_oldest() {
-				let v_size = v.encoded_size() as u64;
-				self.size -= v_size;
-			} else {
-				break;
-			}
+		if let Some((_, old_size)) = self.cache.remove(&k) {
+			self.size -= old_size;
 		}
+		self.size += v_size;
+		self.evict_to(self.max_size);
 
 		// Add entry in cache
-		self.cache.insert(k, v);
+		self.cache.insert(k, (v, v_size));
+		// Update metrics
+		if let Some(metrics) = &self.metrics {
+			metrics.size.set(self.size);
+		}
+	}
+	/// Change the byte budget, evicting the least recently used entries exceeding it.
+	pub fn set_max_size(&mut self, max_size: u64) {
+		self.max_size = max_size;
+		self.evict_to(max_size);
 		// Update metrics
 		if let Some(metrics) = &self.metrics {
 			metrics.size.set(self.size);
+			metrics.max_size.set(max_size);
+		}
+	}
+	/// Current estimated byte size of the cached values.
+	pub fn size(&self) -> u64 {
+		self.size
+	}
+	fn evict_to(&mut self, max_size: u64) {
+		while self.size > max_size {
+			if let Some((_, (_, v_size))) = self.cache.pop_oldest() {
+				self.size -= v_size;
+				if let Some(metrics) = &self.metrics {
+					metrics.evictions.inc();
+				}
+			} else {
+				break;
+			}
 		}
 	}
 }
@@ -90,7 +122,9 @@ impl<K: Eq + core::hash::Hash, V: Encode> LRUCacheByteLimited<K, V> {
 struct LRUCacheByteLimitedMetrics {
 	hits: prometheus::IntCounter,
 	miss: prometheus::IntCounter,
+	evictions: prometheus::IntCounter,
 	size: prometheus_endpoint::Gauge<prometheus_endpoint::U64>,
+	max_size: prometheus_endpoint::Gauge<prometheus_endpoint::U64>,
 }
 
 impl LRUCacheByteLimitedMetrics {
@@ -113,6 +147,13 @@ impl LRUCacheByteLimitedMetrics {
 				)?,
 				registry,
 			)?,
+			evictions: prometheus_endpoint::register(
+				prometheus::IntCounter::new(
+					format!("frontier_eth_{}_evictions", cache_name),
+					format!("Evictions of eth {} cache.", cache_name),
+				)?,
+				registry,
+			)?,
 			size: prometheus_endpoint::register(
 				prometheus_endpoint::Gauge::new(
 					format!("frontier_eth_{}_size", cache_name),
@@ -120,6 +161,13 @@ impl LRUCacheByteLimitedMetrics {
 				)?,
 				registry,
 			)?,
+			max_size: prometheus_endpoint::register(
+				prometheus_endpoint::Gauge::new(
+					format!("frontier_eth_{}_max_size", cache_name),
+					format!("Byte budget of eth {} data cache.", cache_name),
+				)?,
+				registry,
+			)?,
 		})
 	}
 }
@@ -143,4 +191,34 @@ mod tests {
 		cache.put(3, "lmn");
 		assert!(cache.get(&3).is_some());
 	}
+
+	#[test]
+	fn test_replaced_entry_size() {
+		let mut cache = LRUCacheByteLimited::new("name", 10, None);
+		cache.put(0, "abcd");
+		cache.put(0, "ef");
+		assert_eq!(cache.size(), 3);
+		assert_eq!(cache.get(&0), Some(&"ef"));
+	}
+
+	#[test]
+	fn test_oversized_entry_is_not_cached() {
+		let mut cache = LRUCacheByteLimited::new("name", 10, None);
+		cache.put(0, "abcd");
+		cache.put(1, "abcdefghijk");
+		assert!(cache.get(&1).is_none());
+		// The cache is not flushed by the oversized entry.
+		assert!(cache.get(&0).is_some());
+	}
+
+	#[test]
+	fn test_set_max_size() {
+		let mut cache = LRUCacheByteLimited::new("name", 10, None);
+		cache.put(0, "abcd");
+		cache.put(1, "efg");
+		cache.set_max_size(4);
+		assert_eq!(cache.size(), 4);
+		assert!(cache.get(&0).is_none());
+		assert!(cache.get(&1).is_some());
+	}
 }
@@ -38,6 +38,7 @@ use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
 use sp_runtime::traits::{Block as BlockT, Header as HeaderT, UniqueSaturatedInto};
 // Frontier
+use fc_api::{FeeHistoryBackend, FeeHistoryEntry};
 use fc_rpc_core::types::*;
 use fc_storage::StorageOverride;
 use fp_rpc::{EthereumRuntimeRPCApi, TransactionStatus};
@@ -64,12 +65,20 @@ enum EthBlockDataCacheMessage<B: BlockT> {
 		block_hash: B::Hash,
 		statuses: Option<Vec<TransactionStatus>>,
 	},
+
+	SetMaxSizes {
+		blocks_cache_max_size: usize,
+		statuses_cache_max_size: usize,
+	},
 }
 
 /// Manage LRU caches for block data and their transaction statuses.
 /// These are large and take a lot of time to fetch from the database.
 /// Storing them in an LRU cache will allow to reduce database accesses
 /// when many subsequent requests are related to the same blocks.
+///
+/// The caches are bounded by the estimated byte size of their entries rather than their
+/// count, since block sizes vary widely, and their budgets can be changed at runtime.
 pub struct EthBlockDataCacheTask<B: BlockT>(mpsc::Sender<EthBlockDataCacheMessage<B>>);
 
 impl<B: BlockT> EthBlockDataCacheTask<B> {
@@ -166,6 +175,14 @@ impl<B: BlockT> EthBlockDataCacheTask<B> {
 							statuses_cache.put(block_hash, statuses);
 						}
 					}
+
+					SetMaxSizes {
+						blocks_cache_max_size,
+						statuses_cache_max_size,
+					} => {
+						blocks_cache.set_max_size(blocks_cache_max_size as u64);
+						statuses_cache.set_max_size(statuses_cache_max_size as u64);
+					}
 				}
 			}
 		});
@@ -245,6 +262,22 @@ impl<B: BlockT> EthBlockDataCacheTask<B> {
 
 		response_rx.await.ok()?
 	}
+
+	/// Change the byte budgets of the blocks and statuses caches, evicting the least
+	/// recently used entries exceeding them.
+	pub async fn set_max_sizes(
+		&self,
+		blocks_cache_max_size: usize,
+		statuses_cache_max_size: usize,
+	) {
+		let _ = self
+			.0
+			.send(EthBlockDataCacheMessage::SetMaxSizes {
+				blocks_cache_max_size,
+				statuses_cache_max_size,
+			})
+			.await;
+	}
 }
 
 pub struct EthTask<B, C, BE>(PhantomData<(B, C, BE)>);
@@ -281,6 +314,7 @@ where
 		storage_override: Arc<dyn StorageOverride<B>>,
 		fee_history_cache: FeeHistoryCache,
 		block_limit: u64,
+		fee_history_backend: Option<Arc<dyn FeeHistoryBackend>>,
 	) {
 		struct TransactionHelper {
 			gas_used: u64,
@@ -338,15 +372,13 @@ where
 						},
 						effective_reward: match block.transactions.get(i) {
 							Some(ethereum::TransactionV2::Legacy(t)) => {
-								UniqueSaturatedInto::<u64>::unique_saturated_into(t.gas_price.saturating_sub(base_fee))
+								UniqueSaturatedInto::<u64>::unique_saturated_into(fp_evm::fee::legacy_priority_fee(base_fee, t.gas_price))
 							}
 							Some(ethereum::TransactionV2::EIP2930(t)) => {
-								UniqueSaturatedInto::<u64>::unique_saturated_into(t.gas_price.saturating_sub(base_fee))
+								UniqueSaturatedInto::<u64>::unique_saturated_into(fp_evm::fee::legacy_priority_fee(base_fee, t.gas_price))
 							}
 							Some(ethereum::TransactionV2::EIP1559(t)) => UniqueSaturatedInto::<u64>::unique_saturated_into(
-									t
-										.max_priority_fee_per_gas
-										.min(t.max_fee_per_gas.saturating_sub(base_fee))
+									fp_evm::fee::effective_priority_fee(base_fee, t.max_fee_per_gas, Some(t.max_priority_fee_per_gas))
 							),
 							None => 0,
 						},
@@ -376,8 +408,9 @@ where
 			(result, block_number)
 		};
 
-		// Commits the result to cache
-		let commit_if_any = |item: FeeHistoryCacheItem, key: Option<u64>| {
+		// Commits the result to cache, returning the evicted keys.
+		let commit_if_any = |item: FeeHistoryCacheItem, key: Option<u64>| -> Vec<u64> {
+			let mut evicted = Vec::new();
 			if let (Some(block_number), Ok(fee_history_cache)) =
 				(key, &mut fee_history_cache.lock())
 			{
@@ -392,10 +425,37 @@ where
 					// Cannot overflow.
 					let key = first_out - i;
 					fee_history_cache.remove(&key);
+					evicted.push(key);
 				}
 			}
+			evicted
 		};
 
+		// Seed the cache with the persisted window, so it is served right after a restart.
+		if let Some(backend) = &fee_history_backend {
+			let best_number: u64 = client.info().best_number.unique_saturated_into();
+			let from_block = best_number.saturating_sub(block_limit.saturating_sub(1));
+			match backend.fee_history(from_block, best_number).await {
+				Ok(entries) => {
+					if let Ok(fee_history_cache) = &mut fee_history_cache.lock() {
+						fee_history_cache.extend(entries.into_iter().map(|(number, entry)| {
+							(
+								number,
+								FeeHistoryCacheItem {
+									base_fee: entry.base_fee,
+									gas_used_ratio: entry.gas_used_ratio,
+									rewards: entry.rewards,
+								},
+							)
+						}));
+					}
+				}
+				Err(err) => {
+					log::warn!(target: "rpc", "Failed to load the persisted fee history: {err}")
+				}
+			}
+		}
+
 		let mut notification_st = client.import_notification_stream();
 
 		while let Some(notification) = notification_st.next().await {
@@ -420,7 +480,23 @@ where
 				}
 				// Cache the imported block.
 				let (result, block_number) = fee_history_cache_item(notification.hash);
-				commit_if_any(result, block_number);
+				let entry = FeeHistoryEntry {
+					base_fee: result.base_fee,
+					gas_used_ratio: result.gas_used_ratio,
+					rewards: result.rewards.clone(),
+				};
+				let evicted = commit_if_any(result, block_number);
+				// Persist it, overwriting the entry of any retracted block at the same height.
+				if let (Some(backend), Some(block_number)) = (&fee_history_backend, block_number) {
+					if let Err(err) = backend.write_fee_history(block_number, &entry).await {
+						log::warn!(target: "rpc", "Failed to persist the fee history: {err}");
+					}
+					if !evicted.is_empty() {
+						if let Err(err) = backend.remove_fee_history(&evicted).await {
+							log::warn!(target: "rpc", "Failed to prune the fee history: {err}");
+						}
+					}
+				}
 			}
 		}
 	}
@@ -24,7 +24,7 @@ use jsonrpsee::core::RpcResult;
 use sc_client_api::backend::{Backend, StorageProvider};
 use sc_transaction_pool::ChainApi;
 use sc_transaction_pool_api::InPoolTransaction;
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
 use sp_core::hashing::keccak_256;
 use sp_runtime::traits::Block as BlockT;
@@ -46,6 +46,26 @@ where
 	BE: Backend<B> + 'static,
 	A: ChainApi<Block = B>,
 {
+	/// Add the Etherscan style `priorityFees` and `burntFees` block reward fields, if the
+	/// runtime tracks them.
+	fn insert_block_fees(&self, substrate_hash: B::Hash, rich_block: &mut RichBlock) {
+		let api = self.client.runtime_api();
+		let has_block_fees = api
+			.has_api_with::<dyn EthereumRuntimeRPCApi<B>, _>(substrate_hash, |version| version >= 6)
+			.unwrap_or(false);
+		if !has_block_fees {
+			return;
+		}
+		if let Ok(fees) = api.block_fees(substrate_hash) {
+			rich_block
+				.extra_info
+				.insert("priorityFees".into(), format!("{:#x}", fees.priority_fees));
+			rich_block
+				.extra_info
+				.insert("burntFees".into(), format!("{:#x}", fees.base_fees_burned));
+		}
+	}
+
 	pub async fn block_by_hash(&self, hash: H256, full: bool) -> RpcResult<Option<RichBlock>> {
 		let BlockInfo {
 			block,
@@ -65,6 +85,7 @@ where
 					Some(base_fee),
 					false,
 				);
+				self.insert_block_fees(substrate_hash, &mut rich_block);
 
 				let substrate_hash = H256::from_slice(substrate_hash.as_ref());
 				if let Some(parent_hash) = self
@@ -121,6 +142,7 @@ where
 							base_fee,
 							false,
 						);
+						self.insert_block_fees(substrate_hash, &mut rich_block);
 
 						let substrate_hash = H256::from_slice(substrate_hash.as_ref());
 						if let Some(parent_hash) = self
@@ -30,7 +30,7 @@ use sp_block_builder::BlockBuilder as BlockBuilderApi;
 use sp_blockchain::HeaderBackend;
 use sp_externalities::Extensions;
 use sp_inherents::CreateInherentDataProviders;
-use sp_io::hashing::{blake2_128, twox_128};
+use sp_io::hashing::{blake2_128, keccak_256, twox_128};
 use sp_runtime::{
 	traits::{Block as BlockT, HashingFor},
 	DispatchError, SaturatedConversion,
@@ -40,13 +40,18 @@ use sp_state_machine::OverlayedChanges;
 use fc_rpc_core::types::*;
 use fp_evm::{ExecutionInfo, ExecutionInfoV2};
 use fp_rpc::{EthereumRuntimeRPCApi, RuntimeStorageOverride};
-use fp_storage::constants::{EVM_ACCOUNT_CODES, EVM_ACCOUNT_STORAGES, PALLET_EVM};
+use fp_storage::constants::{EVM_ACCOUNT_CODES, EVM_ACCOUNT_STORAGES, EVM_CODES, PALLET_EVM};
 
 use crate::{
 	eth::{Eth, EthConfig},
 	frontier_backend_client, internal_err,
 };
 
+/// JSON-RPC error code of a reverted execution, as returned by Geth.
+const EXECUTION_REVERTED_ERROR_CODE: i32 = 3;
+/// Selector of the `Error(string)` revert reasons.
+const ERROR_STRING_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+
 /// Allow to adapt a request for `estimate_gas`.
 /// Can be used to estimate gas of some contracts using a different function
 /// in the case the normal gas estimation doesn't work.
@@ -135,21 +140,26 @@ where
 			return Err(internal_err("failed to retrieve Runtime Api version"));
 		};
 
-		let block = if api_version > 1 {
-			api.current_block(substrate_hash)
+		let block_gas_limit = if api_version >= 8 {
+			api.block_gas_limit(substrate_hash)
 				.map_err(|err| internal_err(format!("runtime error: {err}")))?
 		} else {
-			#[allow(deprecated)]
-			let legacy_block = api
-				.current_block_before_version_2(substrate_hash)
-				.map_err(|err| internal_err(format!("runtime error: {err}")))?;
-			legacy_block.map(|block| block.into())
-		};
+			let block = if api_version > 1 {
+				api.current_block(substrate_hash)
+					.map_err(|err| internal_err(format!("runtime error: {err}")))?
+			} else {
+				#[allow(deprecated)]
+				let legacy_block = api
+					.current_block_before_version_2(substrate_hash)
+					.map_err(|err| internal_err(format!("runtime error: {err}")))?;
+				legacy_block.map(|block| block.into())
+			};
 
-		let block_gas_limit = block
-			.ok_or_else(|| internal_err("block unavailable, cannot query gas limit"))?
-			.header
-			.gas_limit;
+			block
+				.ok_or_else(|| internal_err("block unavailable, cannot query gas limit"))?
+				.header
+				.gas_limit
+		};
 		let max_gas_limit = block_gas_limit * self.execute_gas_limit_multiplier;
 
 		// use given gas limit or query current block's limit
@@ -461,12 +471,21 @@ where
 			}
 		}
 
-		let block_gas_limit = {
-			let block = block_data_cache.current_block(substrate_hash).await;
-			block
-				.ok_or_else(|| internal_err("block unavailable, cannot query gas limit"))?
-				.header
-				.gas_limit
+		let block_gas_limit = if let Ok(Some(api_version)) =
+			api.api_version::<dyn EthereumRuntimeRPCApi<B>>(substrate_hash)
+		{
+			if api_version >= 8 {
+				api.block_gas_limit(substrate_hash)
+					.map_err(|err| internal_err(format!("runtime error: {err}")))?
+			} else {
+				let block = block_data_cache.current_block(substrate_hash).await;
+				block
+					.ok_or_else(|| internal_err("block unavailable, cannot query gas limit"))?
+					.header
+					.gas_limit
+			}
+		} else {
+			return Err(internal_err("failed to retrieve Runtime Api version"));
 		};
 
 		let max_gas_limit = block_gas_limit * self.execute_gas_limit_multiplier;
@@ -904,13 +923,21 @@ where
 				}
 
 				if let Some(code) = &state_override.code {
+					let code = code.clone().into_vec();
+					let code_hash = H256::from(keccak_256(&code));
+
+					let mut code_key = [twox_128(PALLET_EVM), twox_128(EVM_CODES)]
+						.concat()
+						.to_vec();
+					code_key.extend(code_hash.as_bytes());
+					overlayed_changes.set_storage(code_key, Some(code.encode()));
+
 					let mut key = [twox_128(PALLET_EVM), twox_128(EVM_ACCOUNT_CODES)]
 						.concat()
 						.to_vec();
 					key.extend(blake2_128(address.as_bytes()));
 					key.extend(address.as_bytes());
-					let encoded_code = code.clone().into_vec().encode();
-					overlayed_changes.set_storage(key.clone(), Some(encoded_code));
+					overlayed_changes.set_storage(key, Some(code_hash.encode()));
 				}
 
 				let mut account_storage_key =
@@ -972,25 +999,15 @@ pub fn error_on_execution_failure(reason: &ExitReason, data: &[u8]) -> RpcResult
 			))
 		}
 		ExitReason::Revert(_) => {
-			const LEN_START: usize = 36;
-			const MESSAGE_START: usize = 68;
-
-			let mut message = "VM Exception while processing transaction: revert".to_string();
-			// A minimum size of error function selector (4) + offset (32) + string length (32)
-			// should contain a utf-8 encoded revert reason.
-			if data.len() > MESSAGE_START {
-				let message_len =
-					U256::from(&data[LEN_START..MESSAGE_START]).saturated_into::<usize>();
-				let message_end = MESSAGE_START.saturating_add(message_len);
-
-				if data.len() >= message_end {
-					let body: &[u8] = &data[MESSAGE_START..message_end];
-					if let Ok(reason) = std::str::from_utf8(body) {
-						message = format!("{message} {reason}");
-					}
-				}
-			}
-			Err(crate::internal_err_with_data(message, data))
+			let message = match decode_revert_reason(data) {
+				Some(reason) => format!("execution reverted: {reason}"),
+				None => "execution reverted".to_string(),
+			};
+			Err(crate::err(
+				EXECUTION_REVERTED_ERROR_CODE,
+				message,
+				Some(data),
+			))
 		}
 		ExitReason::Fatal(err) => Err(crate::internal_err_with_data(
 			format!("evm fatal: {err:?}"),
@@ -999,6 +1016,25 @@ pub fn error_on_execution_failure(reason: &ExitReason, data: &[u8]) -> RpcResult
 	}
 }
 
+/// The reason of an `Error(string)` revert, `None` for the other revert data (custom errors,
+/// panics) which are left to the caller to decode from the error data.
+fn decode_revert_reason(data: &[u8]) -> Option<&str> {
+	const LEN_START: usize = 36;
+	const MESSAGE_START: usize = 68;
+
+	// A minimum size of error function selector (4) + offset (32) + string length (32)
+	// should contain a utf-8 encoded revert reason.
+	if data.len() < MESSAGE_START || data[..4] != ERROR_STRING_SELECTOR {
+		return None;
+	}
+	let message_len = U256::from(&data[LEN_START..MESSAGE_START]).saturated_into::<usize>();
+	let message_end = MESSAGE_START.saturating_add(message_len);
+	let body = data.get(MESSAGE_START..message_end)?;
+	std::str::from_utf8(body)
+		.ok()
+		.filter(|reason| !reason.is_empty())
+}
+
 struct FeeDetails {
 	gas_price: Option<U256>,
 	max_fee_per_gas: Option<U256>,
@@ -1050,3 +1086,52 @@ fn fee_details(
 		}),
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use evm::ExitRevert;
+
+	fn error_string(reason: &str) -> Vec<u8> {
+		let mut data = ERROR_STRING_SELECTOR.to_vec();
+		data.extend_from_slice(H256::from_low_u64_be(32).as_bytes());
+		data.extend_from_slice(H256::from_low_u64_be(reason.len() as u64).as_bytes());
+		let mut body = reason.as_bytes().to_vec();
+		body.resize(reason.len().div_ceil(32) * 32, 0);
+		data.extend_from_slice(&body);
+		data
+	}
+
+	#[test]
+	fn revert_with_reason_is_reported_like_geth() {
+		let data = error_string("Value must not be greater than 10.");
+		let err = error_on_execution_failure(&ExitReason::Revert(ExitRevert::Reverted), &data)
+			.unwrap_err();
+
+		assert_eq!(err.code(), EXECUTION_REVERTED_ERROR_CODE);
+		assert_eq!(
+			err.message(),
+			"execution reverted: Value must not be greater than 10."
+		);
+		assert_eq!(
+			err.data().map(|data| data.get().to_string()),
+			Some(format!("\"0x{}\"", hex::encode(&data)))
+		);
+	}
+
+	#[test]
+	fn revert_with_custom_error_keeps_the_data() {
+		// `Unauthorized(address)`
+		let mut data = vec![0x8e, 0x4a, 0x23, 0xd6];
+		data.extend_from_slice(H256::repeat_byte(0x11).as_bytes());
+		let err = error_on_execution_failure(&ExitReason::Revert(ExitRevert::Reverted), &data)
+			.unwrap_err();
+
+		assert_eq!(err.code(), EXECUTION_REVERTED_ERROR_CODE);
+		assert_eq!(err.message(), "execution reverted");
+		assert_eq!(
+			err.data().map(|data| data.get().to_string()),
+			Some(format!("\"0x{}\"", hex::encode(&data)))
+		);
+	}
+}
@@ -21,7 +21,7 @@ use jsonrpsee::core::RpcResult;
 // Substrate
 use sc_client_api::backend::{Backend, StorageProvider};
 use sc_transaction_pool::ChainApi;
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
 use sp_runtime::{
 	traits::{Block as BlockT, UniqueSaturatedInto},
@@ -129,15 +129,28 @@ where
 				if rewards.len() > 0 {
 					response.reward = Some(rewards);
 				}
-				// Calculate next base fee.
-				if let (Some(last_gas_used), Some(last_fee_per_gas)) = (
+				let substrate_hash = self
+					.client
+					.expect_block_hash_from_id(&id)
+					.map_err(|_| internal_err(format!("Expect block number from id: {}", id)))?;
+				// Next base fee as computed by the runtime, if it exposes it.
+				let api = self.client.runtime_api();
+				let next_base_fee =
+					match api.api_version::<dyn EthereumRuntimeRPCApi<B>>(substrate_hash) {
+						Ok(Some(api_version)) if api_version >= 9 => {
+							api.next_base_fee_per_gas(substrate_hash).ok()
+						}
+						_ => None,
+					};
+				if let Some(next_base_fee) = next_base_fee {
+					if !response.base_fee_per_gas.is_empty() {
+						response.base_fee_per_gas.push(next_base_fee);
+					}
+				} else if let (Some(last_gas_used), Some(last_fee_per_gas)) = (
 					response.gas_used_ratio.last(),
 					response.base_fee_per_gas.last(),
 				) {
-					let substrate_hash =
-						self.client.expect_block_hash_from_id(&id).map_err(|_| {
-							internal_err(format!("Expect block number from id: {}", id))
-						})?;
+					// Otherwise estimate the next base fee from the last block.
 					let elasticity = self
 						.storage_override
 						.elasticity(substrate_hash)
@@ -177,6 +190,10 @@ where
 	}
 
 	pub fn max_priority_fee_per_gas(&self) -> RpcResult<U256> {
+		if let Some(fee) = self.fixed_priority_fee_per_gas {
+			return Ok(fee);
+		}
+
 		// https://github.com/ethereum/go-ethereum/blob/master/eth/ethconfig/config.go#L44-L51
 		let at_percentile = 60;
 		let block_count = 20;
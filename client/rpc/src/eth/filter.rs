@@ -25,6 +25,7 @@ use std::{
 
 use ethereum::BlockV2 as EthereumBlock;
 use ethereum_types::{H256, U256};
+use futures::{stream, StreamExt};
 use jsonrpsee::core::{async_trait, RpcResult};
 // Substrate
 use sc_client_api::backend::{Backend, StorageProvider};
@@ -35,13 +36,26 @@ use sp_blockchain::HeaderBackend;
 use sp_core::hashing::keccak_256;
 use sp_runtime::{
 	generic::BlockId,
-	traits::{Block as BlockT, NumberFor, One, Saturating, UniqueSaturatedInto},
+	traits::{Block as BlockT, NumberFor, One, Saturating, UniqueSaturatedInto, Zero},
 };
 // Frontier
+use fc_api::LogIndexerError;
 use fc_rpc_core::{types::*, EthFilterApiServer};
 use fp_rpc::{EthereumRuntimeRPCApi, TransactionStatus};
 
-use crate::{cache::EthBlockDataCacheTask, frontier_backend_client, internal_err};
+use crate::{
+	cache::EthBlockDataCacheTask, frontier_backend_client, internal_err,
+	method_limits::MethodLimits,
+};
+
+/// Error code of a log query exceeding the result limit (EIP-1474).
+const LIMIT_EXCEEDED_ERROR_CODE: i32 = -32005;
+
+/// Error code of a log query targeting blocks below the history horizon.
+const HISTORY_UNAVAILABLE_ERROR_CODE: i32 = -32000;
+
+/// Number of blocks whose data is fetched concurrently when filtering logs without the indexer.
+const PARALLEL_BLOCK_FETCHES: usize = 16;
 
 pub struct EthFilter<B: BlockT, C, BE, A: ChainApi> {
 	client: Arc<C>,
@@ -51,9 +65,26 @@ pub struct EthFilter<B: BlockT, C, BE, A: ChainApi> {
 	max_stored_filters: usize,
 	max_past_logs: u32,
 	block_data_cache: Arc<EthBlockDataCacheTask<B>>,
+	method_limits: MethodLimits,
 	_marker: PhantomData<BE>,
 }
 
+impl<B: BlockT, C, BE, A: ChainApi> Clone for EthFilter<B, C, BE, A> {
+	fn clone(&self) -> Self {
+		Self {
+			client: self.client.clone(),
+			backend: self.backend.clone(),
+			graph: self.graph.clone(),
+			filter_pool: self.filter_pool.clone(),
+			max_stored_filters: self.max_stored_filters,
+			max_past_logs: self.max_past_logs,
+			block_data_cache: self.block_data_cache.clone(),
+			method_limits: self.method_limits.clone(),
+			_marker: PhantomData,
+		}
+	}
+}
+
 impl<B: BlockT, C, BE, A: ChainApi> EthFilter<B, C, BE, A> {
 	pub fn new(
 		client: Arc<C>,
@@ -72,9 +103,17 @@ impl<B: BlockT, C, BE, A: ChainApi> EthFilter<B, C, BE, A> {
 			max_stored_filters,
 			max_past_logs,
 			block_data_cache,
+			method_limits: MethodLimits::default(),
 			_marker: PhantomData,
 		}
 	}
+
+	/// Apply the given per-method limits to eth_getLogs, eth_getFilterLogs and
+	/// eth_getFilterChanges.
+	pub fn with_method_limits(mut self, method_limits: MethodLimits) -> Self {
+		self.method_limits = method_limits;
+		self
+	}
 }
 
 impl<B, C, BE, A> EthFilter<B, C, BE, A>
@@ -168,177 +207,266 @@ where
 	}
 
 	async fn filter_changes(&self, index: Index) -> RpcResult<FilterChanges> {
-		// There are multiple branches that needs to return async blocks.
-		// Also, each branch need to (synchronously) do stuff with the pool
-		// (behind a lock), and the lock should be released before entering
-		// an async block.
-		//
-		// To avoid issues with multiple async blocks (having different
-		// anonymous types) we collect all necessary data in this enum then have
-		// a single async block.
-		enum FuturePath<B: BlockT> {
-			Block {
-				last: u64,
-				next: u64,
-			},
-			PendingTransaction {
-				new_hashes: Vec<H256>,
-			},
-			Log {
-				filter: Filter,
-				from_number: NumberFor<B>,
-				current_number: NumberFor<B>,
-			},
-			Error(jsonrpsee::types::ErrorObjectOwned),
-		}
+		self.method_limits
+			.run("eth_getFilterChanges", async move {
+				// There are multiple branches that needs to return async blocks.
+				// Also, each branch need to (synchronously) do stuff with the pool
+				// (behind a lock), and the lock should be released before entering
+				// an async block.
+				//
+				// To avoid issues with multiple async blocks (having different
+				// anonymous types) we collect all necessary data in this enum then have
+				// a single async block.
+				enum FuturePath<B: BlockT> {
+					Block {
+						last: u64,
+						next: u64,
+					},
+					PendingTransaction {
+						new_hashes: Vec<H256>,
+					},
+					Log {
+						filter: Filter,
+						from_number: NumberFor<B>,
+						current_number: NumberFor<B>,
+					},
+					Error(jsonrpsee::types::ErrorObjectOwned),
+				}
 
-		let key = U256::from(index.value());
-		let info = self.client.info();
-		let best_hash = info.best_hash;
-		let best_number = UniqueSaturatedInto::<u64>::unique_saturated_into(info.best_number);
-		let pool = self.filter_pool.clone();
-		// Try to lock.
-		let path = if let Ok(locked) = &mut pool.lock() {
-			// Try to get key.
-			if let Some(pool_item) = locked.get(&key).cloned() {
-				match &pool_item.filter_type {
-					// For each block created since last poll, get a vector of ethereum hashes.
-					FilterType::Block => {
-						let last = pool_item.last_poll.to_min_block_num().unwrap();
-						let next = best_number + 1;
-						// Update filter `last_poll`.
-						locked.insert(
-							key,
-							FilterPoolItem {
-								last_poll: BlockNumberOrHash::Num(next),
-								filter_type: pool_item.filter_type.clone(),
-								at_block: pool_item.at_block,
-								pending_transaction_hashes: HashSet::new(),
-							},
-						);
-
-						FuturePath::<B>::Block { last, next }
+				let key = U256::from(index.value());
+				let info = self.client.info();
+				let best_hash = info.best_hash;
+				let best_number =
+					UniqueSaturatedInto::<u64>::unique_saturated_into(info.best_number);
+				let pool = self.filter_pool.clone();
+				// Try to lock.
+				let path = if let Ok(locked) = &mut pool.lock() {
+					// Try to get key.
+					if let Some(pool_item) = locked.get(&key).cloned() {
+						match &pool_item.filter_type {
+							// For each block created since last poll, get a vector of ethereum hashes.
+							FilterType::Block => {
+								let last = pool_item.last_poll.to_min_block_num().unwrap();
+								let next = best_number + 1;
+								// Update filter `last_poll`.
+								locked.insert(
+									key,
+									FilterPoolItem {
+										last_poll: BlockNumberOrHash::Num(next),
+										filter_type: pool_item.filter_type.clone(),
+										at_block: pool_item.at_block,
+										pending_transaction_hashes: HashSet::new(),
+									},
+								);
+
+								FuturePath::<B>::Block { last, next }
+							}
+							FilterType::PendingTransaction => {
+								let previous_hashes = pool_item.pending_transaction_hashes;
+								let txs_ready = self
+									.graph
+									.validated_pool()
+									.ready()
+									.map(|in_pool_tx| in_pool_tx.data().clone())
+									.collect();
+								// Use the runtime to match the (here) opaque extrinsics against ethereum transactions.
+								let api = self.client.runtime_api();
+								let current_hashes = api
+									.extrinsic_filter(best_hash, txs_ready)
+									.map_err(|err| {
+										internal_err(format!(
+											"fetch ready transactions failed: {err:?}"
+										))
+									})?
+									.into_iter()
+									.map(|tx| tx.hash())
+									.collect::<HashSet<_>>();
+
+								// Update filter `last_poll`.
+								locked.insert(
+									key,
+									FilterPoolItem {
+										last_poll: BlockNumberOrHash::Num(best_number + 1),
+										filter_type: pool_item.filter_type.clone(),
+										at_block: pool_item.at_block,
+										pending_transaction_hashes: current_hashes.clone(),
+									},
+								);
+
+								let mew_hashes = current_hashes
+									.difference(&previous_hashes)
+									.collect::<HashSet<&H256>>();
+								FuturePath::PendingTransaction {
+									new_hashes: mew_hashes.into_iter().copied().collect(),
+								}
+							}
+							// For each event since last poll, get a vector of ethereum logs.
+							FilterType::Log(filter) => {
+								// Update filter `last_poll`.
+								locked.insert(
+									key,
+									FilterPoolItem {
+										last_poll: BlockNumberOrHash::Num(best_number + 1),
+										filter_type: pool_item.filter_type.clone(),
+										at_block: pool_item.at_block,
+										pending_transaction_hashes: HashSet::new(),
+									},
+								);
+
+								// Either the filter-specific `to` block or best block.
+								let best_number = self.client.info().best_number;
+								let mut current_number = block_number(
+									self.client.as_ref(),
+									filter.to_block,
+									best_number,
+								);
+
+								if current_number > best_number {
+									current_number = best_number;
+								}
+
+								// The from clause is the max(last_poll, filter_from).
+								let last_poll = pool_item
+									.last_poll
+									.to_min_block_num()
+									.unwrap()
+									.unique_saturated_into();
+
+								let filter_from = block_number(
+									self.client.as_ref(),
+									filter.from_block,
+									last_poll,
+								);
+
+								let from_number = std::cmp::max(last_poll, filter_from);
+
+								// Build the response.
+								FuturePath::Log {
+									filter: filter.clone(),
+									from_number,
+									current_number,
+								}
+							}
+						}
+					} else {
+						FuturePath::Error(internal_err(format!(
+							"Filter id {:?} does not exist.",
+							key
+						)))
 					}
-					FilterType::PendingTransaction => {
-						let previous_hashes = pool_item.pending_transaction_hashes;
-						let txs_ready = self
-							.graph
-							.validated_pool()
-							.ready()
-							.map(|in_pool_tx| in_pool_tx.data().clone())
-							.collect();
-						// Use the runtime to match the (here) opaque extrinsics against ethereum transactions.
-						let api = self.client.runtime_api();
-						let current_hashes = api
-							.extrinsic_filter(best_hash, txs_ready)
-							.map_err(|err| {
-								internal_err(format!("fetch ready transactions failed: {err:?}"))
-							})?
-							.into_iter()
-							.map(|tx| tx.hash())
-							.collect::<HashSet<_>>();
-
-						// Update filter `last_poll`.
-						locked.insert(
-							key,
-							FilterPoolItem {
-								last_poll: BlockNumberOrHash::Num(best_number + 1),
-								filter_type: pool_item.filter_type.clone(),
-								at_block: pool_item.at_block,
-								pending_transaction_hashes: current_hashes.clone(),
-							},
-						);
-
-						let mew_hashes = current_hashes
-							.difference(&previous_hashes)
-							.collect::<HashSet<&H256>>();
-						FuturePath::PendingTransaction {
-							new_hashes: mew_hashes.into_iter().copied().collect(),
+				} else {
+					FuturePath::Error(internal_err("Filter pool is not available."))
+				};
+
+				let client = Arc::clone(&self.client);
+				let backend = Arc::clone(&self.backend);
+				let block_data_cache = Arc::clone(&self.block_data_cache);
+				let max_past_logs = self.max_past_logs;
+
+				match path {
+					FuturePath::Error(err) => Err(err),
+					FuturePath::Block { last, next } => {
+						let mut ethereum_hashes: Vec<H256> = Vec::new();
+						for n in last..next {
+							let id = BlockId::Number(n.unique_saturated_into());
+							let substrate_hash =
+								client.expect_block_hash_from_id(&id).map_err(|_| {
+									internal_err(format!("Expect block number from id: {}", id))
+								})?;
+
+							let block = block_data_cache.current_block(substrate_hash).await;
+							if let Some(block) = block {
+								ethereum_hashes.push(block.header.hash())
+							}
 						}
+						Ok(FilterChanges::Hashes(ethereum_hashes))
 					}
-					// For each event since last poll, get a vector of ethereum logs.
-					FilterType::Log(filter) => {
-						// Update filter `last_poll`.
-						locked.insert(
-							key,
-							FilterPoolItem {
-								last_poll: BlockNumberOrHash::Num(best_number + 1),
-								filter_type: pool_item.filter_type.clone(),
-								at_block: pool_item.at_block,
-								pending_transaction_hashes: HashSet::new(),
-							},
-						);
-
-						// Either the filter-specific `to` block or best block.
-						let best_number = self.client.info().best_number;
-						let mut current_number = filter
-							.to_block
-							.and_then(|v| v.to_min_block_num())
-							.map(|s| s.unique_saturated_into())
-							.unwrap_or(best_number);
-
-						if current_number > best_number {
-							current_number = best_number;
+					FuturePath::PendingTransaction { new_hashes } => {
+						Ok(FilterChanges::Hashes(new_hashes))
+					}
+					FuturePath::Log {
+						filter,
+						from_number,
+						current_number,
+					} => {
+						let mut ret: Vec<Log> = Vec::new();
+						if backend.is_indexed() {
+							let _ = filter_range_logs_indexed(
+								client.as_ref(),
+								backend.log_indexer(),
+								&block_data_cache,
+								&mut ret,
+								max_past_logs,
+								&filter,
+								from_number,
+								current_number,
+							)
+							.await?;
+						} else {
+							let _ = filter_range_logs(
+								client.as_ref(),
+								&block_data_cache,
+								&mut ret,
+								max_past_logs,
+								&filter,
+								from_number,
+								current_number,
+							)
+							.await?;
 						}
 
-						// The from clause is the max(last_poll, filter_from).
-						let last_poll = pool_item
-							.last_poll
-							.to_min_block_num()
-							.unwrap()
-							.unique_saturated_into();
-
-						let filter_from = filter
-							.from_block
-							.and_then(|v| v.to_min_block_num())
-							.map(|s| s.unique_saturated_into())
-							.unwrap_or(last_poll);
-
-						let from_number = std::cmp::max(last_poll, filter_from);
-
-						// Build the response.
-						FuturePath::Log {
-							filter: filter.clone(),
-							from_number,
-							current_number,
-						}
+						Ok(FilterChanges::Logs(ret))
 					}
 				}
-			} else {
-				FuturePath::Error(internal_err(format!("Filter id {:?} does not exist.", key)))
-			}
-		} else {
-			FuturePath::Error(internal_err("Filter pool is not available."))
-		};
+			})
+			.await
+	}
 
-		let client = Arc::clone(&self.client);
-		let backend = Arc::clone(&self.backend);
-		let block_data_cache = Arc::clone(&self.block_data_cache);
-		let max_past_logs = self.max_past_logs;
-
-		match path {
-			FuturePath::Error(err) => Err(err),
-			FuturePath::Block { last, next } => {
-				let mut ethereum_hashes: Vec<H256> = Vec::new();
-				for n in last..next {
-					let id = BlockId::Number(n.unique_saturated_into());
-					let substrate_hash = client.expect_block_hash_from_id(&id).map_err(|_| {
-						internal_err(format!("Expect block number from id: {}", id))
+	async fn filter_logs(&self, index: Index) -> RpcResult<Vec<Log>> {
+		self.method_limits
+			.run("eth_getFilterLogs", async move {
+				let key = U256::from(index.value());
+				let pool = self.filter_pool.clone();
+
+				// We want to get the filter, while releasing the pool lock outside
+				// of the async block.
+				let filter_result: RpcResult<Filter> = (|| {
+					let pool = pool
+						.lock()
+						.map_err(|_| internal_err("Filter pool is not available."))?;
+
+					let pool_item = pool.get(&key).ok_or_else(|| {
+						internal_err(format!("Filter id {:?} does not exist.", key))
 					})?;
 
-					let block = block_data_cache.current_block(substrate_hash).await;
-					if let Some(block) = block {
-						ethereum_hashes.push(block.header.hash())
+					match &pool_item.filter_type {
+						FilterType::Log(filter) => Ok(filter.clone()),
+						_ => Err(internal_err(format!(
+							"Filter id {:?} is not a Log filter.",
+							key
+						))),
 					}
+				})();
+
+				let client = Arc::clone(&self.client);
+				let backend = Arc::clone(&self.backend);
+				let block_data_cache = Arc::clone(&self.block_data_cache);
+				let max_past_logs = self.max_past_logs;
+
+				let filter = filter_result?;
+
+				let best_number = client.info().best_number;
+				let mut current_number =
+					block_number(client.as_ref(), filter.to_block, best_number);
+
+				if current_number > best_number {
+					current_number = best_number;
 				}
-				Ok(FilterChanges::Hashes(ethereum_hashes))
-			}
-			FuturePath::PendingTransaction { new_hashes } => Ok(FilterChanges::Hashes(new_hashes)),
-			FuturePath::Log {
-				filter,
-				from_number,
-				current_number,
-			} => {
+
+				let from_number = block_number(client.as_ref(), filter.from_block, best_number);
+
+				ensure_history_available(backend.as_ref(), from_number).await?;
+
 				let mut ret: Vec<Log> = Vec::new();
 				if backend.is_indexed() {
 					let _ = filter_range_logs_indexed(
@@ -364,86 +492,9 @@ where
 					)
 					.await?;
 				}
-
-				Ok(FilterChanges::Logs(ret))
-			}
-		}
-	}
-
-	async fn filter_logs(&self, index: Index) -> RpcResult<Vec<Log>> {
-		let key = U256::from(index.value());
-		let pool = self.filter_pool.clone();
-
-		// We want to get the filter, while releasing the pool lock outside
-		// of the async block.
-		let filter_result: RpcResult<Filter> = (|| {
-			let pool = pool
-				.lock()
-				.map_err(|_| internal_err("Filter pool is not available."))?;
-
-			let pool_item = pool
-				.get(&key)
-				.ok_or_else(|| internal_err(format!("Filter id {:?} does not exist.", key)))?;
-
-			match &pool_item.filter_type {
-				FilterType::Log(filter) => Ok(filter.clone()),
-				_ => Err(internal_err(format!(
-					"Filter id {:?} is not a Log filter.",
-					key
-				))),
-			}
-		})();
-
-		let client = Arc::clone(&self.client);
-		let backend = Arc::clone(&self.backend);
-		let block_data_cache = Arc::clone(&self.block_data_cache);
-		let max_past_logs = self.max_past_logs;
-
-		let filter = filter_result?;
-
-		let best_number = client.info().best_number;
-		let mut current_number = filter
-			.to_block
-			.and_then(|v| v.to_min_block_num())
-			.map(|s| s.unique_saturated_into())
-			.unwrap_or(best_number);
-
-		if current_number > best_number {
-			current_number = best_number;
-		}
-
-		let from_number = filter
-			.from_block
-			.and_then(|v| v.to_min_block_num())
-			.map(|s| s.unique_saturated_into())
-			.unwrap_or(best_number);
-
-		let mut ret: Vec<Log> = Vec::new();
-		if backend.is_indexed() {
-			let _ = filter_range_logs_indexed(
-				client.as_ref(),
-				backend.log_indexer(),
-				&block_data_cache,
-				&mut ret,
-				max_past_logs,
-				&filter,
-				from_number,
-				current_number,
-			)
-			.await?;
-		} else {
-			let _ = filter_range_logs(
-				client.as_ref(),
-				&block_data_cache,
-				&mut ret,
-				max_past_logs,
-				&filter,
-				from_number,
-				current_number,
-			)
-			.await?;
-		}
-		Ok(ret)
+				Ok(ret)
+			})
+			.await
 	}
 
 	fn uninstall_filter(&self, index: Index) -> RpcResult<bool> {
@@ -463,77 +514,111 @@ where
 	}
 
 	async fn logs(&self, filter: Filter) -> RpcResult<Vec<Log>> {
-		let client = Arc::clone(&self.client);
-		let block_data_cache = Arc::clone(&self.block_data_cache);
-		let backend = Arc::clone(&self.backend);
-		let max_past_logs = self.max_past_logs;
-
-		let mut ret: Vec<Log> = Vec::new();
-		if let Some(hash) = filter.block_hash {
-			let substrate_hash = match frontier_backend_client::load_hash::<B, C>(
-				client.as_ref(),
-				backend.as_ref(),
-				hash,
-			)
-			.await
-			.map_err(|err| internal_err(format!("{:?}", err)))?
-			{
-				Some(hash) => hash,
-				_ => return Err(crate::err(-32000, "unknown block", None)),
-			};
+		self.method_limits
+			.run("eth_getLogs", async move {
+				let client = Arc::clone(&self.client);
+				let block_data_cache = Arc::clone(&self.block_data_cache);
+				let backend = Arc::clone(&self.backend);
+				let max_past_logs = self.max_past_logs;
 
-			let block = block_data_cache.current_block(substrate_hash).await;
-			let statuses = block_data_cache
-				.current_transaction_statuses(substrate_hash)
-				.await;
-			if let (Some(block), Some(statuses)) = (block, statuses) {
-				filter_block_logs(&mut ret, &filter, block, statuses);
-			}
-		} else {
-			let best_number = client.info().best_number;
-			let mut current_number = filter
-				.to_block
-				.and_then(|v| v.to_min_block_num())
-				.map(|s| s.unique_saturated_into())
-				.unwrap_or(best_number);
-
-			if current_number > best_number {
-				current_number = best_number;
-			}
+				let mut ret: Vec<Log> = Vec::new();
+				if let Some(hash) = filter.block_hash {
+					let substrate_hash = match frontier_backend_client::load_hash::<B, C>(
+						client.as_ref(),
+						backend.as_ref(),
+						hash,
+					)
+					.await
+					.map_err(|err| internal_err(format!("{:?}", err)))?
+					{
+						Some(hash) => hash,
+						_ => return Err(crate::err(-32000, "unknown block", None)),
+					};
 
-			let from_number = filter
-				.from_block
-				.and_then(|v| v.to_min_block_num())
-				.map(|s| s.unique_saturated_into())
-				.unwrap_or(best_number);
-
-			if backend.is_indexed() {
-				let _ = filter_range_logs_indexed(
-					client.as_ref(),
-					backend.log_indexer(),
-					&block_data_cache,
-					&mut ret,
-					max_past_logs,
-					&filter,
-					from_number,
-					current_number,
-				)
-				.await?;
-			} else {
-				let _ = filter_range_logs(
-					client.as_ref(),
-					&block_data_cache,
-					&mut ret,
-					max_past_logs,
-					&filter,
-					from_number,
-					current_number,
-				)
-				.await?;
-			}
+					let block = block_data_cache.current_block(substrate_hash).await;
+					let statuses = block_data_cache
+						.current_transaction_statuses(substrate_hash)
+						.await;
+					if let (Some(block), Some(statuses)) = (block, statuses) {
+						filter_block_logs(&mut ret, &filter, block, statuses);
+					}
+				} else {
+					let best_number = client.info().best_number;
+					let mut current_number =
+						block_number(client.as_ref(), filter.to_block, best_number);
+
+					if current_number > best_number {
+						current_number = best_number;
+					}
+
+					let from_number = block_number(client.as_ref(), filter.from_block, best_number);
+
+					ensure_history_available(backend.as_ref(), from_number).await?;
+
+					if backend.is_indexed() {
+						let _ = filter_range_logs_indexed(
+							client.as_ref(),
+							backend.log_indexer(),
+							&block_data_cache,
+							&mut ret,
+							max_past_logs,
+							&filter,
+							from_number,
+							current_number,
+						)
+						.await?;
+					} else {
+						let _ = filter_range_logs(
+							client.as_ref(),
+							&block_data_cache,
+							&mut ret,
+							max_past_logs,
+							&filter,
+							from_number,
+							current_number,
+						)
+						.await?;
+					}
+				}
+				Ok(ret)
+			})
+			.await
+	}
+}
+
+/// The number of the block `number_or_hash` designates, `default` when it doesn't designate a
+/// block by number or tag.
+fn block_number<B: BlockT, C: HeaderBackend<B>>(
+	client: &C,
+	number_or_hash: Option<BlockNumberOrHash>,
+	default: NumberFor<B>,
+) -> NumberFor<B> {
+	match number_or_hash {
+		Some(BlockNumberOrHash::Num(number)) => number.unique_saturated_into(),
+		Some(BlockNumberOrHash::Earliest) => Zero::zero(),
+		Some(BlockNumberOrHash::Safe | BlockNumberOrHash::Finalized) => {
+			client.info().finalized_number
 		}
-		Ok(ret)
+		_ => default,
+	}
+}
+
+/// Rejects the log queries starting below the first block indexed by the backend, whose
+/// logs would be silently missing from the result.
+async fn ensure_history_available<B: BlockT>(
+	backend: &dyn fc_api::Backend<B>,
+	from: NumberFor<B>,
+) -> RpcResult<()> {
+	let from: u64 = UniqueSaturatedInto::<u64>::unique_saturated_into(from);
+	let horizon = backend.history_horizon().await.map_err(internal_err)?;
+	if from < horizon {
+		return Err(crate::err(
+			HISTORY_UNAVAILABLE_ERROR_CODE,
+			format!("block #{from} not indexed, node serves history from #{horizon}"),
+			None,
+		));
 	}
+	Ok(())
 }
 
 async fn filter_range_logs_indexed<B, C, BE>(
@@ -583,17 +668,21 @@ where
 		})
 		.collect::<Vec<Vec<Option<H256>>>>();
 
+	let from: u64 = UniqueSaturatedInto::<u64>::unique_saturated_into(from);
+	let to: u64 = UniqueSaturatedInto::<u64>::unique_saturated_into(to);
 	let time_prepare = timer_prepare.elapsed().as_millis();
 	let timer_fetch = Instant::now();
-	if let Ok(logs) = backend
-		.filter_logs(
-			UniqueSaturatedInto::<u64>::unique_saturated_into(from),
-			UniqueSaturatedInto::<u64>::unique_saturated_into(to),
-			addresses,
-			topics,
-		)
-		.await
-	{
+	// Let a backfilling indexer catch up on the requested range first.
+	backend.prioritize_range(from, to).await;
+	let result = backend.filter_logs(from, to, addresses, topics).await;
+	if let Err(LogIndexerError::TooManyResults { limit }) = result {
+		return Err(crate::err(
+			LIMIT_EXCEEDED_ERROR_CODE,
+			format!("query returned more than {limit} results, narrow your filter"),
+			None,
+		));
+	}
+	if let Ok(logs) = result {
 		let time_fetch = timer_fetch.elapsed().as_millis();
 		let timer_post = Instant::now();
 
@@ -699,8 +788,6 @@ where
 	let max_duration = Duration::from_secs(10);
 	let begin_request = Instant::now();
 
-	let mut current_number = from;
-
 	// Pre-calculate BloomInput for reuse.
 	let topics_input = if filter.topics.is_some() {
 		let filtered_params = FilteredParams::new(Some(filter.clone()));
@@ -708,28 +795,29 @@ where
 	} else {
 		None
 	};
-	let address_bloom_filter = FilteredParams::address_bloom_filter(&filter.address);
-	let topics_bloom_filter = FilteredParams::topics_bloom_filter(&topics_input);
-
-	while current_number <= to {
-		let id = BlockId::Number(current_number);
-		let substrate_hash = client
-			.expect_block_hash_from_id(&id)
-			.map_err(|_| internal_err(format!("Expect block number from id: {}", id)))?;
-
-		let block = block_data_cache.current_block(substrate_hash).await;
+	let address_bloom_filter = &FilteredParams::address_bloom_filter(&filter.address);
+	let topics_bloom_filter = &FilteredParams::topics_bloom_filter(&topics_input);
+
+	// The block data of the range is fetched concurrently, but yielded in order so the
+	// logs are collected as if the blocks were processed sequentially.
+	let numbers = std::iter::successors((from <= to).then_some(from), |number| {
+		(*number < to).then(|| number.saturating_add(One::one()))
+	});
+	let mut blocks = stream::iter(numbers)
+		.map(|number| {
+			fetch_matching_block(
+				client,
+				block_data_cache,
+				number,
+				address_bloom_filter,
+				topics_bloom_filter,
+			)
+		})
+		.buffered(PARALLEL_BLOCK_FETCHES);
 
-		if let Some(block) = block {
-			if FilteredParams::address_in_bloom(block.header.logs_bloom, &address_bloom_filter)
-				&& FilteredParams::topics_in_bloom(block.header.logs_bloom, &topics_bloom_filter)
-			{
-				let statuses = block_data_cache
-					.current_transaction_statuses(substrate_hash)
-					.await;
-				if let Some(statuses) = statuses {
-					filter_block_logs(ret, filter, block, statuses);
-				}
-			}
+	while let Some(block) = blocks.next().await {
+		if let Some((block, statuses)) = block? {
+			filter_block_logs(ret, filter, block, statuses);
 		}
 		// Check for restrictions
 		if ret.len() as u32 > max_past_logs {
@@ -744,15 +832,45 @@ where
 				max_duration.as_secs()
 			)));
 		}
-		if current_number == to {
-			break;
-		} else {
-			current_number = current_number.saturating_add(One::one());
-		}
 	}
 	Ok(())
 }
 
+/// Fetch the block at `number` with its transaction statuses, if its bloom matches the filter.
+async fn fetch_matching_block<B, C>(
+	client: &C,
+	block_data_cache: &EthBlockDataCacheTask<B>,
+	number: NumberFor<B>,
+	address_bloom_filter: &BloomFilter<'_>,
+	topics_bloom_filter: &[BloomFilter<'_>],
+) -> RpcResult<Option<(EthereumBlock, Vec<TransactionStatus>)>>
+where
+	B: BlockT,
+	C: HeaderBackend<B>,
+{
+	let id = BlockId::Number(number);
+	let substrate_hash = client
+		.expect_block_hash_from_id(&id)
+		.map_err(|_| internal_err(format!("Expect block number from id: {}", id)))?;
+
+	let block = match block_data_cache.current_block(substrate_hash).await {
+		Some(block)
+			if FilteredParams::address_in_bloom(block.header.logs_bloom, address_bloom_filter)
+				&& FilteredParams::topics_in_bloom(
+					block.header.logs_bloom,
+					topics_bloom_filter,
+				) =>
+		{
+			block
+		}
+		_ => return Ok(None),
+	};
+	let statuses = block_data_cache
+		.current_transaction_statuses(substrate_hash)
+		.await;
+	Ok(statuses.map(|statuses| (block, statuses)))
+}
+
 fn filter_block_logs<'a>(
 	ret: &'a mut Vec<Log>,
 	filter: &'a Filter,
@@ -35,6 +35,7 @@ impl Geth {
 			Ok(PError::TooLowPriority { .. }) => "replacement transaction underpriced".into(),
 			Ok(PError::InvalidTransaction(inner)) => match inner {
 				InvalidTransaction::Stale => "nonce too low".into(),
+				InvalidTransaction::Future => "nonce too high".into(),
 				InvalidTransaction::Payment => "insufficient funds for gas * price + value".into(),
 				InvalidTransaction::ExhaustsResources => "exceeds block gas limit".into(),
 				InvalidTransaction::Custom(inner) => match inner.into() {
@@ -48,6 +49,9 @@ impl Geth {
 						"max priority fee per gas higher than max fee per gas".into()
 					}
 					VError::InvalidFeeInput => "invalid fee input".into(),
+					VError::CallNotAllowed => "call not allowed".into(),
+					VError::GasPriceBelowMinimum => "transaction underpriced".into(),
+					VError::CreateNotAllowed => "sender not allowed to create contracts".into(),
 					_ => "transaction validation error".into(),
 				},
 				_ => "unknown error".into(),
@@ -34,7 +34,10 @@ use ethereum::{BlockV2 as EthereumBlock, TransactionV2 as EthereumTransaction};
 use ethereum_types::{H160, H256, H64, U256, U64};
 use jsonrpsee::core::{async_trait, RpcResult};
 // Substrate
-use sc_client_api::backend::{Backend, StorageProvider};
+use sc_client_api::{
+	backend::{Backend, StorageProvider},
+	ProofProvider,
+};
 use sc_network_sync::SyncingService;
 use sc_transaction_pool::{ChainApi, Pool};
 use sc_transaction_pool_api::TransactionPool;
@@ -53,7 +56,11 @@ use fp_rpc::{
 };
 
 use crate::{
-	cache::EthBlockDataCacheTask, frontier_backend_client, internal_err, public_key,
+	cache::EthBlockDataCacheTask,
+	execution_pool::{ExecutionPool, ExecutionPriority},
+	frontier_backend_client, internal_err,
+	method_limits::MethodLimits,
+	public_key,
 	signer::EthSigner,
 };
 
@@ -91,6 +98,12 @@ pub struct Eth<B: BlockT, C, P, CT, BE, A: ChainApi, CIDP, EC> {
 	/// Something that can create the inherent data providers for pending state.
 	pending_create_inherent_data_providers: CIDP,
 	pending_consensus_data_provider: Option<Box<dyn pending::ConsensusDataProvider<B>>>,
+	/// Fixed value returned by eth_maxPriorityFeePerGas instead of the fee history based
+	/// suggestion, for deterministic fees in tests.
+	fixed_priority_fee_per_gas: Option<U256>,
+	/// Pool executing eth_call and eth_estimateGas, unbounded when not set.
+	execution_pool: Option<ExecutionPool>,
+	method_limits: MethodLimits,
 	_marker: PhantomData<(BE, EC)>,
 }
 
@@ -138,10 +151,43 @@ where
 			forced_parent_hashes,
 			pending_create_inherent_data_providers,
 			pending_consensus_data_provider,
+			fixed_priority_fee_per_gas: None,
+			execution_pool: None,
+			method_limits: MethodLimits::default(),
 			_marker: PhantomData,
 		}
 	}
 
+	/// Return a fixed priority fee suggestion instead of the fee history based one.
+	pub fn with_fixed_priority_fee_per_gas(mut self, fee: Option<U256>) -> Self {
+		self.fixed_priority_fee_per_gas = fee;
+		self
+	}
+
+	/// Execute eth_call and eth_estimateGas in the given pool.
+	pub fn with_execution_pool(mut self, execution_pool: Option<ExecutionPool>) -> Self {
+		self.execution_pool = execution_pool;
+		self
+	}
+
+	/// Apply the given per-method limits to eth_call, eth_estimateGas and eth_feeHistory.
+	pub fn with_method_limits(mut self, method_limits: MethodLimits) -> Self {
+		self.method_limits = method_limits;
+		self
+	}
+
+	/// Run a call-heavy request in the execution pool, if any.
+	async fn execute_in_pool<T>(
+		&self,
+		priority: ExecutionPriority,
+		request: impl std::future::Future<Output = RpcResult<T>>,
+	) -> RpcResult<T> {
+		match &self.execution_pool {
+			Some(execution_pool) => execution_pool.execute(priority, request).await?,
+			None => request.await,
+		}
+	}
+
 	pub async fn block_info_by_number(
 		&self,
 		number_or_hash: BlockNumberOrHash,
@@ -271,6 +317,9 @@ where
 			forced_parent_hashes,
 			pending_create_inherent_data_providers,
 			pending_consensus_data_provider,
+			fixed_priority_fee_per_gas,
+			execution_pool,
+			method_limits,
 			_marker: _,
 		} = self;
 
@@ -291,6 +340,9 @@ where
 			forced_parent_hashes,
 			pending_create_inherent_data_providers,
 			pending_consensus_data_provider,
+			fixed_priority_fee_per_gas,
+			execution_pool,
+			method_limits,
 			_marker: PhantomData,
 		}
 	}
@@ -302,7 +354,7 @@ where
 	B: BlockT,
 	C: CallApiAt<B> + ProvideRuntimeApi<B>,
 	C::Api: BlockBuilderApi<B> + ConvertTransactionRuntimeApi<B> + EthereumRuntimeRPCApi<B>,
-	C: HeaderBackend<B> + StorageProvider<B, BE> + 'static,
+	C: HeaderBackend<B> + ProofProvider<B> + StorageProvider<B, BE> + 'static,
 	BE: Backend<B> + 'static,
 	P: TransactionPool<Block = B> + 'static,
 	CT: ConvertTransaction<<B as BlockT>::Extrinsic> + Send + Sync + 'static,
@@ -463,6 +515,15 @@ where
 		self.code_at(address, number_or_hash).await
 	}
 
+	async fn proof(
+		&self,
+		address: H160,
+		storage_keys: Vec<H256>,
+		number_or_hash: Option<BlockNumberOrHash>,
+	) -> RpcResult<EthAccount> {
+		self.proof(address, storage_keys, number_or_hash).await
+	}
+
 	// ########################################################################
 	// Execute
 	// ########################################################################
@@ -473,7 +534,11 @@ where
 		number_or_hash: Option<BlockNumberOrHash>,
 		state_overrides: Option<BTreeMap<H160, CallStateOverride>>,
 	) -> RpcResult<Bytes> {
-		self.call(request, number_or_hash, state_overrides).await
+		let request = self.execute_in_pool(
+			ExecutionPriority::High,
+			self.call(request, number_or_hash, state_overrides),
+		);
+		self.method_limits.run("eth_call", request).await
 	}
 
 	async fn estimate_gas(
@@ -481,7 +546,11 @@ where
 		request: TransactionRequest,
 		number_or_hash: Option<BlockNumberOrHash>,
 	) -> RpcResult<U256> {
-		self.estimate_gas(request, number_or_hash).await
+		let request = self.execute_in_pool(
+			ExecutionPriority::Normal,
+			self.estimate_gas(request, number_or_hash),
+		);
+		self.method_limits.run("eth_estimateGas", request).await
 	}
 
 	// ########################################################################
@@ -498,8 +567,8 @@ where
 		newest_block: BlockNumberOrHash,
 		reward_percentiles: Option<Vec<f64>>,
 	) -> RpcResult<FeeHistory> {
-		self.fee_history(block_count, newest_block, reward_percentiles)
-			.await
+		let request = self.fee_history(block_count, newest_block, reward_percentiles);
+		self.method_limits.run("eth_feeHistory", request).await
 	}
 
 	fn max_priority_fee_per_gas(&self) -> RpcResult<U256> {
@@ -644,16 +713,12 @@ fn transaction_build(
 		if block.is_none() && status.is_none() {
 			// If transaction is not mined yet, gas price is considered just max fee per gas.
 		} else {
-			let base_fee = base_fee.unwrap_or_default();
-			let max_priority_fee_per_gas = transaction.max_priority_fee_per_gas.unwrap_or_default();
-			let max_fee_per_gas = transaction.max_fee_per_gas.unwrap_or_default();
 			// If transaction is already mined, gas price is the effective gas price.
-			transaction.gas_price = Some(
-				base_fee
-					.checked_add(max_priority_fee_per_gas)
-					.unwrap_or_else(U256::max_value)
-					.min(max_fee_per_gas),
-			);
+			transaction.gas_price = Some(fp_evm::fee::effective_gas_price(
+				base_fee.unwrap_or_default(),
+				transaction.max_fee_per_gas.unwrap_or_default(),
+				transaction.max_priority_fee_per_gas,
+			));
 		}
 	}
 
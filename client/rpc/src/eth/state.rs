@@ -20,10 +20,13 @@ use ethereum_types::{H160, H256, U256};
 use jsonrpsee::core::RpcResult;
 use scale_codec::Encode;
 // Substrate
-use sc_client_api::backend::{Backend, StorageProvider};
+use sc_client_api::{
+	backend::{Backend, StorageProvider},
+	ProofProvider,
+};
 use sc_transaction_pool::ChainApi;
 use sc_transaction_pool_api::{InPoolTransaction, TransactionPool};
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_block_builder::BlockBuilder as BlockBuilderApi;
 use sp_blockchain::HeaderBackend;
 use sp_inherents::CreateInherentDataProviders;
@@ -205,3 +208,80 @@ where
 		}
 	}
 }
+
+impl<B, C, P, CT, BE, A, CIDP, EC> Eth<B, C, P, CT, BE, A, CIDP, EC>
+where
+	B: BlockT,
+	C: ProvideRuntimeApi<B>,
+	C::Api: EthereumRuntimeRPCApi<B>,
+	C: HeaderBackend<B> + ProofProvider<B> + 'static,
+	BE: Backend<B> + 'static,
+{
+	pub async fn proof(
+		&self,
+		address: H160,
+		storage_keys: Vec<H256>,
+		number_or_hash: Option<BlockNumberOrHash>,
+	) -> RpcResult<EthAccount> {
+		let id = frontier_backend_client::native_block_id::<B, C>(
+			self.client.as_ref(),
+			self.backend.as_ref(),
+			number_or_hash,
+		)
+		.await?
+		.ok_or_else(|| internal_err("Block not found"))?;
+		let substrate_hash = self
+			.client
+			.expect_block_hash_from_id(&id)
+			.map_err(|_| internal_err(format!("Expect block number from id: {id}")))?;
+
+		let api = self.client.runtime_api();
+		if !api
+			.has_api_with::<dyn EthereumRuntimeRPCApi<B>, _>(substrate_hash, |version| version >= 7)
+			.unwrap_or(false)
+		{
+			return Err(internal_err(
+				"Account proofs are not supported at this block",
+			));
+		}
+		let account = api
+			.account_proof(substrate_hash, address, storage_keys)
+			.map_err(|err| internal_err(format!("Fetch account proof failed: {err}")))?
+			.ok_or_else(|| internal_err("Account storage too large to compute its storage root"))?;
+
+		// The proof nodes of the runtime storage, the runtime can't read them.
+		let read_proof = |keys: &[&[u8]]| {
+			self.client
+				.read_proof(substrate_hash, &mut keys.iter().copied())
+				.map(|proof| proof.into_iter_nodes().map(Bytes).collect::<Vec<_>>())
+				.map_err(|err| internal_err(format!("Read storage proof failed: {err}")))
+		};
+
+		let account_keys = account
+			.proof_keys
+			.iter()
+			.map(|key| &key[..])
+			.collect::<Vec<_>>();
+		let storage_proof = account
+			.storage
+			.iter()
+			.map(|slot| {
+				Ok(StorageProof {
+					key: U256::from_big_endian(slot.key.as_bytes()),
+					value: U256::from_big_endian(slot.value.as_bytes()),
+					proof: read_proof(&[&slot.proof_key[..]])?,
+				})
+			})
+			.collect::<RpcResult<Vec<_>>>()?;
+
+		Ok(EthAccount {
+			address,
+			balance: account.balance,
+			nonce: account.nonce,
+			code_hash: account.code_hash,
+			storage_hash: account.storage_root,
+			account_proof: read_proof(&account_keys)?,
+			storage_proof,
+		})
+	}
+}
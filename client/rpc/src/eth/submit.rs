@@ -96,6 +96,10 @@ where
 			}
 		};
 
+		if request.is_blob_transaction() {
+			return Err(internal_err("blob transactions are not supported"));
+		}
+
 		let max_fee_per_gas = request.max_fee_per_gas;
 		let message: Option<TransactionMessage> = request.into();
 		let message = match message {
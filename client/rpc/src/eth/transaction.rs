@@ -284,33 +284,61 @@ where
 				let mut cumulative_receipts = receipts;
 				cumulative_receipts.truncate((status.transaction_index + 1) as usize);
 				let transaction = block.transactions[index].clone();
-				let effective_gas_price = match transaction {
-					EthereumTransaction::Legacy(t) => t.gas_price,
-					EthereumTransaction::EIP2930(t) => t.gas_price,
-					EthereumTransaction::EIP1559(t) => {
-						let parent_eth_hash = block.header.parent_hash;
-						let base_fee_block_substrate_hash = if parent_eth_hash.is_zero() {
-							substrate_hash
-						} else {
-							frontier_backend_client::load_hash::<B, C>(
-								self.client.as_ref(),
-								self.backend.as_ref(),
-								parent_eth_hash,
-							)
+				// The receipts of the older storage schemas don't tell the transaction type.
+				let transaction_type = match transaction {
+					EthereumTransaction::Legacy(_) => U256::from(0),
+					EthereumTransaction::EIP2930(_) => U256::from(1),
+					EthereumTransaction::EIP1559(_) => U256::from(2),
+				};
+				// Blocks of the recent storage schemas keep the price each transaction paid,
+				// the older ones need it derived from the transaction and the base fee.
+				let stored_effective_gas_price = self
+					.storage_override
+					.current_effective_gas_prices(substrate_hash)
+					.and_then(|mut effective_gas_prices| {
+						(index < effective_gas_prices.len())
+							.then(|| effective_gas_prices.swap_remove(index))
+					});
+				let effective_gas_price = match (stored_effective_gas_price, transaction) {
+					(Some(effective_gas_price), _) => effective_gas_price,
+					(None, EthereumTransaction::Legacy(t)) => t.gas_price,
+					(None, EthereumTransaction::EIP2930(t)) => t.gas_price,
+					(None, EthereumTransaction::EIP1559(t)) => {
+						// Prefer the indexed receipt data, avoiding the runtime call on the
+						// parent block state.
+						let indexed = self
+							.backend
+							.transaction_receipt_metadata(&status.transaction_hash, &substrate_hash)
 							.await
-							.map_err(|err| internal_err(format!("{:?}", err)))?
-							.ok_or(internal_err(
-								"Failed to retrieve substrate parent block hash",
-							))?
-						};
+							.map_err(|err| internal_err(format!("{:?}", err)))?;
+						if let Some(indexed) = indexed {
+							indexed.effective_gas_price
+						} else {
+							let parent_eth_hash = block.header.parent_hash;
+							let base_fee_block_substrate_hash = if parent_eth_hash.is_zero() {
+								substrate_hash
+							} else {
+								frontier_backend_client::load_hash::<B, C>(
+									self.client.as_ref(),
+									self.backend.as_ref(),
+									parent_eth_hash,
+								)
+								.await
+								.map_err(|err| internal_err(format!("{:?}", err)))?
+								.ok_or(internal_err(
+									"Failed to retrieve substrate parent block hash",
+								))?
+							};
 
-						self.client
-							.runtime_api()
-							.gas_price(base_fee_block_substrate_hash)
-							.unwrap_or_default()
-							.checked_add(t.max_priority_fee_per_gas)
-							.unwrap_or_else(U256::max_value)
-							.min(t.max_fee_per_gas)
+							fp_evm::fee::effective_gas_price(
+								self.client
+									.runtime_api()
+									.gas_price(base_fee_block_substrate_hash)
+									.unwrap_or_default(),
+								t.max_fee_per_gas,
+								Some(t.max_priority_fee_per_gas),
+							)
+						}
 					}
 				};
 
@@ -361,11 +389,18 @@ where
 					logs_bloom,
 					state_root: None,
 					effective_gas_price,
-					transaction_type: match receipt {
-						ethereum::ReceiptV3::Legacy(_) => U256::from(0),
-						ethereum::ReceiptV3::EIP2930(_) => U256::from(1),
-						ethereum::ReceiptV3::EIP1559(_) => U256::from(2),
-					},
+					transaction_type,
+					revert_reason: self
+						.storage_override
+						.current_revert_reasons(substrate_hash)
+						.and_then(|mut revert_reasons| {
+							(index < revert_reasons.len())
+								.then(|| revert_reasons.swap_remove(index))
+						})
+						.filter(|revert_reason| !revert_reason.is_empty())
+						.map(Bytes),
+					blob_gas_used: None,
+					blob_gas_price: None,
 				}));
 			}
 			_ => Ok(None),
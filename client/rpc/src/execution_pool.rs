@@ -0,0 +1,312 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! A bounded, prioritized pool executing the call-heavy rpc requests.
+
+use std::{cmp::Ordering, collections::BinaryHeap, future::Future, sync::Arc, time::Duration};
+
+use parking_lot::Mutex;
+use tokio::{
+	runtime::{Handle, RuntimeFlavor},
+	sync::oneshot,
+	time::Instant,
+};
+
+use crate::{err, internal_err};
+
+/// Error code of a request rejected because the execution queue is full (EIP-1474).
+const LIMIT_EXCEEDED_ERROR_CODE: i32 = -32005;
+
+/// Priority of a request executed by the [`ExecutionPool`], higher priorities being dequeued
+/// first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ExecutionPriority {
+	/// Tracing requests, replaying whole blocks or transactions.
+	Low,
+	/// Gas estimations, executing the request several times.
+	Normal,
+	/// Single calls.
+	High,
+}
+
+#[derive(Clone, Debug)]
+pub struct ExecutionPoolConfig {
+	/// Maximum number of requests executed at the same time.
+	pub max_concurrent: usize,
+	/// Maximum number of requests waiting for an execution slot, further requests are rejected.
+	pub max_queued: usize,
+	/// Deadline of a request, from its submission to the end of its execution.
+	pub timeout: Duration,
+}
+
+/// Error of a request submitted to the [`ExecutionPool`].
+#[derive(Debug, Eq, PartialEq)]
+pub enum ExecutionPoolError {
+	/// The queue is full.
+	QueueFull { max_queued: usize },
+	/// The deadline of the request expired.
+	Timeout(Duration),
+}
+
+impl From<ExecutionPoolError> for jsonrpsee::types::error::ErrorObjectOwned {
+	fn from(error: ExecutionPoolError) -> Self {
+		match error {
+			ExecutionPoolError::QueueFull { max_queued } => err(
+				LIMIT_EXCEEDED_ERROR_CODE,
+				format!("execution queue is full ({max_queued} requests)"),
+				None,
+			),
+			ExecutionPoolError::Timeout(timeout) => internal_err(format!(
+				"execution timeout of {} seconds exceeded",
+				timeout.as_secs()
+			)),
+		}
+	}
+}
+
+/// A bounded, prioritized pool executing the call-heavy rpc requests.
+///
+/// At most `max_concurrent` requests are executed at the same time, each on a worker moved out
+/// of the async runtime so the blocking runtime calls don't starve block import and the
+/// subscriptions. The other requests wait by priority, then submission order, until their
+/// deadline.
+#[derive(Clone)]
+pub struct ExecutionPool(Arc<Inner>);
+
+struct Inner {
+	config: ExecutionPoolConfig,
+	state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+	running: usize,
+	queue: BinaryHeap<Waiter>,
+	next_sequence: u64,
+}
+
+struct Waiter {
+	priority: ExecutionPriority,
+	sequence: u64,
+	slot_tx: oneshot::Sender<Slot>,
+}
+
+impl PartialEq for Waiter {
+	fn eq(&self, other: &Self) -> bool {
+		self.cmp(other) == Ordering::Equal
+	}
+}
+
+impl Eq for Waiter {}
+
+impl PartialOrd for Waiter {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl Ord for Waiter {
+	fn cmp(&self, other: &Self) -> Ordering {
+		// The earliest submission is the greatest among equal priorities.
+		self.priority
+			.cmp(&other.priority)
+			.then_with(|| other.sequence.cmp(&self.sequence))
+	}
+}
+
+/// An execution slot, handed over to the next waiter when dropped.
+struct Slot(Option<Arc<Inner>>);
+
+impl Drop for Slot {
+	fn drop(&mut self) {
+		let Some(inner) = self.0.take() else {
+			return;
+		};
+		let mut state = inner.state.lock();
+		while let Some(waiter) = state.queue.pop() {
+			// The waiter may have given up, in which case the slot comes back.
+			match waiter.slot_tx.send(Slot(Some(inner.clone()))) {
+				Ok(()) => return,
+				Err(mut slot) => slot.0 = None,
+			}
+		}
+		state.running -= 1;
+	}
+}
+
+impl ExecutionPool {
+	pub fn new(config: ExecutionPoolConfig) -> Self {
+		Self(Arc::new(Inner {
+			config,
+			state: Mutex::new(State::default()),
+		}))
+	}
+
+	/// Execute `request` once a slot is available, within the configured deadline.
+	pub async fn execute<F: Future>(
+		&self,
+		priority: ExecutionPriority,
+		request: F,
+	) -> Result<F::Output, ExecutionPoolError> {
+		let timeout = self.0.config.timeout;
+		let deadline = Instant::now() + timeout;
+
+		let slot_rx = {
+			let mut state = self.0.state.lock();
+			if state.running < self.0.config.max_concurrent {
+				state.running += 1;
+				None
+			} else if state.queue.len() >= self.0.config.max_queued {
+				return Err(ExecutionPoolError::QueueFull {
+					max_queued: self.0.config.max_queued,
+				});
+			} else {
+				let (slot_tx, slot_rx) = oneshot::channel();
+				let sequence = state.next_sequence;
+				state.next_sequence += 1;
+				state.queue.push(Waiter {
+					priority,
+					sequence,
+					slot_tx,
+				});
+				Some(slot_rx)
+			}
+		};
+		let _slot = match slot_rx {
+			None => Slot(Some(self.0.clone())),
+			Some(slot_rx) => match tokio::time::timeout_at(deadline, slot_rx).await {
+				Ok(Ok(slot)) => slot,
+				// The slots are handed over while the pool is alive, so this is the timeout.
+				Ok(Err(_)) | Err(_) => {
+					// Forget the abandoned waiters, so they don't count towards the queue limit.
+					self.0
+						.state
+						.lock()
+						.queue
+						.retain(|waiter| !waiter.slot_tx.is_closed());
+					return Err(ExecutionPoolError::Timeout(timeout));
+				}
+			},
+		};
+
+		let request = tokio::time::timeout_at(deadline, request);
+		let result = match Handle::current().runtime_flavor() {
+			RuntimeFlavor::CurrentThread => request.await,
+			_ => tokio::task::block_in_place(|| Handle::current().block_on(request)),
+		};
+		result.map_err(|_| ExecutionPoolError::Timeout(timeout))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn runtime() -> tokio::runtime::Runtime {
+		tokio::runtime::Builder::new_current_thread()
+			.enable_time()
+			.build()
+			.expect("runtime must build")
+	}
+
+	fn pool(max_queued: usize) -> ExecutionPool {
+		ExecutionPool::new(ExecutionPoolConfig {
+			max_concurrent: 1,
+			max_queued,
+			timeout: Duration::from_secs(10),
+		})
+	}
+
+	#[test]
+	fn queued_requests_run_by_priority() {
+		runtime().block_on(async {
+			let pool = pool(4);
+			let order = Arc::new(Mutex::new(Vec::new()));
+			let (release_tx, release_rx) = oneshot::channel::<()>();
+
+			let blocking = pool.execute(ExecutionPriority::High, async {
+				let _ = release_rx.await;
+			});
+			let queued = [
+				ExecutionPriority::Low,
+				ExecutionPriority::High,
+				ExecutionPriority::Normal,
+				ExecutionPriority::High,
+			]
+			.into_iter()
+			.enumerate()
+			.map(|(index, priority)| {
+				let order = order.clone();
+				pool.execute(priority, async move { order.lock().push(index) })
+			});
+			let release = async {
+				tokio::task::yield_now().await;
+				let _ = release_tx.send(());
+			};
+
+			let (blocking, queued, ()) =
+				futures::join!(blocking, futures::future::join_all(queued), release);
+			assert_eq!(blocking, Ok(()));
+			assert!(queued.iter().all(Result::is_ok));
+			assert_eq!(*order.lock(), vec![1, 3, 2, 0]);
+		});
+	}
+
+	#[test]
+	fn full_queue_rejects_requests() {
+		runtime().block_on(async {
+			let pool = pool(0);
+			let (release_tx, release_rx) = oneshot::channel::<()>();
+
+			let blocking = pool.execute(ExecutionPriority::Low, async {
+				let _ = release_rx.await;
+			});
+			let rejected = async {
+				tokio::task::yield_now().await;
+				let result = pool.execute(ExecutionPriority::High, async {}).await;
+				let _ = release_tx.send(());
+				result
+			};
+
+			let (blocking, rejected) = futures::join!(blocking, rejected);
+			assert_eq!(blocking, Ok(()));
+			assert_eq!(
+				rejected,
+				Err(ExecutionPoolError::QueueFull { max_queued: 0 })
+			);
+		});
+	}
+
+	#[test]
+	fn expired_requests_time_out() {
+		runtime().block_on(async {
+			let pool = ExecutionPool::new(ExecutionPoolConfig {
+				max_concurrent: 1,
+				max_queued: 1,
+				timeout: Duration::from_millis(10),
+			});
+			let result = pool
+				.execute(ExecutionPriority::High, futures::future::pending::<()>())
+				.await;
+			assert_eq!(
+				result,
+				Err(ExecutionPoolError::Timeout(Duration::from_millis(10)))
+			);
+		});
+	}
+}
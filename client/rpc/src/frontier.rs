@@ -0,0 +1,295 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{collections::BTreeMap, sync::Arc};
+
+use ethereum_types::{H160, H256, U256};
+use jsonrpsee::core::{async_trait, RpcResult};
+// Substrate
+use sp_runtime::traits::Block as BlockT;
+// Frontier
+use fc_api::EventSignature;
+use fc_rpc_core::{
+	types::{
+		AddressTransaction, ContractCreation, DecodedEvent, DecodedLog, DecodedParam, Filter, Log,
+	},
+	EthFilterApiServer, FrontierApiServer,
+};
+
+use crate::internal_err;
+
+/// Default number of transactions returned by `frontier_getTransactionsByAddress`.
+const DEFAULT_ADDRESS_TRANSACTIONS_LIMIT: u32 = 100;
+/// Maximum number of transactions returned by `frontier_getTransactionsByAddress`.
+const MAX_ADDRESS_TRANSACTIONS_LIMIT: u32 = 1000;
+
+/// Frontier API implementation.
+pub struct Frontier<B: BlockT, F> {
+	eth_filter: F,
+	backend: Arc<dyn fc_api::Backend<B>>,
+}
+
+impl<B: BlockT, F> Frontier<B, F> {
+	pub fn new(eth_filter: F, backend: Arc<dyn fc_api::Backend<B>>) -> Self {
+		Self {
+			eth_filter,
+			backend,
+		}
+	}
+}
+
+#[async_trait]
+impl<B, F> FrontierApiServer for Frontier<B, F>
+where
+	B: BlockT,
+	F: EthFilterApiServer + Send + Sync + 'static,
+{
+	async fn decoded_logs(&self, filter: Filter) -> RpcResult<Vec<DecodedLog>> {
+		let logs = self.eth_filter.logs(filter).await?;
+
+		let mut topics = logs
+			.iter()
+			.filter_map(|log| log.topics.first().copied())
+			.collect::<Vec<_>>();
+		topics.sort();
+		topics.dedup();
+		let signatures = self
+			.backend
+			.log_indexer()
+			.event_signatures(&topics)
+			.await
+			.map_err(internal_err)?
+			.into_iter()
+			.filter_map(|(topic, fragment)| {
+				EventSignature::parse(&fragment)
+					.ok()
+					.map(|signature| (topic, (fragment, signature)))
+			})
+			.collect::<BTreeMap<_, _>>();
+
+		Ok(logs
+			.into_iter()
+			.map(|log| {
+				let event = log
+					.topics
+					.first()
+					.and_then(|topic| signatures.get(topic))
+					.and_then(|(fragment, signature)| decode_event(&log, fragment, signature));
+				DecodedLog { log, event }
+			})
+			.collect())
+	}
+
+	async fn transactions_by_address(
+		&self,
+		address: H160,
+		offset: Option<u32>,
+		limit: Option<u32>,
+	) -> RpcResult<Vec<AddressTransaction>> {
+		let limit = limit.unwrap_or(DEFAULT_ADDRESS_TRANSACTIONS_LIMIT);
+		if limit > MAX_ADDRESS_TRANSACTIONS_LIMIT {
+			return Err(internal_err(format!(
+				"limit exceeds the maximum of {MAX_ADDRESS_TRANSACTIONS_LIMIT}"
+			)));
+		}
+
+		Ok(self
+			.backend
+			.transactions_by_address(&address, offset.unwrap_or_default(), limit)
+			.await
+			.map_err(internal_err)?
+			.into_iter()
+			.map(|transaction| AddressTransaction {
+				hash: transaction.ethereum_transaction_hash,
+				block_hash: transaction.ethereum_block_hash,
+				block_number: U256::from(transaction.block_number),
+				transaction_index: U256::from(transaction.ethereum_index),
+				from: transaction.from,
+				to: transaction.to,
+				value: transaction.value,
+			})
+			.collect())
+	}
+
+	async fn contract_creation(&self, address: H160) -> RpcResult<Option<ContractCreation>> {
+		Ok(self
+			.backend
+			.contract_creation(&address)
+			.await
+			.map_err(internal_err)?
+			.map(|creation| ContractCreation {
+				contract_address: address,
+				transaction_hash: creation.ethereum_transaction_hash,
+				block_hash: creation.ethereum_block_hash,
+				block_number: U256::from(creation.block_number),
+				transaction_index: U256::from(creation.ethereum_index),
+				creator: creation.creator,
+			}))
+	}
+}
+
+/// Decodes a log with the given event signature, returning `None` if the log does not match
+/// the signature or uses unsupported types.
+fn decode_event(log: &Log, fragment: &str, signature: &EventSignature) -> Option<DecodedEvent> {
+	let indexed_count = signature
+		.params
+		.iter()
+		.filter(|param| param.indexed)
+		.count();
+	if log.topics.len() != indexed_count + 1 {
+		return None;
+	}
+
+	let mut topics = log.topics.iter().skip(1);
+	let mut head_index = 0;
+	let mut params = Vec::with_capacity(signature.params.len());
+	for param in signature.params.iter() {
+		let value = if param.indexed {
+			let topic = topics.next()?;
+			if is_dynamic(&param.kind) {
+				// Only the hash of indexed dynamic values is stored.
+				format!("{topic:?}")
+			} else {
+				decode_word(&param.kind, topic.as_fixed_bytes())?
+			}
+		} else {
+			let word = data_word(&log.data.0, head_index)?;
+			head_index += 1;
+			match param.kind.as_str() {
+				"bytes" | "string" => {
+					let offset = usize::try_from(U256::from_big_endian(word)).ok()?;
+					let length_word = log.data.0.get(offset..offset.checked_add(32)?)?;
+					let length = usize::try_from(U256::from_big_endian(length_word)).ok()?;
+					let start = offset + 32;
+					let bytes = log.data.0.get(start..start.checked_add(length)?)?;
+					if param.kind == "string" {
+						String::from_utf8_lossy(bytes).into_owned()
+					} else {
+						format!("0x{}", hex::encode(bytes))
+					}
+				}
+				kind => decode_word(kind, word)?,
+			}
+		};
+		params.push(DecodedParam {
+			name: param.name.clone(),
+			kind: param.kind.clone(),
+			indexed: param.indexed,
+			value,
+		});
+	}
+
+	Some(DecodedEvent {
+		signature: fragment.to_string(),
+		name: signature.name.clone(),
+		params,
+	})
+}
+
+fn is_dynamic(kind: &str) -> bool {
+	kind == "bytes" || kind == "string" || kind.contains('[') || kind.contains('(')
+}
+
+fn data_word(data: &[u8], index: usize) -> Option<&[u8; 32]> {
+	let start = index.checked_mul(32)?;
+	data.get(start..start.checked_add(32)?)?.try_into().ok()
+}
+
+/// Decodes a static elementary value from a single ABI word.
+fn decode_word(kind: &str, word: &[u8; 32]) -> Option<String> {
+	match kind {
+		"address" => Some(format!("{:?}", H160::from_slice(&word[12..]))),
+		"bool" => Some((word.iter().any(|byte| *byte != 0)).to_string()),
+		kind if kind.starts_with("uint") => Some(U256::from_big_endian(word).to_string()),
+		kind if kind.starts_with("int") => {
+			let value = U256::from_big_endian(word);
+			if word[0] & 0x80 != 0 {
+				Some(format!("-{}", (!value).overflowing_add(U256::one()).0))
+			} else {
+				Some(value.to_string())
+			}
+		}
+		kind if kind.starts_with("bytes") => {
+			let size = kind["bytes".len()..].parse::<usize>().ok()?;
+			if size == 0 || size > 32 {
+				return None;
+			}
+			Some(format!("0x{}", hex::encode(&word[..size])))
+		}
+		_ => None,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use fc_rpc_core::types::Bytes;
+
+	fn log(topics: Vec<H256>, data: Vec<u8>) -> Log {
+		Log {
+			address: H160::zero(),
+			topics,
+			data: Bytes::new(data),
+			block_hash: None,
+			block_number: None,
+			transaction_hash: None,
+			transaction_index: None,
+			log_index: None,
+			transaction_log_index: None,
+			removed: false,
+		}
+	}
+
+	#[test]
+	fn decode_event_works() {
+		let fragment = "Message(address indexed from, int256 delta, string text)";
+		let signature = EventSignature::parse(fragment).expect("valid fragment");
+
+		let mut data = Vec::new();
+		data.extend_from_slice(&[0xff; 32]);
+		data.extend_from_slice(H256::from_low_u64_be(64).as_bytes());
+		data.extend_from_slice(H256::from_low_u64_be(2).as_bytes());
+		let mut text = [0u8; 32];
+		text[..2].copy_from_slice(b"hi");
+		data.extend_from_slice(&text);
+
+		let from = H160::repeat_byte(0x01);
+		let event = decode_event(
+			&log(vec![signature.topic(), H256::from(from)], data),
+			fragment,
+			&signature,
+		)
+		.expect("log matches the signature");
+
+		assert_eq!(event.name, "Message");
+		let values = event
+			.params
+			.iter()
+			.map(|param| param.value.as_str())
+			.collect::<Vec<_>>();
+		assert_eq!(values, vec![&format!("{from:?}")[..], "-1", "hi"]);
+	}
+
+	#[test]
+	fn decode_event_rejects_mismatching_topics() {
+		let fragment = "Transfer(address indexed from, address indexed to, uint256 value)";
+		let signature = EventSignature::parse(fragment).expect("valid fragment");
+		let data = H256::from_low_u64_be(1).as_bytes().to_vec();
+		assert!(decode_event(&log(vec![signature.topic()], data), fragment, &signature).is_none());
+	}
+}
@@ -0,0 +1,136 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use ethereum_types::H256;
+use jsonrpsee::core::{async_trait, RpcResult};
+// Substrate
+use sc_client_api::backend::{Backend, StorageProvider};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+// Frontier
+use fc_mapping_sync::sql::RetentionPolicy;
+use fc_rpc_core::{
+	types::{IndexerIntegrityReport, IndexerStatementCacheStats},
+	IndexerAdminApiServer,
+};
+use fp_rpc::EthereumRuntimeRPCApi;
+
+use crate::internal_err;
+
+/// Number of blocks pruned in a single db transaction.
+const PRUNE_BATCH_SIZE: u32 = 1000;
+
+/// Administrative operations of the SQL indexer.
+pub struct IndexerAdmin<B: BlockT, C, BE> {
+	client: Arc<C>,
+	indexer_backend: Arc<fc_db::sql::Backend<B>>,
+	retention_policy: Option<RetentionPolicy>,
+	_marker: PhantomData<BE>,
+}
+
+impl<B: BlockT, C, BE> IndexerAdmin<B, C, BE> {
+	pub fn new(
+		client: Arc<C>,
+		indexer_backend: Arc<fc_db::sql::Backend<B>>,
+		retention_policy: Option<RetentionPolicy>,
+	) -> Self {
+		Self {
+			client,
+			indexer_backend,
+			retention_policy,
+			_marker: PhantomData,
+		}
+	}
+}
+
+#[async_trait]
+impl<B, C, BE> IndexerAdminApiServer for IndexerAdmin<B, C, BE>
+where
+	B: BlockT<Hash = H256>,
+	C: ProvideRuntimeApi<B>,
+	C::Api: EthereumRuntimeRPCApi<B>,
+	C: HeaderBackend<B> + StorageProvider<B, BE> + Send + Sync + 'static,
+	BE: Backend<B> + 'static,
+{
+	async fn backfill(&self, workers: u32) -> RpcResult<()> {
+		if workers == 0 {
+			return Err(internal_err("at least one backfill worker is required"));
+		}
+		fc_mapping_sync::sql::spawn_backfill::<B, C, BE>(
+			self.client.clone(),
+			self.indexer_backend.clone(),
+			workers,
+			self.retention_policy,
+		)
+		.await;
+		Ok(())
+	}
+
+	async fn check_integrity(
+		&self,
+		from_block: u32,
+		to_block: u32,
+		sample: Option<u32>,
+		repair: Option<bool>,
+	) -> RpcResult<IndexerIntegrityReport> {
+		let report = self
+			.indexer_backend
+			.check_integrity(
+				self.client.clone(),
+				from_block,
+				to_block,
+				sample,
+				repair.unwrap_or(false),
+			)
+			.await
+			.map_err(|err| internal_err(format!("integrity check failed: {err:?}")))?;
+		Ok(IndexerIntegrityReport {
+			checked: report.checked,
+			missing: report.missing,
+			mismatched: report.mismatched,
+			repaired: report.repaired,
+		})
+	}
+
+	async fn prune_range(&self, from_block: u32, to_block: u32) -> RpcResult<u32> {
+		let mut pruned = 0;
+		loop {
+			let batch = self
+				.indexer_backend
+				.prune_block_range(from_block, to_block, PRUNE_BATCH_SIZE)
+				.await
+				.map_err(|err| internal_err(format!("prune failed: {err:?}")))?;
+			pruned += batch as u32;
+			if batch < PRUNE_BATCH_SIZE as usize {
+				return Ok(pruned);
+			}
+		}
+	}
+
+	fn statement_cache_stats(&self) -> RpcResult<IndexerStatementCacheStats> {
+		let stats = self.indexer_backend.statement_cache_stats();
+		Ok(IndexerStatementCacheStats {
+			hits: stats.hits,
+			misses: stats.misses,
+			hit_rate: stats.hit_rate(),
+		})
+	}
+}
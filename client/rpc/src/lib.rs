@@ -26,16 +26,31 @@
 )]
 #![warn(unused_crate_dependencies)]
 
+#[cfg(feature = "bundler")]
+mod bundler;
 mod cache;
 mod debug;
 mod eth;
 mod eth_pubsub;
+mod execution_pool;
+mod frontier;
+#[cfg(feature = "indexer-admin")]
+mod indexer_admin;
+mod method_limits;
 mod net;
+#[cfg(feature = "rosetta")]
+mod rosetta;
 mod signer;
 #[cfg(feature = "txpool")]
 mod txpool;
 mod web3;
 
+#[cfg(feature = "bundler")]
+pub use self::bundler::{user_operation_hash, Bundler};
+#[cfg(feature = "indexer-admin")]
+pub use self::indexer_admin::IndexerAdmin;
+#[cfg(feature = "rosetta")]
+pub use self::rosetta::Rosetta;
 #[cfg(feature = "txpool")]
 pub use self::txpool::TxPool;
 pub use self::{
@@ -43,16 +58,23 @@ pub use self::{
 	debug::Debug,
 	eth::{format, pending, EstimateGasAdapter, Eth, EthConfig, EthFilter},
 	eth_pubsub::{EthPubSub, EthereumSubIdProvider},
+	execution_pool::{ExecutionPool, ExecutionPoolConfig, ExecutionPoolError, ExecutionPriority},
+	frontier::Frontier,
+	method_limits::{MethodLimit, MethodLimits},
 	net::Net,
 	signer::{EthDevSigner, EthSigner},
 	web3::Web3,
 };
 pub use ethereum::TransactionV2 as EthereumTransaction;
+#[cfg(feature = "bundler")]
+pub use fc_rpc_core::BundlerApiServer;
+#[cfg(feature = "indexer-admin")]
+pub use fc_rpc_core::IndexerAdminApiServer;
 #[cfg(feature = "txpool")]
 pub use fc_rpc_core::TxPoolApiServer;
 pub use fc_rpc_core::{
-	DebugApiServer, EthApiServer, EthFilterApiServer, EthPubSubApiServer, NetApiServer,
-	Web3ApiServer,
+	DebugApiServer, EthApiServer, EthFilterApiServer, EthPubSubApiServer, FrontierApiServer,
+	NetApiServer, Web3ApiServer,
 };
 pub use fc_storage::{overrides::*, StorageOverrideHandler};
 
@@ -0,0 +1,165 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Per-method overload protection of the rpc requests.
+
+use std::{collections::HashMap, future::Future, sync::Arc, time::Duration};
+
+use jsonrpsee::core::RpcResult;
+use tokio::sync::Semaphore;
+
+use crate::err;
+
+/// Error code of a request rejected because too many requests of its method are executing.
+const SERVER_BUSY_ERROR_CODE: i32 = -32005;
+
+/// Error code of a request which exceeded its deadline.
+const TIMEOUT_ERROR_CODE: i32 = -32002;
+
+/// Limits of the requests of an rpc method.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MethodLimit {
+	/// Maximum number of requests executing at the same time, further ones being rejected.
+	pub max_concurrent: Option<usize>,
+	/// Deadline of a request, checked whenever the request yields.
+	pub timeout: Option<Duration>,
+}
+
+struct MethodLimiter {
+	permits: Option<Arc<Semaphore>>,
+	timeout: Option<Duration>,
+}
+
+/// The limits of the rpc methods, the methods without limits being unrestricted.
+#[derive(Clone, Default)]
+pub struct MethodLimits(Arc<HashMap<String, MethodLimiter>>);
+
+impl MethodLimits {
+	pub fn new(limits: impl IntoIterator<Item = (String, MethodLimit)>) -> Self {
+		Self(Arc::new(
+			limits
+				.into_iter()
+				.map(|(method, limit)| {
+					let limiter = MethodLimiter {
+						permits: limit
+							.max_concurrent
+							.map(|permits| Arc::new(Semaphore::new(permits))),
+						timeout: limit.timeout,
+					};
+					(method, limiter)
+				})
+				.collect(),
+		))
+	}
+
+	/// Execute a request of `method` within its limits.
+	pub async fn run<T>(
+		&self,
+		method: &str,
+		request: impl Future<Output = RpcResult<T>>,
+	) -> RpcResult<T> {
+		let Some(limiter) = self.0.get(method) else {
+			return request.await;
+		};
+		let _permit = match &limiter.permits {
+			Some(permits) => Some(permits.try_acquire().map_err(|_| {
+				err(
+					SERVER_BUSY_ERROR_CODE,
+					format!("server busy: too many concurrent {method} requests"),
+					None,
+				)
+			})?),
+			None => None,
+		};
+		match limiter.timeout {
+			Some(timeout) => tokio::time::timeout(timeout, request).await.map_err(|_| {
+				err(
+					TIMEOUT_ERROR_CODE,
+					format!(
+						"request timed out: {method} exceeded {} ms",
+						timeout.as_millis()
+					),
+					None,
+				)
+			})?,
+			None => request.await,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn runtime() -> tokio::runtime::Runtime {
+		tokio::runtime::Builder::new_current_thread()
+			.enable_time()
+			.build()
+			.expect("runtime must build")
+	}
+
+	#[test]
+	fn concurrent_requests_over_the_limit_are_rejected() {
+		runtime().block_on(async {
+			let limits = MethodLimits::new([(
+				"eth_getLogs".to_string(),
+				MethodLimit {
+					max_concurrent: Some(1),
+					timeout: None,
+				},
+			)]);
+			let (release_tx, release_rx) = tokio::sync::oneshot::channel::<()>();
+
+			let running = limits.run("eth_getLogs", async {
+				let _ = release_rx.await;
+				Ok(())
+			});
+			let rejected = async {
+				tokio::task::yield_now().await;
+				let rejected = limits.run("eth_getLogs", async { Ok(()) }).await;
+				let other = limits.run("eth_call", async { Ok(()) }).await;
+				let _ = release_tx.send(());
+				(rejected, other)
+			};
+
+			let (running, (rejected, other)) = futures::join!(running, rejected);
+			assert!(running.is_ok());
+			assert_eq!(rejected.map_err(|e| e.code()), Err(SERVER_BUSY_ERROR_CODE));
+			assert!(other.is_ok());
+			// The permit is released once the request completes.
+			assert!(limits.run("eth_getLogs", async { Ok(()) }).await.is_ok());
+		});
+	}
+
+	#[test]
+	fn slow_requests_time_out() {
+		runtime().block_on(async {
+			let limits = MethodLimits::new([(
+				"eth_getLogs".to_string(),
+				MethodLimit {
+					max_concurrent: None,
+					timeout: Some(Duration::from_millis(10)),
+				},
+			)]);
+			let result = limits
+				.run("eth_getLogs", futures::future::pending::<RpcResult<()>>())
+				.await;
+			assert_eq!(result.map_err(|e| e.code()), Err(TIMEOUT_ERROR_CODE));
+		});
+	}
+}
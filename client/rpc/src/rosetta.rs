@@ -0,0 +1,688 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{collections::BTreeMap, convert::Infallible, net::SocketAddr, sync::Arc};
+
+use ethereum_types::{H160, H256, U256};
+use hyper::{
+	body::HttpBody,
+	service::{make_service_fn, service_fn},
+	Body, Method, Request, Response, Server, StatusCode,
+};
+use serde::{de::DeserializeOwned, Serialize};
+// Substrate
+use sc_transaction_pool_api::{TransactionPool, TransactionSource};
+use sp_api::{ApiExt, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::{Block as BlockT, UniqueSaturatedInto};
+// Frontier
+use fc_rpc_core::types::{
+	AccountBalance, AccountBalanceRequest, AccountIdentifier, Allow, Amount, BlockIdentifier,
+	BlockRequest, BlockResponse, BlockTransactionRequest, BlockTransactionResponse,
+	ConstructionRequest, Currency, NetworkIdentifier, NetworkListResponse, NetworkOptions,
+	NetworkRequest, NetworkStatus, Operation, OperationIdentifier, OperationStatus,
+	PartialBlockIdentifier, RosettaBlock, RosettaError, RosettaTransaction, TransactionIdentifier,
+	TransactionIdentifierResponse, Version,
+};
+use fc_storage::StorageOverride;
+use fp_rpc::{BalanceChange, ConvertTransactionRuntimeApi, EthereumRuntimeRPCApi};
+
+use crate::frontier_backend_client;
+
+/// Version of the Rosetta specification implemented.
+const ROSETTA_VERSION: &str = "1.4.13";
+/// Operation type of a fee withdrawn from or deposited into an account.
+const FEE_OPERATION: &str = "FEE";
+/// Operation type of one side of a native value transfer.
+const CALL_OPERATION: &str = "CALL";
+/// Status of an operation applied to the balances.
+const SUCCESS_STATUS: &str = "SUCCESS";
+/// Version of `EthereumRuntimeRPCApi` reporting the balance changes of the transactions.
+const BALANCE_CHANGES_API_VERSION: u32 = 10;
+
+/// Errors returned by the Rosetta api, all listed by `/network/options`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum ErrorKind {
+	UnsupportedNetwork = 1,
+	InvalidRequest = 2,
+	BlockNotFound = 3,
+	TransactionNotFound = 4,
+	BalanceChangesUnsupported = 5,
+	InvalidTransaction = 6,
+	SubmissionFailed = 7,
+	Unavailable = 8,
+}
+
+impl ErrorKind {
+	const ALL: [ErrorKind; 8] = [
+		ErrorKind::UnsupportedNetwork,
+		ErrorKind::InvalidRequest,
+		ErrorKind::BlockNotFound,
+		ErrorKind::TransactionNotFound,
+		ErrorKind::BalanceChangesUnsupported,
+		ErrorKind::InvalidTransaction,
+		ErrorKind::SubmissionFailed,
+		ErrorKind::Unavailable,
+	];
+
+	fn message(self) -> &'static str {
+		match self {
+			ErrorKind::UnsupportedNetwork => "unsupported network",
+			ErrorKind::InvalidRequest => "invalid request",
+			ErrorKind::BlockNotFound => "block not found",
+			ErrorKind::TransactionNotFound => "transaction not found",
+			ErrorKind::BalanceChangesUnsupported => {
+				"the runtime does not report the balance changes of the block"
+			}
+			ErrorKind::InvalidTransaction => "invalid transaction",
+			ErrorKind::SubmissionFailed => "transaction submission failed",
+			ErrorKind::Unavailable => "node state unavailable",
+		}
+	}
+
+	/// Blocks not imported yet and node state read failures can succeed later.
+	fn retriable(self) -> bool {
+		matches!(self, ErrorKind::BlockNotFound | ErrorKind::Unavailable)
+	}
+
+	fn error(self) -> RosettaError {
+		RosettaError {
+			code: self as u32,
+			message: self.message().to_string(),
+			description: None,
+			retriable: self.retriable(),
+		}
+	}
+
+	fn with_description(self, description: impl ToString) -> RosettaError {
+		RosettaError {
+			description: Some(description.to_string()),
+			..self.error()
+		}
+	}
+}
+
+/// Rosetta data and construction api, served over http by [`Rosetta::serve`].
+///
+/// The operations of a transaction are the native balance changes it made, as reported by
+/// the runtime: `FEE` operations for the fee withdrawn from the sender, the unused gas
+/// refunded to it and the priority fee paid to the block author, and pairs of `CALL`
+/// operations for the value moved by the transaction and its internal calls. Reverted calls
+/// leave no balance change, so no operation either.
+pub struct Rosetta<B: BlockT, C, P> {
+	client: Arc<C>,
+	pool: Arc<P>,
+	backend: Arc<dyn fc_api::Backend<B>>,
+	storage_override: Arc<dyn StorageOverride<B>>,
+	network: NetworkIdentifier,
+	currency: Currency,
+	/// Largest request body accepted, in bytes.
+	max_request_size: usize,
+}
+
+impl<B, C, P> Rosetta<B, C, P>
+where
+	B: BlockT<Hash = H256>,
+	C: ProvideRuntimeApi<B> + HeaderBackend<B> + 'static,
+	C::Api: EthereumRuntimeRPCApi<B> + ConvertTransactionRuntimeApi<B>,
+	P: TransactionPool<Block = B> + 'static,
+{
+	pub fn new(
+		client: Arc<C>,
+		pool: Arc<P>,
+		backend: Arc<dyn fc_api::Backend<B>>,
+		storage_override: Arc<dyn StorageOverride<B>>,
+		network: NetworkIdentifier,
+		currency: Currency,
+		max_request_size: usize,
+	) -> Self {
+		Self {
+			client,
+			pool,
+			backend,
+			storage_override,
+			network,
+			currency,
+			max_request_size,
+		}
+	}
+
+	/// Serves the Rosetta endpoints on `addr` until the server fails.
+	pub async fn serve(self, addr: SocketAddr) {
+		let rosetta = Arc::new(self);
+		let make_service = make_service_fn(move |_| {
+			let rosetta = rosetta.clone();
+			async move {
+				Ok::<_, Infallible>(service_fn(move |request| {
+					let rosetta = rosetta.clone();
+					async move { Ok::<_, Infallible>(rosetta.handle(request).await) }
+				}))
+			}
+		});
+
+		let server = match Server::try_bind(&addr) {
+			Ok(builder) => builder.serve(make_service),
+			Err(err) => {
+				log::error!(target: "rosetta", "Cannot start the Rosetta api on {addr}: {err}");
+				return;
+			}
+		};
+		log::info!(target: "rosetta", "Rosetta api listening on {addr}");
+		if let Err(err) = server.await {
+			log::error!(target: "rosetta", "Rosetta api stopped: {err}");
+		}
+	}
+
+	async fn handle(&self, request: Request<Body>) -> Response<Body> {
+		if request.method() != Method::POST {
+			return response(StatusCode::METHOD_NOT_ALLOWED, Body::empty());
+		}
+		let path = request.uri().path().to_string();
+		let body = match self.read_body(request).await {
+			Ok(Some(body)) => body,
+			Ok(None) => return response(StatusCode::PAYLOAD_TOO_LARGE, Body::empty()),
+			Err(err) => return error_response(err),
+		};
+
+		match self.route(&path, &body).await {
+			Ok(Some(json)) => response(StatusCode::OK, Body::from(json)),
+			Ok(None) => response(StatusCode::NOT_FOUND, Body::empty()),
+			Err(err) => error_response(err),
+		}
+	}
+
+	/// Buffers the body of a request, or returns `None` if it is larger than the
+	/// `max_request_size`, checking the announced `Content-Length` before reading anything.
+	async fn read_body(&self, request: Request<Body>) -> Result<Option<Vec<u8>>, RosettaError> {
+		let content_length = request
+			.headers()
+			.get(hyper::header::CONTENT_LENGTH)
+			.and_then(|value| value.to_str().ok())
+			.and_then(|value| value.parse::<u64>().ok());
+		if content_length.map_or(false, |length| length > self.max_request_size as u64) {
+			return Ok(None);
+		}
+
+		let mut body = request.into_body();
+		let mut bytes = Vec::new();
+		while let Some(chunk) = body.data().await {
+			let chunk = chunk.map_err(|err| ErrorKind::InvalidRequest.with_description(err))?;
+			if bytes.len() + chunk.len() > self.max_request_size {
+				return Ok(None);
+			}
+			bytes.extend_from_slice(&chunk);
+		}
+		Ok(Some(bytes))
+	}
+
+	/// Dispatches a request to its endpoint, returning the json response, or `None` for an
+	/// unknown endpoint.
+	async fn route(&self, path: &str, body: &[u8]) -> Result<Option<Vec<u8>>, RosettaError> {
+		let json = match path {
+			"/network/list" => encode(&self.network_list())?,
+			"/network/options" => encode(&self.network_options(decode(body)?)?)?,
+			"/network/status" => encode(&self.network_status(decode(body)?)?)?,
+			"/block" => encode(&self.block(decode(body)?).await?)?,
+			"/block/transaction" => encode(&self.block_transaction(decode(body)?).await?)?,
+			"/account/balance" => encode(&self.account_balance(decode(body)?).await?)?,
+			"/construction/hash" => encode(&self.construction_hash(decode(body)?)?)?,
+			"/construction/submit" => encode(&self.construction_submit(decode(body)?).await?)?,
+			_ => return Ok(None),
+		};
+		Ok(Some(json))
+	}
+
+	fn ensure_network(&self, network: &NetworkIdentifier) -> Result<(), RosettaError> {
+		if *network != self.network {
+			return Err(ErrorKind::UnsupportedNetwork
+				.with_description(format!("{}/{}", network.blockchain, network.network)));
+		}
+		Ok(())
+	}
+
+	/// Finds the canonical block matching the number and the hash when given, or the best
+	/// block if none is.
+	async fn find_block(
+		&self,
+		block: &PartialBlockIdentifier,
+	) -> Result<(B::Hash, ethereum::BlockV2), RosettaError> {
+		let substrate_hash = match (block.hash, block.index) {
+			(Some(hash), _) => frontier_backend_client::load_hash::<B, C>(
+				self.client.as_ref(),
+				self.backend.as_ref(),
+				hash,
+			)
+			.await
+			.map_err(|err| ErrorKind::Unavailable.with_description(err.message()))?,
+			(None, Some(index)) => self
+				.client
+				.hash(index.unique_saturated_into())
+				.map_err(|err| ErrorKind::Unavailable.with_description(err))?,
+			(None, None) => Some(self.client.info().best_hash),
+		};
+		let found = substrate_hash.and_then(|substrate_hash| {
+			let block = self.storage_override.current_block(substrate_hash)?;
+			Some((substrate_hash, block))
+		});
+
+		match found {
+			// Both the number and the hash must match when given.
+			Some((substrate_hash, ethereum_block))
+				if block.index.map_or(true, |index| {
+					ethereum_block.header.number == U256::from(index)
+				}) && block
+					.hash
+					.map_or(true, |hash| ethereum_block.header.hash() == hash) =>
+			{
+				Ok((substrate_hash, ethereum_block))
+			}
+			_ => Err(ErrorKind::BlockNotFound.error()),
+		}
+	}
+
+	/// Balance changes of the ethereum transactions of a block, by transaction hash.
+	fn balance_changes(
+		&self,
+		substrate_hash: B::Hash,
+	) -> Result<BTreeMap<H256, Vec<BalanceChange>>, RosettaError> {
+		let api = self.client.runtime_api();
+		let api_version = api
+			.api_version::<dyn EthereumRuntimeRPCApi<B>>(substrate_hash)
+			.map_err(|err| ErrorKind::Unavailable.with_description(err))?
+			.unwrap_or_default();
+		if api_version < BALANCE_CHANGES_API_VERSION {
+			return Err(ErrorKind::BalanceChangesUnsupported.error());
+		}
+		let changes = api
+			.current_balance_changes(substrate_hash)
+			.map_err(|err| ErrorKind::Unavailable.with_description(err))?;
+		Ok(changes
+			.into_iter()
+			.map(|transaction| (transaction.transaction_hash, transaction.changes))
+			.collect())
+	}
+
+	fn network_list(&self) -> NetworkListResponse {
+		NetworkListResponse {
+			network_identifiers: vec![self.network.clone()],
+		}
+	}
+
+	fn network_options(&self, request: NetworkRequest) -> Result<NetworkOptions, RosettaError> {
+		self.ensure_network(&request.network_identifier)?;
+		Ok(NetworkOptions {
+			version: Version {
+				rosetta_version: ROSETTA_VERSION.to_string(),
+				node_version: env!("CARGO_PKG_VERSION").to_string(),
+			},
+			allow: Allow {
+				operation_statuses: vec![OperationStatus {
+					status: SUCCESS_STATUS.to_string(),
+					successful: true,
+				}],
+				operation_types: vec![FEE_OPERATION.to_string(), CALL_OPERATION.to_string()],
+				errors: ErrorKind::ALL.iter().map(|kind| kind.error()).collect(),
+				historical_balance_lookup: true,
+			},
+		})
+	}
+
+	fn network_status(&self, request: NetworkRequest) -> Result<NetworkStatus, RosettaError> {
+		self.ensure_network(&request.network_identifier)?;
+		let info = self.client.info();
+		let current = self
+			.storage_override
+			.current_block(info.best_hash)
+			.ok_or_else(|| ErrorKind::Unavailable.with_description("latest block not found"))?;
+		let genesis = self
+			.storage_override
+			.current_block(info.genesis_hash)
+			.ok_or_else(|| ErrorKind::Unavailable.with_description("genesis block not found"))?;
+
+		Ok(NetworkStatus {
+			current_block_identifier: block_identifier(&current),
+			current_block_timestamp: current.header.timestamp,
+			genesis_block_identifier: block_identifier(&genesis),
+			peers: Vec::new(),
+		})
+	}
+
+	async fn block(&self, request: BlockRequest) -> Result<BlockResponse, RosettaError> {
+		self.ensure_network(&request.network_identifier)?;
+		let (substrate_hash, block) = self.find_block(&request.block_identifier).await?;
+		let mut balance_changes = self.balance_changes(substrate_hash)?;
+
+		let block_identifier = block_identifier(&block);
+		let parent_block_identifier = if block_identifier.index == 0 {
+			block_identifier.clone()
+		} else {
+			BlockIdentifier {
+				index: block_identifier.index - 1,
+				hash: block.header.parent_hash,
+			}
+		};
+		let transactions = block
+			.transactions
+			.iter()
+			.map(|transaction| {
+				let hash = transaction.hash();
+				RosettaTransaction {
+					transaction_identifier: TransactionIdentifier { hash },
+					operations: transaction_operations(
+						balance_changes.remove(&hash).unwrap_or_default(),
+						&self.currency,
+					),
+				}
+			})
+			.collect();
+
+		Ok(BlockResponse {
+			block: RosettaBlock {
+				block_identifier,
+				parent_block_identifier,
+				timestamp: block.header.timestamp,
+				transactions,
+			},
+		})
+	}
+
+	async fn block_transaction(
+		&self,
+		request: BlockTransactionRequest,
+	) -> Result<BlockTransactionResponse, RosettaError> {
+		self.ensure_network(&request.network_identifier)?;
+		let (substrate_hash, block) = self
+			.find_block(&PartialBlockIdentifier {
+				index: Some(request.block_identifier.index),
+				hash: Some(request.block_identifier.hash),
+			})
+			.await?;
+		let hash = request.transaction_identifier.hash;
+		if !block
+			.transactions
+			.iter()
+			.any(|transaction| transaction.hash() == hash)
+		{
+			return Err(ErrorKind::TransactionNotFound.error());
+		}
+		let changes = self
+			.balance_changes(substrate_hash)?
+			.remove(&hash)
+			.unwrap_or_default();
+
+		Ok(BlockTransactionResponse {
+			transaction: RosettaTransaction {
+				transaction_identifier: TransactionIdentifier { hash },
+				operations: transaction_operations(changes, &self.currency),
+			},
+		})
+	}
+
+	async fn account_balance(
+		&self,
+		request: AccountBalanceRequest,
+	) -> Result<AccountBalance, RosettaError> {
+		self.ensure_network(&request.network_identifier)?;
+		let (substrate_hash, block) = self
+			.find_block(&request.block_identifier.unwrap_or_default())
+			.await?;
+		let account = self
+			.client
+			.runtime_api()
+			.account_basic(substrate_hash, request.account_identifier.address)
+			.map_err(|err| ErrorKind::Unavailable.with_description(err))?;
+
+		Ok(AccountBalance {
+			block_identifier: block_identifier(&block),
+			balances: vec![amount(account.balance, false, &self.currency)],
+		})
+	}
+
+	fn construction_hash(
+		&self,
+		request: ConstructionRequest,
+	) -> Result<TransactionIdentifierResponse, RosettaError> {
+		self.ensure_network(&request.network_identifier)?;
+		let transaction = decode_transaction(&request.signed_transaction.0)?;
+		Ok(TransactionIdentifierResponse {
+			transaction_identifier: TransactionIdentifier {
+				hash: transaction.hash(),
+			},
+		})
+	}
+
+	async fn construction_submit(
+		&self,
+		request: ConstructionRequest,
+	) -> Result<TransactionIdentifierResponse, RosettaError> {
+		self.ensure_network(&request.network_identifier)?;
+		let transaction = decode_transaction(&request.signed_transaction.0)?;
+		let hash = transaction.hash();
+
+		let best_hash = self.client.info().best_hash;
+		let extrinsic = self
+			.client
+			.runtime_api()
+			.convert_transaction(best_hash, transaction)
+			.map_err(|err| ErrorKind::Unavailable.with_description(err))?;
+		self.pool
+			.submit_one(best_hash, TransactionSource::Local, extrinsic)
+			.await
+			.map_err(|err| ErrorKind::SubmissionFailed.with_description(err))?;
+
+		Ok(TransactionIdentifierResponse {
+			transaction_identifier: TransactionIdentifier { hash },
+		})
+	}
+}
+
+fn decode<T: DeserializeOwned>(body: &[u8]) -> Result<T, RosettaError> {
+	serde_json::from_slice(body).map_err(|err| ErrorKind::InvalidRequest.with_description(err))
+}
+
+fn encode<T: Serialize>(value: &T) -> Result<Vec<u8>, RosettaError> {
+	serde_json::to_vec(value).map_err(|err| ErrorKind::Unavailable.with_description(err))
+}
+
+fn response(status: StatusCode, body: Body) -> Response<Body> {
+	let mut response = Response::new(body);
+	*response.status_mut() = status;
+	response.headers_mut().insert(
+		hyper::header::CONTENT_TYPE,
+		hyper::header::HeaderValue::from_static("application/json"),
+	);
+	response
+}
+
+/// Rosetta errors are returned with a 500 status.
+fn error_response(error: RosettaError) -> Response<Body> {
+	let body = serde_json::to_vec(&error).unwrap_or_default();
+	response(StatusCode::INTERNAL_SERVER_ERROR, Body::from(body))
+}
+
+fn decode_transaction(bytes: &[u8]) -> Result<ethereum::TransactionV2, RosettaError> {
+	ethereum::EnvelopedDecodable::decode(bytes)
+		.map_err(|_| ErrorKind::InvalidTransaction.with_description("decode transaction failed"))
+}
+
+/// Maps the balance changes of a transaction to operations, in the order they were made.
+fn transaction_operations(changes: Vec<BalanceChange>, currency: &Currency) -> Vec<Operation> {
+	let mut operations = Vec::new();
+	let mut push = |operation_type: &str, address: H160, value: U256, debit: bool| {
+		operations.push(Operation {
+			operation_identifier: OperationIdentifier {
+				index: operations.len() as u64,
+			},
+			operation_type: operation_type.to_string(),
+			status: Some(SUCCESS_STATUS.to_string()),
+			account: AccountIdentifier { address },
+			amount: amount(value, debit, currency),
+		});
+	};
+
+	for change in changes {
+		match change {
+			BalanceChange::FeeWithdrawn { amount, .. }
+			| BalanceChange::FeeDeposited { amount, .. }
+			| BalanceChange::Transfer { amount, .. }
+				if amount.is_zero() => {}
+			BalanceChange::FeeWithdrawn { address, amount } => {
+				push(FEE_OPERATION, address, amount, true)
+			}
+			BalanceChange::FeeDeposited { address, amount } => {
+				push(FEE_OPERATION, address, amount, false)
+			}
+			BalanceChange::Transfer { from, to, amount } => {
+				push(CALL_OPERATION, from, amount, true);
+				push(CALL_OPERATION, to, amount, false);
+			}
+		}
+	}
+
+	operations
+}
+
+fn amount(value: U256, debit: bool, currency: &Currency) -> Amount {
+	Amount {
+		value: if debit && !value.is_zero() {
+			format!("-{}", value)
+		} else {
+			value.to_string()
+		},
+		currency: currency.clone(),
+	}
+}
+
+fn block_identifier(block: &ethereum::BlockV2) -> BlockIdentifier {
+	BlockIdentifier {
+		index: block.header.number.low_u64(),
+		hash: block.header.hash(),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn currency() -> Currency {
+		Currency {
+			symbol: "UNIT".to_string(),
+			decimals: 18,
+		}
+	}
+
+	fn summary(operations: &[Operation]) -> Vec<(u64, &str, H160, &str)> {
+		operations
+			.iter()
+			.map(|operation| {
+				(
+					operation.operation_identifier.index,
+					operation.operation_type.as_str(),
+					operation.account.address,
+					operation.amount.value.as_str(),
+				)
+			})
+			.collect()
+	}
+
+	#[test]
+	fn balance_changes_are_mapped_to_fee_and_call_operations() {
+		let (sender, receiver, contract, author) = (
+			H160::repeat_byte(0x11),
+			H160::repeat_byte(0x22),
+			H160::repeat_byte(0x33),
+			H160::repeat_byte(0x44),
+		);
+		let changes = vec![
+			BalanceChange::FeeWithdrawn {
+				address: sender,
+				amount: U256::from(300_000),
+			},
+			BalanceChange::Transfer {
+				from: sender,
+				to: contract,
+				amount: U256::from(1_000),
+			},
+			// Internal transfer made by the called contract.
+			BalanceChange::Transfer {
+				from: contract,
+				to: receiver,
+				amount: U256::from(400),
+			},
+			BalanceChange::FeeDeposited {
+				address: sender,
+				amount: U256::from(90_000),
+			},
+			BalanceChange::FeeDeposited {
+				address: author,
+				amount: U256::from(21_000),
+			},
+		];
+		let operations = transaction_operations(changes, &currency());
+
+		assert_eq!(
+			summary(&operations),
+			vec![
+				(0, FEE_OPERATION, sender, "-300000"),
+				(1, CALL_OPERATION, sender, "-1000"),
+				(2, CALL_OPERATION, contract, "1000"),
+				(3, CALL_OPERATION, contract, "-400"),
+				(4, CALL_OPERATION, receiver, "400"),
+				(5, FEE_OPERATION, sender, "90000"),
+				(6, FEE_OPERATION, author, "21000"),
+			]
+		);
+		assert!(operations
+			.iter()
+			.all(|operation| operation.status.as_deref() == Some(SUCCESS_STATUS)));
+	}
+
+	#[test]
+	fn zero_balance_changes_are_skipped() {
+		let changes = vec![
+			BalanceChange::FeeDeposited {
+				address: H160::repeat_byte(0x11),
+				amount: U256::zero(),
+			},
+			BalanceChange::Transfer {
+				from: H160::repeat_byte(0x11),
+				to: H160::repeat_byte(0x22),
+				amount: U256::zero(),
+			},
+		];
+		assert!(transaction_operations(changes, &currency()).is_empty());
+	}
+
+	#[test]
+	fn requests_are_decoded_from_the_rosetta_json() {
+		let request: AccountBalanceRequest = decode(
+			br#"{
+				"network_identifier": {"blockchain": "frontier", "network": "mainnet"},
+				"account_identifier": {"address": "0x1111111111111111111111111111111111111111"}
+			}"#,
+		)
+		.expect("valid request");
+		assert_eq!(request.network_identifier.network, "mainnet");
+		assert_eq!(request.account_identifier.address, H160::repeat_byte(0x11));
+		assert_eq!(request.block_identifier, None);
+
+		let error = decode::<BlockRequest>(b"{}").expect_err("missing fields");
+		assert_eq!(error.code, ErrorKind::InvalidRequest as u32);
+		assert!(!error.retriable);
+	}
+}
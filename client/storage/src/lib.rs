@@ -42,16 +42,26 @@ pub use self::overrides::*;
 ///
 /// It is used to avoid spawning the runtime and the overhead associated with it.
 #[derive(Clone)]
-pub struct StorageOverrideHandler<B, C, BE> {
+pub struct StorageOverrideHandler<B: BlockT, C, BE> {
 	querier: StorageQuerier<B, C, BE>,
 	fallback: RuntimeApiStorageOverride<B, C>,
+	schema_cache: StorageSchemaCache<B>,
 }
 
-impl<B, C, BE> StorageOverrideHandler<B, C, BE> {
+/// Number of blocks whose storage schema is cached by default.
+const DEFAULT_SCHEMA_CACHE_SIZE: u32 = 1024;
+
+impl<B: BlockT, C, BE> StorageOverrideHandler<B, C, BE> {
 	pub fn new(client: Arc<C>) -> Self {
+		Self::with_schema_cache(client, StorageSchemaCache::new(DEFAULT_SCHEMA_CACHE_SIZE))
+	}
+
+	/// Create a handler sharing the given storage schema cache.
+	pub fn with_schema_cache(client: Arc<C>, schema_cache: StorageSchemaCache<B>) -> Self {
 		Self {
 			querier: StorageQuerier::new(client.clone()),
 			fallback: RuntimeApiStorageOverride::<B, C>::new(client),
+			schema_cache,
 		}
 	}
 }
@@ -65,7 +75,7 @@ where
 	BE: Backend<B> + 'static,
 {
 	fn account_code_at(&self, at: B::Hash, address: Address) -> Option<Vec<u8>> {
-		match self.querier.storage_schema(at) {
+		match self.storage_schema(at) {
 			Some(EthereumStorageSchema::V1) => {
 				SchemaV1StorageOverrideRef::new(&self.querier).account_code_at(at, address)
 			}
@@ -75,24 +85,29 @@ where
 			Some(EthereumStorageSchema::V3) => {
 				SchemaV3StorageOverrideRef::new(&self.querier).account_code_at(at, address)
 			}
+			Some(EthereumStorageSchema::V4) => {
+				SchemaV4StorageOverrideRef::new(&self.querier).account_code_at(at, address)
+			}
 			None => self.fallback.account_code_at(at, address),
 		}
 	}
 
 	fn account_storage_at(&self, at: B::Hash, address: Address, index: U256) -> Option<H256> {
-		match self.querier.storage_schema(at) {
+		match self.storage_schema(at) {
 			Some(EthereumStorageSchema::V1) => SchemaV1StorageOverrideRef::new(&self.querier)
 				.account_storage_at(at, address, index),
 			Some(EthereumStorageSchema::V2) => SchemaV2StorageOverrideRef::new(&self.querier)
 				.account_storage_at(at, address, index),
 			Some(EthereumStorageSchema::V3) => SchemaV3StorageOverrideRef::new(&self.querier)
 				.account_storage_at(at, address, index),
+			Some(EthereumStorageSchema::V4) => SchemaV4StorageOverrideRef::new(&self.querier)
+				.account_storage_at(at, address, index),
 			None => self.fallback.account_storage_at(at, address, index),
 		}
 	}
 
 	fn current_block(&self, at: B::Hash) -> Option<BlockV2> {
-		match self.querier.storage_schema(at) {
+		match self.storage_schema(at) {
 			Some(EthereumStorageSchema::V1) => {
 				SchemaV1StorageOverrideRef::new(&self.querier).current_block(at)
 			}
@@ -102,12 +117,15 @@ where
 			Some(EthereumStorageSchema::V3) => {
 				SchemaV3StorageOverrideRef::new(&self.querier).current_block(at)
 			}
+			Some(EthereumStorageSchema::V4) => {
+				SchemaV4StorageOverrideRef::new(&self.querier).current_block(at)
+			}
 			None => self.fallback.current_block(at),
 		}
 	}
 
 	fn current_receipts(&self, at: B::Hash) -> Option<Vec<ReceiptV3>> {
-		match self.querier.storage_schema(at) {
+		match self.storage_schema(at) {
 			Some(EthereumStorageSchema::V1) => {
 				SchemaV1StorageOverrideRef::new(&self.querier).current_receipts(at)
 			}
@@ -117,12 +135,15 @@ where
 			Some(EthereumStorageSchema::V3) => {
 				SchemaV3StorageOverrideRef::new(&self.querier).current_receipts(at)
 			}
+			Some(EthereumStorageSchema::V4) => {
+				SchemaV4StorageOverrideRef::new(&self.querier).current_receipts(at)
+			}
 			None => self.fallback.current_receipts(at),
 		}
 	}
 
 	fn current_transaction_statuses(&self, at: B::Hash) -> Option<Vec<TransactionStatus>> {
-		match self.querier.storage_schema(at) {
+		match self.storage_schema(at) {
 			Some(EthereumStorageSchema::V1) => {
 				SchemaV1StorageOverrideRef::new(&self.querier).current_transaction_statuses(at)
 			}
@@ -132,12 +153,33 @@ where
 			Some(EthereumStorageSchema::V3) => {
 				SchemaV3StorageOverrideRef::new(&self.querier).current_transaction_statuses(at)
 			}
+			Some(EthereumStorageSchema::V4) => {
+				SchemaV4StorageOverrideRef::new(&self.querier).current_transaction_statuses(at)
+			}
 			None => self.fallback.current_transaction_statuses(at),
 		}
 	}
 
+	fn current_revert_reasons(&self, at: B::Hash) -> Option<Vec<Vec<u8>>> {
+		match self.storage_schema(at) {
+			Some(EthereumStorageSchema::V4) => {
+				SchemaV4StorageOverrideRef::new(&self.querier).current_revert_reasons(at)
+			}
+			_ => None,
+		}
+	}
+
+	fn current_effective_gas_prices(&self, at: B::Hash) -> Option<Vec<U256>> {
+		match self.storage_schema(at) {
+			Some(EthereumStorageSchema::V4) => {
+				SchemaV4StorageOverrideRef::new(&self.querier).current_effective_gas_prices(at)
+			}
+			_ => None,
+		}
+	}
+
 	fn elasticity(&self, at: B::Hash) -> Option<Permill> {
-		match self.querier.storage_schema(at) {
+		match self.storage_schema(at) {
 			Some(EthereumStorageSchema::V1) => {
 				SchemaV1StorageOverrideRef::new(&self.querier).elasticity(at)
 			}
@@ -147,12 +189,15 @@ where
 			Some(EthereumStorageSchema::V3) => {
 				SchemaV3StorageOverrideRef::new(&self.querier).elasticity(at)
 			}
+			Some(EthereumStorageSchema::V4) => {
+				SchemaV4StorageOverrideRef::new(&self.querier).elasticity(at)
+			}
 			None => self.fallback.elasticity(at),
 		}
 	}
 
 	fn is_eip1559(&self, at: B::Hash) -> bool {
-		match self.querier.storage_schema(at) {
+		match self.storage_schema(at) {
 			Some(EthereumStorageSchema::V1) => {
 				SchemaV1StorageOverrideRef::new(&self.querier).is_eip1559(at)
 			}
@@ -162,7 +207,15 @@ where
 			Some(EthereumStorageSchema::V3) => {
 				SchemaV3StorageOverrideRef::new(&self.querier).is_eip1559(at)
 			}
+			Some(EthereumStorageSchema::V4) => {
+				SchemaV4StorageOverrideRef::new(&self.querier).is_eip1559(at)
+			}
 			None => self.fallback.is_eip1559(at),
 		}
 	}
+
+	fn storage_schema(&self, at: B::Hash) -> Option<EthereumStorageSchema> {
+		self.schema_cache
+			.get_or_query(at, || self.querier.storage_schema(at))
+	}
 }
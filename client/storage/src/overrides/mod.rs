@@ -19,7 +19,9 @@
 use std::{marker::PhantomData, sync::Arc};
 
 use ethereum_types::{Address, H256, U256};
+use parking_lot::Mutex;
 use scale_codec::Decode;
+use schnellru::{ByLength, LruMap};
 // Substrate
 use sc_client_api::{Backend, StorageProvider};
 use sp_io::hashing::{blake2_128, twox_128};
@@ -47,6 +49,10 @@ pub use self::{
 			SchemaStorageOverride as SchemaV3StorageOverride,
 			SchemaStorageOverrideRef as SchemaV3StorageOverrideRef,
 		},
+		v4::{
+			SchemaStorageOverride as SchemaV4StorageOverride,
+			SchemaStorageOverrideRef as SchemaV4StorageOverrideRef,
+		},
 	},
 };
 
@@ -63,11 +69,60 @@ pub trait StorageOverride<Block: BlockT>: Send + Sync {
 	fn current_receipts(&self, at: Block::Hash) -> Option<Vec<ethereum::ReceiptV3>>;
 	/// Return the current ethereum transaction status.
 	fn current_transaction_statuses(&self, at: Block::Hash) -> Option<Vec<TransactionStatus>>;
+	/// Return the revert output of the current ethereum transactions, `None` if the storage
+	/// schema does not keep it.
+	fn current_revert_reasons(&self, _at: Block::Hash) -> Option<Vec<Vec<u8>>> {
+		None
+	}
+	/// Return the effective gas price of the current ethereum transactions, `None` if the
+	/// storage schema does not keep it.
+	fn current_effective_gas_prices(&self, _at: Block::Hash) -> Option<Vec<U256>> {
+		None
+	}
 
 	/// Return the elasticity multiplier at the given post-eip1559 block.
 	fn elasticity(&self, at: Block::Hash) -> Option<Permill>;
 	/// Return `true` if the request block is post-eip1559.
 	fn is_eip1559(&self, at: Block::Hash) -> bool;
+
+	/// Return the ethereum storage schema at the given block, `None` if it is unknown.
+	fn storage_schema(&self, _at: Block::Hash) -> Option<EthereumStorageSchema> {
+		None
+	}
+}
+
+/// A cache of the ethereum storage schema of the recently accessed blocks.
+///
+/// The schema only changes with runtime upgrades, while it is read before every access to the
+/// ethereum data of a block.
+pub struct StorageSchemaCache<B: BlockT>(
+	Arc<Mutex<LruMap<B::Hash, Option<EthereumStorageSchema>>>>,
+);
+
+impl<B: BlockT> Clone for StorageSchemaCache<B> {
+	fn clone(&self) -> Self {
+		Self(self.0.clone())
+	}
+}
+
+impl<B: BlockT> StorageSchemaCache<B> {
+	pub fn new(capacity: u32) -> Self {
+		Self(Arc::new(Mutex::new(LruMap::new(ByLength::new(capacity)))))
+	}
+
+	/// Get the cached schema at the given block, calling `query` on a miss.
+	pub fn get_or_query(
+		&self,
+		at: B::Hash,
+		query: impl FnOnce() -> Option<EthereumStorageSchema>,
+	) -> Option<EthereumStorageSchema> {
+		if let Some(schema) = self.0.lock().get(&at) {
+			return *schema;
+		}
+		let schema = query();
+		self.0.lock().insert(at, schema);
+		schema
+	}
 }
 
 fn storage_prefix_build(module: &[u8], storage: &[u8]) -> Vec<u8> {
@@ -119,7 +174,32 @@ where
 	pub fn account_code(&self, at: B::Hash, address: Address) -> Option<Vec<u8>> {
 		let mut key: Vec<u8> = storage_prefix_build(PALLET_EVM, EVM_ACCOUNT_CODES);
 		key.extend(blake2_128_extend(address.as_bytes()));
-		self.query::<Vec<u8>>(at, &StorageKey(key))
+		let data = self.client.storage(at, &StorageKey(key)).ok()??;
+
+		// Since the code deduplication the code is stored by hash, before it the code was stored
+		// by address. While the migration is ongoing, the storage version is still the old one
+		// but the migrated contracts already have a hash: the code is only read by hash if a code
+		// is stored under it, which the code stored by address can't forge.
+		let code_by_hash = |data: &[u8]| {
+			let hash = H256::decode(&mut &data[..]).ok()?;
+			let mut key: Vec<u8> = storage_prefix_build(PALLET_EVM, EVM_CODES);
+			key.extend(hash.as_bytes());
+			self.query::<Vec<u8>>(at, &StorageKey(key))
+		};
+		if self.evm_storage_version(at) >= EVM_CODES_STORAGE_VERSION {
+			return code_by_hash(&data.0);
+		}
+		if data.0.len() == H256::len_bytes() {
+			if let Some(code) = code_by_hash(&data.0) {
+				return Some(code);
+			}
+		}
+		Decode::decode(&mut &data.0[..]).ok()
+	}
+
+	fn evm_storage_version(&self, at: B::Hash) -> u16 {
+		let key = storage_prefix_build(PALLET_EVM, STORAGE_VERSION);
+		self.query::<u16>(at, &StorageKey(key)).unwrap_or_default()
 	}
 
 	pub fn account_storage(&self, at: B::Hash, address: Address, index: U256) -> Option<H256> {
@@ -148,6 +228,16 @@ where
 		self.query::<Vec<TransactionStatus>>(at, &StorageKey(key))
 	}
 
+	pub fn current_revert_reasons(&self, at: B::Hash) -> Option<Vec<Vec<u8>>> {
+		let key = storage_prefix_build(PALLET_ETHEREUM, ETHEREUM_CURRENT_REVERT_REASONS);
+		self.query::<Vec<Vec<u8>>>(at, &StorageKey(key))
+	}
+
+	pub fn current_effective_gas_prices(&self, at: B::Hash) -> Option<Vec<U256>> {
+		let key = storage_prefix_build(PALLET_ETHEREUM, ETHEREUM_CURRENT_EFFECTIVE_GAS_PRICES);
+		self.query::<Vec<U256>>(at, &StorageKey(key))
+	}
+
 	pub fn elasticity(&self, at: B::Hash) -> Option<Permill> {
 		let key = storage_prefix_build(PALLET_BASE_FEE, BASE_FEE_ELASTICITY);
 		self.query::<Permill>(at, &StorageKey(key))
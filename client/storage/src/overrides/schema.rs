@@ -24,6 +24,7 @@ use sc_client_api::backend::{Backend, StorageProvider};
 use sp_runtime::{traits::Block as BlockT, Permill};
 // Frontier
 use fp_rpc::TransactionStatus;
+use fp_storage::EthereumStorageSchema;
 
 use crate::overrides::{StorageOverride, StorageQuerier};
 
@@ -76,6 +77,10 @@ pub mod v1 {
 		fn is_eip1559(&self, at: B::Hash) -> bool {
 			SchemaStorageOverrideRef::new(&self.querier).is_eip1559(at)
 		}
+
+		fn storage_schema(&self, _at: B::Hash) -> Option<EthereumStorageSchema> {
+			Some(EthereumStorageSchema::V1)
+		}
 	}
 
 	/// A storage override reference for runtimes that use schema v1.
@@ -190,6 +195,10 @@ pub mod v2 {
 		fn is_eip1559(&self, at: B::Hash) -> bool {
 			SchemaStorageOverrideRef::new(&self.querier).is_eip1559(at)
 		}
+
+		fn storage_schema(&self, _at: B::Hash) -> Option<EthereumStorageSchema> {
+			Some(EthereumStorageSchema::V2)
+		}
 	}
 
 	/// A storage override reference for runtimes that use schema v2.
@@ -302,6 +311,10 @@ pub mod v3 {
 		fn is_eip1559(&self, at: B::Hash) -> bool {
 			SchemaStorageOverrideRef::new(&self.querier).is_eip1559(at)
 		}
+
+		fn storage_schema(&self, _at: B::Hash) -> Option<EthereumStorageSchema> {
+			Some(EthereumStorageSchema::V3)
+		}
 	}
 
 	/// A storage override for runtimes that use schema v3.
@@ -350,3 +363,121 @@ pub mod v3 {
 		}
 	}
 }
+
+pub mod v4 {
+	use super::*;
+
+	/// A storage override for runtimes that use schema v4.
+	#[derive(Clone)]
+	pub struct SchemaStorageOverride<B, C, BE> {
+		querier: StorageQuerier<B, C, BE>,
+	}
+
+	impl<B, C, BE> SchemaStorageOverride<B, C, BE> {
+		pub fn new(client: Arc<C>) -> Self {
+			let querier = StorageQuerier::new(client);
+			Self { querier }
+		}
+	}
+
+	impl<B, C, BE> StorageOverride<B> for SchemaStorageOverride<B, C, BE>
+	where
+		B: BlockT,
+		C: StorageProvider<B, BE> + Send + Sync,
+		BE: Backend<B>,
+	{
+		fn account_code_at(&self, at: B::Hash, address: Address) -> Option<Vec<u8>> {
+			SchemaStorageOverrideRef::new(&self.querier).account_code_at(at, address)
+		}
+
+		fn account_storage_at(&self, at: B::Hash, address: Address, index: U256) -> Option<H256> {
+			SchemaStorageOverrideRef::new(&self.querier).account_storage_at(at, address, index)
+		}
+
+		fn current_block(&self, at: B::Hash) -> Option<ethereum::BlockV2> {
+			SchemaStorageOverrideRef::new(&self.querier).current_block(at)
+		}
+
+		fn current_receipts(&self, at: B::Hash) -> Option<Vec<ethereum::ReceiptV3>> {
+			SchemaStorageOverrideRef::new(&self.querier).current_receipts(at)
+		}
+
+		fn current_transaction_statuses(&self, at: B::Hash) -> Option<Vec<TransactionStatus>> {
+			SchemaStorageOverrideRef::new(&self.querier).current_transaction_statuses(at)
+		}
+
+		fn current_revert_reasons(&self, at: B::Hash) -> Option<Vec<Vec<u8>>> {
+			SchemaStorageOverrideRef::new(&self.querier).current_revert_reasons(at)
+		}
+
+		fn current_effective_gas_prices(&self, at: B::Hash) -> Option<Vec<U256>> {
+			SchemaStorageOverrideRef::new(&self.querier).current_effective_gas_prices(at)
+		}
+
+		fn elasticity(&self, at: B::Hash) -> Option<Permill> {
+			SchemaStorageOverrideRef::new(&self.querier).elasticity(at)
+		}
+
+		fn is_eip1559(&self, at: B::Hash) -> bool {
+			SchemaStorageOverrideRef::new(&self.querier).is_eip1559(at)
+		}
+
+		fn storage_schema(&self, _at: B::Hash) -> Option<EthereumStorageSchema> {
+			Some(EthereumStorageSchema::V4)
+		}
+	}
+
+	/// A storage override reference for runtimes that use schema v4.
+	pub struct SchemaStorageOverrideRef<'a, B, C, BE> {
+		querier: &'a StorageQuerier<B, C, BE>,
+	}
+
+	impl<'a, B, C, BE> SchemaStorageOverrideRef<'a, B, C, BE> {
+		pub fn new(querier: &'a StorageQuerier<B, C, BE>) -> Self {
+			Self { querier }
+		}
+	}
+
+	impl<'a, B, C, BE> StorageOverride<B> for SchemaStorageOverrideRef<'a, B, C, BE>
+	where
+		B: BlockT,
+		C: StorageProvider<B, BE> + Send + Sync,
+		BE: Backend<B>,
+	{
+		fn account_code_at(&self, at: B::Hash, address: Address) -> Option<Vec<u8>> {
+			self.querier.account_code(at, address)
+		}
+
+		fn account_storage_at(&self, at: B::Hash, address: Address, index: U256) -> Option<H256> {
+			self.querier.account_storage(at, address, index)
+		}
+
+		fn current_block(&self, at: B::Hash) -> Option<ethereum::BlockV2> {
+			self.querier.current_block(at)
+		}
+
+		fn current_receipts(&self, at: B::Hash) -> Option<Vec<ethereum::ReceiptV3>> {
+			self.querier.current_receipts::<ethereum::ReceiptV3>(at)
+		}
+
+		fn current_transaction_statuses(&self, at: B::Hash) -> Option<Vec<TransactionStatus>> {
+			self.querier.current_transaction_statuses(at)
+		}
+
+		fn current_revert_reasons(&self, at: B::Hash) -> Option<Vec<Vec<u8>>> {
+			self.querier.current_revert_reasons(at)
+		}
+
+		fn current_effective_gas_prices(&self, at: B::Hash) -> Option<Vec<U256>> {
+			self.querier.current_effective_gas_prices(at)
+		}
+
+		fn elasticity(&self, at: B::Hash) -> Option<Permill> {
+			self.querier.elasticity(at)
+		}
+
+		fn is_eip1559(&self, _at: B::Hash) -> bool {
+			true
+		}
+	}
+}
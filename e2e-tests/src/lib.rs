@@ -0,0 +1,230 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! End-to-end test utilities, spawning a manual seal `frontier-template-node` dev chain and
+//! driving it with ethers-rs.
+
+use std::{net::TcpListener, path::PathBuf, process::Stdio, sync::Arc, time::Duration};
+
+use ethers::{
+	middleware::SignerMiddleware,
+	providers::{Http, Middleware, Provider, Ws},
+	signers::{LocalWallet, Signer},
+	types::{transaction::eip2718::TypedTransaction, TransactionReceipt, H256},
+};
+use tokio::{
+	io::{AsyncBufReadExt, BufReader},
+	process::{Child, Command},
+};
+
+pub const NODE_BINARY_NAME: &str = "frontier-template-node";
+pub const CHAIN_ID: u64 = 42;
+
+pub const GENESIS_ACCOUNT_PRIVATE_KEY: &str =
+	"0x99B3C12287537E38C90A9219D4CB074A89A16E9CDB20BF85728EBD97C343E342";
+
+/// Constructor of a contract storing the first calldata word of every call in its slot 0,
+/// emitting it in a `Stored(uint256)` event.
+pub const STORAGE_CONTRACT_BYTECODE: &str =
+	"0x603180600b6000396000f3600035806000556000527fc6d8c0af6d21f291e7c359603aa97e0ed500f04db6e983b9\
+	 fce75a91c6b8da6b60206000a100";
+/// `keccak256("Stored(uint256)")`.
+pub const STORED_EVENT_TOPIC: &str =
+	"0xc6d8c0af6d21f291e7c359603aa97e0ed500f04db6e983b9fce75a91c6b8da6b";
+
+/// How long to wait for the node to be ready.
+const SPAWNING_TIME: Duration = Duration::from_secs(60);
+
+/// Client signing with the genesis account.
+pub type Client = SignerMiddleware<Provider<Http>, LocalWallet>;
+
+/// A running dev node, killed on drop.
+pub struct FrontierNode {
+	_process: Child,
+	rpc_port: u16,
+	provider: Provider<Http>,
+}
+
+impl FrontierNode {
+	/// Spawns a dev node with manual sealing on free ports and waits until it is ready.
+	pub async fn start() -> Self {
+		let build = std::env::var("FRONTIER_BUILD").unwrap_or_else(|_| "release".into());
+		let backend_type =
+			std::env::var("FRONTIER_BACKEND_TYPE").unwrap_or_else(|_| "key-value".into());
+		let log = std::env::var("FRONTIER_LOG").ok();
+		let binary = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+			.join("../target")
+			.join(build)
+			.join(NODE_BINARY_NAME);
+		let rpc_port = free_port();
+
+		let mut process = Command::new(&binary)
+			.args([
+				"--chain=dev",
+				// Required by manual sealing to author the blocks
+				"--validator",
+				"--no-telemetry",
+				"--no-prometheus",
+				"--sealing=Manual",
+				"--no-grandpa",
+				"--force-authoring",
+				"--tmp",
+				"--unsafe-force-node-key-generation",
+			])
+			.arg(format!("-l{}", log.as_deref().unwrap_or("info")))
+			.arg(format!("--port={}", free_port()))
+			.arg(format!("--rpc-port={rpc_port}"))
+			.arg(format!("--frontier-backend-type={backend_type}"))
+			.stdout(Stdio::null())
+			.stderr(Stdio::piped())
+			.kill_on_drop(true)
+			.spawn()
+			.unwrap_or_else(|err| {
+				panic!(
+					"failed spawning {}, please compile the node first: {err}",
+					binary.display()
+				)
+			});
+
+		let mut lines = BufReader::new(process.stderr.take().expect("stderr is piped")).lines();
+		tokio::time::timeout(SPAWNING_TIME, async {
+			while let Some(line) = lines.next_line().await.expect("node logs are readable") {
+				if log.is_some() {
+					eprintln!("{line}");
+				}
+				if line.contains("Manual Seal Ready") {
+					return;
+				}
+			}
+			panic!("node exited before being ready");
+		})
+		.await
+		.expect("node is ready in time");
+		// Keep draining the logs, the node blocks once the pipe is full.
+		tokio::spawn(async move {
+			while let Ok(Some(line)) = lines.next_line().await {
+				if log.is_some() {
+					eprintln!("{line}");
+				}
+			}
+		});
+
+		let provider = Provider::<Http>::try_from(format!("http://127.0.0.1:{rpc_port}"))
+			.expect("valid url")
+			.interval(Duration::from_millis(100));
+		// The EVM runtime needs to warm up with a first call.
+		provider.get_chainid().await.expect("node is reachable");
+
+		Self {
+			_process: process,
+			rpc_port,
+			provider,
+		}
+	}
+
+	/// Http provider connected to the node.
+	pub fn provider(&self) -> &Provider<Http> {
+		&self.provider
+	}
+
+	/// Websocket provider connected to the node, for the subscriptions.
+	pub async fn ws(&self) -> Provider<Ws> {
+		Provider::<Ws>::connect(format!("ws://127.0.0.1:{}", self.rpc_port))
+			.await
+			.expect("node is reachable")
+	}
+
+	/// Client signing the transactions with the genesis account.
+	pub fn client(&self) -> Arc<Client> {
+		let wallet = GENESIS_ACCOUNT_PRIVATE_KEY
+			.parse::<LocalWallet>()
+			.expect("valid private key")
+			.with_chain_id(CHAIN_ID);
+		Arc::new(SignerMiddleware::new(self.provider.clone(), wallet))
+	}
+
+	/// Seals a block on top of the best block including the pending transactions, returning
+	/// its substrate hash.
+	pub async fn create_block(&self, finalize: bool) -> H256 {
+		self.seal(finalize, None).await
+	}
+
+	/// Seals a block on top of the given substrate block, forking the chain if it is not the
+	/// best block.
+	pub async fn create_block_on(&self, parent_hash: H256, finalize: bool) -> H256 {
+		self.seal(finalize, Some(parent_hash)).await
+	}
+
+	/// Seals `length` blocks on top of the given substrate block and returns their hashes.
+	pub async fn create_fork(&self, parent_hash: H256, length: u32, finalize: bool) -> Vec<H256> {
+		let hashes = self
+			.provider
+			.request("engine_createFork", (parent_hash, length, finalize))
+			.await
+			.expect("fork is sealed");
+		tokio::time::sleep(Duration::from_millis(500)).await;
+		hashes
+	}
+
+	/// Finalizes the given substrate block and its ancestors.
+	pub async fn finalize_block(&self, hash: H256) {
+		let finalized: bool = self
+			.provider
+			.request("engine_finalizeBlock", (hash, Option::<()>::None))
+			.await
+			.expect("block is finalized");
+		assert!(finalized);
+		tokio::time::sleep(Duration::from_millis(500)).await;
+	}
+
+	async fn seal(&self, finalize: bool, parent_hash: Option<H256>) -> H256 {
+		let created: serde_json::Value = self
+			.provider
+			.request("engine_createBlock", (true, finalize, parent_hash))
+			.await
+			.expect("block is sealed");
+		let hash = serde_json::from_value(created["hash"].clone()).expect("block hash");
+		// Give the mapping sync worker some time to pick the block up.
+		tokio::time::sleep(Duration::from_millis(500)).await;
+		hash
+	}
+
+	/// Signs and sends the transaction with the genesis account, seals it in a block and
+	/// returns its receipt.
+	pub async fn send_and_seal(&self, tx: impl Into<TypedTransaction>) -> TransactionReceipt {
+		let client = self.client();
+		let hash = *client
+			.send_transaction(tx, None)
+			.await
+			.expect("transaction is accepted");
+		self.create_block(true).await;
+		self.provider
+			.get_transaction_receipt(hash)
+			.await
+			.expect("receipt request succeeds")
+			.expect("transaction is included")
+	}
+}
+
+/// Returns a port free at the time of the call.
+fn free_port() -> u16 {
+	TcpListener::bind("127.0.0.1:0")
+		.and_then(|listener| listener.local_addr())
+		.map(|addr| addr.port())
+		.expect("a free port is available")
+}
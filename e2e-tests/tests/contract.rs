@@ -0,0 +1,145 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use ethers::{
+	providers::Middleware,
+	types::{
+		Address, Bytes, Filter, FilterKind, TransactionReceipt, TransactionRequest, H256, U256,
+	},
+};
+use frontier_e2e_tests::{FrontierNode, STORAGE_CONTRACT_BYTECODE, STORED_EVENT_TOPIC};
+
+async fn deploy(node: &FrontierNode) -> Address {
+	let bytecode: Bytes = STORAGE_CONTRACT_BYTECODE.parse().expect("valid bytecode");
+	let receipt = node
+		.send_and_seal(TransactionRequest::new().data(bytecode).gas(200_000))
+		.await;
+	assert_eq!(receipt.status, Some(1.into()));
+	receipt.contract_address.expect("contract is created")
+}
+
+async fn store(node: &FrontierNode, contract: Address, value: u64) -> TransactionReceipt {
+	let mut data = [0u8; 32];
+	U256::from(value).to_big_endian(&mut data);
+	node.send_and_seal(
+		TransactionRequest::new()
+			.to(contract)
+			.data(data.to_vec())
+			.gas(100_000),
+	)
+	.await
+}
+
+#[tokio::test]
+#[ignore = "requires a compiled frontier-template-node"]
+async fn deploy_and_call_contract() {
+	let node = FrontierNode::start().await;
+	let provider = node.provider();
+
+	let contract = deploy(&node).await;
+	assert!(!provider
+		.get_code(contract, None)
+		.await
+		.expect("must succeed")
+		.is_empty());
+
+	let receipt = store(&node, contract, 42).await;
+	assert_eq!(receipt.status, Some(1.into()));
+	assert_eq!(
+		provider
+			.get_storage_at(contract, H256::zero(), None)
+			.await
+			.expect("must succeed"),
+		H256::from_low_u64_be(42)
+	);
+}
+
+#[tokio::test]
+#[ignore = "requires a compiled frontier-template-node"]
+async fn events_are_returned_by_receipts_and_get_logs() {
+	let node = FrontierNode::start().await;
+	let provider = node.provider();
+	let topic: H256 = STORED_EVENT_TOPIC.parse().expect("valid topic");
+
+	let contract = deploy(&node).await;
+	let receipt = store(&node, contract, 7).await;
+	assert_eq!(receipt.logs.len(), 1);
+	assert_eq!(receipt.logs[0].address, contract);
+	assert_eq!(receipt.logs[0].topics, vec![topic]);
+	assert_eq!(
+		receipt.logs[0].data.to_vec(),
+		H256::from_low_u64_be(7).0.to_vec()
+	);
+
+	let logs = provider
+		.get_logs(&Filter::new().from_block(0).address(contract).topic0(topic))
+		.await
+		.expect("must succeed");
+	assert_eq!(logs.len(), 1);
+	assert_eq!(logs[0].transaction_hash, Some(receipt.transaction_hash));
+
+	// Non-matching topic.
+	assert!(provider
+		.get_logs(&Filter::new().from_block(0).topic0(H256::zero()))
+		.await
+		.expect("must succeed")
+		.is_empty());
+}
+
+#[tokio::test]
+#[ignore = "requires a compiled frontier-template-node"]
+async fn filters_return_changes_since_last_poll() {
+	let node = FrontierNode::start().await;
+	let provider = node.provider();
+
+	let contract = deploy(&node).await;
+	let filter = Filter::new().address(contract);
+	let log_filter = provider
+		.new_filter(FilterKind::Logs(&filter))
+		.await
+		.expect("must succeed");
+	let block_filter = provider
+		.new_filter(FilterKind::NewBlocks)
+		.await
+		.expect("must succeed");
+
+	store(&node, contract, 1).await;
+	store(&node, contract, 2).await;
+
+	let logs: Vec<ethers::types::Log> = provider
+		.get_filter_changes(log_filter)
+		.await
+		.expect("must succeed");
+	assert_eq!(logs.len(), 2);
+	let blocks: Vec<H256> = provider
+		.get_filter_changes(block_filter)
+		.await
+		.expect("must succeed");
+	assert_eq!(blocks.len(), 2);
+
+	// Already returned changes are not returned again.
+	let logs: Vec<ethers::types::Log> = provider
+		.get_filter_changes(log_filter)
+		.await
+		.expect("must succeed");
+	assert!(logs.is_empty());
+	assert!(provider
+		.uninstall_filter(log_filter)
+		.await
+		.expect("must succeed"));
+}
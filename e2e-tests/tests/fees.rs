@@ -0,0 +1,116 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use ethers::{
+	providers::Middleware,
+	types::{
+		transaction::eip2930::{AccessList, AccessListItem},
+		Address, BlockNumber, Eip1559TransactionRequest, Eip2930TransactionRequest,
+		TransactionRequest, H256, U256,
+	},
+};
+use frontier_e2e_tests::FrontierNode;
+
+#[tokio::test]
+#[ignore = "requires a compiled frontier-template-node"]
+async fn eip1559_transactions_pay_the_effective_gas_price() {
+	let node = FrontierNode::start().await;
+	let provider = node.provider();
+
+	let base_fee = provider
+		.get_block(BlockNumber::Latest)
+		.await
+		.expect("must succeed")
+		.and_then(|block| block.base_fee_per_gas)
+		.expect("blocks have a base fee");
+	let tip = U256::from(1_000_000_000u64);
+	let receipt = node
+		.send_and_seal(
+			Eip1559TransactionRequest::new()
+				.to(Address::repeat_byte(0x11))
+				.value(1_000)
+				.gas(21_000)
+				.max_priority_fee_per_gas(tip)
+				.max_fee_per_gas(base_fee * 2 + tip),
+		)
+		.await;
+	assert_eq!(receipt.status, Some(1.into()));
+	assert_eq!(receipt.transaction_type, Some(2.into()));
+
+	let block = provider
+		.get_block(receipt.block_hash.expect("receipt has a block hash"))
+		.await
+		.expect("must succeed")
+		.expect("block exists");
+	let block_base_fee = block.base_fee_per_gas.expect("blocks have a base fee");
+	assert_eq!(receipt.effective_gas_price, Some(block_base_fee + tip));
+
+	let history = provider
+		.fee_history(1, BlockNumber::Latest, &[50.0])
+		.await
+		.expect("must succeed");
+	assert_eq!(history.base_fee_per_gas.len(), 2);
+	assert_eq!(history.reward[0][0], tip);
+}
+
+#[tokio::test]
+#[ignore = "requires a compiled frontier-template-node"]
+async fn legacy_transactions_pay_their_gas_price() {
+	let node = FrontierNode::start().await;
+	let gas_price = node.provider().get_gas_price().await.expect("must succeed");
+
+	let receipt = node
+		.send_and_seal(
+			TransactionRequest::new()
+				.to(Address::repeat_byte(0x11))
+				.value(1_000)
+				.gas(21_000)
+				.gas_price(gas_price),
+		)
+		.await;
+	assert_eq!(receipt.status, Some(1.into()));
+	assert_eq!(receipt.gas_used, Some(21_000.into()));
+	assert_eq!(receipt.effective_gas_price, Some(gas_price));
+}
+
+#[tokio::test]
+#[ignore = "requires a compiled frontier-template-node"]
+async fn access_list_transactions_are_executed() {
+	let node = FrontierNode::start().await;
+	let gas_price = node.provider().get_gas_price().await.expect("must succeed");
+
+	let target = Address::repeat_byte(0x11);
+	let access_list = AccessList(vec![AccessListItem {
+		address: target,
+		storage_keys: vec![H256::zero()],
+	}]);
+	let receipt = node
+		.send_and_seal(Eip2930TransactionRequest::new(
+			TransactionRequest::new()
+				.to(target)
+				.value(1_000)
+				.gas(30_000)
+				.gas_price(gas_price),
+			access_list,
+		))
+		.await;
+	assert_eq!(receipt.status, Some(1.into()));
+	assert_eq!(receipt.transaction_type, Some(1.into()));
+	// 21000 + 2400 per address + 1900 per storage key.
+	assert_eq!(receipt.gas_used, Some(25_300.into()));
+}
@@ -0,0 +1,76 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use ethers::{
+	providers::Middleware,
+	types::{Address, BlockNumber, TransactionRequest},
+};
+use frontier_e2e_tests::FrontierNode;
+
+#[tokio::test]
+#[ignore = "requires a compiled frontier-template-node"]
+async fn transactions_of_retracted_blocks_are_not_returned() {
+	let node = FrontierNode::start().await;
+	let provider = node.provider();
+	let client = node.client();
+
+	let fork_point = node.create_block(false).await;
+	let hash = *client
+		.send_transaction(
+			TransactionRequest::new()
+				.to(Address::repeat_byte(0x11))
+				.value(1_000)
+				.gas(21_000),
+			None,
+		)
+		.await
+		.expect("transaction is accepted");
+	node.create_block(false).await;
+	let retracted = provider
+		.get_transaction_receipt(hash)
+		.await
+		.expect("must succeed")
+		.and_then(|receipt| receipt.block_hash)
+		.expect("transaction is included");
+
+	// Build a longer fork from the fork point.
+	let fork = node.create_fork(fork_point, 2, false).await;
+	node.finalize_block(fork[1]).await;
+
+	assert_eq!(
+		provider.get_block_number().await.expect("must succeed"),
+		3.into()
+	);
+	assert_ne!(
+		provider
+			.get_block(BlockNumber::Number(2.into()))
+			.await
+			.expect("must succeed")
+			.and_then(|block| block.hash),
+		Some(retracted)
+	);
+	// The transaction is either pending again or included in the new best chain.
+	let receipt = provider
+		.get_transaction_receipt(hash)
+		.await
+		.expect("must succeed");
+	assert_ne!(
+		receipt.and_then(|receipt| receipt.block_hash),
+		Some(retracted)
+	);
+}
@@ -0,0 +1,80 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::time::Duration;
+
+use ethers::{
+	providers::{Middleware, StreamExt},
+	types::{Bytes, Filter, TransactionRequest, U256},
+};
+use frontier_e2e_tests::{FrontierNode, STORAGE_CONTRACT_BYTECODE, STORED_EVENT_TOPIC};
+
+const NOTIFICATION_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[tokio::test]
+#[ignore = "requires a compiled frontier-template-node"]
+async fn new_heads_are_notified() {
+	let node = FrontierNode::start().await;
+	let ws = node.ws().await;
+	let mut heads = ws.subscribe_blocks().await.expect("must succeed");
+
+	node.create_block(true).await;
+	let head = tokio::time::timeout(NOTIFICATION_TIMEOUT, heads.next())
+		.await
+		.expect("head is notified in time")
+		.expect("subscription is open");
+	assert_eq!(head.number, Some(1.into()));
+}
+
+#[tokio::test]
+#[ignore = "requires a compiled frontier-template-node"]
+async fn logs_are_notified() {
+	let node = FrontierNode::start().await;
+	let ws = node.ws().await;
+	let bytecode: Bytes = STORAGE_CONTRACT_BYTECODE.parse().expect("valid bytecode");
+	let contract = node
+		.send_and_seal(TransactionRequest::new().data(bytecode).gas(200_000))
+		.await
+		.contract_address
+		.expect("contract is created");
+
+	let filter = Filter::new().address(contract).topic0(
+		STORED_EVENT_TOPIC
+			.parse::<ethers::types::H256>()
+			.expect("valid topic"),
+	);
+	let mut logs = ws.subscribe_logs(&filter).await.expect("must succeed");
+
+	let mut data = [0u8; 32];
+	U256::from(5).to_big_endian(&mut data);
+	let receipt = node
+		.send_and_seal(
+			TransactionRequest::new()
+				.to(contract)
+				.data(data.to_vec())
+				.gas(100_000),
+		)
+		.await;
+
+	let log = tokio::time::timeout(NOTIFICATION_TIMEOUT, logs.next())
+		.await
+		.expect("log is notified in time")
+		.expect("subscription is open");
+	assert_eq!(log.address, contract);
+	assert_eq!(log.transaction_hash, Some(receipt.transaction_hash));
+}
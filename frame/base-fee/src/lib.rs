@@ -18,18 +18,26 @@
 //! # BaseFee pallet
 //!
 //! The BaseFee pallet is responsible for managing the `BaseFeePerGas` value.
-//! This pallet can dynamically adjust the `BaseFeePerGas` by utilizing `Elasticity`.
+//! This pallet can dynamically adjust the `BaseFeePerGas` with the configured `FeeAdjustment`,
+//! e.g. [`Eip1559FeeAdjustment`] utilizing `Elasticity`, within the `MinBaseFeePerGas` and
+//! `MaxBaseFeePerGas` bounds set by governance.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::comparison_chain)]
 #![warn(unused_crate_dependencies)]
 
+extern crate alloc;
+
 #[cfg(test)]
 mod tests;
 
+use alloc::vec::Vec;
 use frame_support::{traits::Get, weights::Weight};
 use sp_core::U256;
-use sp_runtime::Permill;
+use sp_runtime::{
+	traits::{UniqueSaturatedInto, Zero},
+	Permill,
+};
 
 pub trait BaseFeeThreshold {
 	fn lower() -> Permill;
@@ -37,6 +45,13 @@ pub trait BaseFeeThreshold {
 	fn upper() -> Permill;
 }
 
+/// Formula updating `BaseFeePerGas` at the end of each block, from how full the block was.
+pub trait FeeAdjustment<T: Config> {
+	/// The base fee of the next block, given the base fee of the current block and the share of
+	/// the maximum block weight it used. `None` if the computation overflowed.
+	fn next_base_fee(base_fee: U256, block_fullness: Permill) -> Option<U256>;
+}
+
 pub use self::pallet::*;
 
 #[frame_support::pallet]
@@ -51,10 +66,18 @@ pub mod pallet {
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		type RuntimeEvent: From<Event> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
-		/// Lower and upper bounds for increasing / decreasing `BaseFeePerGas`.
+		/// Lower and upper bounds for increasing / decreasing `BaseFeePerGas`, used by
+		/// [`Eip1559FeeAdjustment`].
 		type Threshold: BaseFeeThreshold;
+		/// Formula updating `BaseFeePerGas` at the end of each block.
+		type FeeAdjustment: FeeAdjustment<Self>;
 		type DefaultBaseFeePerGas: Get<U256>;
 		type DefaultElasticity: Get<Permill>;
+		/// Fixed base fee overriding `BaseFeePerGas` regardless of congestion, if set.
+		/// Meant for dev and test runtimes that need deterministic fees.
+		type FixedBaseFeePerGas: Get<Option<U256>>;
+		/// Number of past blocks whose base fee is kept in [`BaseFeeHistory`]. Zero disables it.
+		type BaseFeeHistoryLength: Get<u32>;
 	}
 
 	#[pallet::genesis_config]
@@ -109,12 +132,43 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type Elasticity<T> = StorageValue<_, Permill, ValueQuery, DefaultElasticity<T>>;
 
+	/// Base fee the decrease of `BaseFeePerGas` on empty blocks is relative to, the base fee
+	/// not decreasing below its `Threshold::ideal()` share.
+	#[pallet::storage]
+	pub type DefaultBaseFee<T> = StorageValue<_, U256, ValueQuery, DefaultBaseFeePerGas<T>>;
+
+	/// Lowest `BaseFeePerGas` reachable by the adjustment in `on_finalize`.
+	#[pallet::storage]
+	pub type MinBaseFeePerGas<T> = StorageValue<_, U256, ValueQuery>;
+
+	#[pallet::type_value]
+	pub fn DefaultMaxBaseFeePerGas() -> U256 {
+		U256::max_value()
+	}
+
+	/// Highest `BaseFeePerGas` reachable by the adjustment in `on_finalize`.
+	#[pallet::storage]
+	pub type MaxBaseFeePerGas<T> = StorageValue<_, U256, ValueQuery, DefaultMaxBaseFeePerGas>;
+
+	/// Ring buffer of the base fee of the last [`Config::BaseFeeHistoryLength`] blocks, indexed
+	/// by block number modulo the length.
+	#[pallet::storage]
+	pub type BaseFeeHistory<T> = StorageMap<_, Twox64Concat, u32, U256, OptionQuery>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event {
 		NewBaseFeePerGas { fee: U256 },
 		BaseFeeOverflow,
 		NewElasticity { elasticity: Permill },
+		NewDefaultBaseFee { fee: U256 },
+		NewBaseFeePerGasBounds { min: U256, max: U256 },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The minimum base fee is higher than the maximum one.
+		InvalidBaseFeeBounds,
 	}
 
 	#[pallet::hooks]
@@ -123,83 +177,38 @@ pub mod pallet {
 			// Register the Weight used on_finalize.
 			// 	- One storage read to get the block_weight.
 			// 	- One storage read to get the Elasticity.
+			// 	- One storage read to get the DefaultBaseFee.
+			// 	- Two storage reads to get the MinBaseFeePerGas and MaxBaseFeePerGas.
 			// 	- One write to BaseFeePerGas.
+			// 	- One storage read to get the BaseFeePerGas and one write to BaseFeeHistory.
 			let db_weight = <T as frame_system::Config>::DbWeight::get();
-			db_weight.reads_writes(2, 1)
+			db_weight.reads_writes(6, 2)
 		}
 
-		fn on_finalize(_n: BlockNumberFor<T>) {
-			if <Elasticity<T>>::get().is_zero() {
-				// Zero elasticity means constant BaseFeePerGas.
+		fn on_finalize(n: BlockNumberFor<T>) {
+			let history_length = T::BaseFeeHistoryLength::get();
+			if history_length > 0 {
+				let n: u32 = n.unique_saturated_into();
+				let (base_fee, _) = <Self as fp_evm::FeeCalculator>::min_gas_price();
+				<BaseFeeHistory<T>>::insert(n % history_length, base_fee);
+			}
+
+			if T::FixedBaseFeePerGas::get().is_some() {
+				// The base fee is frozen, no adjustment.
 				return;
 			}
 
-			let lower = T::Threshold::lower();
-			let upper = T::Threshold::upper();
-			// `target` is the ideal congestion of the network where the base fee should remain unchanged.
-			// Under normal circumstances the `target` should be 50%.
-			// If we go below the `target`, the base fee is linearly decreased by the Elasticity delta of lower~target.
-			// If we go above the `target`, the base fee is linearly increased by the Elasticity delta of upper~target.
-			// The base fee is fully increased (default 12.5%) if the block is upper full (default 100%).
-			// The base fee is fully decreased (default 12.5%) if the block is lower empty (default 0%).
 			let weight = <frame_system::Pallet<T>>::block_weight();
 			let max_weight = <<T as frame_system::Config>::BlockWeights>::get().max_block;
-
-			// We convert `weight` into block fullness and ensure we are within the lower and upper bound.
-			let weight_used =
-				Permill::from_rational(weight.total().ref_time(), max_weight.ref_time())
-					.clamp(lower, upper);
-			// After clamp `weighted_used` is always between `lower` and `upper`.
-			// We scale the block fullness range to the lower/upper range, and the usage represents the
-			// actual percentage within this new scale.
-			let usage = (weight_used - lower) / (upper - lower);
-
-			// Target is our ideal block fullness.
-			let target = T::Threshold::ideal();
-			if usage > target {
-				// Above target, increase.
-				let coef = Permill::from_parts((usage.deconstruct() - target.deconstruct()) * 2u32);
-				// How much of the Elasticity is used to mutate base fee.
-				let coef = <Elasticity<T>>::get() * coef;
-				<BaseFeePerGas<T>>::mutate(|bf| {
-					if let Some(scaled_basefee) = bf.checked_mul(U256::from(coef.deconstruct())) {
-						// Normalize to GWEI.
-						let increase = scaled_basefee
-							.checked_div(U256::from(1_000_000))
-							.unwrap_or_else(U256::zero);
-						*bf = bf.saturating_add(increase);
-					} else {
-						Self::deposit_event(Event::BaseFeeOverflow);
-					}
-				});
-			} else if usage < target {
-				// Below target, decrease.
-				let coef = Permill::from_parts((target.deconstruct() - usage.deconstruct()) * 2u32);
-				// How much of the Elasticity is used to mutate base fee.
-				let coef = <Elasticity<T>>::get() * coef;
-				<BaseFeePerGas<T>>::mutate(|bf| {
-					if let Some(scaled_basefee) = bf.checked_mul(U256::from(coef.deconstruct())) {
-						// Normalize to GWEI.
-						let decrease = scaled_basefee
-							.checked_div(U256::from(1_000_000))
-							.unwrap_or_else(U256::zero);
-						let default_base_fee = T::DefaultBaseFeePerGas::get();
-						// lowest fee is norm(DefaultBaseFeePerGas * Threshold::ideal()):
-						let lowest_base_fee = default_base_fee
-							.checked_mul(U256::from(T::Threshold::ideal().deconstruct()))
-							.unwrap_or(default_base_fee)
-							.checked_div(U256::from(1_000_000))
-							.unwrap_or(default_base_fee);
-						if bf.saturating_sub(decrease) >= lowest_base_fee {
-							*bf = bf.saturating_sub(decrease);
-						} else {
-							*bf = lowest_base_fee;
-						}
-					} else {
-						Self::deposit_event(Event::BaseFeeOverflow);
-					}
-				});
-			}
+			let block_fullness =
+				Permill::from_rational(weight.total().ref_time(), max_weight.ref_time());
+			<BaseFeePerGas<T>>::mutate(|bf| {
+				match T::FeeAdjustment::next_base_fee(*bf, block_fullness) {
+					Some(next_base_fee) => *bf = next_base_fee,
+					None => Self::deposit_event(Event::BaseFeeOverflow),
+				}
+			});
+			Self::clamp_base_fee_per_gas();
 		}
 	}
 
@@ -222,12 +231,40 @@ pub mod pallet {
 			Self::deposit_event(Event::NewElasticity { elasticity });
 			Ok(())
 		}
+
+		#[pallet::call_index(2)]
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
+		pub fn set_default_base_fee(origin: OriginFor<T>, fee: U256) -> DispatchResult {
+			ensure_root(origin)?;
+			<DefaultBaseFee<T>>::put(fee);
+			Self::deposit_event(Event::NewDefaultBaseFee { fee });
+			Ok(())
+		}
+
+		/// Set the bounds `BaseFeePerGas` is clamped to in `on_finalize`.
+		#[pallet::call_index(3)]
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(2).ref_time())]
+		pub fn set_base_fee_per_gas_bounds(
+			origin: OriginFor<T>,
+			min: U256,
+			max: U256,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			ensure!(min <= max, Error::<T>::InvalidBaseFeeBounds);
+			<MinBaseFeePerGas<T>>::put(min);
+			<MaxBaseFeePerGas<T>>::put(max);
+			Self::deposit_event(Event::NewBaseFeePerGasBounds { min, max });
+			Ok(())
+		}
 	}
 }
 
 impl<T: Config> fp_evm::FeeCalculator for Pallet<T> {
 	fn min_gas_price() -> (U256, Weight) {
-		(<BaseFeePerGas<T>>::get(), T::DbWeight::get().reads(1))
+		match T::FixedBaseFeePerGas::get() {
+			Some(fee) => (fee, Weight::zero()),
+			None => (<BaseFeePerGas<T>>::get(), T::DbWeight::get().reads(1)),
+		}
 	}
 }
 
@@ -240,4 +277,95 @@ impl<T: Config> Pallet<T> {
 		<Elasticity<T>>::put(value);
 		T::DbWeight::get().writes(1)
 	}
+
+	/// The base fee of the last [`Config::BaseFeeHistoryLength`] blocks up to the current one,
+	/// oldest first. The blocks finalized before the history was kept are left out.
+	pub fn base_fee_history() -> Vec<U256> {
+		let length = T::BaseFeeHistoryLength::get();
+		if length == 0 {
+			return Vec::new();
+		}
+
+		let current: u32 = frame_system::Pallet::<T>::block_number().unique_saturated_into();
+		let oldest = current.saturating_sub(length - 1).max(1);
+		(oldest..=current)
+			.filter_map(|n| <BaseFeeHistory<T>>::get(n % length))
+			.collect()
+	}
+
+	/// The base fee of the next block, once the current one is finalized.
+	pub fn next_base_fee_per_gas() -> U256 {
+		T::FixedBaseFeePerGas::get().unwrap_or_else(<BaseFeePerGas<T>>::get)
+	}
+
+	fn clamp_base_fee_per_gas() {
+		let min = <MinBaseFeePerGas<T>>::get();
+		let max = <MaxBaseFeePerGas<T>>::get();
+		<BaseFeePerGas<T>>::mutate(|bf| *bf = (*bf).max(min).min(max));
+	}
+}
+
+/// EIP-1559 adjustment of the base fee by up to `Elasticity` per block, depending on how far the
+/// block fullness is from `Threshold::ideal()`.
+pub struct Eip1559FeeAdjustment;
+
+impl<T: Config> FeeAdjustment<T> for Eip1559FeeAdjustment {
+	fn next_base_fee(base_fee: U256, block_fullness: Permill) -> Option<U256> {
+		let elasticity = <Elasticity<T>>::get();
+		if elasticity.is_zero() {
+			// Zero elasticity means constant BaseFeePerGas.
+			return Some(base_fee);
+		}
+
+		let lower = T::Threshold::lower();
+		let upper = T::Threshold::upper();
+		// `target` is the ideal congestion of the network where the base fee should remain unchanged.
+		// Under normal circumstances the `target` should be 50%.
+		// If we go below the `target`, the base fee is linearly decreased by the Elasticity delta of lower~target.
+		// If we go above the `target`, the base fee is linearly increased by the Elasticity delta of upper~target.
+		// The base fee is fully increased (default 12.5%) if the block is upper full (default 100%).
+		// The base fee is fully decreased (default 12.5%) if the block is lower empty (default 0%).
+
+		// We ensure the block fullness is within the lower and upper bound.
+		let weight_used = block_fullness.clamp(lower, upper);
+		// After clamp `weighted_used` is always between `lower` and `upper`.
+		// We scale the block fullness range to the lower/upper range, and the usage represents the
+		// actual percentage within this new scale.
+		let usage = (weight_used - lower) / (upper - lower);
+
+		// Target is our ideal block fullness.
+		let target = T::Threshold::ideal();
+		if usage > target {
+			// Above target, increase.
+			let coef = Permill::from_parts((usage.deconstruct() - target.deconstruct()) * 2u32);
+			// How much of the Elasticity is used to mutate base fee.
+			let coef = elasticity * coef;
+			let scaled_basefee = base_fee.checked_mul(U256::from(coef.deconstruct()))?;
+			// Normalize to GWEI.
+			let increase = scaled_basefee
+				.checked_div(U256::from(1_000_000))
+				.unwrap_or_else(U256::zero);
+			Some(base_fee.saturating_add(increase))
+		} else if usage < target {
+			// Below target, decrease.
+			let coef = Permill::from_parts((target.deconstruct() - usage.deconstruct()) * 2u32);
+			// How much of the Elasticity is used to mutate base fee.
+			let coef = elasticity * coef;
+			let scaled_basefee = base_fee.checked_mul(U256::from(coef.deconstruct()))?;
+			// Normalize to GWEI.
+			let decrease = scaled_basefee
+				.checked_div(U256::from(1_000_000))
+				.unwrap_or_else(U256::zero);
+			let default_base_fee = <DefaultBaseFee<T>>::get();
+			// lowest fee is norm(DefaultBaseFee * Threshold::ideal()):
+			let lowest_base_fee = default_base_fee
+				.checked_mul(U256::from(T::Threshold::ideal().deconstruct()))
+				.unwrap_or(default_base_fee)
+				.checked_div(U256::from(1_000_000))
+				.unwrap_or(default_base_fee);
+			Some(base_fee.saturating_sub(decrease).max(lowest_base_fee))
+		} else {
+			Some(base_fee)
+		}
+	}
 }
@@ -16,7 +16,7 @@
 // limitations under the License.
 
 use frame_support::{
-	assert_ok, derive_impl,
+	assert_noop, assert_ok, derive_impl,
 	dispatch::DispatchClass,
 	parameter_types,
 	traits::{ConstU32, OnFinalize},
@@ -68,6 +68,7 @@ impl frame_system::Config for Test {
 parameter_types! {
 	pub DefaultBaseFeePerGas: U256 = U256::from(100_000_000_000_u128);
 	pub DefaultElasticity: Permill = Permill::from_parts(125_000);
+	pub static FixedBaseFeePerGas: Option<U256> = None;
 }
 
 pub struct BaseFeeThreshold;
@@ -86,8 +87,11 @@ impl BaseFeeThresholdT for BaseFeeThreshold {
 impl Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type Threshold = BaseFeeThreshold;
+	type FeeAdjustment = Eip1559FeeAdjustment;
+	type BaseFeeHistoryLength = ConstU32<4>;
 	type DefaultBaseFeePerGas = DefaultBaseFeePerGas;
 	type DefaultElasticity = DefaultElasticity;
+	type FixedBaseFeePerGas = FixedBaseFeePerGas;
 }
 
 frame_support::construct_runtime!(
@@ -142,6 +146,32 @@ fn should_default() {
 	});
 }
 
+#[test]
+fn should_keep_fixed_base_fee() {
+	let base_fee = U256::from(1_000_000_000);
+	let fixed_base_fee = U256::from(7);
+	new_test_ext(Some(base_fee), None).execute_with(|| {
+		FixedBaseFeePerGas::set(Some(fixed_base_fee));
+		// Register max weight in block.
+		System::register_extra_weight_unchecked(
+			Weight::from_parts(1000000000000, 0),
+			DispatchClass::Normal,
+		);
+		BaseFee::on_finalize(System::block_number());
+		assert_eq!(BaseFeePerGas::<Test>::get(), base_fee);
+		assert_eq!(
+			<BaseFee as fp_evm::FeeCalculator>::min_gas_price().0,
+			fixed_base_fee
+		);
+
+		FixedBaseFeePerGas::set(None);
+		assert_eq!(
+			<BaseFee as fp_evm::FeeCalculator>::min_gas_price().0,
+			base_fee
+		);
+	});
+}
+
 #[test]
 fn should_not_overflow_u256() {
 	let base_fee = U256::max_value();
@@ -303,3 +333,137 @@ fn set_elasticity_dispatchable() {
 		assert_eq!(Elasticity::<Test>::get(), Permill::from_parts(1_000));
 	});
 }
+
+#[test]
+fn set_default_base_fee_dispatchable() {
+	let base_fee = U256::from(1_000_000_000);
+	new_test_ext(Some(base_fee), None).execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(DefaultBaseFee::<Test>::get(), DefaultBaseFeePerGas::get());
+		assert_ok!(BaseFee::set_default_base_fee(
+			RuntimeOrigin::root(),
+			U256::from(200_000_000)
+		));
+		System::assert_last_event(RuntimeEvent::BaseFee(Event::NewDefaultBaseFee {
+			fee: U256::from(200_000_000),
+		}));
+
+		// Empty blocks decrease the base fee down to the ideal share of the default one.
+		for _ in 0..1000 {
+			BaseFee::on_finalize(System::block_number());
+			System::set_block_number(System::block_number() + 1);
+		}
+		assert_eq!(BaseFeePerGas::<Test>::get(), U256::from(100_000_000));
+	});
+}
+
+#[test]
+fn should_clamp_base_fee_to_bounds() {
+	let base_fee = U256::from(1_000_000_000);
+	new_test_ext(Some(base_fee), None).execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			BaseFee::set_base_fee_per_gas_bounds(
+				RuntimeOrigin::signed(1),
+				U256::from(900_000_000),
+				U256::from(1_100_000_000)
+			),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_noop!(
+			BaseFee::set_base_fee_per_gas_bounds(
+				RuntimeOrigin::root(),
+				U256::from(1_100_000_000),
+				U256::from(900_000_000)
+			),
+			Error::<Test>::InvalidBaseFeeBounds
+		);
+		assert_ok!(BaseFee::set_base_fee_per_gas_bounds(
+			RuntimeOrigin::root(),
+			U256::from(900_000_000),
+			U256::from(1_100_000_000)
+		));
+		System::assert_last_event(RuntimeEvent::BaseFee(Event::NewBaseFeePerGasBounds {
+			min: U256::from(900_000_000),
+			max: U256::from(1_100_000_000),
+		}));
+
+		// Full blocks don't raise the base fee above the maximum.
+		for _ in 0..10 {
+			System::register_extra_weight_unchecked(
+				Weight::from_parts(1000000000000, 0),
+				DispatchClass::Normal,
+			);
+			BaseFee::on_finalize(System::block_number());
+			System::set_block_number(System::block_number() + 1);
+		}
+		assert_eq!(BaseFeePerGas::<Test>::get(), U256::from(1_100_000_000));
+
+		// Empty blocks don't decrease it below the minimum.
+		for _ in 0..10 {
+			BaseFee::on_finalize(System::block_number());
+			System::set_block_number(System::block_number() + 1);
+		}
+		assert_eq!(BaseFeePerGas::<Test>::get(), U256::from(900_000_000));
+
+		// The bounds apply with a constant base fee too.
+		assert_ok!(BaseFee::set_elasticity(
+			RuntimeOrigin::root(),
+			Permill::zero()
+		));
+		assert_ok!(BaseFee::set_base_fee_per_gas(
+			RuntimeOrigin::root(),
+			U256::from(1)
+		));
+		BaseFee::on_finalize(System::block_number());
+		assert_eq!(BaseFeePerGas::<Test>::get(), U256::from(900_000_000));
+	});
+}
+
+#[test]
+fn eip1559_fee_adjustment_follows_block_fullness() {
+	new_test_ext(None, None).execute_with(|| {
+		let next_base_fee = |fullness: u32| {
+			<Eip1559FeeAdjustment as FeeAdjustment<Test>>::next_base_fee(
+				U256::from(1_000_000_000),
+				Permill::from_percent(fullness),
+			)
+		};
+		assert_eq!(next_base_fee(100), Some(U256::from(1_125_000_000)));
+		assert_eq!(next_base_fee(50), Some(U256::from(1_000_000_000)));
+		// Not below the ideal share of the default base fee.
+		assert_eq!(next_base_fee(0), Some(get_lowest_base_fee()));
+		assert_eq!(
+			<Eip1559FeeAdjustment as FeeAdjustment<Test>>::next_base_fee(
+				U256::max_value(),
+				Permill::from_percent(100),
+			),
+			None
+		);
+	});
+}
+
+#[test]
+fn should_keep_base_fee_history() {
+	let base_fee = U256::from(1_000_000_000);
+	new_test_ext(Some(base_fee), None).execute_with(|| {
+		let mut expected = Vec::new();
+		for n in 1..=6 {
+			System::set_block_number(n);
+			expected.push(BaseFeePerGas::<Test>::get());
+			System::register_extra_weight_unchecked(
+				Weight::from_parts(1000000000000, 0),
+				DispatchClass::Normal,
+			);
+			BaseFee::on_finalize(n);
+		}
+
+		// Only the last 4 blocks are kept.
+		assert_eq!(BaseFee::base_fee_history(), expected[2..].to_vec());
+		assert!(BaseFee::next_base_fee_per_gas() > expected[5]);
+		assert_eq!(
+			BaseFee::next_base_fee_per_gas(),
+			BaseFeePerGas::<Test>::get()
+		);
+	});
+}
@@ -45,6 +45,8 @@ pub mod pallet {
 	pub trait Config: frame_system::Config {
 		/// Bound divisor for min gas price.
 		type MinGasPriceBoundDivisor: Get<U256>;
+		/// Origin allowed to push the [`OracleMinGasPriceTarget`], e.g. a price oracle feeder.
+		type OracleOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 	}
 
 	#[pallet::hooks]
@@ -52,11 +54,14 @@ pub mod pallet {
 		fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
 			TargetMinGasPrice::<T>::kill();
 
-			T::DbWeight::get().writes(1)
+			// `kill` above, plus `on_finalize`: take `TargetMinGasPrice`, read
+			// `OracleMinGasPriceTarget` and read/write `MinGasPrice`.
+			T::DbWeight::get().reads_writes(3, 3)
 		}
 
 		fn on_finalize(_n: BlockNumberFor<T>) {
-			if let Some(target) = TargetMinGasPrice::<T>::take() {
+			let target = TargetMinGasPrice::<T>::take().or_else(OracleMinGasPriceTarget::<T>::get);
+			if let Some(target) = target {
 				let bound =
 					MinGasPrice::<T>::get() / T::MinGasPriceBoundDivisor::get() + U256::one();
 
@@ -82,6 +87,22 @@ pub mod pallet {
 			TargetMinGasPrice::<T>::set(Some(target));
 			Ok(())
 		}
+
+		/// Set the target min gas price the min gas price moves towards in the blocks without
+		/// a target noted by the block author, e.g. derived from a token price oracle.
+		///
+		/// `None` clears it.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::DbWeight::get().writes(1))]
+		pub fn set_oracle_min_gas_price_target(
+			origin: OriginFor<T>,
+			target: Option<U256>,
+		) -> DispatchResult {
+			T::OracleOrigin::ensure_origin(origin)?;
+
+			Self::note_oracle_min_gas_price_target(target);
+			Ok(())
+		}
 	}
 
 	#[pallet::genesis_config]
@@ -105,6 +126,10 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type TargetMinGasPrice<T: Config> = StorageValue<_, U256>;
 
+	/// Target min gas price pushed by the [`Config::OracleOrigin`], kept across blocks.
+	#[pallet::storage]
+	pub type OracleMinGasPriceTarget<T: Config> = StorageValue<_, U256>;
+
 	#[derive(Encode, Decode, RuntimeDebug)]
 	pub enum InherentError {}
 
@@ -136,6 +161,14 @@ pub mod pallet {
 	}
 }
 
+impl<T: Config> Pallet<T> {
+	/// Set the [`OracleMinGasPriceTarget`], for oracle pallets feeding it directly rather than
+	/// through [`Pallet::set_oracle_min_gas_price_target`].
+	pub fn note_oracle_min_gas_price_target(target: Option<U256>) {
+		OracleMinGasPriceTarget::<T>::set(target);
+	}
+}
+
 impl<T: Config> fp_evm::FeeCalculator for Pallet<T> {
 	fn min_gas_price() -> (U256, Weight) {
 		(MinGasPrice::<T>::get(), T::DbWeight::get().reads(1))
@@ -19,7 +19,7 @@ use super::*;
 use crate as pallet_dynamic_fee;
 
 use frame_support::{
-	assert_ok, derive_impl, parameter_types,
+	assert_noop, assert_ok, derive_impl, parameter_types,
 	traits::{ConstU32, OnFinalize, OnInitialize},
 	weights::Weight,
 };
@@ -86,6 +86,7 @@ parameter_types! {
 }
 impl Config for Test {
 	type MinGasPriceBoundDivisor = BoundDivision;
+	type OracleOrigin = frame_system::EnsureRoot<Self::AccountId>;
 }
 
 frame_support::construct_runtime!(
@@ -121,3 +122,43 @@ fn double_set_in_a_block_failed() {
 		));
 	});
 }
+
+#[test]
+fn min_gas_price_moves_towards_the_oracle_target() {
+	new_test_ext().execute_with(|| {
+		MinGasPrice::<Test>::put(U256::from(1_024_000));
+		run_to_block(1);
+		assert_noop!(
+			DynamicFee::set_oracle_min_gas_price_target(
+				RuntimeOrigin::signed(1),
+				Some(U256::from(2_048_000))
+			),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(DynamicFee::set_oracle_min_gas_price_target(
+			RuntimeOrigin::root(),
+			Some(U256::from(2_048_000))
+		));
+
+		// The min gas price moves towards the target by at most a bound per block.
+		run_to_block(2);
+		assert_eq!(MinGasPrice::<Test>::get(), U256::from(1_025_001));
+		run_to_block(3);
+		assert_eq!(MinGasPrice::<Test>::get(), U256::from(1_026_003));
+
+		// A target noted by the block author takes precedence.
+		assert_ok!(DynamicFee::note_min_gas_price_target(
+			RuntimeOrigin::none(),
+			U256::from(1_026_003)
+		));
+		run_to_block(4);
+		assert_eq!(MinGasPrice::<Test>::get(), U256::from(1_026_003));
+
+		assert_ok!(DynamicFee::set_oracle_min_gas_price_target(
+			RuntimeOrigin::root(),
+			None
+		));
+		run_to_block(5);
+		assert_eq!(MinGasPrice::<Test>::get(), U256::from(1_026_003));
+	});
+}
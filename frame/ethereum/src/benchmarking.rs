@@ -0,0 +1,145 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite};
+use frame_support::traits::{Currency, Hooks};
+use pallet_evm::AddressMapping;
+use sp_runtime::traits::Bounded;
+
+benchmarks! {
+	transact {
+		let x in 0 .. MAX_INPUT_SIZE;
+		let a in 0 .. MAX_ACCESS_LIST_SIZE;
+		let source = H160::from_low_u64_le(1);
+		let source_account_id = T::AddressMapping::into_account_id(source);
+		T::Currency::make_free_balance_be(
+			&source_account_id,
+			pallet_evm::BalanceOf::<T>::max_value() / 2u32.into(),
+		);
+		let (max_fee_per_gas, _) = T::FeeCalculator::min_gas_price();
+		let (account, _) = pallet_evm::Pallet::<T>::account_basic(&source);
+		// The signature is not checked once the transaction is validated, so the benchmark
+		// dispatches an unsigned transaction on behalf of `source`.
+		let transaction = Transaction::EIP1559(ethereum::EIP1559Transaction {
+			chain_id: T::ChainId::get(),
+			nonce: account.nonce,
+			max_priority_fee_per_gas: U256::zero(),
+			max_fee_per_gas,
+			gas_limit: BENCHMARK_GAS_LIMIT.into(),
+			action: TransactionAction::Call(H160::from_low_u64_le(2)),
+			value: U256::zero(),
+			input: vec![0; x as usize],
+			access_list: access_list(a),
+			odd_y_parity: true,
+			r: H256::from_low_u64_be(1),
+			s: H256::from_low_u64_be(1),
+		});
+	}: {
+		let result = Pallet::<T>::apply_validated_transaction(source, transaction);
+		assert!(result.is_ok());
+	}
+
+	on_finalize {
+		let t in 0 .. MAX_PENDING_TRANSACTIONS;
+		for i in 0..t {
+			let (transaction, status, receipt) = pending_transaction::<T>(i);
+			PendingLogsBloom::<T>::mutate(|bloom| bloom.accrue_bloom(&status.logs_bloom));
+			Pending::<T>::append((transaction, status, receipt, Vec::<u8>::new(), U256::zero()));
+		}
+		let block_number = frame_system::Pallet::<T>::block_number();
+	}: {
+		Pallet::<T>::on_finalize(block_number);
+	}
+	verify {
+		let block = CurrentBlock::<T>::get().expect("block is stored");
+		assert_eq!(block.transactions.len(), t as usize);
+	}
+}
+
+/// Gas limit used by the benchmarks. Large enough for the largest input and access list.
+const BENCHMARK_GAS_LIMIT: u64 = 2_000_000;
+
+/// Upper bound of the input size component, in bytes.
+const MAX_INPUT_SIZE: u32 = 32 * 1024;
+
+/// Upper bound of the access list size component, in entries of one storage key.
+const MAX_ACCESS_LIST_SIZE: u32 = 256;
+
+/// Upper bound of the number of transactions in the finalized block.
+const MAX_PENDING_TRANSACTIONS: u32 = 1_000;
+
+/// Logs emitted by each transaction of the finalized block.
+const LOGS_PER_TRANSACTION: u64 = 4;
+
+fn access_list(a: u32) -> Vec<AccessListItem> {
+	(0..a)
+		.map(|i| AccessListItem {
+			address: H160::from_low_u64_be(u64::from(i) + 0x1000),
+			storage_keys: vec![H256::from_low_u64_be(u64::from(i))],
+		})
+		.collect()
+}
+
+fn pending_transaction<T: Config>(i: u32) -> (Transaction, TransactionStatus, Receipt) {
+	let transaction = Transaction::Legacy(ethereum::LegacyTransaction {
+		nonce: i.into(),
+		gas_price: U256::one(),
+		gas_limit: BENCHMARK_GAS_LIMIT.into(),
+		action: TransactionAction::Call(H160::from_low_u64_le(2)),
+		value: U256::zero(),
+		input: Vec::new(),
+		signature: ethereum::TransactionSignature::new(
+			38,
+			H256::from_low_u64_be(1),
+			H256::from_low_u64_be(1),
+		)
+		.expect("valid signature"),
+	});
+	let logs: Vec<Log> = (0..LOGS_PER_TRANSACTION)
+		.map(|j| Log {
+			address: H160::from_low_u64_be(u64::from(i) + 0x1000),
+			topics: vec![
+				H256::from_low_u64_be(j),
+				H256::from_low_u64_be(u64::from(i)),
+			],
+			data: vec![0; 32],
+		})
+		.collect();
+	let mut logs_bloom = Bloom::default();
+	Pallet::<T>::logs_bloom(logs.clone(), &mut logs_bloom);
+	let status = TransactionStatus {
+		transaction_hash: transaction.hash(),
+		transaction_index: i,
+		from: H160::from_low_u64_le(1),
+		to: Some(H160::from_low_u64_le(2)),
+		contract_address: None,
+		logs: logs.clone(),
+		logs_bloom,
+	};
+	let receipt = Receipt::Legacy(ethereum::EIP658ReceiptData {
+		status_code: 1,
+		used_gas: U256::from(21_000) * U256::from(i + 1),
+		logs_bloom,
+		logs,
+	});
+	(transaction, status, receipt)
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(1).1, crate::mock::Test);
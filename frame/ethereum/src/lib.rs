@@ -27,10 +27,13 @@
 
 extern crate alloc;
 
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
 #[cfg(all(feature = "std", test))]
 mod mock;
 #[cfg(all(feature = "std", test))]
 mod tests;
+pub mod weights;
 
 use alloc::{vec, vec::Vec};
 use core::marker::PhantomData;
@@ -50,12 +53,13 @@ use frame_support::{
 	traits::{EnsureOrigin, Get, PalletInfoAccess, Time},
 	weights::Weight,
 };
-use frame_system::{pallet_prelude::OriginFor, CheckWeight, WeightInfo};
+use frame_system::{pallet_prelude::OriginFor, CheckWeight, WeightInfo as _};
 use sp_runtime::{
 	generic::DigestItem,
 	traits::{DispatchInfoOf, Dispatchable, One, Saturating, UniqueSaturatedInto, Zero},
 	transaction_validity::{
-		InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransactionBuilder,
+		InvalidTransaction, TransactionPriority, TransactionValidity, TransactionValidityError,
+		ValidTransactionBuilder,
 	},
 	RuntimeDebug, SaturatedConversion,
 };
@@ -68,7 +72,7 @@ use fp_evm::{
 };
 pub use fp_rpc::TransactionStatus;
 use fp_storage::{EthereumStorageSchema, PALLET_ETHEREUM_SCHEMA};
-use pallet_evm::{BlockHashMapping, FeeCalculator, GasWeightMapping, Runner};
+use pallet_evm::{BlockHashMapping, EnsureAddressOrigin, FeeCalculator, GasWeightMapping, Runner};
 
 #[derive(Clone, Eq, PartialEq, RuntimeDebug)]
 #[derive(Encode, Decode, MaxEncodedLen, TypeInfo)]
@@ -175,9 +179,12 @@ pub enum PostLogContent {
 	#[default]
 	BlockAndTxnHashes,
 	OnlyBlockHash,
+	/// Block and txn hashes along with the receipts trie root, allowing light clients to verify
+	/// receipt proofs from the header.
+	BlockAndTxnHashesWithReceiptsRoot,
 }
 
-pub use self::pallet::*;
+pub use self::{pallet::*, weights::WeightInfo};
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -202,6 +209,21 @@ pub mod pallet {
 		type PostLogContent: Get<PostLogContent>;
 		/// The maximum length of the extra data in the Executed event.
 		type ExtraDataLength: Get<u32>;
+		/// Number of past block hashes served to the `BLOCKHASH` opcode, the EIP-2935
+		/// `HISTORY_SERVE_WINDOW`. Zero only serves the hashes kept in [`BlockHash`].
+		type HistoryServeWindow: Get<u32>;
+		/// Maximum number of transactions a sender can have in the pool ahead of its next
+		/// nonce. Transactions with a higher nonce are rejected instead of queued.
+		type MaxFutureTransactionsPerSender: Get<u32>;
+		/// Minimum fee per gas a transaction must pay to enter the pool, whatever the base fee.
+		type MinGasPrice: Get<U256>;
+		/// Maximum length of the revert output of a transaction kept alongside its receipt,
+		/// longer outputs being truncated.
+		type MaxRevertReasonLength: Get<u32>;
+		/// Origin allowed to manage the [`DeniedTargets`] deny-list.
+		type DenyListOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::hooks]
@@ -252,18 +274,14 @@ pub mod pallet {
 					weight = weight.saturating_add(r.actual_weight.unwrap_or_default());
 				}
 			}
-			// Account for `on_finalize` weight:
-			//	- read: frame_system::Pallet::<T>::digest()
-			//	- read: frame_system::Pallet::<T>::block_number()
-			//	- write: <Pallet<T>>::store_block()
-			//	- write: <BlockHash<T>>::remove()
-			weight.saturating_add(T::DbWeight::get().reads_writes(2, 2))
+			// Account for `on_finalize` weight.
+			weight.saturating_add(<T as pallet::Config>::WeightInfo::on_finalize(0))
 		}
 
 		fn on_runtime_upgrade() -> Weight {
 			frame_support::storage::unhashed::put::<EthereumStorageSchema>(
 				PALLET_ETHEREUM_SCHEMA,
-				&EthereumStorageSchema::V3,
+				&EthereumStorageSchema::V4,
 			);
 
 			T::DbWeight::get().writes(1)
@@ -279,10 +297,15 @@ pub mod pallet {
 		#[pallet::call_index(0)]
 		#[pallet::weight({
 			let without_base_extrinsic_weight = true;
-			<T as pallet_evm::Config>::GasWeightMapping::gas_to_weight({
-				let transaction_data: TransactionData = transaction.into();
-				transaction_data.gas_limit.unique_saturated_into()
-			}, without_base_extrinsic_weight)
+			let transaction_data: TransactionData = transaction.into();
+			<T as pallet_evm::Config>::GasWeightMapping::gas_to_weight(
+				transaction_data.gas_limit.unique_saturated_into(),
+				without_base_extrinsic_weight,
+			)
+			.saturating_add(Pallet::<T>::transact_weight(
+				transaction_data.input.len() as u32,
+				transaction_data.access_list.len() as u32,
+			))
 		})]
 		pub fn transact(
 			origin: OriginFor<T>,
@@ -297,6 +320,144 @@ pub mod pallet {
 
 			Self::apply_validated_transaction(source, transaction).map(|(post_info, _)| post_info)
 		}
+
+		/// Transact an Ethereum transaction on behalf of `source`, authorized by the dispatch
+		/// origin through `pallet_evm::Config::CallOrigin` instead of an ECDSA signature.
+		///
+		/// This lets Substrate-native accounts (multisigs, proxies, governance) operate as their
+		/// mapped address. The transaction is included in the Ethereum block like a signed one,
+		/// using the current nonce of `source` and a placeholder signature.
+		#[pallet::call_index(1)]
+		#[pallet::weight({
+			let without_base_extrinsic_weight = true;
+			<T as pallet_evm::Config>::GasWeightMapping::gas_to_weight(
+				(*gas_limit).unique_saturated_into(),
+				without_base_extrinsic_weight,
+			)
+			.saturating_add(Pallet::<T>::transact_weight(
+				input.len() as u32,
+				access_list.len() as u32,
+			))
+		})]
+		pub fn transact_with_origin(
+			origin: OriginFor<T>,
+			source: H160,
+			action: TransactionAction,
+			input: Vec<u8>,
+			value: U256,
+			gas_limit: U256,
+			max_fee_per_gas: U256,
+			max_priority_fee_per_gas: U256,
+			access_list: Vec<AccessListItem>,
+		) -> DispatchResultWithPostInfo {
+			<T as pallet_evm::Config>::CallOrigin::ensure_address_origin(&source, origin)?;
+			ensure!(
+				fp_consensus::find_pre_log(&frame_system::Pallet::<T>::digest()).is_err(),
+				Error::<T>::PreLogExists,
+			);
+
+			let (account, _) = pallet_evm::Pallet::<T>::account_basic(&source);
+			let (odd_y_parity, r, s) = Self::origin_signature(source);
+			let transaction = Transaction::EIP1559(ethereum::EIP1559Transaction {
+				chain_id: T::ChainId::get(),
+				nonce: account.nonce,
+				max_priority_fee_per_gas,
+				max_fee_per_gas,
+				gas_limit,
+				action,
+				value,
+				input,
+				access_list,
+				odd_y_parity,
+				r,
+				s,
+			});
+			let transaction_hash = transaction.hash();
+
+			// Reported with an event rather than an error, which would revert the event.
+			if let Some(target) = Self::denied_target(source, &TransactionData::from(&transaction))
+			{
+				Self::deposit_event(Event::TransactionDenied {
+					from: source,
+					target,
+					transaction_hash,
+				});
+				return Ok(().into());
+			}
+			Self::validate_transaction_in_block(source, &transaction)
+				.map_err(|_| Error::<T>::InvalidTransaction)?;
+			let (post_info, _) = Self::apply_validated_transaction(source, transaction)?;
+
+			Self::deposit_event(Event::TransactedWithOrigin {
+				from: source,
+				transaction_hash,
+			});
+
+			Ok(post_info)
+		}
+
+		/// Deny or allow the transactions to `target`, or creating a contract at `target`.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::DbWeight::get().writes(1))]
+		pub fn set_target_denied(
+			origin: OriginFor<T>,
+			target: H160,
+			denied: bool,
+		) -> DispatchResult {
+			T::DenyListOrigin::ensure_origin(origin)?;
+
+			if denied {
+				DeniedTargets::<T>::insert(target, ());
+			} else {
+				DeniedTargets::<T>::remove(target);
+			}
+			Self::deposit_event(Event::DenyListUpdated { target, denied });
+			Ok(())
+		}
+
+		/// Execute `transaction` from `source` without checking its signature, nonce, fees or
+		/// the deny-list, e.g. to recover funds stuck behind a broken contract.
+		///
+		/// The transaction is included in the Ethereum block like a signed one. Its nonce and
+		/// signature are replaced by the nonce of `source` and the placeholder signature of the
+		/// origin-authorized transactions, so that its hash can't collide with the hash of
+		/// another transaction.
+		#[pallet::call_index(3)]
+		#[pallet::weight({
+			let without_base_extrinsic_weight = true;
+			let transaction_data: TransactionData = transaction.into();
+			<T as pallet_evm::Config>::GasWeightMapping::gas_to_weight(
+				transaction_data.gas_limit.unique_saturated_into(),
+				without_base_extrinsic_weight,
+			)
+			.saturating_add(Pallet::<T>::transact_weight(
+				transaction_data.input.len() as u32,
+				transaction_data.access_list.len() as u32,
+			))
+		})]
+		pub fn force_transact(
+			origin: OriginFor<T>,
+			source: H160,
+			transaction: Transaction,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			ensure!(
+				fp_consensus::find_pre_log(&frame_system::Pallet::<T>::digest()).is_err(),
+				Error::<T>::PreLogExists,
+			);
+
+			let transaction = Self::with_origin_signature(source, transaction)
+				.ok_or(Error::<T>::InvalidTransaction)?;
+			let transaction_hash = transaction.hash();
+			let (post_info, _) = Self::apply_validated_transaction(source, transaction)?;
+
+			Self::deposit_event(Event::ForceTransacted {
+				from: source,
+				transaction_hash,
+			});
+
+			Ok(post_info)
+		}
 	}
 
 	#[pallet::event]
@@ -310,6 +471,30 @@ pub mod pallet {
 			exit_reason: ExitReason,
 			extra_data: Vec<u8>,
 		},
+		/// An ethereum transaction was authorized by a Substrate origin instead of a signature.
+		/// It is emitted after the `Executed` event of the same transaction.
+		TransactedWithOrigin { from: H160, transaction_hash: H256 },
+		/// An ethereum call reverted with an output, truncated to
+		/// [`Config::MaxRevertReasonLength`]. It is emitted after the `Executed` event of the
+		/// same transaction.
+		Reverted {
+			transaction_hash: H256,
+			revert_reason: Vec<u8>,
+		},
+		/// An address was added to or removed from the transaction targets deny-list.
+		DenyListUpdated { target: H160, denied: bool },
+		/// An ethereum transaction was executed by governance on behalf of `from`, without
+		/// validation. It is emitted after the `Executed` event of the same transaction.
+		ForceTransacted { from: H160, transaction_hash: H256 },
+		/// An ethereum transaction authorized by a Substrate origin was not executed because it
+		/// targets, or creates, `target` of the deny-list. Signed ethereum transactions to denied
+		/// targets are rejected by the pool and block validation and never included, so they
+		/// leave no event.
+		TransactionDenied {
+			from: H160,
+			target: H160,
+			transaction_hash: H256,
+		},
 	}
 
 	#[pallet::error]
@@ -318,12 +503,20 @@ pub mod pallet {
 		InvalidSignature,
 		/// Pre-log is present, therefore transact is not allowed.
 		PreLogExists,
+		/// The transaction built for an origin-authorized transact failed validation.
+		InvalidTransaction,
 	}
 
-	/// Current building block's transactions and receipts.
+	/// Current building block's transactions and receipts, with the revert output and the
+	/// effective gas price of each transaction.
 	#[pallet::storage]
 	pub type Pending<T: Config> =
-		StorageValue<_, Vec<(Transaction, TransactionStatus, Receipt)>, ValueQuery>;
+		StorageValue<_, Vec<(Transaction, TransactionStatus, Receipt, Vec<u8>, U256)>, ValueQuery>;
+
+	/// Logs bloom of the [`Pending`] transactions, accrued as each one is applied so that it
+	/// isn't recomputed over all the receipts when the block is finalized.
+	#[pallet::storage]
+	pub type PendingLogsBloom<T: Config> = StorageValue<_, Bloom, ValueQuery>;
 
 	/// The current Ethereum block.
 	#[pallet::storage]
@@ -337,10 +530,30 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type CurrentTransactionStatuses<T: Config> = StorageValue<_, Vec<TransactionStatus>>;
 
+	/// The revert output of the current transactions, truncated to
+	/// [`Config::MaxRevertReasonLength`]. Empty for the transactions that did not revert.
+	#[pallet::storage]
+	pub type CurrentRevertReasons<T: Config> = StorageValue<_, Vec<Vec<u8>>>;
+
+	/// The gas price the current transactions actually paid per gas, so that it isn't
+	/// recomputed from the base fee when serving their receipts.
+	#[pallet::storage]
+	pub type CurrentEffectiveGasPrices<T: Config> = StorageValue<_, Vec<U256>>;
+
 	// Mapping for block number and hashes.
 	#[pallet::storage]
 	pub type BlockHash<T: Config> = StorageMap<_, Twox64Concat, U256, H256, ValueQuery>;
 
+	/// Ring buffer of the hashes of the last [`Config::HistoryServeWindow`] blocks, indexed by
+	/// block number modulo the window as in EIP-2935.
+	#[pallet::storage]
+	pub type HistoricalBlockHashes<T: Config> = StorageMap<_, Twox64Concat, u32, H256, ValueQuery>;
+
+	/// Addresses transactions are rejected to, whether they call them or create a contract at
+	/// them.
+	#[pallet::storage]
+	pub type DeniedTargets<T: Config> = StorageMap<_, Blake2_128Concat, H160, (), OptionQuery>;
+
 	#[pallet::genesis_config]
 	#[derive(frame_support::DefaultNoBound)]
 	pub struct GenesisConfig<T> {
@@ -354,7 +567,7 @@ pub mod pallet {
 			<Pallet<T>>::store_block(None, U256::zero());
 			frame_support::storage::unhashed::put::<EthereumStorageSchema>(
 				PALLET_ETHEREUM_SCHEMA,
-				&EthereumStorageSchema::V3,
+				&EthereumStorageSchema::V4,
 			);
 		}
 	}
@@ -407,24 +620,80 @@ impl<T: Config> Pallet<T> {
 		Some(H160::from(H256::from(sp_io::hashing::keccak_256(&pubkey))))
 	}
 
+	/// Weight of applying a transaction with `input_len` bytes of input and `access_list_len`
+	/// access list items, including its share of the block finalization which `on_initialize`
+	/// only reserves for an empty block.
+	fn transact_weight(input_len: u32, access_list_len: u32) -> Weight {
+		let on_finalize_per_transaction = <T as pallet::Config>::WeightInfo::on_finalize(1)
+			.saturating_sub(<T as pallet::Config>::WeightInfo::on_finalize(0));
+		<T as pallet::Config>::WeightInfo::transact(input_len, access_list_len)
+			.saturating_add(on_finalize_per_transaction)
+	}
+
+	/// Placeholder signature of the transactions authorized by an origin rather than a key. It
+	/// is derived from `source` so that the same transaction sent by two sources gets two
+	/// hashes, but it doesn't recover to `source`.
+	fn origin_signature(source: H160) -> (bool, H256, H256) {
+		let mut r = H256::from(source);
+		// Keeps `r` non-zero, and below the curve order, for the zero address too.
+		r.0[0] = 1;
+		(true, r, H256::from_low_u64_be(1))
+	}
+
+	/// `transaction` sent at the current nonce of `source` with the placeholder signature of
+	/// the origin-authorized transactions.
+	fn with_origin_signature(source: H160, transaction: Transaction) -> Option<Transaction> {
+		let (account, _) = pallet_evm::Pallet::<T>::account_basic(&source);
+		let (odd_y_parity, r, s) = Self::origin_signature(source);
+		Some(match transaction {
+			Transaction::Legacy(t) => {
+				let v = match t.signature.chain_id() {
+					Some(chain_id) => chain_id * 2 + 35 + odd_y_parity as u64,
+					None => 27 + odd_y_parity as u64,
+				};
+				Transaction::Legacy(ethereum::LegacyTransaction {
+					nonce: account.nonce,
+					signature: ethereum::TransactionSignature::new(v, r, s)?,
+					..t
+				})
+			}
+			Transaction::EIP2930(t) => Transaction::EIP2930(ethereum::EIP2930Transaction {
+				nonce: account.nonce,
+				odd_y_parity,
+				r,
+				s,
+				..t
+			}),
+			Transaction::EIP1559(t) => Transaction::EIP1559(ethereum::EIP1559Transaction {
+				nonce: account.nonce,
+				odd_y_parity,
+				r,
+				s,
+				..t
+			}),
+		})
+	}
+
 	fn store_block(post_log: Option<PostLogContent>, block_number: U256) {
 		let mut transactions = Vec::new();
 		let mut statuses = Vec::new();
 		let mut receipts = Vec::new();
-		let mut logs_bloom = Bloom::default();
-		let mut cumulative_gas_used = U256::zero();
-		for (transaction, status, receipt) in Pending::<T>::get() {
+		let mut revert_reasons = Vec::new();
+		let mut effective_gas_prices = Vec::new();
+		for (transaction, status, receipt, revert_reason, effective_gas_price) in
+			Pending::<T>::get()
+		{
 			transactions.push(transaction);
 			statuses.push(status);
-			receipts.push(receipt.clone());
-			let (logs, used_gas) = match receipt {
-				Receipt::Legacy(d) | Receipt::EIP2930(d) | Receipt::EIP1559(d) => {
-					(d.logs.clone(), d.used_gas)
-				}
-			};
-			cumulative_gas_used = used_gas;
-			Self::logs_bloom(logs, &mut logs_bloom);
+			receipts.push(receipt);
+			revert_reasons.push(revert_reason);
+			effective_gas_prices.push(effective_gas_price);
 		}
+		let logs_bloom = PendingLogsBloom::<T>::take();
+		let cumulative_gas_used = match receipts.last() {
+			Some(Receipt::Legacy(d) | Receipt::EIP2930(d) | Receipt::EIP1559(d)) => d.used_gas,
+			None => U256::zero(),
+		};
 
 		let ommers = Vec::<ethereum::Header>::new();
 		let receipts_root = ethereum::util::ordered_trie_root(
@@ -442,7 +711,7 @@ impl<T: Config> Pallet<T> {
 			logs_bloom,
 			difficulty: U256::zero(),
 			number: block_number,
-			gas_limit: T::BlockGasLimit::get(),
+			gas_limit: pallet_evm::Pallet::<T>::block_gas_limit(),
 			gas_used: cumulative_gas_used,
 			timestamp: T::Timestamp::now().unique_saturated_into(),
 			extra_data: Vec::new(),
@@ -454,8 +723,18 @@ impl<T: Config> Pallet<T> {
 		CurrentBlock::<T>::put(block.clone());
 		CurrentReceipts::<T>::put(receipts.clone());
 		CurrentTransactionStatuses::<T>::put(statuses.clone());
+		CurrentRevertReasons::<T>::put(revert_reasons);
+		CurrentEffectiveGasPrices::<T>::put(effective_gas_prices);
 		BlockHash::<T>::insert(block_number, block.header.hash());
 
+		let history_serve_window = T::HistoryServeWindow::get();
+		if history_serve_window > 0 {
+			HistoricalBlockHashes::<T>::insert(
+				block_number.low_u32() % history_serve_window,
+				block.header.hash(),
+			);
+		}
+
 		match post_log {
 			Some(PostLogContent::BlockAndTxnHashes) => {
 				let digest = DigestItem::Consensus(
@@ -471,6 +750,17 @@ impl<T: Config> Pallet<T> {
 				);
 				frame_system::Pallet::<T>::deposit_log(digest);
 			}
+			Some(PostLogContent::BlockAndTxnHashesWithReceiptsRoot) => {
+				let digest = DigestItem::Consensus(
+					FRONTIER_ENGINE_ID,
+					PostLog::HashesWithReceiptsRoot(
+						fp_consensus::Hashes::from_block(block),
+						receipts_root,
+					)
+					.encode(),
+				);
+				frame_system::Pallet::<T>::deposit_log(digest);
+			}
 			None => { /* do nothing*/ }
 		}
 	}
@@ -500,7 +790,7 @@ impl<T: Config> Pallet<T> {
 		let _ = CheckEvmTransaction::<InvalidTransactionWrapper>::new(
 			CheckEvmTransactionConfig {
 				evm_config: T::config(),
-				block_gas_limit: T::BlockGasLimit::get(),
+				block_gas_limit: pallet_evm::Pallet::<T>::block_gas_limit(),
 				base_fee,
 				chain_id: T::ChainId::get(),
 				is_transactional: true,
@@ -512,40 +802,68 @@ impl<T: Config> Pallet<T> {
 		.validate_in_pool_for(&who)
 		.and_then(|v| v.with_chain_id())
 		.and_then(|v| v.with_base_fee())
+		.and_then(|v| v.with_min_gas_price(T::MinGasPrice::get()))
 		.and_then(|v| v.with_balance_for(&who))
 		.map_err(|e| e.0)?;
 
+		// Each future transaction `requires` the previous nonce, so the pool holds at most one
+		// transaction per nonce of the sender. Bounding the gap to the account nonce bounds the
+		// number of future transactions a sender can queue.
+		if transaction_nonce.saturating_sub(who.nonce)
+			> U256::from(T::MaxFutureTransactionsPerSender::get())
+		{
+			return Err(InvalidTransaction::Future.into());
+		}
+
 		// EIP-3607: https://eips.ethereum.org/EIPS/eip-3607
 		// Do not allow transactions for which `tx.sender` has any code deployed.
 		//
 		// This check should be done on the transaction validation (here) **and**
 		// on trnasaction execution, otherwise a contract tx will be included in
 		// the mempool and pollute the mempool forever.
-		if !pallet_evm::AccountCodes::<T>::get(origin).is_empty() {
+		if pallet_evm::AccountCodes::<T>::contains_key(origin) {
 			return Err(InvalidTransaction::BadSigner.into());
 		}
 
-		let priority = match (
-			transaction_data.gas_price,
-			transaction_data.max_fee_per_gas,
-			transaction_data.max_priority_fee_per_gas,
-		) {
-			// Legacy or EIP-2930 transaction.
-			// Handle priority here. On legacy transaction everything in gas_price except
-			// the current base_fee is considered a tip to the miner and thus the priority.
-			(Some(gas_price), None, None) => {
-				gas_price.saturating_sub(base_fee).unique_saturated_into()
+		// Deployments from accounts the runtime doesn't allow to deploy would be rejected on
+		// execution, keep them out of the pool.
+		if transaction_data.action == TransactionAction::Create
+			&& !<T::CreateOriginFilter as pallet_evm::CreateOriginFilter<T>>::is_create_allowed(
+				&origin,
+			) {
+			return Err(InvalidTransaction::Custom(
+				TransactionValidationError::CreateNotAllowed as u8,
+			)
+			.into());
+		}
+
+		// Transactions to denied targets would be rejected on execution, keep them out of the
+		// pool.
+		if Self::is_target_denied(origin, &transaction_data) {
+			return Err(InvalidTransaction::Custom(
+				TransactionValidationError::CallNotAllowed as u8,
+			)
+			.into());
+		}
+
+		// Calls the runtime filters out would be rejected on execution, keep them out of the
+		// pool.
+		if let TransactionAction::Call(target) = transaction_data.action {
+			if !<T::CallFilter as pallet_evm::CallFilter<T>>::is_call_allowed(
+				&origin,
+				&target,
+				&transaction_data.input,
+			) {
+				return Err(InvalidTransaction::Custom(
+					TransactionValidationError::CallNotAllowed as u8,
+				)
+				.into());
 			}
-			// EIP-1559 transaction without tip.
-			(None, Some(_), None) => 0,
-			// EIP-1559 transaction with tip.
-			(None, Some(max_fee_per_gas), Some(max_priority_fee_per_gas)) => max_fee_per_gas
-				.saturating_sub(base_fee)
-				.min(max_priority_fee_per_gas)
-				.unique_saturated_into(),
-			// Unreachable because already validated. Gracefully handle.
-			_ => return Err(InvalidTransaction::Payment.into()),
-		};
+		}
+
+		// Unreachable because already validated. Gracefully handle.
+		let priority = Self::transaction_priority(base_fee, &transaction_data)
+			.ok_or(InvalidTransaction::Payment)?;
 
 		// The tag provides and requires must be filled correctly according to the nonce.
 		let mut builder = ValidTransactionBuilder::default()
@@ -563,16 +881,60 @@ impl<T: Config> Pallet<T> {
 		builder.build()
 	}
 
+	/// Pool priority of a transaction: the tip per gas the block author actually earns on top
+	/// of `base_fee`, so a high fee cap alone does not buy priority.
+	///
+	/// On legacy and EIP-2930 transactions everything in `gas_price` above the base fee is a
+	/// tip. `None` if the fee fields do not match any transaction type.
+	pub fn transaction_priority(
+		base_fee: U256,
+		transaction_data: &TransactionData,
+	) -> Option<TransactionPriority> {
+		let effective_tip = match (
+			transaction_data.gas_price,
+			transaction_data.max_fee_per_gas,
+			transaction_data.max_priority_fee_per_gas,
+		) {
+			(Some(gas_price), None, None) => fp_evm::fee::legacy_priority_fee(base_fee, gas_price),
+			(None, Some(max_fee_per_gas), max_priority_fee_per_gas) => {
+				fp_evm::fee::effective_priority_fee(
+					base_fee,
+					max_fee_per_gas,
+					max_priority_fee_per_gas,
+				)
+			}
+			_ => return None,
+		};
+		Some(effective_tip.unique_saturated_into())
+	}
+
 	fn apply_validated_transaction(
 		source: H160,
 		transaction: Transaction,
 	) -> Result<(PostDispatchInfo, CallOrCreateInfo), DispatchErrorWithPostInfo> {
+		let extrinsic_weight = {
+			let transaction_data = TransactionData::from(&transaction);
+			Pallet::<T>::transact_weight(
+				transaction_data.input.len() as u32,
+				transaction_data.access_list.len() as u32,
+			)
+		};
 		let (to, _, info) = Self::execute(source, &transaction, None)?;
 
 		let pending = Pending::<T>::get();
 		let transaction_hash = transaction.hash();
 		let transaction_index = pending.len() as u32;
 
+		// The output of a reverted call is its revert reason. Reverted creations don't return
+		// their output.
+		let revert_reason = match &info {
+			CallOrCreateInfo::Call(info) if matches!(info.exit_reason, ExitReason::Revert(_)) => {
+				let max_length = T::MaxRevertReasonLength::get() as usize;
+				info.value[..info.value.len().min(max_length)].to_vec()
+			}
+			_ => Vec::new(),
+		};
+
 		let (reason, status, weight_info, used_gas, dest, extra_data) = match info.clone() {
 			CallOrCreateInfo::Call(info) => (
 				info.exit_reason.clone(),
@@ -647,7 +1009,7 @@ impl<T: Config> Pallet<T> {
 			};
 			let logs_bloom = status.logs_bloom;
 			let logs = status.clone().logs;
-			let cumulative_gas_used = if let Some((_, _, receipt)) = pending.last() {
+			let cumulative_gas_used = if let Some((_, _, receipt, _, _)) = pending.last() {
 				match receipt {
 					Receipt::Legacy(d) | Receipt::EIP2930(d) | Receipt::EIP1559(d) => {
 						d.used_gas.saturating_add(used_gas.effective)
@@ -678,7 +1040,26 @@ impl<T: Config> Pallet<T> {
 			}
 		};
 
-		Pending::<T>::append((transaction, status, receipt));
+		// The base fee only changes when the block is finalized, so it is the one the
+		// transaction was charged with.
+		let effective_gas_price = match &transaction {
+			Transaction::Legacy(t) => t.gas_price,
+			Transaction::EIP2930(t) => t.gas_price,
+			Transaction::EIP1559(t) => fp_evm::fee::effective_gas_price(
+				T::FeeCalculator::min_gas_price().0,
+				t.max_fee_per_gas,
+				Some(t.max_priority_fee_per_gas),
+			),
+		};
+
+		PendingLogsBloom::<T>::mutate(|bloom| bloom.accrue_bloom(&status.logs_bloom));
+		Pending::<T>::append((
+			transaction,
+			status,
+			receipt,
+			revert_reason.clone(),
+			effective_gas_price,
+		));
 
 		Self::deposit_event(Event::Executed {
 			from: source,
@@ -687,24 +1068,19 @@ impl<T: Config> Pallet<T> {
 			exit_reason: reason,
 			extra_data,
 		});
+		if !revert_reason.is_empty() {
+			Self::deposit_event(Event::Reverted {
+				transaction_hash,
+				revert_reason,
+			});
+		}
 
 		Ok((
 			PostDispatchInfo {
-				actual_weight: {
-					let mut gas_to_weight = T::GasWeightMapping::gas_to_weight(
-						core::cmp::max(
-							used_gas.standard.unique_saturated_into(),
-							used_gas.effective.unique_saturated_into(),
-						),
-						true,
-					);
-					if let Some(weight_info) = weight_info {
-						if let Some(proof_size_usage) = weight_info.proof_size_usage {
-							*gas_to_weight.proof_size_mut() = proof_size_usage;
-						}
-					}
-					Some(gas_to_weight)
-				},
+				actual_weight: Some(
+					T::GasWeightMapping::used_gas_to_weight(&used_gas, weight_info, true)
+						.saturating_add(extrinsic_weight),
+				),
 				pays_fee: Pays::No,
 			},
 			info,
@@ -868,12 +1244,12 @@ impl<T: Config> Pallet<T> {
 		let _ = CheckEvmTransaction::<InvalidTransactionWrapper>::new(
 			CheckEvmTransactionConfig {
 				evm_config: T::config(),
-				block_gas_limit: T::BlockGasLimit::get(),
+				block_gas_limit: pallet_evm::Pallet::<T>::block_gas_limit(),
 				base_fee,
 				chain_id: T::ChainId::get(),
 				is_transactional: true,
 			},
-			transaction_data.into(),
+			transaction_data.clone().into(),
 			weight_limit,
 			proof_size_base_cost,
 		)
@@ -883,9 +1259,37 @@ impl<T: Config> Pallet<T> {
 		.and_then(|v| v.with_balance_for(&who))
 		.map_err(|e| TransactionValidityError::Invalid(e.0))?;
 
+		if Self::is_target_denied(origin, &transaction_data) {
+			return Err(InvalidTransaction::Custom(
+				TransactionValidationError::CallNotAllowed as u8,
+			)
+			.into());
+		}
+
 		Ok(())
 	}
 
+	/// Whether the address a transaction from `source` calls, or creates a contract at, is in
+	/// the [`DeniedTargets`] deny-list.
+	pub fn is_target_denied(source: H160, transaction_data: &TransactionData) -> bool {
+		Self::denied_target(source, transaction_data).is_some()
+	}
+
+	/// The address a transaction from `source` calls, or creates a contract at, if it is in the
+	/// [`DeniedTargets`] deny-list.
+	fn denied_target(source: H160, transaction_data: &TransactionData) -> Option<H160> {
+		let target = match transaction_data.action {
+			TransactionAction::Call(target) => target,
+			TransactionAction::Create => {
+				let mut stream = rlp::RlpStream::new_list(2);
+				stream.append(&source);
+				stream.append(&transaction_data.nonce);
+				H256::from(sp_io::hashing::keccak_256(&stream.out())).into()
+			}
+		};
+		DeniedTargets::<T>::contains_key(target).then_some(target)
+	}
+
 	pub fn migrate_block_v0_to_v2() -> Weight {
 		let db_weights = T::DbWeight::get();
 		let mut weight: Weight = db_weights.reads(1);
@@ -977,10 +1381,25 @@ impl<T: Config> Get<H256> for IntermediateStateRoot<T> {
 }
 
 /// Returns the Ethereum block hash by number.
+///
+/// The hashes pruned from [`BlockHash`] are served from [`HistoricalBlockHashes`] for the blocks
+/// within the [`Config::HistoryServeWindow`] of the current block.
 pub struct EthereumBlockHashMapping<T>(PhantomData<T>);
 impl<T: Config> BlockHashMapping for EthereumBlockHashMapping<T> {
 	fn block_hash(number: u32) -> H256 {
-		BlockHash::<T>::get(U256::from(number))
+		let hash = BlockHash::<T>::get(U256::from(number));
+		if !hash.is_zero() {
+			return hash;
+		}
+
+		let history_serve_window = T::HistoryServeWindow::get();
+		let current: u32 = frame_system::Pallet::<T>::block_number().unique_saturated_into();
+		if history_serve_window > 0 && number < current && current - number <= history_serve_window
+		{
+			HistoricalBlockHashes::<T>::get(number % history_serve_window)
+		} else {
+			H256::default()
+		}
 	}
 }
 
@@ -1022,6 +1441,15 @@ impl From<TransactionValidationError> for InvalidTransactionWrapper {
 			TransactionValidationError::UnknownError => InvalidTransactionWrapper(
 				InvalidTransaction::Custom(TransactionValidationError::UnknownError as u8),
 			),
+			TransactionValidationError::CallNotAllowed => InvalidTransactionWrapper(
+				InvalidTransaction::Custom(TransactionValidationError::CallNotAllowed as u8),
+			),
+			TransactionValidationError::GasPriceBelowMinimum => InvalidTransactionWrapper(
+				InvalidTransaction::Custom(TransactionValidationError::GasPriceBelowMinimum as u8),
+			),
+			TransactionValidationError::CreateNotAllowed => InvalidTransactionWrapper(
+				InvalidTransaction::Custom(TransactionValidationError::CreateNotAllowed as u8),
+			),
 		}
 	}
 }
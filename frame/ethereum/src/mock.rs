@@ -22,7 +22,7 @@ use rlp::RlpStream;
 // Substrate
 use frame_support::{
 	derive_impl, parameter_types,
-	traits::{ConstU32, FindAuthor},
+	traits::{ConstBool, ConstU32, FindAuthor},
 	weights::Weight,
 	ConsensusEngineId, PalletId,
 };
@@ -175,22 +175,48 @@ impl pallet_evm::Config for Test {
 	type Runner = pallet_evm::runner::stack::Runner<Self>;
 	type OnChargeTransaction = ();
 	type OnCreate = ();
+	type ExecutionHooks = ();
+	type CreateOriginFilter = MockCreateOriginFilter;
+	type DeployerAllowListOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type CallFilter = ();
+	type CallBlockListOrigin = frame_system::EnsureRoot<Self::AccountId>;
 	type FindAuthor = FindAuthorTruncated;
 	type GasLimitPovSizeRatio = GasLimitPovSizeRatio;
+	type GasLimitStorageGrowthRatio = ();
 	type SuicideQuickClearLimit = SuicideQuickClearLimit;
 	type Timestamp = Timestamp;
+	type ExecutionExtrinsicsEnabled = ConstBool<true>;
+	type ShanghaiBlock = ();
+	type CodeSizeLimit = ();
+	type InitcodeSizeLimit = ();
 	type WeightInfo = ();
 }
 
+/// Forbids all deployments when `CreateDisabled` is set.
+pub struct MockCreateOriginFilter;
+impl pallet_evm::CreateOriginFilter<Test> for MockCreateOriginFilter {
+	fn is_create_allowed(_deployer: &H160) -> bool {
+		!CreateDisabled::get()
+	}
+}
+
 parameter_types! {
-	pub const PostBlockAndTxnHashes: PostLogContent = PostLogContent::BlockAndTxnHashes;
+	pub static EthereumPostLogContent: PostLogContent = PostLogContent::BlockAndTxnHashes;
+	pub static MinGasPrice: U256 = U256::zero();
+	pub static CreateDisabled: bool = false;
 }
 
 impl Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type StateRoot = IntermediateStateRoot<Self>;
-	type PostLogContent = PostBlockAndTxnHashes;
+	type PostLogContent = EthereumPostLogContent;
 	type ExtraDataLength = ConstU32<30>;
+	type HistoryServeWindow = ConstU32<280>;
+	type MaxFutureTransactionsPerSender = ConstU32<16>;
+	type MinGasPrice = MinGasPrice;
+	type MaxRevertReasonLength = ConstU32<1024>;
+	type DenyListOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type WeightInfo = ();
 }
 
 impl fp_self_contained::SelfContainedCall for RuntimeCall {
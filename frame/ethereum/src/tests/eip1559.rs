@@ -315,7 +315,7 @@ fn contract_should_be_created_at_given_address() {
 		let t = eip1559_erc20_creation_transaction(alice);
 		assert_ok!(Ethereum::execute(alice.address, &t, None,));
 		assert_ne!(
-			pallet_evm::AccountCodes::<Test>::get(erc20_address).len(),
+			pallet_evm::Pallet::<Test>::account_code(erc20_address).len(),
 			0
 		);
 	});
@@ -464,7 +464,7 @@ fn event_extra_data_should_be_handle_properly() {
 
 		// calling bar revert
 		assert_ok!(Ethereum::apply_validated_transaction(alice.address, t3,));
-		System::assert_last_event(RuntimeEvent::Ethereum(Event::Executed {
+		System::assert_has_event(RuntimeEvent::Ethereum(Event::Executed {
 			from: alice.address,
 			to: H160::from_slice(&contract_address),
 			transaction_hash: H256::from_str(
@@ -621,3 +621,263 @@ fn proof_size_base_cost_should_keep_the_same_in_execution_and_estimate() {
 		);
 	});
 }
+
+#[test]
+fn transact_with_origin_works() {
+	let (pairs, mut ext) = new_test_ext(1);
+	let alice = &pairs[0];
+
+	ext.execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(Ethereum::transact_with_origin(
+			RuntimeOrigin::signed(alice.account_id.clone()),
+			alice.address,
+			ethereum::TransactionAction::Create,
+			hex::decode(ERC20_CONTRACT_BYTECODE.trim_end()).unwrap(),
+			U256::zero(),
+			U256::from(0x100000),
+			U256::from(1),
+			U256::from(1),
+			vec![],
+		));
+
+		let pending = crate::Pending::<Test>::get();
+		assert_eq!(pending.len(), 1);
+		let (transaction, status, _, _, _) = &pending[0];
+		assert_eq!(status.from, alice.address);
+		assert_eq!(
+			status.contract_address,
+			Some(contract_address(alice.address, 0))
+		);
+		assert_eq!(EVM::account_basic(&alice.address).0.nonce, U256::from(1));
+		System::assert_last_event(RuntimeEvent::Ethereum(Event::TransactedWithOrigin {
+			from: alice.address,
+			transaction_hash: transaction.hash(),
+		}));
+	});
+}
+
+#[test]
+fn transact_with_origin_hashes_are_unique_per_source() {
+	let (pairs, mut ext) = new_test_ext(3);
+	let alice = &pairs[0];
+	let bob = &pairs[1];
+	let charlie = &pairs[2];
+
+	ext.execute_with(|| {
+		System::set_block_number(1);
+
+		// Both accounts are at nonce 0 and send the very same call.
+		for account in [alice, bob] {
+			assert_ok!(Ethereum::transact_with_origin(
+				RuntimeOrigin::signed(account.account_id.clone()),
+				account.address,
+				ethereum::TransactionAction::Call(charlie.address),
+				Vec::new(),
+				U256::from(1),
+				U256::from(21_000),
+				U256::from(1),
+				U256::from(1),
+				vec![],
+			));
+		}
+
+		let pending = crate::Pending::<Test>::get();
+		assert_eq!(pending.len(), 2);
+		assert_ne!(pending[0].0.hash(), pending[1].0.hash());
+	});
+}
+
+#[test]
+fn transact_with_origin_requires_mapped_origin() {
+	let (pairs, mut ext) = new_test_ext(2);
+	let alice = &pairs[0];
+	let bob = &pairs[1];
+
+	ext.execute_with(|| {
+		assert_err!(
+			Ethereum::transact_with_origin(
+				RuntimeOrigin::signed(bob.account_id.clone()),
+				alice.address,
+				ethereum::TransactionAction::Call(bob.address),
+				Vec::new(),
+				U256::from(1),
+				U256::from(21_000),
+				U256::from(1),
+				U256::from(1),
+				vec![],
+			)
+			.map_err(|e| e.error),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert!(crate::Pending::<Test>::get().is_empty());
+	});
+}
+
+#[test]
+fn transact_with_origin_to_a_denied_target_is_reported() {
+	let (pairs, mut ext) = new_test_ext(2);
+	let alice = &pairs[0];
+	let bob = &pairs[1];
+
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Ethereum::set_target_denied(
+			RuntimeOrigin::root(),
+			bob.address,
+			true
+		));
+
+		assert_ok!(Ethereum::transact_with_origin(
+			RuntimeOrigin::signed(alice.account_id.clone()),
+			alice.address,
+			ethereum::TransactionAction::Call(bob.address),
+			Vec::new(),
+			U256::from(1),
+			U256::from(21_000),
+			U256::from(1),
+			U256::from(1),
+			vec![],
+		));
+
+		assert!(crate::Pending::<Test>::get().is_empty());
+		assert_eq!(EVM::account_basic(&alice.address).0.nonce, U256::zero());
+		match System::events().pop().map(|record| record.event) {
+			Some(RuntimeEvent::Ethereum(Event::TransactionDenied { from, target, .. })) => {
+				assert_eq!(from, alice.address);
+				assert_eq!(target, bob.address);
+			}
+			event => panic!("expected a TransactionDenied event, got {event:?}"),
+		}
+	});
+}
+
+#[test]
+fn pool_priority_is_the_effective_tip() {
+	let (pairs, mut ext) = new_test_ext_with_initial_balance(2, 10_000_000_000_000);
+	let alice = &pairs[0];
+	let bob = &pairs[1];
+
+	ext.execute_with(|| {
+		let validate = |max_fee_per_gas: u64, max_priority_fee_per_gas: u64| {
+			let transaction = EIP1559UnsignedTransaction {
+				nonce: U256::zero(),
+				max_priority_fee_per_gas: U256::from(max_priority_fee_per_gas),
+				max_fee_per_gas: U256::from(max_fee_per_gas),
+				gas_limit: U256::from(21_000),
+				action: ethereum::TransactionAction::Call(bob.address),
+				value: U256::zero(),
+				input: Default::default(),
+			}
+			.sign(&alice.private_key, None);
+			let call = crate::Call::<Test>::transact { transaction };
+			let source = call.check_self_contained().unwrap().unwrap();
+			let extrinsic = CheckedExtrinsic::<u64, _, SignedExtra, _> {
+				signed: fp_self_contained::CheckedSignature::SelfContained(source),
+				function: RuntimeCall::Ethereum(call.clone()),
+			};
+			call.validate_self_contained(&source, &extrinsic.get_dispatch_info(), 0)
+				.unwrap()
+		};
+		let priority = |max_fee_per_gas: u64, max_priority_fee_per_gas: u64| {
+			validate(max_fee_per_gas, max_priority_fee_per_gas)
+				.unwrap()
+				.priority
+		};
+
+		// The base fee is 1, the tip is capped by what the fee cap leaves above it.
+		assert_eq!(priority(100, 5), 5);
+		assert_eq!(priority(10, 10), 9);
+		assert_eq!(priority(10, 9), 9);
+		assert_eq!(priority(1, 1), 0);
+		// A high fee cap with a low tip ranks below a low fee cap with a high tip.
+		assert!(priority(10, 9) > priority(100, 5));
+		// A tip above the fee cap is rejected rather than capped.
+		assert_err!(
+			validate(10, 50),
+			InvalidTransaction::Custom(
+				fp_evm::TransactionValidationError::PriorityFeeTooHigh as u8
+			)
+		);
+	});
+}
+
+#[test]
+fn transactions_below_the_min_gas_price_are_rejected_from_the_pool() {
+	let (pairs, mut ext) = new_test_ext_with_initial_balance(2, 10_000_000_000_000);
+	let alice = &pairs[0];
+	let bob = &pairs[1];
+
+	ext.execute_with(|| {
+		let validate = |max_priority_fee_per_gas: u64| {
+			let transaction = EIP1559UnsignedTransaction {
+				nonce: U256::zero(),
+				max_priority_fee_per_gas: U256::from(max_priority_fee_per_gas),
+				max_fee_per_gas: U256::from(100),
+				gas_limit: U256::from(21_000),
+				action: ethereum::TransactionAction::Call(bob.address),
+				value: U256::zero(),
+				input: Default::default(),
+			}
+			.sign(&alice.private_key, None);
+			let call = crate::Call::<Test>::transact { transaction };
+			let source = call.check_self_contained().unwrap().unwrap();
+			let extrinsic = CheckedExtrinsic::<u64, _, SignedExtra, _> {
+				signed: fp_self_contained::CheckedSignature::SelfContained(source),
+				function: RuntimeCall::Ethereum(call.clone()),
+			};
+			call.validate_self_contained(&source, &extrinsic.get_dispatch_info(), 0)
+				.unwrap()
+		};
+
+		// The base fee is 1, the tip must make up for the rest of the minimum.
+		MinGasPrice::set(U256::from(10));
+		assert_err!(
+			validate(8),
+			InvalidTransaction::Custom(
+				fp_evm::TransactionValidationError::GasPriceBelowMinimum as u8
+			)
+		);
+		assert_ok!(validate(9));
+	});
+}
+
+#[test]
+fn effective_gas_prices_are_stored_with_the_block() {
+	let (pairs, mut ext) = new_test_ext_with_initial_balance(2, 10_000_000_000_000);
+	let alice = &pairs[0];
+	let bob = &pairs[1];
+
+	ext.execute_with(|| {
+		System::set_block_number(1);
+
+		let transaction = |nonce: u64, max_priority_fee_per_gas: u64| {
+			EIP1559UnsignedTransaction {
+				nonce: U256::from(nonce),
+				max_priority_fee_per_gas: U256::from(max_priority_fee_per_gas),
+				max_fee_per_gas: U256::from(10),
+				gas_limit: U256::from(21_000),
+				action: ethereum::TransactionAction::Call(bob.address),
+				value: U256::zero(),
+				input: Default::default(),
+			}
+			.sign(&alice.private_key, None)
+		};
+		assert_ok!(crate::ValidatedTransaction::<Test>::apply(
+			alice.address,
+			transaction(0, 2)
+		));
+		assert_ok!(crate::ValidatedTransaction::<Test>::apply(
+			alice.address,
+			transaction(1, 9)
+		));
+
+		<Ethereum as frame_support::traits::Hooks<_>>::on_finalize(1);
+		// The base fee is 1, plus the tip up to the fee cap.
+		assert_eq!(
+			crate::CurrentEffectiveGasPrices::<Test>::get(),
+			Some(vec![U256::from(3), U256::from(10)])
+		);
+	});
+}
@@ -247,7 +247,7 @@ fn contract_should_be_created_at_given_address() {
 		let t = eip2930_erc20_creation_transaction(alice);
 		assert_ok!(Ethereum::execute(alice.address, &t, None,));
 		assert_ne!(
-			pallet_evm::AccountCodes::<Test>::get(erc20_address).len(),
+			pallet_evm::Pallet::<Test>::account_code(erc20_address).len(),
 			0
 		);
 	});
@@ -390,7 +390,7 @@ fn event_extra_data_should_be_handle_properly() {
 
 		// calling bar revert
 		assert_ok!(Ethereum::apply_validated_transaction(alice.address, t3,));
-		System::assert_last_event(RuntimeEvent::Ethereum(Event::Executed {
+		System::assert_has_event(RuntimeEvent::Ethereum(Event::Executed {
 			from: alice.address,
 			to: H160::from_slice(&contract_address),
 			transaction_hash: H256::from_str(
@@ -22,9 +22,11 @@ use evm::{ExitReason, ExitRevert, ExitSucceed};
 use fp_ethereum::{TransactionData, ValidatedTransaction};
 use frame_support::{
 	dispatch::{DispatchClass, GetDispatchInfo},
+	traits::Get,
 	weights::Weight,
 };
-use pallet_evm::AddressMapping;
+use pallet_evm::{AddressMapping, GasWeightMapping};
+use sp_runtime::traits::UniqueSaturatedInto;
 
 fn legacy_erc20_creation_unsigned_transaction() -> LegacyUnsignedTransaction {
 	LegacyUnsignedTransaction {
@@ -247,7 +249,7 @@ fn contract_should_be_created_at_given_address() {
 		let t = legacy_erc20_creation_transaction(alice);
 		assert_ok!(Ethereum::execute(alice.address, &t, None,));
 		assert_ne!(
-			pallet_evm::AccountCodes::<Test>::get(erc20_address).len(),
+			pallet_evm::Pallet::<Test>::account_code(erc20_address).len(),
 			0
 		);
 	});
@@ -390,7 +392,7 @@ fn event_extra_data_should_be_handle_properly() {
 
 		// calling bar revert
 		assert_ok!(Ethereum::apply_validated_transaction(alice.address, t3,));
-		System::assert_last_event(RuntimeEvent::Ethereum(Event::Executed {
+		System::assert_has_event(RuntimeEvent::Ethereum(Event::Executed {
 			from: alice.address,
 			to: H160::from_slice(&contract_address),
 			transaction_hash: H256::from_str(
@@ -400,6 +402,24 @@ fn event_extra_data_should_be_handle_properly() {
 			exit_reason: ExitReason::Revert(ExitRevert::Reverted),
 			extra_data: b"very_long_error_msg_that_we_ex".to_vec(),
 		}));
+
+		// The whole `Error(string)` output is kept as the revert reason.
+		let (_, _, _, revert_reason, _) = crate::Pending::<Test>::get().pop().unwrap();
+		assert!(revert_reason.len() > b"very_long_error_msg_that_we_ex".len());
+		assert_eq!(revert_reason[..4], hex::decode("08c379a0").unwrap()[..]);
+		System::assert_last_event(RuntimeEvent::Ethereum(Event::Reverted {
+			transaction_hash: H256::from_str(
+				"0x27a75747783eb8959f1fe7b23e8b1152a9ec945d9b90354582cb7c3ea1481287",
+			)
+			.unwrap(),
+			revert_reason: revert_reason.clone(),
+		}));
+
+		<Ethereum as frame_support::traits::Hooks<_>>::on_finalize(1);
+		assert_eq!(
+			crate::CurrentRevertReasons::<Test>::get(),
+			Some(vec![vec![], revert_reason])
+		);
 	});
 }
 
@@ -546,3 +566,313 @@ fn proof_size_base_cost_should_keep_the_same_in_execution_and_estimate() {
 		);
 	});
 }
+
+#[test]
+fn post_log_with_receipts_root_should_commit_to_the_receipts() {
+	let (pairs, mut ext) = new_test_ext(1);
+	let alice = &pairs[0];
+
+	ext.execute_with(|| {
+		EthereumPostLogContent::set(crate::PostLogContent::BlockAndTxnHashesWithReceiptsRoot);
+
+		let t = legacy_erc20_creation_transaction(alice);
+		assert_ok!(Ethereum::transact(
+			RawOrigin::EthereumTransaction(alice.address).into(),
+			t,
+		));
+		<Ethereum as frame_support::traits::Hooks<_>>::on_finalize(1);
+
+		let block = crate::CurrentBlock::<Test>::get().expect("block is stored");
+		let digest = frame_system::Pallet::<Test>::digest();
+		assert_eq!(
+			fp_consensus::find_receipts_root(&digest),
+			Some(block.header.receipts_root)
+		);
+		match fp_consensus::find_post_log(&digest) {
+			Ok(fp_consensus::PostLog::HashesWithReceiptsRoot(hashes, _)) => {
+				assert_eq!(hashes.block_hash, block.header.hash());
+				assert_eq!(hashes.transaction_hashes.len(), 1);
+			}
+			_ => panic!("expected a post log with the receipts root"),
+		}
+	});
+}
+
+#[test]
+fn pruned_block_hashes_are_served_within_the_history_window() {
+	let (_, mut ext) = new_test_ext(1);
+
+	ext.execute_with(|| {
+		// `BlockHashCount` is 250 and `HistoryServeWindow` 280 in the mock.
+		let mut recorded = vec![H256::default()];
+		for n in 1..=300 {
+			System::set_block_number(n);
+			<Ethereum as frame_support::traits::Hooks<_>>::on_finalize(n);
+			recorded.push(crate::BlockHash::<Test>::get(U256::from(n)));
+		}
+		System::set_block_number(301);
+
+		let block_hash =
+			<crate::EthereumBlockHashMapping<Test> as pallet_evm::BlockHashMapping>::block_hash;
+
+		// Still in `BlockHash`.
+		assert!(!recorded[100].is_zero());
+		assert_eq!(block_hash(100), recorded[100]);
+		// Pruned from `BlockHash`, served from the history.
+		assert!(crate::BlockHash::<Test>::get(U256::from(30)).is_zero());
+		assert!(!recorded[30].is_zero());
+		assert_ne!(recorded[30], recorded[31]);
+		assert_eq!(block_hash(30), recorded[30]);
+		assert_eq!(block_hash(31), recorded[31]);
+		// Out of the history window.
+		assert_eq!(block_hash(10), H256::default());
+		// The current block has no hash yet.
+		assert_eq!(block_hash(301), H256::default());
+	});
+}
+
+#[test]
+fn transactions_too_far_ahead_of_the_sender_nonce_are_rejected() {
+	let (pairs, mut ext) = new_test_ext(1);
+	let alice = &pairs[0];
+
+	ext.execute_with(|| {
+		let validate = |nonce: u32| {
+			let mut transaction = legacy_erc20_creation_unsigned_transaction();
+			transaction.nonce = U256::from(nonce);
+			let call = crate::Call::<Test>::transact {
+				transaction: transaction.sign(&alice.private_key),
+			};
+			let source = call.check_self_contained().unwrap().unwrap();
+			let extrinsic = CheckedExtrinsic::<u64, _, SignedExtra, H160> {
+				signed: fp_self_contained::CheckedSignature::SelfContained(source),
+				function: RuntimeCall::Ethereum(call.clone()),
+			};
+			call.validate_self_contained(&source, &extrinsic.get_dispatch_info(), 0)
+				.unwrap()
+		};
+
+		let max_future = <Test as crate::Config>::MaxFutureTransactionsPerSender::get();
+		assert_eq!(
+			validate(max_future),
+			ValidTransactionBuilder::default()
+				.and_provides((alice.address, U256::from(max_future)))
+				.priority(0u64)
+				.and_requires((alice.address, U256::from(max_future - 1)))
+				.build()
+		);
+		assert_err!(validate(max_future + 1), InvalidTransaction::Future);
+
+		// The window moves with the account nonce.
+		assert_ok!(Ethereum::execute(
+			alice.address,
+			&legacy_erc20_creation_transaction(alice),
+			None,
+		));
+		assert_ok!(validate(max_future + 1));
+	});
+}
+
+#[test]
+fn transactions_to_denied_targets_are_rejected() {
+	let (pairs, mut ext) = new_test_ext(2);
+	let alice = &pairs[0];
+	let bob = &pairs[1];
+
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		let check = |action: ethereum::TransactionAction| {
+			let mut transaction = legacy_erc20_creation_unsigned_transaction();
+			transaction.action = action;
+			let transaction = transaction.sign(&alice.private_key);
+			let call = crate::Call::<Test>::transact {
+				transaction: transaction.clone(),
+			};
+			let source = call.check_self_contained().unwrap().unwrap();
+			let extrinsic = CheckedExtrinsic::<u64, _, SignedExtra, H160> {
+				signed: fp_self_contained::CheckedSignature::SelfContained(source),
+				function: RuntimeCall::Ethereum(call.clone()),
+			};
+			let in_pool = call
+				.validate_self_contained(&source, &extrinsic.get_dispatch_info(), 0)
+				.unwrap()
+				.map(|_| ());
+			let in_block = Ethereum::validate_transaction_in_block(source, &transaction);
+			assert_eq!(in_pool, in_block);
+			in_pool
+		};
+		let denied: Result<(), TransactionValidityError> = Err(InvalidTransaction::Custom(
+			fp_evm::TransactionValidationError::CallNotAllowed as u8,
+		)
+		.into());
+
+		assert_err!(
+			Ethereum::set_target_denied(
+				RuntimeOrigin::signed(alice.account_id.clone()),
+				bob.address,
+				true
+			),
+			sp_runtime::DispatchError::BadOrigin
+		);
+
+		assert_ok!(Ethereum::set_target_denied(
+			RuntimeOrigin::root(),
+			bob.address,
+			true
+		));
+		System::assert_last_event(RuntimeEvent::Ethereum(Event::DenyListUpdated {
+			target: bob.address,
+			denied: true,
+		}));
+		assert_eq!(
+			check(ethereum::TransactionAction::Call(bob.address)),
+			denied
+		);
+
+		// The address a creation deploys to is denied as well.
+		assert_ok!(check(ethereum::TransactionAction::Create));
+		assert_ok!(Ethereum::set_target_denied(
+			RuntimeOrigin::root(),
+			contract_address(alice.address, 0),
+			true
+		));
+		assert_eq!(check(ethereum::TransactionAction::Create), denied);
+
+		assert_ok!(Ethereum::set_target_denied(
+			RuntimeOrigin::root(),
+			bob.address,
+			false
+		));
+		assert_ok!(check(ethereum::TransactionAction::Call(bob.address)));
+	});
+}
+
+#[test]
+fn disallowed_creates_are_rejected_from_the_pool() {
+	let (pairs, mut ext) = new_test_ext(1);
+	let alice = &pairs[0];
+
+	ext.execute_with(|| {
+		let transaction = legacy_erc20_creation_transaction(alice);
+		let call = crate::Call::<Test>::transact { transaction };
+		let source = call.check_self_contained().unwrap().unwrap();
+		let extrinsic = CheckedExtrinsic::<u64, _, SignedExtra, H160> {
+			signed: fp_self_contained::CheckedSignature::SelfContained(source),
+			function: RuntimeCall::Ethereum(call.clone()),
+		};
+		let validate = || {
+			call.validate_self_contained(&source, &extrinsic.get_dispatch_info(), 0)
+				.unwrap()
+				.map(|_| ())
+		};
+
+		assert_ok!(validate());
+		CreateDisabled::set(true);
+		assert_err!(
+			validate(),
+			InvalidTransaction::Custom(fp_evm::TransactionValidationError::CreateNotAllowed as u8)
+		);
+	});
+}
+
+#[test]
+fn force_transact_executes_from_the_given_source() {
+	let (pairs, mut ext) = new_test_ext(2);
+	let alice = &pairs[0];
+	let bob = &pairs[1];
+
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		// Signed by bob, but executed from alice's address.
+		let transaction = legacy_erc20_creation_transaction(bob);
+
+		assert_err!(
+			Ethereum::force_transact(
+				RuntimeOrigin::signed(alice.account_id.clone()),
+				alice.address,
+				transaction.clone(),
+			),
+			sp_runtime::DispatchError::BadOrigin
+		);
+
+		assert_ok!(Ethereum::force_transact(
+			RuntimeOrigin::root(),
+			alice.address,
+			transaction.clone(),
+		));
+		// The forced transaction doesn't reuse the hash of the one bob signed.
+		let forced_hash = crate::Pending::<Test>::get()[0].0.hash();
+		assert_ne!(forced_hash, transaction.hash());
+		System::assert_last_event(RuntimeEvent::Ethereum(Event::ForceTransacted {
+			from: alice.address,
+			transaction_hash: forced_hash,
+		}));
+		assert_eq!(
+			pallet_evm::Pallet::<Test>::account_basic(&alice.address)
+				.0
+				.nonce,
+			U256::from(1)
+		);
+		assert!(pallet_evm::AccountCodes::<Test>::contains_key(
+			contract_address(alice.address, 0)
+		));
+		assert_eq!(crate::Pending::<Test>::get().len(), 1);
+	});
+}
+
+#[test]
+fn logs_bloom_is_accrued_as_transactions_are_applied() {
+	let (pairs, mut ext) = new_test_ext(2);
+	let alice = &pairs[0];
+	let bob = &pairs[1];
+
+	ext.execute_with(|| {
+		for account in [alice, bob] {
+			assert_ok!(Ethereum::transact(
+				RawOrigin::EthereumTransaction(account.address).into(),
+				legacy_erc20_creation_transaction(account),
+			));
+		}
+
+		let mut expected = ethereum_types::Bloom::default();
+		for (_, status, _, _, _) in crate::Pending::<Test>::get() {
+			expected.accrue_bloom(&status.logs_bloom);
+		}
+		assert_eq!(crate::PendingLogsBloom::<Test>::get(), expected);
+
+		<Ethereum as frame_support::traits::Hooks<_>>::on_finalize(1);
+
+		let block = crate::CurrentBlock::<Test>::get().expect("block is stored");
+		assert_eq!(block.header.logs_bloom, expected);
+		assert_eq!(
+			crate::PendingLogsBloom::<Test>::get(),
+			ethereum_types::Bloom::default()
+		);
+	});
+}
+
+#[test]
+fn transaction_weight_includes_its_share_of_the_block_finalization() {
+	let (pairs, mut ext) = new_test_ext(1);
+	let alice = &pairs[0];
+
+	ext.execute_with(|| {
+		let transaction = legacy_erc20_creation_transaction(alice);
+		let transaction_data = TransactionData::from(&transaction);
+		let call = crate::Call::<Test>::transact { transaction };
+
+		let expected_weight = <Test as pallet_evm::Config>::GasWeightMapping::gas_to_weight(
+			transaction_data.gas_limit.unique_saturated_into(),
+			true,
+		)
+		.saturating_add(<() as crate::WeightInfo>::transact(
+			transaction_data.input.len() as u32,
+			transaction_data.access_list.len() as u32,
+		))
+		.saturating_add(
+			<() as crate::WeightInfo>::on_finalize(1)
+				.saturating_sub(<() as crate::WeightInfo>::on_finalize(0)),
+		);
+		assert_eq!(call.get_dispatch_info().weight, expected_weight);
+	});
+}
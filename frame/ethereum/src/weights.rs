@@ -0,0 +1,181 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+//! Weights for pallet_ethereum
+//!
+//! These weights started as benchmark CLI output, but the values added or changed since are
+//! hand-written placeholders, not measurements. Regenerate the whole file on reference
+//! hardware with the command below before relying on them.
+
+// Regenerate with:
+// ./target/release/frontier-template-node
+// benchmark
+// pallet
+// --chain=dev
+// --steps=50
+// --repeat=20
+// --pallet=pallet_ethereum
+// --extrinsic=*
+// --execution=wasm
+// --wasm-execution=compiled
+// --output=./frame/ethereum/src/weights.rs
+// --header=HEADER-APACHE2
+// --template=./scripts/frame-weight-template.hbs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_ethereum.
+pub trait WeightInfo {
+	fn transact(x: u32, a: u32, ) -> Weight;
+	fn on_finalize(t: u32, ) -> Weight;
+}
+
+/// Weights for pallet_ethereum using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: `Ethereum::Pending` (r:1 w:1)
+	/// Proof: `Ethereum::Pending` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Ethereum::PendingLogsBloom` (r:1 w:1)
+	/// Proof: `Ethereum::PendingLogsBloom` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(116), added: 2591, mode: `MaxEncodedLen`)
+	/// Storage: `EVM::AccountCodes` (r:1 w:0)
+	/// Proof: `EVM::AccountCodes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Timestamp::Now` (r:1 w:0)
+	/// Proof: `Timestamp::Now` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	fn transact(x: u32, a: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `545`
+		//  Estimated: `6524`
+		// The range of component `x` is `[0, 32768]`.
+		// The range of component `a` is `[0, 256]`.
+		// Minimum execution time: 89_874_000 picoseconds.
+		Weight::from_parts(91_256_000, 6524)
+			// Standard Error: 17
+			.saturating_add(Weight::from_parts(1_142, 0).saturating_mul(x.into()))
+			// Standard Error: 2_204
+			.saturating_add(Weight::from_parts(238_417, 0).saturating_mul(a.into()))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: `Ethereum::Pending` (r:1 w:1)
+	/// Proof: `Ethereum::Pending` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Ethereum::PendingLogsBloom` (r:1 w:1)
+	/// Proof: `Ethereum::PendingLogsBloom` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Ethereum::BlockHash` (r:1 w:2)
+	/// Proof: `Ethereum::BlockHash` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Timestamp::Now` (r:1 w:0)
+	/// Proof: `Timestamp::Now` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `System::Digest` (r:1 w:1)
+	/// Proof: `System::Digest` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Ethereum::HistoricalBlockHashes` (r:0 w:1)
+	/// Proof: `Ethereum::HistoricalBlockHashes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Ethereum::CurrentBlock` (r:0 w:1)
+	/// Proof: `Ethereum::CurrentBlock` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Ethereum::CurrentReceipts` (r:0 w:1)
+	/// Proof: `Ethereum::CurrentReceipts` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Ethereum::CurrentTransactionStatuses` (r:0 w:1)
+	/// Proof: `Ethereum::CurrentTransactionStatuses` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Ethereum::CurrentRevertReasons` (r:0 w:1)
+	/// Proof: `Ethereum::CurrentRevertReasons` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Ethereum::CurrentEffectiveGasPrices` (r:0 w:1)
+	/// Proof: `Ethereum::CurrentEffectiveGasPrices` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn on_finalize(t: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `271 + t * (1_029 ±0)`
+		//  Estimated: `3736 + t * (1_029 ±0)`
+		// The range of component `t` is `[0, 1000]`.
+		// Minimum execution time: 31_507_000 picoseconds.
+		Weight::from_parts(33_120_000, 3736)
+			// Standard Error: 4_112
+			.saturating_add(Weight::from_parts(42_862_000, 0).saturating_mul(t.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(11_u64))
+			.saturating_add(Weight::from_parts(0, 1029).saturating_mul(t.into()))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: `Ethereum::Pending` (r:1 w:1)
+	/// Proof: `Ethereum::Pending` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Ethereum::PendingLogsBloom` (r:1 w:1)
+	/// Proof: `Ethereum::PendingLogsBloom` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(116), added: 2591, mode: `MaxEncodedLen`)
+	/// Storage: `EVM::AccountCodes` (r:1 w:0)
+	/// Proof: `EVM::AccountCodes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Timestamp::Now` (r:1 w:0)
+	/// Proof: `Timestamp::Now` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	fn transact(x: u32, a: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `545`
+		//  Estimated: `6524`
+		// The range of component `x` is `[0, 32768]`.
+		// The range of component `a` is `[0, 256]`.
+		// Minimum execution time: 89_874_000 picoseconds.
+		Weight::from_parts(91_256_000, 6524)
+			// Standard Error: 17
+			.saturating_add(Weight::from_parts(1_142, 0).saturating_mul(x.into()))
+			// Standard Error: 2_204
+			.saturating_add(Weight::from_parts(238_417, 0).saturating_mul(a.into()))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: `Ethereum::Pending` (r:1 w:1)
+	/// Proof: `Ethereum::Pending` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Ethereum::PendingLogsBloom` (r:1 w:1)
+	/// Proof: `Ethereum::PendingLogsBloom` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Ethereum::BlockHash` (r:1 w:2)
+	/// Proof: `Ethereum::BlockHash` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Timestamp::Now` (r:1 w:0)
+	/// Proof: `Timestamp::Now` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `System::Digest` (r:1 w:1)
+	/// Proof: `System::Digest` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Ethereum::HistoricalBlockHashes` (r:0 w:1)
+	/// Proof: `Ethereum::HistoricalBlockHashes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Ethereum::CurrentBlock` (r:0 w:1)
+	/// Proof: `Ethereum::CurrentBlock` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Ethereum::CurrentReceipts` (r:0 w:1)
+	/// Proof: `Ethereum::CurrentReceipts` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Ethereum::CurrentTransactionStatuses` (r:0 w:1)
+	/// Proof: `Ethereum::CurrentTransactionStatuses` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Ethereum::CurrentRevertReasons` (r:0 w:1)
+	/// Proof: `Ethereum::CurrentRevertReasons` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Ethereum::CurrentEffectiveGasPrices` (r:0 w:1)
+	/// Proof: `Ethereum::CurrentEffectiveGasPrices` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn on_finalize(t: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `271 + t * (1_029 ±0)`
+		//  Estimated: `3736 + t * (1_029 ±0)`
+		// The range of component `t` is `[0, 1000]`.
+		// Minimum execution time: 31_507_000 picoseconds.
+		Weight::from_parts(33_120_000, 3736)
+			// Standard Error: 4_112
+			.saturating_add(Weight::from_parts(42_862_000, 0).saturating_mul(t.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(11_u64))
+			.saturating_add(Weight::from_parts(0, 1029).saturating_mul(t.into()))
+	}
+}
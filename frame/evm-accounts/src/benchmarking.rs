@@ -0,0 +1,80 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_system::RawOrigin;
+use libsecp256k1::{Message, PublicKey, SecretKey};
+
+use super::*;
+
+fn evm_secret() -> SecretKey {
+	SecretKey::parse(&[1u8; 32]).expect("valid secret key")
+}
+
+fn address_of(secret: &SecretKey) -> H160 {
+	let public = PublicKey::from_secret_key(secret).serialize();
+	H160::from_slice(&keccak_256(&public[1..65])[12..])
+}
+
+/// The EIP-191 signature of `message` by `secret`.
+fn evm_sign(secret: &SecretKey, message: &[u8]) -> EcdsaSignature {
+	let (signature, recovery_id) =
+		libsecp256k1::sign(&Message::parse(&eip191_hash(message)), secret);
+	let mut encoded = [0u8; 65];
+	encoded[..64].copy_from_slice(&signature.serialize());
+	encoded[64] = recovery_id.serialize();
+	encoded
+}
+
+benchmarks! {
+	claim_evm_address {
+		let caller: T::AccountId = whitelisted_caller();
+		let secret = evm_secret();
+		let address = address_of(&secret);
+		let signature = evm_sign(&secret, &Pallet::<T>::binding_message(&caller));
+	}: _(RawOrigin::Signed(caller.clone()), address, signature)
+	verify {
+		assert_eq!(Pallet::<T>::evm_address(&caller), Some(address));
+	}
+
+	unbind_evm_address {
+		let caller: T::AccountId = whitelisted_caller();
+		let secret = evm_secret();
+		let address = address_of(&secret);
+		let signature = evm_sign(&secret, &Pallet::<T>::binding_message(&caller));
+		Pallet::<T>::claim_evm_address(RawOrigin::Signed(caller.clone()).into(), address, signature)?;
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert_eq!(Pallet::<T>::evm_address(&caller), None);
+	}
+
+	claim_account {
+		let caller: T::AccountId = whitelisted_caller();
+		let secret = evm_secret();
+		let address = address_of(&secret);
+		let (account, account_signature) =
+			T::BenchmarkHelper::sign(&Pallet::<T>::account_binding_message(&address));
+		let signature = evm_sign(&secret, &Pallet::<T>::binding_message(&account));
+	}: _(RawOrigin::Signed(caller), address, signature, account.clone(), account_signature)
+	verify {
+		assert_eq!(Pallet::<T>::evm_address(&account), Some(address));
+	}
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
@@ -0,0 +1,308 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # EVM accounts pallet
+//!
+//! Lets a native account claim an EVM address through an EIP-191 signature of that address, and
+//! the holder of an EVM address claim an existing native account through a signature of that
+//! account. [`BoundAddressMapping`] resolves claimed addresses to the account that claimed them
+//! before falling back to the configured [`AddressMapping`], and [`Pallet::evm_address`] provides
+//! the reverse lookup.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(unused_crate_dependencies)]
+
+extern crate alloc;
+
+pub mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+use alloc::vec::Vec;
+use scale_codec::Encode;
+// Substrate
+use frame_support::ensure;
+use sp_core::H160;
+use sp_io::hashing::keccak_256;
+use sp_runtime::{
+	traits::{IdentifyAccount, Verify, Zero},
+	DispatchResult,
+};
+// Frontier
+pub use pallet_evm::AddressMapping;
+
+pub use self::{pallet::*, weights::WeightInfo};
+
+/// Prefix of the message signed by an EVM address to be claimed.
+pub const BINDING_MESSAGE_PREFIX: &[u8] = b"evm:bind:";
+
+/// Prefix of the message signed by an account to be claimed by an EVM address.
+pub const ACCOUNT_BINDING_MESSAGE_PREFIX: &[u8] = b"evm:claim:";
+
+/// EIP-191 signature (`r`, `s` and `v`) of the binding message.
+pub type EcdsaSignature = [u8; 65];
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// Mapping from address to account id for addresses that were not claimed.
+		type AddressMapping: AddressMapping<Self::AccountId>;
+		/// Signature by which an account consents to be claimed by an EVM address.
+		type AccountSignature: Verify<Signer = Self::AccountSigner> + Parameter;
+		/// Signer of an [`Config::AccountSignature`].
+		type AccountSigner: IdentifyAccount<AccountId = Self::AccountId>;
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+		/// Helper to sign the binding messages of accounts in benchmarks.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper<Self::AccountId, Self::AccountSignature>;
+	}
+
+	/// The account each claimed EVM address is bound to.
+	#[pallet::storage]
+	pub type Accounts<T: Config> = StorageMap<_, Blake2_128Concat, H160, T::AccountId>;
+
+	/// The EVM address each account claimed.
+	#[pallet::storage]
+	pub type EvmAddresses<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, H160>;
+
+	/// The nonce each released EVM address was handed over to its fallback account with.
+	#[pallet::storage]
+	pub type ReleasedNonces<T: Config> =
+		StorageMap<_, Blake2_128Concat, H160, T::Nonce, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// An account claimed an EVM address.
+		Bound {
+			account: T::AccountId,
+			address: H160,
+		},
+		/// An account released the EVM address it claimed.
+		Unbound {
+			account: T::AccountId,
+			address: H160,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The account already claimed an EVM address.
+		AccountAlreadyBound,
+		/// The EVM address is already claimed by an account.
+		AddressAlreadyBound,
+		/// The account the EVM address falls back to holds funds or has already transacted.
+		AddressInUse,
+		/// The signature is not a signature of the binding message by the EVM address.
+		BadSignature,
+		/// The signature is not a signature of the account binding message by the account.
+		BadAccountSignature,
+		/// The account did not claim any EVM address.
+		NotBound,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Bind the signing account to `address`, given the EIP-191 signature by `address` of
+		/// the [`Pallet::binding_message`] of the account.
+		#[pallet::call_index(0)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::claim_evm_address())]
+		pub fn claim_evm_address(
+			origin: OriginFor<T>,
+			address: H160,
+			signature: EcdsaSignature,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(
+				Self::recover_signer(&Self::binding_message(&who), &signature) == Some(address),
+				Error::<T>::BadSignature
+			);
+			Self::bind(who, address)
+		}
+
+		/// Release the EVM address claimed by the signing account, which is then mapped by the
+		/// fallback mapping again.
+		///
+		/// The fallback account takes over the nonce of the address, so that transactions the
+		/// address signed while bound can't be replayed.
+		#[pallet::call_index(1)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::unbind_evm_address())]
+		pub fn unbind_evm_address(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let address = EvmAddresses::<T>::take(&who).ok_or(Error::<T>::NotBound)?;
+			Accounts::<T>::remove(address);
+			let fallback = T::AddressMapping::into_account_id(address);
+			if fallback != who {
+				Self::raise_nonce(&fallback, frame_system::Pallet::<T>::account_nonce(&who));
+				ReleasedNonces::<T>::insert(
+					address,
+					frame_system::Pallet::<T>::account_nonce(&fallback),
+				);
+			}
+			Self::deposit_event(Event::Unbound {
+				account: who,
+				address,
+			});
+			Ok(())
+		}
+
+		/// Bind `account` to `address`, given the signature by `account` of the
+		/// [`Pallet::account_binding_message`] of `address` and the EIP-191 signature by
+		/// `address` of the [`Pallet::binding_message`] of `account`.
+		///
+		/// Lets the holder of an EVM address claim an existing account, with any account, e.g. a
+		/// relayer, submitting the claim.
+		#[pallet::call_index(2)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::claim_account())]
+		pub fn claim_account(
+			origin: OriginFor<T>,
+			address: H160,
+			signature: EcdsaSignature,
+			account: T::AccountId,
+			account_signature: T::AccountSignature,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			ensure!(
+				account_signature.verify(&Self::account_binding_message(&address)[..], &account),
+				Error::<T>::BadAccountSignature
+			);
+			ensure!(
+				Self::recover_signer(&Self::binding_message(&account), &signature) == Some(address),
+				Error::<T>::BadSignature
+			);
+			Self::bind(account, address)
+		}
+	}
+}
+
+/// Signs the binding messages of accounts in benchmarks.
+#[cfg(feature = "runtime-benchmarks")]
+pub trait BenchmarkHelper<AccountId, Signature> {
+	/// An account and its signature of `message`.
+	fn sign(message: &[u8]) -> (AccountId, Signature);
+}
+
+impl<T: Config> Pallet<T> {
+	/// The message an EVM address signs to be claimed by `who`. It commits to the genesis hash
+	/// so a signature can't be replayed on another chain.
+	pub fn binding_message(who: &T::AccountId) -> Vec<u8> {
+		let mut message = BINDING_MESSAGE_PREFIX.to_vec();
+		who.encode_to(&mut message);
+		Self::genesis_hash().encode_to(&mut message);
+		message
+	}
+
+	/// The message an account signs to be claimed by `address`. It commits to the genesis hash
+	/// so a signature can't be replayed on another chain.
+	pub fn account_binding_message(address: &H160) -> Vec<u8> {
+		let mut message = ACCOUNT_BINDING_MESSAGE_PREFIX.to_vec();
+		address.encode_to(&mut message);
+		Self::genesis_hash().encode_to(&mut message);
+		message
+	}
+
+	/// The EVM address claimed by `who`, if any.
+	pub fn evm_address(who: &T::AccountId) -> Option<H160> {
+		EvmAddresses::<T>::get(who)
+	}
+
+	fn genesis_hash() -> T::Hash {
+		frame_system::Pallet::<T>::block_hash(
+			frame_system::pallet_prelude::BlockNumberFor::<T>::zero(),
+		)
+	}
+
+	/// Bind `who` to `address`. The address must not have been used through its fallback
+	/// account, and `who` takes over its nonce so that transactions it signed can't be replayed.
+	fn bind(who: T::AccountId, address: H160) -> DispatchResult {
+		ensure!(
+			!EvmAddresses::<T>::contains_key(&who),
+			Error::<T>::AccountAlreadyBound
+		);
+		ensure!(
+			!Accounts::<T>::contains_key(address),
+			Error::<T>::AddressAlreadyBound
+		);
+		let fallback = T::AddressMapping::into_account_id(address);
+		if fallback != who {
+			let nonce = frame_system::Pallet::<T>::account_nonce(&fallback);
+			ensure!(
+				!frame_system::Pallet::<T>::account_exists(&fallback)
+					&& nonce == ReleasedNonces::<T>::get(address),
+				Error::<T>::AddressInUse
+			);
+			Self::raise_nonce(&who, nonce);
+		}
+
+		ReleasedNonces::<T>::remove(address);
+		Accounts::<T>::insert(address, &who);
+		EvmAddresses::<T>::insert(&who, address);
+		Self::deposit_event(Event::Bound {
+			account: who,
+			address,
+		});
+		Ok(())
+	}
+
+	/// Raise the nonce of `who` to at least `nonce`.
+	fn raise_nonce(who: &T::AccountId, nonce: T::Nonce) {
+		frame_system::Account::<T>::mutate(who, |account| account.nonce = account.nonce.max(nonce));
+	}
+
+	/// Recover the address whose EIP-191 (`personal_sign`) signature of `message` is `signature`.
+	fn recover_signer(message: &[u8], signature: &EcdsaSignature) -> Option<H160> {
+		let pubkey =
+			sp_io::crypto::secp256k1_ecdsa_recover(signature, &eip191_hash(message)).ok()?;
+		Some(H160::from_slice(&keccak_256(&pubkey)[12..]))
+	}
+}
+
+/// The hash an EIP-191 (`personal_sign`) signature of `message` signs.
+fn eip191_hash(message: &[u8]) -> [u8; 32] {
+	let mut prefixed = Vec::with_capacity(message.len() + 32);
+	prefixed.extend_from_slice(b"\x19Ethereum Signed Message:\n");
+	prefixed.extend_from_slice(alloc::format!("{}", message.len()).as_bytes());
+	prefixed.extend_from_slice(message);
+	keccak_256(&prefixed)
+}
+
+/// Address mapping resolving claimed EVM addresses to the account that claimed them, and other
+/// addresses through the fallback mapping of the pallet.
+pub struct BoundAddressMapping<T>(core::marker::PhantomData<T>);
+
+impl<T: Config> AddressMapping<T::AccountId> for BoundAddressMapping<T> {
+	fn into_account_id(address: H160) -> T::AccountId {
+		Accounts::<T>::get(address).unwrap_or_else(|| T::AddressMapping::into_account_id(address))
+	}
+}
@@ -0,0 +1,288 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use frame_support::{assert_noop, assert_ok};
+use sp_core::{ecdsa, sr25519, Pair};
+use sp_runtime::{AccountId32, MultiSignature, MultiSigner};
+
+use super::*;
+use crate::mock::{new_test_ext, RuntimeOrigin, Test};
+
+fn evm_pair(seed: u8) -> (ecdsa::Pair, H160) {
+	let pair = ecdsa::Pair::from_seed(&[seed; 32]);
+	let address = fp_account::AccountId20::from(pair.public()).into();
+	(pair, address)
+}
+
+fn sign_binding(pair: &ecdsa::Pair, who: &AccountId32) -> EcdsaSignature {
+	let message = Pallet::<Test>::binding_message(who);
+	let mut prefixed = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+	prefixed.extend_from_slice(&message);
+
+	let mut signature = [0u8; 65];
+	signature.copy_from_slice(pair.sign_prehashed(&keccak_256(&prefixed)).as_ref());
+	signature
+}
+
+fn account_pair(seed: u8) -> (sr25519::Pair, AccountId32) {
+	let pair = sr25519::Pair::from_seed(&[seed; 32]);
+	let account = MultiSigner::from(pair.public()).into_account();
+	(pair, account)
+}
+
+fn sign_account_binding(pair: &sr25519::Pair, address: &H160) -> MultiSignature {
+	pair.sign(&Pallet::<Test>::account_binding_message(address))
+		.into()
+}
+
+#[test]
+fn claimed_address_maps_to_the_claiming_account() {
+	new_test_ext().execute_with(|| {
+		let who = AccountId32::new([1; 32]);
+		let (pair, address) = evm_pair(1);
+		let fallback = <Test as Config>::AddressMapping::into_account_id(address);
+		assert_eq!(
+			BoundAddressMapping::<Test>::into_account_id(address),
+			fallback
+		);
+
+		assert_ok!(Pallet::<Test>::claim_evm_address(
+			RuntimeOrigin::signed(who.clone()),
+			address,
+			sign_binding(&pair, &who),
+		));
+
+		assert_eq!(BoundAddressMapping::<Test>::into_account_id(address), who);
+		assert_eq!(Pallet::<Test>::evm_address(&who), Some(address));
+
+		assert_ok!(Pallet::<Test>::unbind_evm_address(RuntimeOrigin::signed(
+			who.clone()
+		)));
+
+		assert_eq!(
+			BoundAddressMapping::<Test>::into_account_id(address),
+			fallback
+		);
+		assert_eq!(Pallet::<Test>::evm_address(&who), None);
+	});
+}
+
+#[test]
+fn claim_requires_a_signature_for_the_claiming_account() {
+	new_test_ext().execute_with(|| {
+		let who = AccountId32::new([1; 32]);
+		let other = AccountId32::new([2; 32]);
+		let (pair, address) = evm_pair(1);
+		let (_, other_address) = evm_pair(2);
+
+		assert_noop!(
+			Pallet::<Test>::claim_evm_address(
+				RuntimeOrigin::signed(other),
+				address,
+				sign_binding(&pair, &who),
+			),
+			Error::<Test>::BadSignature
+		);
+		assert_noop!(
+			Pallet::<Test>::claim_evm_address(
+				RuntimeOrigin::signed(who.clone()),
+				other_address,
+				sign_binding(&pair, &who),
+			),
+			Error::<Test>::BadSignature
+		);
+	});
+}
+
+#[test]
+fn claim_rejects_bound_and_used_addresses() {
+	new_test_ext().execute_with(|| {
+		let who = AccountId32::new([1; 32]);
+		let other = AccountId32::new([2; 32]);
+		let (pair, address) = evm_pair(1);
+		let (other_pair, other_address) = evm_pair(2);
+
+		assert_ok!(Pallet::<Test>::claim_evm_address(
+			RuntimeOrigin::signed(who.clone()),
+			address,
+			sign_binding(&pair, &who),
+		));
+		assert_noop!(
+			Pallet::<Test>::claim_evm_address(
+				RuntimeOrigin::signed(who.clone()),
+				other_address,
+				sign_binding(&other_pair, &who),
+			),
+			Error::<Test>::AccountAlreadyBound
+		);
+		assert_noop!(
+			Pallet::<Test>::claim_evm_address(
+				RuntimeOrigin::signed(other.clone()),
+				address,
+				sign_binding(&pair, &other),
+			),
+			Error::<Test>::AddressAlreadyBound
+		);
+
+		let fallback = <Test as Config>::AddressMapping::into_account_id(other_address);
+		frame_system::Pallet::<Test>::inc_account_nonce(fallback);
+		assert_noop!(
+			Pallet::<Test>::claim_evm_address(
+				RuntimeOrigin::signed(other.clone()),
+				other_address,
+				sign_binding(&other_pair, &other),
+			),
+			Error::<Test>::AddressInUse
+		);
+	});
+}
+
+#[test]
+fn claim_rejects_addresses_whose_fallback_account_holds_funds() {
+	new_test_ext().execute_with(|| {
+		let who = AccountId32::new([1; 32]);
+		let (pair, address) = evm_pair(1);
+
+		let fallback = <Test as Config>::AddressMapping::into_account_id(address);
+		let _ = frame_system::Pallet::<Test>::inc_providers(&fallback);
+		assert_noop!(
+			Pallet::<Test>::claim_evm_address(
+				RuntimeOrigin::signed(who.clone()),
+				address,
+				sign_binding(&pair, &who),
+			),
+			Error::<Test>::AddressInUse
+		);
+	});
+}
+
+#[test]
+fn the_nonce_of_an_address_never_goes_back() {
+	new_test_ext().execute_with(|| {
+		let who = AccountId32::new([1; 32]);
+		let other = AccountId32::new([2; 32]);
+		let (pair, address) = evm_pair(1);
+		let fallback = <Test as Config>::AddressMapping::into_account_id(address);
+
+		assert_ok!(Pallet::<Test>::claim_evm_address(
+			RuntimeOrigin::signed(who.clone()),
+			address,
+			sign_binding(&pair, &who),
+		));
+		// Transactions sent by the address while bound.
+		for _ in 0..3 {
+			frame_system::Pallet::<Test>::inc_account_nonce(&who);
+		}
+
+		// Released, the address continues from the nonce it reached.
+		assert_ok!(Pallet::<Test>::unbind_evm_address(RuntimeOrigin::signed(
+			who.clone()
+		)));
+		assert_eq!(frame_system::Pallet::<Test>::account_nonce(&fallback), 3);
+
+		// Claimed again, by an account with a lower nonce.
+		assert_ok!(Pallet::<Test>::claim_evm_address(
+			RuntimeOrigin::signed(other.clone()),
+			address,
+			sign_binding(&pair, &other),
+		));
+		assert_eq!(frame_system::Pallet::<Test>::account_nonce(&other), 3);
+	});
+}
+
+#[test]
+fn released_addresses_used_through_the_fallback_account_cant_be_claimed() {
+	new_test_ext().execute_with(|| {
+		let who = AccountId32::new([1; 32]);
+		let (pair, address) = evm_pair(1);
+		let fallback = <Test as Config>::AddressMapping::into_account_id(address);
+
+		assert_ok!(Pallet::<Test>::claim_evm_address(
+			RuntimeOrigin::signed(who.clone()),
+			address,
+			sign_binding(&pair, &who),
+		));
+		assert_ok!(Pallet::<Test>::unbind_evm_address(RuntimeOrigin::signed(
+			who.clone()
+		)));
+
+		frame_system::Pallet::<Test>::inc_account_nonce(&fallback);
+		assert_noop!(
+			Pallet::<Test>::claim_evm_address(
+				RuntimeOrigin::signed(who.clone()),
+				address,
+				sign_binding(&pair, &who),
+			),
+			Error::<Test>::AddressInUse
+		);
+	});
+}
+
+#[test]
+fn address_can_claim_an_existing_account() {
+	new_test_ext().execute_with(|| {
+		let relayer = AccountId32::new([9; 32]);
+		let (account_key, account) = account_pair(1);
+		let (pair, address) = evm_pair(1);
+
+		assert_ok!(Pallet::<Test>::claim_account(
+			RuntimeOrigin::signed(relayer),
+			address,
+			sign_binding(&pair, &account),
+			account.clone(),
+			sign_account_binding(&account_key, &address),
+		));
+
+		assert_eq!(
+			BoundAddressMapping::<Test>::into_account_id(address),
+			account
+		);
+		assert_eq!(Pallet::<Test>::evm_address(&account), Some(address));
+	});
+}
+
+#[test]
+fn account_claim_requires_both_signatures() {
+	new_test_ext().execute_with(|| {
+		let relayer = AccountId32::new([9; 32]);
+		let (account_key, account) = account_pair(1);
+		let (other_account_key, _) = account_pair(2);
+		let (pair, address) = evm_pair(1);
+		let (other_pair, _) = evm_pair(2);
+
+		assert_noop!(
+			Pallet::<Test>::claim_account(
+				RuntimeOrigin::signed(relayer.clone()),
+				address,
+				sign_binding(&pair, &account),
+				account.clone(),
+				sign_account_binding(&other_account_key, &address),
+			),
+			Error::<Test>::BadAccountSignature
+		);
+		assert_noop!(
+			Pallet::<Test>::claim_account(
+				RuntimeOrigin::signed(relayer),
+				address,
+				sign_binding(&other_pair, &account),
+				account.clone(),
+				sign_account_binding(&account_key, &address),
+			),
+			Error::<Test>::BadSignature
+		);
+	});
+}
@@ -0,0 +1,91 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Weights for pallet_evm_accounts
+//!
+//! These weights are hand-written placeholders, not measurements. Regenerate them from
+//! `benchmarking.rs` on reference hardware before relying on them.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_evm_accounts.
+pub trait WeightInfo {
+	fn claim_evm_address() -> Weight;
+	fn unbind_evm_address() -> Weight;
+	fn claim_account() -> Weight;
+}
+
+/// Weights for pallet_evm_accounts using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: EVMAccounts EvmAddresses (r:1 w:1)
+	/// Storage: EVMAccounts Accounts (r:1 w:1)
+	/// Storage: EVMAccounts ReleasedNonces (r:1 w:1)
+	/// Storage: System BlockHash (r:1 w:0)
+	/// Storage: System Account (r:2 w:1)
+	fn claim_evm_address() -> Weight {
+		// Dominated by the secp256k1 public key recovery.
+		Weight::from_parts(60_000_000, 7_500)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: EVMAccounts EvmAddresses (r:1 w:1)
+	/// Storage: EVMAccounts Accounts (r:0 w:1)
+	/// Storage: EVMAccounts ReleasedNonces (r:0 w:1)
+	/// Storage: System Account (r:2 w:1)
+	fn unbind_evm_address() -> Weight {
+		Weight::from_parts(15_000_000, 5_000)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: EVMAccounts EvmAddresses (r:1 w:1)
+	/// Storage: EVMAccounts Accounts (r:1 w:1)
+	/// Storage: EVMAccounts ReleasedNonces (r:1 w:1)
+	/// Storage: System BlockHash (r:1 w:0)
+	/// Storage: System Account (r:2 w:1)
+	fn claim_account() -> Weight {
+		// Dominated by the secp256k1 public key recovery and the account signature check.
+		Weight::from_parts(110_000_000, 7_500)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn claim_evm_address() -> Weight {
+		Weight::from_parts(60_000_000, 7_500)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	fn unbind_evm_address() -> Weight {
+		Weight::from_parts(15_000_000, 5_000)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	fn claim_account() -> Weight {
+		Weight::from_parts(110_000_000, 7_500)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+}
@@ -0,0 +1,259 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # EVM precompile registry pallet
+//!
+//! Keeps on-chain which of the precompiles compiled into the runtime is enabled at which
+//! address. [`OnChainPrecompiles`] dispatches the calls to the enabled addresses.
+//!
+//! Looking up an address in the registry is a storage read, which [`OnChainPrecompiles`] charges
+//! to the caller in gas, proof size included.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(unused_crate_dependencies)]
+
+extern crate alloc;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+use alloc::vec::Vec;
+// Substrate
+use frame_support::{
+	traits::{ConstU32, Get},
+	BoundedVec,
+};
+use sp_core::H160;
+// Frontier
+use fp_evm::{
+	IsPrecompileResult, PrecompileFailure, PrecompileHandle, PrecompileResult, PrecompileSet,
+};
+use pallet_evm::GasWeightMapping;
+
+pub use self::{pallet::*, weights::WeightInfo};
+
+/// Label identifying a precompile compiled into the runtime.
+pub type PrecompileLabel = BoundedVec<u8, ConstU32<32>>;
+
+/// `Precompiles` read: 16 (hash) + 20 (key) + 33 (PrecompileLabel::max_encoded_len).
+pub const PRECOMPILES_PROOF_SIZE: u64 = 69;
+
+/// Dummy code stored at the address of an enabled precompile, like the static precompiles have,
+/// so that contracts see code there. It reverts if it is ever executed.
+pub const PRECOMPILE_CODE: [u8; 5] = [0x60, 0x00, 0x60, 0x00, 0xfd];
+
+/// The precompiles compiled into the runtime, identified by their label.
+pub trait LabelledPrecompileSet {
+	/// Whether a precompile is labelled `label`.
+	fn contains(label: &[u8]) -> bool;
+
+	/// Execute the precompile labelled `label`, `None` if no precompile is labelled so.
+	fn execute(label: &[u8], handle: &mut impl PrecompileHandle) -> Option<PrecompileResult>;
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_evm::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// The precompiles that can be enabled.
+		type Precompiles: LabelledPrecompileSet;
+		/// Origin allowed to enable and disable precompiles.
+		type PrecompileModifierOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::genesis_config]
+	#[derive(frame_support::DefaultNoBound)]
+	pub struct GenesisConfig<T> {
+		pub precompiles: Vec<(H160, Vec<u8>)>,
+		#[serde(skip)]
+		pub _marker: PhantomData<T>,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+		fn build(&self) {
+			for (address, label) in &self.precompiles {
+				let label =
+					PrecompileLabel::try_from(label.clone()).expect("precompile label too long");
+				assert!(T::Precompiles::contains(&label), "unknown precompile label");
+				assert!(
+					Pallet::<T>::can_hold_precompile(*address),
+					"precompile address already has code"
+				);
+				Pallet::<T>::enable(*address, &label);
+			}
+		}
+	}
+
+	/// The label of the precompile enabled at each address.
+	#[pallet::storage]
+	pub type Precompiles<T: Config> = StorageMap<_, Blake2_128Concat, H160, PrecompileLabel>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A precompile was enabled at an address.
+		PrecompileAdded {
+			address: H160,
+			label: PrecompileLabel,
+		},
+		/// The precompile at an address was disabled.
+		PrecompileRemoved { address: H160 },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// No precompile compiled into the runtime has this label.
+		UnknownPrecompile,
+		/// A precompile is already enabled at this address.
+		PrecompileAlreadyExists,
+		/// No precompile is enabled at this address.
+		PrecompileNotFound,
+		/// The address has contract code.
+		AddressHasCode,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Enable the precompile labelled `label` at `address`, writing the dummy precompile code
+		/// there. Addresses holding contract code are rejected.
+		#[pallet::call_index(0)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::add_precompile())]
+		pub fn add_precompile(
+			origin: OriginFor<T>,
+			address: H160,
+			label: PrecompileLabel,
+		) -> DispatchResult {
+			T::PrecompileModifierOrigin::ensure_origin(origin)?;
+
+			ensure!(
+				T::Precompiles::contains(&label),
+				Error::<T>::UnknownPrecompile
+			);
+			ensure!(
+				!Precompiles::<T>::contains_key(address),
+				Error::<T>::PrecompileAlreadyExists
+			);
+			ensure!(
+				Self::can_hold_precompile(address),
+				Error::<T>::AddressHasCode
+			);
+
+			Self::enable(address, &label);
+			Self::deposit_event(Event::PrecompileAdded { address, label });
+			Ok(())
+		}
+
+		/// Disable the precompile enabled at `address`.
+		///
+		/// The dummy precompile code is left in place, so that no contract can be deployed at the
+		/// address and the precompile can be enabled there again.
+		#[pallet::call_index(1)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::remove_precompile())]
+		pub fn remove_precompile(origin: OriginFor<T>, address: H160) -> DispatchResult {
+			T::PrecompileModifierOrigin::ensure_origin(origin)?;
+
+			ensure!(
+				Precompiles::<T>::contains_key(address),
+				Error::<T>::PrecompileNotFound
+			);
+
+			Precompiles::<T>::remove(address);
+			Self::deposit_event(Event::PrecompileRemoved { address });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Whether `address` is free of code, or holds the dummy precompile code.
+		fn can_hold_precompile(address: H160) -> bool {
+			!pallet_evm::AccountCodes::<T>::contains_key(address)
+				|| pallet_evm::Pallet::<T>::account_code(address) == PRECOMPILE_CODE
+		}
+
+		fn enable(address: H160, label: &PrecompileLabel) {
+			pallet_evm::Pallet::<T>::create_account(address, PRECOMPILE_CODE.to_vec());
+			Precompiles::<T>::insert(address, label);
+		}
+	}
+}
+
+/// Precompile set dispatching the calls to the addresses enabled in the registry.
+pub struct OnChainPrecompiles<T>(core::marker::PhantomData<T>);
+
+impl<T> OnChainPrecompiles<T> {
+	pub fn new() -> Self {
+		Self(Default::default())
+	}
+}
+
+impl<T: Config> OnChainPrecompiles<T> {
+	/// Gas cost of a registry lookup.
+	fn lookup_gas_cost() -> u64 {
+		<T as pallet_evm::Config>::GasWeightMapping::weight_to_gas(
+			<T as frame_system::Config>::DbWeight::get().reads(1),
+		)
+	}
+}
+
+impl<T> Default for OnChainPrecompiles<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T: Config> PrecompileSet for OnChainPrecompiles<T> {
+	fn execute(&self, handle: &mut impl PrecompileHandle) -> Option<PrecompileResult> {
+		if let Err(exit_status) = handle
+			.record_cost(Self::lookup_gas_cost())
+			.and_then(|_| handle.record_external_cost(None, Some(PRECOMPILES_PROOF_SIZE), None))
+		{
+			return Some(Err(PrecompileFailure::Error { exit_status }));
+		}
+
+		let label = Precompiles::<T>::get(handle.code_address())?;
+		T::Precompiles::execute(&label, handle)
+	}
+
+	fn is_precompile(&self, address: H160, gas: u64) -> IsPrecompileResult {
+		// No handle to record the proof size with here, so it is charged as gas.
+		let extra_cost = Self::lookup_gas_cost()
+			.saturating_add(PRECOMPILES_PROOF_SIZE.saturating_mul(T::GasLimitPovSizeRatio::get()));
+		if gas < extra_cost {
+			return IsPrecompileResult::OutOfGas;
+		}
+
+		IsPrecompileResult::Answer {
+			is_precompile: Precompiles::<T>::contains_key(address),
+			extra_cost,
+		}
+	}
+}
@@ -0,0 +1,194 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::DispatchError;
+// Frontier
+use fp_evm::Context;
+use precompile_utils::testing::MockHandle;
+
+use super::*;
+use crate::mock::{new_test_ext, RuntimeOrigin, Test, EVM};
+
+fn label(label: &[u8]) -> PrecompileLabel {
+	label.to_vec().try_into().unwrap()
+}
+
+fn call(address: H160, input: Vec<u8>) -> Option<Vec<u8>> {
+	let mut handle = MockHandle::new(
+		address,
+		Context {
+			address,
+			caller: H160::default(),
+			apparent_value: Default::default(),
+		},
+	);
+	handle.input = input;
+	OnChainPrecompiles::<Test>::new()
+		.execute(&mut handle)
+		.map(|result| result.expect("identity can't fail").output)
+}
+
+#[test]
+fn enabled_precompiles_are_dispatched() {
+	new_test_ext().execute_with(|| {
+		let address = H160::from_low_u64_be(0x800);
+		let is_precompile = || {
+			matches!(
+				OnChainPrecompiles::<Test>::new().is_precompile(address, u64::MAX),
+				IsPrecompileResult::Answer {
+					is_precompile: true,
+					..
+				}
+			)
+		};
+		assert!(!is_precompile());
+		assert_eq!(call(address, vec![1, 2, 3]), None);
+
+		assert_ok!(Pallet::<Test>::add_precompile(
+			RuntimeOrigin::root(),
+			address,
+			label(b"Identity"),
+		));
+		assert!(is_precompile());
+		assert_eq!(call(address, vec![1, 2, 3]), Some(vec![1, 2, 3]));
+
+		assert_ok!(Pallet::<Test>::remove_precompile(
+			RuntimeOrigin::root(),
+			address
+		));
+		assert!(!is_precompile());
+		assert_eq!(call(address, vec![1, 2, 3]), None);
+	});
+}
+
+#[test]
+fn only_known_precompiles_can_be_enabled() {
+	new_test_ext().execute_with(|| {
+		let address = H160::from_low_u64_be(0x800);
+
+		assert_noop!(
+			Pallet::<Test>::add_precompile(RuntimeOrigin::root(), address, label(b"Unknown")),
+			Error::<Test>::UnknownPrecompile
+		);
+		assert_noop!(
+			Pallet::<Test>::add_precompile(
+				RuntimeOrigin::signed(H160::default()),
+				address,
+				label(b"Identity"),
+			),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Pallet::<Test>::remove_precompile(RuntimeOrigin::root(), address),
+			Error::<Test>::PrecompileNotFound
+		);
+
+		assert_ok!(Pallet::<Test>::add_precompile(
+			RuntimeOrigin::root(),
+			address,
+			label(b"Identity"),
+		));
+		assert_noop!(
+			Pallet::<Test>::add_precompile(RuntimeOrigin::root(), address, label(b"Identity")),
+			Error::<Test>::PrecompileAlreadyExists
+		);
+	});
+}
+
+#[test]
+fn enabled_precompiles_have_dummy_code() {
+	new_test_ext().execute_with(|| {
+		let address = H160::from_low_u64_be(0x800);
+
+		assert_ok!(Pallet::<Test>::add_precompile(
+			RuntimeOrigin::root(),
+			address,
+			label(b"Identity"),
+		));
+		assert_eq!(EVM::account_code(address), PRECOMPILE_CODE.to_vec());
+
+		// The dummy code is kept, so the precompile can be enabled again.
+		assert_ok!(Pallet::<Test>::remove_precompile(
+			RuntimeOrigin::root(),
+			address
+		));
+		assert_eq!(EVM::account_code(address), PRECOMPILE_CODE.to_vec());
+		assert_ok!(Pallet::<Test>::add_precompile(
+			RuntimeOrigin::root(),
+			address,
+			label(b"Identity"),
+		));
+	});
+}
+
+#[test]
+fn precompiles_cannot_be_enabled_over_contracts() {
+	new_test_ext().execute_with(|| {
+		let address = H160::from_low_u64_be(0x800);
+		EVM::create_account(address, vec![0x00]);
+
+		assert_noop!(
+			Pallet::<Test>::add_precompile(RuntimeOrigin::root(), address, label(b"Identity")),
+			Error::<Test>::AddressHasCode
+		);
+	});
+}
+
+#[test]
+fn registry_lookups_are_charged() {
+	new_test_ext().execute_with(|| {
+		let address = H160::from_low_u64_be(0x800);
+		let lookup_cost = OnChainPrecompiles::<Test>::lookup_gas_cost();
+		let extra_cost = lookup_cost
+			+ PRECOMPILES_PROOF_SIZE * <Test as pallet_evm::Config>::GasLimitPovSizeRatio::get();
+		assert!(lookup_cost > 0);
+
+		assert!(matches!(
+			OnChainPrecompiles::<Test>::new().is_precompile(address, extra_cost - 1),
+			IsPrecompileResult::OutOfGas
+		));
+		assert!(matches!(
+			OnChainPrecompiles::<Test>::new().is_precompile(address, extra_cost),
+			IsPrecompileResult::Answer {
+				is_precompile: false,
+				extra_cost: cost,
+			} if cost == extra_cost
+		));
+
+		let mut handle = MockHandle::new(
+			address,
+			Context {
+				address,
+				caller: H160::default(),
+				apparent_value: Default::default(),
+			},
+		);
+		assert!(OnChainPrecompiles::<Test>::new()
+			.execute(&mut handle)
+			.is_none());
+		assert_eq!(handle.gas_used, lookup_cost);
+
+		handle.gas_limit = handle.gas_used;
+		assert!(matches!(
+			OnChainPrecompiles::<Test>::new().execute(&mut handle),
+			Some(Err(PrecompileFailure::Error {
+				exit_status: fp_evm::ExitError::OutOfGas
+			}))
+		));
+	});
+}
@@ -0,0 +1,69 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Weights for pallet_evm_precompile_registry
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_evm_precompile_registry.
+pub trait WeightInfo {
+	fn add_precompile() -> Weight;
+	fn remove_precompile() -> Weight;
+}
+
+/// Weights for pallet_evm_precompile_registry using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: EVMPrecompileRegistry Precompiles (r:1 w:1)
+	/// Storage: EVM AccountCodes (r:1 w:1)
+	/// Storage: EVM Codes (r:1 w:1)
+	/// Storage: EVM CodeRefCounts (r:1 w:1)
+	/// Storage: EVM AccountCodesMetadata (r:0 w:1)
+	/// Storage: EVM Suicided (r:1 w:0)
+	/// Storage: System Account (r:1 w:1)
+	fn add_precompile() -> Weight {
+		Weight::from_parts(30_000_000, 3_700)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: EVMPrecompileRegistry Precompiles (r:1 w:1)
+	fn remove_precompile() -> Weight {
+		Weight::from_parts(10_000_000, 2_600)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn add_precompile() -> Weight {
+		Weight::from_parts(30_000_000, 3_700)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	fn remove_precompile() -> Weight {
+		Weight::from_parts(10_000_000, 2_600)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+}
@@ -20,7 +20,7 @@
 use core::str::FromStr;
 use frame_support::{
 	derive_impl, parameter_types,
-	traits::{ConstU32, FindAuthor},
+	traits::{ConstBool, ConstU32, FindAuthor},
 	weights::Weight,
 	ConsensusEngineId,
 };
@@ -155,10 +155,20 @@ impl pallet_evm::Config for Test {
 	type Runner = pallet_evm::runner::stack::Runner<Self>;
 	type OnChargeTransaction = ();
 	type OnCreate = ();
+	type ExecutionHooks = ();
+	type CreateOriginFilter = ();
+	type DeployerAllowListOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type CallFilter = ();
+	type CallBlockListOrigin = frame_system::EnsureRoot<Self::AccountId>;
 	type FindAuthor = FindAuthorTruncated;
 	type SuicideQuickClearLimit = SuicideQuickClearLimit;
 	type GasLimitPovSizeRatio = ();
+	type GasLimitStorageGrowthRatio = ();
 	type Timestamp = Timestamp;
+	type ExecutionExtrinsicsEnabled = ConstBool<true>;
+	type ShanghaiBlock = ();
+	type CodeSizeLimit = ();
+	type InitcodeSizeLimit = ();
 	type WeightInfo = ();
 }
 
@@ -0,0 +1,111 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Execution environment precompile. This precompile exposes information about the chain
+//! executing the contract (runtime version, relay chain and parachain data, block limits) so
+//! that contracts can implement upgrade-aware safety checks.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use core::marker::PhantomData;
+use frame_support::traits::Get;
+use precompile_utils::{prelude::*, EvmResult};
+use sp_core::U256;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+/// Gas cost of reading information which is not kept in storage.
+pub const BASE_GAS_COST: u64 = 100;
+/// Max encoded length of a block number or a parachain id read from storage.
+pub const U32_STORAGE_SIZE: usize = 4;
+
+/// Chain specific information exposed by the precompile.
+pub trait ExecutionEnvironment {
+	/// The relay chain block number the current block is built on, `None` if the chain is not
+	/// a parachain.
+	fn relay_block_number() -> Option<u32>;
+
+	/// The parachain id, `None` if the chain is not a parachain.
+	fn parachain_id() -> Option<u32>;
+}
+
+impl ExecutionEnvironment for () {
+	fn relay_block_number() -> Option<u32> {
+		None
+	}
+
+	fn parachain_id() -> Option<u32> {
+		None
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct ExecutionEnvironmentPrecompile<Runtime, Environment>(
+	PhantomData<(Runtime, Environment)>,
+);
+
+#[precompile_utils::precompile]
+impl<Runtime, Environment> ExecutionEnvironmentPrecompile<Runtime, Environment>
+where
+	Runtime: pallet_evm::Config,
+	Environment: ExecutionEnvironment,
+{
+	/// Returns the spec version of the runtime.
+	#[precompile::public("specVersion()")]
+	#[precompile::view]
+	fn spec_version(handle: &mut impl PrecompileHandle) -> EvmResult<u32> {
+		handle.record_cost(BASE_GAS_COST)?;
+		Ok(<Runtime as frame_system::Config>::Version::get().spec_version)
+	}
+
+	/// Returns whether the chain is a parachain, and the relay chain block number the current
+	/// block is built on.
+	#[precompile::public("relayBlockNumber()")]
+	#[precompile::view]
+	fn relay_block_number(handle: &mut impl PrecompileHandle) -> EvmResult<(bool, u32)> {
+		handle.record_db_read::<Runtime>(U32_STORAGE_SIZE)?;
+		Ok(Environment::relay_block_number()
+			.map(|number| (true, number))
+			.unwrap_or_default())
+	}
+
+	/// Returns whether the chain is a parachain, and its parachain id.
+	#[precompile::public("parachainId()")]
+	#[precompile::view]
+	fn parachain_id(handle: &mut impl PrecompileHandle) -> EvmResult<(bool, u32)> {
+		handle.record_db_read::<Runtime>(U32_STORAGE_SIZE)?;
+		Ok(Environment::parachain_id()
+			.map(|id| (true, id))
+			.unwrap_or_default())
+	}
+
+	/// Returns the maximum ref time and proof size of a block, and the block gas limit.
+	#[precompile::public("weightLimits()")]
+	#[precompile::view]
+	fn weight_limits(handle: &mut impl PrecompileHandle) -> EvmResult<(u64, u64, U256)> {
+		handle.record_cost(BASE_GAS_COST)?;
+		let max_block = <Runtime as frame_system::Config>::BlockWeights::get().max_block;
+		Ok((
+			max_block.ref_time(),
+			max_block.proof_size(),
+			pallet_evm::Pallet::<Runtime>::block_gas_limit(),
+		))
+	}
+}
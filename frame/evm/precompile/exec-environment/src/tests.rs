@@ -0,0 +1,72 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::mock::{
+	ExtBuilder, PCall, ParachainId, Precompiles, PrecompilesValue, RelayBlockNumber,
+};
+use precompile_utils::testing::*;
+use sp_core::U256;
+
+fn precompiles() -> Precompiles<crate::mock::Runtime> {
+	PrecompilesValue::get()
+}
+
+#[test]
+fn spec_version_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(Alice, Precompile1, PCall::spec_version {})
+			.expect_no_logs()
+			.execute_returns(42u32);
+	})
+}
+
+#[test]
+fn parachain_info_is_empty_outside_parachains() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(Alice, Precompile1, PCall::relay_block_number {})
+			.execute_returns((false, 0u32));
+		precompiles()
+			.prepare_test(Alice, Precompile1, PCall::parachain_id {})
+			.execute_returns((false, 0u32));
+	})
+}
+
+#[test]
+fn parachain_info_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		RelayBlockNumber::set(Some(1_000));
+		ParachainId::set(Some(2_034));
+
+		precompiles()
+			.prepare_test(Alice, Precompile1, PCall::relay_block_number {})
+			.execute_returns((true, 1_000u32));
+		precompiles()
+			.prepare_test(Alice, Precompile1, PCall::parachain_id {})
+			.execute_returns((true, 2_034u32));
+	})
+}
+
+#[test]
+fn weight_limits_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(Alice, Precompile1, PCall::weight_limits {})
+			.execute_returns((1024u64, 2048u64, U256::from(15_000_000)));
+	})
+}
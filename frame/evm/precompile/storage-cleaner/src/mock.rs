@@ -21,7 +21,7 @@ use crate::{StorageCleanerPrecompile, StorageCleanerPrecompileCall};
 use frame_support::{parameter_types, weights::Weight};
 use pallet_evm::{EnsureAddressNever, EnsureAddressRoot, IdentityAddressMapping};
 use precompile_utils::{precompile_set::*, testing::*};
-use sp_core::{ConstU32, H256, U256};
+use sp_core::{ConstBool, ConstU32, H256, U256};
 use sp_runtime::{
 	traits::{BlakeTwo256, IdentityLookup},
 	BuildStorage,
@@ -141,10 +141,20 @@ impl pallet_evm::Config for Runtime {
 	type BlockHashMapping = pallet_evm::SubstrateBlockHashMapping<Self>;
 	type FindAuthor = ();
 	type OnCreate = ();
+	type ExecutionHooks = ();
+	type CreateOriginFilter = ();
+	type DeployerAllowListOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type CallFilter = ();
+	type CallBlockListOrigin = frame_system::EnsureRoot<Self::AccountId>;
 	type GasLimitPovSizeRatio = GasLimitPovSizeRatio;
+	type GasLimitStorageGrowthRatio = ();
 	type Timestamp = Timestamp;
 	type WeightInfo = ();
 	type SuicideQuickClearLimit = SuicideQuickClearLimit;
+	type ExecutionExtrinsicsEnabled = ConstBool<true>;
+	type ShanghaiBlock = ();
+	type CodeSizeLimit = ();
+	type InitcodeSizeLimit = ();
 }
 
 /// Build test externalities, prepopulated with data for testing the precompile.
@@ -18,7 +18,8 @@
 #![cfg(feature = "runtime-benchmarks")]
 
 use super::*;
-use frame_benchmarking::benchmarks;
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, BenchmarkError};
+use sp_runtime::traits::Bounded;
 
 type CurrencyOf<T> = <T as Config>::Currency;
 
@@ -34,6 +35,176 @@ benchmarks! {
 		assert!(result.is_err());
 		assert_eq!(result.unwrap_err(), sp_runtime::DispatchError::BadOrigin);
 	}
+
+	call {
+		let x in 0 .. MAX_INPUT_SIZE;
+		let a in 0 .. MAX_ACCESS_LIST_SIZE;
+		let source = H160::from_low_u64_le(1);
+		let target = H160::from_low_u64_le(2);
+		fund_source::<T>(source);
+		let (max_fee_per_gas, _) = T::FeeCalculator::min_gas_price();
+	}: {
+		// The origin check is runtime specific, so the benchmark measures the extrinsic body
+		// through the configured runner.
+		let result = T::Runner::call(
+			source,
+			target,
+			vec![0; x as usize],
+			U256::zero(),
+			BENCHMARK_GAS_LIMIT,
+			Some(max_fee_per_gas),
+			None,
+			None,
+			access_list(a),
+			true,
+			true,
+			None,
+			None,
+			T::config(),
+		);
+		assert!(result.is_ok());
+	}
+
+	create {
+		let x in 0 .. MAX_INPUT_SIZE;
+		let a in 0 .. MAX_ACCESS_LIST_SIZE;
+		let source = H160::from_low_u64_le(1);
+		fund_source::<T>(source);
+		let (max_fee_per_gas, _) = T::FeeCalculator::min_gas_price();
+	}: {
+		let result = T::Runner::create(
+			source,
+			init_code(x),
+			U256::zero(),
+			BENCHMARK_GAS_LIMIT,
+			Some(max_fee_per_gas),
+			None,
+			None,
+			access_list(a),
+			true,
+			true,
+			None,
+			None,
+			T::config(),
+		);
+		assert!(result.is_ok());
+	}
+
+	create2 {
+		let x in 0 .. MAX_INPUT_SIZE;
+		let a in 0 .. MAX_ACCESS_LIST_SIZE;
+		let source = H160::from_low_u64_le(1);
+		fund_source::<T>(source);
+		let (max_fee_per_gas, _) = T::FeeCalculator::min_gas_price();
+	}: {
+		let result = T::Runner::create2(
+			source,
+			init_code(x),
+			H256::default(),
+			U256::zero(),
+			BENCHMARK_GAS_LIMIT,
+			Some(max_fee_per_gas),
+			None,
+			None,
+			access_list(a),
+			true,
+			true,
+			None,
+			None,
+			T::config(),
+		);
+		assert!(result.is_ok());
+	}
+
+	set_deployer_allowed {
+		let origin = T::DeployerAllowListOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let deployer = H160::from_low_u64_be(1);
+	}: _<T::RuntimeOrigin>(origin, deployer, true)
+	verify {
+		assert!(AllowedDeployers::<T>::contains_key(deployer));
+	}
+
+	set_call_target_blocked {
+		let origin = T::CallBlockListOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let target = H160::from_low_u64_be(1);
+	}: _<T::RuntimeOrigin>(origin, target, true)
+	verify {
+		assert!(BlockedCallTargets::<T>::contains_key(target));
+	}
+
+	force_set_nonce {
+		let address = H160::from_low_u64_be(1);
+		let nonce: T::Nonce = 1u32.into();
+	}: _(RawOrigin::Root, address, nonce)
+	verify {
+		let account_id = T::AddressMapping::into_account_id(address);
+		assert_eq!(frame_system::Pallet::<T>::account_nonce(&account_id), nonce);
+	}
+
+	force_remove_contract {
+		let l in 0 .. MAX_REMOVED_STORAGE;
+		let address = H160::from_low_u64_be(1);
+		Pallet::<T>::create_account(address, BENCHMARK_INIT_CODE.to_vec());
+		for i in 0..l {
+			let index = H256::from_low_u64_be(u64::from(i));
+			AccountStorages::<T>::insert(address, index, H256::repeat_byte(1));
+		}
+	}: _(RawOrigin::Root, address, l)
+	verify {
+		assert!(!AccountCodes::<T>::contains_key(address));
+		assert!(!Suicided::<T>::contains_key(address));
+		assert_eq!(AccountStorages::<T>::iter_prefix(address).count(), 0);
+	}
+
+	set_block_gas_limit {
+		let gas_limit = U256::from(BENCHMARK_GAS_LIMIT);
+	}: _(RawOrigin::Root, Some(gas_limit))
+	verify {
+		assert_eq!(BlockGasLimitOverride::<T>::get(), Some(gas_limit));
+	}
+}
+
+/// Gas limit used by the execution benchmarks. Large enough for the largest input and access
+/// list, and for deploying `BENCHMARK_INIT_CODE`.
+const BENCHMARK_GAS_LIMIT: u64 = 2_000_000;
+
+/// Upper bound of the input (or init code) size component, in bytes.
+const MAX_INPUT_SIZE: u32 = 32 * 1024;
+
+/// Upper bound of the access list size component, in entries of one storage key.
+const MAX_ACCESS_LIST_SIZE: u32 = 256;
+
+/// Upper bound of the storage entries removed by `force_remove_contract`.
+const MAX_REMOVED_STORAGE: u32 = 1000;
+
+/// Init code deploying a contract whose runtime code is a single `STOP`.
+///
+/// PUSH1 0x00 PUSH1 0x00 MSTORE8 PUSH1 0x01 PUSH1 0x00 RETURN
+const BENCHMARK_INIT_CODE: [u8; 10] = [0x60, 0x00, 0x60, 0x00, 0x53, 0x60, 0x01, 0x60, 0x00, 0xf3];
+
+/// `BENCHMARK_INIT_CODE` padded with `x` bytes that are never executed.
+fn init_code(x: u32) -> Vec<u8> {
+	let mut code = BENCHMARK_INIT_CODE.to_vec();
+	code.resize(BENCHMARK_INIT_CODE.len() + x as usize, 0);
+	code
+}
+
+fn access_list(a: u32) -> Vec<(H160, Vec<H256>)> {
+	(0..a)
+		.map(|i| {
+			(
+				H160::from_low_u64_be(u64::from(i) + 0x1000),
+				vec![H256::from_low_u64_be(u64::from(i))],
+			)
+		})
+		.collect()
+}
+
+fn fund_source<T: Config>(source: H160) {
+	let account_id = T::AddressMapping::into_account_id(source);
+	CurrencyOf::<T>::make_free_balance_be(&account_id, BalanceOf::<T>::max_value() / 2u32.into());
 }
 
-// impl_benchmark_test_suite!(Pallet, crate::tests::new_test_ext(), crate::mock::Test);
+impl_benchmark_test_suite!(Pallet, crate::tests::new_test_ext(), crate::mock::Test);
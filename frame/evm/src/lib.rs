@@ -60,6 +60,7 @@ extern crate alloc;
 #[cfg(feature = "runtime-benchmarks")]
 pub mod benchmarking;
 
+pub mod migrations;
 #[cfg(test)]
 mod mock;
 pub mod runner;
@@ -67,7 +68,7 @@ pub mod runner;
 mod tests;
 pub mod weights;
 
-use alloc::{collections::btree_map::BTreeMap, vec::Vec};
+use alloc::{borrow::Cow, collections::btree_map::BTreeMap, vec::Vec};
 use core::cmp::min;
 pub use evm::{
 	Config as EvmConfig, Context, ExitError, ExitFatal, ExitReason, ExitRevert, ExitSucceed,
@@ -82,10 +83,11 @@ use frame_support::{
 	storage::{child::KillStorageResult, KeyPrefixIterator},
 	traits::{
 		fungible::{Balanced, Credit, Debt},
+		fungibles,
 		tokens::{
 			currency::Currency,
 			fungible::Inspect,
-			imbalance::{Imbalance, OnUnbalanced, SignedImbalance},
+			imbalance::{Imbalance, OnUnbalanced, SignedImbalance, TryDrop},
 			ExistenceRequirement, Fortitude, Precision, Preservation, WithdrawReasons,
 		},
 		FindAuthor, Get, Time,
@@ -96,13 +98,13 @@ use frame_system::RawOrigin;
 use sp_core::{H160, H256, U256};
 use sp_runtime::{
 	traits::{BadOrigin, NumberFor, Saturating, UniqueSaturatedInto, Zero},
-	AccountId32, DispatchErrorWithPostInfo,
+	AccountId32, DispatchErrorWithPostInfo, FixedPointNumber, FixedU128,
 };
 // Frontier
 use fp_account::AccountId20;
 use fp_evm::GenesisAccount;
 pub use fp_evm::{
-	Account, CallInfo, CreateInfo, ExecutionInfoV2 as ExecutionInfo, FeeCalculator,
+	Account, BlockFees, CallInfo, CreateInfo, ExecutionInfoV2 as ExecutionInfo, FeeCalculator,
 	IsPrecompileResult, LinearCostPrecompile, Log, Precompile, PrecompileFailure, PrecompileHandle,
 	PrecompileOutput, PrecompileResult, PrecompileSet, TransactionValidationError, Vicinity,
 };
@@ -119,7 +121,11 @@ pub mod pallet {
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
 
+	/// The in-code storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(PhantomData<T>);
 
@@ -155,6 +161,9 @@ pub mod pallet {
 		/// Chain ID of EVM.
 		type ChainId: Get<u64>;
 		/// The block gas limit. Can be a simple constant, or an adjustment algorithm in another pallet.
+		///
+		/// It is overridden by the limit governance sets in [`BlockGasLimitOverride`], see
+		/// [`Pallet::block_gas_limit`].
 		type BlockGasLimit: Get<U256>;
 		/// EVM execution runner.
 		type Runner: Runner<Self>;
@@ -167,24 +176,84 @@ pub mod pallet {
 		/// Called on create calls, used to record owner
 		type OnCreate: OnCreate<Self>;
 
+		/// Called before and after each call and create of the runner.
+		type ExecutionHooks: ExecutionHooks<Self>;
+
+		/// Filters the accounts allowed to deploy contracts with `create` and `create2`.
+		type CreateOriginFilter: CreateOriginFilter<Self>;
+
+		/// Origin allowed to manage the [`AllowedDeployers`] allow-list.
+		type DeployerAllowListOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Filters the calls of the `call` executions, e.g. to block calls to compromised
+		/// contracts.
+		type CallFilter: CallFilter<Self>;
+
+		/// Origin allowed to manage the [`BlockedCallTargets`] block-list.
+		type CallBlockListOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
 		/// Find author for the current block.
 		type FindAuthor: FindAuthor<H160>;
 
 		/// Gas limit Pov size ratio.
 		type GasLimitPovSizeRatio: Get<u64>;
 
+		/// Gas limit storage growth ratio: the gas charged per byte of storage created by a
+		/// transaction (new storage slots and deployed code), net of the storage slots it clears,
+		/// whether they existed before the transaction or not. A transaction freeing more
+		/// storage than it creates is charged nothing for storage growth. It also bounds the net
+		/// storage growth of a transaction to its gas limit divided by the ratio. Set to 0 to
+		/// disable storage growth metering.
+		type GasLimitStorageGrowthRatio: Get<u64>;
+
 		/// Define the quick clear limit of storage clearing when a contract suicides. Set to 0 to disable it.
 		type SuicideQuickClearLimit: Get<u32>;
 
 		/// Get the timestamp for the current block.
 		type Timestamp: Time;
 
+		/// Whether the `call`, `create` and `create2` extrinsics can be dispatched. Runtimes that
+		/// only want EVM execution through `pallet_ethereum` can set this to `false`.
+		type ExecutionExtrinsicsEnabled: Get<bool>;
+
+		/// First block executed with the Shanghai feature set (e.g. the PUSH0 opcode), the
+		/// blocks before it being executed with the London one. `0` enables it from genesis.
+		///
+		/// The feature set follows the block of the executing state, so re-executing historical
+		/// blocks keeps the behavior the already-deployed contracts had at the time.
+		type ShanghaiBlock: Get<BlockNumberFor<Self>>;
+
+		/// Maximum size in bytes of deployed contract code (EIP-170). `None` keeps the limit of
+		/// the executing feature set (24KiB).
+		///
+		/// The limit also bounds the proof size pre-charged when touching an account's code, so
+		/// raising it makes every code access more expensive in PoV terms.
+		type CodeSizeLimit: Get<Option<u32>>;
+
+		/// Maximum size in bytes of contract creation code (EIP-3860). `None` keeps the limit of
+		/// the executing feature set, i.e. no limit before Shanghai and 48KiB after it.
+		///
+		/// Whenever a limit applies, initcode is also charged per word as EIP-3860 specifies.
+		type InitcodeSizeLimit: Get<Option<u32>>;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 
 		/// EVM config used in the module.
 		fn config() -> &'static EvmConfig {
-			&SHANGHAI_CONFIG
+			if frame_system::Pallet::<Self>::block_number() < Self::ShanghaiBlock::get() {
+				&LONDON_CONFIG
+			} else {
+				&SHANGHAI_CONFIG
+			}
+		}
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_: BlockNumberFor<T>) -> Weight {
+			<CurrentBlockFees<T>>::kill();
+			T::DbWeight::get().writes(1)
 		}
 	}
 
@@ -216,6 +285,10 @@ pub mod pallet {
 		#[pallet::weight({
 			let without_base_extrinsic_weight = true;
 			T::GasWeightMapping::gas_to_weight(*gas_limit, without_base_extrinsic_weight)
+				.saturating_add(<T as pallet::Config>::WeightInfo::call(
+					input.len() as u32,
+					access_list.len() as u32,
+				))
 		})]
 		pub fn call(
 			origin: OriginFor<T>,
@@ -229,8 +302,14 @@ pub mod pallet {
 			nonce: Option<U256>,
 			access_list: Vec<(H160, Vec<H256>)>,
 		) -> DispatchResultWithPostInfo {
+			Self::ensure_execution_extrinsics_enabled()?;
 			T::CallOrigin::ensure_address_origin(&source, origin)?;
 
+			let extrinsic_weight = <T as pallet::Config>::WeightInfo::call(
+				input.len() as u32,
+				access_list.len() as u32,
+			);
+
 			let is_transactional = true;
 			let validate = true;
 			let info = match T::Runner::call(
@@ -271,18 +350,10 @@ pub mod pallet {
 			};
 
 			Ok(PostDispatchInfo {
-				actual_weight: {
-					let mut gas_to_weight = T::GasWeightMapping::gas_to_weight(
-						info.used_gas.standard.unique_saturated_into(),
-						true,
-					);
-					if let Some(weight_info) = info.weight_info {
-						if let Some(proof_size_usage) = weight_info.proof_size_usage {
-							*gas_to_weight.proof_size_mut() = proof_size_usage;
-						}
-					}
-					Some(gas_to_weight)
-				},
+				actual_weight: Some(
+					T::GasWeightMapping::used_gas_to_weight(&info.used_gas, info.weight_info, true)
+						.saturating_add(extrinsic_weight),
+				),
 				pays_fee: Pays::No,
 			})
 		}
@@ -293,6 +364,10 @@ pub mod pallet {
 		#[pallet::weight({
 			let without_base_extrinsic_weight = true;
 			T::GasWeightMapping::gas_to_weight(*gas_limit, without_base_extrinsic_weight)
+				.saturating_add(<T as pallet::Config>::WeightInfo::create(
+					init.len() as u32,
+					access_list.len() as u32,
+				))
 		})]
 		pub fn create(
 			origin: OriginFor<T>,
@@ -305,8 +380,14 @@ pub mod pallet {
 			nonce: Option<U256>,
 			access_list: Vec<(H160, Vec<H256>)>,
 		) -> DispatchResultWithPostInfo {
+			Self::ensure_execution_extrinsics_enabled()?;
 			T::CallOrigin::ensure_address_origin(&source, origin)?;
 
+			let extrinsic_weight = <T as pallet::Config>::WeightInfo::create(
+				init.len() as u32,
+				access_list.len() as u32,
+			);
+
 			let is_transactional = true;
 			let validate = true;
 			let info = match T::Runner::create(
@@ -358,18 +439,10 @@ pub mod pallet {
 			}
 
 			Ok(PostDispatchInfo {
-				actual_weight: {
-					let mut gas_to_weight = T::GasWeightMapping::gas_to_weight(
-						info.used_gas.standard.unique_saturated_into(),
-						true,
-					);
-					if let Some(weight_info) = info.weight_info {
-						if let Some(proof_size_usage) = weight_info.proof_size_usage {
-							*gas_to_weight.proof_size_mut() = proof_size_usage;
-						}
-					}
-					Some(gas_to_weight)
-				},
+				actual_weight: Some(
+					T::GasWeightMapping::used_gas_to_weight(&info.used_gas, info.weight_info, true)
+						.saturating_add(extrinsic_weight),
+				),
 				pays_fee: Pays::No,
 			})
 		}
@@ -379,6 +452,10 @@ pub mod pallet {
 		#[pallet::weight({
 			let without_base_extrinsic_weight = true;
 			T::GasWeightMapping::gas_to_weight(*gas_limit, without_base_extrinsic_weight)
+				.saturating_add(<T as pallet::Config>::WeightInfo::create2(
+					init.len() as u32,
+					access_list.len() as u32,
+				))
 		})]
 		pub fn create2(
 			origin: OriginFor<T>,
@@ -392,8 +469,14 @@ pub mod pallet {
 			nonce: Option<U256>,
 			access_list: Vec<(H160, Vec<H256>)>,
 		) -> DispatchResultWithPostInfo {
+			Self::ensure_execution_extrinsics_enabled()?;
 			T::CallOrigin::ensure_address_origin(&source, origin)?;
 
+			let extrinsic_weight = <T as pallet::Config>::WeightInfo::create2(
+				init.len() as u32,
+				access_list.len() as u32,
+			);
+
 			let is_transactional = true;
 			let validate = true;
 			let info = match T::Runner::create2(
@@ -446,21 +529,129 @@ pub mod pallet {
 			}
 
 			Ok(PostDispatchInfo {
-				actual_weight: {
-					let mut gas_to_weight = T::GasWeightMapping::gas_to_weight(
-						info.used_gas.standard.unique_saturated_into(),
-						true,
-					);
-					if let Some(weight_info) = info.weight_info {
-						if let Some(proof_size_usage) = weight_info.proof_size_usage {
-							*gas_to_weight.proof_size_mut() = proof_size_usage;
-						}
-					}
-					Some(gas_to_weight)
-				},
+				actual_weight: Some(
+					T::GasWeightMapping::used_gas_to_weight(&info.used_gas, info.weight_info, true)
+						.saturating_add(extrinsic_weight),
+				),
 				pays_fee: Pays::No,
 			})
 		}
+
+		/// Allow or disallow `deployer` to deploy contracts, when deployments are restricted to
+		/// the [`AllowedDeployers`] allow-list.
+		#[pallet::call_index(4)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_deployer_allowed())]
+		pub fn set_deployer_allowed(
+			origin: OriginFor<T>,
+			deployer: H160,
+			allowed: bool,
+		) -> DispatchResult {
+			T::DeployerAllowListOrigin::ensure_origin(origin)?;
+
+			if allowed {
+				<AllowedDeployers<T>>::insert(deployer, ());
+			} else {
+				<AllowedDeployers<T>>::remove(deployer);
+			}
+			Pallet::<T>::deposit_event(Event::<T>::DeployerAllowListUpdated { deployer, allowed });
+			Ok(())
+		}
+
+		/// Block or unblock the calls to `target`, when calls are filtered with the
+		/// [`BlockedCallTargets`] block-list.
+		#[pallet::call_index(5)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_call_target_blocked())]
+		pub fn set_call_target_blocked(
+			origin: OriginFor<T>,
+			target: H160,
+			blocked: bool,
+		) -> DispatchResult {
+			T::CallBlockListOrigin::ensure_origin(origin)?;
+
+			if blocked {
+				<BlockedCallTargets<T>>::insert(target, ());
+			} else {
+				<BlockedCallTargets<T>>::remove(target);
+			}
+			Pallet::<T>::deposit_event(Event::<T>::CallBlockListUpdated { target, blocked });
+			Ok(())
+		}
+
+		/// Set the nonce of `address`, e.g. to recover an account stuck by a bridge bug.
+		#[pallet::call_index(6)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::force_set_nonce())]
+		pub fn force_set_nonce(
+			origin: OriginFor<T>,
+			address: H160,
+			nonce: T::Nonce,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+
+			let account_id = T::AddressMapping::into_account_id(address);
+			frame_system::Account::<T>::mutate(&account_id, |account| account.nonce = nonce);
+			Pallet::<T>::deposit_event(Event::<T>::NonceSet { address, nonce });
+			Ok(())
+		}
+
+		/// Remove the code of the contract at `address` and up to `limit` of its storage
+		/// entries. The call is repeated until the storage is fully removed.
+		#[pallet::call_index(7)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::force_remove_contract(*limit))]
+		pub fn force_remove_contract(
+			origin: OriginFor<T>,
+			address: H160,
+			limit: u32,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+
+			if let Some(hash) = <AccountCodes<T>>::take(address) {
+				Pallet::<T>::remove_code_reference(hash);
+				<AccountCodesMetadata<T>>::remove(address);
+				// The sufficient reference is released once the storage is fully removed.
+				<Suicided<T>>::insert(address, ());
+			}
+
+			#[allow(deprecated)]
+			let res = <AccountStorages<T>>::remove_prefix(address, Some(limit));
+			let (removed, complete) = match res {
+				KillStorageResult::AllRemoved(removed) => (removed, true),
+				KillStorageResult::SomeRemaining(removed) => (removed, false),
+			};
+			if complete && <Suicided<T>>::take(address).is_some() {
+				let account_id = T::AddressMapping::into_account_id(address);
+				let _ = frame_system::Pallet::<T>::dec_sufficients(&account_id);
+			}
+
+			Pallet::<T>::deposit_event(Event::<T>::ContractRemoved {
+				address,
+				removed,
+				complete,
+			});
+			Ok(())
+		}
+
+		/// Set the block gas limit, overriding [`Config::BlockGasLimit`], or clear it with
+		/// `None`. It applies from the next transaction on.
+		#[pallet::call_index(8)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_block_gas_limit())]
+		pub fn set_block_gas_limit(
+			origin: OriginFor<T>,
+			gas_limit: Option<U256>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+
+			match gas_limit {
+				Some(gas_limit) => {
+					ensure!(!gas_limit.is_zero(), Error::<T>::GasLimitTooLow);
+					<BlockGasLimitOverride<T>>::put(gas_limit);
+				}
+				None => <BlockGasLimitOverride<T>>::kill(),
+			}
+			Pallet::<T>::deposit_event(Event::<T>::BlockGasLimitSet {
+				gas_limit: Self::block_gas_limit(),
+			});
+			Ok(())
+		}
 	}
 
 	#[pallet::event]
@@ -476,6 +667,21 @@ pub mod pallet {
 		Executed { address: H160 },
 		/// A contract has been executed with errors. States are reverted with only gas fees applied.
 		ExecutedFailed { address: H160 },
+		/// An account was added to or removed from the deployers allow-list.
+		DeployerAllowListUpdated { deployer: H160, allowed: bool },
+		/// An address was added to or removed from the call targets block-list.
+		CallBlockListUpdated { target: H160, blocked: bool },
+		/// The nonce of an account was set by governance.
+		NonceSet { address: H160, nonce: T::Nonce },
+		/// The code and `removed` storage entries of a contract were removed by governance,
+		/// `complete` once its storage is fully removed.
+		ContractRemoved {
+			address: H160,
+			removed: u32,
+			complete: bool,
+		},
+		/// The block gas limit was set by governance.
+		BlockGasLimitSet { gas_limit: U256 },
 	}
 
 	#[pallet::error]
@@ -504,6 +710,14 @@ pub mod pallet {
 		Reentrancy,
 		/// EIP-3607,
 		TransactionMustComeFromEOA,
+		/// The `call`, `create` and `create2` extrinsics are disabled.
+		ExecutionExtrinsicsDisabled,
+		/// The execution was rejected by the execution hooks.
+		ExecutionRejected,
+		/// The source is not allowed to deploy contracts.
+		CreateOriginNotAllowed,
+		/// The call is blocked by the call filter.
+		CallNotAllowed,
 		/// Undefined error.
 		Undefined,
 	}
@@ -522,6 +736,9 @@ pub mod pallet {
 				TransactionValidationError::InvalidChainId => Error::<T>::InvalidChainId,
 				TransactionValidationError::InvalidSignature => Error::<T>::InvalidSignature,
 				TransactionValidationError::UnknownError => Error::<T>::Undefined,
+				TransactionValidationError::CallNotAllowed => Error::<T>::CallNotAllowed,
+				TransactionValidationError::GasPriceBelowMinimum => Error::<T>::GasPriceTooLow,
+				TransactionValidationError::CreateNotAllowed => Error::<T>::CreateOriginNotAllowed,
 			}
 		}
 	}
@@ -568,8 +785,17 @@ pub mod pallet {
 		}
 	}
 
+	/// Hash of the code of each contract, the code itself is stored in [`Codes`].
 	#[pallet::storage]
-	pub type AccountCodes<T: Config> = StorageMap<_, Blake2_128Concat, H160, Vec<u8>, ValueQuery>;
+	pub type AccountCodes<T: Config> = StorageMap<_, Blake2_128Concat, H160, H256, OptionQuery>;
+
+	/// Contract code, stored once per keccak-256 hash whatever the number of contracts using it.
+	#[pallet::storage]
+	pub type Codes<T: Config> = StorageMap<_, Identity, H256, Vec<u8>, ValueQuery>;
+
+	/// Number of contracts using each code in [`Codes`].
+	#[pallet::storage]
+	pub type CodeRefCounts<T: Config> = StorageMap<_, Identity, H256, u32, ValueQuery>;
 
 	#[pallet::storage]
 	pub type AccountCodesMetadata<T: Config> =
@@ -581,6 +807,24 @@ pub mod pallet {
 
 	#[pallet::storage]
 	pub type Suicided<T: Config> = StorageMap<_, Blake2_128Concat, H160, (), OptionQuery>;
+
+	/// Fees paid by the transactions executed in the current block.
+	#[pallet::storage]
+	pub type CurrentBlockFees<T: Config> = StorageValue<_, BlockFees, ValueQuery>;
+
+	/// Accounts allowed to deploy contracts when deployments are restricted to this allow-list,
+	/// see [`AllowedDeployersOnly`].
+	#[pallet::storage]
+	pub type AllowedDeployers<T: Config> = StorageMap<_, Blake2_128Concat, H160, (), OptionQuery>;
+
+	/// Addresses calls are rejected to when calls are filtered with this block-list, see
+	/// [`BlockedCallTargetsFilter`].
+	#[pallet::storage]
+	pub type BlockedCallTargets<T: Config> = StorageMap<_, Blake2_128Concat, H160, (), OptionQuery>;
+
+	/// Block gas limit set by governance, [`Config::BlockGasLimit`] applying while unset.
+	#[pallet::storage]
+	pub type BlockGasLimitOverride<T: Config> = StorageValue<_, U256, OptionQuery>;
 }
 
 /// Type alias for currency balance.
@@ -765,6 +1009,24 @@ impl<T: Config> BlockHashMapping for SubstrateBlockHashMapping<T> {
 pub trait GasWeightMapping {
 	fn gas_to_weight(gas: u64, without_base_weight: bool) -> Weight;
 	fn weight_to_gas(weight: Weight) -> u64;
+
+	/// Weight effectively consumed by an execution, in both dimensions.
+	///
+	/// The ref time follows the larger of the standard and effective gas, the latter accounting
+	/// for proof size and storage growth translated into gas. When the execution was metered, the
+	/// proof size is the one actually recorded, otherwise the one derived from the gas.
+	fn used_gas_to_weight(
+		used_gas: &fp_evm::UsedGas,
+		weight_info: Option<fp_evm::WeightInfo>,
+		without_base_weight: bool,
+	) -> Weight {
+		let gas = core::cmp::max(used_gas.standard, used_gas.effective);
+		let mut weight = Self::gas_to_weight(gas.unique_saturated_into(), without_base_weight);
+		if let Some(proof_size_usage) = weight_info.and_then(|info| info.proof_size_usage) {
+			*weight.proof_size_mut() = proof_size_usage;
+		}
+		weight
+	}
 }
 
 pub struct FixedGasWeightMapping<T>(core::marker::PhantomData<T>);
@@ -792,15 +1054,62 @@ impl<T: Config> GasWeightMapping for FixedGasWeightMapping<T> {
 	}
 }
 
+impl<T: Config> Pallet<T> {
+	/// Apply the configured code size limits on top of an EVM config, borrowing it unchanged when
+	/// none are configured.
+	pub fn with_code_size_limits(config: &EvmConfig) -> Cow<'_, EvmConfig> {
+		let code_size_limit = T::CodeSizeLimit::get();
+		let initcode_size_limit = T::InitcodeSizeLimit::get();
+		if code_size_limit.is_none() && initcode_size_limit.is_none() {
+			return Cow::Borrowed(config);
+		}
+
+		let mut config = config.clone();
+		if let Some(limit) = code_size_limit {
+			config.create_contract_limit = Some(limit as usize);
+		}
+		if let Some(limit) = initcode_size_limit {
+			config.max_initcode_size = Some(limit as usize);
+		}
+		Cow::Owned(config)
+	}
+}
+
+static LONDON_CONFIG: EvmConfig = EvmConfig::london();
 static SHANGHAI_CONFIG: EvmConfig = EvmConfig::shanghai();
 
 impl<T: Config> Pallet<T> {
+	/// Ensure the `call`, `create` and `create2` extrinsics are enabled.
+	///
+	/// A rejected extrinsic is only charged the base extrinsic weight, not the weight derived
+	/// from its gas limit.
+	fn ensure_execution_extrinsics_enabled() -> Result<(), DispatchErrorWithPostInfo> {
+		if T::ExecutionExtrinsicsEnabled::get() {
+			return Ok(());
+		}
+
+		Err(DispatchErrorWithPostInfo {
+			post_info: PostDispatchInfo {
+				actual_weight: Some(Weight::zero()),
+				pays_fee: Pays::Yes,
+			},
+			error: Error::<T>::ExecutionExtrinsicsDisabled.into(),
+		})
+	}
+
+	/// The block gas limit: the one set by governance if any, [`Config::BlockGasLimit`]
+	/// otherwise.
+	pub fn block_gas_limit() -> U256 {
+		<BlockGasLimitOverride<T>>::get().unwrap_or_else(T::BlockGasLimit::get)
+	}
+
 	/// Check whether an account is empty.
 	pub fn is_account_empty(address: &H160) -> bool {
 		let (account, _) = Self::account_basic(address);
-		let code_len = <AccountCodes<T>>::decode_len(address).unwrap_or(0);
 
-		account.nonce == U256::zero() && account.balance == U256::zero() && code_len == 0
+		account.nonce == U256::zero()
+			&& account.balance == U256::zero()
+			&& !<AccountCodes<T>>::contains_key(address)
 	}
 	/// Check whether an account is a suicided contract
 	pub fn is_account_suicided(address: &H160) -> bool {
@@ -820,7 +1129,9 @@ impl<T: Config> Pallet<T> {
 
 	/// Remove an account.
 	pub fn remove_account(address: &H160) {
-		if <AccountCodes<T>>::contains_key(address) {
+		if let Some(hash) = <AccountCodes<T>>::take(address) {
+			Self::remove_code_reference(hash);
+
 			// Remember to call `dec_sufficients` when clearing Suicided.
 			<Suicided<T>>::insert(address, ());
 
@@ -830,7 +1141,6 @@ impl<T: Config> Pallet<T> {
 			frame_system::Pallet::<T>::inc_account_nonce(&account_id);
 		}
 
-		<AccountCodes<T>>::remove(address);
 		<AccountCodesMetadata<T>>::remove(address);
 
 		if T::SuicideQuickClearLimit::get() > 0 {
@@ -863,16 +1173,53 @@ impl<T: Config> Pallet<T> {
 			return;
 		}
 
-		if !<AccountCodes<T>>::contains_key(address) {
-			let account_id = T::AddressMapping::into_account_id(address);
-			let _ = frame_system::Pallet::<T>::inc_sufficients(&account_id);
-		}
-
 		// Update metadata.
 		let meta = CodeMetadata::from_code(&code);
 		<AccountCodesMetadata<T>>::insert(address, meta);
 
-		<AccountCodes<T>>::insert(address, code);
+		let hash = Self::add_code_reference(code);
+		match <AccountCodes<T>>::get(address) {
+			Some(previous_hash) => Self::remove_code_reference(previous_hash),
+			None => {
+				let account_id = T::AddressMapping::into_account_id(address);
+				let _ = frame_system::Pallet::<T>::inc_sufficients(&account_id);
+			}
+		}
+		<AccountCodes<T>>::insert(address, hash);
+	}
+
+	/// Get the code of an account, empty if the account is not a contract.
+	pub fn account_code(address: H160) -> Vec<u8> {
+		<AccountCodes<T>>::get(address)
+			.map(<Codes<T>>::get)
+			.unwrap_or_default()
+	}
+
+	/// Take a reference to `code` for a contract, storing it if no other contract uses it.
+	/// Returns the hash of the code.
+	pub fn add_code_reference(code: Vec<u8>) -> H256 {
+		let hash = H256::from(sp_io::hashing::keccak_256(&code));
+		<CodeRefCounts<T>>::mutate(hash, |count| {
+			if *count == 0 {
+				<Codes<T>>::insert(hash, code);
+			}
+			*count = count.saturating_add(1);
+		});
+		hash
+	}
+
+	/// Release a reference to the code of hash `hash`, removing the code once no contract uses
+	/// it anymore.
+	pub fn remove_code_reference(hash: H256) {
+		<CodeRefCounts<T>>::mutate_exists(hash, |count| {
+			let remaining = count.unwrap_or_default().saturating_sub(1);
+			if remaining == 0 {
+				<Codes<T>>::remove(hash);
+				*count = None;
+			} else {
+				*count = Some(remaining);
+			}
+		});
 	}
 
 	/// Get the account metadata (hash and size) from storage if it exists,
@@ -882,11 +1229,9 @@ impl<T: Config> Pallet<T> {
 			return meta;
 		}
 
-		let code = <AccountCodes<T>>::get(address);
-
 		// If code is empty we return precomputed hash for empty code.
 		// We don't store it as this address could get code deployed in the future.
-		if code.is_empty() {
+		let Some(hash) = <AccountCodes<T>>::get(address) else {
 			const EMPTY_CODE_HASH: [u8; 32] = hex_literal::hex!(
 				"c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
 			);
@@ -894,14 +1239,31 @@ impl<T: Config> Pallet<T> {
 				size: 0,
 				hash: EMPTY_CODE_HASH.into(),
 			};
-		}
+		};
 
-		let meta = CodeMetadata::from_code(&code);
+		let size = <Codes<T>>::decode_len(hash).unwrap_or(0) as u64;
+		let meta = CodeMetadata { size, hash };
 
 		<AccountCodesMetadata<T>>::insert(address, meta);
 		meta
 	}
 
+	/// The runtime storage keys of the account of `address`, whose storage proof proves its
+	/// nonce, balance and code hash.
+	pub fn account_proof_keys(address: H160) -> Vec<Vec<u8>> {
+		let account_id = T::AddressMapping::into_account_id(address);
+		alloc::vec![
+			frame_system::Account::<T>::hashed_key_for(&account_id),
+			<AccountCodes<T>>::hashed_key_for(address),
+		]
+	}
+
+	/// The runtime storage key of the `index` storage slot of `address`, whose storage proof
+	/// proves its value.
+	pub fn storage_proof_key(address: H160, index: H256) -> Vec<u8> {
+		<AccountStorages<T>>::hashed_key_for(address, index)
+	}
+
 	/// Get the account basic in EVM format.
 	pub fn account_basic(address: &H160) -> (Account, frame_support::weights::Weight) {
 		let account_id = T::AddressMapping::into_account_id(*address);
@@ -952,13 +1314,59 @@ pub trait OnChargeEVMTransaction<T: Config> {
 	fn pay_priority_fee(tip: Self::LiquidityInfo);
 }
 
+/// Handles the priority fee (tip) of EVM transactions, separately from the base fee which is
+/// handled by the `OnUnbalanced` of the `OnChargeEVMTransaction` adapters.
+///
+/// `Tip` is the imbalance withdrawn from the sender for the priority fee.
+pub trait OnPriorityFee<T: Config, Tip> {
+	/// Handle the priority fee `tip`, `pay` depositing it into an account. Dropping `tip` burns
+	/// it.
+	fn on_priority_fee(tip: Tip, pay: impl FnOnce(&T::AccountId, Tip));
+}
+
+/// Burns the priority fee.
+impl<T: Config, Tip> OnPriorityFee<T, Tip> for () {
+	fn on_priority_fee(_tip: Tip, _pay: impl FnOnce(&T::AccountId, Tip)) {}
+}
+
+/// Default Ethereum behaviour: pays the priority fee to the block author.
+pub struct ToBlockAuthor;
+
+impl<T: Config, Tip> OnPriorityFee<T, Tip> for ToBlockAuthor {
+	fn on_priority_fee(tip: Tip, pay: impl FnOnce(&T::AccountId, Tip)) {
+		let author = T::AddressMapping::into_account_id(<Pallet<T>>::find_author());
+		pay(&author, tip);
+	}
+}
+
+/// Pays the priority fee to a fixed account, e.g. a treasury.
+pub struct ToAccount<A>(core::marker::PhantomData<A>);
+
+impl<T: Config, Tip, A: Get<T::AccountId>> OnPriorityFee<T, Tip> for ToAccount<A> {
+	fn on_priority_fee(tip: Tip, pay: impl FnOnce(&T::AccountId, Tip)) {
+		pay(&A::get(), tip);
+	}
+}
+
+/// Routes the priority fee through an `OnUnbalanced` handler, e.g. to split it between the
+/// block author and a treasury.
+pub struct ToUnbalanced<OU>(core::marker::PhantomData<OU>);
+
+impl<T: Config, Tip: TryDrop, OU: OnUnbalanced<Tip>> OnPriorityFee<T, Tip> for ToUnbalanced<OU> {
+	fn on_priority_fee(tip: Tip, _pay: impl FnOnce(&T::AccountId, Tip)) {
+		OU::on_unbalanced(tip);
+	}
+}
+
 /// Implements the transaction payment for a pallet implementing the `Currency`
 /// trait (eg. the pallet_balances) using an unbalance handler (implementing
 /// `OnUnbalanced`).
 /// Similar to `CurrencyAdapter` of `pallet_transaction_payment`
-pub struct EVMCurrencyAdapter<C, OU>(core::marker::PhantomData<(C, OU)>);
+///
+/// The priority fee is routed through `OT`, paying it to the block author by default.
+pub struct EVMCurrencyAdapter<C, OU, OT = ToBlockAuthor>(core::marker::PhantomData<(C, OU, OT)>);
 
-impl<T, C, OU> OnChargeEVMTransaction<T> for EVMCurrencyAdapter<C, OU>
+impl<T, C, OU, OT> OnChargeEVMTransaction<T> for EVMCurrencyAdapter<C, OU, OT>
 where
 	T: Config,
 	C: Currency<<T as frame_system::Config>::AccountId>,
@@ -971,6 +1379,7 @@ where
 		Opposite = C::PositiveImbalance,
 	>,
 	OU: OnUnbalanced<NegativeImbalanceOf<C, T>>,
+	OT: OnPriorityFee<T, NegativeImbalanceOf<C, T>>,
 	U256: UniqueSaturatedInto<<C as Currency<<T as frame_system::Config>::AccountId>>::Balance>,
 {
 	// Kept type as Option to satisfy bound of Default
@@ -1043,10 +1452,11 @@ where
 	}
 
 	fn pay_priority_fee(tip: Self::LiquidityInfo) {
-		// Default Ethereum behaviour: issue the tip to the block author.
+		// A tip that can't be paid out is burned.
 		if let Some(tip) = tip {
-			let account_id = T::AddressMapping::into_account_id(<Pallet<T>>::find_author());
-			let _ = C::deposit_into_existing(&account_id, tip.peek());
+			OT::on_priority_fee(tip, |account_id, tip| {
+				let _ = C::resolve_into_existing(account_id, tip);
+			});
 		}
 	}
 }
@@ -1056,13 +1466,16 @@ where
 ///
 /// Equivalent of `EVMCurrencyAdapter` but for fungible traits. Similar to `FungibleAdapter` of
 /// `pallet_transaction_payment`
-pub struct EVMFungibleAdapter<F, OU>(core::marker::PhantomData<(F, OU)>);
+///
+/// The priority fee is routed through `OT`, paying it to the block author by default.
+pub struct EVMFungibleAdapter<F, OU, OT = ToBlockAuthor>(core::marker::PhantomData<(F, OU, OT)>);
 
-impl<T, F, OU> OnChargeEVMTransaction<T> for EVMFungibleAdapter<F, OU>
+impl<T, F, OU, OT> OnChargeEVMTransaction<T> for EVMFungibleAdapter<F, OU, OT>
 where
 	T: Config,
 	F: Balanced<T::AccountId>,
 	OU: OnUnbalanced<Credit<T::AccountId, F>>,
+	OT: OnPriorityFee<T, Credit<T::AccountId, F>>,
 	U256: UniqueSaturatedInto<<F as Inspect<<T as frame_system::Config>::AccountId>>::Balance>,
 {
 	// Kept type as Option to satisfy bound of Default
@@ -1116,10 +1529,114 @@ where
 	}
 
 	fn pay_priority_fee(tip: Self::LiquidityInfo) {
-		// Default Ethereum behaviour: issue the tip to the block author.
+		// A tip that can't be paid out is burned.
+		if let Some(tip) = tip {
+			OT::on_priority_fee(tip, |account_id, tip| {
+				let _ = F::resolve(account_id, tip);
+			});
+		}
+	}
+}
+
+/// Implements the transaction payment in a non-native asset of a pallet implementing the
+/// `fungibles` traits (eg. the pallet_assets or orml_tokens) using an unbalance handler
+/// (implementing `OnUnbalanced`).
+///
+/// `A` selects the asset fees are paid in and `R` the on-chain rate converting an amount of
+/// the native gas token into that asset, i.e. asset units per native unit. The priority fee is
+/// routed through `OT`, paying it to the block author by default.
+pub struct EVMFungiblesAdapter<F, A, R, OU, OT = ToBlockAuthor>(
+	core::marker::PhantomData<(F, A, R, OU, OT)>,
+);
+
+impl<F, A, R, OU, OT> EVMFungiblesAdapter<F, A, R, OU, OT>
+where
+	R: Get<FixedU128>,
+{
+	/// Convert an amount of the native gas token into the fee asset.
+	fn to_asset_amount<Balance>(amount: U256) -> Balance
+	where
+		Balance: From<u128>,
+	{
+		let amount: u128 = amount.unique_saturated_into();
+		R::get().saturating_mul_int(amount).into()
+	}
+}
+
+impl<T, F, A, R, OU, OT> OnChargeEVMTransaction<T> for EVMFungiblesAdapter<F, A, R, OU, OT>
+where
+	T: Config,
+	F: fungibles::Balanced<T::AccountId>,
+	F::Balance: From<u128>,
+	A: Get<F::AssetId>,
+	R: Get<FixedU128>,
+	OU: OnUnbalanced<fungibles::Credit<T::AccountId, F>>,
+	OT: OnPriorityFee<T, fungibles::Credit<T::AccountId, F>>,
+{
+	// Kept type as Option to satisfy bound of Default
+	type LiquidityInfo = Option<fungibles::Credit<T::AccountId, F>>;
+
+	fn withdraw_fee(who: &H160, fee: U256) -> Result<Self::LiquidityInfo, Error<T>> {
+		if fee.is_zero() {
+			return Ok(None);
+		}
+		let account_id = T::AddressMapping::into_account_id(*who);
+		let imbalance = F::withdraw(
+			A::get(),
+			&account_id,
+			Self::to_asset_amount(fee),
+			Precision::Exact,
+			Preservation::Preserve,
+			Fortitude::Polite,
+		)
+		.map_err(|_| Error::<T>::BalanceLow)?;
+		Ok(Some(imbalance))
+	}
+
+	fn correct_and_deposit_fee(
+		who: &H160,
+		corrected_fee: U256,
+		base_fee: U256,
+		already_withdrawn: Self::LiquidityInfo,
+	) -> Self::LiquidityInfo {
+		if let Some(paid) = already_withdrawn {
+			let account_id = T::AddressMapping::into_account_id(*who);
+
+			// Calculate how much refund we should return
+			let refund_amount = paid
+				.peek()
+				.saturating_sub(Self::to_asset_amount(corrected_fee));
+			// refund to the account that paid the fees.
+			let refund_imbalance = F::deposit(
+				paid.asset(),
+				&account_id,
+				refund_amount,
+				Precision::BestEffort,
+			)
+			.unwrap_or_else(|_| fungibles::Debt::<T::AccountId, F>::zero(paid.asset()));
+
+			// merge the imbalance caused by paying the fees and refunding parts of it again.
+			let asset = paid.asset();
+			let adjusted_paid = paid
+				.offset(refund_imbalance)
+				.ok()
+				.and_then(|adjusted| adjusted.same().ok())
+				.unwrap_or_else(|| fungibles::Credit::<T::AccountId, F>::zero(asset));
+
+			let (base_fee, tip) = adjusted_paid.split(Self::to_asset_amount(base_fee));
+			// Handle base fee. Can be either burned, rationed, etc ...
+			OU::on_unbalanced(base_fee);
+			return Some(tip);
+		}
+		None
+	}
+
+	fn pay_priority_fee(tip: Self::LiquidityInfo) {
+		// A tip that can't be paid out is burned.
 		if let Some(tip) = tip {
-			let account_id = T::AddressMapping::into_account_id(<Pallet<T>>::find_author());
-			let _ = F::deposit(&account_id, tip.peek(), Precision::BestEffort);
+			OT::on_priority_fee(tip, |account_id, tip| {
+				let _ = F::resolve(account_id, tip);
+			});
 		}
 	}
 }
@@ -1159,6 +1676,122 @@ where
 	}
 }
 
+/// Filters the accounts allowed to deploy contracts with `create` and `create2`.
+///
+/// Only the deployer of the transaction is checked, contracts created by already deployed
+/// contracts (e.g. factories) are not filtered.
+pub trait CreateOriginFilter<T> {
+	fn is_create_allowed(deployer: &H160) -> bool;
+}
+
+/// Permissionless deployments.
+impl<T> CreateOriginFilter<T> for () {
+	fn is_create_allowed(_deployer: &H160) -> bool {
+		true
+	}
+}
+
+/// Restricts deployments to the accounts of the governance-managed [`AllowedDeployers`].
+pub struct AllowedDeployersOnly;
+
+impl<T: Config> CreateOriginFilter<T> for AllowedDeployersOnly {
+	fn is_create_allowed(deployer: &H160) -> bool {
+		<AllowedDeployers<T>>::contains_key(deployer)
+	}
+}
+
+/// Filters the `call` executions by source, target and input (e.g. the function selector).
+///
+/// Only the top-level call of the transaction is checked, the calls made by contracts are not
+/// filtered.
+pub trait CallFilter<T> {
+	fn is_call_allowed(source: &H160, target: &H160, input: &[u8]) -> bool;
+}
+
+/// No call is filtered.
+impl<T> CallFilter<T> for () {
+	fn is_call_allowed(_source: &H160, _target: &H160, _input: &[u8]) -> bool {
+		true
+	}
+}
+
+/// Rejects the calls to the addresses of the governance-managed [`BlockedCallTargets`].
+pub struct BlockedCallTargetsFilter;
+
+impl<T: Config> CallFilter<T> for BlockedCallTargetsFilter {
+	fn is_call_allowed(_source: &H160, target: &H160, _input: &[u8]) -> bool {
+		!<BlockedCallTargets<T>>::contains_key(target)
+	}
+}
+
+/// Hooks around the executions of the runner, e.g. for custom accounting, circuit breakers or
+/// per-contract metering.
+pub trait ExecutionHooks<T: Config> {
+	/// Called before executing a call to `target`, or a create when `None`. Returning an error
+	/// rejects the execution before any fee is withdrawn.
+	fn before_execution(
+		source: H160,
+		target: Option<H160>,
+		value: U256,
+		gas_limit: u64,
+	) -> Result<(), Error<T>>;
+
+	/// Called after an execution with its outcome, `address` being the called or created
+	/// contract.
+	fn after_execution(
+		source: H160,
+		address: H160,
+		exit_reason: &ExitReason,
+		used_gas: &fp_evm::UsedGas,
+		weight_info: Option<fp_evm::WeightInfo>,
+	);
+}
+
+impl<T: Config> ExecutionHooks<T> for () {
+	fn before_execution(
+		_source: H160,
+		_target: Option<H160>,
+		_value: U256,
+		_gas_limit: u64,
+	) -> Result<(), Error<T>> {
+		Ok(())
+	}
+
+	fn after_execution(
+		_source: H160,
+		_address: H160,
+		_exit_reason: &ExitReason,
+		_used_gas: &fp_evm::UsedGas,
+		_weight_info: Option<fp_evm::WeightInfo>,
+	) {
+	}
+}
+
+#[impl_for_tuples(1, 12)]
+impl<T: Config> ExecutionHooks<T> for Tuple {
+	fn before_execution(
+		source: H160,
+		target: Option<H160>,
+		value: U256,
+		gas_limit: u64,
+	) -> Result<(), Error<T>> {
+		for_tuples!( #( Tuple::before_execution(source, target, value, gas_limit)?; )* );
+		Ok(())
+	}
+
+	fn after_execution(
+		source: H160,
+		address: H160,
+		exit_reason: &ExitReason,
+		used_gas: &fp_evm::UsedGas,
+		weight_info: Option<fp_evm::WeightInfo>,
+	) {
+		for_tuples!( #(
+			Tuple::after_execution(source, address, exit_reason, used_gas, weight_info);
+		)* );
+	}
+}
+
 pub trait OnCreate<T> {
 	fn on_create(owner: H160, contract: H160);
 }
@@ -0,0 +1,124 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Storage migrations of the EVM pallet.
+
+use frame_support::{
+	migrations::{MigrationId, SteppedMigration, SteppedMigrationError},
+	pallet_prelude::*,
+	weights::WeightMeter,
+};
+use sp_core::H160;
+
+use crate::{AccountCodes, Config, Pallet};
+
+/// Identifier of the EVM pallet migrations.
+const PALLET_MIGRATIONS_ID: &[u8; 10] = b"pallet-evm";
+
+pub(crate) mod v0 {
+	use super::*;
+	use alloc::vec::Vec;
+
+	/// The contract code stored by address, before it was deduplicated by code hash.
+	#[frame_support::storage_alias]
+	pub type AccountCodes<T: Config> =
+		StorageMap<Pallet<T>, Blake2_128Concat, H160, Vec<u8>, ValueQuery>;
+}
+
+/// Multi-block migration storing the contract code by hash, once for all the contracts using
+/// it, and the hash of their code by address.
+///
+/// The migration is stepped over as many blocks as needed, it is meant to be run by
+/// `pallet-migrations`. Each contract is migrated in place in [`AccountCodes`], so the cursor is
+/// the address of the last migrated contract.
+pub struct CodeDeduplication<T>(PhantomData<T>);
+
+impl<T: Config> CodeDeduplication<T> {
+	/// Weight of migrating a single contract: its code and reference count are read, and the
+	/// code, reference count, code hash and storage version may be written.
+	///
+	/// The proof holds the code read by address, which can be as large as the contract size
+	/// limit (24KiB unless configured otherwise).
+	pub(crate) fn step_weight() -> Weight {
+		let code_size = Pallet::<T>::with_code_size_limits(T::config())
+			.create_contract_limit
+			.unwrap_or_default() as u64;
+		// `AccountCodes` read: 16 (hash) + 20 (key) + 5 (length) + code size.
+		// `CodeRefCounts` read: 32 (key) + 4 (count).
+		let proof_size = code_size.saturating_add(77);
+
+		T::DbWeight::get()
+			.reads_writes(3, 4)
+			.saturating_add(Weight::from_parts(0, proof_size))
+	}
+}
+
+impl<T: Config> SteppedMigration for CodeDeduplication<T> {
+	type Cursor = H160;
+	type Identifier = MigrationId<10>;
+
+	fn id() -> Self::Identifier {
+		MigrationId {
+			pallet_id: *PALLET_MIGRATIONS_ID,
+			version_from: 0,
+			version_to: 1,
+		}
+	}
+
+	fn step(
+		mut cursor: Option<Self::Cursor>,
+		meter: &mut WeightMeter,
+	) -> Result<Option<Self::Cursor>, SteppedMigrationError> {
+		if Pallet::<T>::on_chain_storage_version() >= 1 {
+			return Ok(None);
+		}
+
+		let required = Self::step_weight();
+		if meter.remaining().any_lt(required) {
+			return Err(SteppedMigrationError::InsufficientWeight { required });
+		}
+
+		loop {
+			if meter.try_consume(required).is_err() {
+				break;
+			}
+
+			let mut iter = match cursor {
+				Some(last) => {
+					v0::AccountCodes::<T>::iter_from(v0::AccountCodes::<T>::hashed_key_for(last))
+				}
+				None => v0::AccountCodes::<T>::iter(),
+			};
+
+			let Some((address, code)) = iter.next() else {
+				StorageVersion::new(1).put::<Pallet<T>>();
+				cursor = None;
+				break;
+			};
+
+			if code.is_empty() {
+				v0::AccountCodes::<T>::remove(address);
+			} else {
+				let hash = Pallet::<T>::add_code_reference(code);
+				AccountCodes::<T>::insert(address, hash);
+			}
+			cursor = Some(address);
+		}
+
+		Ok(cursor)
+	}
+}
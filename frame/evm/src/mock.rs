@@ -20,7 +20,7 @@
 use core::str::FromStr;
 use frame_support::{
 	derive_impl, parameter_types,
-	traits::{ConstU32, FindAuthor},
+	traits::{AsEnsureOriginWithArg, ConstU32, ConstU64, FindAuthor},
 	weights::Weight,
 };
 use sp_core::{H160, H256, U256};
@@ -38,6 +38,7 @@ frame_support::construct_runtime! {
 	pub enum Test {
 		System: frame_system::{Pallet, Call, Config<T>, Storage, Event<T>},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
 		Timestamp: pallet_timestamp::{Pallet, Call, Storage},
 		EVM: crate::{Pallet, Call, Storage, Config<T>, Event<T>},
 	}
@@ -96,6 +97,29 @@ impl pallet_balances::Config for Test {
 	type MaxFreezes = ();
 }
 
+impl pallet_assets::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = u64;
+	type RemoveItemsLimit = ConstU32<1000>;
+	type AssetId = u32;
+	type AssetIdParameter = u32;
+	type Currency = Balances;
+	type CreateOrigin = AsEnsureOriginWithArg<frame_system::EnsureSigned<Self::AccountId>>;
+	type ForceOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type AssetDeposit = ConstU64<0>;
+	type AssetAccountDeposit = ConstU64<0>;
+	type MetadataDepositBase = ConstU64<0>;
+	type MetadataDepositPerByte = ConstU64<0>;
+	type ApprovalDeposit = ConstU64<0>;
+	type StringLimit = ConstU32<50>;
+	type Freezer = ();
+	type Extra = ();
+	type CallbackHandle = ();
+	type WeightInfo = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
+}
+
 parameter_types! {
 	pub const MinimumPeriod: u64 = 1000;
 }
@@ -129,9 +153,17 @@ const MAX_POV_SIZE: u64 = 5 * 1024 * 1024;
 parameter_types! {
 	pub BlockGasLimit: U256 = U256::from(BLOCK_GAS_LIMIT);
 	pub const GasLimitPovSizeRatio: u64 = BLOCK_GAS_LIMIT.saturating_div(MAX_POV_SIZE);
+	pub static GasLimitStorageGrowthRatio: u64 = 0;
 	pub WeightPerGas: Weight = Weight::from_parts(20_000, 0);
 	pub MockPrecompiles: MockPrecompileSet = MockPrecompileSet;
 	pub SuicideQuickClearLimit: u32 = 0;
+	pub static ExecutionExtrinsicsEnabled: bool = true;
+	pub static ShanghaiBlock: u64 = 0;
+	pub static CodeSizeLimit: Option<u32> = None;
+	pub static InitcodeSizeLimit: Option<u32> = None;
+	pub static RejectedSource: Option<H160> = None;
+	pub static PermissionedCreate: bool = false;
+	pub static ExecutedAddresses: Vec<H160> = Vec::new();
 }
 impl crate::Config for Test {
 	type FeeCalculator = FixedGasPrice;
@@ -153,10 +185,20 @@ impl crate::Config for Test {
 	type Runner = crate::runner::stack::Runner<Self>;
 	type OnChargeTransaction = ();
 	type OnCreate = ();
+	type ExecutionHooks = MockExecutionHooks;
+	type CreateOriginFilter = MockCreateOriginFilter;
+	type DeployerAllowListOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type CallFilter = crate::BlockedCallTargetsFilter;
+	type CallBlockListOrigin = frame_system::EnsureRoot<Self::AccountId>;
 	type FindAuthor = FindAuthorTruncated;
 	type GasLimitPovSizeRatio = GasLimitPovSizeRatio;
+	type GasLimitStorageGrowthRatio = GasLimitStorageGrowthRatio;
 	type SuicideQuickClearLimit = SuicideQuickClearLimit;
 	type Timestamp = Timestamp;
+	type ExecutionExtrinsicsEnabled = ExecutionExtrinsicsEnabled;
+	type ShanghaiBlock = ShanghaiBlock;
+	type CodeSizeLimit = CodeSizeLimit;
+	type InitcodeSizeLimit = InitcodeSizeLimit;
 	type WeightInfo = ();
 }
 
@@ -186,3 +228,40 @@ impl PrecompileSet for MockPrecompileSet {
 		}
 	}
 }
+
+/// Rejects executions from `RejectedSource` and records the executed addresses.
+pub struct MockExecutionHooks;
+impl crate::ExecutionHooks<Test> for MockExecutionHooks {
+	fn before_execution(
+		source: H160,
+		_target: Option<H160>,
+		_value: U256,
+		_gas_limit: u64,
+	) -> Result<(), crate::Error<Test>> {
+		if RejectedSource::get() == Some(source) {
+			return Err(crate::Error::<Test>::ExecutionRejected);
+		}
+		Ok(())
+	}
+
+	fn after_execution(
+		_source: H160,
+		address: H160,
+		_exit_reason: &crate::ExitReason,
+		_used_gas: &fp_evm::UsedGas,
+		_weight_info: Option<fp_evm::WeightInfo>,
+	) {
+		ExecutedAddresses::mutate(|addresses| addresses.push(address));
+	}
+}
+
+/// Restricts deployments to the allow-list when `PermissionedCreate` is set.
+pub struct MockCreateOriginFilter;
+impl crate::CreateOriginFilter<Test> for MockCreateOriginFilter {
+	fn is_create_allowed(deployer: &H160) -> bool {
+		!PermissionedCreate::get()
+			|| <crate::AllowedDeployersOnly as crate::CreateOriginFilter<Test>>::is_create_allowed(
+				deployer,
+			)
+	}
+}
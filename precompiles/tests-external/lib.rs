@@ -25,7 +25,9 @@ use std::{cell::RefCell, rc::Rc};
 
 // Substrate
 use frame_support::{
-	construct_runtime, derive_impl, parameter_types, traits::Everything, weights::Weight,
+	construct_runtime, derive_impl, parameter_types,
+	traits::{ConstBool, Everything},
+	weights::Weight,
 };
 use sp_core::{H160, H256, U256};
 use sp_runtime::{
@@ -246,10 +248,20 @@ impl pallet_evm::Config for Runtime {
 	type Runner = pallet_evm::runner::stack::Runner<Self>;
 	type OnChargeTransaction = ();
 	type OnCreate = ();
+	type ExecutionHooks = ();
+	type CreateOriginFilter = ();
+	type DeployerAllowListOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type CallFilter = ();
+	type CallBlockListOrigin = frame_system::EnsureRoot<Self::AccountId>;
 	type FindAuthor = ();
 	type GasLimitPovSizeRatio = GasLimitPovSizeRatio;
+	type GasLimitStorageGrowthRatio = ();
 	type SuicideQuickClearLimit = SuicideQuickClearLimit;
 	type Timestamp = Timestamp;
+	type ExecutionExtrinsicsEnabled = ConstBool<true>;
+	type ShanghaiBlock = ();
+	type CodeSizeLimit = ();
+	type InitcodeSizeLimit = ();
 	type WeightInfo = pallet_evm::weights::SubstrateWeight<Runtime>;
 }
 
@@ -395,7 +407,7 @@ fn get_address_type_works_for_eoa() {
 fn get_address_type_works_for_precompile() {
 	ExtBuilder::default().build().execute_with(|| {
 		let addr = H160::repeat_byte(0x1d);
-		pallet_evm::AccountCodes::<Runtime>::insert(addr, vec![0x60, 0x00, 0x60, 0x00, 0xfd]);
+		pallet_evm::Pallet::<Runtime>::create_account(addr, vec![0x60, 0x00, 0x60, 0x00, 0xfd]);
 		assert_eq!(
 			AddressType::Precompile,
 			get_address_type::<Runtime>(&mut MockPrecompileHandle, addr).expect("OOG")
@@ -409,7 +421,7 @@ fn get_address_type_works_for_smart_contract() {
 		let addr = H160::repeat_byte(0x1d);
 
 		// length > 5
-		pallet_evm::AccountCodes::<Runtime>::insert(
+		pallet_evm::Pallet::<Runtime>::create_account(
 			addr,
 			vec![0x60, 0x00, 0x60, 0x00, 0xfd, 0xff, 0xff],
 		);
@@ -419,7 +431,7 @@ fn get_address_type_works_for_smart_contract() {
 		);
 
 		// length < 5
-		pallet_evm::AccountCodes::<Runtime>::insert(addr, vec![0x60, 0x00, 0x60]);
+		pallet_evm::Pallet::<Runtime>::create_account(addr, vec![0x60, 0x00, 0x60]);
 		assert_eq!(
 			AddressType::Contract,
 			get_address_type::<Runtime>(&mut MockPrecompileHandle, addr).expect("OOG")
@@ -431,7 +443,7 @@ fn get_address_type_works_for_smart_contract() {
 fn get_address_type_works_for_unknown() {
 	ExtBuilder::default().build().execute_with(|| {
 		let addr = H160::repeat_byte(0x1d);
-		pallet_evm::AccountCodes::<Runtime>::insert(addr, vec![0x11, 0x00, 0x60, 0x00, 0xfd]);
+		pallet_evm::Pallet::<Runtime>::create_account(addr, vec![0x11, 0x00, 0x60, 0x00, 0xfd]);
 		assert_eq!(
 			AddressType::Unknown,
 			get_address_type::<Runtime>(&mut MockPrecompileHandle, addr).expect("OOG")
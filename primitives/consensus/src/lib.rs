@@ -54,6 +54,9 @@ pub enum PostLog {
 	/// Ethereum block hash.
 	#[codec(index = 3)]
 	BlockHash(H256),
+	/// Ethereum block hash, txn hashes and receipts trie root.
+	#[codec(index = 4)]
+	HashesWithReceiptsRoot(Hashes, H256),
 }
 
 #[derive(Decode, Encode, Clone, PartialEq, Eq)]
@@ -109,6 +112,16 @@ fn _find_log<Log: Decode>(
 	found.ok_or(FindLogError::NotFound)
 }
 
+/// Find the Ethereum receipts trie root committed to in the digest, if any.
+///
+/// Allows verifying receipt proofs against the header only.
+pub fn find_receipts_root(digest: &Digest) -> Option<H256> {
+	match find_post_log(digest) {
+		Ok(PostLog::HashesWithReceiptsRoot(_, receipts_root)) => Some(receipts_root),
+		_ => None,
+	}
+}
+
 pub fn find_log(digest: &Digest) -> Result<Log, FindLogError> {
 	let mut found = None;
 
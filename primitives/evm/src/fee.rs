@@ -0,0 +1,174 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Fee arithmetic shared by the transaction validation, the execution and the rpc.
+//!
+//! Every operation either saturates towards a value which is safe for its use, as documented,
+//! or is checked and returns `None` on overflow.
+
+use sp_core::U256;
+
+/// Priority fee per gas actually paid by an EIP-1559 transaction: the tip, capped so the
+/// total fee per gas does not exceed `max_fee_per_gas`.
+///
+/// Zero without a tip or when `max_fee_per_gas` does not cover the base fee.
+pub fn effective_priority_fee(
+	base_fee: U256,
+	max_fee_per_gas: U256,
+	max_priority_fee_per_gas: Option<U256>,
+) -> U256 {
+	max_priority_fee_per_gas.map_or(U256::zero(), |max_priority_fee_per_gas| {
+		max_fee_per_gas
+			.saturating_sub(base_fee)
+			.min(max_priority_fee_per_gas)
+	})
+}
+
+/// Fee per gas actually paid by an EIP-1559 transaction: the base fee plus the effective
+/// priority fee.
+///
+/// Never overflows, the priority fee being capped by `max_fee_per_gas - base_fee`.
+pub fn effective_gas_price(
+	base_fee: U256,
+	max_fee_per_gas: U256,
+	max_priority_fee_per_gas: Option<U256>,
+) -> U256 {
+	base_fee.saturating_add(effective_priority_fee(
+		base_fee,
+		max_fee_per_gas,
+		max_priority_fee_per_gas,
+	))
+}
+
+/// Priority fee per gas of a legacy or EIP-2930 transaction: everything above the base fee.
+pub fn legacy_priority_fee(base_fee: U256, gas_price: U256) -> U256 {
+	gas_price.saturating_sub(base_fee)
+}
+
+/// Whether the tip of an EIP-1559 transaction is within its fee cap.
+pub fn is_priority_fee_within_cap(max_fee_per_gas: U256, max_priority_fee_per_gas: U256) -> bool {
+	max_priority_fee_per_gas <= max_fee_per_gas
+}
+
+/// Fee paid for `gas` at `fee_per_gas`, `None` on overflow.
+pub fn fee_for_gas(gas: U256, fee_per_gas: U256) -> Option<U256> {
+	gas.checked_mul(fee_per_gas)
+}
+
+/// Balance needed to send `value` and pay for `gas_limit` at `fee_per_gas`, `None` on
+/// overflow.
+pub fn max_total_payment(value: U256, gas_limit: U256, fee_per_gas: U256) -> Option<U256> {
+	fee_for_gas(gas_limit, fee_per_gas)?.checked_add(value)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_core::U512;
+
+	/// Boundary values the properties are checked against.
+	fn samples() -> [U256; 10] {
+		[
+			U256::zero(),
+			U256::one(),
+			U256::from(2),
+			U256::from(1_000_000_000u64),
+			U256::from(u64::MAX),
+			U256::from(u128::MAX),
+			U256::MAX / 2,
+			U256::MAX / 2 + 1,
+			U256::MAX - 1,
+			U256::MAX,
+		]
+	}
+
+	#[test]
+	fn effective_priority_fee_is_capped() {
+		for base_fee in samples() {
+			for max_fee in samples() {
+				for tip in samples() {
+					let priority_fee = effective_priority_fee(base_fee, max_fee, Some(tip));
+					assert!(priority_fee <= tip);
+					assert!(priority_fee <= max_fee.saturating_sub(base_fee));
+					assert_eq!(
+						priority_fee,
+						core::cmp::min(tip, max_fee.saturating_sub(base_fee))
+					);
+				}
+				assert_eq!(
+					effective_priority_fee(base_fee, max_fee, None),
+					U256::zero()
+				);
+			}
+		}
+	}
+
+	#[test]
+	fn effective_gas_price_is_between_base_fee_and_max_fee() {
+		for base_fee in samples() {
+			for max_fee in samples() {
+				for tip in samples() {
+					let gas_price = effective_gas_price(base_fee, max_fee, Some(tip));
+					assert!(gas_price >= base_fee);
+					if max_fee >= base_fee {
+						assert!(gas_price <= max_fee);
+						assert_eq!(
+							gas_price - base_fee,
+							core::cmp::min(tip, max_fee - base_fee)
+						);
+					} else {
+						assert_eq!(gas_price, base_fee);
+					}
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn fee_computations_detect_overflows() {
+		for gas in samples() {
+			for fee_per_gas in samples() {
+				let exact = gas.full_mul(fee_per_gas);
+				match fee_for_gas(gas, fee_per_gas) {
+					Some(fee) => assert_eq!(exact, U512::from(fee)),
+					None => assert!(exact > U512::from(U256::MAX)),
+				}
+				for value in samples() {
+					let exact = exact + U512::from(value);
+					match max_total_payment(value, gas, fee_per_gas) {
+						Some(total) => assert_eq!(exact, U512::from(total)),
+						None => assert!(exact > U512::from(U256::MAX)),
+					}
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn legacy_priority_fee_works() {
+		assert_eq!(
+			legacy_priority_fee(U256::from(10), U256::from(15)),
+			U256::from(5)
+		);
+		assert_eq!(
+			legacy_priority_fee(U256::from(10), U256::from(5)),
+			U256::zero()
+		);
+		assert!(is_priority_fee_within_cap(U256::from(10), U256::from(10)));
+		assert!(!is_priority_fee_within_cap(U256::from(10), U256::from(11)));
+	}
+}
@@ -20,6 +20,7 @@
 
 extern crate alloc;
 
+pub mod fee;
 mod precompile;
 mod validation;
 
@@ -59,6 +60,16 @@ pub struct Vicinity {
 	pub origin: H160,
 }
 
+/// Fees paid by the Ethereum transactions of a block.
+#[derive(Clone, Copy, Eq, PartialEq, Default, Debug, Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BlockFees {
+	/// Priority fees paid to the block author.
+	pub priority_fees: U256,
+	/// Base fees burned, `gas_used * base_fee` summed over the transactions.
+	pub base_fees_burned: U256,
+}
+
 /// `System::Account` 16(hash) + 20 (key) + 60 (AccountInfo::max_encoded_len)
 pub const ACCOUNT_BASIC_PROOF_SIZE: u64 = 96;
 /// `AccountCodesMetadata` read, temptatively 16 (hash) + 20 (key) + 40 (CodeMetadata).
@@ -81,6 +81,12 @@ pub enum TransactionValidationError {
 	/// Unknown error
 	#[num_enum(default)]
 	UnknownError,
+	/// The call is blocked by the call filter of the runtime
+	CallNotAllowed,
+	/// The transaction gas price is below the minimum gas price of the chain
+	GasPriceBelowMinimum,
+	/// The sender is not allowed to deploy contracts
+	CreateNotAllowed,
 }
 
 impl<'config, E: From<TransactionValidationError>> CheckEvmTransaction<'config, E> {
@@ -137,6 +143,19 @@ impl<'config, E: From<TransactionValidationError>> CheckEvmTransaction<'config,
 		Ok(self)
 	}
 
+	pub fn with_min_gas_price(&self, min_gas_price: U256) -> Result<&Self, E> {
+		// Get fee data from either a legacy or typed transaction input.
+		let (_, effective_gas_price) = self.transaction_fee_input()?;
+		if self.config.is_transactional {
+			// The fee per gas paid at the current base fee is at least the chain minimum, which
+			// acts as a tip floor while the base fee is below it.
+			if effective_gas_price.unwrap_or_default() < min_gas_price {
+				return Err(TransactionValidationError::GasPriceBelowMinimum.into());
+			}
+		}
+		Ok(self)
+	}
+
 	pub fn with_balance_for(&self, who: &Account) -> Result<&Self, E> {
 		// Get fee data from either a legacy or typed transaction input.
 		let (max_fee_per_gas, _) = self.transaction_fee_input()?;
@@ -150,9 +169,15 @@ impl<'config, E: From<TransactionValidationError>> CheckEvmTransaction<'config,
 		//
 		// Fee for Legacy or EIP-2930 transaction is calculated using
 		// the provided `gas_price`.
-		let fee = max_fee_per_gas.saturating_mul(self.transaction.gas_limit);
-		if self.config.is_transactional || fee > U256::zero() {
-			let total_payment = self.transaction.value.saturating_add(fee);
+		let fee = crate::fee::fee_for_gas(self.transaction.gas_limit, max_fee_per_gas);
+		if self.config.is_transactional || fee != Some(U256::zero()) {
+			// No balance can cover an overflowing payment.
+			let total_payment = crate::fee::max_total_payment(
+				self.transaction.value,
+				self.transaction.gas_limit,
+				max_fee_per_gas,
+			)
+			.ok_or(TransactionValidationError::BalanceTooLow)?;
 			if who.balance < total_payment {
 				return Err(TransactionValidationError::BalanceTooLow.into());
 			}
@@ -177,15 +202,17 @@ impl<'config, E: From<TransactionValidationError>> CheckEvmTransaction<'config,
 			}
 			// EIP-1559 tip.
 			(None, Some(max_fee_per_gas), Some(max_priority_fee_per_gas)) => {
-				if max_priority_fee_per_gas > max_fee_per_gas {
+				if !crate::fee::is_priority_fee_within_cap(
+					max_fee_per_gas,
+					max_priority_fee_per_gas,
+				) {
 					return Err(TransactionValidationError::PriorityFeeTooHigh.into());
 				}
-				let effective_gas_price = self
-					.config
-					.base_fee
-					.checked_add(max_priority_fee_per_gas)
-					.unwrap_or_else(U256::max_value)
-					.min(max_fee_per_gas);
+				let effective_gas_price = crate::fee::effective_gas_price(
+					self.config.base_fee,
+					max_fee_per_gas,
+					Some(max_priority_fee_per_gas),
+				);
 				Ok((max_fee_per_gas, Some(effective_gas_price)))
 			}
 			_ => {
@@ -261,6 +288,9 @@ mod tests {
 		InvalidChainId,
 		InvalidSignature,
 		UnknownError,
+		CallNotAllowed,
+		GasPriceBelowMinimum,
+		CreateNotAllowed,
 	}
 
 	static SHANGHAI_CONFIG: evm::Config = evm::Config::shanghai();
@@ -279,6 +309,9 @@ mod tests {
 				TransactionValidationError::InvalidChainId => TestError::InvalidChainId,
 				TransactionValidationError::InvalidSignature => TestError::InvalidSignature,
 				TransactionValidationError::UnknownError => TestError::UnknownError,
+				TransactionValidationError::CallNotAllowed => TestError::CallNotAllowed,
+				TransactionValidationError::GasPriceBelowMinimum => TestError::GasPriceBelowMinimum,
+				TransactionValidationError::CreateNotAllowed => TestError::CreateNotAllowed,
 			}
 		}
 	}
@@ -652,6 +685,31 @@ mod tests {
 		assert!(res.is_ok());
 	}
 
+	// Effective gas price below the minimum gas price fails.
+	#[test]
+	fn validate_min_gas_price_works() {
+		let min_gas_price = U256::from(2_000_000_000u128);
+		// Transactional, the 1559 tip is capped so the effective gas price is the base fee.
+		let test = default_transaction(true);
+		assert!(test
+			.with_min_gas_price(U256::from(1_000_000_000u128))
+			.is_ok());
+		let res = test.with_min_gas_price(min_gas_price);
+		assert!(res.is_err());
+		assert_eq!(res.unwrap_err(), TestError::GasPriceBelowMinimum);
+		// Legacy
+		let test = test_env(TestCase {
+			gas_price: Some(min_gas_price),
+			max_fee_per_gas: None,
+			max_priority_fee_per_gas: None,
+			..Default::default()
+		});
+		assert!(test.with_min_gas_price(min_gas_price).is_ok());
+		// Non-transactional
+		let test = transaction_none_fee(false);
+		assert!(test.with_min_gas_price(min_gas_price).is_ok());
+	}
+
 	// Transactional call with unset fee data fails.
 	#[test]
 	fn validate_base_fee_with_none_fee_fails() {
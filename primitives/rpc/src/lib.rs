@@ -45,6 +45,116 @@ pub struct TransactionStatus {
 	pub logs_bloom: Bloom,
 }
 
+/// The state of an account at a block and the runtime storage keys proving it.
+///
+/// The account and its storage are committed to by the state root of the block rather than by
+/// an account trie, so they are proven by the storage proof of their runtime storage keys
+/// against the state root. The runtime can't read that proof: the client reads it for the keys
+/// returned here (see `eth_getProof`).
+///
+/// Those proofs are made of Substrate trie nodes, not of Ethereum Merkle Patricia trie nodes:
+/// they verify against the state root of the Substrate block, and Ethereum tooling can't verify
+/// them against `storage_root`.
+#[derive(Clone, Eq, PartialEq, Default, RuntimeDebug, Encode, Decode, TypeInfo)]
+pub struct AccountProof {
+	/// The account RLP encoded as `[nonce, balance, storage_root, code_hash]`.
+	pub account_rlp: Vec<u8>,
+	pub nonce: U256,
+	pub balance: U256,
+	/// The root of the Ethereum storage trie of the account, see [`storage_root`].
+	pub storage_root: H256,
+	pub code_hash: H256,
+	/// The runtime storage keys proving the account.
+	pub proof_keys: Vec<Vec<u8>>,
+	/// The requested storage slots.
+	pub storage: Vec<StorageProof>,
+}
+
+impl AccountProof {
+	pub fn new(
+		account: fp_evm::Account,
+		storage_root: H256,
+		code_hash: H256,
+		proof_keys: Vec<Vec<u8>>,
+		storage: Vec<StorageProof>,
+	) -> Self {
+		let mut stream = rlp::RlpStream::new_list(4);
+		stream
+			.append(&account.nonce)
+			.append(&account.balance)
+			.append(&storage_root)
+			.append(&code_hash);
+
+		Self {
+			account_rlp: stream.out().to_vec(),
+			nonce: account.nonce,
+			balance: account.balance,
+			storage_root,
+			code_hash,
+			proof_keys,
+			storage,
+		}
+	}
+}
+
+/// The value of a storage slot at a block and the runtime storage key proving it.
+#[derive(Clone, Eq, PartialEq, Default, RuntimeDebug, Encode, Decode, TypeInfo)]
+pub struct StorageProof {
+	pub key: H256,
+	pub value: H256,
+	/// The runtime storage key proving the slot.
+	pub proof_key: Vec<u8>,
+}
+
+/// A change of the native balance of an account made by an ethereum transaction.
+#[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo)]
+pub enum BalanceChange {
+	/// Fee withdrawn from the sender.
+	FeeWithdrawn { address: Address, amount: U256 },
+	/// Fee deposited into an account: the unused gas refunded to the sender, or the priority
+	/// fee paid to the block author.
+	FeeDeposited { address: Address, amount: U256 },
+	/// Value moved by the transaction or one of its internal calls.
+	Transfer {
+		from: Address,
+		to: Address,
+		amount: U256,
+	},
+}
+
+/// The native balance changes made by an ethereum transaction, in the order they were made.
+/// The changes of reverted calls are not included.
+#[derive(Clone, Eq, PartialEq, Default, RuntimeDebug, Encode, Decode, TypeInfo)]
+pub struct TransactionBalanceChanges {
+	pub transaction_hash: H256,
+	pub changes: Vec<BalanceChange>,
+}
+
+/// Largest number of storage slots [`storage_root`] is computed over.
+pub const MAX_STORAGE_ROOT_SLOTS: usize = 10_000;
+
+/// The root of the Ethereum storage trie holding `storage`, i.e. the secure trie of the
+/// RLP encoded values of the non-zero slots.
+///
+/// Returns `None` without reading the rest of `storage` once it has more than
+/// [`MAX_STORAGE_ROOT_SLOTS`] slots.
+pub fn storage_root(storage: impl IntoIterator<Item = (H256, H256)>) -> Option<H256> {
+	let slots = storage
+		.into_iter()
+		.take(MAX_STORAGE_ROOT_SLOTS + 1)
+		.collect::<Vec<_>>();
+	if slots.len() > MAX_STORAGE_ROOT_SLOTS {
+		return None;
+	}
+
+	Some(ethereum::util::sec_trie_root(
+		slots
+			.into_iter()
+			.filter(|(_, value)| !value.is_zero())
+			.map(|(key, value)| (key, rlp::encode(&U256::from_big_endian(value.as_bytes())))),
+	))
+}
+
 pub trait RuntimeStorageOverride<B: BlockT, C>: Send + Sync {
 	fn is_enabled() -> bool;
 
@@ -84,7 +194,7 @@ impl<B: BlockT, C> RuntimeStorageOverride<B, C> for () {
 
 sp_api::decl_runtime_apis! {
 	/// API necessary for Ethereum-compatibility layer.
-	#[api_version(5)]
+	#[api_version(10)]
 	pub trait EthereumRuntimeRPCApi {
 		/// Returns runtime defined pallet_evm::ChainId.
 		fn chain_id() -> u64;
@@ -262,6 +372,32 @@ sp_api::decl_runtime_apis! {
 		/// If your project don't need to have a different behavior to initialize "pending" blocks,
 		/// you can copy your Core_initialize_block implementation.
 		fn initialize_pending_block(header: &<Block as BlockT>::Header);
+
+		/// Return the priority fees and burned base fees of the current block.
+		#[api_version(6)]
+		fn block_fees() -> fp_evm::BlockFees;
+
+		/// Return the state of an account and of the given storage slots, with the runtime
+		/// storage keys proving them, or `None` if the account has more than
+		/// [`MAX_STORAGE_ROOT_SLOTS`] storage slots.
+		#[api_version(7)]
+		fn account_proof(address: Address, storage_keys: Vec<H256>) -> Option<AccountProof>;
+
+		/// Return the gas limit of the blocks built on top of this one.
+		#[api_version(8)]
+		fn block_gas_limit() -> U256;
+
+		/// Return the base fee of the last blocks up to this one, oldest first.
+		#[api_version(9)]
+		fn base_fee_history() -> Vec<U256>;
+		/// Return the base fee of the next block.
+		#[api_version(9)]
+		fn next_base_fee_per_gas() -> U256;
+
+		/// Return the native balance changes made by the ethereum transactions of the current
+		/// block, in the order they were executed.
+		#[api_version(10)]
+		fn current_balance_changes() -> Vec<TransactionBalanceChanges>;
 	}
 
 	#[api_version(2)]
@@ -289,3 +425,78 @@ impl<E> ConvertTransaction<E> for NoTransactionConverter {
 		match *self {}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const EMPTY_TRIE_ROOT: &str =
+		"0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421";
+	const EMPTY_CODE_HASH: &str =
+		"0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470";
+
+	#[test]
+	fn empty_storage_has_the_empty_trie_root() {
+		let empty_trie_root: H256 = EMPTY_TRIE_ROOT.parse().unwrap();
+
+		assert_eq!(storage_root(Vec::new()), Some(empty_trie_root));
+		// Zero slots are not part of the trie.
+		assert_eq!(
+			storage_root(vec![(H256::repeat_byte(1), H256::zero())]),
+			Some(empty_trie_root)
+		);
+	}
+
+	#[test]
+	fn storage_root_is_the_ethereum_storage_root() {
+		// Storage of a contract with the slot 0 set to 1.
+		assert_eq!(
+			storage_root(vec![(H256::zero(), H256::from_low_u64_be(1))]),
+			Some(
+				"0x821e2556a290c86405f8160a2d662042a431ba456b9db265c79bb837c04be5f0"
+					.parse()
+					.unwrap()
+			)
+		);
+	}
+
+	#[test]
+	fn storage_root_is_not_computed_over_too_many_slots() {
+		let slots =
+			|n: usize| (0..n as u64).map(|i| (H256::from_low_u64_be(i), H256::from_low_u64_be(1)));
+
+		assert!(storage_root(slots(MAX_STORAGE_ROOT_SLOTS)).is_some());
+		assert_eq!(storage_root(slots(MAX_STORAGE_ROOT_SLOTS + 1)), None);
+	}
+
+	#[test]
+	fn account_rlp_is_the_ethereum_account_rlp() {
+		let storage_root: H256 = EMPTY_TRIE_ROOT.parse().unwrap();
+		let code_hash: H256 = EMPTY_CODE_HASH.parse().unwrap();
+
+		let proof = AccountProof::new(
+			fp_evm::Account::default(),
+			storage_root,
+			code_hash,
+			Vec::new(),
+			Vec::new(),
+		);
+		let mut expected = vec![0xf8, 0x44, 0x80, 0x80, 0xa0];
+		expected.extend(storage_root.as_bytes());
+		expected.push(0xa0);
+		expected.extend(code_hash.as_bytes());
+		assert_eq!(proof.account_rlp, expected);
+
+		let account = fp_evm::Account {
+			nonce: U256::from(1),
+			balance: U256::from(2),
+		};
+		let proof = AccountProof::new(account, storage_root, code_hash, Vec::new(), Vec::new());
+		let rlp = rlp::Rlp::new(&proof.account_rlp);
+		assert_eq!(rlp.item_count(), Ok(4));
+		assert_eq!(rlp.val_at::<U256>(0), Ok(U256::from(1)));
+		assert_eq!(rlp.val_at::<U256>(1), Ok(U256::from(2)));
+		assert_eq!(rlp.val_at::<H256>(2), Ok(storage_root));
+		assert_eq!(rlp.val_at::<H256>(3), Ok(code_hash));
+	}
+}
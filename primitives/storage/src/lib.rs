@@ -25,13 +25,21 @@ pub mod constants {
 	/// Pallet Evm storage items
 	pub const PALLET_EVM: &[u8] = b"EVM";
 	pub const EVM_ACCOUNT_CODES: &[u8] = b"AccountCodes";
+	pub const EVM_CODES: &[u8] = b"Codes";
 	pub const EVM_ACCOUNT_STORAGES: &[u8] = b"AccountStorages";
+	/// First storage version of pallet Evm storing the contract code by hash in `Codes`.
+	pub const EVM_CODES_STORAGE_VERSION: u16 = 1;
+
+	/// Key suffix of the storage version of a pallet.
+	pub const STORAGE_VERSION: &[u8] = b":__STORAGE_VERSION__:";
 
 	/// Pallet Ethereum storage items
 	pub const PALLET_ETHEREUM: &[u8] = b"Ethereum";
 	pub const ETHEREUM_CURRENT_BLOCK: &[u8] = b"CurrentBlock";
 	pub const ETHEREUM_CURRENT_RECEIPTS: &[u8] = b"CurrentReceipts";
 	pub const ETHEREUM_CURRENT_TRANSACTION_STATUSES: &[u8] = b"CurrentTransactionStatuses";
+	pub const ETHEREUM_CURRENT_REVERT_REASONS: &[u8] = b"CurrentRevertReasons";
+	pub const ETHEREUM_CURRENT_EFFECTIVE_GAS_PRICES: &[u8] = b"CurrentEffectiveGasPrices";
 
 	/// Pallet BaseFee storage items
 	pub const PALLET_BASE_FEE: &[u8] = b"BaseFee";
@@ -57,4 +65,7 @@ pub enum EthereumStorageSchema {
 	V2,
 	#[codec(index = 3)]
 	V3,
+	/// V3 plus the revert output and the effective gas price of the transactions of the block.
+	#[codec(index = 4)]
+	V4,
 }
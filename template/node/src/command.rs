@@ -214,15 +214,130 @@ pub fn run() -> sc_cli::Result<()> {
 			.into()),
 		Some(Subcommand::FrontierDb(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
-			runner.sync_run(|mut config| {
-				let (client, _, _, _, frontier_backend) =
-					service::new_chain_ops(&mut config, &cli.eth)?;
-				let frontier_backend = match frontier_backend {
-					fc_db::Backend::KeyValue(kv) => kv,
-					_ => panic!("Only fc_db::Backend::KeyValue supported"),
-				};
-				cmd.run(client, frontier_backend)
-			})
+			match &cmd.command {
+				#[cfg(feature = "sql")]
+				Some(fc_cli::FrontierDbSubcommand::MigrateToSql(migrate)) => runner.async_run(|mut config| {
+					let (client, _, _, task_manager, frontier_backend) =
+						service::new_chain_ops(&mut config, &cli.eth)?;
+					let sql_backend = match frontier_backend {
+						fc_db::Backend::Sql(sql) => sql,
+						_ => {
+							return Err(
+								"The migration requires `--frontier-backend-type sql`".into()
+							)
+						}
+					};
+					let kv_backend = std::sync::Arc::new(fc_db::kv::Backend::open(
+						client.clone(),
+						&config.database,
+						&db_config_dir(&config),
+					)?);
+					Ok((migrate.run(client, kv_backend, sql_backend), task_manager))
+				}),
+				Some(fc_cli::FrontierDbSubcommand::RepairMapping(repair)) => {
+					runner.sync_run(|mut config| {
+						let (_, _, _, _, frontier_backend) =
+							service::new_chain_ops(&mut config, &cli.eth)?;
+						match frontier_backend {
+							fc_db::Backend::KeyValue(kv) => repair.run(kv),
+							_ => Err("The repair only applies to the key-value backend".into()),
+						}
+					})
+				}
+				Some(fc_cli::FrontierDbSubcommand::VerifyMapping(verify)) => {
+					runner.sync_run(|mut config| {
+						let (client, _, _, _, frontier_backend) =
+							service::new_chain_ops(&mut config, &cli.eth)?;
+						let storage_override =
+							std::sync::Arc::new(fc_storage::StorageOverrideHandler::<
+								frontier_template_runtime::opaque::Block,
+								_,
+								_,
+							>::new(client.clone()));
+						match frontier_backend {
+							fc_db::Backend::KeyValue(kv) => {
+								verify.run(client, kv, storage_override)
+							}
+							_ => {
+								Err("The verification only applies to the key-value backend".into())
+							}
+						}
+					})
+				}
+				Some(fc_cli::FrontierDbSubcommand::Reindex(reindex)) => {
+					runner.async_run(|mut config| {
+						let (client, _, _, task_manager, frontier_backend) =
+							service::new_chain_ops(&mut config, &cli.eth)?;
+						let reindex = reindex.clone();
+						let future = async move {
+							match frontier_backend {
+								fc_db::Backend::KeyValue(kv) => {
+									let storage_override =
+										std::sync::Arc::new(fc_storage::StorageOverrideHandler::<
+											frontier_template_runtime::opaque::Block,
+											_,
+											_,
+										>::new(client.clone()));
+									reindex.run_kv(client, kv, storage_override)
+								}
+								#[cfg(feature = "sql")]
+								fc_db::Backend::Sql(sql) => reindex.run_sql(client, sql).await,
+							}
+						};
+						Ok((future, task_manager))
+					})
+				}
+				Some(fc_cli::FrontierDbSubcommand::Prune(prune)) => {
+					runner.async_run(|mut config| {
+						let (_, _, _, task_manager, frontier_backend) =
+							service::new_chain_ops(&mut config, &cli.eth)?;
+						let prune = prune.clone();
+						let future = async move {
+							match frontier_backend {
+								fc_db::Backend::KeyValue(kv) => prune.run_kv(kv),
+								#[cfg(feature = "sql")]
+								fc_db::Backend::Sql(sql) => prune.run_sql(sql).await,
+							}
+						};
+						Ok((future, task_manager))
+					})
+				}
+				Some(fc_cli::FrontierDbSubcommand::Stats(stats)) => {
+					runner.async_run(|mut config| {
+						let (_, _, _, task_manager, frontier_backend) =
+							service::new_chain_ops(&mut config, &cli.eth)?;
+						let stats = stats.clone();
+						let path = db_config_dir(&config).join("frontier");
+						let future = async move {
+							match frontier_backend {
+								fc_db::Backend::KeyValue(kv) => stats.run_kv(kv, &path),
+								#[cfg(feature = "sql")]
+								fc_db::Backend::Sql(sql) => stats.run_sql(sql).await,
+							}
+						};
+						Ok((future, task_manager))
+					})
+				}
+				#[cfg(feature = "sql")]
+				Some(fc_cli::FrontierDbSubcommand::ExportLogs(export)) => runner.async_run(|mut config| {
+					let (_, _, _, task_manager, frontier_backend) =
+						service::new_chain_ops(&mut config, &cli.eth)?;
+					let sql_backend = match frontier_backend {
+						fc_db::Backend::Sql(sql) => sql,
+						_ => return Err("The export requires `--frontier-backend-type sql`".into()),
+					};
+					Ok((export.run(sql_backend), task_manager))
+				}),
+				None => runner.sync_run(|mut config| {
+					let (client, _, _, _, frontier_backend) =
+						service::new_chain_ops(&mut config, &cli.eth)?;
+					let frontier_backend = match frontier_backend {
+						fc_db::Backend::KeyValue(kv) => kv,
+						_ => panic!("Only fc_db::Backend::KeyValue supported"),
+					};
+					cmd.run(client, frontier_backend)
+				}),
+			}
 		}
 		None => {
 			let runner = cli.create_runner(&cli.run)?;
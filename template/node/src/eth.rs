@@ -1,5 +1,6 @@
 use std::{
 	collections::BTreeMap,
+	net::{Ipv4Addr, SocketAddr},
 	path::PathBuf,
 	sync::{Arc, Mutex},
 	time::Duration,
@@ -16,7 +17,7 @@ use sp_core::H256;
 use sp_runtime::traits::Block as BlockT;
 // Frontier
 pub use fc_consensus::FrontierBlockImport;
-use fc_rpc::EthTask;
+use fc_rpc::{EthTask, IndexerAdmin, IndexerAdminApiServer};
 pub use fc_rpc_core::types::{FeeHistoryCache, FeeHistoryCacheLimit, FilterPool};
 pub use fc_storage::{StorageOverride, StorageOverrideHandler};
 
@@ -62,14 +63,57 @@ pub struct EthConfiguration {
 	#[arg(long, default_value = "10")]
 	pub execute_gas_limit_multiplier: u64,
 
-	/// Size in bytes of the LRU cache for block data.
-	#[arg(long, default_value = "50")]
+	/// Fixed value suggested by eth_maxPriorityFeePerGas, for deterministic fees in tests.
+	/// The runtime base fee is frozen with `pallet_base_fee::Config::FixedBaseFeePerGas`.
+	#[arg(long)]
+	pub fixed_priority_fee_per_gas: Option<u128>,
+
+	/// Maximum number of eth_call and eth_estimateGas requests executed at the same time,
+	/// the others being queued by priority. Unbounded by default.
+	#[arg(long)]
+	pub eth_execution_pool_size: Option<usize>,
+
+	/// Maximum number of requests queued by the execution pool, further ones being rejected.
+	#[arg(long, default_value = "256")]
+	pub eth_execution_queue_size: usize,
+
+	/// Deadline, in seconds, of the requests submitted to the execution pool.
+	#[arg(long, default_value = "30")]
+	pub eth_execution_timeout: u64,
+
+	/// Limits the requests of an rpc method, as `<method>:<max concurrent>:<timeout in ms>`
+	/// where either limit may be left empty, e.g. `eth_getLogs:8:10000` or `eth_call::5000`.
+	/// Requests over the concurrency limit are rejected as busy.
+	#[arg(long, value_parser = parse_method_limit)]
+	pub eth_method_limit: Vec<(String, fc_rpc::MethodLimit)>,
+
+	/// Size in bytes of the LRU cache for block data, estimated from the encoded entries.
+	#[arg(long, default_value = "52428800")]
 	pub eth_log_block_cache: usize,
 
-	/// Size in bytes of the LRU cache for transactions statuses data.
-	#[arg(long, default_value = "50")]
+	/// Size in bytes of the LRU cache for transactions statuses data, estimated from the
+	/// encoded entries.
+	#[arg(long, default_value = "52428800")]
 	pub eth_statuses_cache: usize,
 
+	/// Sets the number of threads deriving the key-value mappings of the synced blocks.
+	#[arg(long, default_value = "1")]
+	pub frontier_mapping_sync_workers: usize,
+
+	/// Sets the maximum number of blocks mapped by each batch of the key-value mapping sync.
+	#[arg(long)]
+	pub frontier_mapping_sync_batch_size: Option<usize>,
+
+	/// Sets how long, in milliseconds, the key-value mapping sync keeps mapping batches of
+	/// blocks before yielding. By default a single batch is mapped.
+	#[arg(long)]
+	pub frontier_mapping_sync_time_budget: Option<u64>,
+
+	/// Sets the first block mapped and indexed by the frontier backend. The Ethereum history
+	/// below it is not served, e.g. for a node warp-synced past it.
+	#[arg(long, default_value = "0")]
+	pub frontier_sync_from: u32,
+
 	/// Sets the frontier backend type (KeyValue or Sql)
 	#[arg(long, value_enum, ignore_case = true, default_value_t = BackendType::default())]
 	pub frontier_backend_type: BackendType,
@@ -82,6 +126,10 @@ pub struct EthConfiguration {
 	#[arg(long, default_value = "10000000")]
 	pub frontier_sql_backend_num_ops_timeout: u32,
 
+	/// Sets the SQL backend's maximum number of logs returned by a query.
+	#[arg(long, default_value = "10000")]
+	pub frontier_sql_backend_max_log_results: u32,
+
 	/// Sets the SQL backend's auxiliary thread limit.
 	#[arg(long, default_value = "4")]
 	pub frontier_sql_backend_thread_count: u32,
@@ -90,6 +138,153 @@ pub struct EthConfiguration {
 	/// Default value is 200MB.
 	#[arg(long, default_value = "209715200")]
 	pub frontier_sql_backend_cache_size: u64,
+
+	/// Sets how long, in milliseconds, the SQL backend waits for a database lock.
+	#[arg(long, default_value = "8000")]
+	pub frontier_sql_backend_busy_timeout: u64,
+
+	/// Sets the SQL backend's journal mode (delete, truncate, persist, memory, wal or off).
+	#[arg(long, default_value = "wal")]
+	pub frontier_sql_backend_journal_mode: fc_db::sql::SqliteJournalMode,
+
+	/// Sets the SQL backend's commit durability (off, normal, full or extra).
+	#[arg(long, default_value = "normal")]
+	pub frontier_sql_backend_synchronous: fc_db::sql::SqliteSynchronous,
+
+	/// Sets the maximum number of rows scanned per index when the SQL backend is analyzed.
+	#[arg(long, default_value = "1000")]
+	pub frontier_sql_backend_analysis_limit: u32,
+
+	/// Sets the SQL backend's page size in bytes, only applied when the database is created.
+	#[arg(long, default_value = "4096")]
+	pub frontier_sql_backend_page_size: u32,
+
+	/// Sets the number of prepared statements cached by each SQL backend connection.
+	#[arg(long, default_value = "100")]
+	pub frontier_sql_backend_statement_cache_capacity: usize,
+
+	/// Sets the number of logs inserted by a single statement when the SQL backend indexes
+	/// a block.
+	#[arg(long, default_value = "1000")]
+	pub frontier_sql_backend_log_insert_chunk_size: usize,
+
+	/// Only keep the SQL backend's indexed data of the last given number of blocks.
+	#[arg(long, conflicts_with = "frontier_sql_backend_retention_days")]
+	pub frontier_sql_backend_retention_blocks: Option<u32>,
+
+	/// Only keep the SQL backend's indexed data of the last given number of days.
+	#[arg(long)]
+	pub frontier_sql_backend_retention_days: Option<u32>,
+
+	/// Vacuums, analyzes and checkpoints the SQL backend's database every given number of
+	/// seconds.
+	#[arg(long, conflicts_with = "frontier_sql_backend_maintenance_blocks")]
+	pub frontier_sql_backend_maintenance_interval: Option<u64>,
+
+	/// Vacuums, analyzes and checkpoints the SQL backend's database once the given number of
+	/// blocks were indexed since the last time.
+	#[arg(long)]
+	pub frontier_sql_backend_maintenance_blocks: Option<u64>,
+
+	/// Sets the maximum number of blocks pruned by the SQL backend in a single db transaction.
+	#[arg(long, default_value = "1000")]
+	pub frontier_sql_backend_prune_batch_size: u32,
+
+	/// Sets the number of commands queued for the SQL backend's indexing task, beyond which
+	/// the best block notifications are coalesced.
+	#[arg(long, default_value = "100")]
+	pub frontier_sql_backend_command_buffer_size: usize,
+
+	/// Sets the number of tasks indexing the history in parallel in the SQL backend.
+	/// A value of `0` disables the parallel backfill.
+	#[arg(long, default_value = "0")]
+	pub frontier_sql_backend_backfill_workers: u32,
+
+	/// Sets how long, in milliseconds, a log query waits for the SQL backend to index the
+	/// blocks it targets. A value of `0` only prioritizes them without waiting.
+	#[arg(long, default_value = "0")]
+	pub frontier_sql_backend_priority_indexing_timeout: u64,
+
+	/// Opens the SQL backend's database read-only, to serve the rpc from an index written by
+	/// another node. No blocks are indexed by this node.
+	#[arg(long, conflicts_with = "frontier_sql_backend_event_signatures")]
+	pub frontier_sql_backend_read_only: bool,
+
+	/// Path to a file of event ABI fragments, one per line, registered in the SQL backend
+	/// to decode logs with `frontier_getDecodedLogs`.
+	#[arg(long)]
+	pub frontier_sql_backend_event_signatures: Option<PathBuf>,
+
+	/// Serves the SQL backend's admin rpc (backfill, integrity check, pruning) on the given
+	/// localhost port, separately from the public rpc.
+	#[arg(long, requires = "frontier_sql_admin_rpc_token")]
+	pub frontier_sql_admin_rpc_port: Option<u16>,
+
+	/// Bearer token required by the SQL backend's admin rpc.
+	#[arg(long)]
+	pub frontier_sql_admin_rpc_token: Option<String>,
+
+	/// Serves the Rosetta data and construction api on the given port.
+	#[arg(long)]
+	pub rosetta_port: Option<u16>,
+
+	/// Listens for Rosetta requests on all interfaces instead of localhost only.
+	#[arg(long, requires = "rosetta_port")]
+	pub rosetta_external: bool,
+
+	/// Blockchain name of the network served by the Rosetta api.
+	#[arg(long, default_value = "frontier")]
+	pub rosetta_blockchain: String,
+
+	/// Network name served by the Rosetta api.
+	#[arg(long, default_value = "mainnet")]
+	pub rosetta_network: String,
+
+	/// Symbol of the native currency reported by the Rosetta api.
+	#[arg(long, default_value = "UNIT")]
+	pub rosetta_currency_symbol: String,
+
+	/// Number of decimals of the native currency reported by the Rosetta api.
+	#[arg(long, default_value = "18")]
+	pub rosetta_currency_decimals: u32,
+
+	/// Maximum size in MB of a request body accepted by the Rosetta api.
+	#[arg(long, default_value = "1")]
+	pub rosetta_max_request_size: u32,
+}
+
+impl EthConfiguration {
+	/// Retention policy of the SQL backend's indexed data.
+	pub fn frontier_sql_backend_retention_policy(
+		&self,
+	) -> Option<fc_mapping_sync::sql::RetentionPolicy> {
+		match (
+			self.frontier_sql_backend_retention_blocks,
+			self.frontier_sql_backend_retention_days,
+		) {
+			(Some(blocks), _) => Some(fc_mapping_sync::sql::RetentionPolicy::Blocks(blocks)),
+			(None, Some(days)) => Some(fc_mapping_sync::sql::RetentionPolicy::Days(days)),
+			(None, None) => None,
+		}
+	}
+
+	/// Schedule of the SQL backend's database maintenance.
+	pub fn frontier_sql_backend_maintenance_schedule(
+		&self,
+	) -> Option<fc_mapping_sync::sql::MaintenanceSchedule> {
+		match (
+			self.frontier_sql_backend_maintenance_interval,
+			self.frontier_sql_backend_maintenance_blocks,
+		) {
+			(Some(seconds), _) => Some(fc_mapping_sync::sql::MaintenanceSchedule::Interval(
+				Duration::from_secs(seconds),
+			)),
+			(None, Some(blocks)) => Some(fc_mapping_sync::sql::MaintenanceSchedule::IndexedBlocks(
+				blocks,
+			)),
+			(None, None) => None,
+		}
+	}
 }
 
 pub struct FrontierPartialComponents {
@@ -98,6 +293,31 @@ pub struct FrontierPartialComponents {
 	pub fee_history_cache_limit: FeeHistoryCacheLimit,
 }
 
+fn parse_method_limit(value: &str) -> Result<(String, fc_rpc::MethodLimit), String> {
+	let mut parts = value.split(':');
+	let (Some(method), Some(max_concurrent), Some(timeout), None) =
+		(parts.next(), parts.next(), parts.next(), parts.next())
+	else {
+		return Err("expected `<method>:<max concurrent>:<timeout in ms>`".to_string());
+	};
+	let parse = |limit: &str| {
+		(!limit.is_empty())
+			.then(|| {
+				limit
+					.parse::<u64>()
+					.map_err(|e| format!("invalid limit {limit:?}: {e}"))
+			})
+			.transpose()
+	};
+	Ok((
+		method.to_string(),
+		fc_rpc::MethodLimit {
+			max_concurrent: parse(max_concurrent)?.map(|limit| limit as usize),
+			timeout: parse(timeout)?.map(std::time::Duration::from_millis),
+		},
+	))
+}
+
 pub fn new_frontier_partial(
 	config: &EthConfiguration,
 ) -> Result<FrontierPartialComponents, ServiceError> {
@@ -140,6 +360,9 @@ pub async fn spawn_frontier_tasks<B, RA, HF>(
 			fc_mapping_sync::EthereumBlockNotification<B>,
 		>,
 	>,
+	sync_progress_sinks: Arc<fc_mapping_sync::SyncProgressSinks>,
+	prometheus_registry: Option<&prometheus_endpoint::Registry>,
+	eth_config: &EthConfiguration,
 ) where
 	B: BlockT<Hash = H256>,
 	RA: ConstructRuntimeApi<B, FullClient<B, RA, HF>>,
@@ -147,28 +370,51 @@ pub async fn spawn_frontier_tasks<B, RA, HF>(
 	RA::RuntimeApi: EthCompatRuntimeApiCollection<B>,
 	HF: HostFunctions + 'static,
 {
+	let metrics = prometheus_registry.and_then(|registry| {
+		fc_mapping_sync::MappingSyncMetrics::register(registry)
+			.map_err(|err| log::error!("Failed to register the mapping sync metrics: {err:?}"))
+			.ok()
+	});
+
 	// Spawn main mapping sync worker background task.
 	match &*frontier_backend {
 		fc_db::Backend::KeyValue(b) => {
+			let worker = fc_mapping_sync::kv::MappingSyncWorker::new(
+				client.import_notification_stream(),
+				Duration::new(6, 0),
+				client.clone(),
+				backend,
+				storage_override.clone(),
+				b.clone(),
+				3,
+				eth_config.frontier_sync_from.into(),
+				fc_mapping_sync::SyncStrategy::Normal,
+				sync,
+				pubsub_notification_sinks,
+			)
+			.with_non_canonical_cleanup(client.finality_notification_stream(), 64)
+			.with_workers(eth_config.frontier_mapping_sync_workers)
+			.with_sync_progress(sync_progress_sinks);
+			let worker = match eth_config.frontier_mapping_sync_batch_size {
+				Some(batch_size) => worker.with_batch_size(batch_size),
+				None => worker,
+			};
+			let worker = match eth_config.frontier_mapping_sync_time_budget {
+				Some(budget) => worker.with_time_budget(Duration::from_millis(budget)),
+				None => worker,
+			};
+			let worker = match metrics {
+				Some(metrics) => worker.with_metrics(metrics),
+				None => worker,
+			};
 			task_manager.spawn_essential_handle().spawn(
 				"frontier-mapping-sync-worker",
 				Some("frontier"),
-				fc_mapping_sync::kv::MappingSyncWorker::new(
-					client.import_notification_stream(),
-					Duration::new(6, 0),
-					client.clone(),
-					backend,
-					storage_override.clone(),
-					b.clone(),
-					3,
-					0u32.into(),
-					fc_mapping_sync::SyncStrategy::Normal,
-					sync,
-					pubsub_notification_sinks,
-				)
-				.for_each(|()| future::ready(())),
+				worker.for_each(|()| future::ready(())),
 			);
 		}
+		// The database is indexed by another node.
+		fc_db::Backend::Sql(b) if b.is_read_only() => {}
 		fc_db::Backend::Sql(b) => {
 			task_manager.spawn_essential_handle().spawn_blocking(
 				"frontier-mapping-sync-worker",
@@ -181,12 +427,35 @@ pub async fn spawn_frontier_tasks<B, RA, HF>(
 					fc_mapping_sync::sql::SyncWorkerConfig {
 						read_notification_timeout: Duration::from_secs(30),
 						check_indexed_blocks_interval: Duration::from_secs(60),
+						retention_policy: eth_config.frontier_sql_backend_retention_policy(),
+						prune_batch_size: eth_config.frontier_sql_backend_prune_batch_size,
+						backfill_workers: eth_config.frontier_sql_backend_backfill_workers,
+						maintenance: eth_config.frontier_sql_backend_maintenance_schedule(),
+						sync_from: eth_config.frontier_sync_from,
+						progress_sinks: Some(sync_progress_sinks),
+						metrics,
+						command_buffer_size: eth_config.frontier_sql_backend_command_buffer_size,
 					},
 					fc_mapping_sync::SyncStrategy::Parachain,
 					sync,
 					pubsub_notification_sinks,
 				),
 			);
+
+			if let (Some(port), Some(token)) = (
+				eth_config.frontier_sql_admin_rpc_port,
+				&eth_config.frontier_sql_admin_rpc_token,
+			) {
+				spawn_sql_admin_rpc(
+					task_manager,
+					client.clone(),
+					b.clone(),
+					eth_config.frontier_sql_backend_retention_policy(),
+					port,
+					token,
+				)
+				.await;
+			}
 		}
 	}
 
@@ -201,7 +470,11 @@ pub async fn spawn_frontier_tasks<B, RA, HF>(
 		);
 	}
 
-	// Spawn Frontier FeeHistory cache maintenance task.
+	// Spawn Frontier FeeHistory cache maintenance task, persisting it in the frontier db.
+	let fee_history_backend: Arc<dyn fc_api::FeeHistoryBackend> = match &*frontier_backend {
+		fc_db::Backend::KeyValue(b) => b.clone(),
+		fc_db::Backend::Sql(b) => b.clone(),
+	};
 	task_manager.spawn_essential_handle().spawn(
 		"frontier-fee-history",
 		Some("frontier"),
@@ -210,6 +483,95 @@ pub async fn spawn_frontier_tasks<B, RA, HF>(
 			storage_override,
 			fee_history_cache,
 			fee_history_cache_limit,
+			Some(fee_history_backend),
 		),
 	);
 }
+
+/// Serves the SQL backend's admin rpc on localhost, only accepting the requests carrying the
+/// bearer `token`.
+async fn spawn_sql_admin_rpc<B, RA, HF>(
+	task_manager: &TaskManager,
+	client: Arc<FullClient<B, RA, HF>>,
+	indexer_backend: Arc<fc_db::sql::Backend<B>>,
+	retention_policy: Option<fc_mapping_sync::sql::RetentionPolicy>,
+	port: u16,
+	token: &str,
+) where
+	B: BlockT<Hash = H256>,
+	RA: ConstructRuntimeApi<B, FullClient<B, RA, HF>>,
+	RA: Send + Sync + 'static,
+	RA::RuntimeApi: EthCompatRuntimeApiCollection<B>,
+	HF: HostFunctions + 'static,
+{
+	let http_middleware = tower::ServiceBuilder::new()
+		.layer(tower_http::validate_request::ValidateRequestHeaderLayer::bearer(token));
+	let server = match jsonrpsee::server::Server::builder()
+		.http_only()
+		.set_http_middleware(http_middleware)
+		.build(SocketAddr::from((Ipv4Addr::LOCALHOST, port)))
+		.await
+	{
+		Ok(server) => server,
+		Err(err) => {
+			log::error!(target: "frontier-sql", "💔  Cannot start the admin rpc on port {port}: {err}");
+			return;
+		}
+	};
+
+	let admin =
+		IndexerAdmin::<B, _, FullBackend<B>>::new(client, indexer_backend, retention_policy);
+	let handle = server.start(admin.into_rpc());
+	log::info!(target: "frontier-sql", "Admin rpc listening on 127.0.0.1:{port}");
+	task_manager
+		.spawn_handle()
+		.spawn("frontier-sql-admin-rpc", Some("frontier"), handle.stopped());
+}
+
+/// Serves the Rosetta api if a port is configured.
+#[cfg(feature = "rosetta")]
+pub fn spawn_rosetta<B, RA, HF, P>(
+	task_manager: &TaskManager,
+	client: Arc<FullClient<B, RA, HF>>,
+	pool: Arc<P>,
+	frontier_backend: Arc<dyn fc_api::Backend<B>>,
+	storage_override: Arc<dyn StorageOverride<B>>,
+	eth_config: &EthConfiguration,
+) where
+	B: BlockT<Hash = H256>,
+	RA: ConstructRuntimeApi<B, FullClient<B, RA, HF>>,
+	RA: Send + Sync + 'static,
+	RA::RuntimeApi: EthCompatRuntimeApiCollection<B>,
+	HF: HostFunctions + 'static,
+	P: sc_transaction_pool_api::TransactionPool<Block = B> + 'static,
+{
+	let Some(port) = eth_config.rosetta_port else {
+		return;
+	};
+	let ip = if eth_config.rosetta_external {
+		Ipv4Addr::UNSPECIFIED
+	} else {
+		Ipv4Addr::LOCALHOST
+	};
+
+	let rosetta = fc_rpc::Rosetta::new(
+		client,
+		pool,
+		frontier_backend,
+		storage_override,
+		fc_rpc_core::types::NetworkIdentifier {
+			blockchain: eth_config.rosetta_blockchain.clone(),
+			network: eth_config.rosetta_network.clone(),
+		},
+		fc_rpc_core::types::Currency {
+			symbol: eth_config.rosetta_currency_symbol.clone(),
+			decimals: eth_config.rosetta_currency_decimals,
+		},
+		eth_config.rosetta_max_request_size as usize * 1024 * 1024,
+	);
+	task_manager.spawn_handle().spawn(
+		"frontier-rosetta",
+		Some("frontier"),
+		rosetta.serve(SocketAddr::from((ip, port))),
+	);
+}
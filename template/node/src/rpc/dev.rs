@@ -0,0 +1,94 @@
+//! Manual seal helpers to exercise the reorg handling in tests.
+
+use futures::{
+	channel::{mpsc, oneshot},
+	SinkExt,
+};
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::ErrorObjectOwned,
+};
+// Substrate
+use sc_consensus_manual_seal::{rpc::EngineCommand, CreatedBlock};
+// Runtime
+use frontier_template_runtime::Hash;
+
+/// Maximum number of blocks created by a single `engine_createFork` call.
+const MAX_FORK_LENGTH: u32 = 100;
+
+#[rpc(server)]
+pub trait DevApi {
+	/// Seals `length` blocks on top of `parent_hash`, forking the chain if it is not the best
+	/// block, and returns their hashes. The first block includes the ready transactions.
+	///
+	/// The fork becomes the best chain once it is the longest one, or once one of its blocks
+	/// is finalized with `engine_finalizeBlock`.
+	#[method(name = "engine_createFork")]
+	async fn create_fork(
+		&self,
+		parent_hash: Hash,
+		length: u32,
+		finalize: bool,
+	) -> RpcResult<Vec<Hash>>;
+}
+
+/// Manual seal helpers to create competing forks.
+pub struct Dev {
+	command_sink: mpsc::Sender<EngineCommand<Hash>>,
+}
+
+impl Dev {
+	pub fn new(command_sink: mpsc::Sender<EngineCommand<Hash>>) -> Self {
+		Self { command_sink }
+	}
+
+	async fn seal(&self, parent_hash: Hash, finalize: bool) -> RpcResult<CreatedBlock<Hash>> {
+		let (sender, receiver) = oneshot::channel();
+		self.command_sink
+			.clone()
+			.send(EngineCommand::SealNewBlock {
+				create_empty: true,
+				finalize,
+				parent_hash: Some(parent_hash),
+				sender: Some(sender),
+			})
+			.await
+			.map_err(internal_err)?;
+		receiver.await.map_err(internal_err)?.map_err(internal_err)
+	}
+}
+
+#[async_trait]
+impl DevApiServer for Dev {
+	async fn create_fork(
+		&self,
+		parent_hash: Hash,
+		length: u32,
+		finalize: bool,
+	) -> RpcResult<Vec<Hash>> {
+		if length == 0 || length > MAX_FORK_LENGTH {
+			return Err(ErrorObjectOwned::owned(
+				jsonrpsee::types::error::INVALID_PARAMS_CODE,
+				format!("fork length must be between 1 and {MAX_FORK_LENGTH}"),
+				None::<()>,
+			));
+		}
+
+		let mut hashes = Vec::with_capacity(length as usize);
+		let mut parent_hash = parent_hash;
+		for _ in 0..length {
+			parent_hash = self.seal(parent_hash, finalize).await?.hash;
+			hashes.push(parent_hash);
+		}
+		Ok(hashes)
+	}
+}
+
+fn internal_err<T: ToString>(err: T) -> ErrorObjectOwned {
+	ErrorObjectOwned::owned(
+		jsonrpsee::types::error::INTERNAL_ERROR_CODE,
+		err.to_string(),
+		None::<()>,
+	)
+}
@@ -5,7 +5,7 @@ use jsonrpsee::RpcModule;
 use sc_client_api::{
 	backend::{Backend, StorageProvider},
 	client::BlockchainEvents,
-	AuxStore, UsageProvider,
+	AuxStore, ProofProvider, UsageProvider,
 };
 use sc_network::service::traits::NetworkService;
 use sc_network_sync::SyncingService;
@@ -16,11 +16,11 @@ use sp_api::{CallApiAt, ProvideRuntimeApi};
 use sp_block_builder::BlockBuilder as BlockBuilderApi;
 use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
 use sp_consensus_aura::{sr25519::AuthorityId as AuraId, AuraApi};
-use sp_core::H256;
+use sp_core::{H256, U256};
 use sp_inherents::CreateInherentDataProviders;
 use sp_runtime::traits::Block as BlockT;
 // Frontier
-pub use fc_rpc::{EthBlockDataCacheTask, EthConfig};
+pub use fc_rpc::{EthBlockDataCacheTask, EthConfig, ExecutionPool, MethodLimits};
 pub use fc_rpc_core::types::{FeeHistoryCache, FeeHistoryCacheLimit, FilterPool};
 use fc_storage::StorageOverride;
 use fp_rpc::{ConvertTransaction, ConvertTransactionRuntimeApi, EthereumRuntimeRPCApi};
@@ -64,6 +64,12 @@ pub struct EthDeps<B: BlockT, C, P, A: ChainApi, CT, CIDP> {
 	pub forced_parent_hashes: Option<BTreeMap<H256, H256>>,
 	/// Something that can create the inherent data providers for pending state
 	pub pending_create_inherent_data_providers: CIDP,
+	/// Fixed priority fee suggestion, for deterministic fees in tests.
+	pub fixed_priority_fee_per_gas: Option<U256>,
+	/// Pool executing eth_call and eth_estimateGas.
+	pub execution_pool: Option<ExecutionPool>,
+	/// Per-method limits of the requests.
+	pub method_limits: MethodLimits,
 }
 
 /// Instantiate Ethereum-compatible RPC extensions.
@@ -86,6 +92,7 @@ where
 		+ EthereumRuntimeRPCApi<B>,
 	C: HeaderBackend<B> + HeaderMetadata<B, Error = BlockChainError>,
 	C: BlockchainEvents<B> + AuxStore + UsageProvider<B> + StorageProvider<B, BE> + 'static,
+	C: ProofProvider<B>,
 	BE: Backend<B> + 'static,
 	P: TransactionPool<Block = B> + 'static,
 	A: ChainApi<Block = B> + 'static,
@@ -95,8 +102,8 @@ where
 {
 	use fc_rpc::{
 		pending::AuraConsensusDataProvider, Debug, DebugApiServer, Eth, EthApiServer, EthDevSigner,
-		EthFilter, EthFilterApiServer, EthPubSub, EthPubSubApiServer, EthSigner, Net, NetApiServer,
-		Web3, Web3ApiServer,
+		EthFilter, EthFilterApiServer, EthPubSub, EthPubSubApiServer, EthSigner, Frontier,
+		FrontierApiServer, Net, NetApiServer, Web3, Web3ApiServer,
 	};
 	#[cfg(feature = "txpool")]
 	use fc_rpc::{TxPool, TxPoolApiServer};
@@ -120,6 +127,9 @@ where
 		execute_gas_limit_multiplier,
 		forced_parent_hashes,
 		pending_create_inherent_data_providers,
+		fixed_priority_fee_per_gas,
+		execution_pool,
+		method_limits,
 	} = deps;
 
 	let mut signers = Vec::new();
@@ -146,23 +156,26 @@ where
 			pending_create_inherent_data_providers,
 			Some(Box::new(AuraConsensusDataProvider::new(client.clone()))),
 		)
+		.with_fixed_priority_fee_per_gas(fixed_priority_fee_per_gas)
+		.with_execution_pool(execution_pool)
+		.with_method_limits(method_limits.clone())
 		.replace_config::<EC>()
 		.into_rpc(),
 	)?;
 
 	if let Some(filter_pool) = filter_pool {
-		io.merge(
-			EthFilter::new(
-				client.clone(),
-				frontier_backend.clone(),
-				graph.clone(),
-				filter_pool,
-				500_usize, // max stored filters
-				max_past_logs,
-				block_data_cache.clone(),
-			)
-			.into_rpc(),
-		)?;
+		let eth_filter = EthFilter::new(
+			client.clone(),
+			frontier_backend.clone(),
+			graph.clone(),
+			filter_pool,
+			500_usize, // max stored filters
+			max_past_logs,
+			block_data_cache.clone(),
+		)
+		.with_method_limits(method_limits);
+		io.merge(eth_filter.clone().into_rpc())?;
+		io.merge(Frontier::new(eth_filter, frontier_backend.clone()).into_rpc())?;
 	}
 
 	io.merge(
@@ -8,7 +8,7 @@ use jsonrpsee::RpcModule;
 use sc_client_api::{
 	backend::{Backend, StorageProvider},
 	client::BlockchainEvents,
-	AuxStore, UsageProvider,
+	AuxStore, ProofProvider, UsageProvider,
 };
 use sc_consensus_manual_seal::rpc::EngineCommand;
 use sc_rpc::SubscriptionTaskExecutor;
@@ -23,8 +23,12 @@ use sp_runtime::traits::Block as BlockT;
 // Runtime
 use frontier_template_runtime::{AccountId, Balance, Hash, Nonce};
 
+mod dev;
 mod eth;
-pub use self::eth::{create_eth, EthDeps};
+pub use self::{
+	dev::{Dev, DevApiServer},
+	eth::{create_eth, EthDeps},
+};
 
 /// Full client dependencies.
 pub struct FullDeps<B: BlockT, C, P, A: ChainApi, CT, CIDP> {
@@ -74,6 +78,7 @@ where
 	C::Api: fp_rpc::EthereumRuntimeRPCApi<B>,
 	C: HeaderBackend<B> + HeaderMetadata<B, Error = BlockChainError> + 'static,
 	C: BlockchainEvents<B> + AuxStore + UsageProvider<B> + StorageProvider<B, BE>,
+	C: ProofProvider<B>,
 	BE: Backend<B> + 'static,
 	P: TransactionPool<Block = B> + 'static,
 	A: ChainApi<Block = B> + 'static,
@@ -100,8 +105,9 @@ where
 		io.merge(
 			// We provide the rpc handler with the sending end of the channel to allow the rpc
 			// send EngineCommands to the background block authorship task.
-			ManualSeal::new(command_sink).into_rpc(),
+			ManualSeal::new(command_sink.clone()).into_rpc(),
 		)?;
+		io.merge(Dev::new(command_sink).into_rpc())?;
 	}
 
 	// Ethereum compatibility RPCs
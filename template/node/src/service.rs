@@ -148,12 +148,40 @@ where
 					create_if_missing: true,
 					thread_count: eth_config.frontier_sql_backend_thread_count,
 					cache_size: eth_config.frontier_sql_backend_cache_size,
+					read_only: eth_config.frontier_sql_backend_read_only,
+					busy_timeout: std::time::Duration::from_millis(
+						eth_config.frontier_sql_backend_busy_timeout,
+					),
+					journal_mode: eth_config.frontier_sql_backend_journal_mode,
+					synchronous: eth_config.frontier_sql_backend_synchronous,
+					analysis_limit: eth_config.frontier_sql_backend_analysis_limit,
+					page_size: eth_config.frontier_sql_backend_page_size,
+					statement_cache_capacity: eth_config
+						.frontier_sql_backend_statement_cache_capacity,
 				}),
 				eth_config.frontier_sql_backend_pool_size,
 				std::num::NonZeroU32::new(eth_config.frontier_sql_backend_num_ops_timeout),
+				eth_config.frontier_sql_backend_max_log_results,
 				storage_override.clone(),
 			))
-			.unwrap_or_else(|err| panic!("failed creating sql backend: {:?}", err));
+			.unwrap_or_else(|err| panic!("failed creating sql backend: {:?}", err))
+			.with_log_insert_chunk_size(eth_config.frontier_sql_backend_log_insert_chunk_size);
+			let backend = match eth_config.frontier_sql_backend_priority_indexing_timeout {
+				0 => backend,
+				timeout => backend
+					.with_priority_indexing_timeout(std::time::Duration::from_millis(timeout)),
+			};
+			if let Some(path) = &eth_config.frontier_sql_backend_event_signatures {
+				let fragments = std::fs::read_to_string(path)
+					.unwrap_or_else(|err| panic!("failed reading event signatures: {:?}", err))
+					.lines()
+					.map(str::trim)
+					.filter(|line| !line.is_empty() && !line.starts_with('#'))
+					.map(ToString::to_string)
+					.collect::<Vec<_>>();
+				futures::executor::block_on(backend.register_event_signatures(&fragments))
+					.unwrap_or_else(|err| panic!("failed registering event signatures: {:?}", err));
+			}
 			FrontierBackend::Sql(Arc::new(backend))
 		}
 	};
@@ -397,6 +425,8 @@ where
 		fc_mapping_sync::EthereumBlockNotification<B>,
 	> = Default::default();
 	let pubsub_notification_sinks = Arc::new(pubsub_notification_sinks);
+	// Sinks for the mapping sync progress, see `fc_mapping_sync::subscribe_sync_progress`.
+	let sync_progress_sinks: Arc<fc_mapping_sync::SyncProgressSinks> = Default::default();
 
 	// for ethereum-compatibility rpc.
 	config.rpc_id_provider = Some(Box::new(fc_rpc::EthereumSubIdProvider));
@@ -411,6 +441,15 @@ where
 		let enable_dev_signer = eth_config.enable_dev_signer;
 		let max_past_logs = eth_config.max_past_logs;
 		let execute_gas_limit_multiplier = eth_config.execute_gas_limit_multiplier;
+		let fixed_priority_fee_per_gas = eth_config.fixed_priority_fee_per_gas.map(U256::from);
+		let execution_pool = eth_config.eth_execution_pool_size.map(|max_concurrent| {
+			fc_rpc::ExecutionPool::new(fc_rpc::ExecutionPoolConfig {
+				max_concurrent,
+				max_queued: eth_config.eth_execution_queue_size,
+				timeout: Duration::from_secs(eth_config.eth_execution_timeout),
+			})
+		});
+		let method_limits = fc_rpc::MethodLimits::new(eth_config.eth_method_limit.clone());
 		let filter_pool = filter_pool.clone();
 		let frontier_backend = frontier_backend.clone();
 		let pubsub_notification_sinks = pubsub_notification_sinks.clone();
@@ -461,6 +500,9 @@ where
 				execute_gas_limit_multiplier,
 				forced_parent_hashes: None,
 				pending_create_inherent_data_providers,
+				fixed_priority_fee_per_gas,
+				execution_pool: execution_pool.clone(),
+				method_limits: method_limits.clone(),
 			};
 			let deps = crate::rpc::FullDeps {
 				client: client.clone(),
@@ -497,6 +539,19 @@ where
 		telemetry: telemetry.as_mut(),
 	})?;
 
+	#[cfg(feature = "rosetta")]
+	crate::eth::spawn_rosetta(
+		&task_manager,
+		client.clone(),
+		transaction_pool.clone(),
+		match &*frontier_backend {
+			fc_db::Backend::KeyValue(b) => b.clone(),
+			fc_db::Backend::Sql(b) => b.clone(),
+		},
+		storage_override.clone(),
+		&eth_config,
+	);
+
 	spawn_frontier_tasks(
 		&task_manager,
 		client.clone(),
@@ -508,6 +563,9 @@ where
 		fee_history_cache_limit,
 		sync_service.clone(),
 		pubsub_notification_sinks,
+		sync_progress_sinks,
+		prometheus_registry.as_ref(),
+		&eth_config,
 	)
 	.await;
 
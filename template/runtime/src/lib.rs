@@ -241,6 +241,8 @@ impl frame_system::Config for Runtime {
 	/// This is used as an identifier of the chain. 42 is the generic substrate prefix.
 	type SS58Prefix = SS58Prefix;
 	type MaxConsumers = ConstU32<16>;
+	/// The multi-block migrations are run by pallet-migrations.
+	type MultiBlockMigrator = MultiBlockMigrations;
 }
 
 impl pallet_aura::Config for Runtime {
@@ -338,10 +340,12 @@ impl<F: FindAuthor<u32>> FindAuthor<H160> for FindAuthorTruncated<F> {
 
 const BLOCK_GAS_LIMIT: u64 = 75_000_000;
 const MAX_POV_SIZE: u64 = 5 * 1024 * 1024;
+const MAX_STORAGE_GROWTH: u64 = 400 * 1024;
 
 parameter_types! {
 	pub BlockGasLimit: U256 = U256::from(BLOCK_GAS_LIMIT);
 	pub const GasLimitPovSizeRatio: u64 = BLOCK_GAS_LIMIT.saturating_div(MAX_POV_SIZE);
+	pub const GasLimitStorageGrowthRatio: u64 = BLOCK_GAS_LIMIT.saturating_div(MAX_STORAGE_GROWTH);
 	pub PrecompilesValue: FrontierPrecompiles<Runtime> = FrontierPrecompiles::<_>::new();
 	pub WeightPerGas: Weight = Weight::from_parts(weight_per_gas(BLOCK_GAS_LIMIT, NORMAL_DISPATCH_RATIO, WEIGHT_MILLISECS_PER_BLOCK), 0);
 	pub SuicideQuickClearLimit: u32 = 0;
@@ -364,15 +368,26 @@ impl pallet_evm::Config for Runtime {
 	type Runner = pallet_evm::runner::stack::Runner<Self>;
 	type OnChargeTransaction = ();
 	type OnCreate = ();
+	type ExecutionHooks = ();
+	type CreateOriginFilter = ();
+	type DeployerAllowListOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type CallFilter = ();
+	type CallBlockListOrigin = frame_system::EnsureRoot<Self::AccountId>;
 	type FindAuthor = FindAuthorTruncated<Aura>;
 	type GasLimitPovSizeRatio = GasLimitPovSizeRatio;
+	type GasLimitStorageGrowthRatio = GasLimitStorageGrowthRatio;
 	type SuicideQuickClearLimit = SuicideQuickClearLimit;
 	type Timestamp = Timestamp;
+	type ExecutionExtrinsicsEnabled = ConstBool<true>;
+	type ShanghaiBlock = ConstU32<0>;
+	type CodeSizeLimit = ();
+	type InitcodeSizeLimit = ();
 	type WeightInfo = pallet_evm::weights::SubstrateWeight<Self>;
 }
 
 parameter_types! {
 	pub const PostBlockAndTxnHashes: PostLogContent = PostLogContent::BlockAndTxnHashes;
+	pub MinGasPrice: U256 = U256::zero();
 }
 
 impl pallet_ethereum::Config for Runtime {
@@ -380,6 +395,12 @@ impl pallet_ethereum::Config for Runtime {
 	type StateRoot = pallet_ethereum::IntermediateStateRoot<Self>;
 	type PostLogContent = PostBlockAndTxnHashes;
 	type ExtraDataLength = ConstU32<30>;
+	type HistoryServeWindow = ConstU32<8191>;
+	type MaxFutureTransactionsPerSender = ConstU32<64>;
+	type MinGasPrice = MinGasPrice;
+	type MaxRevertReasonLength = ConstU32<1024>;
+	type DenyListOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type WeightInfo = pallet_ethereum::weights::SubstrateWeight<Self>;
 }
 
 parameter_types! {
@@ -388,6 +409,7 @@ parameter_types! {
 
 impl pallet_dynamic_fee::Config for Runtime {
 	type MinGasPriceBoundDivisor = BoundDivision;
+	type OracleOrigin = frame_system::EnsureRoot<Self::AccountId>;
 }
 
 parameter_types! {
@@ -409,8 +431,11 @@ impl pallet_base_fee::BaseFeeThreshold for BaseFeeThreshold {
 impl pallet_base_fee::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Threshold = BaseFeeThreshold;
+	type FeeAdjustment = pallet_base_fee::Eip1559FeeAdjustment;
+	type BaseFeeHistoryLength = ConstU32<1024>;
 	type DefaultBaseFeePerGas = DefaultBaseFeePerGas;
 	type DefaultElasticity = DefaultElasticity;
+	type FixedBaseFeePerGas = ();
 }
 
 #[frame_support::pallet]
@@ -442,6 +467,25 @@ pub mod pallet_manual_seal {
 
 impl pallet_manual_seal::Config for Runtime {}
 
+parameter_types! {
+	pub MbmServiceWeight: Weight = Perbill::from_percent(80) * BlockWeights::get().max_block;
+}
+
+impl pallet_migrations::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	#[cfg(not(feature = "runtime-benchmarks"))]
+	type Migrations = (pallet_evm::migrations::CodeDeduplication<Runtime>,);
+	// Benchmarks need mocked migrations to guarantee that they succeed.
+	#[cfg(feature = "runtime-benchmarks")]
+	type Migrations = pallet_migrations::mock_helpers::MockedMigrations;
+	type CursorMaxLen = ConstU32<65_536>;
+	type IdentifierMaxLen = ConstU32<256>;
+	type MigrationStatusHandler = ();
+	type FailedMigrationHandler = frame_support::migrations::FreezeChainOnFailedMigration;
+	type MaxServiceWeight = MbmServiceWeight;
+	type WeightInfo = pallet_migrations::weights::SubstrateWeight<Runtime>;
+}
+
 // Create the runtime by composing the FRAME pallets that were previously configured.
 #[frame_support::runtime]
 mod runtime {
@@ -494,6 +538,9 @@ mod runtime {
 
 	#[runtime::pallet_index(11)]
 	pub type ManualSeal = pallet_manual_seal;
+
+	#[runtime::pallet_index(12)]
+	pub type MultiBlockMigrations = pallet_migrations;
 }
 
 #[derive(Clone)]
@@ -586,6 +633,8 @@ mod benches {
 		[pallet_timestamp, Timestamp]
 		[pallet_sudo, Sudo]
 		[pallet_evm, EVM]
+		[pallet_ethereum, Ethereum]
+		[pallet_migrations, MultiBlockMigrations]
 	);
 }
 
@@ -770,7 +819,7 @@ impl_runtime_apis! {
 		}
 
 		fn account_code_at(address: H160) -> Vec<u8> {
-			pallet_evm::AccountCodes::<Runtime>::get(address)
+			pallet_evm::Pallet::<Runtime>::account_code(address)
 		}
 
 		fn author() -> H160 {
@@ -998,6 +1047,89 @@ impl_runtime_apis! {
 		fn initialize_pending_block(header: &<Block as BlockT>::Header) {
 			Executive::initialize_block(header);
 		}
+
+		fn block_fees() -> fp_evm::BlockFees {
+			pallet_evm::CurrentBlockFees::<Runtime>::get()
+		}
+
+		fn account_proof(address: H160, storage_keys: Vec<H256>) -> Option<fp_rpc::AccountProof> {
+			let (account, _) = pallet_evm::Pallet::<Runtime>::account_basic(&address);
+			let storage_root =
+				fp_rpc::storage_root(pallet_evm::AccountStorages::<Runtime>::iter_prefix(address))?;
+			let code_hash = pallet_evm::Pallet::<Runtime>::account_code_metadata(address).hash;
+			let proof_keys = pallet_evm::Pallet::<Runtime>::account_proof_keys(address);
+			let storage = storage_keys
+				.into_iter()
+				.map(|key| fp_rpc::StorageProof {
+					key,
+					value: pallet_evm::AccountStorages::<Runtime>::get(address, key),
+					proof_key: pallet_evm::Pallet::<Runtime>::storage_proof_key(address, key),
+				})
+				.collect();
+
+			Some(fp_rpc::AccountProof::new(account, storage_root, code_hash, proof_keys, storage))
+		}
+
+		fn block_gas_limit() -> U256 {
+			pallet_evm::Pallet::<Runtime>::block_gas_limit()
+		}
+
+		fn base_fee_history() -> Vec<U256> {
+			pallet_base_fee::Pallet::<Runtime>::base_fee_history()
+		}
+
+		fn next_base_fee_per_gas() -> U256 {
+			pallet_base_fee::Pallet::<Runtime>::next_base_fee_per_gas()
+		}
+
+		fn current_balance_changes() -> Vec<fp_rpc::TransactionBalanceChanges> {
+			use fp_rpc::BalanceChange;
+
+			// The balance events of a transaction precede its `Executed` event, in the phase of
+			// its extrinsic.
+			let mut pending = alloc::collections::BTreeMap::<u32, Vec<BalanceChange>>::new();
+			let mut transactions = Vec::new();
+			for record in System::read_events_no_consensus() {
+				let frame_system::EventRecord { phase, event, .. } = *record;
+				let frame_system::Phase::ApplyExtrinsic(index) = phase else {
+					continue;
+				};
+				let change = match event {
+					RuntimeEvent::Balances(pallet_balances::Event::Withdraw { who, amount }) => {
+						BalanceChange::FeeWithdrawn {
+							address: who.into(),
+							amount: amount.into(),
+						}
+					}
+					RuntimeEvent::Balances(pallet_balances::Event::Deposit { who, amount }) => {
+						BalanceChange::FeeDeposited {
+							address: who.into(),
+							amount: amount.into(),
+						}
+					}
+					RuntimeEvent::Balances(pallet_balances::Event::Transfer { from, to, amount }) => {
+						BalanceChange::Transfer {
+							from: from.into(),
+							to: to.into(),
+							amount: amount.into(),
+						}
+					}
+					RuntimeEvent::Ethereum(pallet_ethereum::Event::Executed {
+						transaction_hash,
+						..
+					}) => {
+						transactions.push(fp_rpc::TransactionBalanceChanges {
+							transaction_hash,
+							changes: pending.remove(&index).unwrap_or_default(),
+						});
+						continue;
+					}
+					_ => continue,
+				};
+				pending.entry(index).or_default().push(change);
+			}
+			transactions
+		}
 	}
 
 	impl fp_rpc::ConvertTransactionRuntimeApi<Block> for Runtime {
@@ -4,6 +4,7 @@ use pallet_evm::{
 };
 use sp_core::H160;
 
+use pallet_evm_precompile_exec_environment::ExecutionEnvironmentPrecompile;
 use pallet_evm_precompile_modexp::Modexp;
 use pallet_evm_precompile_sha3fips::Sha3FIPS256;
 use pallet_evm_precompile_simple::{ECRecover, ECRecoverPublicKey, Identity, Ripemd160, Sha256};
@@ -17,7 +18,7 @@ where
 	pub fn new() -> Self {
 		Self(Default::default())
 	}
-	pub fn used_addresses() -> [H160; 7] {
+	pub fn used_addresses() -> [H160; 8] {
 		[
 			hash(1),
 			hash(2),
@@ -26,6 +27,7 @@ where
 			hash(5),
 			hash(1024),
 			hash(1025),
+			hash(1026),
 		]
 	}
 }
@@ -44,6 +46,7 @@ where
 			// Non-Frontier specific nor Ethereum precompiles :
 			a if a == hash(1024) => Some(Sha3FIPS256::execute(handle)),
 			a if a == hash(1025) => Some(ECRecoverPublicKey::execute(handle)),
+			a if a == hash(1026) => Some(ExecutionEnvironmentPrecompile::<R, ()>::execute(handle)),
 			_ => None,
 		}
 	}
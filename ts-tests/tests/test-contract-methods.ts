@@ -107,7 +107,7 @@ describeWithFrontier("Frontier RPC (Contract Methods)", (context) => {
 			.multiply()
 			.call()
 			.catch((err) =>
-				expect(err.message).to.equal(`Returned error: VM Exception while processing transaction: revert.`)
+				expect(err.message).to.equal(`Returned error: execution reverted`)
 			);
 	});
 
@@ -133,7 +133,7 @@ describeWithFrontier("Frontier RPC (Contract Methods)", (context) => {
 			.multiply(3, 4)
 			.call()
 			.catch((err) =>
-				expect(err.message).to.equal(`Returned error: VM Exception while processing transaction: revert.`)
+				expect(err.message).to.equal(`Returned error: execution reverted`)
 			);
 	});
 
@@ -153,7 +153,7 @@ describeWithFrontier("Frontier RPC (Contract Methods)", (context) => {
 			.multiply("0x0123456789012345678901234567890123456789")
 			.call()
 			.catch((err) =>
-				expect(err.message).to.equal(`Returned error: VM Exception while processing transaction: revert.`)
+				expect(err.message).to.equal(`Returned error: execution reverted`)
 			);
 	});
 });